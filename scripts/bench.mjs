@@ -0,0 +1,283 @@
+import { execFileSync } from 'node:child_process';
+import { existsSync } from 'node:fs';
+import { mkdir, readFile, writeFile } from 'node:fs/promises';
+import path from 'node:path';
+import { fileURLToPath } from 'node:url';
+import { parseArgs } from 'node:util';
+import { Client } from '@modelcontextprotocol/sdk/client/index.js';
+import { StdioClientTransport } from '@modelcontextprotocol/sdk/client/stdio.js';
+
+const scriptDir = path.dirname(fileURLToPath(import.meta.url));
+const repoRoot = path.resolve(scriptDir, '..');
+const serverEntry = path.join(repoRoot, 'dist', 'index.js');
+
+const BASELINE_VERSION = 1;
+const READY_TIMEOUT_MS = 120_000;
+const READY_POLL_MS = 250;
+
+// Differences below these are noise, however large in relative terms
+const MIN_LATENCY_DELTA_MS = 2;
+const MIN_MEMORY_DELTA_MB = 10;
+
+// Integration test projects and the calls timed against them
+const PROJECTS = {
+  mock: {
+    main: 'main.mock',
+    position: { line: 1, character: 7 },
+    query: 'Greeter',
+  },
+  typescript: {
+    main: 'src/main.ts',
+    position: { line: 8, character: 17 },
+    query: 'TestService',
+  },
+  python: {
+    main: 'main.py',
+    position: { line: 1, character: 1 },
+    query: 'calculate',
+  },
+  go: {
+    main: 'main.go',
+    position: { line: 12, character: 10 },
+    query: 'calculate',
+  },
+  rust: {
+    main: 'src/main.rs',
+    position: { line: 6, character: 15 },
+    query: 'calculate',
+  },
+  csharp: {
+    main: 'Program.cs',
+    position: { line: 14, character: 21 },
+    query: 'TestService',
+  },
+};
+
+function getCases(project, workspace) {
+  const file = path.join(workspace, project.main);
+  return {
+    outline: { file },
+    search: { query: project.query },
+    inspect: { file, ...project.position },
+    references: { file, ...project.position },
+  };
+}
+
+const { values: options } = parseArgs({
+  options: {
+    projects: { type: 'string', default: 'mock' },
+    iterations: { type: 'string', default: '20' },
+    output: { type: 'string' },
+    compare: { type: 'string' },
+    threshold: { type: 'string', default: '0.2' },
+  },
+});
+
+const projectNames = options.projects.split(',').map((name) => name.trim());
+const iterations = Number(options.iterations);
+const threshold = Number(options.threshold);
+
+for (const name of projectNames) {
+  if (!(name in PROJECTS)) {
+    throw new Error(
+      `Unknown project ${name}; expected one of ${Object.keys(PROJECTS).join(', ')}`
+    );
+  }
+}
+if (!Number.isInteger(iterations) || iterations < 1) {
+  throw new Error(`Invalid --iterations ${options.iterations}`);
+}
+if (!(threshold >= 0)) {
+  throw new Error(`Invalid --threshold ${options.threshold}`);
+}
+if (!existsSync(serverEntry)) {
+  throw new Error(`${serverEntry} not found; run pnpm build first`);
+}
+
+function percentile(sorted, fraction) {
+  const index = Math.min(
+    sorted.length - 1,
+    Math.ceil(sorted.length * fraction) - 1
+  );
+  return sorted[Math.max(index, 0)];
+}
+
+function round(value) {
+  return Math.round(value * 100) / 100;
+}
+
+// Resident set size of the server process, in MB
+function readRssMb(pid) {
+  const output = execFileSync('ps', ['-o', 'rss=', '-p', String(pid)], {
+    encoding: 'utf8',
+  });
+  return round(Number(output.trim()) / 1024);
+}
+
+async function callTool(client, name, args) {
+  const result = await client.callTool({ name, arguments: args });
+  const text = (result.content ?? [])
+    .map((item) => (item.type === 'text' ? item.text : ''))
+    .join('\n');
+  return { isError: result.isError === true, text };
+}
+
+async function waitForWorkspace(client, args) {
+  const deadline = Date.now() + READY_TIMEOUT_MS;
+  for (;;) {
+    const result = await callTool(client, 'outline', args);
+    if (!result.text.startsWith('Workspace is still loading')) {
+      return;
+    }
+    if (Date.now() > deadline) {
+      throw new Error('Timed out waiting for the workspace to load');
+    }
+    await new Promise((resolve) => setTimeout(resolve, READY_POLL_MS));
+  }
+}
+
+async function benchmarkProject(name) {
+  const directory = path.join(repoRoot, 'test/integration/languages', name);
+  const workspace = path.join(directory, 'test-project');
+  const cases = getCases(PROJECTS[name], workspace);
+
+  const transport = new StdioClientTransport({
+    command: process.execPath,
+    args: [
+      serverEntry,
+      'start',
+      '--workspace',
+      workspace,
+      '--config',
+      path.join(directory, 'language-servers.yaml'),
+      '--loglevel',
+      'error',
+    ],
+    cwd: repoRoot,
+    stderr: 'ignore',
+  });
+  const client = new Client({ name: 'symbols-bench', version: '1.0.0' });
+
+  const startedAt = performance.now();
+  await client.connect(transport);
+  await waitForWorkspace(client, cases.outline);
+  const startupMs = performance.now() - startedAt;
+
+  const latencies = {};
+  try {
+    for (const [tool, args] of Object.entries(cases)) {
+      // Warm up caches and lazily opened documents before timing
+      const warmup = await callTool(client, tool, args);
+      if (warmup.isError) {
+        console.warn(`Skipping ${name}/${tool}: ${warmup.text.split('\n')[0]}`);
+        continue;
+      }
+
+      const samples = [];
+      for (let index = 0; index < iterations; index++) {
+        const callStartedAt = performance.now();
+        await callTool(client, tool, args);
+        samples.push(performance.now() - callStartedAt);
+      }
+      samples.sort((left, right) => left - right);
+      latencies[`${name}/${tool}`] = {
+        p50_ms: round(percentile(samples, 0.5)),
+        p95_ms: round(percentile(samples, 0.95)),
+      };
+    }
+
+    return {
+      latencies,
+      memory: { rss_mb: readRssMb(transport.pid) },
+      startup_ms: round(startupMs),
+    };
+  } finally {
+    await client.close();
+  }
+}
+
+function findRegressions(baseline, current) {
+  const regressions = [];
+  const check = (metric, before, after, minDelta) => {
+    if (
+      typeof before === 'number' &&
+      typeof after === 'number' &&
+      after - before > minDelta &&
+      after > before * (1 + threshold)
+    ) {
+      regressions.push({ metric, before, after });
+    }
+  };
+
+  for (const [name, before] of Object.entries(baseline.latencies)) {
+    const after = current.latencies[name];
+    if (!after) {
+      continue;
+    }
+    check(`${name} p50`, before.p50_ms, after.p50_ms, MIN_LATENCY_DELTA_MS);
+    check(`${name} p95`, before.p95_ms, after.p95_ms, MIN_LATENCY_DELTA_MS);
+  }
+  for (const [name, before] of Object.entries(baseline.memory)) {
+    check(
+      `${name} rss`,
+      before.rss_mb,
+      current.memory[name]?.rss_mb,
+      MIN_MEMORY_DELTA_MB
+    );
+  }
+  return regressions;
+}
+
+const report = {
+  version: BASELINE_VERSION,
+  created_at: new Date().toISOString(),
+  node: process.version,
+  platform: `${process.platform}-${process.arch}`,
+  iterations,
+  latencies: {},
+  memory: {},
+  startup: {},
+};
+
+for (const name of projectNames) {
+  console.log(`Benchmarking ${name}...`);
+  const result = await benchmarkProject(name);
+  Object.assign(report.latencies, result.latencies);
+  report.memory[name] = result.memory;
+  report.startup[name] = { ms: result.startup_ms };
+}
+
+console.table(report.latencies);
+console.table(report.memory);
+
+if (options.output) {
+  const outputPath = path.resolve(options.output);
+  await mkdir(path.dirname(outputPath), { recursive: true });
+  await writeFile(outputPath, `${JSON.stringify(report, null, 2)}\n`, 'utf8');
+  console.log(`Wrote baseline to ${path.relative(process.cwd(), outputPath)}`);
+}
+
+if (options.compare) {
+  const baseline = JSON.parse(await readFile(options.compare, 'utf8'));
+  if (baseline.version !== BASELINE_VERSION) {
+    throw new Error(`Unsupported baseline version ${baseline.version}`);
+  }
+  if (baseline.platform !== report.platform) {
+    console.warn(
+      `Baseline was recorded on ${baseline.platform}, comparing on ${report.platform}`
+    );
+  }
+
+  const regressions = findRegressions(baseline, report);
+  if (regressions.length > 0) {
+    console.error(
+      `Regressions beyond ${Math.round(threshold * 100)}% of ${options.compare}:`
+    );
+    for (const { metric, before, after } of regressions) {
+      console.error(`  ${metric}: ${before} -> ${after}`);
+    }
+    process.exitCode = 1;
+  } else {
+    console.log(`No regressions beyond ${Math.round(threshold * 100)}%`);
+  }
+}
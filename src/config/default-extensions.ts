@@ -22,6 +22,8 @@ export const DEFAULT_EXTENSIONS: Record<string, string> = {
   '.mts': 'typescript',
   '.cts': 'typescript',
   '.tsx': 'typescriptreact',
+  '.vue': 'vue',
+  '.svelte': 'svelte',
   '.json': 'json',
   '.jsonc': 'json',
 
@@ -74,7 +76,18 @@ export const DEFAULT_EXTENSIONS: Record<string, string> = {
   '.kts': 'kotlin',
   '.scala': 'scala',
   '.sc': 'scala',
+  '.sbt': 'scala',
+  '.mill': 'scala',
+  '.dart': 'dart',
+  '.jl': 'julia',
   '.lua': 'lua',
+  '.ex': 'elixir',
+  '.exs': 'elixir',
+  '.heex': 'phoenix-heex',
+  '.erl': 'erlang',
+  '.hrl': 'erlang',
+  '.escript': 'erlang',
+  '.gleam': 'gleam',
   '.sh': 'shellscript',
   '.bash': 'shellscript',
   '.zsh': 'shellscript',
@@ -84,6 +97,8 @@ export const DEFAULT_EXTENSIONS: Record<string, string> = {
   '.rmd': 'r',
   '.Rmd': 'r',
   '.sql': 'sql',
+  '.tf': 'terraform',
+  '.tfvars': 'terraform-vars',
   '.yaml': 'yaml',
   '.yml': 'yaml',
   '.xml': 'xml',
@@ -137,6 +152,11 @@ export const DEFAULT_PROFILE_EXTENSIONS: Record<
   java: {
     '.java': 'java',
   },
+  bash: {
+    '.sh': 'shellscript',
+    '.bash': 'shellscript',
+    '.zsh': 'shellscript',
+  },
   clangd: {
     '.c': 'c',
     '.h': 'c',
@@ -149,10 +169,35 @@ export const DEFAULT_PROFILE_EXTENSIONS: Record<
     '.hh': 'cpp',
     '.hxx': 'cpp',
   },
+  dart: {
+    '.dart': 'dart',
+  },
+  elixir: {
+    '.ex': 'elixir',
+    '.exs': 'elixir',
+    '.heex': 'phoenix-heex',
+  },
+  erlang: {
+    '.erl': 'erlang',
+    '.hrl': 'erlang',
+    '.escript': 'erlang',
+  },
+  gleam: {
+    '.gleam': 'gleam',
+  },
+  julia: {
+    '.jl': 'julia',
+  },
   kotlin: {
     '.kt': 'kotlin',
     '.kts': 'kotlin',
   },
+  metals: {
+    '.scala': 'scala',
+    '.sc': 'scala',
+    '.sbt': 'scala',
+    '.mill': 'scala',
+  },
   lua: {
     '.lua': 'lua',
   },
@@ -164,6 +209,12 @@ export const DEFAULT_PROFILE_EXTENSIONS: Record<
     '.php5': 'php',
     '.phps': 'php',
   },
+  r: {
+    '.r': 'r',
+    '.R': 'r',
+    '.rmd': 'r',
+    '.Rmd': 'r',
+  },
   ruby: {
     '.rb': 'ruby',
     '.rbw': 'ruby',
@@ -172,7 +223,28 @@ export const DEFAULT_PROFILE_EXTENSIONS: Record<
     '.ru': 'ruby',
     '.erb': 'erb',
   },
+  solargraph: {
+    '.rb': 'ruby',
+    '.rbw': 'ruby',
+    '.rake': 'ruby',
+    '.gemspec': 'ruby',
+    '.ru': 'ruby',
+  },
+  svelte: {
+    '.svelte': 'svelte',
+  },
   swift: {
     '.swift': 'swift',
   },
+  terraform: {
+    '.tf': 'terraform',
+    '.tfvars': 'terraform-vars',
+  },
+  vue: {
+    '.vue': 'vue',
+  },
+  yaml: {
+    '.yaml': 'yaml',
+    '.yml': 'yaml',
+  },
 };
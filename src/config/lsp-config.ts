@@ -36,12 +36,33 @@ const SearchConfigSchema = z.object({
     .default(DEFAULT_SEARCH_WARMUP_WINDOW_MS),
 });
 
+// Case-insensitive patterns matched against what the server reports
+const PatternSchema = z.string().refine(
+  (pattern) => {
+    try {
+      new RegExp(pattern, 'i');
+      return true;
+    } catch {
+      return false;
+    }
+  },
+  { message: 'Must be a valid regular expression' }
+);
+
+const ReadinessConfigSchema = z.object({
+  progress_title: PatternSchema.optional(), // ready once $/progress work with a matching title ends
+  log_message: PatternSchema.optional(), // ready once a matching window/logMessage arrives
+  timeout_ms: z.number().min(0).max(600000).default(120000), // treated as ready after this long
+});
+
 const LspConfigSchema = z.object({
   command: z.string(),
   extensions: z.record(z.string(), z.string()).default({}), // file extension -> language ID handled by this profile
   workspace_files: z.array(z.string()).default([]),
   preload_files: z.array(z.string()).default([]), // files or glob patterns to open during initialization
+  preload: z.boolean().optional(), // start with the MCP server instead of on the first query for its files
   workspace_ready_delay_ms: z.number().min(0).max(30000).default(0), // wait before marking workspace ready
+  readiness: ReadinessConfigSchema.optional(), // what the default workspace loader waits for before requests
   diagnostics: DiagnosticsConfigSchema.default({
     strategy: 'push',
     wait_timeout_ms: 2000,
@@ -50,10 +71,103 @@ const LspConfigSchema = z.object({
   search: SearchConfigSchema.optional(),
   environment: z.record(z.string(), z.string()).optional(),
   workspace_loader: z.string().optional(), // workspace loader type ('default', 'roslyn', etc.)
+  initialization_options: z.record(z.string(), z.unknown()).optional(), // sent as initializationOptions in the initialize request
+  settings: z.record(z.string(), z.unknown()).optional(), // sent in workspace/didChangeConfiguration and answered to workspace/configuration
+  tsserver_profile: z.string().optional(), // profile whose TypeScript plugin answers this server's tsserver/request notifications (Vue)
+});
+
+const OwnersConfigSchema = z.object({
+  file: z.string().optional(), // CODEOWNERS path, discovered when omitted
+  teams: z.record(z.string(), z.string()).default({}), // owner -> team
+});
+
+const ToolHookConfigSchema = z.object({
+  command: z.string().min(1), // shell command; reads JSON on stdin, prints the result
+  timeout_ms: z.number().min(100).max(60000).default(5000),
+});
+
+const WasmPluginConfigSchema = z.object({
+  path: z.string().min(1), // .wasm module, relative to the workspace
+  extensions: z.array(z.string()).default([]), // files whose symbols the plugin extracts
+  tools: z.array(z.string()).default(['*']), // tools whose results the plugin enriches
+  timeout_ms: z.number().int().min(100).max(60000).default(5000), // per call, after which the plugin's worker is stopped
+});
+
+const RateLimitConfigSchema = z.object({
+  requests_per_minute: z.number().int().min(1).nullable().default(null), // per client
+  burst: z.number().int().min(1).default(20),
+  max_concurrent: z.number().int().min(1).nullable().default(null), // across clients
+  max_queued_per_client: z.number().int().min(0).default(50),
+});
+
+const SandboxConfigSchema = z.object({
+  enabled: z.boolean().default(true),
+  network: z.boolean().default(false),
+  readable_paths: z.array(z.string()).default([]), // '~/' expands to the home directory
+  writable_paths: z.array(z.string()).default([]),
+});
+
+const AuditLogConfigSchema = z.object({
+  path: z.string().min(1).optional(), // relative to the workspace; defaults to the log directory
+  max_bytes: z.number().int().min(1024).default(10 * 1024 * 1024),
+  max_files: z.number().int().min(0).default(5),
+});
+
+const SessionStateConfigSchema = z.object({
+  path: z.string().min(1).optional(), // relative to the workspace; defaults to the data directory
+  max_files: z.number().int().min(1).default(10), // recent files reopened per client
+  max_age_days: z.number().min(0).default(7),
+  max_clients: z.number().int().min(1).default(20), // least recently seen clients are forgotten first
+});
+
+const SymbolIndexConfigSchema = z.object({
+  path: z.string().min(1).optional(), // relative to the workspace; defaults to the data directory
+  max_files: z.number().int().min(1).default(20000), // source files indexed, in path order
+});
+
+const ScanConfigSchema = z.object({
+  parallelism: z.number().int().min(1).max(64).default(8), // per-file LSP requests in flight at once during workspace-wide scans
+});
+
+const AuthScopeSchema = z.array(z.enum(['read', 'edit'])).min(1);
+
+const HttpAuthConfigSchema = z.object({
+  tokens: z
+    .array(
+      z.object({
+        name: z.string().min(1), // client identity in rate limits and the audit log
+        token: z.string().min(1), // e.g. '${SYMBOLS_AGENT_TOKEN}'
+        scopes: AuthScopeSchema,
+      })
+    )
+    .default([]),
+  mtls: z
+    .object({
+      cert: z.string().min(1), // PEM files, relative to the workspace
+      key: z.string().min(1),
+      ca: z.string().min(1), // CA that signs client certificates
+      clients: z.array(
+        z.object({
+          subject: z.string().min(1), // certificate common name
+          scopes: AuthScopeSchema,
+        })
+      ),
+    })
+    .optional(),
 });
 
 const ConfigFileSchema = z.object({
   'language-servers': z.record(z.string(), LspConfigSchema),
+  owners: OwnersConfigSchema.optional(),
+  hooks: z.record(z.string(), z.array(ToolHookConfigSchema)).optional(), // tool name or '*' -> hooks
+  plugins: z.array(WasmPluginConfigSchema).optional(),
+  rate_limits: RateLimitConfigSchema.optional(),
+  sandbox: SandboxConfigSchema.optional(),
+  audit: AuditLogConfigSchema.optional(),
+  auth: HttpAuthConfigSchema.optional(),
+  resume: SessionStateConfigSchema.optional(),
+  index: SymbolIndexConfigSchema.optional(), // saved workspace symbols answering searches during startup
+  scan: ScanConfigSchema.optional(),
 });
 
 // TypeScript interfaces derived from schemas
@@ -239,6 +353,31 @@ export function loadLspConfig(
   };
 }
 
+/**
+ * Initialization options or settings from an environment variable holding a
+ * JSON object. The value isn't echoed in errors since it may hold a licence
+ * key.
+ */
+function getJsonObjectOverride(
+  name: 'SYMBOLS_INITIALIZATION_OPTIONS' | 'SYMBOLS_SETTINGS'
+): Record<string, unknown> | undefined {
+  const value = process.env[name];
+  if (!value) {
+    return undefined;
+  }
+
+  let parsed: unknown;
+  try {
+    parsed = JSON.parse(value);
+  } catch {
+    parsed = undefined;
+  }
+  if (!parsed || typeof parsed !== 'object' || Array.isArray(parsed)) {
+    throw new Error(`Invalid ${name}. Must be a JSON object.`);
+  }
+  return parsed as Record<string, unknown>;
+}
+
 /**
  * Expand environment variables in configuration values
  * Supports $VAR and ${VAR} syntax
@@ -337,6 +476,20 @@ export function getLspConfig(
     lspConfig.workspace_loader = process.env.SYMBOLS_WORKSPACE_LOADER;
   }
 
+  // Override initialization_options if SYMBOLS_INITIALIZATION_OPTIONS is set
+  const initializationOptions = getJsonObjectOverride(
+    'SYMBOLS_INITIALIZATION_OPTIONS'
+  );
+  if (initializationOptions) {
+    lspConfig.initialization_options = initializationOptions;
+  }
+
+  // Override settings if SYMBOLS_SETTINGS is set
+  const settings = getJsonObjectOverride('SYMBOLS_SETTINGS');
+  if (settings) {
+    lspConfig.settings = settings;
+  }
+
   // Override diagnostics.strategy if SYMBOLS_DIAGNOSTICS_STRATEGY is set
   if (process.env.SYMBOLS_DIAGNOSTICS_STRATEGY) {
     const strategy = process.env.SYMBOLS_DIAGNOSTICS_STRATEGY;
@@ -577,6 +730,9 @@ export function createConfigFromDirectCommand(
     },
     symbols: {},
     workspace_loader: undefined,
+    readiness: undefined,
+    initialization_options: undefined,
+    settings: undefined,
     environment: undefined,
   };
 
@@ -585,6 +741,18 @@ export function createConfigFromDirectCommand(
     config.workspace_loader = process.env.SYMBOLS_WORKSPACE_LOADER;
   }
 
+  const initializationOptions = getJsonObjectOverride(
+    'SYMBOLS_INITIALIZATION_OPTIONS'
+  );
+  if (initializationOptions) {
+    config.initialization_options = initializationOptions;
+  }
+
+  const settings = getJsonObjectOverride('SYMBOLS_SETTINGS');
+  if (settings) {
+    config.settings = settings;
+  }
+
   if (process.env.SYMBOLS_DIAGNOSTICS_STRATEGY) {
     const strategy = process.env.SYMBOLS_DIAGNOSTICS_STRATEGY;
     if (strategy !== 'push' && strategy !== 'pull') {
@@ -0,0 +1,182 @@
+/**
+ * Core library API - embed symbol lookup in other Node programs instead of
+ * running the MCP server or shelling out to the CLI
+ *
+ * ```ts
+ * import { openWorkspace } from '@p1va/symbols/core';
+ *
+ * const workspace = await openWorkspace({ path: '/repos/app' });
+ * try {
+ *   const report = await workspace.query('kind:class refs:>10');
+ *   const outline = await workspace.outline('src/index.ts');
+ *   const refs = await workspace.references({
+ *     file: 'src/index.ts',
+ *     line: 12,
+ *     character: 17,
+ *   });
+ * } finally {
+ *   await workspace.close();
+ * }
+ * ```
+ *
+ * Everything exported here is the supported surface; other modules may
+ * change between releases.
+ */
+
+import * as path from 'path';
+import { createLspManager } from './runtime/lsp-manager.js';
+import {
+  bindToWorkspace,
+  createWorkspaceContext,
+} from './runtime/workspace-context.js';
+import type {
+  LspManagerProfileStatus,
+  LspManagerStatus,
+} from './runtime/lsp-manager.js';
+import {
+  findDefinitionsFrom,
+  findReferencesFrom,
+  findSymbolsByName,
+  getOutlineSymbols,
+  type FilePosition,
+  type LookupLocation,
+  type LookupReport,
+} from './map/symbol-lookup.js';
+import {
+  buildSymbolQueryReport,
+  type QueriedSymbol,
+  type SymbolQueryReport,
+} from './map/workspace-symbols.js';
+import type { FlattenedSymbol } from './types/lsp.js';
+import { toAbsoluteFilePath } from './utils/path-format.js';
+import { initLogger } from './utils/logger.js';
+
+export { parseSymbolQuery } from './utils/symbol-query.js';
+export type {
+  CountFilter,
+  SymbolQuery,
+  SymbolQueryFilters,
+} from './utils/symbol-query.js';
+export type {
+  FilePosition,
+  FlattenedSymbol,
+  LookupLocation,
+  LookupReport,
+  LspManagerProfileStatus,
+  LspManagerStatus,
+  QueriedSymbol,
+  SymbolQueryReport,
+};
+
+export interface WorkspaceOptions {
+  // Workspace root; defaults to the current directory
+  path?: string;
+  // Config file; defaults to the usual discovery from the workspace
+  configPath?: string;
+  // Profile used when a file matches no profile by extension
+  lsp?: string;
+  // Mirror logs to the console as well as the log file
+  console?: boolean;
+}
+
+/**
+ * Raw LSP access to the language server that owns a file
+ */
+export interface LanguageClient {
+  readonly profile: string;
+  request: <TResult, TParams = unknown>(
+    method: string,
+    params: TParams
+  ) => Promise<TResult>;
+}
+
+export interface Workspace {
+  readonly path: string;
+  // Configured profiles and their state
+  status: () => LspManagerStatus;
+  // Symbols matching a query with filters, e.g. `kind:function name:~^get`
+  query: (query: string, limit?: number) => Promise<SymbolQueryReport>;
+  // Symbols whose name fuzzily matches, best matches first
+  find: (name: string, limit?: number) => Promise<LookupReport>;
+  // References to the symbol at a 1-based position, declaration included
+  references: (position: FilePosition, limit?: number) => Promise<LookupReport>;
+  // Where the symbol at a 1-based position is defined
+  definition: (position: FilePosition, limit?: number) => Promise<LookupReport>;
+  // Flattened document symbols of a file, relative to the workspace or absolute
+  outline: (file: string) => Promise<FlattenedSymbol[]>;
+  // Language server for a file, started on first use
+  client: (file: string) => Promise<LanguageClient>;
+  // Stop every language server started for this workspace
+  close: () => Promise<void>;
+}
+
+const DEFAULT_QUERY_LIMIT = 100;
+
+let loggerReady = false;
+
+/**
+ * Load the workspace config and profiles. Language servers start lazily on
+ * the first call that needs them. Each workspace keeps its own
+ * configuration, so several can be open at once.
+ */
+export async function openWorkspace(
+  options: WorkspaceOptions = {}
+): Promise<Workspace> {
+  if (!loggerReady) {
+    initLogger(Boolean(options.console));
+    loggerReady = true;
+  }
+
+  const workspace = path.resolve(options.path ?? process.cwd());
+  const context = createWorkspaceContext();
+  const manager = createLspManager(context);
+  await manager.configureForStart({
+    command: 'start',
+    workspace,
+    ...(options.lsp && { lsp: options.lsp }),
+    ...(options.configPath && { configPath: options.configPath }),
+    console: Boolean(options.console),
+  });
+
+  const status = manager.getStatus();
+  if (status.profiles.length === 0) {
+    await manager.shutdown();
+    throw new Error(
+      status.issues[0] ?? 'No language server profiles are configured.'
+    );
+  }
+
+  return bindToWorkspace<Workspace>(context, {
+    path: workspace,
+    status: () => manager.getStatus(),
+    query: async (query, limit = DEFAULT_QUERY_LIMIT) =>
+      await buildSymbolQueryReport(manager, query, limit),
+    find: async (name, limit = DEFAULT_QUERY_LIMIT) =>
+      await findSymbolsByName(manager, name, limit),
+    references: async (position, limit = DEFAULT_QUERY_LIMIT) =>
+      await findReferencesFrom(
+        manager,
+        { ...position, file: toAbsoluteFilePath(position.file) },
+        limit
+      ),
+    definition: async (position, limit = DEFAULT_QUERY_LIMIT) =>
+      await findDefinitionsFrom(
+        manager,
+        { ...position, file: toAbsoluteFilePath(position.file) },
+        limit
+      ),
+    outline: async (file) =>
+      await getOutlineSymbols(manager, toAbsoluteFilePath(file)),
+    client: async (file) => {
+      const session = await manager.getSessionForFile(file);
+      return bindToWorkspace<LanguageClient>(context, {
+        profile: session.getProfile().name,
+        request: async <TResult, TParams = unknown>(
+          method: string,
+          params: TParams
+        ) => await session.request<TResult, TParams>(method, params),
+      });
+    },
+    close: async () => await manager.shutdown(),
+  });
+}
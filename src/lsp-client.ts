@@ -4,14 +4,20 @@
  */
 
 import * as cp from 'child_process';
+import { createHash } from 'crypto';
 import * as fs from 'fs';
 import * as path from 'path';
+import { fileURLToPath } from 'url';
 import * as rpc from 'vscode-jsonrpc';
 import which from 'which';
 import {
+  ApplyWorkspaceEditParams,
+  ConfigurationParams,
   InitializeParams,
   WorkspaceFolder,
   PublishDiagnosticsParams,
+  ShowMessageParams,
+  ShowMessageRequestParams,
   InitializeResult,
 } from 'vscode-languageserver-protocol';
 
@@ -25,6 +31,7 @@ import {
   DiagnosticProvider,
   WindowLogStore,
   LogMessage,
+  ProgressStore,
   createLspError,
   ErrorCode,
 } from './types.js';
@@ -32,7 +39,17 @@ import { ParsedLspConfig } from './config/lsp-config.js';
 import logger from './utils/logger.js';
 import { createWorkspaceLoader } from './workspace/registry.js';
 import { WorkspaceLoaderStore } from './types.js';
-import { expandEnvVars } from './utils/env-expansion.js';
+import {
+  expandEnvVars,
+  expandEnvVarsInValue,
+} from './utils/env-expansion.js';
+import {
+  getReplayCommand,
+  startSessionRecording,
+} from './lsp/session-recording.js';
+import { traceConnection } from './utils/tracing.js';
+import { getAppPaths } from './utils/app-paths.js';
+import { wrapSandboxCommand } from './lsp/sandbox.js';
 
 function summarizeProgressNotification(params: unknown): unknown {
   if (!params || typeof params !== 'object') {
@@ -63,6 +80,70 @@ function summarizeProgressNotification(params: unknown): unknown {
   };
 }
 
+/**
+ * Environment for variable substitution in the command, args,
+ * initialization options and settings: all env vars, YAML overrides and the
+ * SYMBOLS_ workspace variables. SYMBOLS_WORKSPACE_ID adds a hash of the path
+ * so two checkouts with the same folder name get separate server data
+ * directories.
+ */
+function createExpansionEnv(
+  workspaceConfig: LspConfig,
+  lspConfig: ParsedLspConfig
+): NodeJS.ProcessEnv {
+  const workspaceHash = createHash('sha256')
+    .update(fileURLToPath(workspaceConfig.workspaceUri))
+    .digest('hex')
+    .slice(0, 8);
+  return {
+    ...process.env,
+    ...(lspConfig.environment || {}),
+    SYMBOLS_WORKSPACE_NAME: workspaceConfig.workspaceName,
+    SYMBOLS_WORKSPACE_ID: `${workspaceConfig.workspaceName}-${workspaceHash}`,
+    SYMBOLS_DATA_DIR: getAppPaths().data,
+  };
+}
+
+/**
+ * The part of the profile settings a workspace/configuration item asks for,
+ * by dotted section name, or all of them without one
+ */
+function getSettingsSection(
+  settings: Record<string, unknown>,
+  section: string | undefined
+): unknown {
+  let value: unknown = settings;
+  for (const key of section ? section.split('.') : []) {
+    if (!value || typeof value !== 'object') {
+      return null;
+    }
+    value = (value as Record<string, unknown>)[key];
+  }
+  return value ?? null;
+}
+
+// Server processes leading their own process group
+const groupedProcesses = new WeakSet<cp.ChildProcess>();
+
+/**
+ * Send a signal to a server process, or to its whole process group when it
+ * was started in one
+ */
+export function killServerProcess(
+  serverProcess: cp.ChildProcess,
+  signal: NodeJS.Signals
+): void {
+  if (serverProcess.pid && groupedProcesses.has(serverProcess)) {
+    try {
+      process.kill(-serverProcess.pid, signal);
+      return;
+    } catch {
+      // The group is gone already; signal the process itself
+    }
+  }
+  serverProcess.kill(signal);
+}
+
 function resolveWorkspaceLoaderType(lspConfig: ParsedLspConfig): string {
   return lspConfig.workspace_loader || 'default';
 }
@@ -73,7 +154,8 @@ export function createLspClient(
   diagnosticsStore: DiagnosticsStore,
   diagnosticProviderStore: DiagnosticProviderStore,
   windowLogStore: WindowLogStore,
-  workspaceLoaderStore: WorkspaceLoaderStore
+  workspaceLoaderStore: WorkspaceLoaderStore,
+  progressStore: ProgressStore
 ): Result<LspClientResult> {
   try {
     logger.debug('Creating LSP client', {
@@ -85,13 +167,8 @@ export function createLspClient(
         : 'none',
     });
 
-    // Create expansion environment (temporary, for variable substitution in command/args)
-    // Includes all env vars + YAML overrides + SYMBOLS_WORKSPACE_NAME for substitution
-    const expansionEnv = {
-      ...process.env,
-      ...(lspConfig.environment || {}),
-      SYMBOLS_WORKSPACE_NAME: workspaceConfig.workspaceName,
-    };
+    const workspacePath = fileURLToPath(workspaceConfig.workspaceUri);
+    const expansionEnv = createExpansionEnv(workspaceConfig, lspConfig);
 
     logger.debug('Spawning LSP server process', {
       commandName: lspConfig.commandName,
@@ -99,14 +176,31 @@ export function createLspClient(
       hasCustomEnv: !!lspConfig.environment,
     });
 
-    // Expand command and args using the expansion environment
-    const processedCommandName = expandEnvVars(
-      lspConfig.commandName.trim(),
-      expansionEnv
-    );
-    const processedCommandArgs = lspConfig.commandArgs.map((arg) =>
-      expandEnvVars(arg.trim(), expansionEnv)
+    // Expand command and args using the expansion environment, then let the
+    // workspace loader adjust them, such as pointing clangd at its
+    // compilation database. Replayed sessions use the recorded command.
+    const replayCommand = getReplayCommand(lspConfig.name);
+    const expandedCommand = {
+      commandName: expandEnvVars(lspConfig.commandName.trim(), expansionEnv),
+      commandArgs: lspConfig.commandArgs.map((arg) =>
+        expandEnvVars(arg.trim(), expansionEnv)
+      ),
+    };
+    const workspaceLoader = createWorkspaceLoader(
+      resolveWorkspaceLoaderType(lspConfig)
     );
+    const preparedCommand = replayCommand
+      ? undefined
+      : workspaceLoader.prepareCommand?.(workspacePath, expandedCommand);
+    const processedCommandName =
+      replayCommand?.commandName ??
+      preparedCommand?.commandName ??
+      expandedCommand.commandName;
+    const processedCommandArgs = [
+      ...(replayCommand?.commandArgs ??
+        preparedCommand?.commandArgs ??
+        expandedCommand.commandArgs),
+    ];
 
     // Create clean LSP runtime environment (filters out SYMBOLS_* vars except those in YAML)
     // This ensures SYMBOLS_* vars used by the MCP server don't leak to LSP processes
@@ -114,9 +208,11 @@ export function createLspClient(
       Object.entries(process.env).filter(([key]) => !key.startsWith('SYMBOLS_'))
     );
 
-    const lspEnv = lspConfig.environment
-      ? { ...filteredProcessEnv, ...lspConfig.environment }
-      : filteredProcessEnv;
+    const lspEnv = {
+      ...filteredProcessEnv,
+      ...(lspConfig.environment || {}),
+      ...(preparedCommand?.environment || {}),
+    };
 
     logger.debug('Processed LSP command with environment variables', {
       originalCommand: `${lspConfig.commandName} ${lspConfig.commandArgs.join(' ')}`,
@@ -188,12 +284,33 @@ export function createLspClient(
       hasCustomEnv: !!lspConfig.environment,
     });
 
+    // Confine real servers when the sandbox is enabled; replays are this CLI
+    const spawnCommand = replayCommand
+      ? replayCommand
+      : wrapSandboxCommand(
+          {
+            commandName: processedCommandName,
+            commandArgs: processedCommandArgs,
+          },
+          lspEnv
+        );
+
     // Spawn the configured Language Server with clean environment
-    const serverProcess = cp.spawn(processedCommandName, processedCommandArgs, {
-      env: lspEnv,
-      // 1st stdin, 2nd stdout, 3rd stderr
-      stdio: ['pipe', 'pipe', 'pipe'],
-    });
+    const processGroup =
+      preparedCommand?.processGroup === true && process.platform !== 'win32';
+    const serverProcess = cp.spawn(
+      spawnCommand.commandName,
+      spawnCommand.commandArgs,
+      {
+        env: lspEnv,
+        // 1st stdin, 2nd stdout, 3rd stderr
+        stdio: ['pipe', 'pipe', 'pipe'],
+        detached: processGroup,
+      }
+    );
+    if (processGroup) {
+      groupedProcesses.add(serverProcess);
+    }
 
     logger.info(`LSP server process spawned with PID: ${serverProcess.pid}`);
 
@@ -245,14 +362,29 @@ export function createLspClient(
 
     logger.debug('Creating JSON-RPC connection over process streams');
 
+    // Tee every message into the session recording when one is configured
+    const recorder = startSessionRecording({
+      profile: lspConfig.name,
+      command: `${processedCommandName} ${processedCommandArgs.join(' ')}`,
+      workspaceUri: workspaceConfig.workspaceUri,
+    });
+
     // Create JSON-RPC connection using the overload that accepts streams directly
     // vscode-jsonrpc expects Readable/Writable but child_process streams are compatible
-    const connection = rpc.createMessageConnection(
-      // eslint-disable-next-line @typescript-eslint/no-explicit-any, @typescript-eslint/no-unsafe-argument
-      serverProcess.stdout as any, // Readable stream from child process
-      // eslint-disable-next-line @typescript-eslint/no-explicit-any, @typescript-eslint/no-unsafe-argument
-      serverProcess.stdin as any // Writable stream to child process
-    );
+    const rawConnection = recorder
+      ? rpc.createMessageConnection(
+          recorder.wrapReader(
+            new rpc.StreamMessageReader(serverProcess.stdout)
+          ),
+          recorder.wrapWriter(new rpc.StreamMessageWriter(serverProcess.stdin))
+        )
+      : rpc.createMessageConnection(
+          // eslint-disable-next-line @typescript-eslint/no-explicit-any, @typescript-eslint/no-unsafe-argument
+          serverProcess.stdout as any, // Readable stream from child process
+          // eslint-disable-next-line @typescript-eslint/no-explicit-any, @typescript-eslint/no-unsafe-argument
+          serverProcess.stdin as any // Writable stream to child process
+        );
+    const connection = traceConnection(rawConnection, lspConfig.name);
 
     logger.debug('JSON-RPC connection created successfully');
 
@@ -277,13 +409,47 @@ export function createLspClient(
       logger.debug('LSP request received', { method, params });
 
       if (method === 'workspace/configuration') {
-        return [];
+        if (!lspConfig.settings) {
+          return [];
+        }
+        const settings = expandEnvVarsInValue(
+          lspConfig.settings,
+          expansionEnv
+        ) as Record<string, unknown>;
+        return (params as ConfigurationParams).items.map((item) =>
+          getSettingsSection(settings, item.section)
+        );
       }
 
       if (method === 'window/workDoneProgress/create') {
         return null;
       }
 
+      if (method === 'window/showMessageRequest') {
+        // The workspace loader may answer prompts such as a build import;
+        // otherwise no action is chosen, but the message itself is kept
+        const request = params as ShowMessageRequestParams;
+        windowLogStore.addMessage(
+          { type: request.type, message: request.message },
+          true
+        );
+        return workspaceLoader.chooseMessageAction?.(request) ?? null;
+      }
+
+      if (method === 'workspace/applyEdit') {
+        // Edits are only accepted while a code action is being applied,
+        // which writes them to disk itself
+        const listener = client.workspaceEditListener;
+        if (!listener) {
+          return {
+            applied: false,
+            failureReason: 'Edits are only applied as part of a code action',
+          };
+        }
+        listener((params as ApplyWorkspaceEditParams).edit);
+        return { applied: true };
+      }
+
       // Return null instead of MethodNotFound error to prevent LSP crashes
       // Some LSPs (like pyright) crash when receiving MethodNotFound responses
       // for requests they send that we don't handle (e.g., textDocument/semanticTokens/full)
@@ -310,7 +476,8 @@ export function createLspClient(
       diagnosticsStore,
       diagnosticProviderStore,
       windowLogStore,
-      workspaceLoaderStore
+      workspaceLoaderStore,
+      progressStore
     );
 
     // Start listening
@@ -374,12 +541,22 @@ export async function initializeLspClient(
           uri: config.workspaceUri,
         } as WorkspaceFolder,
       ],
+      ...(lspConfig.initialization_options && {
+        initializationOptions: expandEnvVarsInValue(
+          lspConfig.initialization_options,
+          createExpansionEnv(config, lspConfig)
+        ),
+      }),
       capabilities: {
         window: {
           workDoneProgress: true,
         },
         workspace: {
           // diagnostics capability disabled for now
+          applyEdit: true,
+          workspaceEdit: {
+            documentChanges: true,
+          },
         },
         textDocument: {
           publishDiagnostics: {
@@ -395,12 +572,71 @@ export async function initializeLspClient(
           synchronization: {
             didSave: true,
           },
+          completion: {
+            dynamicRegistration: false,
+            completionItem: {
+              documentationFormat: ['markdown', 'plaintext'],
+              preselectSupport: true,
+              resolveSupport: {
+                properties: ['documentation', 'detail', 'additionalTextEdits'],
+              },
+            },
+          },
+          inlayHint: {
+            dynamicRegistration: false,
+          },
+          selectionRange: {
+            dynamicRegistration: false,
+          },
+          documentHighlight: {
+            dynamicRegistration: false,
+          },
+          foldingRange: {
+            dynamicRegistration: false,
+            lineFoldingOnly: true,
+            foldingRangeKind: {
+              valueSet: ['comment', 'imports', 'region'],
+            },
+          },
+          codeAction: {
+            dynamicRegistration: false,
+            codeActionLiteralSupport: {
+              codeActionKind: {
+                valueSet: [
+                  'quickfix',
+                  'refactor',
+                  'refactor.extract',
+                  'refactor.inline',
+                  'refactor.rewrite',
+                  'source',
+                  'source.organizeImports',
+                  'source.fixAll',
+                ],
+              },
+            },
+            isPreferredSupport: true,
+            disabledSupport: true,
+            dataSupport: true,
+            resolveSupport: {
+              properties: ['edit'],
+            },
+          },
+          rename: {
+            dynamicRegistration: false,
+            prepareSupport: true,
+          },
+          onTypeFormatting: {
+            dynamicRegistration: false,
+          },
+          codeLens: {
+            dynamicRegistration: false,
+          },
           semanticTokens: {
             dynamicRegistration: true,
             requests: {
               range: false,
               full: {
-                delta: false,
+                delta: true,
               },
             },
             tokenTypes: [
@@ -455,6 +691,19 @@ export async function initializeLspClient(
     // Send initialized notification
     await client.connection.sendNotification('initialized', {});
 
+    // Servers that don't ask for their settings take them pushed
+    if (lspConfig.settings) {
+      await client.connection.sendNotification(
+        'workspace/didChangeConfiguration',
+        {
+          settings: expandEnvVarsInValue(
+            lspConfig.settings,
+            createExpansionEnv(config, lspConfig)
+          ),
+        }
+      );
+    }
+
     client.isInitialized = true;
     client.serverCapabilities = initResult.capabilities;
 
@@ -590,7 +839,8 @@ function setupNotificationHandlers(
   diagnosticsStore: DiagnosticsStore,
   diagnosticProviderStore: DiagnosticProviderStore,
   windowLogStore: WindowLogStore,
-  workspaceLoaderStore?: WorkspaceLoaderStore
+  workspaceLoaderStore?: WorkspaceLoaderStore,
+  progressStore?: ProgressStore
 ): void {
   // Handle diagnostics publication (critical for getDiagnostics tool)
   connection.onNotification(
@@ -608,10 +858,14 @@ function setupNotificationHandlers(
     }
   });
 
-  // Handle show message notifications (silent)
-  connection.onNotification('window/showMessage', () => {
-    // Store or ignore as needed
-  });
+  // Messages meant for a popup often explain why results are missing, such
+  // as an absent toolchain, so they are kept alongside the log
+  connection.onNotification(
+    'window/showMessage',
+    (params: ShowMessageParams) => {
+      windowLogStore.addMessage(params, true);
+    }
+  );
 
   // Handle capability registration requests
   connection.onRequest('client/registerCapability', (params: unknown) => {
@@ -689,6 +943,13 @@ function setupNotificationHandlers(
     if (workspaceLoaderStore) {
       workspaceLoaderStore.updateState('$/progress', params);
     }
+    // Partial results also arrive as $/progress, without a kind
+    const value = (params as { value?: { kind?: unknown } } | null)?.value;
+    if (progressStore && typeof value?.kind === 'string') {
+      progressStore.handleProgress(
+        params as Parameters<ProgressStore['handleProgress']>[0]
+      );
+    }
   });
 
   // Handle C# Roslyn toast notifications (silent)
@@ -699,6 +960,40 @@ function setupNotificationHandlers(
     }
   });
 
+  // Handle JDT LS status notifications, which report the project import
+  connection.onNotification('language/status', (params: unknown) => {
+    logger.debug('Received language status notification', { params });
+    if (workspaceLoaderStore) {
+      workspaceLoaderStore.updateState('language/status', params);
+    }
+  });
+
+  // Handle Metals status bar notifications, which report the build import
+  connection.onNotification('metals/status', (params: unknown) => {
+    logger.debug('Received Metals status notification', { params });
+    if (workspaceLoaderStore) {
+      workspaceLoaderStore.updateState('metals/status', params);
+    }
+  });
+
+  // Handle Dart analysis server status, sent while analysis runs
+  connection.onNotification('$/analyzerStatus', (params: unknown) => {
+    logger.debug('Received analyzer status notification', { params });
+    if (workspaceLoaderStore) {
+      workspaceLoaderStore.updateState('$/analyzerStatus', params);
+    }
+  });
+
+  // Handle Intelephense indexing notifications
+  for (const method of ['indexingStarted', 'indexingEnded']) {
+    connection.onNotification(method, () => {
+      logger.debug('Received indexing notification', { method });
+      if (workspaceLoaderStore) {
+        workspaceLoaderStore.updateState(method);
+      }
+    });
+  }
+
   // Handle other notifications silently
   connection.onNotification(() => {
     // Silent handling of other notifications
@@ -15,6 +15,7 @@ import {
   ErrorCode,
   tryResultAsync,
 } from '../../types.js';
+import { hashContent } from '../../utils/content-hash.js';
 
 /**
  * Force close a file without checking strategy
@@ -77,16 +78,21 @@ export async function openFile(
         didOpenParams
       );
 
-      // Update preloaded files state
+      // Update preloaded files state, remembering the content the server saw
+      const contentHash = hashContent(content);
       const document = sessionDocuments.get(uri);
       if (document) {
         document.isOpen = true;
+        document.content = content;
+        document.version = version;
+        document.contentHash = contentHash;
       } else {
         sessionDocuments.set(uri, {
           uri,
           content,
           version,
           isOpen: true,
+          contentHash,
         });
       }
     },
@@ -0,0 +1,396 @@
+/**
+ * LSP gateway - speak LSP to an editor and route each request to the
+ * language server configured for the file it names, so one editor
+ * connection reaches every language the workspace has a server for
+ */
+
+import { fileURLToPath } from 'url';
+import * as rpc from 'vscode-jsonrpc';
+import {
+  LSPErrorCodes,
+  TextDocumentSyncKind,
+  type Diagnostic,
+  type DiagnosticSeverity,
+  type DidChangeTextDocumentParams,
+  type DidCloseTextDocumentParams,
+  type DidOpenTextDocumentParams,
+  type InitializeResult,
+  type ServerCapabilities,
+} from 'vscode-languageserver-protocol';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import type { LspSession } from '../runtime/lsp-session.js';
+import type { DiagnosticEntry } from '../types.js';
+import { prepareFileRequest } from '../preparation.js';
+import * as LspOperations from './operations/index.js';
+import logger from '../utils/logger.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+// Diagnostics are published once edits to a document settle
+const DIAGNOSTICS_DELAY_MS = 300;
+
+// Whole documents, which become the overlays the servers see
+const GATEWAY_TEXT_DOCUMENT_SYNC: ServerCapabilities['textDocumentSync'] = {
+  openClose: true,
+  change: TextDocumentSyncKind.Full,
+};
+
+// Capabilities not passed on from the servers: document sync and
+// diagnostics are the gateway's own, requests naming no document aren't
+// routed, and semantic token legends differ from server to server
+const UNROUTED_CAPABILITIES = new Set([
+  'textDocumentSync',
+  'notebookDocumentSync',
+  'positionEncoding',
+  'diagnosticProvider',
+  'executeCommandProvider',
+  'semanticTokensProvider',
+  'workspace',
+  'experimental',
+]);
+
+function isRecord(value: unknown): value is Record<string, unknown> {
+  return typeof value === 'object' && value !== null && !Array.isArray(value);
+}
+
+// Options win over a bare true; options of two servers are combined, with
+// trigger characters and other lists joined
+function mergeCapability(current: unknown, next: unknown): unknown {
+  if (!isRecord(current) || !isRecord(next)) {
+    return isRecord(current) ? current : next || current;
+  }
+  const merged = { ...current };
+  for (const [key, value] of Object.entries(next)) {
+    const existing = merged[key];
+    merged[key] =
+      Array.isArray(existing) && Array.isArray(value)
+        ? [...new Set([...existing, ...value])]
+        : existing || value;
+  }
+  return merged;
+}
+
+/**
+ * Capabilities the gateway offers the editor: each feature at least one of
+ * the servers behind it declared in its initialize response
+ */
+export function mergeServerCapabilities(
+  capabilities: ServerCapabilities[]
+): ServerCapabilities {
+  const merged: Record<string, unknown> = {};
+  for (const server of capabilities) {
+    for (const [key, value] of Object.entries(server)) {
+      if (!UNROUTED_CAPABILITIES.has(key)) {
+        merged[key] = mergeCapability(merged[key], value);
+      }
+    }
+  }
+  return {
+    ...(merged as ServerCapabilities),
+    textDocumentSync: GATEWAY_TEXT_DOCUMENT_SYNC,
+    workspaceSymbolProvider: capabilities.some(
+      (server) => server.workspaceSymbolProvider
+    ),
+  };
+}
+
+// Resolve requests name no document, so they go to the server that
+// answered the request the item came from
+const RESOLVE_ORIGINS: Record<string, string> = {
+  'completionItem/resolve': 'textDocument/completion',
+  'codeAction/resolve': 'textDocument/codeAction',
+  'inlayHint/resolve': 'textDocument/inlayHint',
+};
+
+/**
+ * Document a request is about: its text document, or for call and type
+ * hierarchy follow-ups the item it starts from
+ */
+export function getRequestDocumentUri(params: unknown): string | undefined {
+  const { textDocument, item } = (params ?? {}) as {
+    textDocument?: { uri?: unknown };
+    item?: { uri?: unknown };
+  };
+  const uri = textDocument?.uri ?? item?.uri;
+  return typeof uri === 'string' && uri.startsWith('file:') ? uri : undefined;
+}
+
+export function toLspDiagnostic(entry: DiagnosticEntry): Diagnostic {
+  return {
+    range: entry.range,
+    severity: entry.severity as DiagnosticSeverity,
+    code: entry.code,
+    source: entry.source,
+    message: entry.message,
+    ...(entry.codeDescription && {
+      codeDescription: { href: entry.codeDescription },
+    }),
+    ...(entry.relatedInformation && {
+      relatedInformation: entry.relatedInformation.map((related) => ({
+        location: { uri: related.uri, range: related.range },
+        message: related.message,
+      })),
+    }),
+  };
+}
+
+function toErrorMessage(error: unknown): string {
+  return error instanceof Error ? error.message : String(error);
+}
+
+/**
+ * Serve the gateway over a reader and writer until the editor sends exit
+ * or disconnects. Resolves with the process exit code.
+ */
+export async function serveLspGateway(
+  manager: LspManager,
+  reader: rpc.MessageReader,
+  writer: rpc.MessageWriter
+): Promise<number> {
+  const connection = rpc.createMessageConnection(reader, writer);
+  const resolveSessions = new Map<string, LspSession>();
+  const diagnosticTimers = new Map<string, NodeJS.Timeout>();
+
+  // Document notifications apply in order, and requests wait for the ones
+  // before them, so a query never sees an older buffer than the editor
+  let documentUpdates = Promise.resolve();
+  const enqueue = (update: () => Promise<void>) => {
+    documentUpdates = documentUpdates.then(update).catch((error: unknown) => {
+      logger.warn('Failed to forward document to language server', {
+        error: toErrorMessage(error),
+      });
+    });
+  };
+
+  const sendDiagnostics = async (uri: string, diagnostics: Diagnostic[]) =>
+    await connection.sendNotification('textDocument/publishDiagnostics', {
+      uri,
+      diagnostics,
+    });
+
+  const publishDiagnostics = async (uri: string) => {
+    const filePath = fileURLToPath(uri);
+    const session = await manager.getSessionForFile(filePath);
+    const prepared = prepareFileRequest(session, { file: filePath });
+    if (!prepared.ok) throw toCodedError(prepared.error);
+
+    const result = await LspOperations.getDiagnostics(session, prepared.data);
+    if (!result.ok) throw toCodedError(result.error);
+
+    await sendDiagnostics(uri, result.data.map(toLspDiagnostic));
+  };
+
+  const scheduleDiagnostics = (uri: string) => {
+    clearTimeout(diagnosticTimers.get(uri));
+    diagnosticTimers.set(
+      uri,
+      setTimeout(() => {
+        diagnosticTimers.delete(uri);
+        enqueue(async () => await publishDiagnostics(uri));
+      }, DIAGNOSTICS_DELAY_MS)
+    );
+  };
+
+  const setOverlay = (uri: string, content: string | null) => {
+    enqueue(async () => {
+      const filePath = fileURLToPath(uri);
+      let session: LspSession;
+      try {
+        session = await manager.getSessionForFile(filePath);
+      } catch (error) {
+        // Editors open files no configured server handles
+        logger.debug('No language server for document', {
+          uri,
+          error: toErrorMessage(error),
+        });
+        return;
+      }
+      const result = await session.setDocumentOverlay(filePath, content);
+      if (!result.ok) throw toCodedError(result.error);
+    });
+    if (content === null) {
+      clearTimeout(diagnosticTimers.get(uri));
+      diagnosticTimers.delete(uri);
+      enqueue(async () => await sendDiagnostics(uri, []));
+    } else {
+      scheduleDiagnostics(uri);
+    }
+  };
+
+  const forward = async (
+    session: LspSession,
+    filePath: string,
+    method: string,
+    params: unknown,
+    token: rpc.CancellationToken
+  ): Promise<unknown> => {
+    // The server's own error reaches the editor with its code, e.g.
+    // RequestCancelled or ContentModified
+    let serverError: rpc.ResponseError<unknown> | undefined;
+    const result = await session.executeWithDocumentLifecycle(
+      filePath,
+      'respect_existing',
+      async (scope) => {
+        try {
+          return {
+            ok: true,
+            data: await scope.request<unknown>(method, params, token),
+          };
+        } catch (error) {
+          if (!(error instanceof rpc.ResponseError)) {
+            throw error;
+          }
+          serverError = error;
+          return { ok: true, data: null };
+        }
+      }
+    );
+    if (serverError) {
+      throw serverError;
+    }
+    if (!result.ok) {
+      throw new rpc.ResponseError(
+        LSPErrorCodes.RequestFailed,
+        result.error.message
+      );
+    }
+    return result.data;
+  };
+
+  // Start the workspace's servers so the editor is offered what they
+  // support rather than every feature
+  connection.onRequest('initialize', async (): Promise<InitializeResult> => {
+    let sessions: LspSession[] = [];
+    try {
+      sessions = await manager.getSearchSessions();
+    } catch (error) {
+      logger.warn('No language servers started for the gateway', {
+        error: toErrorMessage(error),
+      });
+    }
+    return {
+      capabilities: mergeServerCapabilities(
+        sessions.flatMap((session) => session.getServerCapabilities() ?? [])
+      ),
+      serverInfo: { name: 'symbols' },
+    };
+  });
+
+  let shutdownRequested = false;
+  connection.onRequest('shutdown', async () => {
+    shutdownRequested = true;
+    await documentUpdates;
+    await manager.shutdown();
+    return null;
+  });
+
+  // Every language's symbols, in the order the servers are configured
+  connection.onRequest(
+    'workspace/symbol',
+    async (params: unknown, token: rpc.CancellationToken) => {
+      const sessions = await manager.getSearchSessions();
+      const results = await Promise.all(
+        sessions.map(async (session) => {
+          try {
+            return (
+              (await session.request<unknown[] | null>(
+                'workspace/symbol',
+                params,
+                token
+              )) ?? []
+            );
+          } catch (error) {
+            logger.warn('Workspace symbol request failed', {
+              profile: session.getProfile().name,
+              error: toErrorMessage(error),
+            });
+            return [];
+          }
+        })
+      );
+      if (token.isCancellationRequested) {
+        throw new rpc.ResponseError(
+          LSPErrorCodes.RequestCancelled,
+          'Workspace symbol request cancelled'
+        );
+      }
+      return results.flat();
+    }
+  );
+
+  connection.onRequest(async (method, params, token) => {
+    await documentUpdates;
+
+    const origin = RESOLVE_ORIGINS[method];
+    if (origin) {
+      const session = resolveSessions.get(origin);
+      if (!session) {
+        throw new rpc.ResponseError(
+          LSPErrorCodes.RequestFailed,
+          `No ${origin} result to resolve`
+        );
+      }
+      return await session.request(method, params, token);
+    }
+
+    const uri = getRequestDocumentUri(params);
+    if (!uri) {
+      throw new rpc.ResponseError(
+        rpc.ErrorCodes.MethodNotFound,
+        `Unhandled method ${method}`
+      );
+    }
+
+    const filePath = fileURLToPath(uri);
+    const session = await manager.getSessionForFile(filePath);
+    const result = await forward(session, filePath, method, params, token);
+    if (Object.values(RESOLVE_ORIGINS).includes(method)) {
+      resolveSessions.set(method, session);
+    }
+    return result;
+  });
+
+  connection.onNotification(
+    'textDocument/didOpen',
+    (params: DidOpenTextDocumentParams) => {
+      setOverlay(params.textDocument.uri, params.textDocument.text);
+    }
+  );
+
+  connection.onNotification(
+    'textDocument/didChange',
+    (params: DidChangeTextDocumentParams) => {
+      const change = params.contentChanges.at(-1);
+      if (change && !('range' in change)) {
+        setOverlay(params.textDocument.uri, change.text);
+      }
+    }
+  );
+
+  connection.onNotification(
+    'textDocument/didClose',
+    (params: DidCloseTextDocumentParams) => {
+      setOverlay(params.textDocument.uri, null);
+    }
+  );
+
+  return await new Promise<number>((resolve) => {
+    const finish = (exitCode: number) => {
+      for (const timer of diagnosticTimers.values()) {
+        clearTimeout(timer);
+      }
+      diagnosticTimers.clear();
+      connection.dispose();
+      resolve(exitCode);
+    };
+
+    // Exiting without a shutdown request first is an error exit
+    connection.onNotification('exit', () => finish(shutdownRequested ? 0 : 1));
+    connection.onClose(() => finish(0));
+    connection.onError(([error]) => {
+      logger.error('LSP gateway connection error', {
+        error: toErrorMessage(error),
+      });
+    });
+    connection.listen();
+  });
+}
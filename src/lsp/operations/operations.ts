@@ -2,7 +2,9 @@
  * Public LspOperations - MCP-backed navigation operations.
  */
 
+import { readFile } from 'node:fs/promises';
 import { setTimeout as delay } from 'node:timers/promises';
+import { fileURLToPath } from 'node:url';
 import {
   createLspError,
   DiagnosticEntry,
@@ -10,8 +12,6 @@ import {
   Result,
   tryResult,
   tryResultAsync,
-  DiagnosticsStore,
-  DiagnosticProviderStore,
   DiagnosticProvider,
 } from '../../types.js';
 import {
@@ -22,37 +22,95 @@ import {
 } from '../../preparation.js';
 import { DEFAULT_SEARCH_WARMUP_WINDOW_MS } from '../../config/lsp-config.js';
 import logger from '../../utils/logger.js';
+import { splitLines } from '../../utils/text.js';
 import {
   CallHierarchyDirection,
   CallHierarchyIncomingCall,
   CallHierarchyItem,
+  CallHierarchyOptions,
   CallHierarchyOutgoingCall,
   CallHierarchyResult,
+  CallHierarchyTarget,
+  CallPathOptions,
+  CallPathResult,
+  CallPathStep,
+  CallTreeNode,
+  CodeAction,
+  CodeActionEdits,
+  CodeLens,
+  CodeLensParams,
+  CodeLensResult,
+  CommandExecution,
+  ExecuteCommandParams,
+  CodeActionParams,
+  Command,
   CompletionItem,
   CompletionList,
   CompletionParams,
   CompletionResult,
+  decodeSemanticTokens,
+  Diagnostic,
   DocumentDiagnosticParams,
+  WorkspaceDiagnosticParams,
+  WorkspaceDiagnosticReport,
   FlattenedSymbol,
   getDocumentSymbols,
+  getSemanticTokensLegend,
   Hover,
+  isCodeActionCommand,
+  DocumentFormattingParams,
+  DocumentRangeFormattingParams,
+  DocumentOnTypeFormattingParams,
+  FormattingOptions,
+  FoldingRange,
+  FoldingRangeParams,
+  SelectionRange,
+  SelectionRangeParams,
+  DocumentHighlight,
+  DocumentHighlightParams,
+  HighlightEntry,
+  ReferenceAccess,
+  InlayHint,
+  InlayHintParams,
+  TextEdit,
   Location,
+  LocationRequestMethod,
   LogMessageResult,
+  Position,
   Range,
   ReferenceParams,
+  RelatedTestsOptions,
+  RelatedTestsResult,
   RenameParams,
+  PrepareRenameParams,
+  PrepareRenameResult,
   RenameResult,
+  requestSemanticTokenData,
+  SemanticToken,
+  SemanticTokensCacheEntry,
   SymbolInformation,
   SymbolInspection,
   // Internal result types
   SymbolReference,
   SymbolSearchResult,
+  TestCaller,
   TextDocumentPositionParams,
+  TypeHierarchyDirection,
+  TypeHierarchyItem,
+  TypeHierarchyOptions,
+  TypeHierarchyResult,
+  TypeHierarchyTarget,
+  TypeTreeNode,
   WorkspaceEdit,
   WorkspaceSymbol,
   WorkspaceSymbolParams,
 } from '../../types/lsp.js';
-import { CompletionTriggerKind } from 'vscode-languageserver-protocol';
+import {
+  CodeActionTriggerKind,
+  CompletionTriggerKind,
+  type LocationLink,
+  type TextDocumentIdentifier,
+} from 'vscode-languageserver-protocol';
 import type {
   CursorContextOperationResult,
   LspSession,
@@ -151,6 +209,7 @@ export async function inspectSymbol(
           const [
             hoverResult,
             definitionResult,
+            declarationResult,
             typeDefinitionResult,
             implementationResult,
           ] = await Promise.allSettled([
@@ -159,6 +218,10 @@ export async function inspectSymbol(
               'textDocument/definition',
               positionParams
             ),
+            scope.request<Location | Location[]>(
+              'textDocument/declaration',
+              positionParams
+            ),
             scope.request<Location | Location[]>(
               'textDocument/typeDefinition',
               positionParams
@@ -176,6 +239,10 @@ export async function inspectSymbol(
               definitionResult.status === 'fulfilled'
                 ? definitionResult.value
                 : null,
+            declaration:
+              declarationResult.status === 'fulfilled'
+                ? declarationResult.value
+                : null,
             typeDefinition:
               typeDefinitionResult.status === 'fulfilled'
                 ? typeDefinitionResult.value
@@ -208,6 +275,9 @@ export async function inspectSymbol(
           };
 
           inspectData.definition = transformLocations(inspectData.definition);
+          inspectData.declaration = transformLocations(
+            inspectData.declaration
+          );
           inspectData.typeDefinition = transformLocations(
             inspectData.typeDefinition
           );
@@ -228,9 +298,46 @@ export async function inspectSymbol(
   );
 }
 
-export async function findReferences(
+/**
+ * Hover alone, for scans that only need a symbol's signature and docs
+ */
+export async function hoverSymbol(
   session: LspSession,
   prepared: PreparedSymbolPositionRequest
+): Promise<Result<CursorContextOperationResult<Hover | null>>> {
+  return await session.executeWithCursorContext(
+    'hover',
+    prepared.filePath,
+    prepared.position,
+    'transient',
+    async (scope) => {
+      return await tryResultAsync(
+        async () => {
+          const params: TextDocumentPositionParams = {
+            textDocument: { uri: scope.uri },
+            position: prepared.lspPosition,
+          };
+          const hover = await scope.request<Hover | null>(
+            'textDocument/hover',
+            params
+          );
+          return hover ?? null;
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Hover failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
+
+export async function findReferences(
+  session: LspSession,
+  prepared: PreparedSymbolPositionRequest,
+  options: { includeDeclaration?: boolean } = {}
 ): Promise<Result<CursorContextOperationResult<SymbolReference[]>>> {
   return await session.executeWithCursorContext(
     'references',
@@ -244,7 +351,7 @@ export async function findReferences(
             textDocument: { uri: scope.uri },
             position: prepared.lspPosition,
             context: {
-              includeDeclaration: true,
+              includeDeclaration: options.includeDeclaration ?? true,
             },
           };
 
@@ -279,10 +386,256 @@ export async function findReferences(
   );
 }
 
+// DocumentHighlightKind values; servers omitting the kind mean text
+const HIGHLIGHT_ACCESS: Record<number, ReferenceAccess> = {
+  1: 'text',
+  2: 'read',
+  3: 'write',
+};
+
+/**
+ * Occurrences of the symbol at a position in the scope's document, sorted
+ */
+async function requestHighlights(
+  session: LspSession,
+  scope: SessionDocumentScope,
+  position: Position
+): Promise<HighlightEntry[]> {
+  if (!scope.serverCapabilities?.documentHighlightProvider) {
+    throw new Error(
+      `${session.getProfile().name} does not support document highlights`
+    );
+  }
+
+  const highlights = await scope.request<
+    DocumentHighlight[] | null,
+    DocumentHighlightParams
+  >('textDocument/documentHighlight', {
+    textDocument: { uri: scope.uri },
+    position,
+  });
+  return (highlights ?? [])
+    .map((highlight) => ({
+      range: highlight.range,
+      access: HIGHLIGHT_ACCESS[highlight.kind ?? 1] ?? 'text',
+    }))
+    .sort(
+      (a, b) =>
+        a.range.start.line - b.range.start.line ||
+        a.range.start.character - b.range.start.character
+    );
+}
+
+function createHighlightError(error: unknown) {
+  return createLspError(
+    ErrorCode.LSPError,
+    `Document highlight request failed: ${error instanceof Error ? error.message : String(error)}`,
+    error instanceof Error ? error : undefined
+  );
+}
+
+/**
+ * Occurrences of the symbol at a position within its document, tagged as
+ * reads or writes where the server tells them apart
+ */
+export async function documentHighlights(
+  session: LspSession,
+  prepared: PreparedSymbolPositionRequest
+): Promise<Result<CursorContextOperationResult<HighlightEntry[]>>> {
+  return await session.executeWithCursorContext(
+    'document highlights',
+    prepared.filePath,
+    prepared.position,
+    'transient',
+    async (scope) =>
+      await tryResultAsync(
+        async () =>
+          await requestHighlights(session, scope, prepared.lspPosition),
+        createHighlightError
+      )
+  );
+}
+
+/**
+ * Tag references with their access from document highlights, requested at
+ * the first reference in each file. Files past `maxFiles`, and occurrences
+ * the highlights don't cover, stay untagged.
+ */
+export async function classifyReferenceAccess(
+  session: LspSession,
+  references: SymbolReference[],
+  maxFiles: number
+): Promise<Result<SymbolReference[]>> {
+  const firstByFile = new Map<string, SymbolReference>();
+  for (const reference of references) {
+    if (!firstByFile.has(reference.uri)) {
+      firstByFile.set(reference.uri, reference);
+    }
+  }
+
+  const positionKey = (uri: string, range: Range) =>
+    `${uri}:${range.start.line}:${range.start.character}`;
+  const accessByPosition = new Map<string, ReferenceAccess>();
+
+  for (const [uri, first] of [...firstByFile].slice(0, maxFiles)) {
+    const result = await session.executeWithDocumentLifecycle(
+      fileURLToPath(uri),
+      'transient',
+      async (scope): Promise<Result<HighlightEntry[]>> =>
+        await tryResultAsync(
+          async () =>
+            await requestHighlights(session, scope, first.range.start),
+          createHighlightError
+        )
+    );
+    if (!result.ok) {
+      return result;
+    }
+
+    for (const highlight of result.data) {
+      accessByPosition.set(positionKey(uri, highlight.range), highlight.access);
+    }
+  }
+
+  return {
+    ok: true,
+    data: references.map((reference) => {
+      const access = accessByPosition.get(
+        positionKey(reference.uri, reference.range)
+      );
+      return access ? { ...reference, access } : reference;
+    }),
+  };
+}
+
+/**
+ * Servers answer definition-style requests with a location, an array of
+ * locations or location links; links point at their target selection
+ */
+function normalizeLocations(
+  result: Location | Location[] | LocationLink[] | null
+): Location[] {
+  if (!result) {
+    return [];
+  }
+
+  return (Array.isArray(result) ? result : [result]).map((entry) =>
+    'targetUri' in entry
+      ? { uri: entry.targetUri, range: entry.targetSelectionRange }
+      : entry
+  );
+}
+
+/**
+ * Locations for a definition-style request at a position, 1-based like
+ * the locations returned by inspect
+ */
+export async function findLocations(
+  session: LspSession,
+  prepared: PreparedSymbolPositionRequest,
+  method: LocationRequestMethod
+): Promise<Result<CursorContextOperationResult<Location[]>>> {
+  return await session.executeWithCursorContext(
+    method.replace('textDocument/', ''),
+    prepared.filePath,
+    prepared.position,
+    'transient',
+    async (scope) =>
+      await tryResultAsync(
+        async () => {
+          const result = await scope.request<
+            Location | Location[] | LocationLink[] | null
+          >(method, {
+            textDocument: { uri: scope.uri },
+            position: prepared.lspPosition,
+          });
+
+          return normalizeLocations(result).map((location) => ({
+            uri: location.uri,
+            range: {
+              start: {
+                line: location.range.start.line + 1,
+                character: location.range.start.character + 1,
+              },
+              end: {
+                line: location.range.end.line + 1,
+                character: location.range.end.character + 1,
+              },
+            },
+          }));
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `${method} failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      )
+  );
+}
+
+function toIncomingCallNode(call: CallHierarchyIncomingCall): CallTreeNode {
+  return { item: call.from, callSites: call.fromRanges, children: null };
+}
+
+function toOutgoingCallNode(call: CallHierarchyOutgoingCall): CallTreeNode {
+  return { item: call.to, callSites: call.fromRanges, children: null };
+}
+
+/**
+ * Follow calls past the first level, breadth first so the budget goes to
+ * the nearest calls. Items already in the tree aren't expanded again.
+ */
+async function expandCallTree(
+  scope: SessionDocumentScope,
+  roots: CallTreeNode[],
+  direction: 'incoming' | 'outgoing',
+  visited: Set<string>,
+  budget: { depth: number; remainingItems: number }
+): Promise<boolean> {
+  let frontier = roots;
+
+  for (let level = 2; level <= budget.depth && frontier.length > 0; level++) {
+    const next: CallTreeNode[] = [];
+
+    for (const node of frontier) {
+      const key = getCallItemKey(node.item);
+      if (visited.has(key)) {
+        continue;
+      }
+      if (budget.remainingItems <= 0) {
+        return true;
+      }
+      visited.add(key);
+      budget.remainingItems--;
+
+      if (direction === 'incoming') {
+        const calls = await scope.request<CallHierarchyIncomingCall[] | null>(
+          'callHierarchy/incomingCalls',
+          { item: node.item }
+        );
+        node.children = (calls ?? []).map(toIncomingCallNode);
+      } else {
+        const calls = await scope.request<CallHierarchyOutgoingCall[] | null>(
+          'callHierarchy/outgoingCalls',
+          { item: node.item }
+        );
+        node.children = (calls ?? []).map(toOutgoingCallNode);
+      }
+      next.push(...node.children);
+    }
+
+    frontier = next;
+  }
+
+  return false;
+}
+
 export async function callHierarchy(
   session: LspSession,
   prepared: PreparedSymbolPositionRequest,
-  direction: CallHierarchyDirection = 'both'
+  direction: CallHierarchyDirection = 'both',
+  options: CallHierarchyOptions = { depth: 1, maxExploredItems: 0 }
 ): Promise<Result<CursorContextOperationResult<CallHierarchyResult>>> {
   return await session.executeWithCursorContext(
     'callHierarchy',
@@ -306,6 +659,8 @@ export async function callHierarchy(
             return {
               direction,
               targets: [],
+              depth: options.depth,
+              limitReached: false,
             };
           }
 
@@ -339,9 +694,56 @@ export async function callHierarchy(
             })
           );
 
+          if (options.depth <= 1) {
+            return {
+              direction,
+              targets,
+              depth: options.depth,
+              limitReached: false,
+            };
+          }
+
+          // Trees are expanded one after another so they share the budget
+          const budget = {
+            depth: options.depth,
+            remainingItems: options.maxExploredItems,
+          };
+          let limitReached = false;
+          const expandedTargets: CallHierarchyTarget[] = [];
+          for (const target of targets) {
+            const expanded: CallHierarchyTarget = { ...target };
+            if (target.incomingCalls) {
+              expanded.incomingTree =
+                target.incomingCalls.map(toIncomingCallNode);
+              limitReached =
+                (await expandCallTree(
+                  scope,
+                  expanded.incomingTree,
+                  'incoming',
+                  new Set([getCallItemKey(target.item)]),
+                  budget
+                )) || limitReached;
+            }
+            if (target.outgoingCalls) {
+              expanded.outgoingTree =
+                target.outgoingCalls.map(toOutgoingCallNode);
+              limitReached =
+                (await expandCallTree(
+                  scope,
+                  expanded.outgoingTree,
+                  'outgoing',
+                  new Set([getCallItemKey(target.item)]),
+                  budget
+                )) || limitReached;
+            }
+            expandedTargets.push(expanded);
+          }
+
           return {
             direction,
-            targets,
+            targets: expandedTargets,
+            depth: options.depth,
+            limitReached,
           };
         },
         (error) =>
@@ -355,145 +757,688 @@ export async function callHierarchy(
   );
 }
 
-export async function completion(
+/**
+ * Follow supertypes or subtypes past the first level, breadth first like
+ * call trees
+ */
+async function expandTypeTree(
+  scope: SessionDocumentScope,
+  roots: TypeTreeNode[],
+  method: 'typeHierarchy/supertypes' | 'typeHierarchy/subtypes',
+  visited: Set<string>,
+  budget: { depth: number; remainingItems: number }
+): Promise<boolean> {
+  let frontier = roots;
+
+  for (let level = 2; level <= budget.depth && frontier.length > 0; level++) {
+    const next: TypeTreeNode[] = [];
+
+    for (const node of frontier) {
+      const key = getCallItemKey(node.item);
+      if (visited.has(key)) {
+        continue;
+      }
+      if (budget.remainingItems <= 0) {
+        return true;
+      }
+      visited.add(key);
+      budget.remainingItems--;
+
+      const items = await scope.request<TypeHierarchyItem[] | null>(method, {
+        item: node.item,
+      });
+      node.children = (items ?? []).map((item) => ({ item, children: null }));
+      next.push(...node.children);
+    }
+
+    frontier = next;
+  }
+
+  return false;
+}
+
+export async function typeHierarchy(
   session: LspSession,
-  prepared: PreparedSymbolPositionRequest
-): Promise<Result<CursorContextOperationResult<CompletionResult[]>>> {
+  prepared: PreparedSymbolPositionRequest,
+  direction: TypeHierarchyDirection,
+  options: TypeHierarchyOptions
+): Promise<Result<CursorContextOperationResult<TypeHierarchyResult>>> {
   return await session.executeWithCursorContext(
-    'completion',
+    'typeHierarchy',
     prepared.filePath,
     prepared.position,
     'transient',
-    async (scope) => {
-      return await tryResultAsync(
+    async (scope) =>
+      await tryResultAsync(
         async () => {
-          const params: CompletionParams = {
+          const preparedItems = await scope.request<
+            TypeHierarchyItem[] | null
+          >('textDocument/prepareTypeHierarchy', {
             textDocument: { uri: scope.uri },
             position: prepared.lspPosition,
-            context: {
-              triggerKind: CompletionTriggerKind.Invoked,
-            },
+          });
+          const budget = {
+            depth: options.depth,
+            remainingItems: options.maxExploredItems,
           };
+          let limitReached = false;
+
+          const targets: TypeHierarchyTarget[] = [];
+          const items = Array.isArray(preparedItems) ? preparedItems : [];
+          for (const item of items) {
+            const expand = async (
+              method: 'typeHierarchy/supertypes' | 'typeHierarchy/subtypes'
+            ) => {
+              const found = await scope.request<TypeHierarchyItem[] | null>(
+                method,
+                { item }
+              );
+              const roots: TypeTreeNode[] = (found ?? []).map((entry) => ({
+                item: entry,
+                children: null,
+              }));
+              limitReached =
+                (await expandTypeTree(
+                  scope,
+                  roots,
+                  method,
+                  new Set([getCallItemKey(item)]),
+                  budget
+                )) || limitReached;
+              return roots;
+            };
 
-          const completionResult: CompletionList | CompletionItem[] =
-            await scope.request('textDocument/completion', params);
-
-          let completions: CompletionItem[] = [];
-
-          if (Array.isArray(completionResult)) {
-            completions = completionResult;
-          } else if (
-            completionResult &&
-            typeof completionResult === 'object' &&
-            'items' in completionResult
-          ) {
-            // CompletionList format - items is always CompletionItem[] per LSP spec
-            completions = completionResult.items;
+            targets.push({
+              item,
+              supertypes:
+                direction === 'subtypes'
+                  ? null
+                  : await expand('typeHierarchy/supertypes'),
+              subtypes:
+                direction === 'supertypes'
+                  ? null
+                  : await expand('typeHierarchy/subtypes'),
+            });
           }
 
-          // Transform LSP completion items to our format
-          const results: CompletionResult[] = completions.map((item) => ({
-            label: item.label,
-            kind: item.kind || 1, // Default to Text if kind is undefined
-            detail: item.detail || '',
-            documentation: item.documentation || '',
-            insertText: item.insertText || item.label,
-            filterText: item.filterText || item.label,
-            sortText: item.sortText || item.label,
-            ...(item.textEdit && 'range' in item.textEdit
-              ? {
-                  textEdit: {
-                    newText: item.textEdit.newText,
-                    range: {
-                      start: {
-                        line: item.textEdit.range.start.line + 1,
-                        character: item.textEdit.range.start.character + 1,
-                      },
-                      end: {
-                        line: item.textEdit.range.end.line + 1,
-                        character: item.textEdit.range.end.character + 1,
-                      },
-                    },
-                  },
-                }
-              : {}),
-          }));
-
-          return results;
+          return {
+            direction,
+            targets,
+            depth: options.depth,
+            limitReached,
+          };
         },
         (error) =>
           createLspError(
             ErrorCode.LSPError,
-            `Code completion failed: ${error instanceof Error ? error.message : String(error)}`,
+            `Type hierarchy failed: ${error instanceof Error ? error.message : String(error)}`,
             error instanceof Error ? error : undefined
           )
-      );
-    }
+      )
   );
 }
 
-export async function searchSymbols(
-  session: LspSession,
-  prepared: PreparedWorkspaceRequest
-): Promise<Result<SymbolSearchResult[]>> {
-  return await tryResultAsync(
-    async () => {
-      const params: WorkspaceSymbolParams = {
-        query: prepared.query,
-      };
+function getCallItemKey(item: CallHierarchyItem): string {
+  // Some servers omit selectionRange despite the LSP spec requiring it.
+  const start = (item.selectionRange ?? item.range).start;
+  return `${item.uri}#${start.line}:${start.character}`;
+}
 
-      let results = await requestWorkspaceSymbols(session, params);
-      const warmupDeadlineAt = getSearchWarmupDeadlineMs(session);
+async function prepareCallItems(
+  scope: SessionDocumentScope,
+  position: TextDocumentPositionParams['position']
+): Promise<CallHierarchyItem[]> {
+  const items = await scope.request<CallHierarchyItem[] | null>(
+    'textDocument/prepareCallHierarchy',
+    { textDocument: { uri: scope.uri }, position }
+  );
+  return Array.isArray(items) ? items : [];
+}
 
-      if (results.length === 0 && warmupDeadlineAt !== null) {
-        for (const retryDelayMs of SEARCH_WARMUP_RETRY_DELAYS_MS) {
-          const remainingWarmupMs = warmupDeadlineAt - Date.now();
-          if (remainingWarmupMs <= 0) {
-            break;
-          }
+/**
+ * Breadth-first search over outgoing calls, so the paths found are the
+ * shortest ones. Each function is expanded at most once and a path is
+ * recorded for every distinct caller of the target.
+ */
+async function searchCallPaths(
+  scope: SessionDocumentScope,
+  sources: CallHierarchyItem[],
+  targets: CallHierarchyItem[],
+  options: CallPathOptions
+): Promise<CallPathResult> {
+  const targetKeys = new Set(targets.map(getCallItemKey));
+  const visited = new Set<string>();
+  const paths: CallPathStep[][] = [];
+  let frontier: Array<{ item: CallHierarchyItem; path: CallPathStep[] }> = [];
+
+  for (const item of sources) {
+    const key = getCallItemKey(item);
+    if (visited.has(key)) {
+      continue;
+    }
+    visited.add(key);
+    const path: CallPathStep[] = [{ item, callSites: [] }];
+    if (targetKeys.has(key)) {
+      paths.push(path);
+    } else {
+      frontier.push({ item, path });
+    }
+  }
 
-          await delay(Math.min(retryDelayMs, remainingWarmupMs));
-          results = await requestWorkspaceSymbols(session, params);
+  let depth = 0;
+  let exploredItems = 0;
+  let limitReached = false;
+
+  while (
+    depth < options.maxDepth &&
+    frontier.length > 0 &&
+    paths.length < options.maxPaths &&
+    !limitReached
+  ) {
+    depth++;
+    const next: typeof frontier = [];
+
+    for (const { item, path } of frontier) {
+      if (paths.length >= options.maxPaths) {
+        break;
+      }
+      if (exploredItems >= options.maxExploredItems) {
+        limitReached = true;
+        break;
+      }
+      exploredItems++;
 
-          if (results.length > 0) {
-            logger.info('Workspace symbol search warmed up after retry', {
-              profile: session.getProfile().name,
-              query: prepared.query,
-            });
-            break;
+      const calls = await scope.request<CallHierarchyOutgoingCall[] | null>(
+        'callHierarchy/outgoingCalls',
+        { item }
+      );
+
+      for (const call of Array.isArray(calls) ? calls : []) {
+        const key = getCallItemKey(call.to);
+        const extended = [
+          ...path,
+          { item: call.to, callSites: call.fromRanges },
+        ];
+        if (targetKeys.has(key)) {
+          if (paths.length < options.maxPaths) {
+            paths.push(extended);
           }
+        } else if (!visited.has(key)) {
+          visited.add(key);
+          next.push({ item: call.to, path: extended });
         }
       }
+    }
 
-      return results;
-    },
-    (error) =>
-      createLspError(
-        ErrorCode.LSPError,
-        `Workspace symbol search failed: ${error instanceof Error ? error.message : String(error)}`,
-        error instanceof Error ? error : undefined
-      )
+    frontier = next;
+  }
+
+  return {
+    sources,
+    targets,
+    paths,
+    maxDepth: options.maxDepth,
+    exploredItems,
+    limitReached,
+  };
+}
+
+function createCallPathError(error: unknown) {
+  return createLspError(
+    ErrorCode.LSPError,
+    `Call path search failed: ${error instanceof Error ? error.message : String(error)}`,
+    error instanceof Error ? error : undefined
   );
 }
 
-export async function outlineSymbols(
+/**
+ * Find example call paths from the callable symbol at `source` to the one at
+ * `target`, following outgoing calls up to `options.maxDepth` calls deep
+ */
+export async function findCallPaths(
   session: LspSession,
-  prepared: PreparedFileRequest
-): Promise<Result<FlattenedSymbol[]>> {
-  return await session.executeWithDocumentLifecycle(
-    prepared.filePath,
+  source: PreparedSymbolPositionRequest,
+  target: PreparedSymbolPositionRequest,
+  options: CallPathOptions
+): Promise<Result<CursorContextOperationResult<CallPathResult>>> {
+  const targetItems = await session.executeWithCursorContext(
+    'callPaths',
+    target.filePath,
+    target.position,
     'transient',
-    async (scope): Promise<Result<FlattenedSymbol[]>> => {
-      return await tryResultAsync(
-        async () =>
-          await getDocumentSymbols(
-            async (method, params) => await scope.request(method, params),
-            scope.uri
-          ),
-        (error) =>
-          createLspError(
-            ErrorCode.LSPError,
-            `Document symbol request failed: ${error instanceof Error ? error.message : String(error)}`,
+    async (scope) =>
+      await tryResultAsync(
+        () => prepareCallItems(scope, target.lspPosition),
+        createCallPathError
+      )
+  );
+  if (!targetItems.ok) {
+    return targetItems;
+  }
+
+  return await session.executeWithCursorContext(
+    'callPaths',
+    source.filePath,
+    source.position,
+    'transient',
+    async (scope) =>
+      await tryResultAsync(async () => {
+        const sources = await prepareCallItems(scope, source.lspPosition);
+        return await searchCallPaths(
+          scope,
+          sources,
+          targetItems.data.result,
+          options
+        );
+      }, createCallPathError)
+  );
+}
+
+/**
+ * Walk incoming calls from the symbol up to `options.maxDepth` hops. Test
+ * callers are recorded and not expanded further; other callers are.
+ */
+async function searchTestCallers(
+  scope: SessionDocumentScope,
+  targets: CallHierarchyItem[],
+  options: RelatedTestsOptions
+): Promise<
+  Pick<RelatedTestsResult, 'callers' | 'exploredItems' | 'limitReached'>
+> {
+  const visited = new Set(targets.map(getCallItemKey));
+  const callers: TestCaller[] = [];
+  let frontier: Array<{ item: CallHierarchyItem; via: CallHierarchyItem[] }> =
+    targets.map((item) => ({ item, via: [] }));
+  let depth = 0;
+  let exploredItems = 0;
+  let limitReached = false;
+
+  while (depth < options.maxDepth && frontier.length > 0 && !limitReached) {
+    depth++;
+    const next: typeof frontier = [];
+
+    for (const { item, via } of frontier) {
+      if (exploredItems >= options.maxExploredItems) {
+        limitReached = true;
+        break;
+      }
+      exploredItems++;
+
+      const calls = await scope.request<CallHierarchyIncomingCall[] | null>(
+        'callHierarchy/incomingCalls',
+        { item }
+      );
+      // Direct callers sit next to the symbol, so their chain stays empty
+      const callerVia = depth === 1 ? [] : [item, ...via];
+
+      for (const call of Array.isArray(calls) ? calls : []) {
+        const key = getCallItemKey(call.from);
+        if (visited.has(key)) {
+          continue;
+        }
+        visited.add(key);
+        if (options.isTestFile(call.from.uri)) {
+          callers.push({ item: call.from, via: callerVia, hops: depth });
+        } else {
+          next.push({ item: call.from, via: callerVia });
+        }
+      }
+    }
+
+    frontier = next;
+  }
+
+  return { callers, exploredItems, limitReached };
+}
+
+/**
+ * Find tests that reference the symbol at `prepared` directly, or call it
+ * through up to `options.maxDepth` hops of non-test functions
+ */
+export async function findRelatedTests(
+  session: LspSession,
+  prepared: PreparedSymbolPositionRequest,
+  options: RelatedTestsOptions
+): Promise<Result<CursorContextOperationResult<RelatedTestsResult>>> {
+  return await session.executeWithCursorContext(
+    'relatedTests',
+    prepared.filePath,
+    prepared.position,
+    'transient',
+    async (scope) =>
+      await tryResultAsync(
+        async () => {
+          const params: ReferenceParams = {
+            textDocument: { uri: scope.uri },
+            position: prepared.lspPosition,
+            context: { includeDeclaration: false },
+          };
+          const references = await scope.request<Location[] | null>(
+            'textDocument/references',
+            params
+          );
+          const targets = await prepareCallItems(scope, prepared.lspPosition);
+          const search = await searchTestCallers(scope, targets, options);
+
+          return {
+            targets,
+            references: (Array.isArray(references) ? references : []).filter(
+              (reference) => options.isTestFile(reference.uri)
+            ),
+            ...search,
+            maxDepth: options.maxDepth,
+          };
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Related tests search failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      )
+  );
+}
+
+/**
+ * Completion item in our format, with 1-based edit ranges
+ */
+function toCompletionResult(
+  item: CompletionItem,
+  resolved: boolean
+): CompletionResult {
+  const toDisplayRange = (range: Range) => ({
+    start: {
+      line: range.start.line + 1,
+      character: range.start.character + 1,
+    },
+    end: {
+      line: range.end.line + 1,
+      character: range.end.character + 1,
+    },
+  });
+
+  return {
+    label: item.label,
+    kind: item.kind || 1, // Default to Text if kind is undefined
+    detail: item.detail || '',
+    documentation: item.documentation || '',
+    insertText: item.insertText || item.label,
+    filterText: item.filterText || item.label,
+    sortText: item.sortText || item.label,
+    ...(item.textEdit && 'range' in item.textEdit
+      ? {
+          textEdit: {
+            newText: item.textEdit.newText,
+            range: toDisplayRange(item.textEdit.range),
+          },
+        }
+      : {}),
+    ...(item.additionalTextEdits?.length
+      ? {
+          additionalTextEdits: item.additionalTextEdits.map((edit) => ({
+            newText: edit.newText,
+            range: toDisplayRange(edit.range),
+          })),
+        }
+      : {}),
+    ...(item.preselect ? { preselect: true } : {}),
+    ...(resolved ? { resolved: true } : {}),
+  };
+}
+
+/**
+ * Completions at a position, ranked preselected first and then by sort
+ * text. The top `resolve` items are resolved for their documentation and
+ * the extra edits, such as imports, that accepting them makes.
+ */
+export async function completion(
+  session: LspSession,
+  prepared: PreparedSymbolPositionRequest,
+  options: { resolve?: number } = {}
+): Promise<Result<CursorContextOperationResult<CompletionResult[]>>> {
+  return await session.executeWithCursorContext(
+    'completion',
+    prepared.filePath,
+    prepared.position,
+    'transient',
+    async (scope) => {
+      return await tryResultAsync(
+        async () => {
+          const params: CompletionParams = {
+            textDocument: { uri: scope.uri },
+            position: prepared.lspPosition,
+            context: {
+              triggerKind: CompletionTriggerKind.Invoked,
+            },
+          };
+
+          const completionResult: CompletionList | CompletionItem[] =
+            await scope.request('textDocument/completion', params);
+
+          let completions: CompletionItem[] = [];
+
+          if (Array.isArray(completionResult)) {
+            completions = completionResult;
+          } else if (
+            completionResult &&
+            typeof completionResult === 'object' &&
+            'items' in completionResult
+          ) {
+            // CompletionList format - items is always CompletionItem[] per LSP spec
+            completions = completionResult.items;
+          }
+
+          const ranked = [...completions].sort(
+            (a, b) =>
+              Number(b.preselect ?? false) - Number(a.preselect ?? false) ||
+              (a.sortText ?? a.label).localeCompare(b.sortText ?? b.label)
+          );
+
+          const provider = scope.serverCapabilities?.completionProvider;
+          const resolveCount = provider?.resolveProvider
+            ? Math.min(options.resolve ?? 0, ranked.length)
+            : 0;
+          const results: CompletionResult[] = [];
+          for (const [index, item] of ranked.entries()) {
+            if (index >= resolveCount) {
+              results.push(toCompletionResult(item, false));
+              continue;
+            }
+            const resolved = await scope.request<
+              CompletionItem,
+              CompletionItem
+            >('completionItem/resolve', item);
+            results.push(toCompletionResult(resolved, true));
+          }
+
+          return results;
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Code completion failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
+
+export async function searchSymbols(
+  session: LspSession,
+  prepared: PreparedWorkspaceRequest
+): Promise<Result<SymbolSearchResult[]>> {
+  return await tryResultAsync(
+    async () => {
+      const params: WorkspaceSymbolParams = {
+        query: prepared.query,
+      };
+
+      let results = await requestWorkspaceSymbols(session, params);
+      const warmupDeadlineAt = getSearchWarmupDeadlineMs(session);
+
+      if (results.length === 0 && warmupDeadlineAt !== null) {
+        for (const retryDelayMs of SEARCH_WARMUP_RETRY_DELAYS_MS) {
+          const remainingWarmupMs = warmupDeadlineAt - Date.now();
+          if (remainingWarmupMs <= 0) {
+            break;
+          }
+
+          await delay(Math.min(retryDelayMs, remainingWarmupMs));
+          results = await requestWorkspaceSymbols(session, params);
+
+          if (results.length > 0) {
+            logger.info('Workspace symbol search warmed up after retry', {
+              profile: session.getProfile().name,
+              query: prepared.query,
+            });
+            break;
+          }
+        }
+      }
+
+      return results;
+    },
+    (error) =>
+      createLspError(
+        ErrorCode.LSPError,
+        `Workspace symbol search failed: ${error instanceof Error ? error.message : String(error)}`,
+        error instanceof Error ? error : undefined
+      )
+  );
+}
+
+export async function outlineSymbols(
+  session: LspSession,
+  prepared: PreparedFileRequest
+): Promise<Result<FlattenedSymbol[]>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<FlattenedSymbol[]>> => {
+      return await tryResultAsync(
+        async () =>
+          await getDocumentSymbols(
+            async (method, params) => await scope.request(method, params),
+            scope.uri
+          ),
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Document symbol request failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
+
+// Documents whose last token data is kept as the base for delta requests
+const MAX_CACHED_SEMANTIC_TOKEN_DOCUMENTS = 20;
+
+const semanticTokensCache = new WeakMap<
+  LspSession,
+  Map<string, SemanticTokensCacheEntry>
+>();
+
+function getSemanticTokensCache(
+  session: LspSession
+): Map<string, SemanticTokensCacheEntry> {
+  let cache = semanticTokensCache.get(session);
+  if (!cache) {
+    cache = new Map();
+    semanticTokensCache.set(session, cache);
+  }
+  return cache;
+}
+
+export async function semanticTokens(
+  session: LspSession,
+  prepared: PreparedFileRequest
+): Promise<Result<SemanticToken[]>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<SemanticToken[]>> => {
+      return await tryResultAsync(
+        async () => {
+          const cache = getSemanticTokensCache(session);
+          const data = await requestSemanticTokenData(
+            async (method, params) => await scope.request(method, params),
+            scope.uri,
+            scope.serverCapabilities,
+            cache
+          );
+
+          // Entries are re-inserted on use, so the first is the oldest
+          const oldest = cache.keys().next().value;
+          if (
+            cache.size > MAX_CACHED_SEMANTIC_TOKEN_DOCUMENTS &&
+            oldest !== undefined
+          ) {
+            cache.delete(oldest);
+          }
+
+          if (!data) {
+            return [];
+          }
+
+          const legend = getSemanticTokensLegend(scope.serverCapabilities);
+          return decodeSemanticTokens(
+            data,
+            legend.tokenTypes,
+            legend.tokenModifiers,
+            await readFile(prepared.filePath, 'utf8')
+          );
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Semantic tokens request failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
+
+/**
+ * Inlay hints for a whole file, empty when the server doesn't provide them
+ */
+export async function inlayHints(
+  session: LspSession,
+  prepared: PreparedFileRequest
+): Promise<Result<InlayHint[]>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<InlayHint[]>> => {
+      return await tryResultAsync(
+        async () => {
+          if (!scope.serverCapabilities?.inlayHintProvider) {
+            return [];
+          }
+
+          const lines = splitLines(await readFile(prepared.filePath, 'utf8'));
+          const hints = await scope.request<
+            InlayHint[] | null,
+            InlayHintParams
+          >('textDocument/inlayHint', {
+            textDocument: { uri: scope.uri },
+            range: {
+              start: { line: 0, character: 0 },
+              end: {
+                line: lines.length - 1,
+                character: lines[lines.length - 1]?.length ?? 0,
+              },
+            },
+          });
+          return hints ?? [];
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Inlay hint request failed: ${error instanceof Error ? error.message : String(error)}`,
             error instanceof Error ? error : undefined
           )
       );
@@ -501,42 +1446,581 @@ export async function outlineSymbols(
   );
 }
 
-export async function getDiagnostics(
+/**
+ * Enclosing syntactic ranges at a position, innermost first, e.g. an
+ * expression, its statement, the function and then the class around it
+ */
+export async function selectionRanges(
+  session: LspSession,
+  prepared: PreparedSymbolPositionRequest
+): Promise<Result<CursorContextOperationResult<Range[]>>> {
+  return await session.executeWithCursorContext(
+    'selection range',
+    prepared.filePath,
+    prepared.position,
+    'transient',
+    async (scope) =>
+      await tryResultAsync(
+        async () => {
+          if (!scope.serverCapabilities?.selectionRangeProvider) {
+            throw new Error(
+              `${session.getProfile().name} does not support selection ranges`
+            );
+          }
+
+          const result = await scope.request<
+            SelectionRange[] | null,
+            SelectionRangeParams
+          >('textDocument/selectionRange', {
+            textDocument: { uri: scope.uri },
+            positions: [prepared.lspPosition],
+          });
+
+          const ranges: Range[] = [];
+          for (
+            let selection: SelectionRange | undefined = result?.[0];
+            selection;
+            selection = selection.parent
+          ) {
+            const previous = ranges[ranges.length - 1];
+            // Servers repeat a range when two syntax nodes cover the same text
+            if (
+              !previous ||
+              previous.start.line !== selection.range.start.line ||
+              previous.start.character !== selection.range.start.character ||
+              previous.end.line !== selection.range.end.line ||
+              previous.end.character !== selection.range.end.character
+            ) {
+              ranges.push(selection.range);
+            }
+          }
+          return ranges;
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Selection range request failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      )
+  );
+}
+
+export async function foldingRanges(
+  session: LspSession,
+  prepared: PreparedFileRequest
+): Promise<Result<FoldingRange[]>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<FoldingRange[]>> => {
+      return await tryResultAsync(
+        async () => {
+          if (!scope.serverCapabilities?.foldingRangeProvider) {
+            return [];
+          }
+
+          const ranges = await scope.request<
+            FoldingRange[] | null,
+            FoldingRangeParams
+          >('textDocument/foldingRange', {
+            textDocument: { uri: scope.uri },
+          });
+          return ranges ?? [];
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Folding range request failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
+
+/**
+ * Edits the server's formatter makes to a document, or to a range of it
+ */
+export async function formatDocument(
+  session: LspSession,
+  prepared: PreparedFileRequest,
+  options: FormattingOptions,
+  range?: Range
+): Promise<Result<RenameResult>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<RenameResult>> => {
+      return await tryResultAsync(
+        async () => {
+          const profileName = session.getProfile().name;
+          let edits: TextEdit[] | null;
+          if (range) {
+            if (!scope.serverCapabilities?.documentRangeFormattingProvider) {
+              throw new Error(
+                `${profileName} does not support range formatting`
+              );
+            }
+            edits = await scope.request<
+              TextEdit[] | null,
+              DocumentRangeFormattingParams
+            >('textDocument/rangeFormatting', {
+              textDocument: { uri: scope.uri },
+              range,
+              options,
+            });
+          } else {
+            if (!scope.serverCapabilities?.documentFormattingProvider) {
+              throw new Error(`${profileName} does not support formatting`);
+            }
+            edits = await scope.request<
+              TextEdit[] | null,
+              DocumentFormattingParams
+            >('textDocument/formatting', {
+              textDocument: { uri: scope.uri },
+              options,
+            });
+          }
+
+          const changes: RenameResult = {};
+          if (edits && edits.length > 0) {
+            addWorkspaceEditChanges(
+              { changes: { [scope.uri]: edits } },
+              changes
+            );
+          }
+          return changes;
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Formatting failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
+
+function rangesOverlap(a: Range, b: Range): boolean {
+  const before = (x: Position, y: Position) =>
+    x.line < y.line || (x.line === y.line && x.character < y.character);
+  return before(a.start, b.end) && before(b.start, a.end);
+}
+
+/**
+ * Positions just after each character in `lines` that the server formats on
+ * when typed: a trigger ending a line, or a newline inside the range
+ */
+function findOnTypeTriggers(
+  ranges: Range[],
+  lines: string[],
+  triggers: string[]
+): Array<{ position: Position; ch: string }> {
+  const found: Array<{ position: Position; ch: string }> = [];
+  for (const range of ranges) {
+    for (let line = range.start.line; line <= range.end.line; line++) {
+      const text = (lines[line] ?? '').trimEnd();
+      const last = text.at(-1);
+      if (last && triggers.includes(last)) {
+        found.push({ position: { line, character: text.length }, ch: last });
+      }
+      const next = lines[line + 1];
+      if (triggers.includes('\n') && line < range.end.line && next) {
+        const indent = /^\s*/.exec(next)![0].length;
+        found.push({
+          position: { line: line + 1, character: indent },
+          ch: '\n',
+        });
+      }
+    }
+  }
+  return found;
+}
+
+/**
+ * Formatting edits confined to freshly edited ranges of a file, so inserted
+ * code follows the project's style without touching the lines around it.
+ * Uses range formatting, falling back to on-type formatting at the trigger
+ * characters in the ranges. All requests see the same content, so edits
+ * overlapping one already kept are dropped.
+ */
+export async function formatEditedRanges(
+  session: LspSession,
+  prepared: PreparedFileRequest,
+  options: FormattingOptions,
+  ranges: Range[],
+  lines: string[]
+): Promise<Result<RenameResult>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<RenameResult>> => {
+      return await tryResultAsync(
+        async () => {
+          const capabilities = scope.serverCapabilities;
+          const responses: Array<TextEdit[] | null> = [];
+          if (capabilities?.documentRangeFormattingProvider) {
+            for (const range of ranges) {
+              responses.push(
+                await scope.request<
+                  TextEdit[] | null,
+                  DocumentRangeFormattingParams
+                >('textDocument/rangeFormatting', {
+                  textDocument: { uri: scope.uri },
+                  range,
+                  options,
+                })
+              );
+            }
+          } else if (capabilities?.documentOnTypeFormattingProvider) {
+            const provider = capabilities.documentOnTypeFormattingProvider;
+            const triggers = [
+              provider.firstTriggerCharacter,
+              ...(provider.moreTriggerCharacter ?? []),
+            ];
+            for (const trigger of findOnTypeTriggers(ranges, lines, triggers)) {
+              responses.push(
+                await scope.request<
+                  TextEdit[] | null,
+                  DocumentOnTypeFormattingParams
+                >('textDocument/onTypeFormatting', {
+                  textDocument: { uri: scope.uri },
+                  ...trigger,
+                  options,
+                })
+              );
+            }
+          } else {
+            throw new Error(
+              `${session.getProfile().name} does not support range or on-type formatting`
+            );
+          }
+
+          const kept: TextEdit[] = [];
+          for (const edit of responses.flatMap((edits) => edits ?? [])) {
+            if (!kept.some((other) => rangesOverlap(other.range, edit.range))) {
+              kept.push(edit);
+            }
+          }
+
+          const changes: RenameResult = {};
+          if (kept.length > 0) {
+            addWorkspaceEditChanges(
+              { changes: { [scope.uri]: kept } },
+              changes
+            );
+          }
+          return changes;
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Formatting edited ranges failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
+
+// Quick fixes are looked up for at most this many diagnostics per file
+const MAX_DIAGNOSTIC_FIXES = 10;
+
+function toDiagnosticEntry(diagnostic: Diagnostic): DiagnosticEntry {
+  return {
+    code: diagnostic.code !== undefined ? String(diagnostic.code) : 'unknown',
+    message: diagnostic.message,
+    severity: diagnostic.severity || 1, // Error by default
+    range: diagnostic.range,
+    source: diagnostic.source || 'unknown',
+    ...(diagnostic.codeDescription && {
+      codeDescription: diagnostic.codeDescription.href,
+    }),
+    ...(diagnostic.relatedInformation && {
+      relatedInformation: diagnostic.relatedInformation.map((related) => ({
+        uri: related.location.uri,
+        range: related.location.range,
+        message: related.message,
+      })),
+    }),
+  };
+}
+
+/**
+ * Titles of the quick fixes a server offers for a diagnostic
+ */
+async function getDiagnosticFixes(
+  request: SessionDocumentScope['request'],
+  uri: string,
+  diagnostic: Diagnostic
+): Promise<string[]> {
+  try {
+    const actions = await request<
+      (CodeAction | Command)[] | null,
+      CodeActionParams
+    >('textDocument/codeAction', {
+      textDocument: { uri },
+      range: diagnostic.range,
+      context: {
+        diagnostics: [diagnostic],
+        only: ['quickfix'],
+        triggerKind: CodeActionTriggerKind.Automatic,
+      },
+    });
+    return (actions ?? [])
+      .filter((action) => isCodeActionCommand(action) || !action.disabled)
+      .map((action) => action.title);
+  } catch (error) {
+    logger.debug('Failed to get quick fixes for diagnostic', {
+      uri,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return [];
+  }
+}
+
+export async function getDiagnostics(
+  session: LspSession,
+  prepared: PreparedFileRequest,
+  options: { fixes: boolean } = { fixes: false }
+): Promise<Result<DiagnosticEntry[]>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<DiagnosticEntry[]>> => {
+      return await tryResultAsync(
+        async () => {
+          const diagnostics = await getDocumentDiagnostics(
+            session,
+            async (method, params) => await scope.request(method, params),
+            scope.uri
+          );
+          const entries = diagnostics.map(toDiagnosticEntry);
+
+          if (options.fixes && scope.serverCapabilities?.codeActionProvider) {
+            // Most severe first, so the cap keeps the fixes that matter
+            const order = entries
+              .map((_, index) => index)
+              .sort(
+                (left, right) =>
+                  entries[left]!.severity - entries[right]!.severity
+              )
+              .slice(0, MAX_DIAGNOSTIC_FIXES);
+            for (const index of order) {
+              const fixes = await getDiagnosticFixes(
+                async (method, params) => await scope.request(method, params),
+                scope.uri,
+                diagnostics[index]!
+              );
+              if (fixes.length > 0) {
+                entries[index]!.fixes = fixes;
+              }
+            }
+          }
+
+          return entries;
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Get diagnostics failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
+
+/**
+ * Diagnostics for every file the server knows about, keyed by URI, from the
+ * providers that support `workspace/diagnostic`. Null when none of them do.
+ */
+export async function workspaceDiagnostics(
+  session: LspSession
+): Promise<Result<Map<string, DiagnosticEntry[]> | null>> {
+  const providers = session
+    .getDiagnosticProviderStore()
+    .getProviders()
+    .filter((provider) => provider.workspaceDiagnostics);
+  if (providers.length === 0) {
+    return { ok: true, data: null };
+  }
+
+  return await tryResultAsync(
+    async () => {
+      const diagnostics = new Map<string, DiagnosticEntry[]>();
+      for (const provider of providers) {
+        const report = await session.request<
+          WorkspaceDiagnosticReport,
+          WorkspaceDiagnosticParams
+        >('workspace/diagnostic', {
+          identifier: provider.id,
+          previousResultIds: [],
+        });
+
+        for (const item of report.items) {
+          if (item.kind !== 'full') {
+            continue;
+          }
+          const entries = diagnostics.get(item.uri) ?? [];
+          entries.push(
+            ...item.items.map((diagnostic) =>
+              toDiagnosticEntry(
+                diagnostic.source
+                  ? diagnostic
+                  : { ...diagnostic, source: provider.id }
+              )
+            )
+          );
+          diagnostics.set(item.uri, entries);
+        }
+      }
+      return diagnostics;
+    },
+    (error) =>
+      createLspError(
+        ErrorCode.LSPError,
+        `Workspace diagnostics failed: ${error instanceof Error ? error.message : String(error)}`,
+        error instanceof Error ? error : undefined
+      )
+  );
+}
+
+/**
+ * Run a command on the server while collecting the edits it sends back
+ * through workspace/applyEdit
+ */
+async function runCommand(
+  scope: SessionDocumentScope,
+  command: Command
+): Promise<{ result: unknown; edits: WorkspaceEdit[] }> {
+  if (!scope.collectWorkspaceEdits) {
+    throw new Error(`Cannot run command ${command.command} for this session`);
+  }
+  return await scope.collectWorkspaceEdits(
+    async () =>
+      await scope.request<unknown, ExecuteCommandParams>(
+        'workspace/executeCommand',
+        {
+          command: command.command,
+          ...(command.arguments && { arguments: command.arguments }),
+        }
+      )
+  );
+}
+
+// Resolving a lens is a request each, so stop after this many
+const MAX_RESOLVED_CODE_LENSES = 100;
+
+/**
+ * Code lenses in a document, resolved where the server fills in their
+ * commands lazily, in document order
+ */
+export async function codeLenses(
   session: LspSession,
   prepared: PreparedFileRequest
-): Promise<Result<DiagnosticEntry[]>> {
-  const diagnosticsStore = session.getDiagnosticsStore();
-  const diagnosticProviderStore = session.getDiagnosticProviderStore();
-  const profile = session.getProfile();
-  const lspName = profile.name;
-  const lspConfig = profile.config;
-  const filePath = prepared.filePath;
+): Promise<Result<CodeLensResult>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<CodeLensResult>> => {
+      return await tryResultAsync(
+        async () => {
+          const provider = scope.serverCapabilities?.codeLensProvider;
+          if (!provider) {
+            throw new Error(
+              `${session.getProfile().name} does not support code lenses`
+            );
+          }
 
-  // Determine diagnostic strategy from LSP configuration
-  const strategy = lspConfig?.diagnostics?.strategy || 'push';
+          const lenses =
+            (await scope.request<CodeLens[] | null, CodeLensParams>(
+              'textDocument/codeLens',
+              { textDocument: { uri: scope.uri } }
+            )) ?? [];
+
+          let resolved = 0;
+          const results: CodeLens[] = [];
+          for (const lens of lenses) {
+            if (
+              lens.command ||
+              !provider.resolveProvider ||
+              resolved >= MAX_RESOLVED_CODE_LENSES
+            ) {
+              results.push(lens);
+              continue;
+            }
+            resolved++;
+            results.push(
+              await scope.request<CodeLens, CodeLens>('codeLens/resolve', lens)
+            );
+          }
 
-  logger.debug('Using diagnostic strategy', { strategy, lspName, filePath });
+          return {
+            lenses: results.sort(
+              (a, b) =>
+                a.range.start.line - b.range.start.line ||
+                a.range.start.character - b.range.start.character
+            ),
+            serverCommands:
+              scope.serverCapabilities?.executeCommandProvider?.commands ?? [],
+          };
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Code lens request failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
 
+/**
+ * Run a command through workspace/executeCommand and gather the edits it
+ * makes. Nothing is written to disk. Commands the server doesn't list are
+ * meant for editors, so they're rejected instead of sent.
+ */
+export async function executeCommand(
+  session: LspSession,
+  prepared: PreparedFileRequest,
+  command: Command
+): Promise<Result<CommandExecution>> {
   return await session.executeWithDocumentLifecycle(
-    filePath,
+    prepared.filePath,
     'transient',
-    async (scope): Promise<Result<DiagnosticEntry[]>> => {
+    async (scope): Promise<Result<CommandExecution>> => {
       return await tryResultAsync(
         async () => {
-          if (strategy === 'pull') {
-            return await getPullDiagnostics(
-              async (method, params) => await scope.request(method, params),
-              diagnosticProviderStore,
-              scope.uri
+          const commands =
+            scope.serverCapabilities?.executeCommandProvider?.commands ?? [];
+          if (!commands.includes(command.command)) {
+            throw new Error(
+              `${session.getProfile().name} does not support command ${command.command}; it is handled by editors`
             );
-          } else {
-            return await getPushDiagnostics(diagnosticsStore, scope.uri);
           }
+
+          const { result, edits } = await runCommand(scope, command);
+          const changes: RenameResult = {};
+          let skippedOperations = 0;
+          for (const edit of edits) {
+            skippedOperations += addWorkspaceEditChanges(edit, changes);
+          }
+
+          return {
+            result,
+            changes,
+            skippedOperations,
+            executedCommands: [command.command],
+          };
         },
         (error) =>
           createLspError(
             ErrorCode.LSPError,
-            `Get diagnostics failed: ${error instanceof Error ? error.message : String(error)}`,
+            `Execute command failed: ${error instanceof Error ? error.message : String(error)}`,
             error instanceof Error ? error : undefined
           )
       );
@@ -544,119 +2028,309 @@ export async function getDiagnostics(
   );
 }
 
-async function getPushDiagnostics(
-  diagnosticsStore: DiagnosticsStore,
-  uri: string
-): Promise<DiagnosticEntry[]> {
-  // Give LSP a moment to send diagnostics after opening the file
-  await new Promise((resolve) => setTimeout(resolve, 500));
-
-  // Get diagnostics from store (populated by push notifications)
-  const diagnostics = diagnosticsStore.getDiagnostics(uri);
-
-  // Convert to DiagnosticEntry format
-  const diagnosticEntries: DiagnosticEntry[] = diagnostics.map(
-    (diagnostic) => ({
-      code: diagnostic.code?.toString() || 'unknown',
-      message: diagnostic.message,
-      severity: diagnostic.severity || 1, // Error by default
-      range: diagnostic.range,
-      source: diagnostic.source || 'unknown',
+/**
+ * Commands the server registers for workspace/executeCommand
+ */
+export async function serverCommands(
+  session: LspSession,
+  prepared: PreparedFileRequest
+): Promise<Result<string[]>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<string[]>> => ({
+      ok: true,
+      data: [
+        ...(scope.serverCapabilities?.executeCommandProvider?.commands ?? []),
+      ].sort(),
     })
   );
-
-  return diagnosticEntries;
 }
 
-async function getPullDiagnostics(
-  request: SessionDocumentScope['request'],
-  diagnosticProviderStore: DiagnosticProviderStore,
-  uri: string
-): Promise<DiagnosticEntry[]> {
-  const allDiagnostics: DiagnosticEntry[] = [];
+/**
+ * Resolve a code action and gather the edits it makes: its own edit plus any
+ * the server sends back while its command runs. Nothing is written to disk.
+ */
+export async function applyCodeAction(
+  session: LspSession,
+  prepared: PreparedFileRequest,
+  action: CodeAction | Command
+): Promise<Result<CodeActionEdits>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<CodeActionEdits>> => {
+      return await tryResultAsync(
+        async () => {
+          let resolved: CodeAction | Command = action;
+          const provider = scope.serverCapabilities?.codeActionProvider;
+          if (
+            !isCodeActionCommand(action) &&
+            !action.edit &&
+            typeof provider === 'object' &&
+            provider.resolveProvider
+          ) {
+            resolved = await scope.request<CodeAction, CodeAction>(
+              'codeAction/resolve',
+              action
+            );
+          }
 
-  // Get all diagnostic providers that support this document
-  const providers: DiagnosticProvider[] =
-    diagnosticProviderStore.getProvidersForDocument(uri);
+          const command = isCodeActionCommand(resolved)
+            ? resolved
+            : resolved.command;
+          const edits: WorkspaceEdit[] =
+            !isCodeActionCommand(resolved) && resolved.edit
+              ? [resolved.edit]
+              : [];
 
-  logger.debug('Found diagnostic providers for document', {
-    uri,
-    providerCount: providers.length,
-    providerIds: providers.map((p) => p.id),
-  });
+          if (command) {
+            edits.push(...(await runCommand(scope, command)).edits);
+          }
+
+          const changes: RenameResult = {};
+          let skippedOperations = 0;
+          for (const edit of edits) {
+            skippedOperations += addWorkspaceEditChanges(edit, changes);
+          }
 
-  // Request diagnostics from each provider
+          return {
+            changes,
+            skippedOperations,
+            executedCommands: command ? [command.command] : [],
+          };
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Apply code action failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
+
+function rangesOverlap(left: Range, right: Range): boolean {
+  const before = (a: Range['start'], b: Range['start']) =>
+    a.line < b.line || (a.line === b.line && a.character < b.character);
+  return !before(left.end, right.start) && !before(right.end, left.start);
+}
+
+/**
+ * Diagnostics from each pull provider for a document, or null when none
+ * of them answered
+ */
+async function pullDocumentDiagnostics(
+  request: SessionDocumentScope['request'],
+  providers: DiagnosticProvider[],
+  uri: string
+): Promise<Diagnostic[] | null> {
+  let answered = false;
+  const diagnostics: Diagnostic[] = [];
   for (const provider of providers) {
     try {
-      logger.debug('Requesting diagnostics from provider', {
-        providerId: provider.id,
-        uri,
-      });
-
-      const params: DocumentDiagnosticParams = {
+      const report = await request<
+        { kind: string; items?: Diagnostic[] },
+        DocumentDiagnosticParams
+      >('textDocument/diagnostic', {
         textDocument: { uri },
         identifier: provider.id,
-        // previousResultId omitted for first request
-      };
-
-      const diagnosticReport = await request('textDocument/diagnostic', params);
-
-      // Handle different types of diagnostic reports
-      if ((diagnosticReport as { kind?: string }).kind === 'full') {
-        const fullReport = diagnosticReport as { items: unknown[] };
-
-        // Convert diagnostics to our format
-        const diagnosticEntries: DiagnosticEntry[] = fullReport.items.map(
-          (diagnostic: unknown) => {
-            const d = diagnostic as {
-              code?: { value: string | number } | string | number;
-              message: string;
-              severity?: number;
-              range: Range;
-              source?: string;
-            };
-            return {
-              code:
-                typeof d.code === 'object' && d.code && 'value' in d.code
-                  ? String(d.code.value)
-                  : d.code
-                    ? String(d.code)
-                    : 'unknown',
-              message: d.message,
-              severity: d.severity || 1, // Error by default
-              range: d.range,
-              source: d.source || provider.id,
-            };
-          }
+      });
+      answered = true;
+      if (report.kind === 'full') {
+        diagnostics.push(
+          ...(report.items ?? []).map((diagnostic) =>
+            diagnostic.source
+              ? diagnostic
+              : { ...diagnostic, source: provider.id }
+          )
         );
-
-        allDiagnostics.push(...diagnosticEntries);
-
-        logger.debug('Retrieved diagnostics from provider', {
-          providerId: provider.id,
-          diagnosticCount: diagnosticEntries.length,
-        });
-      } else {
-        logger.debug('Provider returned unchanged diagnostics', {
-          providerId: provider.id,
-        });
       }
     } catch (error) {
       logger.warn('Failed to get diagnostics from provider', {
         providerId: provider.id,
         error: error instanceof Error ? error.message : String(error),
       });
-      // Continue with other providers
     }
   }
+  return answered ? diagnostics : null;
+}
 
-  logger.info('Completed pull diagnostics request', {
-    uri,
-    totalDiagnostics: allDiagnostics.length,
-    providerCount: providers.length,
-  });
+/**
+ * Raw diagnostics for a document, including the data servers attach for
+ * their quick fixes. Pulled where the server supports it, otherwise read
+ * from what it published.
+ */
+async function getDocumentDiagnostics(
+  session: LspSession,
+  request: SessionDocumentScope['request'],
+  uri: string
+): Promise<Diagnostic[]> {
+  const strategy = session.getProfile().config?.diagnostics?.strategy;
+  const providers = session
+    .getDiagnosticProviderStore()
+    .getProvidersForDocument(uri);
+
+  const pulled = await pullDocumentDiagnostics(request, providers, uri);
+  if (pulled || strategy === 'pull') {
+    return pulled ?? [];
+  }
+
+  const diagnosticsStore = session.getDiagnosticsStore();
+  if (diagnosticsStore.getDiagnostics(uri).length === 0) {
+    // Give LSP a moment to send diagnostics after opening the file
+    await delay(500);
+  }
+  return diagnosticsStore.getDiagnostics(uri);
+}
+
+/**
+ * Quick fixes and refactorings available for a range, given the
+ * diagnostics that overlap it
+ */
+export async function codeActions(
+  session: LspSession,
+  prepared: PreparedSymbolPositionRequest,
+  range: Range,
+  only: string[] = []
+): Promise<Result<CursorContextOperationResult<(CodeAction | Command)[]>>> {
+  return await session.executeWithCursorContext(
+    'code actions',
+    prepared.filePath,
+    prepared.position,
+    'transient',
+    async (scope) => {
+      return await tryResultAsync(
+        async () => {
+          const diagnostics = await getDocumentDiagnostics(
+            session,
+            async (method, params) => await scope.request(method, params),
+            scope.uri
+          );
+
+          const params: CodeActionParams = {
+            textDocument: { uri: scope.uri },
+            range,
+            context: {
+              diagnostics: diagnostics.filter((diagnostic) =>
+                rangesOverlap(diagnostic.range, range)
+              ),
+              triggerKind: CodeActionTriggerKind.Invoked,
+              ...(only.length > 0 && { only }),
+            },
+          };
+
+          const actions: (CodeAction | Command)[] | null = await scope.request(
+            'textDocument/codeAction',
+            params
+          );
+          return actions ?? [];
+        },
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Code action request failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
 
-  return allDiagnostics;
+/**
+ * Add the text edits of a workspace edit to per-file changes with 1-based
+ * display positions. Returns how many file creations, renames and
+ * deletions were left out.
+ */
+function addWorkspaceEditChanges(
+  workspaceEdit: WorkspaceEdit,
+  changes: RenameResult
+): number {
+  let skippedOperations = 0;
+
+  const addEdits = (
+    fileUri: string,
+    edits: Array<{
+      range: Range;
+      newText: string;
+    }>
+  ) => {
+    const fileChanges = edits.map((edit) => ({
+      range: edit.range,
+      newText: edit.newText,
+      // Convert positions back to 1-based for user display
+      startLine: edit.range.start.line + 1,
+      startCharacter: edit.range.start.character + 1,
+      endLine: edit.range.end.line + 1,
+      endCharacter: edit.range.end.character + 1,
+    }));
+
+    changes[fileUri] = [...(changes[fileUri] || []), ...fileChanges];
+  };
+
+  if ('changes' in workspaceEdit && workspaceEdit.changes) {
+    for (const [fileUri, edits] of Object.entries(workspaceEdit.changes)) {
+      addEdits(fileUri, edits);
+    }
+  }
+
+  if (
+    'documentChanges' in workspaceEdit &&
+    Array.isArray(workspaceEdit.documentChanges)
+  ) {
+    for (const change of workspaceEdit.documentChanges) {
+      if (!change || typeof change !== 'object') {
+        continue;
+      }
+
+      if (!('textDocument' in change) || !('edits' in change)) {
+        skippedOperations++;
+        continue;
+      }
+
+      const fileUri = change.textDocument?.uri;
+      const edits = Array.isArray(change.edits) ? change.edits : [];
+      if (!fileUri || edits.length === 0) {
+        continue;
+      }
+
+      addEdits(fileUri, edits);
+    }
+  }
+
+  return skippedOperations;
+}
+
+/**
+ * Ask the server whether the symbol at a position can be renamed, so an
+ * invalid target fails before any edit is computed
+ */
+async function validateRenameTarget(
+  request: SessionDocumentScope['request'],
+  uri: string,
+  prepared: PreparedRenameRequest
+): Promise<void> {
+  const target = `The symbol at ${prepared.position.line}:${prepared.position.character}`;
+
+  let result: PrepareRenameResult | null;
+  try {
+    result = await request<PrepareRenameResult | null, PrepareRenameParams>(
+      'textDocument/prepareRename',
+      {
+        textDocument: { uri },
+        position: prepared.lspPosition,
+      }
+    );
+  } catch (error) {
+    throw new Error(
+      `${target} cannot be renamed: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+
+  if (!result) {
+    throw new Error(`${target} cannot be renamed`);
+  }
 }
 
 export async function rename(
@@ -671,6 +2345,18 @@ export async function rename(
     async (scope) => {
       return await tryResultAsync(
         async () => {
+          const renameProvider = scope.serverCapabilities?.renameProvider;
+          if (
+            typeof renameProvider === 'object' &&
+            renameProvider.prepareProvider
+          ) {
+            await validateRenameTarget(
+              async (method, params) => await scope.request(method, params),
+              scope.uri,
+              prepared
+            );
+          }
+
           const params: RenameParams = {
             textDocument: { uri: scope.uri },
             position: prepared.lspPosition,
@@ -688,57 +2374,7 @@ export async function rename(
 
           // Transform LSP WorkspaceEdit response to our format
           const changes: RenameResult = {};
-
-          const addEdits = (
-            fileUri: string,
-            edits: Array<{
-              range: Range;
-              newText: string;
-            }>
-          ) => {
-            const fileChanges = edits.map((edit) => ({
-              range: edit.range,
-              newText: edit.newText,
-              // Convert positions back to 1-based for user display
-              startLine: edit.range.start.line + 1,
-              startCharacter: edit.range.start.character + 1,
-              endLine: edit.range.end.line + 1,
-              endCharacter: edit.range.end.character + 1,
-            }));
-
-            changes[fileUri] = [...(changes[fileUri] || []), ...fileChanges];
-          };
-
-          if ('changes' in workspaceEdit && workspaceEdit.changes) {
-            for (const [fileUri, edits] of Object.entries(
-              workspaceEdit.changes
-            )) {
-              addEdits(fileUri, edits);
-            }
-          }
-
-          if (
-            'documentChanges' in workspaceEdit &&
-            Array.isArray(workspaceEdit.documentChanges)
-          ) {
-            for (const change of workspaceEdit.documentChanges) {
-              if (!change || typeof change !== 'object') {
-                continue;
-              }
-
-              if (!('textDocument' in change) || !('edits' in change)) {
-                continue;
-              }
-
-              const fileUri = change.textDocument?.uri;
-              const edits = Array.isArray(change.edits) ? change.edits : [];
-              if (!fileUri || edits.length === 0) {
-                continue;
-              }
-
-              addEdits(fileUri, edits);
-            }
-          }
+          addWorkspaceEditChanges(workspaceEdit, changes);
 
           if (Object.keys(changes).length === 0) {
             logger.info('Rename returned no file edits', {
@@ -766,6 +2402,110 @@ export async function rename(
   );
 }
 
+/**
+ * The header of a source file or the source of a header, through clangd's
+ * textDocument/switchSourceHeader extension. Null when there is none.
+ */
+export async function switchSourceHeader(
+  session: LspSession,
+  prepared: PreparedFileRequest
+): Promise<Result<string | null>> {
+  return await session.executeWithDocumentLifecycle(
+    prepared.filePath,
+    'transient',
+    async (scope): Promise<Result<string | null>> => {
+      return await tryResultAsync(
+        async () =>
+          await scope.request<string | null, TextDocumentIdentifier>(
+            'textDocument/switchSourceHeader',
+            { uri: scope.uri }
+          ),
+        (error) =>
+          createLspError(
+            ErrorCode.LSPError,
+            `Switch source/header failed: ${error instanceof Error ? error.message : String(error)}`,
+            error instanceof Error ? error : undefined
+          )
+      );
+    }
+  );
+}
+
+// Requests returning the source of documents a server addresses with a URI
+// scheme of its own, keyed by that scheme
+const VIRTUAL_DOCUMENT_REQUESTS: Record<
+  string,
+  // Read with a server command instead when `command` is set
+  { method: string; languageId: string; command?: string }
+> = {
+  // Classes inside dependency jars, from their source jar or decompiled
+  jdt: { method: 'java/classFileContents', languageId: 'java' },
+  // Sources inside dependency jars and decoded class or TASTy files
+  jar: {
+    method: 'workspace/executeCommand',
+    command: 'metals.file-decode',
+    languageId: 'scala',
+  },
+  metalsDecode: {
+    method: 'workspace/executeCommand',
+    command: 'metals.file-decode',
+    languageId: 'scala',
+  },
+};
+
+/**
+ * Language of the servers that can read a virtual document URI, or null when
+ * no known request reads its scheme
+ */
+export function getVirtualDocumentLanguage(uri: string): string | null {
+  const scheme = uri.slice(0, Math.max(uri.indexOf(':'), 0));
+  return VIRTUAL_DOCUMENT_REQUESTS[scheme]?.languageId ?? null;
+}
+
+/**
+ * Source of a document that isn't a file on disk, such as a jdt:// class
+ * or a jar: source inside a dependency
+ */
+export async function virtualDocumentContents(
+  session: LspSession,
+  uri: string
+): Promise<Result<string>> {
+  return await tryResultAsync(
+    async () => {
+      const scheme = uri.slice(0, Math.max(uri.indexOf(':'), 0));
+      const request = VIRTUAL_DOCUMENT_REQUESTS[scheme];
+      if (!request) {
+        throw new Error(`${scheme || uri} URIs can't be read`);
+      }
+
+      const response = await session.request<unknown>(
+        request.method,
+        request.command
+          ? { command: request.command, arguments: [uri] }
+          : { uri }
+      );
+      // Server commands answer with the content and any error in an object
+      const decoded = (response ?? {}) as { value?: unknown; error?: unknown };
+      const content = typeof response === 'string' ? response : decoded.value;
+      if (typeof decoded.error === 'string' && decoded.error) {
+        throw new Error(decoded.error);
+      }
+      if (typeof content !== 'string') {
+        throw new Error(
+          `${session.getProfile().name} returned no contents for ${uri}`
+        );
+      }
+      return content;
+    },
+    (error) =>
+      createLspError(
+        ErrorCode.LSPError,
+        `Reading virtual document failed: ${error instanceof Error ? error.message : String(error)}`,
+        error instanceof Error ? error : undefined
+      )
+  );
+}
+
 export function logs(session: LspSession): Result<LogMessageResult[]> {
   const windowLogStore = session.getWindowLogStore();
 
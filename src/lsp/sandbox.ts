@@ -0,0 +1,282 @@
+/**
+ * Sandbox - run language servers with file system access limited to the
+ * workspace, caches, their own install and the system's executables and
+ * libraries, and optionally no network.
+ *
+ * Linux uses bubblewrap (`bwrap`) and macOS `sandbox-exec`. Other
+ * platforms, Windows included, can't be sandboxed, so servers fail to start
+ * rather than run unconfined when the sandbox is enabled.
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import which from 'which';
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+import { CodedError } from '../utils/error-codes.js';
+
+export interface SandboxSettings {
+  enabled: boolean;
+  // Allow servers to reach the network, e.g. to download dependencies
+  network: boolean;
+  // Extra paths servers may read, such as toolchains under the home directory
+  readable_paths: string[];
+  // Extra paths servers may write, beyond the workspace and caches
+  writable_paths: string[];
+}
+
+export interface SandboxCommand {
+  commandName: string;
+  commandArgs: string[];
+}
+
+export interface SandboxContext {
+  workspace: string;
+  home: string;
+  // Resolved server executable, when found in PATH
+  executable: string | null;
+  env: NodeJS.ProcessEnv;
+}
+
+const DISABLED: SandboxSettings = {
+  enabled: false,
+  network: true,
+  readable_paths: [],
+  writable_paths: [],
+};
+
+// System directories servers need to run at all: the dynamic loader,
+// shared libraries, interpreters and the few files under /etc that name
+// resolution, TLS and locale lookups read
+const LINUX_SYSTEM_PATHS = [
+  '/usr',
+  '/bin',
+  '/sbin',
+  '/lib',
+  '/lib32',
+  '/lib64',
+  '/opt',
+  '/nix/store',
+  '/etc/alternatives',
+  '/etc/ld.so.cache',
+  '/etc/ld.so.conf',
+  '/etc/ld.so.conf.d',
+  '/etc/ssl',
+  '/etc/ca-certificates',
+  '/etc/pki',
+  '/etc/resolv.conf',
+  '/etc/hosts',
+  '/etc/nsswitch.conf',
+  '/etc/passwd',
+  '/etc/group',
+  '/etc/localtime',
+];
+
+const MACOS_SYSTEM_PATHS = [
+  '/usr',
+  '/bin',
+  '/sbin',
+  '/System',
+  '/Library',
+  '/opt/homebrew',
+  '/private/etc',
+  '/private/var/db/timezone',
+  '/dev',
+];
+
+const getState = defineWorkspaceState(() => ({
+  settings: DISABLED,
+  workspacePath: process.cwd(),
+}));
+
+/**
+ * Replace the sandbox settings, e.g. after the config file is reloaded
+ */
+export function configureSandbox(update: {
+  workspace: string;
+  sandbox: SandboxSettings | null;
+}): void {
+  const state = getState();
+  state.workspacePath = update.workspace;
+  state.settings = update.sandbox ?? DISABLED;
+}
+
+function expandHome(value: string, context: SandboxContext): string {
+  return value === '~' || value.startsWith('~/')
+    ? path.join(context.home, value.slice(1))
+    : path.resolve(context.workspace, value);
+}
+
+/**
+ * Directories a server needs beyond the workspace: the cache directory and
+ * the install prefix of its executable, e.g. `~/.nvm/versions/node/v22`
+ * for `~/.nvm/versions/node/v22/bin/pyright-langserver`
+ */
+function getDefaultPaths(context: SandboxContext): {
+  readable: string[];
+  writable: string[];
+} {
+  const cache =
+    context.env.XDG_CACHE_HOME || path.join(context.home, '.cache');
+  return {
+    readable: context.executable
+      ? [path.dirname(path.dirname(context.executable))]
+      : [],
+    writable: [context.workspace, cache],
+  };
+}
+
+/**
+ * bubblewrap arguments: an empty root with the system directories bound
+ * read-only, then the server's install and allowed paths bound in
+ */
+export function buildBwrapArgs(
+  command: SandboxCommand,
+  context: SandboxContext,
+  sandbox: SandboxSettings
+): string[] {
+  const defaults = getDefaultPaths(context);
+  const readable = [
+    ...defaults.readable,
+    ...sandbox.readable_paths.map((entry) => expandHome(entry, context)),
+  ];
+  const writable = [
+    ...defaults.writable,
+    ...sandbox.writable_paths.map((entry) => expandHome(entry, context)),
+  ];
+
+  return [
+    ...LINUX_SYSTEM_PATHS.flatMap((entry) => ['--ro-bind-try', entry, entry]),
+    '--dev',
+    '/dev',
+    '--proc',
+    '/proc',
+    '--tmpfs',
+    '/tmp',
+    ...readable.flatMap((entry) => ['--ro-bind-try', entry, entry]),
+    ...writable.flatMap((entry) => ['--bind-try', entry, entry]),
+    ...(sandbox.network ? [] : ['--unshare-net']),
+    '--die-with-parent',
+    '--chdir',
+    context.workspace,
+    '--',
+    command.commandName,
+    ...command.commandArgs,
+  ];
+}
+
+function quoteSbpl(value: string): string {
+  return `"${value.replace(/\\/g, '\\\\').replace(/"/g, '\\"')}"`;
+}
+
+// sandbox-exec matches resolved paths, so `/var/folders/...` temporary
+// directories must be given as `/private/var/folders/...`
+function resolveProfilePath(entry: string): string {
+  try {
+    return fs.realpathSync(entry);
+  } catch {
+    return entry;
+  }
+}
+
+/**
+ * sandbox-exec profile: everything denied, then running processes, reading
+ * the system directories, the server's install and allowed paths, and
+ * writing allowed paths granted
+ */
+export function buildSandboxProfile(
+  context: SandboxContext,
+  sandbox: SandboxSettings
+): string {
+  const defaults = getDefaultPaths(context);
+  const readable = [
+    ...MACOS_SYSTEM_PATHS,
+    ...defaults.readable,
+    ...sandbox.readable_paths.map((entry) => expandHome(entry, context)),
+  ].map(resolveProfilePath);
+  const writable = [
+    ...defaults.writable,
+    ...sandbox.writable_paths.map((entry) => expandHome(entry, context)),
+    os.tmpdir(),
+  ].map(resolveProfilePath);
+
+  return [
+    '(version 1)',
+    '(deny default)',
+    '(allow process-exec process-fork)',
+    '(allow signal (target same-sandbox))',
+    '(allow sysctl-read mach-lookup ipc-posix-shm)',
+    // Path lookups stat every parent directory of the files they open
+    '(allow file-read-metadata)',
+    '(allow file-read* (literal "/"))',
+    '(allow file-write* (literal "/dev/null") (literal "/dev/tty"))',
+    ...readable.map(
+      (entry) => `(allow file-read* (subpath ${quoteSbpl(entry)}))`
+    ),
+    ...writable.map(
+      (entry) => `(allow file-read* file-write* (subpath ${quoteSbpl(entry)}))`
+    ),
+    ...(sandbox.network ? ['(allow network* system-socket)'] : []),
+  ].join('\n');
+}
+
+function findExecutable(name: string, env: NodeJS.ProcessEnv): string | null {
+  if (path.isAbsolute(name)) {
+    return name;
+  }
+  return which.sync(name, { path: env.PATH, nothrow: true });
+}
+
+/**
+ * Command that runs the server inside the sandbox, or the command itself
+ * when sandboxing is off
+ */
+export function wrapSandboxCommand(
+  command: SandboxCommand,
+  env: NodeJS.ProcessEnv
+): SandboxCommand {
+  const { settings, workspacePath } = getState();
+  if (!settings.enabled) {
+    return command;
+  }
+
+  const context: SandboxContext = {
+    workspace: workspacePath,
+    home: os.homedir(),
+    executable: findExecutable(command.commandName, env),
+    env,
+  };
+
+  if (process.platform === 'linux') {
+    const bwrap = which.sync('bwrap', { path: env.PATH, nothrow: true });
+    if (!bwrap) {
+      throw new CodedError(
+        'Sandbox is enabled but bubblewrap (bwrap) is not installed',
+        'SANDBOX_UNAVAILABLE',
+        'spawn'
+      );
+    }
+    return {
+      commandName: bwrap,
+      commandArgs: buildBwrapArgs(command, context, settings),
+    };
+  }
+
+  if (process.platform === 'darwin') {
+    return {
+      commandName: '/usr/bin/sandbox-exec',
+      commandArgs: [
+        '-p',
+        buildSandboxProfile(context, settings),
+        command.commandName,
+        ...command.commandArgs,
+      ],
+    };
+  }
+
+  throw new CodedError(
+    `Sandbox is enabled but not supported on ${process.platform}`,
+    'SANDBOX_UNAVAILABLE',
+    'spawn'
+  );
+}
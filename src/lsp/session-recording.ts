@@ -0,0 +1,429 @@
+/**
+ * Session recording - capture the JSON-RPC traffic of a language server
+ * session to a JSONL file and serve it back in place of the real server
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import * as rpc from 'vscode-jsonrpc';
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+import logger from '../utils/logger.js';
+
+export const RECORDING_VERSION = 1;
+
+// instant answers right away, recorded waits as long as the real server did
+export const REPLAY_TIMINGS = ['instant', 'recorded'] as const;
+
+export type ReplayTiming = (typeof REPLAY_TIMINGS)[number];
+
+export interface SessionRecordingHeader {
+  version: number;
+  profile: string;
+  command: string;
+  workspaceUri: string;
+  recordedAt: string;
+}
+
+export interface RecordedMessage {
+  // Milliseconds since the session started
+  time: number;
+  from: 'client' | 'server';
+  message: rpc.Message;
+}
+
+export interface SessionRecording {
+  header: SessionRecordingHeader;
+  messages: RecordedMessage[];
+}
+
+interface SessionRecordingSettings {
+  // Directory each session is recorded into
+  record: string | null;
+  // Directory sessions are replayed from instead of starting servers
+  replay: string | null;
+  timing: ReplayTiming;
+}
+
+const getSettings = defineWorkspaceState<SessionRecordingSettings>(() => ({
+  record: null,
+  replay: null,
+  timing: 'instant',
+}));
+
+/**
+ * Update where sessions are recorded to or replayed from
+ */
+export function configureSessionRecording(
+  update: Partial<SessionRecordingSettings>
+): void {
+  const settings = getSettings();
+  if (update.record !== undefined) {
+    settings.record = update.record && path.resolve(update.record);
+  }
+  if (update.replay !== undefined) {
+    settings.replay = update.replay && path.resolve(update.replay);
+  }
+  if (update.timing) {
+    settings.timing = update.timing;
+  }
+}
+
+/**
+ * Recording file for a profile within a recording directory
+ */
+export function getRecordingPath(
+  directory: string,
+  profileName: string
+): string {
+  return path.join(directory, `${profileName.replace(/[^\w.-]/g, '_')}.jsonl`);
+}
+
+/**
+ * Command that replays the profile's recording, or null when sessions run
+ * against real servers
+ */
+export function getReplayCommand(
+  profileName: string
+): { commandName: string; commandArgs: string[] } | null {
+  const settings = getSettings();
+  if (!settings.replay) {
+    return null;
+  }
+
+  const recordingPath = getRecordingPath(settings.replay, profileName);
+  if (!fs.existsSync(recordingPath)) {
+    throw new Error(
+      `No session recording for profile '${profileName}': ${recordingPath}`
+    );
+  }
+  const entryPoint = process.argv[1];
+  if (!entryPoint) {
+    throw new Error('Cannot locate the symbols entry point to replay with');
+  }
+
+  // Re-run this CLI, keeping loaders such as tsx that live in execArgv
+  return {
+    commandName: process.execPath,
+    commandArgs: [
+      ...process.execArgv,
+      entryPoint,
+      'replay',
+      recordingPath,
+      '--timing',
+      settings.timing,
+    ],
+  };
+}
+
+export interface SessionRecorder {
+  wrapReader(reader: rpc.MessageReader): rpc.MessageReader;
+  wrapWriter(writer: rpc.MessageWriter): rpc.MessageWriter;
+}
+
+/**
+ * Start recording a session when a recording directory is configured.
+ * Each start overwrites the profile's previous recording.
+ */
+export function startSessionRecording(
+  header: Pick<SessionRecordingHeader, 'profile' | 'command' | 'workspaceUri'>
+): SessionRecorder | null {
+  const settings = getSettings();
+  if (!settings.record) {
+    return null;
+  }
+
+  const filePath = getRecordingPath(settings.record, header.profile);
+  fs.mkdirSync(path.dirname(filePath), { recursive: true });
+  const fullHeader: SessionRecordingHeader = {
+    version: RECORDING_VERSION,
+    ...header,
+    recordedAt: new Date().toISOString(),
+  };
+  fs.writeFileSync(filePath, `${JSON.stringify(fullHeader)}\n`);
+  logger.info('Recording LSP session', { profile: header.profile, filePath });
+
+  const startedAt = Date.now();
+  const record = (from: RecordedMessage['from'], message: rpc.Message) => {
+    const entry: RecordedMessage = {
+      time: Date.now() - startedAt,
+      from,
+      message,
+    };
+    try {
+      fs.appendFileSync(filePath, `${JSON.stringify(entry)}\n`);
+    } catch (error) {
+      logger.warn('Failed to record LSP message', {
+        filePath,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+  };
+
+  return {
+    wrapReader: (reader) => ({
+      onError: reader.onError,
+      onClose: reader.onClose,
+      onPartialMessage: reader.onPartialMessage,
+      listen: (callback) =>
+        reader.listen((message) => {
+          record('server', message);
+          callback(message);
+        }),
+      dispose: () => reader.dispose(),
+    }),
+    wrapWriter: (writer) => ({
+      onError: writer.onError,
+      onClose: writer.onClose,
+      write: async (message) => {
+        record('client', message);
+        await writer.write(message);
+      },
+      end: () => writer.end(),
+      dispose: () => writer.dispose(),
+    }),
+  };
+}
+
+/**
+ * Read a recording written by startSessionRecording
+ */
+export function loadSessionRecording(filePath: string): SessionRecording {
+  const [headerLine, ...lines] = fs
+    .readFileSync(filePath, 'utf-8')
+    .split('\n')
+    .filter((line) => line.trim());
+  if (!headerLine) {
+    throw new Error(`Empty session recording: ${filePath}`);
+  }
+
+  const header = JSON.parse(headerLine) as SessionRecordingHeader;
+  if (header.version !== RECORDING_VERSION) {
+    throw new Error(
+      `Unsupported session recording version ${header.version}: ${filePath}`
+    );
+  }
+  return {
+    header,
+    messages: lines.map((line) => JSON.parse(line) as RecordedMessage),
+  };
+}
+
+export interface ReplayExchange {
+  method: string;
+  params: unknown;
+  time: number;
+  // Server messages to send back, in recorded order: the response plus
+  // notifications and requests that followed the client message
+  replies: RecordedMessage[];
+  used: boolean;
+}
+
+/**
+ * Group a recording into client messages and the server messages they led
+ * to. Responses follow their request by id; anything else the server sent
+ * belongs to the latest client message.
+ */
+export function buildReplayExchanges(
+  recording: SessionRecording
+): ReplayExchange[] {
+  const exchanges: ReplayExchange[] = [];
+  const byRequestId = new Map<number | string, ReplayExchange>();
+
+  for (const entry of recording.messages) {
+    const message = entry.message;
+    if (entry.from === 'client') {
+      // Answers to server requests need no reply
+      if (!('method' in message)) {
+        continue;
+      }
+      const exchange: ReplayExchange = {
+        method: message.method,
+        params: message.params,
+        time: entry.time,
+        replies: [],
+        used: false,
+      };
+      exchanges.push(exchange);
+      if (rpc.Message.isRequest(message) && message.id !== null) {
+        byRequestId.set(message.id, exchange);
+      }
+      continue;
+    }
+
+    const owner = rpc.Message.isResponse(message)
+      ? message.id !== null
+        ? byRequestId.get(message.id)
+        : undefined
+      : exchanges.at(-1);
+    owner?.replies.push(entry);
+  }
+
+  return exchanges;
+}
+
+/**
+ * Pick the recorded exchange for a live client message: an unused one with
+ * the same params, then a used one with the same params, then any unused
+ * one for the method
+ */
+export function takeReplayExchange(
+  exchanges: ReplayExchange[],
+  method: string,
+  params: unknown,
+  rebase: (value: unknown) => unknown = (value) => value
+): ReplayExchange | null {
+  const key = JSON.stringify(params);
+  const candidates = exchanges.filter(
+    (exchange) => exchange.method === method
+  );
+  const sameParams = candidates.filter(
+    (exchange) => JSON.stringify(rebase(exchange.params)) === key
+  );
+  const exchange =
+    sameParams.find((candidate) => !candidate.used) ??
+    sameParams.at(-1) ??
+    candidates.find((candidate) => !candidate.used) ??
+    null;
+  if (exchange) {
+    exchange.used = true;
+  }
+  return exchange;
+}
+
+/**
+ * Answer client messages from a recording until the client sends `exit`
+ * or closes the stream. Resolves with the exit code for the process.
+ */
+export async function serveSessionRecording(
+  recording: SessionRecording,
+  reader: rpc.MessageReader,
+  writer: rpc.MessageWriter,
+  timing: ReplayTiming
+): Promise<number> {
+  const exchanges = buildReplayExchanges(recording);
+  const recordedRoot = recording.header.workspaceUri;
+  let liveRoot = recordedRoot;
+
+  // Recordings made in another checkout point at that checkout's files
+  const rebase = (value: unknown): unknown =>
+    liveRoot === recordedRoot || value === undefined
+      ? value
+      : JSON.parse(JSON.stringify(value).replaceAll(recordedRoot, liveRoot));
+
+  const send = async (message: rpc.Message, delay: number) => {
+    if (timing === 'recorded' && delay > 0) {
+      await new Promise((resolve) => setTimeout(resolve, delay));
+    }
+    await writer.write(rebase(message) as rpc.Message);
+  };
+
+  const reply = async (
+    message: rpc.RequestMessage | rpc.NotificationMessage
+  ) => {
+    const exchange = takeReplayExchange(
+      exchanges,
+      message.method,
+      message.params,
+      rebase
+    );
+    const isRequest = rpc.Message.isRequest(message);
+
+    if (!exchange) {
+      logger.warn('No recorded exchange for LSP message', {
+        method: message.method,
+      });
+      if (isRequest) {
+        await writer.write({
+          jsonrpc: '2.0',
+          id: message.id,
+          ...(message.method === 'shutdown'
+            ? { result: null }
+            : {
+                error: {
+                  code: rpc.ErrorCodes.MethodNotFound,
+                  message: `No recorded response for ${message.method}`,
+                },
+              }),
+        } as rpc.ResponseMessage);
+      }
+      return;
+    }
+
+    let elapsed = 0;
+    for (const entry of exchange.replies) {
+      const delay = entry.time - exchange.time - elapsed;
+      elapsed += Math.max(0, delay);
+      const recorded = entry.message;
+      await send(
+        rpc.Message.isResponse(recorded) && isRequest
+          ? { ...recorded, id: message.id }
+          : recorded,
+        delay
+      );
+    }
+  };
+
+  return await new Promise<number>((resolve) => {
+    reader.onClose(() => resolve(0));
+    reader.onError((error) => {
+      logger.error('Replay connection error', {
+        error: error instanceof Error ? error.message : String(error),
+      });
+      resolve(1);
+    });
+
+    let queue = Promise.resolve();
+    reader.listen((message) => {
+      if (!rpc.Message.isRequest(message)) {
+        if (!rpc.Message.isNotification(message)) {
+          // Client answers to server requests
+          return;
+        }
+        if (message.method === 'exit') {
+          resolve(0);
+          return;
+        }
+      }
+      if (message.method === 'initialize') {
+        const rootUri = (message.params as { rootUri?: unknown } | undefined)
+          ?.rootUri;
+        if (typeof rootUri === 'string') {
+          liveRoot = rootUri;
+        }
+      }
+
+      // Keep replies in the order the client asked
+      queue = queue
+        .then(() => reply(message))
+        .catch((error: unknown) => {
+          logger.error('Failed to replay LSP message', {
+            method: message.method,
+            error: error instanceof Error ? error.message : String(error),
+          });
+        });
+    });
+  });
+}
+
+/**
+ * Serve a recording over stdin/stdout (replay command)
+ */
+export async function runReplayServer(
+  filePath: string,
+  timing: ReplayTiming
+): Promise<number> {
+  try {
+    const recording = loadSessionRecording(filePath);
+    return await serveSessionRecording(
+      recording,
+      new rpc.StreamMessageReader(process.stdin),
+      new rpc.StreamMessageWriter(process.stdout),
+      timing
+    );
+  } catch (error) {
+    process.stderr.write(
+      `Error replaying ${filePath}: ${error instanceof Error ? error.message : String(error)}\n`
+    );
+    return 1;
+  }
+}
@@ -0,0 +1,500 @@
+/**
+ * Analysis commands - start the configured language servers, print a
+ * workspace-wide report or the result of one lookup and shut down
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import * as readline from 'readline';
+import * as rpc from 'vscode-jsonrpc';
+import type {
+  ApiCheckArgs,
+  ApiCommandArgs,
+  ApiSnapshotArgs,
+  BatchCommandArgs,
+  CyclesCommandArgs,
+  DeadCodeCommandArgs,
+  DocCoverageCommandArgs,
+  ExportCommandArgs,
+  GatewayCommandArgs,
+  LookupCommandArgs,
+  MapCommandArgs,
+  QueryCommandArgs,
+  StartCommandArgs,
+  UsageCommandArgs,
+} from '../utils/cli.js';
+import { createLspManager, type LspManager } from '../runtime/lsp-manager.js';
+import {
+  buildArchitectureMap,
+  formatArchitectureMap,
+} from '../map/architecture-map.js';
+import { buildDeadCodeReport, formatDeadCodeReport } from '../map/dead-code.js';
+import {
+  checkApiCompatibility,
+  collectApiSurface,
+  formatApiCheckReport,
+  type ApiSnapshot,
+} from '../map/api-surface.js';
+import { buildUsageReport, formatUsageReport } from '../map/usage-report.js';
+import {
+  buildImportCycleReport,
+  formatImportCycleReport,
+} from '../map/import-cycles.js';
+import {
+  buildDocCoverageReport,
+  formatDocCoverageReport,
+  meetsDocCoverageThreshold,
+} from '../map/doc-coverage.js';
+import {
+  buildSymbolQueryReport,
+  formatSymbolQueryReport,
+} from '../map/workspace-symbols.js';
+import {
+  buildSymbolChunks,
+  formatSymbolChunks,
+} from '../map/symbol-chunks.js';
+import {
+  formatLookupReport,
+  runBatchQuery,
+  runLookup,
+} from '../map/symbol-lookup.js';
+import { toReportPath } from '../map/workspace-scan.js';
+import { serveLspGateway } from '../lsp/lsp-gateway.js';
+import { getFileChurn, withGitWorktree } from '../utils/git.js';
+import { highlightReport } from '../utils/highlight.js';
+import logger from '../utils/logger.js';
+
+type AnalysisCommandArgs = Omit<StartCommandArgs, 'command'>;
+
+interface AnalysisResult {
+  output: string;
+  exitCode?: number;
+}
+
+/**
+ * Configure a manager for `workspace` like `start` would, run `fn` with it
+ * and shut the language servers down afterwards
+ */
+async function withConfiguredManager<T>(
+  args: AnalysisCommandArgs,
+  workspace: string,
+  fn: (manager: LspManager) => Promise<T>
+): Promise<T> {
+  const manager = createLspManager();
+
+  try {
+    await manager.configureForStart({
+      command: 'start',
+      workspace,
+      ...(args.lsp && { lsp: args.lsp }),
+      ...(args.loglevel && { loglevel: args.loglevel }),
+      ...(args.configPath && { configPath: args.configPath }),
+      ...(args.paths && { paths: args.paths }),
+      console: Boolean(args.console),
+    });
+
+    const status = manager.getStatus();
+    if (status.profiles.length === 0) {
+      throw new Error(
+        status.issues[0] ?? 'No language server profiles are configured.'
+      );
+    }
+
+    return await fn(manager);
+  } finally {
+    await manager.shutdown().catch((error: unknown) => {
+      logger.debug('Language server shutdown failed after analysis', {
+        workspace,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    });
+  }
+}
+
+/**
+ * Run an analysis against the workspace and print its report. Returns the
+ * process exit code.
+ */
+async function runAnalysis(
+  args: AnalysisCommandArgs,
+  label: string,
+  analyze: (manager: LspManager, workspace: string) => Promise<AnalysisResult>
+): Promise<number> {
+  const workspace = path.resolve(args.workspace || process.cwd());
+
+  try {
+    const result = await withConfiguredManager(
+      args,
+      workspace,
+      async (manager) => await analyze(manager, workspace)
+    );
+    console.log(result.output);
+    return result.exitCode ?? 0;
+  } catch (error) {
+    console.error(
+      `Error building ${label}:`,
+      error instanceof Error ? error.message : String(error)
+    );
+    return 1;
+  }
+}
+
+/**
+ * Print an architecture map of the workspace (map command)
+ */
+export async function runMapCommand(args: MapCommandArgs): Promise<number> {
+  return await runAnalysis(
+    args,
+    'architecture map',
+    async (manager, workspace) => {
+      const map = await buildArchitectureMap(manager, {
+        workspace,
+        depth: args.depth,
+        maxFiles: args.maxFiles,
+      });
+      return {
+        output: highlightReport(
+          formatArchitectureMap(map, args.format),
+          args.format,
+          args.color ?? 'auto'
+        ),
+      };
+    }
+  );
+}
+
+/**
+ * Print symbols unreachable from the configured entry points (dead-code
+ * command)
+ */
+export async function runDeadCodeCommand(
+  args: DeadCodeCommandArgs
+): Promise<number> {
+  return await runAnalysis(
+    args,
+    'dead code report',
+    async (manager, workspace) => {
+      const report = await buildDeadCodeReport(manager, {
+        workspace,
+        maxFiles: args.maxFiles,
+        entryPoints: args.entryPoints,
+        entrySymbols: args.entrySymbols,
+      });
+      return { output: formatDeadCodeReport(report, args.format) };
+    }
+  );
+}
+
+/**
+ * Print reference counts, module coupling and hotspots (usage command)
+ */
+export async function runUsageCommand(
+  args: UsageCommandArgs
+): Promise<number> {
+  return await runAnalysis(args, 'usage report', async (manager, workspace) => {
+    const report = await buildUsageReport(manager, {
+      workspace,
+      depth: args.depth,
+      maxFiles: args.maxFiles,
+      maxSymbols: args.maxSymbols,
+      since: args.since,
+      churn: getFileChurn(workspace, args.since),
+    });
+    return { output: formatUsageReport(report, args.format, args.top) };
+  });
+}
+
+/**
+ * Print import cycles and fail when there are any (cycles command)
+ */
+export async function runCyclesCommand(
+  args: CyclesCommandArgs
+): Promise<number> {
+  return await runAnalysis(
+    args,
+    'import cycle report',
+    async (manager, workspace) => {
+      const report = await buildImportCycleReport(manager, {
+        workspace,
+        maxFiles: args.maxFiles,
+        ...(args.depth && { depth: args.depth }),
+      });
+      return {
+        output: formatImportCycleReport(report, args.format),
+        exitCode: report.cycles.length > 0 ? 1 : 0,
+      };
+    }
+  );
+}
+
+/**
+ * Print documentation coverage and fail when it is below `--fail-under`
+ * (doc-coverage command)
+ */
+export async function runDocCoverageCommand(
+  args: DocCoverageCommandArgs
+): Promise<number> {
+  return await runAnalysis(
+    args,
+    'documentation coverage report',
+    async (manager, workspace) => {
+      const report = await buildDocCoverageReport(manager, {
+        workspace,
+        depth: args.depth,
+        maxFiles: args.maxFiles,
+      });
+      const passed =
+        args.failUnder === undefined ||
+        meetsDocCoverageThreshold(report, args.failUnder);
+      return {
+        output: formatDocCoverageReport(report, args.format),
+        exitCode: passed ? 0 : 1,
+      };
+    }
+  );
+}
+
+/**
+ * List the symbols matching a filtered query (query command)
+ */
+export async function runQueryCommand(
+  args: QueryCommandArgs
+): Promise<number> {
+  return await runAnalysis(args, 'query results', async (manager) => {
+    const report = await buildSymbolQueryReport(
+      manager,
+      args.query,
+      args.limit
+    );
+    return { output: formatSymbolQueryReport(report, args.format) };
+  });
+}
+
+/**
+ * Run one find, refs, def or outline lookup and print its locations
+ */
+export async function runLookupCommand(
+  args: LookupCommandArgs
+): Promise<number> {
+  return await runAnalysis(args, `${args.command} results`, async (manager) => {
+    const report = await runLookup(
+      manager,
+      args.command,
+      args.target,
+      args.limit
+    );
+    return {
+      output: highlightReport(
+        formatLookupReport(report, args.format),
+        args.format,
+        args.color ?? 'auto'
+      ),
+    };
+  });
+}
+
+/**
+ * Answer NDJSON queries read from stdin with one NDJSON result each, in
+ * order, through a single set of language servers (batch command)
+ */
+export async function runBatchCommand(args: BatchCommandArgs): Promise<number> {
+  const workspace = path.resolve(args.workspace || process.cwd());
+
+  try {
+    await withConfiguredManager(args, workspace, async (manager) => {
+      const lines = readline.createInterface({
+        input: process.stdin,
+        crlfDelay: Infinity,
+      });
+      for await (const line of lines) {
+        if (line.trim() === '') {
+          continue;
+        }
+        const result = await runBatchQuery(manager, line, args.limit);
+        process.stdout.write(`${JSON.stringify(result)}\n`);
+      }
+    });
+    return 0;
+  } catch (error) {
+    console.error(
+      'Error running batch queries:',
+      error instanceof Error ? error.message : String(error)
+    );
+    return 1;
+  }
+}
+
+/**
+ * Serve LSP over stdio to an editor, routing each document to the
+ * language server configured for it (gateway command)
+ */
+export async function runGatewayCommand(
+  args: GatewayCommandArgs
+): Promise<number> {
+  const workspace = path.resolve(args.workspace || process.cwd());
+
+  try {
+    return await withConfiguredManager(
+      args,
+      workspace,
+      async (manager) =>
+        await serveLspGateway(
+          manager,
+          new rpc.StreamMessageReader(process.stdin),
+          new rpc.StreamMessageWriter(process.stdout)
+        )
+    );
+  } catch (error) {
+    console.error(
+      'Error running LSP gateway:',
+      error instanceof Error ? error.message : String(error)
+    );
+    return 1;
+  }
+}
+
+/**
+ * Emit one JSONL record per symbol (export chunks command)
+ */
+export async function runExportCommand(
+  args: ExportCommandArgs
+): Promise<number> {
+  const chunkArgs = args.subcommandArgs;
+  return await runAnalysis(
+    args,
+    'symbol chunks',
+    async (manager, workspace) => {
+      const { files, chunks, truncated } = await buildSymbolChunks(manager, {
+        workspace,
+        maxFiles: args.maxFiles,
+        maxChars: chunkArgs.maxChars,
+        includeTests: chunkArgs.includeTests,
+        docs: chunkArgs.docs,
+      });
+      if (truncated) {
+        logger.warn('File limit reached while exporting chunks', {
+          workspace,
+          files,
+        });
+      }
+
+      const jsonl = formatSymbolChunks(chunks);
+      if (!chunkArgs.output) {
+        return { output: jsonl };
+      }
+      fs.writeFileSync(chunkArgs.output, chunks.length > 0 ? `${jsonl}\n` : '');
+      const limit = truncated
+        ? ` (file limit reached: only the first ${files} file(s) were read)`
+        : '';
+      return {
+        output: `Wrote ${chunks.length} chunk(s) from ${files} file(s) to ${chunkArgs.output}${limit}`,
+      };
+    }
+  );
+}
+
+/**
+ * Record the public API surface (api snapshot command)
+ */
+async function runApiSnapshot(
+  args: ApiCommandArgs,
+  snapshotArgs: ApiSnapshotArgs
+): Promise<number> {
+  return await runAnalysis(args, 'API snapshot', async (manager, workspace) => {
+    const symbols = await collectApiSurface(manager, workspace, args.maxFiles);
+    const snapshot: ApiSnapshot = {
+      ...(snapshotArgs.release && { version: snapshotArgs.release }),
+      symbols: symbols.map((symbol) => ({
+        ...symbol,
+        file: toReportPath(workspace, symbol.file),
+      })),
+    };
+    const json = JSON.stringify(snapshot, null, 2);
+
+    if (!snapshotArgs.output) {
+      return { output: json };
+    }
+    fs.writeFileSync(snapshotArgs.output, `${json}\n`);
+    return {
+      output: `Wrote ${snapshot.symbols.length} public symbol(s) to ${snapshotArgs.output}`,
+    };
+  });
+}
+
+/**
+ * Load the baseline surface from a snapshot file, or scan a git revision
+ * checked out into a temporary worktree
+ */
+async function loadApiBaseline(
+  args: ApiCommandArgs,
+  checkArgs: ApiCheckArgs,
+  workspace: string
+): Promise<ApiSnapshot> {
+  const versionOverride = checkArgs.baselineVersion && {
+    version: checkArgs.baselineVersion,
+  };
+
+  if (fs.existsSync(checkArgs.baseline)) {
+    const snapshot = JSON.parse(
+      fs.readFileSync(checkArgs.baseline, 'utf-8')
+    ) as Partial<ApiSnapshot>;
+    if (!Array.isArray(snapshot.symbols)) {
+      throw new Error(`Not an API snapshot: ${checkArgs.baseline}`);
+    }
+    return {
+      ...(snapshot.version && { version: snapshot.version }),
+      ...versionOverride,
+      symbols: snapshot.symbols,
+    };
+  }
+
+  const symbols = await withGitWorktree(
+    workspace,
+    checkArgs.baseline,
+    async (baselineWorkspace) =>
+      await withConfiguredManager(
+        { ...args, workspace: baselineWorkspace },
+        baselineWorkspace,
+        async (manager) =>
+          await collectApiSurface(manager, baselineWorkspace, args.maxFiles)
+      )
+  );
+  // Tags like v1.2.3 double as the baseline version
+  const tagVersion = /^v?\d+\.\d+\.\d+/.test(checkArgs.baseline)
+    ? { version: checkArgs.baseline.replace(/^v/, '') }
+    : undefined;
+  return { ...tagVersion, ...versionOverride, symbols };
+}
+
+/**
+ * Compare the public API against a baseline and fail when the declared
+ * release bump is too small (api check command)
+ */
+async function runApiCheck(
+  args: ApiCommandArgs,
+  checkArgs: ApiCheckArgs
+): Promise<number> {
+  return await runAnalysis(args, 'API check', async (manager, workspace) => {
+    const current: ApiSnapshot = {
+      ...(checkArgs.release && { version: checkArgs.release }),
+      symbols: await collectApiSurface(manager, workspace, args.maxFiles),
+    };
+    const baseline = await loadApiBaseline(args, checkArgs, workspace);
+    const report = checkApiCompatibility(baseline, current, checkArgs.baseline);
+
+    return {
+      output: formatApiCheckReport(report, checkArgs.format),
+      exitCode: report.sufficient ? 0 : 1,
+    };
+  });
+}
+
+/**
+ * Route the api subcommands
+ */
+export async function runApiCommand(args: ApiCommandArgs): Promise<number> {
+  const subcommandArgs = args.subcommandArgs;
+  return subcommandArgs.subcommand === 'snapshot'
+    ? await runApiSnapshot(args, subcommandArgs)
+    : await runApiCheck(args, subcommandArgs);
+}
@@ -2,6 +2,8 @@ import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
 import type { LspManager } from '../runtime/lsp-manager.js';
 import { registerAllTools } from '../tools/index.js';
 import { registerLanguageServerResources } from '../resources/language-servers.js';
+import { registerWorkspaceFileResources } from '../resources/workspace-files.js';
+import { registerNavigationPrompts } from '../prompts/navigation.js';
 
 /**
  * Creates and configures an MCP server with all LSP tools registered
@@ -14,6 +16,8 @@ export function createServer(manager: LspManager): McpServer {
 
   registerAllTools(server, manager);
   registerLanguageServerResources(server, manager);
+  registerWorkspaceFileResources(server, manager);
+  registerNavigationPrompts(server);
 
   return server;
 }
@@ -0,0 +1,614 @@
+/**
+ * HTTP transport - serve MCP over streamable HTTP, WebSocket or the legacy
+ * SSE transport, so several clients share one long-lived set of language
+ * servers. Listens on a TCP port, or on a unix socket or Windows named pipe.
+ */
+
+import { randomUUID } from 'crypto';
+import * as fs from 'fs';
+import * as http from 'http';
+import * as https from 'https';
+import * as net from 'net';
+import type { Duplex } from 'stream';
+import type { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { SSEServerTransport } from '@modelcontextprotocol/sdk/server/sse.js';
+import { StreamableHTTPServerTransport } from '@modelcontextprotocol/sdk/server/streamableHttp.js';
+import type { AuthInfo } from '@modelcontextprotocol/sdk/server/auth/types.js';
+import { isInitializeRequest } from '@modelcontextprotocol/sdk/types.js';
+import {
+  assertHttpAuthForHost,
+  authenticateHttpRequest,
+  getHttpsServerOptions,
+  isLoopbackHost,
+} from '../tools/http-auth.js';
+import { forgetRateLimitClient } from '../tools/rate-limits.js';
+import { CodedError } from '../utils/error-codes.js';
+import logger from '../utils/logger.js';
+import {
+  acceptWebSocket,
+  WebSocketServerTransport,
+} from './websocket-transport.js';
+
+export const TRANSPORTS = ['stdio', 'http', 'sse', 'websocket'] as const;
+
+export type Transport = (typeof TRANSPORTS)[number];
+
+export const DEFAULT_HTTP_HOST = '127.0.0.1';
+export const DEFAULT_HTTP_PORT = 3000;
+
+// Path of the streamable HTTP endpoint; other paths are not found
+const MCP_PATH = '/mcp';
+
+// Paths of the SSE event stream and of the messages clients post back
+const SSE_PATH = '/sse';
+const SSE_MESSAGES_PATH = '/messages';
+
+// Path WebSocket clients upgrade their connection on
+const WEBSOCKET_PATH = '/ws';
+
+// Request bodies above this size are rejected without being parsed
+const MAX_BODY_BYTES = 4 * 1024 * 1024;
+
+// Sessions held at once; clients past it are refused until one closes
+export const DEFAULT_MAX_SESSIONS = 64;
+
+// Sessions that receive no message for this long are closed
+export const DEFAULT_SESSION_IDLE_TIMEOUT_MS = 30 * 60 * 1000;
+
+export interface HttpServerOptions {
+  transport: Exclude<Transport, 'stdio'>;
+  host: string;
+  // 0 picks a free port
+  port: number;
+  // Unix socket or Windows named pipe listened on instead of host and port
+  socket?: string | undefined;
+  // Browser origins allowed to open WebSocket sessions; defaults to pages
+  // served from the loopback address and port listened on
+  allowedOrigins?: string[] | undefined;
+  // Defaults to DEFAULT_MAX_SESSIONS
+  maxSessions?: number | undefined;
+  // Defaults to DEFAULT_SESSION_IDLE_TIMEOUT_MS
+  sessionIdleTimeoutMs?: number | undefined;
+}
+
+export interface HttpServerHandle {
+  // Endpoint clients connect to, e.g. http://127.0.0.1:3000/mcp,
+  // ws://127.0.0.1:3000/ws, or http+unix://%2Ftmp%2Fsymbols.sock/mcp for a
+  // socket
+  url: string;
+  // Closes every client session, then stops listening
+  close(): Promise<void>;
+}
+
+interface ClientSession {
+  server: McpServer;
+  transport:
+    | StreamableHTTPServerTransport
+    | SSEServerTransport
+    | WebSocketServerTransport;
+  // Authenticated client that opened the session, when auth is configured
+  clientId: string | undefined;
+  // Closes the session once it has been idle for the timeout
+  idleTimer?: NodeJS.Timeout | undefined;
+}
+
+type AuthenticatedRequest = http.IncomingMessage & { auth?: AuthInfo };
+
+/**
+ * Request rejected before it reaches a session, answered with a JSON-RPC
+ * error and the HTTP status
+ */
+class HttpRequestError extends Error {
+  constructor(
+    readonly status: number,
+    readonly rpcCode: number,
+    message: string
+  ) {
+    super(message);
+    this.name = 'HttpRequestError';
+  }
+}
+
+function sendJsonRpcError(
+  res: http.ServerResponse,
+  error: HttpRequestError
+): void {
+  res
+    .writeHead(error.status, {
+      'Content-Type': 'application/json',
+      ...(error.status === 401 && { 'WWW-Authenticate': 'Bearer' }),
+    })
+    .end(
+      JSON.stringify({
+        jsonrpc: '2.0',
+        error: { code: error.rpcCode, message: error.message },
+        id: null,
+      })
+    );
+}
+
+async function readJsonBody(req: http.IncomingMessage): Promise<unknown> {
+  const chunks: Buffer[] = [];
+  let size = 0;
+  for await (const chunk of req) {
+    const buffer = chunk as Buffer;
+    size += buffer.length;
+    if (size > MAX_BODY_BYTES) {
+      throw new HttpRequestError(413, -32600, 'Request body too large');
+    }
+    chunks.push(buffer);
+  }
+
+  try {
+    return JSON.parse(Buffer.concat(chunks).toString('utf8')) as unknown;
+  } catch {
+    throw new HttpRequestError(400, -32700, 'Parse error: invalid JSON');
+  }
+}
+
+/**
+ * Host headers accepted from browsers when bound to localhost, so a web
+ * page can't reach the server through DNS rebinding
+ */
+function getLoopbackHosts(port: number): string[] {
+  return [`localhost:${port}`, `127.0.0.1:${port}`, `[::1]:${port}`];
+}
+
+/**
+ * Origins of pages served from the loopback address the server listens on
+ */
+function getLoopbackOrigins(port: number): string[] {
+  return getLoopbackHosts(port).flatMap((host) => [
+    `http://${host}`,
+    `https://${host}`,
+  ]);
+}
+
+function isNamedPipe(socketPath: string): boolean {
+  return /^\\\\[.?]\\pipe\\/i.test(socketPath);
+}
+
+/**
+ * Remove a socket file left behind by a server that didn't shut down,
+ * refusing when a server still answers on it or the path isn't a socket
+ */
+async function removeStaleSocket(socketPath: string): Promise<void> {
+  if (isNamedPipe(socketPath) || !fs.existsSync(socketPath)) {
+    return;
+  }
+  if (!fs.statSync(socketPath).isSocket()) {
+    throw new Error(`${socketPath} exists and is not a socket`);
+  }
+
+  const inUse = await new Promise<boolean>((resolve) => {
+    const probe = net.connect(socketPath);
+    probe.once('connect', () => {
+      probe.destroy();
+      resolve(true);
+    });
+    probe.once('error', () => resolve(false));
+  });
+  if (inUse) {
+    throw new Error(`Another server is listening on ${socketPath}`);
+  }
+  fs.rmSync(socketPath);
+}
+
+/**
+ * Serve MCP over streamable HTTP, WebSocket, or SSE for clients that haven't
+ * moved to streamable HTTP. Each client session gets its own MCP server from
+ * `createMcpServer`, all sharing the same language servers. Refuses to
+ * listen beyond localhost unless authentication is configured, and serves
+ * HTTPS when mTLS is. A unix socket is only accessible to the current user.
+ * Sessions are capped in number and closed once idle, so clients that go
+ * away without closing theirs don't keep MCP servers alive.
+ */
+export async function startHttpServer(
+  createMcpServer: () => McpServer,
+  options: HttpServerOptions
+): Promise<HttpServerHandle> {
+  const socketPath = options.socket;
+  if (!socketPath) {
+    assertHttpAuthForHost(options.host);
+  }
+
+  const sessions = new Map<string, ClientSession>();
+  const maxSessions = options.maxSessions ?? DEFAULT_MAX_SESSIONS;
+  const idleTimeoutMs =
+    options.sessionIdleTimeoutMs ?? DEFAULT_SESSION_IDLE_TIMEOUT_MS;
+  let port = options.port;
+
+  // Browsers can't reach a socket, so only ports need the Host check
+  const getDnsRebindingOptions = () =>
+    !socketPath && isLoopbackHost(options.host)
+      ? {
+          enableDnsRebindingProtection: true,
+          allowedHosts: getLoopbackHosts(port),
+        }
+      : {};
+
+  const getPaths = () => {
+    if (options.transport === 'sse') {
+      return [SSE_PATH, SSE_MESSAGES_PATH];
+    }
+    return options.transport === 'websocket' ? [WEBSOCKET_PATH] : [MCP_PATH];
+  };
+
+  const authenticate = (req: AuthenticatedRequest) => {
+    try {
+      const authInfo = authenticateHttpRequest(req);
+      if (authInfo) {
+        req.auth = authInfo;
+      }
+    } catch (error) {
+      if (error instanceof CodedError && error.code === 'UNAUTHORIZED') {
+        throw new HttpRequestError(401, -32001, error.message);
+      }
+      throw error;
+    }
+  };
+
+  const closeSession = (sessionId: string, clientId: string | undefined) => {
+    clearTimeout(sessions.get(sessionId)?.idleTimer);
+    if (sessions.delete(sessionId)) {
+      logger.info('MCP client session closed', { sessionId, clientId });
+    }
+    // Authenticated clients share their budget across sessions
+    if (!clientId) {
+      forgetRateLimitClient(sessionId);
+    }
+  };
+
+  // Restart the session's idle timeout; called on every message it receives
+  const touchSession = (sessionId: string) => {
+    const session = sessions.get(sessionId);
+    if (!session) {
+      return;
+    }
+    clearTimeout(session.idleTimer);
+    session.idleTimer = setTimeout(() => {
+      logger.info('Closing idle MCP client session', {
+        sessionId,
+        clientId: session.clientId,
+        idleTimeoutMs,
+      });
+      void session.server
+        .close()
+        .finally(() => closeSession(sessionId, session.clientId));
+    }, idleTimeoutMs);
+    session.idleTimer.unref();
+  };
+
+  const addSession = (sessionId: string, session: ClientSession) => {
+    sessions.set(sessionId, session);
+    touchSession(sessionId);
+  };
+
+  const assertSessionCapacity = () => {
+    if (sessions.size >= maxSessions) {
+      throw new HttpRequestError(
+        503,
+        -32000,
+        `Too many open sessions (${maxSessions}); close one or retry later`
+      );
+    }
+  };
+
+  const getSession = (
+    sessionId: string,
+    req: AuthenticatedRequest
+  ): ClientSession => {
+    const session = sessions.get(sessionId);
+    if (!session) {
+      throw new HttpRequestError(404, -32001, 'Session not found');
+    }
+    if (session.clientId !== req.auth?.clientId) {
+      throw new HttpRequestError(
+        403,
+        -32001,
+        'Session was opened by another client'
+      );
+    }
+    touchSession(sessionId);
+    return session;
+  };
+
+  const openSession = async (
+    req: AuthenticatedRequest,
+    res: http.ServerResponse,
+    body: unknown
+  ): Promise<void> => {
+    assertSessionCapacity();
+    const clientId = req.auth?.clientId;
+    const server = createMcpServer();
+    const transport = new StreamableHTTPServerTransport({
+      sessionIdGenerator: () => randomUUID(),
+      onsessioninitialized: (sessionId) => {
+        addSession(sessionId, { server, transport, clientId });
+        logger.info('MCP client session opened', { sessionId, clientId });
+      },
+      ...getDnsRebindingOptions(),
+    });
+    // Set before connecting, which chains the server's own close handler
+    transport.onclose = () => {
+      if (transport.sessionId) {
+        closeSession(transport.sessionId, clientId);
+      }
+    };
+
+    await server.connect(transport);
+    await transport.handleRequest(req, res, body);
+  };
+
+  /**
+   * Legacy SSE transport: a GET opens the event stream, which tells the
+   * client where to post its messages, tagged with the session ID
+   */
+  const handleSseRequest = async (
+    req: AuthenticatedRequest,
+    res: http.ServerResponse,
+    url: URL
+  ): Promise<void> => {
+    if (url.pathname === SSE_PATH) {
+      if (req.method !== 'GET') {
+        throw new HttpRequestError(405, -32000, 'Open the stream with GET');
+      }
+      assertSessionCapacity();
+      const clientId = req.auth?.clientId;
+      const server = createMcpServer();
+      const transport = new SSEServerTransport(
+        SSE_MESSAGES_PATH,
+        res,
+        getDnsRebindingOptions()
+      );
+      addSession(transport.sessionId, { server, transport, clientId });
+      transport.onclose = () => closeSession(transport.sessionId, clientId);
+
+      await server.connect(transport);
+      logger.info('MCP client session opened', {
+        sessionId: transport.sessionId,
+        clientId,
+      });
+      return;
+    }
+
+    if (req.method !== 'POST') {
+      throw new HttpRequestError(405, -32000, 'Post messages with POST');
+    }
+    const sessionId = url.searchParams.get('sessionId');
+    if (!sessionId) {
+      throw new HttpRequestError(
+        400,
+        -32000,
+        'Missing sessionId query parameter'
+      );
+    }
+    const { transport } = getSession(sessionId, req);
+    if (!(transport instanceof SSEServerTransport)) {
+      throw new HttpRequestError(400, -32000, 'Not an SSE session');
+    }
+    await transport.handlePostMessage(req, res, await readJsonBody(req));
+  };
+
+  const handleRequest = async (
+    req: AuthenticatedRequest,
+    res: http.ServerResponse
+  ): Promise<void> => {
+    const url = new URL(req.url ?? '/', 'http://localhost');
+    if (!getPaths().includes(url.pathname)) {
+      throw new HttpRequestError(404, -32601, `Not found: ${url.pathname}`);
+    }
+
+    authenticate(req);
+
+    if (options.transport === 'sse') {
+      await handleSseRequest(req, res, url);
+      return;
+    }
+    if (options.transport === 'websocket') {
+      throw new HttpRequestError(
+        426,
+        -32000,
+        'Connect with a WebSocket upgrade request'
+      );
+    }
+
+    const sessionId = req.headers['mcp-session-id'];
+    const body = req.method === 'POST' ? await readJsonBody(req) : undefined;
+
+    if (typeof sessionId !== 'string') {
+      if (!isInitializeRequest(body)) {
+        throw new HttpRequestError(
+          400,
+          -32000,
+          'Missing mcp-session-id header; start a session with an initialize request'
+        );
+      }
+      await openSession(req, res, body);
+      return;
+    }
+
+    const { transport } = getSession(sessionId, req);
+    if (!(transport instanceof StreamableHTTPServerTransport)) {
+      throw new HttpRequestError(400, -32000, 'Not a streamable HTTP session');
+    }
+    await transport.handleRequest(req, res, body);
+  };
+
+  /**
+   * WebSocket transport: an upgrade request on the WebSocket path opens a
+   * session that lasts as long as the connection
+   */
+  const handleUpgrade = (
+    req: AuthenticatedRequest,
+    socket: Duplex,
+    head: Buffer
+  ): void => {
+    const url = new URL(req.url ?? '/', 'http://localhost');
+    if (options.transport !== 'websocket' || url.pathname !== WEBSOCKET_PATH) {
+      throw new HttpRequestError(404, -32601, `Not found: ${url.pathname}`);
+    }
+    authenticate(req);
+
+    // Upgrades bypass the SDK transports, so check the Host header here
+    const { allowedHosts } = getDnsRebindingOptions();
+    if (allowedHosts && !allowedHosts.includes(req.headers.host ?? '')) {
+      throw new HttpRequestError(
+        403,
+        -32000,
+        `Invalid Host header: ${req.headers.host}`
+      );
+    }
+    // Browsers send no preflight for WebSocket handshakes, so a page on any
+    // site could otherwise open a session; clients outside a browser send no
+    // Origin
+    const origin = req.headers.origin;
+    const allowedOrigins = options.allowedOrigins ?? getLoopbackOrigins(port);
+    if (origin !== undefined && !allowedOrigins.includes(origin)) {
+      throw new HttpRequestError(
+        403,
+        -32000,
+        `Invalid Origin header: ${origin}`
+      );
+    }
+    assertSessionCapacity();
+    if (!acceptWebSocket(req, socket)) {
+      throw new HttpRequestError(400, -32000, 'Invalid WebSocket handshake');
+    }
+
+    const clientId = req.auth?.clientId;
+    const server = createMcpServer();
+    const transport = new WebSocketServerTransport(
+      socket,
+      MAX_BODY_BYTES,
+      req.auth
+    );
+    addSession(transport.sessionId, { server, transport, clientId });
+    transport.onclose = () => closeSession(transport.sessionId, clientId);
+    if (head.length > 0) {
+      socket.unshift(head);
+    }
+
+    void server.connect(transport).then(() => {
+      // Messages arrive on the socket rather than as requests, so count
+      // them as activity here
+      const onmessage = transport.onmessage;
+      transport.onmessage = (message, extra) => {
+        touchSession(transport.sessionId);
+        onmessage?.(message, extra);
+      };
+      logger.info('MCP client session opened', {
+        sessionId: transport.sessionId,
+        clientId,
+      });
+    });
+  };
+
+  const upgradeListener = (
+    req: http.IncomingMessage,
+    socket: Duplex,
+    head: Buffer
+  ) => {
+    try {
+      handleUpgrade(req, socket, head);
+    } catch (error) {
+      const status = error instanceof HttpRequestError ? error.status : 500;
+      if (!(error instanceof HttpRequestError)) {
+        logger.error('Failed to upgrade MCP WebSocket request', {
+          url: req.url,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+      socket.end(
+        [
+          `HTTP/1.1 ${status} ${http.STATUS_CODES[status]}`,
+          ...(status === 401 ? ['WWW-Authenticate: Bearer'] : []),
+          'Connection: close',
+          '',
+          '',
+        ].join('\r\n')
+      );
+    }
+  };
+
+  const listener = (req: http.IncomingMessage, res: http.ServerResponse) => {
+    void handleRequest(req, res).catch((error: unknown) => {
+      if (res.headersSent) {
+        res.end();
+        return;
+      }
+      if (error instanceof HttpRequestError) {
+        sendJsonRpcError(res, error);
+        return;
+      }
+      logger.error('Failed to handle MCP HTTP request', {
+        method: req.method,
+        url: req.url,
+        error: error instanceof Error ? error.message : String(error),
+      });
+      sendJsonRpcError(
+        res,
+        new HttpRequestError(500, -32603, 'Internal server error')
+      );
+    });
+  };
+
+  const tlsOptions = getHttpsServerOptions();
+  const httpServer = tlsOptions
+    ? https.createServer(tlsOptions, listener)
+    : http.createServer(listener);
+  httpServer.on('upgrade', upgradeListener);
+
+  if (socketPath) {
+    await removeStaleSocket(socketPath);
+  }
+  await new Promise<void>((resolve, reject) => {
+    httpServer.once('error', reject);
+    const onListening = () => {
+      httpServer.off('error', reject);
+      resolve();
+    };
+    if (socketPath) {
+      httpServer.listen(socketPath, onListening);
+    } else {
+      httpServer.listen(options.port, options.host, onListening);
+    }
+  });
+
+  let scheme = tlsOptions ? 'https' : 'http';
+  if (options.transport === 'websocket') {
+    scheme = tlsOptions ? 'wss' : 'ws';
+  }
+  const endpoint = getPaths()[0]!;
+  let url: string;
+  if (socketPath) {
+    if (!isNamedPipe(socketPath)) {
+      fs.chmodSync(socketPath, 0o600);
+    }
+    url = `${scheme}+unix://${encodeURIComponent(socketPath)}${endpoint}`;
+  } else {
+    port = (httpServer.address() as net.AddressInfo).port;
+    const host =
+      options.host.includes(':') && !options.host.startsWith('[')
+        ? `[${options.host}]`
+        : options.host;
+    url = `${scheme}://${host}:${port}${endpoint}`;
+  }
+
+  return {
+    url,
+    async close() {
+      await Promise.allSettled(
+        [...sessions.values()].map(async ({ server, idleTimer }) => {
+          clearTimeout(idleTimer);
+          await server.close();
+        })
+      );
+      sessions.clear();
+      await new Promise<void>((resolve) => {
+        httpServer.close(() => resolve());
+        httpServer.closeAllConnections();
+      });
+    },
+  };
+}
@@ -4,14 +4,46 @@ import {
   handleConfigInit,
   handleConfigPath,
   handleConfigShow,
+  ApiCommandArgs,
+  BatchCommandArgs,
+  GatewayCommandArgs,
   ConfigCommandArgs,
+  CyclesCommandArgs,
+  DeadCodeCommandArgs,
+  DocCoverageCommandArgs,
+  ExportCommandArgs,
+  LookupCommandArgs,
+  MapCommandArgs,
+  QueryCommandArgs,
+  ReplayCommandArgs,
   RunCommandArgs,
   StartCommandArgs,
+  UsageCommandArgs,
+  resolveTransportConfig,
 } from '../utils/cli.js';
 import logger from '../utils/logger.js';
+import {
+  configureTracing,
+  resolveTracingSettings,
+} from '../utils/tracing.js';
 import { createServer } from './create-server.js';
 import { createLspManager } from '../runtime/lsp-manager.js';
 import { setupShutdown } from './shutdown.js';
+import { startHttpServer } from './http-transport.js';
+import { runReplayServer } from '../lsp/session-recording.js';
+import {
+  runApiCommand,
+  runBatchCommand,
+  runCyclesCommand,
+  runDeadCodeCommand,
+  runDocCoverageCommand,
+  runExportCommand,
+  runGatewayCommand,
+  runLookupCommand,
+  runMapCommand,
+  runQueryCommand,
+  runUsageCommand,
+} from './analysis-commands.js';
 
 /**
  * Main entry point - routes commands and starts appropriate handlers
@@ -36,6 +68,78 @@ export async function main(): Promise<void> {
     }
   }
 
+  if (cliArgs.command === 'replay') {
+    const replayArgs = cliArgs as ReplayCommandArgs;
+    const exitCode = await runReplayServer(
+      replayArgs.recording,
+      replayArgs.timing
+    );
+    process.exit(exitCode);
+  }
+
+  if (cliArgs.command === 'map') {
+    const exitCode = await runMapCommand(cliArgs as MapCommandArgs);
+    process.exit(exitCode);
+  }
+
+  if (cliArgs.command === 'dead-code') {
+    const exitCode = await runDeadCodeCommand(cliArgs as DeadCodeCommandArgs);
+    process.exit(exitCode);
+  }
+
+  if (cliArgs.command === 'usage') {
+    const exitCode = await runUsageCommand(cliArgs as UsageCommandArgs);
+    process.exit(exitCode);
+  }
+
+  if (cliArgs.command === 'cycles') {
+    const exitCode = await runCyclesCommand(cliArgs as CyclesCommandArgs);
+    process.exit(exitCode);
+  }
+
+  if (cliArgs.command === 'doc-coverage') {
+    const exitCode = await runDocCoverageCommand(
+      cliArgs as DocCoverageCommandArgs
+    );
+    process.exit(exitCode);
+  }
+
+  if (cliArgs.command === 'query') {
+    const exitCode = await runQueryCommand(cliArgs as QueryCommandArgs);
+    process.exit(exitCode);
+  }
+
+  if (
+    cliArgs.command === 'find' ||
+    cliArgs.command === 'refs' ||
+    cliArgs.command === 'def' ||
+    cliArgs.command === 'outline'
+  ) {
+    const exitCode = await runLookupCommand(cliArgs as LookupCommandArgs);
+    process.exit(exitCode);
+  }
+
+  if (cliArgs.command === 'batch') {
+    const exitCode = await runBatchCommand(cliArgs as BatchCommandArgs);
+    process.exit(exitCode);
+  }
+
+  if (cliArgs.command === 'gateway') {
+    const exitCode = await runGatewayCommand(cliArgs as GatewayCommandArgs);
+    process.exit(exitCode);
+  }
+
+  if (cliArgs.command === 'export') {
+    const exitCode = await runExportCommand(cliArgs as ExportCommandArgs);
+    process.exit(exitCode);
+  }
+
+  if (cliArgs.command === 'api') {
+    const exitCode = await runApiCommand(cliArgs as ApiCommandArgs);
+    process.exit(exitCode);
+  }
+
+  configureTracing(resolveTracingSettings(process.env));
   const manager = createLspManager();
 
   if (cliArgs.command === 'start') {
@@ -43,7 +147,9 @@ export async function main(): Promise<void> {
   } else if (cliArgs.command === 'run') {
     await manager.configureForRun(cliArgs as RunCommandArgs);
   } else {
-    console.error('Please specify a command: start, run, or config');
+    console.error(
+      'Please specify a command: start, run, map, dead-code, usage, cycles, doc-coverage, query, find, refs, def, outline, batch, gateway, api, export, replay, or config'
+    );
     console.error('Run "symbols --help" for usage information');
     process.exit(1);
   }
@@ -59,6 +165,28 @@ export async function main(): Promise<void> {
     issues: managerStatus.issues,
   });
 
+  // Other servers start on the first query for their files
+  void manager.preloadProfiles();
+
+  const transportConfig = resolveTransportConfig(
+    cliArgs as StartCommandArgs | RunCommandArgs
+  );
+  if (transportConfig.transport !== 'stdio') {
+    logger.debug('Starting MCP HTTP transport', {
+      transport: transportConfig.transport,
+    });
+    const httpServer = await startHttpServer(() => createServer(manager), {
+      ...transportConfig,
+      transport: transportConfig.transport,
+    });
+    setupShutdown(httpServer, manager);
+    logger.info('MCP server listening', { url: httpServer.url });
+    process.stderr.write(
+      `Symbols MCP server listening on ${httpServer.url}\n`
+    );
+    return;
+  }
+
   const server = createServer(manager);
   setupShutdown(server, manager);
 
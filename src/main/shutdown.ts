@@ -5,6 +5,8 @@
 import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
 import type { LspManager } from '../runtime/lsp-manager.js';
 import logger from '../utils/logger.js';
+import { flushSessionState } from '../runtime/session-state.js';
+import { flushTraces } from '../utils/tracing.js';
 
 const DEFAULT_TIMEOUT_MS = 5_000;
 
@@ -13,7 +15,8 @@ interface ShutdownOptions {
 }
 
 export function setupShutdown(
-  server: McpServer,
+  // The stdio MCP server, or the HTTP server with its client sessions
+  server: Pick<McpServer, 'close'>,
   manager: LspManager,
   options: ShutdownOptions = {}
 ): () => void {
@@ -37,6 +40,7 @@ export function setupShutdown(
         manager.shutdown(),
         new Promise<void>((resolve) => setTimeout(resolve, timeoutMs)),
       ]);
+      flushSessionState();
 
       await Promise.race([
         server.close().catch((error) => {
@@ -47,6 +51,11 @@ export function setupShutdown(
         new Promise<void>((resolve) => setTimeout(resolve, 1_000)),
       ]);
 
+      await Promise.race([
+        flushTraces(),
+        new Promise<void>((resolve) => setTimeout(resolve, 1_000)),
+      ]);
+
       process.exit(0);
     } catch (error) {
       logger.error('Error during graceful shutdown', {
@@ -0,0 +1,334 @@
+/**
+ * WebSocket transport - carry MCP JSON-RPC messages as WebSocket text
+ * frames, for browser-based and remote clients
+ */
+
+import { createHash, randomUUID } from 'crypto';
+import type * as http from 'http';
+import type { Duplex } from 'stream';
+import type { AuthInfo } from '@modelcontextprotocol/sdk/server/auth/types.js';
+import type { Transport } from '@modelcontextprotocol/sdk/shared/transport.js';
+import {
+  JSONRPCMessageSchema,
+  type JSONRPCMessage,
+  type MessageExtraInfo,
+} from '@modelcontextprotocol/sdk/types.js';
+
+// Appended to the client's key to prove the server speaks WebSocket
+const HANDSHAKE_GUID = '258EAFA5-E914-47DA-95CA-C5AB0DC85B11';
+
+// Subprotocol the MCP SDK's WebSocket client asks for
+const MCP_SUBPROTOCOL = 'mcp';
+
+const OPCODES = {
+  continuation: 0x0,
+  text: 0x1,
+  binary: 0x2,
+  close: 0x8,
+  ping: 0x9,
+  pong: 0xa,
+} as const;
+
+// Close codes from RFC 6455
+const CLOSE_NORMAL = 1000;
+const CLOSE_PROTOCOL_ERROR = 1002;
+const CLOSE_UNSUPPORTED_DATA = 1003;
+const CLOSE_TOO_BIG = 1009;
+
+interface Frame {
+  fin: boolean;
+  opcode: number;
+  payload: Buffer;
+  // Bytes the frame took up in the buffer
+  size: number;
+}
+
+/**
+ * Frame that breaks the protocol, answered by closing with `code`
+ */
+class FrameError extends Error {
+  constructor(
+    readonly code: number,
+    message: string
+  ) {
+    super(message);
+    this.name = 'FrameError';
+  }
+}
+
+/**
+ * Read the first frame in `buffer`, or null while it hasn't fully arrived.
+ * Client frames must be masked and carry no extension bits.
+ */
+function readFrame(buffer: Buffer, maxPayloadBytes: number): Frame | null {
+  if (buffer.length < 2) {
+    return null;
+  }
+  const first = buffer[0]!;
+  const second = buffer[1]!;
+  if ((first & 0x70) !== 0) {
+    throw new FrameError(CLOSE_PROTOCOL_ERROR, 'Unexpected extension bits');
+  }
+  if ((second & 0x80) === 0) {
+    throw new FrameError(CLOSE_PROTOCOL_ERROR, 'Client frames must be masked');
+  }
+
+  let offset = 2;
+  let payloadLength = second & 0x7f;
+  if (payloadLength === 126) {
+    if (buffer.length < 4) {
+      return null;
+    }
+    payloadLength = buffer.readUInt16BE(2);
+    offset = 4;
+  } else if (payloadLength === 127) {
+    if (buffer.length < 10) {
+      return null;
+    }
+    const longLength = buffer.readBigUInt64BE(2);
+    if (longLength > BigInt(maxPayloadBytes)) {
+      throw new FrameError(CLOSE_TOO_BIG, 'Message too large');
+    }
+    payloadLength = Number(longLength);
+    offset = 10;
+  }
+  if (payloadLength > maxPayloadBytes) {
+    throw new FrameError(CLOSE_TOO_BIG, 'Message too large');
+  }
+
+  const size = offset + 4 + payloadLength;
+  if (buffer.length < size) {
+    return null;
+  }
+  const mask = buffer.subarray(offset, offset + 4);
+  const payload = Buffer.from(buffer.subarray(offset + 4, size));
+  for (let index = 0; index < payload.length; index++) {
+    payload[index] = payload[index]! ^ mask[index % 4]!;
+  }
+
+  return { fin: (first & 0x80) !== 0, opcode: first & 0x0f, payload, size };
+}
+
+/**
+ * Unmasked, unfragmented frame as servers send them
+ */
+function encodeFrame(opcode: number, payload: Buffer): Buffer {
+  const length = payload.length;
+  let header: Buffer;
+  if (length < 126) {
+    header = Buffer.alloc(2);
+    header[1] = length;
+  } else if (length < 0x10000) {
+    header = Buffer.alloc(4);
+    header[1] = 126;
+    header.writeUInt16BE(length, 2);
+  } else {
+    header = Buffer.alloc(10);
+    header[1] = 127;
+    header.writeBigUInt64BE(BigInt(length), 2);
+  }
+  header[0] = 0x80 | opcode;
+  return Buffer.concat([header, payload]);
+}
+
+/**
+ * Complete the opening handshake of an upgrade request. Returns false,
+ * leaving the socket for the caller to reject, when the request isn't a
+ * WebSocket handshake.
+ */
+export function acceptWebSocket(
+  req: http.IncomingMessage,
+  socket: Duplex
+): boolean {
+  const key = req.headers['sec-websocket-key'];
+  if (
+    req.method !== 'GET' ||
+    req.headers.upgrade?.toLowerCase() !== 'websocket' ||
+    req.headers['sec-websocket-version'] !== '13' ||
+    typeof key !== 'string'
+  ) {
+    return false;
+  }
+
+  const accept = createHash('sha1')
+    .update(key + HANDSHAKE_GUID)
+    .digest('base64');
+  const protocols = (req.headers['sec-websocket-protocol'] ?? '')
+    .split(',')
+    .map((protocol) => protocol.trim());
+
+  socket.write(
+    [
+      'HTTP/1.1 101 Switching Protocols',
+      'Upgrade: websocket',
+      'Connection: Upgrade',
+      `Sec-WebSocket-Accept: ${accept}`,
+      ...(protocols.includes(MCP_SUBPROTOCOL)
+        ? [`Sec-WebSocket-Protocol: ${MCP_SUBPROTOCOL}`]
+        : []),
+      '',
+      '',
+    ].join('\r\n')
+  );
+  return true;
+}
+
+/**
+ * MCP transport over an accepted WebSocket connection, one JSON-RPC
+ * message per text message
+ */
+export class WebSocketServerTransport implements Transport {
+  readonly sessionId = randomUUID();
+  onclose?: () => void;
+  onerror?: (error: Error) => void;
+  onmessage?: (message: JSONRPCMessage, extra?: MessageExtraInfo) => void;
+
+  private buffer = Buffer.alloc(0);
+  // Frames of a fragmented message received so far
+  private fragments: Buffer[] = [];
+  private fragmentBytes = 0;
+  private closed = false;
+
+  constructor(
+    private readonly socket: Duplex,
+    private readonly maxMessageBytes: number,
+    // Client the upgrade request authenticated, passed along with messages
+    private readonly authInfo?: AuthInfo
+  ) {}
+
+  start(): Promise<void> {
+    this.socket.on('data', (chunk: Buffer) => this.receive(chunk));
+    this.socket.on('close', () => this.finish());
+    this.socket.on('error', (error) => this.onerror?.(error));
+    return Promise.resolve();
+  }
+
+  send(message: JSONRPCMessage): Promise<void> {
+    if (this.closed) {
+      return Promise.reject(new Error('WebSocket is closed'));
+    }
+    const frame = encodeFrame(
+      OPCODES.text,
+      Buffer.from(JSON.stringify(message), 'utf8')
+    );
+    return new Promise((resolve, reject) => {
+      this.socket.write(frame, (error) => (error ? reject(error) : resolve()));
+    });
+  }
+
+  close(): Promise<void> {
+    this.closeWith(CLOSE_NORMAL, '');
+    return Promise.resolve();
+  }
+
+  private receive(chunk: Buffer): void {
+    this.buffer = Buffer.concat([this.buffer, chunk]);
+    try {
+      while (!this.closed) {
+        const frame = readFrame(this.buffer, this.maxMessageBytes);
+        if (!frame) {
+          return;
+        }
+        this.buffer = this.buffer.subarray(frame.size);
+        this.handleFrame(frame);
+      }
+    } catch (error) {
+      if (error instanceof FrameError) {
+        this.closeWith(error.code, error.message);
+        return;
+      }
+      throw error;
+    }
+  }
+
+  private handleFrame(frame: Frame): void {
+    if (frame.opcode >= OPCODES.close) {
+      if (!frame.fin || frame.payload.length > 125) {
+        throw new FrameError(CLOSE_PROTOCOL_ERROR, 'Invalid control frame');
+      }
+      if (frame.opcode === OPCODES.close) {
+        // Echo the client's close code back
+        const code =
+          frame.payload.length >= 2
+            ? frame.payload.readUInt16BE(0)
+            : CLOSE_NORMAL;
+        this.closeWith(code, '');
+      } else if (frame.opcode === OPCODES.ping) {
+        this.socket.write(encodeFrame(OPCODES.pong, frame.payload));
+      } else if (frame.opcode !== OPCODES.pong) {
+        throw new FrameError(CLOSE_PROTOCOL_ERROR, 'Unknown opcode');
+      }
+      return;
+    }
+
+    if (frame.opcode === OPCODES.binary) {
+      throw new FrameError(
+        CLOSE_UNSUPPORTED_DATA,
+        'Only text messages are supported'
+      );
+    }
+    if (
+      frame.opcode !== OPCODES.text &&
+      frame.opcode !== OPCODES.continuation
+    ) {
+      throw new FrameError(CLOSE_PROTOCOL_ERROR, 'Unknown opcode');
+    }
+    if (frame.opcode === OPCODES.text && this.fragments.length > 0) {
+      throw new FrameError(CLOSE_PROTOCOL_ERROR, 'Expected a continuation');
+    }
+    if (frame.opcode === OPCODES.continuation && this.fragments.length === 0) {
+      throw new FrameError(CLOSE_PROTOCOL_ERROR, 'Unexpected continuation');
+    }
+
+    this.fragmentBytes += frame.payload.length;
+    if (this.fragmentBytes > this.maxMessageBytes) {
+      throw new FrameError(CLOSE_TOO_BIG, 'Message too large');
+    }
+    this.fragments.push(frame.payload);
+    if (!frame.fin) {
+      return;
+    }
+
+    const text = Buffer.concat(this.fragments).toString('utf8');
+    this.fragments = [];
+    this.fragmentBytes = 0;
+    this.deliver(text);
+  }
+
+  private deliver(text: string): void {
+    let message: JSONRPCMessage;
+    try {
+      message = JSONRPCMessageSchema.parse(JSON.parse(text));
+    } catch (error) {
+      this.onerror?.(error instanceof Error ? error : new Error(String(error)));
+      return;
+    }
+    this.onmessage?.(
+      message,
+      this.authInfo ? { authInfo: this.authInfo } : undefined
+    );
+  }
+
+  /**
+   * Send a close frame, end the connection once it's flushed and report
+   * the transport closed
+   */
+  private closeWith(code: number, reason: string): void {
+    if (this.closed) {
+      return;
+    }
+    const payload = Buffer.alloc(2 + Buffer.byteLength(reason));
+    payload.writeUInt16BE(code, 0);
+    payload.write(reason, 2);
+    this.socket.end(encodeFrame(OPCODES.close, payload));
+    this.finish();
+  }
+
+  private finish(): void {
+    if (this.closed) {
+      return;
+    }
+    this.closed = true;
+    this.onclose?.();
+  }
+}
@@ -0,0 +1,459 @@
+/**
+ * API surface - snapshot the public symbols of a workspace and classify
+ * changes against a baseline as semver major, minor or patch
+ */
+
+import * as path from 'path';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import type { FlattenedSymbol } from '../types/lsp.js';
+import { getSymbolKindName } from '../tools/utils.js';
+import { isContainerKind } from '../config/symbol-kinds.js';
+import {
+  getDeclarationPrefix,
+  inferSymbolModifiers,
+} from '../utils/symbol-modifiers.js';
+import { formatQualifiedName } from '../utils/qualified-names.js';
+import { isTestFile } from '../utils/test-classification.js';
+import {
+  discoverWorkspaceFiles,
+  outlineWorkspaceFiles,
+  selectPublicSymbols,
+  type ReportFormat,
+} from './workspace-scan.js';
+
+export const API_CHANGE_LEVELS = ['patch', 'minor', 'major'] as const;
+
+export type ApiChangeLevel = (typeof API_CHANGE_LEVELS)[number];
+
+// Languages where symbols are addressed by namespace rather than file
+const NAMESPACE_SCOPED_EXTENSIONS = new Set([
+  '.cs',
+  '.java',
+  '.kt',
+  '.kts',
+  '.scala',
+  '.swift',
+  '.c',
+  '.h',
+  '.cpp',
+  '.cc',
+  '.hpp',
+]);
+
+// Lines scanned when a declaration spans several lines
+const MAX_SIGNATURE_LINES = 8;
+
+export interface ApiSymbol {
+  // Stable identity: module plus qualified name
+  id: string;
+  name: string;
+  kind: string;
+  file: string;
+  line: number;
+  signature: string;
+}
+
+export interface ApiSnapshot {
+  version?: string;
+  symbols: ApiSymbol[];
+}
+
+export interface ApiChange {
+  level: ApiChangeLevel;
+  change: 'added' | 'removed' | 'changed';
+  id: string;
+  kind: string;
+  before?: string;
+  after?: string;
+}
+
+export interface ApiCheckReport {
+  baseline: string;
+  baselineVersion?: string;
+  version?: string;
+  required: ApiChangeLevel;
+  declared?: ApiChangeLevel;
+  sufficient: boolean;
+  changes: ApiChange[];
+}
+
+function levelRank(level: ApiChangeLevel): number {
+  return API_CHANGE_LEVELS.indexOf(level);
+}
+
+/**
+ * Module a file's symbols are addressed through: the file itself for
+ * module-per-file languages, the package directory for Go, and nothing for
+ * namespace-scoped languages
+ */
+export function getApiModule(file: string): string {
+  const extension = path.posix.extname(file);
+  if (NAMESPACE_SCOPED_EXTENSIONS.has(extension)) {
+    return '';
+  }
+  if (extension === '.go') {
+    return path.posix.dirname(file);
+  }
+  return file.slice(0, file.length - extension.length);
+}
+
+/**
+ * Declaration text from the symbol name up to its body, whitespace
+ * normalized, e.g. `parse(input: string): Ast`
+ */
+export function getDeclarationSignature(
+  lines: string[],
+  symbol: FlattenedSymbol
+): string {
+  if (symbol.detail && symbol.detail.trim().length > 0) {
+    return `${symbol.name} ${symbol.detail.trim()}`;
+  }
+
+  const start = (symbol.selectionRange ?? symbol.range).start;
+  const parts: string[] = [];
+  for (
+    let index = start.line;
+    index < lines.length && index < start.line + MAX_SIGNATURE_LINES;
+    index++
+  ) {
+    const line = lines[index] ?? '';
+    const text = index === start.line ? line.slice(start.character) : line;
+    parts.push(text);
+    if (/[{;]|:\s*$|=>/.test(text)) {
+      break;
+    }
+  }
+
+  return parts
+    .join(' ')
+    .split('{')[0]!
+    .replace(/\s+/g, ' ')
+    .replace(/\s*[:;]\s*$/, '')
+    .trim();
+}
+
+/**
+ * Split the first parenthesized parameter list of a signature
+ */
+function splitParameters(
+  signature: string
+): { parameters: string[]; rest: string } | null {
+  const open = signature.indexOf('(');
+  if (open === -1) {
+    return null;
+  }
+
+  let depth = 0;
+  let current = '';
+  const parameters: string[] = [];
+  for (let index = open + 1; index < signature.length; index++) {
+    const char = signature[index]!;
+    const isArrow = char === '>' && signature[index - 1] === '=';
+    if ('([{<'.includes(char)) {
+      depth++;
+    } else if (')]}>'.includes(char) && !isArrow) {
+      if (depth === 0 && char === ')') {
+        if (current.trim()) {
+          parameters.push(current.trim());
+        }
+        return {
+          parameters,
+          rest: `${signature.slice(0, open)}${signature.slice(index + 1)}`,
+        };
+      }
+      depth--;
+    } else if (char === ',' && depth === 0) {
+      parameters.push(current.trim());
+      current = '';
+      continue;
+    }
+    current += char;
+  }
+  return null;
+}
+
+function isOptionalParameter(parameter: string): boolean {
+  return /^(\.\.\.|\*)|\?\s*:|=/.test(parameter);
+}
+
+/**
+ * Level of a signature change: minor when only optional parameters were
+ * appended, major otherwise
+ */
+export function classifySignatureChange(
+  before: string,
+  after: string
+): ApiChangeLevel {
+  const previous = splitParameters(before);
+  const next = splitParameters(after);
+  if (
+    previous &&
+    next &&
+    previous.rest === next.rest &&
+    next.parameters.length > previous.parameters.length &&
+    previous.parameters.every(
+      (parameter, index) => next.parameters[index] === parameter
+    ) &&
+    next.parameters.slice(previous.parameters.length).every(isOptionalParameter)
+  ) {
+    return 'minor';
+  }
+  return 'major';
+}
+
+/**
+ * Compare two API surfaces: removals and incompatible changes are major,
+ * additions and compatible changes are minor
+ */
+export function compareApiSurfaces(
+  baseline: ApiSymbol[],
+  current: ApiSymbol[]
+): ApiChange[] {
+  const before = new Map(baseline.map((symbol) => [symbol.id, symbol]));
+  const after = new Map(current.map((symbol) => [symbol.id, symbol]));
+  const changes: ApiChange[] = [];
+
+  for (const [id, previous] of before) {
+    const next = after.get(id);
+    if (!next) {
+      changes.push({
+        level: 'major',
+        change: 'removed',
+        id,
+        kind: previous.kind,
+        before: previous.signature,
+      });
+    } else if (previous.kind !== next.kind) {
+      changes.push({
+        level: 'major',
+        change: 'changed',
+        id,
+        kind: next.kind,
+        before: `${previous.kind} ${previous.signature}`,
+        after: `${next.kind} ${next.signature}`,
+      });
+    } else if (previous.signature !== next.signature) {
+      changes.push({
+        level: classifySignatureChange(previous.signature, next.signature),
+        change: 'changed',
+        id,
+        kind: next.kind,
+        before: previous.signature,
+        after: next.signature,
+      });
+    }
+  }
+
+  for (const [id, next] of after) {
+    if (!before.has(id)) {
+      changes.push({
+        level: 'minor',
+        change: 'added',
+        id,
+        kind: next.kind,
+        after: next.signature,
+      });
+    }
+  }
+
+  return changes.sort(
+    (a, b) =>
+      levelRank(b.level) - levelRank(a.level) ||
+      a.id.localeCompare(b.id)
+  );
+}
+
+function parseVersion(version: string): [number, number, number] | null {
+  const match = /^v?(\d+)\.(\d+)\.(\d+)/.exec(version.trim());
+  if (!match) {
+    return null;
+  }
+  return [Number(match[1]), Number(match[2]), Number(match[3])];
+}
+
+/**
+ * Bump between two versions, or null when either isn't a semver version or
+ * the new one isn't greater
+ */
+export function getVersionBump(
+  from: string,
+  to: string
+): ApiChangeLevel | null {
+  const previous = parseVersion(from);
+  const next = parseVersion(to);
+  if (!previous || !next) {
+    return null;
+  }
+
+  const [major, minor, patch] = previous;
+  const [nextMajor, nextMinor, nextPatch] = next;
+  if (nextMajor !== major) {
+    return nextMajor > major ? 'major' : null;
+  }
+  if (nextMinor !== minor) {
+    if (nextMinor < minor) return null;
+    // Before 1.0.0 a minor bump may break the API and a patch may add to it
+    return major === 0 ? 'major' : 'minor';
+  }
+  if (nextPatch <= patch) return null;
+  return major === 0 ? 'minor' : 'patch';
+}
+
+/**
+ * Check a current surface against a baseline and an optional declared
+ * release version
+ */
+export function checkApiCompatibility(
+  baseline: ApiSnapshot,
+  current: ApiSnapshot,
+  baselineLabel: string
+): ApiCheckReport {
+  const changes = compareApiSurfaces(baseline.symbols, current.symbols);
+  const required = changes.reduce<ApiChangeLevel>(
+    (level, change) =>
+      levelRank(change.level) > levelRank(level) ? change.level : level,
+    'patch'
+  );
+
+  let declared: ApiChangeLevel | undefined;
+  if (current.version) {
+    if (!baseline.version) {
+      throw new Error(
+        'A release version was given but the baseline version is unknown. Pass --baseline-version.'
+      );
+    }
+    const bump = getVersionBump(baseline.version, current.version);
+    if (!bump) {
+      throw new Error(
+        `Release version ${current.version} is not a semver increase over ${baseline.version}`
+      );
+    }
+    declared = bump;
+  }
+
+  return {
+    baseline: baselineLabel,
+    ...(baseline.version && { baselineVersion: baseline.version }),
+    ...(current.version && { version: current.version }),
+    required,
+    ...(declared && { declared }),
+    sufficient: !declared || levelRank(declared) >= levelRank(required),
+    changes,
+  };
+}
+
+/**
+ * Public API of the workspace: exported top-level symbols plus the public
+ * members of exported types, excluding test code
+ */
+export async function collectApiSurface(
+  manager: LspManager,
+  workspace: string,
+  maxFiles: number
+): Promise<ApiSymbol[]> {
+  const { files } = await discoverWorkspaceFiles(manager, workspace, maxFiles);
+  const surface: ApiSymbol[] = [];
+  const outlines = await outlineWorkspaceFiles(
+    manager,
+    workspace,
+    files.filter((file) => !isTestFile(file))
+  );
+
+  for (const [file, { symbols, lines }] of outlines) {
+    const filePath = path.join(workspace, file);
+    const exported = new Set(selectPublicSymbols(symbols, lines, filePath));
+    const exportedTypes = new Set(
+      [...exported]
+        .filter((symbol) => isContainerKind(symbol.kind))
+        .map((symbol) => symbol.name)
+    );
+
+    for (const symbol of symbols) {
+      const containerPath = symbol.containerPath ?? [];
+      if (!exported.has(symbol)) {
+        const root = containerPath[0];
+        if (!root || !exportedTypes.has(root)) {
+          continue;
+        }
+        const { visibility } = inferSymbolModifiers(
+          getDeclarationPrefix(lines, symbol),
+          symbol.name,
+          filePath
+        );
+        if (visibility && visibility !== 'public') {
+          continue;
+        }
+      }
+
+      const qualifiedName = formatQualifiedName(
+        symbol.name,
+        containerPath,
+        filePath
+      );
+      const apiModule = getApiModule(file);
+      surface.push({
+        id: apiModule ? `${apiModule}#${qualifiedName}` : qualifiedName,
+        name: symbol.name,
+        kind: getSymbolKindName(symbol.kind),
+        file,
+        line: (symbol.selectionRange ?? symbol.range).start.line + 1,
+        signature: getDeclarationSignature(lines, symbol),
+      });
+    }
+  }
+
+  return surface;
+}
+
+/**
+ * Render the check as markdown grouped by change level
+ */
+export function formatApiCheckReportMarkdown(report: ApiCheckReport): string {
+  const baseline = report.baselineVersion
+    ? `${report.baseline} (${report.baselineVersion})`
+    : report.baseline;
+  const summary = [
+    `Baseline: ${baseline}`,
+    `Required bump: ${report.required}`,
+    ...(report.declared
+      ? [
+          `Declared bump: ${report.declared} (${report.version}) - ${report.sufficient ? 'sufficient' : 'insufficient'}`,
+        ]
+      : []),
+  ];
+  const sections: string[] = ['# API compatibility', summary.join('\n')];
+
+  if (report.changes.length === 0) {
+    sections.push('No public API changes');
+  }
+
+  for (const level of [...API_CHANGE_LEVELS].reverse()) {
+    const changes = report.changes.filter((change) => change.level === level);
+    if (changes.length === 0) {
+      continue;
+    }
+    const heading = `## ${level[0]!.toUpperCase()}${level.slice(1)} (${changes.length})`;
+    const lines = changes.map((change) => {
+      const detail =
+        change.change === 'changed'
+          ? `: \`${change.before}\` -> \`${change.after}\``
+          : '';
+      return `- ${change.change} ${change.kind} ${change.id}${detail}`;
+    });
+    sections.push([heading, ...lines].join('\n'));
+  }
+
+  return sections.join('\n\n');
+}
+
+/**
+ * Render the check in the requested format
+ */
+export function formatApiCheckReport(
+  report: ApiCheckReport,
+  format: ReportFormat
+): string {
+  return format === 'json'
+    ? JSON.stringify(report, null, 2)
+    : formatApiCheckReportMarkdown(report);
+}
@@ -0,0 +1,230 @@
+/**
+ * Architecture map - module-level summary of a workspace built from the
+ * language servers: components, their public symbols and dependency edges
+ */
+
+import * as path from 'path';
+import type { Position } from 'vscode-languageserver-protocol';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { getSymbolKindName } from '../tools/utils.js';
+import { formatReportPath } from '../utils/path-format.js';
+import { isTestFile } from '../utils/test-classification.js';
+import {
+  discoverWorkspaceFiles,
+  findReferencesAt,
+  outlineWorkspaceFiles,
+  selectPublicSymbols,
+  toReportPath,
+  toWorkspacePath,
+  type ReportFormat,
+} from './workspace-scan.js';
+
+// Name used for files that sit directly in the workspace root
+export const ROOT_COMPONENT = '(root)';
+
+// Public symbols per component probed with references to find edges
+const MAX_EDGE_PROBES_PER_COMPONENT = 25;
+
+export interface MapSymbol {
+  name: string;
+  kind: string;
+  file: string;
+  line: number;
+}
+
+export interface MapComponent {
+  name: string;
+  files: number;
+  testFiles: number;
+  publicSymbols: MapSymbol[];
+}
+
+export interface MapEdge {
+  from: string;
+  to: string;
+  references: number;
+}
+
+export interface ArchitectureMap {
+  workspace: string;
+  components: MapComponent[];
+  edges: MapEdge[];
+  truncated: boolean;
+}
+
+export interface ArchitectureMapOptions {
+  workspace: string;
+  depth: number;
+  maxFiles: number;
+}
+
+/**
+ * Component a workspace-relative file belongs to: its leading directories,
+ * up to `depth` segments
+ */
+export function getComponentName(relativePath: string, depth: number): string {
+  const directories = relativePath.split(/[\\/]/).slice(0, -1);
+  if (directories.length === 0) {
+    return ROOT_COMPONENT;
+  }
+  return directories.slice(0, Math.max(1, depth)).join('/');
+}
+
+/**
+ * Build the architecture map by outlining every source file handled by a
+ * configured language server and probing public symbols for references
+ */
+export async function buildArchitectureMap(
+  manager: LspManager,
+  options: ArchitectureMapOptions
+): Promise<ArchitectureMap> {
+  const { files, truncated } = await discoverWorkspaceFiles(
+    manager,
+    options.workspace,
+    options.maxFiles
+  );
+
+  const components = new Map<string, MapComponent>();
+  const probes = new Map<
+    string,
+    { symbol: MapSymbol; position: Position }[]
+  >();
+  const outlines = await outlineWorkspaceFiles(
+    manager,
+    options.workspace,
+    files.filter((file) => !isTestFile(file))
+  );
+
+  for (const file of files) {
+    const name = getComponentName(file, options.depth);
+    const component = components.get(name) ?? {
+      name,
+      files: 0,
+      testFiles: 0,
+      publicSymbols: [],
+    };
+    components.set(name, component);
+    component.files++;
+
+    if (isTestFile(file)) {
+      component.testFiles++;
+      continue;
+    }
+
+    const filePath = path.join(options.workspace, file);
+    const { symbols, lines } = outlines.get(file) ?? { symbols: [], lines: [] };
+    for (const symbol of selectPublicSymbols(symbols, lines, filePath)) {
+      const start = (symbol.selectionRange ?? symbol.range).start;
+      const mapSymbol: MapSymbol = {
+        name: symbol.name,
+        kind: getSymbolKindName(symbol.kind),
+        file,
+        line: start.line + 1,
+      };
+      component.publicSymbols.push(mapSymbol);
+
+      const componentProbes = probes.get(name) ?? [];
+      probes.set(name, componentProbes);
+      if (componentProbes.length < MAX_EDGE_PROBES_PER_COMPONENT) {
+        componentProbes.push({ symbol: mapSymbol, position: start });
+      }
+    }
+  }
+
+  const edgeCounts = new Map<string, MapEdge>();
+  for (const [target, componentProbes] of probes) {
+    for (const { symbol, position } of componentProbes) {
+      const references = await findReferencesAt(
+        manager,
+        path.join(options.workspace, symbol.file),
+        position
+      );
+      const sources = (references ?? []).flatMap((reference) => {
+        const file = toWorkspacePath(options.workspace, reference.uri);
+        return file ? [getComponentName(file, options.depth)] : [];
+      });
+      for (const source of sources) {
+        if (source === target) {
+          continue;
+        }
+        const key = `${source}\u0000${target}`;
+        const edge = edgeCounts.get(key) ?? {
+          from: source,
+          to: target,
+          references: 0,
+        };
+        edge.references++;
+        edgeCounts.set(key, edge);
+      }
+    }
+  }
+
+  return {
+    workspace: formatReportPath(options.workspace),
+    components: [...components.values()]
+      .sort((a, b) => a.name.localeCompare(b.name))
+      .map((component) => ({
+        ...component,
+        publicSymbols: component.publicSymbols.map((symbol) => ({
+          ...symbol,
+          file: toReportPath(options.workspace, symbol.file),
+        })),
+      })),
+    edges: [...edgeCounts.values()].sort(
+      (a, b) => a.from.localeCompare(b.from) || a.to.localeCompare(b.to)
+    ),
+    truncated,
+  };
+}
+
+/**
+ * Render the map as a markdown orientation document
+ */
+export function formatArchitectureMapMarkdown(map: ArchitectureMap): string {
+  const sections: string[] = [`# Architecture map: ${map.workspace}`];
+
+  const totalFiles = map.components.reduce((sum, c) => sum + c.files, 0);
+  sections.push(
+    `${map.components.length} component(s), ${totalFiles} file(s), ${map.edges.length} dependency edge(s)` +
+      (map.truncated ? ' (file limit reached, map is partial)' : '')
+  );
+
+  const componentLines = map.components.map((component) => {
+    const tests =
+      component.testFiles > 0 ? `, ${component.testFiles} test file(s)` : '';
+    return `- ${component.name} - ${component.files} file(s)${tests}, ${component.publicSymbols.length} public symbol(s)`;
+  });
+  sections.push(['## Components', ...componentLines].join('\n'));
+
+  if (map.edges.length > 0) {
+    const edgeLines = map.edges.map(
+      (edge) => `- ${edge.from} -> ${edge.to} (${edge.references} reference(s))`
+    );
+    sections.push(['## Dependencies', ...edgeLines].join('\n'));
+  }
+
+  for (const component of map.components) {
+    if (component.publicSymbols.length === 0) {
+      continue;
+    }
+    const symbolLines = component.publicSymbols.map(
+      (symbol) =>
+        `- ${symbol.name} (${symbol.kind}) - ${symbol.file}:${symbol.line}`
+    );
+    sections.push([`## ${component.name}`, ...symbolLines].join('\n'));
+  }
+
+  return sections.join('\n\n');
+}
+
+/**
+ * Render the map in the requested format
+ */
+export function formatArchitectureMap(
+  map: ArchitectureMap,
+  format: ReportFormat
+): string {
+  return format === 'json'
+    ? JSON.stringify(map, null, 2)
+    : formatArchitectureMapMarkdown(map);
+}
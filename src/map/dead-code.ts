@@ -0,0 +1,472 @@
+/**
+ * Dead code - reachability over the reference graph from configurable entry
+ * points, reporting unreachable symbols with a confidence level
+ */
+
+import * as path from 'path';
+import type { Position, Range } from 'vscode-languageserver-protocol';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { SymbolKind, type FlattenedSymbol } from '../types/lsp.js';
+import { getSymbolKindName } from '../tools/utils.js';
+import {
+  getDeclarationPrefix,
+  inferSymbolModifiers,
+} from '../utils/symbol-modifiers.js';
+import { formatReportPath } from '../utils/path-format.js';
+import { isTestFile, isTestSymbol } from '../utils/test-classification.js';
+import {
+  containsPosition,
+  discoverWorkspaceFiles,
+  findReferencesAt,
+  outlineWorkspaceFiles,
+  selectPublicSymbols,
+  toReportPath,
+  toWorkspacePath,
+  type ReportFormat,
+} from './workspace-scan.js';
+
+export const ENTRY_POINT_KINDS = ['main', 'exported', 'tests'] as const;
+
+export type EntryPointKind = (typeof ENTRY_POINT_KINDS)[number];
+
+export const DEAD_CODE_CONFIDENCES = ['high', 'medium', 'low'] as const;
+
+export type DeadCodeConfidence = (typeof DEAD_CODE_CONFIDENCES)[number];
+
+const MAIN_SYMBOL_NAMES = new Set(['main', 'Main']);
+
+// Kinds analyzed anywhere in a file
+const ANALYZED_KINDS = new Set<number>([
+  SymbolKind.Class,
+  SymbolKind.Method,
+  SymbolKind.Function,
+  SymbolKind.Interface,
+  SymbolKind.Enum,
+  SymbolKind.Struct,
+]);
+
+// Kinds analyzed only at the top level of a file
+const TOP_LEVEL_KINDS = new Set<number>([
+  SymbolKind.Variable,
+  SymbolKind.Constant,
+]);
+
+export interface SymbolNode {
+  id: string;
+  name: string;
+  kind: string;
+  file: string;
+  line: number;
+  containerId?: string;
+  // Why the symbol is an entry point, when it is one
+  entry?: string;
+  // Why the symbol may be used in ways references can't see
+  dynamicHint?: string;
+  // Referenced from anywhere outside its own declaration
+  referenced: boolean;
+}
+
+export interface UsageEdge {
+  // Symbol containing the reference, or null for module-level code
+  from: string | null;
+  to: string;
+}
+
+export interface DeadSymbol {
+  name: string;
+  kind: string;
+  file: string;
+  line: number;
+  confidence: DeadCodeConfidence;
+  reason: string;
+}
+
+export interface DeadCodeReport {
+  workspace: string;
+  entryPointKinds: EntryPointKind[];
+  files: number;
+  analyzedSymbols: number;
+  entryPoints: number;
+  failedLookups: number;
+  unreachable: DeadSymbol[];
+  truncated: boolean;
+}
+
+export interface DeadCodeOptions {
+  workspace: string;
+  maxFiles: number;
+  entryPoints: EntryPointKind[];
+  // Extra symbol names always treated as entry points
+  entrySymbols: string[];
+}
+
+interface ScannedSymbol {
+  node: SymbolNode;
+  symbol: FlattenedSymbol;
+}
+
+function rangeSize(range: Range): number {
+  return (
+    (range.end.line - range.start.line) * 100_000 +
+    (range.end.character - range.start.character)
+  );
+}
+
+/**
+ * Innermost analyzed symbol in a file whose range contains the position
+ */
+function findEnclosingSymbol(
+  scanned: ScannedSymbol[],
+  position: Position
+): ScannedSymbol | null {
+  let best: ScannedSymbol | null = null;
+  for (const entry of scanned) {
+    if (
+      containsPosition(entry.symbol.range, position) &&
+      (!best || rangeSize(entry.symbol.range) < rangeSize(best.symbol.range))
+    ) {
+      best = entry;
+    }
+  }
+  return best;
+}
+
+/**
+ * Reason a symbol may be reached in ways the reference graph can't show
+ */
+function getDynamicHint(
+  symbol: FlattenedSymbol,
+  prefix: string,
+  filePath: string,
+  entryPoints: EntryPointKind[]
+): string | undefined {
+  const info = inferSymbolModifiers(prefix, symbol.name, filePath);
+  if (/^__\w+__$/.test(symbol.name)) {
+    return 'special method invoked by the runtime';
+  }
+  if (
+    info.modifiers.includes('override') ||
+    info.modifiers.includes('abstract')
+  ) {
+    return 'overrides or declares an inherited member';
+  }
+  if (/(^|\s)@\w|#\[/.test(prefix)) {
+    return 'decorators or attributes may register it';
+  }
+  if (info.visibility === 'public' && !entryPoints.includes('exported')) {
+    return 'public, so it may be used outside the workspace';
+  }
+  return undefined;
+}
+
+/**
+ * Why a symbol is an entry point for the enabled entry point kinds
+ */
+function getEntryReason(
+  symbol: FlattenedSymbol,
+  prefix: string,
+  file: string,
+  isPublic: boolean,
+  options: DeadCodeOptions
+): string | undefined {
+  if (options.entrySymbols.includes(symbol.name)) {
+    return 'configured entry symbol';
+  }
+  if (
+    options.entryPoints.includes('main') &&
+    MAIN_SYMBOL_NAMES.has(symbol.name)
+  ) {
+    return 'main';
+  }
+  if (
+    options.entryPoints.includes('tests') &&
+    (isTestFile(file) || isTestSymbol(file, symbol.containerPath, prefix))
+  ) {
+    return 'test';
+  }
+  if (options.entryPoints.includes('exported') && isPublic) {
+    return 'exported API';
+  }
+  return undefined;
+}
+
+/**
+ * Walk the usage graph from entry points and module-level code, returning
+ * every symbol that can't be reached with a confidence level:
+ * high when nothing references it, medium when only unreachable code does,
+ * and low when it may be invoked dynamically.
+ */
+export function findUnreachableSymbols(
+  nodes: SymbolNode[],
+  edges: UsageEdge[]
+): DeadSymbol[] {
+  const usages = new Map<string, string[]>();
+  const reachable = new Set<string>();
+  const queue: string[] = [];
+
+  const markReachable = (id: string) => {
+    if (!reachable.has(id)) {
+      reachable.add(id);
+      queue.push(id);
+    }
+  };
+
+  for (const edge of edges) {
+    if (edge.from === null) {
+      markReachable(edge.to);
+      continue;
+    }
+    const targets = usages.get(edge.from) ?? [];
+    targets.push(edge.to);
+    usages.set(edge.from, targets);
+  }
+  for (const node of nodes) {
+    if (node.entry) {
+      markReachable(node.id);
+    }
+  }
+
+  while (queue.length > 0) {
+    const id = queue.shift()!;
+    for (const target of usages.get(id) ?? []) {
+      markReachable(target);
+    }
+  }
+
+  return nodes
+    .filter((node) => !reachable.has(node.id))
+    .map((node): DeadSymbol => {
+      const containerReachable =
+        node.containerId !== undefined && reachable.has(node.containerId);
+      let confidence: DeadCodeConfidence;
+      let reason: string;
+
+      if (node.dynamicHint) {
+        confidence = 'low';
+        reason = node.dynamicHint;
+      } else if (containerReachable && node.kind === 'Method') {
+        confidence = 'low';
+        reason = 'member of a reachable type, may be called dynamically';
+      } else if (node.referenced) {
+        confidence = 'medium';
+        reason = 'only referenced from unreachable code';
+      } else {
+        confidence = 'high';
+        reason = 'no references';
+      }
+
+      return {
+        name: node.name,
+        kind: node.kind,
+        file: node.file,
+        line: node.line,
+        confidence,
+        reason,
+      };
+    })
+    .sort(
+      (a, b) =>
+        DEAD_CODE_CONFIDENCES.indexOf(a.confidence) -
+          DEAD_CODE_CONFIDENCES.indexOf(b.confidence) ||
+        a.file.localeCompare(b.file) ||
+        a.line - b.line
+    );
+}
+
+/**
+ * Outline every source file, build the usage graph from references and
+ * report symbols unreachable from the configured entry points
+ */
+export async function buildDeadCodeReport(
+  manager: LspManager,
+  options: DeadCodeOptions
+): Promise<DeadCodeReport> {
+  const { files, truncated } = await discoverWorkspaceFiles(
+    manager,
+    options.workspace,
+    options.maxFiles
+  );
+
+  const scannedByFile = new Map<string, ScannedSymbol[]>();
+  const outlines = await outlineWorkspaceFiles(
+    manager,
+    options.workspace,
+    files
+  );
+  for (const [file, { symbols, lines }] of outlines) {
+    const filePath = path.join(options.workspace, file);
+    const publicSymbols = new Set(
+      isTestFile(file) ? [] : selectPublicSymbols(symbols, lines, filePath)
+    );
+    const scanned: ScannedSymbol[] = [];
+    const idsBySymbol = new Map<FlattenedSymbol, string>();
+
+    for (const symbol of symbols) {
+      const isTopLevel =
+        !symbol.containerPath || symbol.containerPath.length === 0;
+      if (
+        !ANALYZED_KINDS.has(symbol.kind) &&
+        !(isTopLevel && TOP_LEVEL_KINDS.has(symbol.kind))
+      ) {
+        continue;
+      }
+
+      const start = (symbol.selectionRange ?? symbol.range).start;
+      const prefix = getDeclarationPrefix(lines, symbol);
+      const id = `${file}:${start.line}:${start.character}:${symbol.name}`;
+      idsBySymbol.set(symbol, id);
+
+      const container = scanned
+        .filter(
+          (entry) =>
+            entry.symbol.name === symbol.containerName &&
+            containsPosition(entry.symbol.range, start)
+        )
+        .at(-1);
+      const entry = getEntryReason(
+        symbol,
+        prefix,
+        file,
+        publicSymbols.has(symbol),
+        options
+      );
+      const dynamicHint = getDynamicHint(
+        symbol,
+        prefix,
+        filePath,
+        options.entryPoints
+      );
+
+      scanned.push({
+        symbol,
+        node: {
+          id,
+          name: symbol.name,
+          kind: getSymbolKindName(symbol.kind),
+          file,
+          line: start.line + 1,
+          ...(container && { containerId: container.node.id }),
+          ...(entry && { entry }),
+          ...(dynamicHint && { dynamicHint }),
+          referenced: false,
+        },
+      });
+    }
+    scannedByFile.set(file, scanned);
+  }
+
+  const edges: UsageEdge[] = [];
+  let failedLookups = 0;
+
+  for (const [file, scanned] of scannedByFile) {
+    for (const { node, symbol } of scanned) {
+      if (node.entry) {
+        continue;
+      }
+
+      const references = await findReferencesAt(
+        manager,
+        path.join(options.workspace, file),
+        (symbol.selectionRange ?? symbol.range).start
+      );
+      if (references === null) {
+        // Without references the symbol can't be judged; keep it reachable
+        failedLookups++;
+        edges.push({ from: null, to: node.id });
+        continue;
+      }
+
+      for (const reference of references) {
+        const referenceFile = toWorkspacePath(options.workspace, reference.uri);
+        const position = reference.range.start;
+        if (
+          referenceFile === null ||
+          (referenceFile === file && containsPosition(symbol.range, position))
+        ) {
+          continue;
+        }
+
+        node.referenced = true;
+        const referenceScope = scannedByFile.get(referenceFile);
+        const enclosing = referenceScope
+          ? findEnclosingSymbol(referenceScope, position)
+          : null;
+        // References from unscanned files count as module-level usage
+        edges.push({ from: enclosing?.node.id ?? null, to: node.id });
+      }
+    }
+  }
+
+  const nodes = [...scannedByFile.values()].flatMap((scanned) =>
+    scanned.map((entry) => entry.node)
+  );
+
+  return {
+    workspace: formatReportPath(options.workspace),
+    entryPointKinds: options.entryPoints,
+    files: files.length,
+    analyzedSymbols: nodes.length,
+    entryPoints: nodes.filter((node) => node.entry).length,
+    failedLookups,
+    unreachable: findUnreachableSymbols(nodes, edges).map((symbol) => ({
+      ...symbol,
+      file: toReportPath(options.workspace, symbol.file),
+    })),
+    truncated,
+  };
+}
+
+/**
+ * Render the report as markdown grouped by confidence
+ */
+export function formatDeadCodeReportMarkdown(report: DeadCodeReport): string {
+  const sections: string[] = [`# Dead code: ${report.workspace}`];
+
+  const entryKinds =
+    report.entryPointKinds.length > 0
+      ? report.entryPointKinds.join(', ')
+      : 'configured symbols only';
+  const summary = [
+    `Analyzed ${report.analyzedSymbols} symbol(s) in ${report.files} file(s) from ${report.entryPoints} entry point(s) (${entryKinds}); ${report.unreachable.length} unreachable`,
+    ...(report.failedLookups > 0
+      ? [
+          `${report.failedLookups} symbol(s) skipped because reference lookup failed`,
+        ]
+      : []),
+    ...(report.truncated
+      ? [
+          `File limit reached: only the first ${report.files} file(s) were analyzed`,
+        ]
+      : []),
+  ];
+  sections.push(summary.join('\n'));
+
+  for (const confidence of DEAD_CODE_CONFIDENCES) {
+    const symbols = report.unreachable.filter(
+      (symbol) => symbol.confidence === confidence
+    );
+    if (symbols.length === 0) {
+      continue;
+    }
+    const heading = `## ${confidence[0]!.toUpperCase()}${confidence.slice(1)} confidence (${symbols.length})`;
+    const lines = symbols.map(
+      (symbol) =>
+        `- ${symbol.name} (${symbol.kind}) - ${symbol.file}:${symbol.line} - ${symbol.reason}`
+    );
+    sections.push([heading, ...lines].join('\n'));
+  }
+
+  return sections.join('\n\n');
+}
+
+/**
+ * Render the report in the requested format
+ */
+export function formatDeadCodeReport(
+  report: DeadCodeReport,
+  format: ReportFormat
+): string {
+  return format === 'json'
+    ? JSON.stringify(report, null, 2)
+    : formatDeadCodeReportMarkdown(report);
+}
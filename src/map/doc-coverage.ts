@@ -0,0 +1,259 @@
+/**
+ * Documentation coverage - public symbols without doc comments, grouped by
+ * module, judged from hover contents so every language is measured alike
+ */
+
+import * as path from 'path';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { getSymbolKindName } from '../tools/utils.js';
+import { hasHoverDocumentation } from '../utils/hover-markdown.js';
+import { formatReportPath } from '../utils/path-format.js';
+import { isTestFile } from '../utils/test-classification.js';
+import { getComponentName } from './architecture-map.js';
+import {
+  discoverWorkspaceFiles,
+  getHoverAt,
+  mapWithParallelism,
+  outlineWorkspaceFiles,
+  selectPublicSymbols,
+  toReportPath,
+  type ReportFormat,
+} from './workspace-scan.js';
+
+export interface DocSymbol {
+  name: string;
+  kind: string;
+  file: string;
+  line: number;
+}
+
+export interface CheckedDocSymbol extends DocSymbol {
+  documented: boolean;
+}
+
+export interface ModuleDocCoverage {
+  module: string;
+  total: number;
+  documented: number;
+  percent: number;
+  undocumented: DocSymbol[];
+}
+
+export interface DocCoverageReport {
+  workspace: string;
+  files: number;
+  total: number;
+  documented: number;
+  percent: number;
+  modules: ModuleDocCoverage[];
+  // Symbols left out because the hover request failed
+  failedLookups: number;
+  truncated: boolean;
+}
+
+export interface DocCoverageOptions {
+  workspace: string;
+  depth: number;
+  maxFiles: number;
+}
+
+/**
+ * Documented share as a percentage with one decimal; nothing to document
+ * counts as fully documented
+ */
+export function computeCoveragePercent(
+  documented: number,
+  total: number
+): number {
+  return total === 0 ? 100 : Math.round((documented / total) * 1000) / 10;
+}
+
+/**
+ * Whether coverage reaches `failUnder` percent, compared before rounding
+ */
+export function meetsDocCoverageThreshold(
+  report: Pick<DocCoverageReport, 'documented' | 'total'>,
+  failUnder: number
+): boolean {
+  return report.documented * 100 >= failUnder * report.total;
+}
+
+/**
+ * Group checked symbols by module, least documented modules first
+ */
+export function summarizeDocCoverage(
+  symbols: CheckedDocSymbol[],
+  depth: number
+): ModuleDocCoverage[] {
+  const modules = new Map<string, ModuleDocCoverage>();
+
+  for (const { documented, ...symbol } of symbols) {
+    const name = getComponentName(symbol.file, depth);
+    const entry = modules.get(name) ?? {
+      module: name,
+      total: 0,
+      documented: 0,
+      percent: 100,
+      undocumented: [],
+    };
+    modules.set(name, entry);
+
+    entry.total++;
+    if (documented) {
+      entry.documented++;
+    } else {
+      entry.undocumented.push(symbol);
+    }
+  }
+
+  return [...modules.values()]
+    .map((entry) => ({
+      ...entry,
+      percent: computeCoveragePercent(entry.documented, entry.total),
+      undocumented: entry.undocumented.sort(
+        (a, b) => a.file.localeCompare(b.file) || a.line - b.line
+      ),
+    }))
+    .sort((a, b) => a.percent - b.percent || a.module.localeCompare(b.module));
+}
+
+/**
+ * Hover every public symbol in non-test files and check whether its hover
+ * carries documentation beyond the signature
+ */
+export async function buildDocCoverageReport(
+  manager: LspManager,
+  options: DocCoverageOptions
+): Promise<DocCoverageReport> {
+  const { files, truncated } = await discoverWorkspaceFiles(
+    manager,
+    options.workspace,
+    options.maxFiles
+  );
+
+  const checked: CheckedDocSymbol[] = [];
+  let failedLookups = 0;
+  const outlines = await outlineWorkspaceFiles(
+    manager,
+    options.workspace,
+    files.filter((file) => !isTestFile(file))
+  );
+
+  for (const [file, { symbols, lines }] of outlines) {
+    const filePath = path.join(options.workspace, file);
+    const publicSymbols = selectPublicSymbols(symbols, lines, filePath);
+    const hovers = await mapWithParallelism(publicSymbols, (symbol) =>
+      getHoverAt(
+        manager,
+        filePath,
+        (symbol.selectionRange ?? symbol.range).start
+      )
+    );
+    for (const [index, symbol] of publicSymbols.entries()) {
+      const start = (symbol.selectionRange ?? symbol.range).start;
+      const hover = hovers[index];
+      if (hover === undefined) {
+        failedLookups++;
+        continue;
+      }
+
+      checked.push({
+        name: symbol.name,
+        kind: getSymbolKindName(symbol.kind),
+        file,
+        line: start.line + 1,
+        documented: hover !== null && hasHoverDocumentation(hover),
+      });
+    }
+  }
+
+  const documented = checked.filter((symbol) => symbol.documented).length;
+
+  return {
+    workspace: formatReportPath(options.workspace),
+    files: files.length,
+    total: checked.length,
+    documented,
+    percent: computeCoveragePercent(documented, checked.length),
+    modules: summarizeDocCoverage(checked, options.depth).map((entry) => ({
+      ...entry,
+      undocumented: entry.undocumented.map((symbol) => ({
+        ...symbol,
+        file: toReportPath(options.workspace, symbol.file),
+      })),
+    })),
+    failedLookups,
+    truncated,
+  };
+}
+
+/**
+ * Render the report as markdown: a per-module table followed by the
+ * undocumented symbols of each module
+ */
+export function formatDocCoverageReportMarkdown(
+  report: DocCoverageReport
+): string {
+  const sections: string[] = [`# Documentation coverage: ${report.workspace}`];
+
+  const summary = [
+    `${report.documented} of ${report.total} public symbol(s) documented in ${report.files} file(s) (${report.percent}%)`,
+    ...(report.failedLookups > 0
+      ? [
+          `${report.failedLookups} symbol(s) skipped because hover failed`,
+        ]
+      : []),
+    ...(report.truncated
+      ? [
+          `File limit reached: only the first ${report.files} file(s) were analyzed`,
+        ]
+      : []),
+  ];
+  sections.push(summary.join('\n'));
+
+  if (report.modules.length === 0) {
+    sections.push('No public symbols found');
+    return sections.join('\n\n');
+  }
+
+  sections.push(
+    [
+      '| Module | Documented | Total | Coverage |',
+      '| --- | --- | --- | --- |',
+      ...report.modules.map(
+        (entry) =>
+          `| ${entry.module} | ${entry.documented} | ${entry.total} | ${entry.percent}% |`
+      ),
+    ].join('\n')
+  );
+
+  for (const entry of report.modules) {
+    if (entry.undocumented.length === 0) {
+      continue;
+    }
+    const lines = entry.undocumented.map(
+      (symbol) =>
+        `- ${symbol.name} (${symbol.kind}) - ${symbol.file}:${symbol.line}`
+    );
+    sections.push(
+      [
+        `## ${entry.module} (${entry.undocumented.length} undocumented)`,
+        ...lines,
+      ].join('\n')
+    );
+  }
+
+  return sections.join('\n\n');
+}
+
+/**
+ * Render the report in the requested format
+ */
+export function formatDocCoverageReport(
+  report: DocCoverageReport,
+  format: ReportFormat
+): string {
+  return format === 'json'
+    ? JSON.stringify(report, null, 2)
+    : formatDocCoverageReportMarkdown(report);
+}
@@ -0,0 +1,518 @@
+/**
+ * Import cycles - file or module import graph per language and the strongly
+ * connected components (cycles) in it
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { getComponentName } from './architecture-map.js';
+import {
+  discoverWorkspaceFiles,
+  toReportPath,
+  type ReportFormat,
+} from './workspace-scan.js';
+import logger from '../utils/logger.js';
+import { formatReportPath } from '../utils/path-format.js';
+
+export type ImportLanguage = 'typescript' | 'python' | 'java';
+
+const IMPORT_LANGUAGES: Record<string, ImportLanguage> = {
+  '.ts': 'typescript',
+  '.tsx': 'typescript',
+  '.mts': 'typescript',
+  '.cts': 'typescript',
+  '.js': 'typescript',
+  '.jsx': 'typescript',
+  '.mjs': 'typescript',
+  '.cjs': 'typescript',
+  '.py': 'python',
+  '.pyi': 'python',
+  '.java': 'java',
+};
+
+const SCRIPT_EXTENSIONS = Object.keys(IMPORT_LANGUAGES).filter(
+  (extension) => IMPORT_LANGUAGES[extension] === 'typescript'
+);
+
+// Roots absolute Python imports are resolved against
+const PYTHON_ROOTS = ['', 'src/'];
+
+export interface ImportEdge {
+  from: string;
+  to: string;
+  // 1-based line of the import statement in `from`
+  line: number;
+  specifier: string;
+}
+
+export interface ImportCycle {
+  language: ImportLanguage;
+  // Files, or modules when grouping by directory
+  members: string[];
+  imports: ImportEdge[];
+}
+
+export interface ImportCycleReport {
+  workspace: string;
+  // Directory levels that make up a module; null for a file-level graph
+  depth: number | null;
+  files: number;
+  imports: number;
+  cycles: ImportCycle[];
+  unsupportedExtensions: string[];
+  truncated: boolean;
+}
+
+export interface ImportCycleOptions {
+  workspace: string;
+  depth?: number;
+  maxFiles: number;
+}
+
+export function getImportLanguage(file: string): ImportLanguage | null {
+  return IMPORT_LANGUAGES[path.posix.extname(file)] ?? null;
+}
+
+function getLineNumber(source: string, index: number): number {
+  let line = 1;
+  for (let i = 0; i < index; i++) {
+    if (source[i] === '\n') {
+      line++;
+    }
+  }
+  return line;
+}
+
+function resolveScriptImport(
+  file: string,
+  specifier: string,
+  files: Set<string>
+): string | null {
+  if (!specifier.startsWith('.')) {
+    return null;
+  }
+
+  const base = path.posix.normalize(
+    path.posix.join(path.posix.dirname(file), specifier)
+  );
+  // ESM sources import `./x.js` for `./x.ts`
+  const stem = base.replace(/\.(?:js|jsx|mjs|cjs)$/, '');
+  const candidates = [
+    base,
+    ...SCRIPT_EXTENSIONS.map((extension) => `${stem}${extension}`),
+    ...SCRIPT_EXTENSIONS.map((extension) => `${base}/index${extension}`),
+  ];
+  return candidates.find((candidate) => files.has(candidate)) ?? null;
+}
+
+function extractScriptImports(
+  file: string,
+  source: string,
+  files: Set<string>
+): ImportEdge[] {
+  const edges: ImportEdge[] = [];
+  const patterns = [
+    // Type-only imports are erased at compile time and cannot form a cycle
+    /\b(?:import|export)\s+(type\s+)?(?:[\w$*{}\s,]+?\s*from\s*)?['"]([^'"\n]+)['"]/g,
+    /\b(?:require|import)\s*\(\s*()['"]([^'"\n]+)['"]\s*\)/g,
+  ];
+
+  for (const pattern of patterns) {
+    for (const match of source.matchAll(pattern)) {
+      const specifier = match[2];
+      if (match[1] || !specifier) {
+        continue;
+      }
+      const target = resolveScriptImport(file, specifier, files);
+      if (target) {
+        edges.push({
+          from: file,
+          to: target,
+          line: getLineNumber(source, match.index),
+          specifier,
+        });
+      }
+    }
+  }
+  return edges;
+}
+
+function resolvePythonModule(
+  modulePath: string,
+  files: Set<string>
+): string | null {
+  const candidates = [`${modulePath}.py`, `${modulePath}/__init__.py`];
+  return candidates.find((candidate) => files.has(candidate)) ?? null;
+}
+
+function extractPythonImports(
+  file: string,
+  source: string,
+  files: Set<string>
+): ImportEdge[] {
+  const edges: ImportEdge[] = [];
+  const seen = new Set<string>();
+
+  const addEdge = (modulePaths: string[], line: number, specifier: string) => {
+    for (const modulePath of modulePaths) {
+      const target = resolvePythonModule(modulePath, files);
+      if (target) {
+        // Several names imported from one module make a single edge
+        const key = `${target}:${line}`;
+        if (target !== file && !seen.has(key)) {
+          seen.add(key);
+          edges.push({ from: file, to: target, line, specifier });
+        }
+        return;
+      }
+    }
+  };
+
+  source.split('\n').forEach((text, index) => {
+    const line = index + 1;
+    const fromImport = /^\s*from\s+(\.*)([\w.]*)\s+import\s+(.+)$/.exec(text);
+    if (fromImport) {
+      const [, dots = '', name = '', imported = ''] = fromImport;
+      const modulePath = name.replaceAll('.', '/');
+      const bases =
+        dots.length > 0
+          ? [
+              path.posix.join(
+                path.posix.dirname(file),
+                ...Array<string>(dots.length - 1).fill('..'),
+                modulePath
+              ),
+            ]
+          : PYTHON_ROOTS.map((root) => `${root}${modulePath}`);
+      const specifier = `${dots}${name}`;
+
+      // `from pkg import mod` imports the submodule when one exists
+      for (const importedName of imported.replace(/[()]/g, '').split(',')) {
+        const member = importedName.trim().split(/\s+/)[0] ?? '';
+        if (/^\w+$/.test(member)) {
+          addEdge(
+            bases.flatMap((base) => [path.posix.join(base, member), base]),
+            line,
+            specifier
+          );
+        }
+      }
+      return;
+    }
+
+    const plainImport = /^\s*import\s+([\w.,\s]+?)\s*(?:#.*)?$/.exec(text);
+    if (plainImport) {
+      for (const entry of (plainImport[1] ?? '').split(',')) {
+        const name = entry.trim().split(/\s+/)[0] ?? '';
+        if (name) {
+          const modulePath = name.replaceAll('.', '/');
+          addEdge(
+            PYTHON_ROOTS.map((root) => `${root}${modulePath}`),
+            line,
+            name
+          );
+        }
+      }
+    }
+  });
+
+  return edges;
+}
+
+function extractJavaImports(
+  file: string,
+  source: string,
+  files: Set<string>
+): ImportEdge[] {
+  const edges: ImportEdge[] = [];
+  const javaFiles = [...files].filter((candidate) =>
+    candidate.endsWith('.java')
+  );
+
+  source.split('\n').forEach((text, index) => {
+    const match = /^\s*import\s+(static\s+)?([\w.]+?)(\.\*)?\s*;/.exec(text);
+    if (!match) {
+      return;
+    }
+    const [, isStatic, name = '', wildcard] = match;
+    const segments = name.split('.');
+    // Static imports name a member of the class
+    if (isStatic && !wildcard) {
+      segments.pop();
+    }
+
+    const suffix = segments.join('/');
+    const targets =
+      wildcard && !isStatic
+        ? javaFiles.filter(
+            (candidate) =>
+              path.posix.dirname(candidate) === suffix ||
+              path.posix.dirname(candidate).endsWith(`/${suffix}`)
+          )
+        : javaFiles.filter(
+            (candidate) =>
+              candidate === `${suffix}.java` ||
+              candidate.endsWith(`/${suffix}.java`)
+          );
+
+    for (const target of targets) {
+      if (target !== file) {
+        edges.push({
+          from: file,
+          to: target,
+          line: index + 1,
+          specifier: name,
+        });
+      }
+    }
+  });
+
+  return edges;
+}
+
+/**
+ * Import edges from `file` to other workspace files. Imports of packages
+ * outside the workspace are ignored.
+ */
+export function extractImports(
+  file: string,
+  source: string,
+  files: Set<string>
+): ImportEdge[] {
+  switch (getImportLanguage(file)) {
+    case 'typescript':
+      return extractScriptImports(file, source, files);
+    case 'python':
+      return extractPythonImports(file, source, files);
+    case 'java':
+      return extractJavaImports(file, source, files);
+    default:
+      return [];
+  }
+}
+
+/**
+ * Tarjan's algorithm; components are returned with their members sorted
+ */
+export function findStronglyConnectedComponents(
+  nodes: string[],
+  edges: Array<{ from: string; to: string }>
+): string[][] {
+  const successors = new Map<string, string[]>(
+    nodes.map((node) => [node, []])
+  );
+  for (const { from, to } of edges) {
+    successors.get(from)?.push(to);
+  }
+
+  const indices = new Map<string, number>();
+  const lowLinks = new Map<string, number>();
+  const stack: string[] = [];
+  const onStack = new Set<string>();
+  const components: string[][] = [];
+  let nextIndex = 0;
+
+  const visit = (node: string) => {
+    indices.set(node, nextIndex);
+    lowLinks.set(node, nextIndex);
+    nextIndex++;
+    stack.push(node);
+    onStack.add(node);
+
+    for (const successor of successors.get(node) ?? []) {
+      if (!indices.has(successor)) {
+        visit(successor);
+        lowLinks.set(
+          node,
+          Math.min(lowLinks.get(node) ?? 0, lowLinks.get(successor) ?? 0)
+        );
+      } else if (onStack.has(successor)) {
+        lowLinks.set(
+          node,
+          Math.min(lowLinks.get(node) ?? 0, indices.get(successor) ?? 0)
+        );
+      }
+    }
+
+    if (lowLinks.get(node) === indices.get(node)) {
+      const component: string[] = [];
+      let member: string | undefined;
+      do {
+        member = stack.pop();
+        if (member !== undefined) {
+          onStack.delete(member);
+          component.push(member);
+        }
+      } while (member !== undefined && member !== node);
+      components.push(component.sort());
+    }
+  };
+
+  for (const node of nodes) {
+    if (!indices.has(node)) {
+      visit(node);
+    }
+  }
+  return components;
+}
+
+/**
+ * Cycles in one language's import graph, at file level or grouped into
+ * modules of `depth` directory levels
+ */
+export function findImportCycles(
+  language: ImportLanguage,
+  edges: ImportEdge[],
+  depth?: number
+): ImportCycle[] {
+  const toNode = (file: string) =>
+    depth === undefined ? file : getComponentName(file, depth);
+  const graphEdges = edges
+    .map((edge) => ({ from: toNode(edge.from), to: toNode(edge.to), edge }))
+    .filter(({ from, to }) => from !== to);
+  const nodes = [
+    ...new Set(graphEdges.flatMap(({ from, to }) => [from, to])),
+  ].sort();
+
+  return findStronglyConnectedComponents(nodes, graphEdges)
+    .filter((component) => component.length > 1)
+    .map((members) => {
+      const memberSet = new Set(members);
+      return {
+        language,
+        members,
+        imports: graphEdges
+          .filter(({ from, to }) => memberSet.has(from) && memberSet.has(to))
+          .map(({ edge }) => edge)
+          .sort((a, b) => a.from.localeCompare(b.from) || a.line - b.line),
+      };
+    });
+}
+
+/**
+ * Build the import graph of every supported source file in the workspace
+ * and report its cycles per language
+ */
+export async function buildImportCycleReport(
+  manager: LspManager,
+  options: ImportCycleOptions
+): Promise<ImportCycleReport> {
+  const { files, truncated } = await discoverWorkspaceFiles(
+    manager,
+    options.workspace,
+    options.maxFiles
+  );
+  const fileSet = new Set(files);
+  const edgesByLanguage = new Map<ImportLanguage, ImportEdge[]>();
+  const unsupportedExtensions = new Set<string>();
+  let imports = 0;
+
+  for (const file of files) {
+    const language = getImportLanguage(file);
+    if (!language) {
+      unsupportedExtensions.add(path.posix.extname(file));
+      continue;
+    }
+
+    let source: string;
+    try {
+      source = fs.readFileSync(path.join(options.workspace, file), 'utf-8');
+    } catch (error) {
+      logger.debug('Skipping unreadable file in import graph', {
+        file,
+        error: error instanceof Error ? error.message : String(error),
+      });
+      continue;
+    }
+
+    const edges = extractImports(file, source, fileSet);
+    imports += edges.length;
+    edgesByLanguage.set(language, [
+      ...(edgesByLanguage.get(language) ?? []),
+      ...edges,
+    ]);
+  }
+
+  const cycles = [...edgesByLanguage.entries()]
+    .flatMap(([language, edges]) =>
+      findImportCycles(language, edges, options.depth)
+    )
+    .sort(
+      (a, b) =>
+        b.members.length - a.members.length ||
+        (a.members[0] ?? '').localeCompare(b.members[0] ?? '')
+    );
+
+  const toReportFile = (file: string) => toReportPath(options.workspace, file);
+  return {
+    workspace: formatReportPath(options.workspace),
+    depth: options.depth ?? null,
+    files: files.length,
+    imports,
+    cycles: cycles.map((cycle) => ({
+      ...cycle,
+      // Modules are directory names rather than paths
+      members:
+        options.depth === undefined
+          ? cycle.members.map(toReportFile)
+          : cycle.members,
+      imports: cycle.imports.map((edge) => ({
+        ...edge,
+        from: toReportFile(edge.from),
+        to: toReportFile(edge.to),
+      })),
+    })),
+    unsupportedExtensions: [...unsupportedExtensions].sort(),
+    truncated,
+  };
+}
+
+export function formatImportCycleReportMarkdown(
+  report: ImportCycleReport
+): string {
+  const graph =
+    report.depth === null
+      ? 'file graph'
+      : `module graph, ${report.depth} directory level(s) per module`;
+  const sections: string[] = [
+    `# Import cycles: ${report.workspace}`,
+    `${report.files} file(s), ${report.imports} import(s), ${report.cycles.length} cycle(s) (${graph})`,
+  ];
+  if (report.truncated) {
+    sections.push('Limits reached: the report covers part of the workspace');
+  }
+  if (report.unsupportedExtensions.length > 0) {
+    sections.push(
+      `Imports not analyzed for: ${report.unsupportedExtensions.join(', ')}`
+    );
+  }
+
+  if (report.cycles.length === 0) {
+    sections.push('No import cycles found');
+    return sections.join('\n\n');
+  }
+
+  report.cycles.forEach((cycle, index) => {
+    const lines = [
+      `## Cycle ${index + 1} (${cycle.language}, ${cycle.members.length} members)`,
+      `Members: ${cycle.members.join(', ')}`,
+      ...cycle.imports.map(
+        (edge) =>
+          `- ${edge.from}:${edge.line} -> ${edge.to} ('${edge.specifier}')`
+      ),
+    ];
+    sections.push(lines.join('\n'));
+  });
+
+  return sections.join('\n\n');
+}
+
+export function formatImportCycleReport(
+  report: ImportCycleReport,
+  format: ReportFormat
+): string {
+  return format === 'json'
+    ? JSON.stringify(report, null, 2)
+    : formatImportCycleReportMarkdown(report);
+}
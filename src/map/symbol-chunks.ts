@@ -0,0 +1,276 @@
+/**
+ * Symbol chunks - one record per declaration with its signature, docs and
+ * body, split to a size embedding models accept, for building vector
+ * search indexes from semantic units
+ */
+
+import * as path from 'path';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { SymbolKind, type FlattenedSymbol } from '../types/lsp.js';
+import { getSymbolKindName } from '../tools/utils.js';
+import { hashContent } from '../utils/content-hash.js';
+import { extractHoverDocumentation } from '../utils/hover-markdown.js';
+import { formatQualifiedName } from '../utils/qualified-names.js';
+import { isTestFile } from '../utils/test-classification.js';
+import { getDeclarationSignature } from './api-surface.js';
+import {
+  discoverWorkspaceFiles,
+  getHoverAt,
+  mapWithParallelism,
+  outlineWorkspaceFiles,
+  toReportPath,
+} from './workspace-scan.js';
+
+// Kinds chunked anywhere in a file
+const CHUNKED_KINDS = new Set<number>([
+  SymbolKind.Class,
+  SymbolKind.Interface,
+  SymbolKind.Enum,
+  SymbolKind.Struct,
+  SymbolKind.Function,
+  SymbolKind.Method,
+  SymbolKind.Constructor,
+]);
+
+// Kinds chunked only at the top level of a file
+const TOP_LEVEL_KINDS = new Set<number>([SymbolKind.Constant]);
+
+export interface SymbolChunk {
+  // `file#qualified.name` with the workspace-relative file, so ids stay
+  // stable whatever the path style; suffixed with the part number for split
+  // bodies
+  id: string;
+  name: string;
+  kind: string;
+  signature: string;
+  doc: string;
+  body: string;
+  file: string;
+  // One-based, inclusive lines the body was taken from
+  range: { startLine: number; endLine: number };
+  hash: string;
+  part: number;
+  parts: number;
+}
+
+export interface BodyLine {
+  // Zero-based line number in the file
+  line: number;
+  text: string;
+}
+
+export interface ChunkBodyPart {
+  startLine: number;
+  endLine: number;
+  text: string;
+}
+
+export interface SymbolChunkOptions {
+  workspace: string;
+  maxFiles: number;
+  // Upper bound on the body length of a single chunk
+  maxChars: number;
+  includeTests: boolean;
+  // Look up doc comments through hover, one request per symbol
+  docs: boolean;
+}
+
+export interface SymbolChunkExport {
+  files: number;
+  chunks: SymbolChunk[];
+  truncated: boolean;
+}
+
+function isChunkedSymbol(symbol: FlattenedSymbol): boolean {
+  const isTopLevel = !symbol.containerPath || symbol.containerPath.length === 0;
+  return (
+    CHUNKED_KINDS.has(symbol.kind) ||
+    (isTopLevel && TOP_LEVEL_KINDS.has(symbol.kind))
+  );
+}
+
+/**
+ * Lines of a symbol's body, leaving out nested symbols that get their own
+ * chunk and removing the indentation the lines share
+ */
+export function collectChunkBody(
+  lines: string[],
+  symbol: FlattenedSymbol,
+  nested: FlattenedSymbol[]
+): BodyLine[] {
+  const { start, end } = symbol.range;
+  const excluded = nested.filter(
+    (child) =>
+      child.range.start.line > start.line && child.range.end.line <= end.line
+  );
+
+  const body: BodyLine[] = [];
+  for (let line = start.line; line <= end.line && line < lines.length; line++) {
+    if (
+      excluded.some(
+        (child) =>
+          line >= child.range.start.line && line <= child.range.end.line
+      )
+    ) {
+      continue;
+    }
+    body.push({ line, text: lines[line] ?? '' });
+  }
+
+  const indents = body
+    .filter(({ text }) => text.trim().length > 0)
+    .map(({ text }) => text.length - text.trimStart().length);
+  const indent = indents.length > 0 ? Math.min(...indents) : 0;
+  return body.map(({ line, text }) => ({ line, text: text.slice(indent) }));
+}
+
+/**
+ * Split body lines into parts of at most `maxChars` characters, breaking
+ * between lines; a single longer line is cut to fit
+ */
+export function splitChunkBody(
+  body: BodyLine[],
+  maxChars: number
+): ChunkBodyPart[] {
+  const parts: ChunkBodyPart[] = [];
+  let current: BodyLine[] = [];
+  let length = 0;
+
+  const flush = () => {
+    const first = current[0];
+    const last = current.at(-1);
+    if (first && last) {
+      parts.push({
+        startLine: first.line + 1,
+        endLine: last.line + 1,
+        text: current.map(({ text }) => text).join('\n'),
+      });
+    }
+    current = [];
+    length = 0;
+  };
+
+  for (const { line, text } of body) {
+    const clipped = text.slice(0, maxChars);
+    // Joining adds a newline before every line but the first
+    if (current.length > 0 && length + 1 + clipped.length > maxChars) {
+      flush();
+    }
+    length += (current.length > 0 ? 1 : 0) + clipped.length;
+    current.push({ line, text: clipped });
+  }
+  flush();
+
+  return parts;
+}
+
+/**
+ * Chunks for every declaration in a file; `docs` holds the documentation
+ * looked up for each symbol
+ */
+export function createFileChunks(
+  file: string,
+  symbols: FlattenedSymbol[],
+  lines: string[],
+  docs: Map<FlattenedSymbol, string>,
+  maxChars: number
+): SymbolChunk[] {
+  const chunked = symbols.filter(isChunkedSymbol);
+  const idCounts = new Map<string, number>();
+  const chunks: SymbolChunk[] = [];
+
+  for (const symbol of chunked) {
+    const name = formatQualifiedName(symbol.name, symbol.containerPath, file);
+    const baseId = `${file}#${name}`;
+    // Overloads share a name; number repeats so ids stay unique
+    const count = (idCounts.get(baseId) ?? 0) + 1;
+    idCounts.set(baseId, count);
+    const id = count > 1 ? `${baseId}~${count}` : baseId;
+
+    const kind = getSymbolKindName(symbol.kind);
+    const signature = getDeclarationSignature(lines, symbol);
+    const doc = docs.get(symbol) ?? '';
+    const body = collectChunkBody(
+      lines,
+      symbol,
+      chunked.filter((other) => other !== symbol)
+    );
+    const parts = splitChunkBody(body, maxChars);
+
+    parts.forEach((part, index) => {
+      chunks.push({
+        id: parts.length > 1 ? `${id}@${index + 1}` : id,
+        name,
+        kind,
+        signature,
+        doc,
+        body: part.text,
+        file,
+        range: { startLine: part.startLine, endLine: part.endLine },
+        hash: hashContent(`${name}\n${signature}\n${doc}\n${part.text}`),
+        part: index + 1,
+        parts: parts.length,
+      });
+    });
+  }
+
+  return chunks;
+}
+
+/**
+ * Outline every source file and emit chunks for its declarations
+ */
+export async function buildSymbolChunks(
+  manager: LspManager,
+  options: SymbolChunkOptions
+): Promise<SymbolChunkExport> {
+  const { files, truncated } = await discoverWorkspaceFiles(
+    manager,
+    options.workspace,
+    options.maxFiles
+  );
+  const chunks: SymbolChunk[] = [];
+  const outlines = await outlineWorkspaceFiles(
+    manager,
+    options.workspace,
+    files.filter((file) => options.includeTests || !isTestFile(file))
+  );
+
+  for (const [file, { symbols, lines }] of outlines) {
+    const filePath = path.join(options.workspace, file);
+    const docs = new Map<FlattenedSymbol, string>();
+    if (options.docs) {
+      const documented = symbols.filter(isChunkedSymbol);
+      const hovers = await mapWithParallelism(documented, (symbol) =>
+        getHoverAt(
+          manager,
+          filePath,
+          (symbol.selectionRange ?? symbol.range).start
+        )
+      );
+      for (const [index, symbol] of documented.entries()) {
+        const hover = hovers[index];
+        const doc = hover ? extractHoverDocumentation(hover) : null;
+        if (doc) {
+          docs.set(symbol, doc);
+        }
+      }
+    }
+
+    const reportFile = toReportPath(options.workspace, file);
+    chunks.push(
+      ...createFileChunks(file, symbols, lines, docs, options.maxChars).map(
+        (chunk) => ({ ...chunk, file: reportFile })
+      )
+    );
+  }
+
+  return { files: files.length, chunks, truncated };
+}
+
+/**
+ * One JSON record per line
+ */
+export function formatSymbolChunks(chunks: SymbolChunk[]): string {
+  return chunks.map((chunk) => JSON.stringify(chunk)).join('\n');
+}
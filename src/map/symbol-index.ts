@@ -0,0 +1,456 @@
+/**
+ * Symbol index - workspace symbols persisted per workspace, so searches
+ * made while language servers start or index are answered from the last
+ * run and the index is reconciled once the servers are ready, and again
+ * after files change
+ */
+
+import { createHash } from 'crypto';
+import * as fs from 'fs';
+import * as path from 'path';
+import { pathToFileURL } from 'url';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+import type { SymbolSearchResult } from '../types/lsp.js';
+import { getAppPaths } from '../utils/app-paths.js';
+import { fuzzyMatch } from '../utils/fuzzy-match.js';
+import logger from '../utils/logger.js';
+import { validateWorkspaceReady } from '../validation.js';
+import {
+  discoverFilesWithExtensions,
+  discoverWorkspaceFiles,
+  getFileSymbols,
+  getWorkspaceExtensions,
+  isIgnoredDirectory,
+  mapWithParallelism,
+} from './workspace-scan.js';
+
+export interface SymbolIndexSettings {
+  // Index file, relative to the workspace; defaults to the data directory
+  path?: string | undefined;
+  // Source files indexed, in path order
+  max_files: number;
+}
+
+interface IndexedSymbol {
+  name: string;
+  kind: number;
+  container?: string;
+  // Start line, start character, end line, end character; 0-based
+  range: [number, number, number, number];
+}
+
+interface IndexedFile {
+  mtime_ms: number;
+  size: number;
+  symbols: IndexedSymbol[];
+}
+
+interface SymbolIndexFile {
+  version: number;
+  workspace: string;
+  updated_at: string;
+  // By workspace-relative path with forward slashes
+  files: Record<string, IndexedFile>;
+}
+
+const INDEX_VERSION = 1;
+// Cap for broad queries such as a single character
+const MAX_INDEX_RESULTS = 1000;
+// Past this many changed paths the next reconcile lists the whole
+// workspace instead
+const MAX_CHANGED_PATHS = 1000;
+
+interface SymbolIndexState {
+  settings: (SymbolIndexSettings & { file: string }) | null;
+  workspacePath: string;
+  index: SymbolIndexFile | null;
+  reconciling: Promise<void> | null;
+  // Whether the next reconcile lists the whole workspace: after loading the
+  // index, or a change that didn't say which path
+  rescan: boolean;
+  // Workspace-relative paths, with forward slashes, changed since the last
+  // reconcile started
+  changedPaths: Set<string>;
+}
+
+interface IndexUpdate {
+  files: Record<string, IndexedFile>;
+  outlined: number;
+}
+
+const getState = defineWorkspaceState<SymbolIndexState>(() => ({
+  settings: null,
+  workspacePath: process.cwd(),
+  index: null,
+  reconciling: null,
+  rescan: true,
+  changedPaths: new Set(),
+}));
+
+function getDefaultIndexFile(workspace: string): string {
+  const key = createHash('sha256').update(workspace).digest('hex');
+  return path.join(getAppPaths().data, 'index', `${key.slice(0, 16)}.json`);
+}
+
+function readIndex(file: string, workspace: string): SymbolIndexFile | null {
+  let stored: Partial<SymbolIndexFile>;
+  try {
+    stored = JSON.parse(fs.readFileSync(file, 'utf-8')) as SymbolIndexFile;
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== 'ENOENT') {
+      logger.warn('Ignoring unreadable symbol index', {
+        file,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+    return null;
+  }
+  if (stored.version !== INDEX_VERSION || stored.workspace !== workspace) {
+    return null;
+  }
+  return stored as SymbolIndexFile;
+}
+
+function writeIndex(file: string, stored: SymbolIndexFile): void {
+  const temporaryFile = `${file}.${process.pid}.tmp`;
+  try {
+    fs.mkdirSync(path.dirname(file), { recursive: true });
+    // Write then rename, so a crash mid-write keeps the previous index
+    fs.writeFileSync(temporaryFile, JSON.stringify(stored), { mode: 0o600 });
+    fs.renameSync(temporaryFile, file);
+  } catch (error) {
+    logger.error('Failed to write symbol index', {
+      file,
+      error: error instanceof Error ? error.message : String(error),
+    });
+  }
+}
+
+/**
+ * Enable or disable the index and load what a previous run saved, e.g. at
+ * start or after the config file is reloaded
+ */
+export function configureSymbolIndex(update: {
+  workspace: string;
+  index: SymbolIndexSettings | null;
+}): void {
+  const state = getState();
+  state.workspacePath = update.workspace;
+  state.settings = update.index && {
+    ...update.index,
+    file: update.index.path
+      ? path.resolve(update.workspace, update.index.path)
+      : getDefaultIndexFile(update.workspace),
+  };
+  state.index = state.settings
+    ? readIndex(state.settings.file, update.workspace)
+    : null;
+  state.rescan = true;
+  state.changedPaths.clear();
+}
+
+/**
+ * Mark a changed file or directory, or the whole workspace when given its
+ * root, for the next search with ready servers to reconcile. Writes of the
+ * index file itself and paths outside the workspace are ignored.
+ */
+export function invalidateSymbolIndex(filePath: string): void {
+  const state = getState();
+  const absolutePath = path.resolve(filePath);
+  if (!state.settings || absolutePath === state.settings.file) {
+    return;
+  }
+  const relativePath = path.relative(state.workspacePath, absolutePath);
+  if (relativePath.startsWith('..') || path.isAbsolute(relativePath)) {
+    return;
+  }
+  if (relativePath === '' || state.changedPaths.size >= MAX_CHANGED_PATHS) {
+    state.rescan = true;
+    state.changedPaths.clear();
+    return;
+  }
+  if (!state.rescan) {
+    state.changedPaths.add(relativePath.split(path.sep).join('/'));
+  }
+}
+
+/**
+ * Whether every running search server is past loading and indexing, so a
+ * live search sees the whole workspace
+ */
+export function isLiveSearchReady(manager: LspManager): boolean {
+  const sessions = manager.getStartedSessions();
+  return (
+    sessions.length > 0 &&
+    sessions.every(
+      (session) =>
+        validateWorkspaceReady(session).valid &&
+        session.getProgressStore().getActive().length === 0
+    )
+  );
+}
+
+/**
+ * Symbols from the saved index matching a query, or null without an index.
+ * Returns when the index was last updated alongside the matches.
+ */
+export function searchSymbolIndex(
+  query: string
+): { symbols: SymbolSearchResult[]; updatedAt: string } | null {
+  const { settings, workspacePath, index } = getState();
+  if (!settings || !index) {
+    return null;
+  }
+
+  const symbols: SymbolSearchResult[] = [];
+  for (const [file, entry] of Object.entries(index.files)) {
+    const uri = pathToFileURL(path.resolve(workspacePath, file)).toString();
+    for (const symbol of entry.symbols) {
+      if (query && !fuzzyMatch(query, symbol.name)) {
+        continue;
+      }
+      const [startLine, startCharacter, endLine, endCharacter] = symbol.range;
+      symbols.push({
+        name: symbol.name,
+        kind: symbol.kind,
+        location: {
+          uri,
+          range: {
+            start: { line: startLine, character: startCharacter },
+            end: { line: endLine, character: endCharacter },
+          },
+        },
+        containerName: symbol.container ?? '',
+      });
+      if (symbols.length >= MAX_INDEX_RESULTS) {
+        return { symbols, updatedAt: index.updated_at };
+      }
+    }
+  }
+  return { symbols, updatedAt: index.updated_at };
+}
+
+async function indexFile(
+  manager: LspManager,
+  filePath: string
+): Promise<IndexedSymbol[]> {
+  const { symbols } = await getFileSymbols(manager, filePath);
+  return symbols.map((symbol) => ({
+    name: symbol.name,
+    kind: symbol.kind,
+    ...(symbol.containerName && { container: symbol.containerName }),
+    range: [
+      symbol.range.start.line,
+      symbol.range.start.character,
+      symbol.range.end.line,
+      symbol.range.end.character,
+    ],
+  }));
+}
+
+/**
+ * Index entries for `files`, in their order, reusing previous entries of
+ * files unchanged since and leaving out files that are gone
+ */
+async function outlineFiles(
+  manager: LspManager,
+  workspace: string,
+  previous: Record<string, IndexedFile>,
+  files: string[]
+): Promise<IndexUpdate> {
+  const updated: Record<string, IndexedFile> = {};
+  let outlined = 0;
+  const entries = await mapWithParallelism(files, async (relativePath) => {
+    const filePath = path.resolve(workspace, relativePath);
+    let stats: fs.Stats;
+    try {
+      stats = await fs.promises.stat(filePath);
+    } catch {
+      return null;
+    }
+    if (!stats.isFile()) {
+      return null;
+    }
+    const entry = previous[relativePath];
+    if (
+      entry &&
+      entry.mtime_ms === stats.mtimeMs &&
+      entry.size === stats.size
+    ) {
+      return entry;
+    }
+    outlined++;
+    return {
+      mtime_ms: stats.mtimeMs,
+      size: stats.size,
+      symbols: await indexFile(manager, filePath),
+    };
+  });
+  for (const [position, relativePath] of files.entries()) {
+    const entry = entries[position];
+    if (entry) {
+      updated[relativePath] = entry;
+    }
+  }
+  return { files: updated, outlined };
+}
+
+/**
+ * Reconcile only the changed paths: outline the source files at or under
+ * them that are new or changed, and drop indexed ones that are gone. Null
+ * when the index would pass its file limit, which only a full listing
+ * keeps in path order.
+ */
+async function reconcileChangedPaths(
+  manager: LspManager,
+  workspace: string,
+  previous: Record<string, IndexedFile>,
+  changedPaths: Set<string>,
+  maxFiles: number
+): Promise<IndexUpdate | null> {
+  const extensions = getWorkspaceExtensions(manager);
+  const kept = { ...previous };
+  const candidates = new Set<string>();
+
+  for (const changedPath of changedPaths) {
+    if (changedPath.split('/').some(isIgnoredDirectory)) {
+      continue;
+    }
+    for (const indexedPath of Object.keys(previous)) {
+      if (
+        indexedPath === changedPath ||
+        indexedPath.startsWith(`${changedPath}/`)
+      ) {
+        delete kept[indexedPath];
+        candidates.add(indexedPath);
+      }
+    }
+
+    const absolutePath = path.resolve(workspace, changedPath);
+    let stats: fs.Stats;
+    try {
+      stats = await fs.promises.stat(absolutePath);
+    } catch {
+      continue;
+    }
+    if (stats.isDirectory()) {
+      const { files } = await discoverFilesWithExtensions(
+        absolutePath,
+        extensions,
+        maxFiles
+      );
+      for (const file of files) {
+        candidates.add(`${changedPath}/${file}`);
+      }
+    } else if (
+      extensions.some((extension) => changedPath.endsWith(extension))
+    ) {
+      candidates.add(changedPath);
+    }
+  }
+
+  if (Object.keys(kept).length + candidates.size > maxFiles) {
+    return null;
+  }
+  const { files, outlined } = await outlineFiles(
+    manager,
+    workspace,
+    previous,
+    [...candidates]
+  );
+  const merged = { ...kept, ...files };
+  return {
+    files: Object.fromEntries(
+      Object.keys(merged)
+        .sort()
+        .map((relativePath) => [relativePath, merged[relativePath]!])
+    ),
+    outlined,
+  };
+}
+
+/**
+ * Index entries after reconciling `changedPaths`, or after listing the
+ * whole workspace when they are null or too many files changed
+ */
+async function buildIndexUpdate(
+  manager: LspManager,
+  workspace: string,
+  previous: Record<string, IndexedFile>,
+  changedPaths: Set<string> | null,
+  maxFiles: number
+): Promise<IndexUpdate> {
+  const update =
+    changedPaths &&
+    (await reconcileChangedPaths(
+      manager,
+      workspace,
+      previous,
+      changedPaths,
+      maxFiles
+    ));
+  if (update) {
+    return update;
+  }
+  const { files } = await discoverWorkspaceFiles(manager, workspace, maxFiles);
+  return await outlineFiles(manager, workspace, previous, files);
+}
+
+/**
+ * Bring the index up to date with the workspace: outline files that are
+ * new or changed since they were indexed and drop deleted ones. Lists the
+ * whole workspace once per configuration, then only the paths passed to
+ * invalidateSymbolIndex since; calls made while it runs wait for it.
+ */
+export async function reconcileSymbolIndex(
+  manager: LspManager
+): Promise<void> {
+  const state = getState();
+  if (!state.settings || (!state.rescan && state.changedPaths.size === 0)) {
+    return;
+  }
+  const { file, max_files: maxFiles } = state.settings;
+  const workspace = state.workspacePath;
+  state.reconciling ??= (async () => {
+    const previous = state.index?.files ?? {};
+    const { rescan, changedPaths } = state;
+    state.rescan = false;
+    state.changedPaths = new Set();
+
+    let update: IndexUpdate;
+    try {
+      update = await buildIndexUpdate(
+        manager,
+        workspace,
+        previous,
+        rescan ? null : changedPaths,
+        maxFiles
+      );
+    } catch (error) {
+      state.rescan = true;
+      throw error;
+    }
+
+    // The index may have been reconfigured while files were outlined
+    if (state.settings?.file !== file || state.workspacePath !== workspace) {
+      return;
+    }
+    state.index = {
+      version: INDEX_VERSION,
+      workspace,
+      updated_at: new Date().toISOString(),
+      files: update.files,
+    };
+    writeIndex(file, state.index);
+    logger.info('Symbol index reconciled', {
+      file,
+      files: Object.keys(update.files).length,
+      changed: rescan ? 'all' : changedPaths.size,
+      outlined: update.outlined,
+    });
+  })().finally(() => {
+    state.reconciling = null;
+  });
+  await state.reconciling;
+}
@@ -0,0 +1,387 @@
+/**
+ * Symbol lookup - one-off find, references, definition and outline queries
+ * for the CLI, printed as a list or JSON for shell scripts and CI
+ */
+
+import * as path from 'path';
+import { z } from 'zod';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import type { FlattenedSymbol } from '../types/lsp.js';
+import { createOneBasedPosition } from '../types.js';
+import {
+  prepareFileRequest,
+  prepareSymbolPositionRequest,
+} from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import {
+  extractSymbolsWithPlugin,
+  findSymbolPlugin,
+} from '../plugins/wasm-plugins.js';
+import { getSymbolKindName } from '../tools/utils.js';
+import { fuzzyMatch } from '../utils/fuzzy-match.js';
+import { formatReportPath } from '../utils/path-format.js';
+import {
+  buildSymbolQueryReport,
+  searchWorkspaceSymbols,
+  type SymbolQueryReport,
+} from './workspace-symbols.js';
+import type { ReportFormat } from './workspace-scan.js';
+import { combineCodedErrors, toCodedError } from '../utils/error-codes.js';
+
+export const LOOKUP_COMMANDS = ['find', 'refs', 'def', 'outline'] as const;
+
+export type LookupCommand = (typeof LOOKUP_COMMANDS)[number];
+
+export interface FilePosition {
+  file: string;
+  // 1-based
+  line: number;
+  character: number;
+}
+
+export interface LookupLocation {
+  // Workspace-relative, forward slashes
+  file: string;
+  line: number;
+  character: number;
+  name?: string;
+  kind?: string;
+  container?: string;
+}
+
+export interface LookupReport {
+  command: LookupCommand;
+  // Name, file or file:line:character that was looked up
+  target: string;
+  total: number;
+  locations: LookupLocation[];
+  truncated: boolean;
+  errors: string[];
+}
+
+const LOOKUP_TITLES: Record<LookupCommand, string> = {
+  find: 'Symbols matching',
+  refs: 'References to',
+  def: 'Definition of',
+  outline: 'Outline of',
+};
+
+/**
+ * Parse a 1-based `file:line:character`. Splits from the end, so paths
+ * with colons such as Windows drives keep them.
+ */
+export function parseFilePosition(value: string): FilePosition {
+  const match = /^(.+):(\d+):(\d+)$/.exec(value);
+  if (!match) {
+    throw new Error(`Expected file:line:character, got ${value}`);
+  }
+  const line = Number(match[2]);
+  const character = Number(match[3]);
+  if (line < 1 || character < 1) {
+    throw new Error(`Line and character are 1-based, got ${value}`);
+  }
+  return { file: match[1]!, line, character };
+}
+
+function createReport(
+  command: LookupCommand,
+  target: string,
+  locations: LookupLocation[],
+  limit: number,
+  errors: string[] = []
+): LookupReport {
+  return {
+    command,
+    target,
+    total: locations.length,
+    locations: locations.slice(0, limit),
+    truncated: locations.length > limit,
+    errors,
+  };
+}
+
+async function preparePosition(manager: LspManager, position: FilePosition) {
+  const session = await manager.getSessionForFile(position.file);
+  const prepared = await prepareSymbolPositionRequest(session, {
+    file: position.file,
+    position: createOneBasedPosition(position.line, position.character),
+  });
+  if (!prepared.ok) throw toCodedError(prepared.error);
+  return { session, prepared: prepared.data };
+}
+
+function formatTarget(position: FilePosition): string {
+  return `${formatReportPath(position.file)}:${position.line}:${position.character}`;
+}
+
+/**
+ * Workspace symbols whose name fuzzily matches `name`, best matches first
+ * (find command)
+ */
+export async function findSymbolsByName(
+  manager: LspManager,
+  name: string,
+  limit: number
+): Promise<LookupReport> {
+  const search = await searchWorkspaceSymbols(manager, name);
+  if (search.symbols.length === 0 && search.errors.length > 0) {
+    throw combineCodedErrors(search.errors);
+  }
+
+  const locations = search.symbols
+    .map((symbol) => ({ symbol, match: fuzzyMatch(name, symbol.name) }))
+    .filter(({ match }) => match !== null)
+    .map(({ symbol, match }) => ({
+      score: match!.score,
+      location: {
+        file: formatReportPath(symbol.location.uri),
+        line: symbol.location.range.start.line + 1,
+        character: symbol.location.range.start.character + 1,
+        name: symbol.name,
+        kind: getSymbolKindName(symbol.kind),
+        ...(symbol.containerName && { container: symbol.containerName }),
+      },
+    }))
+    .sort(
+      (a, b) =>
+        b.score - a.score ||
+        a.location.file.localeCompare(b.location.file) ||
+        a.location.line - b.location.line
+    )
+    .map(({ location }) => location);
+
+  return createReport(
+    'find',
+    name,
+    locations,
+    limit,
+    search.errors.map((error) => error.message)
+  );
+}
+
+/**
+ * References to the symbol at a position, declaration included (refs
+ * command)
+ */
+export async function findReferencesFrom(
+  manager: LspManager,
+  position: FilePosition,
+  limit: number
+): Promise<LookupReport> {
+  const { session, prepared } = await preparePosition(manager, position);
+  const result = await LspOperations.findReferences(session, prepared, {
+    includeDeclaration: true,
+  });
+  if (!result.ok) throw toCodedError(result.error);
+
+  const locations = result.data.result
+    .map((reference) => ({
+      file: formatReportPath(reference.uri),
+      line: reference.line,
+      character: reference.character,
+    }))
+    .sort(
+      (a, b) =>
+        a.file.localeCompare(b.file) ||
+        a.line - b.line ||
+        a.character - b.character
+    );
+
+  return createReport('refs', formatTarget(position), locations, limit);
+}
+
+/**
+ * Where the symbol at a position is defined (def command)
+ */
+export async function findDefinitionsFrom(
+  manager: LspManager,
+  position: FilePosition,
+  limit: number
+): Promise<LookupReport> {
+  const { session, prepared } = await preparePosition(manager, position);
+  const result = await LspOperations.findLocations(
+    session,
+    prepared,
+    'textDocument/definition'
+  );
+  if (!result.ok) throw toCodedError(result.error);
+
+  // findLocations already returns 1-based positions
+  const locations = result.data.result.map((location) => ({
+    file: formatReportPath(location.uri),
+    line: location.range.start.line,
+    character: location.range.start.character,
+  }));
+
+  return createReport('def', formatTarget(position), locations, limit);
+}
+
+/**
+ * Document symbols of a file, from a WASM plugin when one handles its
+ * extension
+ */
+export async function getOutlineSymbols(
+  manager: LspManager,
+  file: string
+): Promise<FlattenedSymbol[]> {
+  const plugin = findSymbolPlugin(file);
+  if (plugin) {
+    return await extractSymbolsWithPlugin(plugin, file);
+  }
+
+  const session = await manager.getSessionForFile(file);
+  const prepared = prepareFileRequest(session, { file });
+  if (!prepared.ok) throw toCodedError(prepared.error);
+  const result = await LspOperations.outlineSymbols(session, prepared.data);
+  if (!result.ok) throw toCodedError(result.error);
+  return result.data;
+}
+
+/**
+ * Symbols declared in a file, in document order (outline command)
+ */
+export async function outlineFile(
+  manager: LspManager,
+  file: string,
+  limit: number
+): Promise<LookupReport> {
+  const symbols = await getOutlineSymbols(manager, file);
+  const displayPath = formatReportPath(file);
+
+  const locations = symbols.map((symbol) => {
+    const start = (symbol.selectionRange ?? symbol.range).start;
+    const container = symbol.containerPath?.join('.') || symbol.containerName;
+    return {
+      file: displayPath,
+      line: start.line + 1,
+      character: start.character + 1,
+      name: symbol.name,
+      kind: getSymbolKindName(symbol.kind),
+      ...(container && { container }),
+    };
+  });
+
+  return createReport('outline', displayPath, locations, limit);
+}
+
+/**
+ * Run a lookup from the command line, with files relative to the current
+ * directory
+ */
+export async function runLookup(
+  manager: LspManager,
+  command: LookupCommand,
+  target: string,
+  limit: number
+): Promise<LookupReport> {
+  if (command === 'find') {
+    return await findSymbolsByName(manager, target, limit);
+  }
+  if (command === 'outline') {
+    return await outlineFile(manager, path.resolve(target), limit);
+  }
+
+  const position = parseFilePosition(target);
+  position.file = path.resolve(position.file);
+  return command === 'refs'
+    ? await findReferencesFrom(manager, position, limit)
+    : await findDefinitionsFrom(manager, position, limit);
+}
+
+const BatchQuerySchema = z.object({
+  // Echoed back so callers can match results to queries
+  id: z.union([z.string(), z.number()]).optional(),
+  command: z.enum([...LOOKUP_COMMANDS, 'query']),
+  target: z.string().min(1),
+  limit: z.number().int().positive().optional(),
+});
+
+export type BatchQuery = z.infer<typeof BatchQuerySchema>;
+
+export type BatchResult = { id?: string | number } & (
+  | { result: LookupReport | SymbolQueryReport }
+  | { error: string }
+);
+
+/**
+ * Answer one NDJSON line of the batch command. Malformed lines and failed
+ * lookups become an error result, so one bad query doesn't end the batch.
+ */
+export async function runBatchQuery(
+  manager: LspManager,
+  line: string,
+  defaultLimit: number
+): Promise<BatchResult> {
+  let query: BatchQuery;
+  try {
+    query = BatchQuerySchema.parse(JSON.parse(line));
+  } catch (error) {
+    const message =
+      error instanceof z.ZodError
+        ? error.issues
+            .map((issue) => `${issue.path.join('.')}: ${issue.message}`)
+            .join('; ')
+        : `Invalid JSON: ${error instanceof Error ? error.message : String(error)}`;
+    return { error: message };
+  }
+
+  const id = query.id !== undefined ? { id: query.id } : {};
+  const limit = query.limit ?? defaultLimit;
+  try {
+    const result =
+      query.command === 'query'
+        ? await buildSymbolQueryReport(manager, query.target, limit)
+        : await runLookup(manager, query.command, query.target, limit);
+    return { ...id, result };
+  } catch (error) {
+    return {
+      ...id,
+      error: error instanceof Error ? error.message : String(error),
+    };
+  }
+}
+
+/**
+ * One `file:line:character` per line, followed by the kind and name when
+ * known, so the output can be piped to grep or an editor
+ */
+export function formatLookupReportMarkdown(report: LookupReport): string {
+  const sections: string[] = [
+    `# ${LOOKUP_TITLES[report.command]} ${report.target}`,
+    `${report.total} location(s)`,
+  ];
+  if (report.truncated) {
+    sections.push(`Showing the first ${report.locations.length}`);
+  }
+  if (report.errors.length > 0) {
+    sections.push(
+      `Warnings:\n${report.errors.map((error) => `- ${error}`).join('\n')}`
+    );
+  }
+  if (report.locations.length > 0) {
+    sections.push(
+      report.locations
+        .map((location) => {
+          const position = `${location.file}:${location.line}:${location.character}`;
+          if (!location.name) {
+            return `- ${position}`;
+          }
+          const name = location.container
+            ? `${location.container}.${location.name}`
+            : location.name;
+          return `- ${position} ${location.kind} ${name}`;
+        })
+        .join('\n')
+    );
+  }
+  return sections.join('\n\n');
+}
+
+export function formatLookupReport(
+  report: LookupReport,
+  format: ReportFormat
+): string {
+  return format === 'json'
+    ? JSON.stringify(report, null, 2)
+    : formatLookupReportMarkdown(report);
+}
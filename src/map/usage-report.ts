@@ -0,0 +1,394 @@
+/**
+ * Usage report - most-referenced symbols, module fan-in/fan-out and hotspot
+ * files that combine reference counts with git churn
+ */
+
+import * as path from 'path';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { SymbolKind } from '../types/lsp.js';
+import { getSymbolKindName } from '../tools/utils.js';
+import { formatReportPath } from '../utils/path-format.js';
+import { formatQualifiedName } from '../utils/qualified-names.js';
+import { isTestFile } from '../utils/test-classification.js';
+import { getComponentName } from './architecture-map.js';
+import {
+  containsPosition,
+  discoverWorkspaceFiles,
+  findReferencesAt,
+  outlineWorkspaceFiles,
+  toReportPath,
+  toWorkspacePath,
+} from './workspace-scan.js';
+
+export const USAGE_FORMATS = ['markdown', 'json', 'csv'] as const;
+
+export type UsageFormat = (typeof USAGE_FORMATS)[number];
+
+const COUNTED_KINDS = new Set<number>([
+  SymbolKind.Class,
+  SymbolKind.Method,
+  SymbolKind.Function,
+  SymbolKind.Interface,
+  SymbolKind.Enum,
+  SymbolKind.Struct,
+  SymbolKind.Constant,
+]);
+
+export interface SymbolUsage {
+  name: string;
+  kind: string;
+  file: string;
+  line: number;
+  references: number;
+  // References from other modules
+  externalReferences: number;
+}
+
+export interface ModuleUsage {
+  module: string;
+  // Distinct modules that use this one / that this one uses
+  fanIn: number;
+  fanOut: number;
+  incomingReferences: number;
+  outgoingReferences: number;
+}
+
+export interface FileHotspot {
+  file: string;
+  references: number;
+  commits: number;
+  score: number;
+}
+
+export interface UsageReport {
+  workspace: string;
+  since: string;
+  symbols: SymbolUsage[];
+  modules: ModuleUsage[];
+  hotspots: FileHotspot[];
+  truncated: boolean;
+}
+
+export interface UsageReportOptions {
+  workspace: string;
+  depth: number;
+  maxFiles: number;
+  maxSymbols: number;
+  // Git date the churn window starts at, e.g. `90 days ago`
+  since: string;
+  churn: Map<string, number>;
+}
+
+export interface ModuleEdge {
+  from: string;
+  to: string;
+}
+
+/**
+ * Fan-in and fan-out per module from individual cross-reference edges
+ */
+export function computeModuleUsage(
+  modules: string[],
+  edges: ModuleEdge[]
+): ModuleUsage[] {
+  const usage = new Map(
+    modules.map((name) => [
+      name,
+      {
+        users: new Set<string>(),
+        uses: new Set<string>(),
+        incoming: 0,
+        outgoing: 0,
+      },
+    ])
+  );
+
+  for (const { from, to } of edges) {
+    if (from === to) {
+      continue;
+    }
+    const source = usage.get(from);
+    const target = usage.get(to);
+    if (source) {
+      source.uses.add(to);
+      source.outgoing++;
+    }
+    if (target) {
+      target.users.add(from);
+      target.incoming++;
+    }
+  }
+
+  return [...usage.entries()]
+    .map(([name, entry]) => ({
+      module: name,
+      fanIn: entry.users.size,
+      fanOut: entry.uses.size,
+      incomingReferences: entry.incoming,
+      outgoingReferences: entry.outgoing,
+    }))
+    .sort(
+      (a, b) =>
+        b.fanIn + b.fanOut - (a.fanIn + a.fanOut) ||
+        a.module.localeCompare(b.module)
+    );
+}
+
+/**
+ * Files ranked by incoming references multiplied by recent commits; files
+ * missing either signal are not hotspots
+ */
+export function computeHotspots(
+  referencesByFile: Map<string, number>,
+  churn: Map<string, number>
+): FileHotspot[] {
+  return [...referencesByFile.entries()]
+    .map(([file, references]) => {
+      const commits = churn.get(file) ?? 0;
+      return { file, references, commits, score: references * commits };
+    })
+    .filter((hotspot) => hotspot.score > 0)
+    .sort((a, b) => b.score - a.score || a.file.localeCompare(b.file));
+}
+
+/**
+ * Count references to every declaration in non-test files and aggregate
+ * them per module and per file
+ */
+export async function buildUsageReport(
+  manager: LspManager,
+  options: UsageReportOptions
+): Promise<UsageReport> {
+  const { files, truncated: filesTruncated } = await discoverWorkspaceFiles(
+    manager,
+    options.workspace,
+    options.maxFiles
+  );
+
+  const symbols: SymbolUsage[] = [];
+  const edges: ModuleEdge[] = [];
+  const referencesByFile = new Map<string, number>();
+  const modules = new Set<string>();
+  let symbolsTruncated = false;
+  const outlines = await outlineWorkspaceFiles(
+    manager,
+    options.workspace,
+    files.filter((file) => !isTestFile(file))
+  );
+
+  for (const file of files) {
+    const fileModule = getComponentName(file, options.depth);
+    modules.add(fileModule);
+    if (isTestFile(file)) {
+      continue;
+    }
+
+    const filePath = path.join(options.workspace, file);
+    for (const symbol of outlines.get(file)?.symbols ?? []) {
+      if (!COUNTED_KINDS.has(symbol.kind)) {
+        continue;
+      }
+      if (symbols.length >= options.maxSymbols) {
+        symbolsTruncated = true;
+        break;
+      }
+
+      const start = (symbol.selectionRange ?? symbol.range).start;
+      const references =
+        (await findReferencesAt(manager, filePath, start)) ?? [];
+      let count = 0;
+      let external = 0;
+
+      for (const reference of references) {
+        const referenceFile = toWorkspacePath(options.workspace, reference.uri);
+        if (
+          referenceFile === null ||
+          (referenceFile === file &&
+            containsPosition(symbol.range, reference.range.start))
+        ) {
+          continue;
+        }
+        count++;
+        const referenceModule = getComponentName(referenceFile, options.depth);
+        modules.add(referenceModule);
+        edges.push({ from: referenceModule, to: fileModule });
+        if (referenceModule !== fileModule) {
+          external++;
+        }
+      }
+
+      referencesByFile.set(file, (referencesByFile.get(file) ?? 0) + count);
+      symbols.push({
+        name: formatQualifiedName(symbol.name, symbol.containerPath, file),
+        kind: getSymbolKindName(symbol.kind),
+        file,
+        line: start.line + 1,
+        references: count,
+        externalReferences: external,
+      });
+    }
+  }
+
+  const toReportFile = <T extends { file: string }>(entry: T): T => ({
+    ...entry,
+    file: toReportPath(options.workspace, entry.file),
+  });
+  return {
+    workspace: formatReportPath(options.workspace),
+    since: options.since,
+    symbols: symbols
+      .sort(
+        (a, b) =>
+          b.references - a.references ||
+          a.file.localeCompare(b.file) ||
+          a.line - b.line
+      )
+      .map(toReportFile),
+    modules: computeModuleUsage([...modules], edges),
+    hotspots: computeHotspots(referencesByFile, options.churn).map(
+      toReportFile
+    ),
+    truncated: filesTruncated || symbolsTruncated,
+  };
+}
+
+function escapeCsv(value: string | number): string {
+  const text = String(value);
+  return /[",\n]/.test(text) ? `"${text.replaceAll('"', '""')}"` : text;
+}
+
+/**
+ * Render the report as a single CSV table with a leading `section` column
+ */
+export function formatUsageReportCsv(report: UsageReport): string {
+  const header = [
+    'section',
+    'name',
+    'kind',
+    'file',
+    'line',
+    'references',
+    'external_references',
+    'fan_in',
+    'fan_out',
+    'commits',
+    'score',
+  ];
+  const rows: (string | number)[][] = [
+    ...report.symbols.map((symbol) => [
+      'symbol',
+      symbol.name,
+      symbol.kind,
+      symbol.file,
+      symbol.line,
+      symbol.references,
+      symbol.externalReferences,
+      '',
+      '',
+      '',
+      '',
+    ]),
+    ...report.modules.map((entry) => [
+      'module',
+      entry.module,
+      '',
+      '',
+      '',
+      entry.incomingReferences,
+      '',
+      entry.fanIn,
+      entry.fanOut,
+      '',
+      '',
+    ]),
+    ...report.hotspots.map((hotspot) => [
+      'hotspot',
+      '',
+      '',
+      hotspot.file,
+      '',
+      hotspot.references,
+      '',
+      '',
+      '',
+      hotspot.commits,
+      hotspot.score,
+    ]),
+  ];
+
+  return [header, ...rows]
+    .map((row) => row.map((value) => escapeCsv(value)).join(','))
+    .join('\n');
+}
+
+function formatSection(
+  heading: string,
+  lines: string[],
+  emptyMessage: string
+): string {
+  return [heading, ...(lines.length > 0 ? lines : [emptyMessage])].join('\n');
+}
+
+/**
+ * Render the top entries of each table as markdown
+ */
+export function formatUsageReportMarkdown(
+  report: UsageReport,
+  top: number
+): string {
+  const sections: string[] = [`# Usage report: ${report.workspace}`];
+  if (report.truncated) {
+    sections.push('Limits reached: the report covers part of the workspace');
+  }
+
+  const symbolLines = report.symbols
+    .slice(0, top)
+    .map(
+      (symbol) =>
+        `- ${symbol.name} (${symbol.kind}) - ${symbol.references} reference(s), ${symbol.externalReferences} from other modules - ${symbol.file}:${symbol.line}`
+    );
+  sections.push(
+    formatSection('## Most referenced symbols', symbolLines, 'No symbols found')
+  );
+
+  const moduleLines = report.modules.map(
+    (entry) =>
+      `- ${entry.module} - fan-in ${entry.fanIn}, fan-out ${entry.fanOut} (${entry.incomingReferences} in, ${entry.outgoingReferences} out)`
+  );
+  sections.push(formatSection('## Modules', moduleLines, 'No modules found'));
+
+  const hotspotLines = report.hotspots
+    .slice(0, top)
+    .map(
+      (hotspot) =>
+        `- ${hotspot.file} - score ${hotspot.score} (${hotspot.references} reference(s) x ${hotspot.commits} commit(s))`
+    );
+  sections.push(
+    formatSection(
+      `## Hotspots (commits since ${report.since})`,
+      hotspotLines,
+      'No files with both references and recent commits'
+    )
+  );
+
+  return sections.join('\n\n');
+}
+
+/**
+ * Render the report in the requested format; JSON and CSV include every
+ * entry, markdown the top `top` symbols and hotspots
+ */
+export function formatUsageReport(
+  report: UsageReport,
+  format: UsageFormat,
+  top: number
+): string {
+  switch (format) {
+    case 'json':
+      return JSON.stringify(report, null, 2);
+    case 'csv':
+      return formatUsageReportCsv(report);
+    default:
+      return formatUsageReportMarkdown(report, top);
+  }
+}
@@ -0,0 +1,168 @@
+/**
+ * Workspace diagnostics - run the language servers' checks across the
+ * project and aggregate what they report, like a compiler pass
+ */
+
+import * as path from 'path';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { findSymbolPlugin } from '../plugins/wasm-plugins.js';
+import type { DiagnosticEntry } from '../types.js';
+import { prepareFileRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { compileCodeOwnersPattern } from '../utils/code-owners.js';
+import logger from '../utils/logger.js';
+import {
+  discoverWorkspaceFiles,
+  mapWithParallelism,
+  toReportPath,
+  toWorkspacePath,
+} from './workspace-scan.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+interface FileDiagnostics {
+  // Workspace-relative path with forward slashes
+  file: string;
+  diagnostics: DiagnosticEntry[];
+}
+
+export interface WorkspaceDiagnosticsReport {
+  // Files with diagnostics, sorted by path
+  files: FileDiagnostics[];
+  // Files opened one by one, for servers without workspace diagnostics
+  checkedFiles: number;
+  // Profiles that reported the whole workspace at once
+  pulledProfiles: string[];
+  // Failures of individual language servers or files
+  errors: string[];
+  // More files matched than were checked
+  truncated: boolean;
+}
+
+interface WorkspaceDiagnosticsOptions {
+  workspace: string;
+  // Least severe level kept, 1 (error) to 4 (hint)
+  severity: number;
+  // Workspace-relative globs; empty checks everything
+  paths: string[];
+  maxFiles: number;
+}
+
+function compareDiagnostics(a: DiagnosticEntry, b: DiagnosticEntry): number {
+  return (
+    a.range.start.line - b.range.start.line ||
+    a.range.start.character - b.range.start.character ||
+    a.severity - b.severity
+  );
+}
+
+/**
+ * Diagnostics across the workspace: pulled in one request from servers
+ * supporting `workspace/diagnostic`, otherwise gathered by opening each
+ * source file they handle, several at once
+ */
+export async function collectWorkspaceDiagnostics(
+  manager: LspManager,
+  options: WorkspaceDiagnosticsOptions
+): Promise<WorkspaceDiagnosticsReport> {
+  const matchers = options.paths.map((pattern) =>
+    compileCodeOwnersPattern(pattern)
+  );
+  const isIncluded = (file: string) =>
+    matchers.length === 0 || matchers.some((matcher) => matcher.test(file));
+
+  const byFile = new Map<string, DiagnosticEntry[]>();
+  const addDiagnostics = (file: string, diagnostics: DiagnosticEntry[]) => {
+    const kept = diagnostics.filter(
+      (diagnostic) => diagnostic.severity <= options.severity
+    );
+    if (kept.length > 0) {
+      byFile.set(file, [...(byFile.get(file) ?? []), ...kept]);
+    }
+  };
+
+  const errors: string[] = [];
+  const pulledSessions = new Set<string>();
+  const pulledProfiles: string[] = [];
+
+  for (const session of await manager.getSearchSessions()) {
+    const result = await LspOperations.workspaceDiagnostics(session);
+    if (!result.ok) {
+      errors.push(`${session.getProfile().name}: ${result.error.message}`);
+      continue;
+    }
+    if (!result.data) {
+      continue;
+    }
+
+    pulledSessions.add(session.sessionKey);
+    pulledProfiles.push(session.getProfile().name);
+    for (const [uri, diagnostics] of result.data) {
+      const file = toWorkspacePath(options.workspace, uri);
+      if (file && isIncluded(file)) {
+        addDiagnostics(file, diagnostics);
+      }
+    }
+  }
+
+  const discovered = await discoverWorkspaceFiles(
+    manager,
+    options.workspace,
+    Number.MAX_SAFE_INTEGER
+  );
+  // Files handled by a WASM plugin have no language server to ask
+  const candidates = discovered.files.filter(
+    (file) =>
+      isIncluded(file) &&
+      !findSymbolPlugin(path.join(options.workspace, file))
+  );
+
+  let checkedFiles = 0;
+  let truncated = false;
+  await mapWithParallelism(candidates, async (file) => {
+    if (truncated) {
+      return;
+    }
+    const filePath = path.join(options.workspace, file);
+    try {
+      const session = await manager.getSessionForFile(filePath);
+      if (pulledSessions.has(session.sessionKey)) {
+        return;
+      }
+      if (checkedFiles >= options.maxFiles) {
+        truncated = true;
+        return;
+      }
+      checkedFiles++;
+
+      const prepared = prepareFileRequest(session, { file: filePath });
+      if (!prepared.ok) {
+        throw toCodedError(prepared.error);
+      }
+      const result = await LspOperations.getDiagnostics(session, prepared.data);
+      if (!result.ok) {
+        throw toCodedError(result.error);
+      }
+      addDiagnostics(file, result.data);
+    } catch (error) {
+      const message = error instanceof Error ? error.message : String(error);
+      logger.warn('Skipping file in workspace diagnostics', {
+        filePath,
+        error: message,
+      });
+      errors.push(`${file}: ${message}`);
+    }
+  });
+
+  return {
+    files: [...byFile.entries()]
+      .sort(([a], [b]) => a.localeCompare(b))
+      .map(([file, diagnostics]) => ({
+        file: toReportPath(options.workspace, file),
+        diagnostics: diagnostics.sort(compareDiagnostics),
+      })),
+    checkedFiles,
+    pulledProfiles,
+    errors,
+    truncated,
+  };
+}
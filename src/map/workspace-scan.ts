@@ -0,0 +1,359 @@
+/**
+ * Workspace scan - shared helpers for workspace-wide analyses that walk
+ * source files through the configured language servers
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { glob } from 'glob';
+import type { Hover, Position, Range } from 'vscode-languageserver-protocol';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import {
+  extractSymbolsWithPlugin,
+  findSymbolPlugin,
+  getSymbolPluginExtensions,
+} from '../plugins/wasm-plugins.js';
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+import {
+  prepareFileRequest,
+  prepareSymbolPositionRequest,
+} from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import type { FlattenedSymbol, SymbolReference } from '../types/lsp.js';
+import {
+  getDeclarationPrefix,
+  inferSymbolModifiers,
+} from '../utils/symbol-modifiers.js';
+import { splitLines } from '../utils/text.js';
+import {
+  formatReportPath,
+  isVirtualUri,
+  toAbsoluteFilePath,
+} from '../utils/path-format.js';
+import logger from '../utils/logger.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+export const REPORT_FORMATS = ['markdown', 'json'] as const;
+
+export type ReportFormat = (typeof REPORT_FORMATS)[number];
+
+// Dependency and build output directories left out of scans and watches
+const IGNORED_DIRECTORY_NAMES = [
+  'node_modules',
+  'dist',
+  'build',
+  'out',
+  'target',
+  'vendor',
+  'bin',
+  'obj',
+  '__pycache__',
+];
+
+const IGNORED_DIRECTORIES = IGNORED_DIRECTORY_NAMES.map(
+  (name) => `**/${name}/**`
+);
+
+/**
+ * Whether scans skip a directory of this name: dependency and build output
+ * directories, and hidden ones such as .git
+ */
+export function isIgnoredDirectory(name: string): boolean {
+  return name.startsWith('.') || IGNORED_DIRECTORY_NAMES.includes(name);
+}
+
+export interface WorkspaceScanSettings {
+  // Per-file requests in flight at once
+  parallelism: number;
+}
+
+const DEFAULT_SCAN_SETTINGS: WorkspaceScanSettings = { parallelism: 8 };
+
+const getState = defineWorkspaceState(() => ({
+  settings: DEFAULT_SCAN_SETTINGS,
+}));
+
+/**
+ * Replace the scan settings, e.g. after the config file is reloaded
+ */
+export function configureWorkspaceScan(
+  update: WorkspaceScanSettings | null
+): void {
+  getState().settings = update ?? DEFAULT_SCAN_SETTINGS;
+}
+
+/**
+ * Run an operation for every item with at most the configured parallelism
+ * in flight, resolving to the results in item order
+ */
+export async function mapWithParallelism<T, R>(
+  items: readonly T[],
+  operation: (item: T) => Promise<R>
+): Promise<R[]> {
+  const { parallelism } = getState().settings;
+  const results = new Array<R>(items.length);
+  let next = 0;
+  const worker = async () => {
+    while (next < items.length) {
+      const index = next++;
+      results[index] = await operation(items[index] as T);
+    }
+  };
+  await Promise.all(
+    Array.from({ length: Math.min(parallelism, items.length) }, worker)
+  );
+  return results;
+}
+
+export interface WorkspaceFiles {
+  // Workspace-relative paths with forward slashes, sorted
+  files: string[];
+  truncated: boolean;
+}
+
+export interface FileSymbols {
+  symbols: FlattenedSymbol[];
+  lines: string[];
+}
+
+/**
+ * Extensions of the source files handled by any configured profile or
+ * symbol plugin
+ */
+export function getWorkspaceExtensions(manager: LspManager): string[] {
+  return [
+    ...new Set([
+      ...manager.listProfiles().flatMap((profile) => profile.extensions),
+      ...getSymbolPluginExtensions(),
+    ]),
+  ];
+}
+
+/**
+ * Source files handled by any configured profile or symbol plugin, capped
+ * at `maxFiles`
+ */
+export async function discoverWorkspaceFiles(
+  manager: LspManager,
+  workspace: string,
+  maxFiles: number
+): Promise<WorkspaceFiles> {
+  return await discoverFilesWithExtensions(
+    workspace,
+    getWorkspaceExtensions(manager),
+    maxFiles
+  );
+}
+
+/**
+ * Workspace files with any of the given extensions, capped at `maxFiles`
+ */
+export async function discoverFilesWithExtensions(
+  workspace: string,
+  extensions: string[],
+  maxFiles: number
+): Promise<WorkspaceFiles> {
+  if (extensions.length === 0) {
+    return { files: [], truncated: false };
+  }
+
+  const patterns = extensions.map((extension) => `**/*${extension}`);
+  const matches = await glob(patterns, {
+    cwd: workspace,
+    ignore: IGNORED_DIRECTORIES,
+    nodir: true,
+  });
+  const files = matches.map((file) => file.split(path.sep).join('/')).sort();
+
+  return {
+    files: files.slice(0, maxFiles),
+    truncated: files.length > maxFiles,
+  };
+}
+
+/**
+ * Document symbols and content lines for a file, from its WASM plugin when
+ * one handles its extension, empty when the file can't be outlined
+ */
+export async function getFileSymbols(
+  manager: LspManager,
+  filePath: string
+): Promise<FileSymbols> {
+  try {
+    const plugin = findSymbolPlugin(filePath);
+    if (plugin) {
+      const symbols = await extractSymbolsWithPlugin(plugin, filePath);
+      const content = await fs.promises.readFile(filePath, 'utf-8');
+      return { symbols, lines: splitLines(content) };
+    }
+
+    const session = await manager.getSessionForFile(filePath);
+    const prepared = prepareFileRequest(session, { file: filePath });
+    if (!prepared.ok) {
+      throw toCodedError(prepared.error);
+    }
+    const result = await LspOperations.outlineSymbols(session, prepared.data);
+    if (!result.ok) {
+      throw toCodedError(result.error);
+    }
+    const content = await fs.promises.readFile(filePath, 'utf-8');
+    return { symbols: result.data, lines: splitLines(content) };
+  } catch (error) {
+    logger.warn('Skipping file in workspace scan', {
+      filePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return { symbols: [], lines: [] };
+  }
+}
+
+/**
+ * Document symbols and content lines of workspace files, outlined in
+ * parallel and keyed by workspace-relative path in the order given
+ */
+export async function outlineWorkspaceFiles(
+  manager: LspManager,
+  workspace: string,
+  files: string[]
+): Promise<Map<string, FileSymbols>> {
+  const outlines = await mapWithParallelism(files, (file) =>
+    getFileSymbols(manager, path.join(workspace, file))
+  );
+  return new Map(
+    files.map((file, index) => [
+      file,
+      outlines[index] ?? { symbols: [], lines: [] },
+    ])
+  );
+}
+
+/**
+ * Top-level symbols a file exposes. Languages with visibility keywords
+ * or naming rules keep only public symbols; when nothing in the file carries
+ * visibility information every top-level symbol is treated as public.
+ */
+export function selectPublicSymbols(
+  symbols: FlattenedSymbol[],
+  lines: string[],
+  filePath: string
+): FlattenedSymbol[] {
+  const topLevel = symbols.filter(
+    (symbol) => !symbol.containerPath || symbol.containerPath.length === 0
+  );
+  const visibilities = topLevel.map(
+    (symbol) =>
+      inferSymbolModifiers(
+        getDeclarationPrefix(lines, symbol),
+        symbol.name,
+        filePath
+      ).visibility
+  );
+
+  if (visibilities.every((visibility) => visibility === undefined)) {
+    return topLevel;
+  }
+  return topLevel.filter((_, index) => visibilities[index] === 'public');
+}
+
+/**
+ * References to the symbol at a zero-based position, or null when the
+ * lookup failed
+ */
+export async function findReferencesAt(
+  manager: LspManager,
+  filePath: string,
+  position: Position
+): Promise<SymbolReference[] | null> {
+  try {
+    const session = await manager.getSessionForFile(filePath);
+    const prepared = await prepareSymbolPositionRequest(session, {
+      file: filePath,
+      position: { line: position.line + 1, character: position.character + 1 },
+    });
+    if (!prepared.ok) {
+      return null;
+    }
+    const result = await LspOperations.findReferences(session, prepared.data);
+    return result.ok ? result.data.result : null;
+  } catch (error) {
+    logger.debug('Reference lookup failed during workspace scan', {
+      filePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return null;
+  }
+}
+
+/**
+ * Hover at a zero-based position; null when the server has nothing there,
+ * undefined when the lookup failed
+ */
+export async function getHoverAt(
+  manager: LspManager,
+  filePath: string,
+  position: Position
+): Promise<Hover | null | undefined> {
+  try {
+    const session = await manager.getSessionForFile(filePath);
+    const prepared = await prepareSymbolPositionRequest(session, {
+      file: filePath,
+      position: { line: position.line + 1, character: position.character + 1 },
+    });
+    if (!prepared.ok) {
+      return undefined;
+    }
+    const result = await LspOperations.hoverSymbol(session, prepared.data);
+    return result.ok ? result.data.result : undefined;
+  } catch (error) {
+    logger.debug('Hover lookup failed during workspace scan', {
+      filePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return undefined;
+  }
+}
+
+/**
+ * Whether a range contains a position, bounds included
+ */
+export function containsPosition(range: Range, position: Position): boolean {
+  const { start, end } = range;
+  if (position.line < start.line || position.line > end.line) {
+    return false;
+  }
+  if (position.line === start.line && position.character < start.character) {
+    return false;
+  }
+  return !(position.line === end.line && position.character > end.character);
+}
+
+/**
+ * Workspace-relative path with forward slashes, or null when the file lives
+ * outside the workspace or isn't a file
+ */
+export function toWorkspacePath(
+  workspace: string,
+  pathOrUri: string
+): string | null {
+  if (isVirtualUri(pathOrUri)) {
+    return null;
+  }
+  const relativePath = path.relative(workspace, toAbsoluteFilePath(pathOrUri));
+  if (
+    relativePath === '' ||
+    relativePath === '..' ||
+    relativePath.startsWith(`..${path.sep}`) ||
+    path.isAbsolute(relativePath)
+  ) {
+    return null;
+  }
+  return relativePath.split(path.sep).join('/');
+}
+
+/**
+ * A workspace-relative file as reports show it, in the configured path
+ * style
+ */
+export function toReportPath(workspace: string, file: string): string {
+  return formatReportPath(path.join(workspace, file));
+}
@@ -0,0 +1,435 @@
+/**
+ * Workspace symbols - search every language server and narrow the results
+ * with symbol query filters, shared by the search tool and query command
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { pathToFileURL } from 'url';
+import type { Position } from 'vscode-languageserver-protocol';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import type { LspSession } from '../runtime/lsp-session.js';
+import {
+  extractSymbolsWithPlugin,
+  findSymbolPlugin,
+  getSymbolPluginExtensions,
+} from '../plugins/wasm-plugins.js';
+import type { FlattenedSymbol, SymbolSearchResult } from '../types/lsp.js';
+import { prepareWorkspaceRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { getSymbolKindName } from '../tools/utils.js';
+import { matchesOwnerFilter } from '../utils/code-owners.js';
+import { fuzzyMatch } from '../utils/fuzzy-match.js';
+import {
+  formatReportPath,
+  toAbsoluteFilePath,
+  toRelativeFilePath,
+} from '../utils/path-format.js';
+import {
+  getDeclarationPrefix,
+  inferSymbolModifiers,
+  matchesModifierFilter,
+} from '../utils/symbol-modifiers.js';
+import {
+  compareCount,
+  getServerQuery,
+  matchesSymbolQuery,
+  parseSymbolQuery,
+  type SymbolQueryFilters,
+} from '../utils/symbol-query.js';
+import { splitLines } from '../utils/text.js';
+import logger from '../utils/logger.js';
+import {
+  isLiveSearchReady,
+  reconcileSymbolIndex,
+  searchSymbolIndex,
+} from './symbol-index.js';
+import {
+  containsPosition,
+  discoverFilesWithExtensions,
+  findReferencesAt,
+  mapWithParallelism,
+  type ReportFormat,
+} from './workspace-scan.js';
+import {
+  asCodedError,
+  combineCodedErrors,
+  toCodedError,
+  type CodedError,
+} from '../utils/error-codes.js';
+
+// refs: costs one references request per candidate
+export const MAX_REFERENCE_LOOKUPS = 50;
+
+// Files searched through their WASM plugin on each live search
+const MAX_PLUGIN_FILES = 5000;
+
+export interface WorkspaceSymbolSearch {
+  symbols: SymbolSearchResult[];
+  // Failures of individual language servers
+  errors: CodedError[];
+  // Set when the symbols came from the saved index: when it was updated
+  indexedAt?: string;
+}
+
+export interface FilteredSymbols {
+  symbols: SymbolSearchResult[];
+  // Reference counts, present when the query filtered on refs:
+  references: Map<SymbolSearchResult, number>;
+}
+
+/**
+ * Run a workspace symbol query against every search session. While the
+ * servers start or index, the saved symbol index answers instead.
+ */
+export async function searchWorkspaceSymbols(
+  manager: LspManager,
+  query: string
+): Promise<WorkspaceSymbolSearch> {
+  if (!isLiveSearchReady(manager)) {
+    const indexed = searchSymbolIndex(query);
+    if (indexed) {
+      // Start the servers without holding the answer up
+      manager.getSearchSessions().catch((error: unknown) => {
+        logger.warn('Language servers failed to start behind symbol index', {
+          error: error instanceof Error ? error.message : String(error),
+        });
+      });
+      return {
+        symbols: indexed.symbols,
+        errors: [],
+        indexedAt: indexed.updatedAt,
+      };
+    }
+  }
+
+  const symbols: SymbolSearchResult[] = [];
+  const errors: CodedError[] = [];
+
+  const pluginExtensions = getSymbolPluginExtensions();
+  let sessions: LspSession[] = [];
+  try {
+    sessions = await manager.getSearchSessions();
+  } catch (error) {
+    // Plugin languages are still searched without any language server
+    if (pluginExtensions.length === 0) {
+      throw error;
+    }
+    errors.push(asCodedError(error));
+  }
+  if (pluginExtensions.length > 0) {
+    const plugged = await searchPluginSymbols(
+      manager.getStatus().workspacePath,
+      pluginExtensions,
+      query
+    );
+    symbols.push(...plugged.symbols);
+    errors.push(...plugged.errors);
+  }
+
+  const settledResults = await Promise.allSettled(
+    sessions.map(async (session) => {
+      const prepared = prepareWorkspaceRequest(session, { query });
+      if (!prepared.ok) {
+        throw toCodedError(prepared.error);
+      }
+
+      return await LspOperations.searchSymbols(session, prepared.data);
+    })
+  );
+
+  for (const settled of settledResults) {
+    if (settled.status === 'rejected') {
+      errors.push(asCodedError(settled.reason));
+      continue;
+    }
+
+    if (!settled.value.ok) {
+      errors.push(toCodedError(settled.value.error));
+      continue;
+    }
+
+    symbols.push(...settled.value.data);
+  }
+
+  if (isLiveSearchReady(manager)) {
+    reconcileSymbolIndex(manager).catch((error: unknown) => {
+      logger.warn('Failed to update symbol index', {
+        error: error instanceof Error ? error.message : String(error),
+      });
+    });
+  }
+
+  return { symbols, errors };
+}
+
+/**
+ * Symbols matching a query in the files WASM plugins extract symbols for,
+ * which no language server answers workspace searches about
+ */
+async function searchPluginSymbols(
+  workspace: string,
+  extensions: string[],
+  query: string
+): Promise<WorkspaceSymbolSearch> {
+  const { files } = await discoverFilesWithExtensions(
+    workspace,
+    extensions,
+    MAX_PLUGIN_FILES
+  );
+  const symbols: SymbolSearchResult[] = [];
+  const errors: CodedError[] = [];
+  await mapWithParallelism(files, async (file) => {
+    const filePath = path.join(workspace, file);
+    const plugin = findSymbolPlugin(filePath);
+    if (!plugin) {
+      return;
+    }
+    try {
+      const extracted = await extractSymbolsWithPlugin(plugin, filePath);
+      const uri = pathToFileURL(filePath).toString();
+      for (const symbol of extracted) {
+        if (query && !fuzzyMatch(query, symbol.name)) {
+          continue;
+        }
+        symbols.push({
+          name: symbol.name,
+          kind: symbol.kind,
+          location: { uri, range: symbol.range },
+          containerName: symbol.containerName ?? '',
+        });
+      }
+    } catch (error) {
+      // A broken plugin fails the same way for each of its files
+      const coded = asCodedError(error);
+      if (!errors.some((known) => known.message === coded.message)) {
+        errors.push(coded);
+      }
+    }
+  });
+  return { symbols, errors };
+}
+
+function toFlattenedSymbol(symbol: SymbolSearchResult): FlattenedSymbol {
+  return {
+    name: symbol.name,
+    kind: symbol.kind as FlattenedSymbol['kind'],
+    range: symbol.location.range,
+  };
+}
+
+/**
+ * Position of the symbol's name; workspace symbol ranges may start at the
+ * declaration's modifiers
+ */
+function getNamePosition(
+  lines: string[],
+  symbol: SymbolSearchResult
+): Position {
+  const start = symbol.location.range.start;
+  const index = (lines[start.line] ?? '').indexOf(
+    symbol.name,
+    start.character
+  );
+  return index >= 0 ? { line: start.line, character: index } : start;
+}
+
+/**
+ * Apply query filters to search results. Name, kind, path and owner
+ * filters are checked first; visibility reads the declaration and refs:
+ * counts references for the symbols that remain.
+ */
+export async function filterWorkspaceSymbols(
+  manager: LspManager,
+  symbols: SymbolSearchResult[],
+  filters: SymbolQueryFilters
+): Promise<FilteredSymbols> {
+  let remaining = symbols.filter(
+    (symbol) =>
+      matchesSymbolQuery(
+        {
+          name: symbol.name,
+          kind: symbol.kind,
+          path: toRelativeFilePath(symbol.location.uri)
+            .split(path.sep)
+            .join('/'),
+        },
+        filters
+      ) && matchesOwnerFilter(symbol.location.uri, filters.owners)
+  );
+  const references = new Map<SymbolSearchResult, number>();
+  if (filters.visibility.length === 0 && !filters.refs) {
+    return { symbols: remaining, references };
+  }
+
+  const linesByFile = new Map<string, string[]>();
+  const getLines = async (filePath: string) => {
+    let lines = linesByFile.get(filePath);
+    if (!lines) {
+      lines = await fs.promises
+        .readFile(filePath, 'utf-8')
+        .then(splitLines, () => []);
+      linesByFile.set(filePath, lines);
+    }
+    return lines;
+  };
+
+  if (filters.visibility.length > 0) {
+    const visible: SymbolSearchResult[] = [];
+    for (const symbol of remaining) {
+      const filePath = toAbsoluteFilePath(symbol.location.uri);
+      const prefix = getDeclarationPrefix(
+        await getLines(filePath),
+        toFlattenedSymbol(symbol)
+      );
+      const info = inferSymbolModifiers(prefix, symbol.name, filePath);
+      if (matchesModifierFilter(info, { visibility: filters.visibility })) {
+        visible.push(symbol);
+      }
+    }
+    remaining = visible;
+  }
+
+  if (filters.refs) {
+    if (remaining.length > MAX_REFERENCE_LOOKUPS) {
+      throw new Error(
+        `refs: needs a reference lookup per symbol and ${remaining.length} symbols match the other filters (limit ${MAX_REFERENCE_LOOKUPS}). Narrow the query with kind:, path: or name:.`
+      );
+    }
+
+    const counted: SymbolSearchResult[] = [];
+    for (const symbol of remaining) {
+      const filePath = toAbsoluteFilePath(symbol.location.uri);
+      const found = await findReferencesAt(
+        manager,
+        filePath,
+        getNamePosition(await getLines(filePath), symbol)
+      );
+      // Leave out the declaration itself
+      const count = (found ?? []).filter(
+        (reference) =>
+          toAbsoluteFilePath(reference.uri) !== filePath ||
+          !containsPosition(symbol.location.range, reference.range.start)
+      ).length;
+      if (found !== null && compareCount(count, filters.refs)) {
+        references.set(symbol, count);
+        counted.push(symbol);
+      }
+    }
+    remaining = counted;
+  }
+
+  return { symbols: remaining, references };
+}
+
+export interface QueriedSymbol {
+  name: string;
+  kind: string;
+  container?: string;
+  // In the configured path style; relative paths use forward slashes
+  file: string;
+  line: number;
+  references?: number;
+}
+
+export interface SymbolQueryReport {
+  query: string;
+  total: number;
+  symbols: QueriedSymbol[];
+  truncated: boolean;
+  errors: string[];
+}
+
+/**
+ * Run a query with filters against the workspace, keeping at most `limit`
+ * symbols ordered by file and line (query command)
+ */
+export async function buildSymbolQueryReport(
+  manager: LspManager,
+  query: string,
+  limit: number
+): Promise<SymbolQueryReport> {
+  const symbolQuery = parseSymbolQuery(query);
+  const search = await searchWorkspaceSymbols(
+    manager,
+    getServerQuery(symbolQuery)
+  );
+  if (search.symbols.length === 0 && search.errors.length > 0) {
+    throw combineCodedErrors(search.errors);
+  }
+
+  const filtered = await filterWorkspaceSymbols(
+    manager,
+    search.symbols,
+    symbolQuery.filters
+  );
+  const symbols = filtered.symbols
+    .map((symbol): QueriedSymbol => {
+      const references = filtered.references.get(symbol);
+      return {
+        name: symbol.name,
+        kind: getSymbolKindName(symbol.kind),
+        ...(symbol.containerName && { container: symbol.containerName }),
+        file: formatReportPath(symbol.location.uri),
+        line: symbol.location.range.start.line + 1,
+        ...(references !== undefined && { references }),
+      };
+    })
+    .sort(
+      (a, b) =>
+        a.file.localeCompare(b.file) ||
+        a.line - b.line ||
+        a.name.localeCompare(b.name)
+    );
+
+  return {
+    query,
+    total: symbols.length,
+    symbols: symbols.slice(0, limit),
+    truncated: symbols.length > limit,
+    errors: search.errors.map((error) => error.message),
+  };
+}
+
+export function formatSymbolQueryReportMarkdown(
+  report: SymbolQueryReport
+): string {
+  const sections: string[] = [
+    `# Query: ${report.query}`,
+    `${report.total} matching symbol(s)`,
+  ];
+  if (report.truncated) {
+    sections.push(`Showing the first ${report.symbols.length}`);
+  }
+  if (report.errors.length > 0) {
+    sections.push(
+      `Warnings:\n${report.errors.map((error) => `- ${error}`).join('\n')}`
+    );
+  }
+  if (report.symbols.length > 0) {
+    sections.push(
+      report.symbols
+        .map((symbol) => {
+          const name = symbol.container
+            ? `${symbol.container}.${symbol.name}`
+            : symbol.name;
+          const references =
+            symbol.references !== undefined
+              ? ` [${symbol.references} references]`
+              : '';
+          return `- ${symbol.file}:${symbol.line} ${symbol.kind} ${name}${references}`;
+        })
+        .join('\n')
+    );
+  }
+  return sections.join('\n\n');
+}
+
+export function formatSymbolQueryReport(
+  report: SymbolQueryReport,
+  format: ReportFormat
+): string {
+  return format === 'json'
+    ? JSON.stringify(report, null, 2)
+    : formatSymbolQueryReportMarkdown(report);
+}
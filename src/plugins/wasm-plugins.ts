@@ -0,0 +1,405 @@
+/**
+ * WASM plugins - WebAssembly modules loaded at runtime that extract symbols
+ * for languages without a language server or enrich tool results.
+ *
+ * A plugin exports `memory` and `alloc(size) -> ptr`, plus either or both
+ * of `extract_symbols` and `enrich`. Both take a pointer and length of
+ * UTF-8 JSON written into memory returned by `alloc`, and return an i64
+ * packing `(ptr << 32) | len` of their UTF-8 JSON output, or 0 for none.
+ *
+ * - `extract_symbols` reads `{ path, text }` and returns LSP DocumentSymbol
+ *   or SymbolInformation records
+ * - `enrich` reads `{ tool, arguments, result }` and returns the tool result
+ *
+ * Plugins get no imports, so they can't reach the file system or network,
+ * and run in a worker thread per plugin under a deadline, so one that
+ * loops forever is terminated instead of blocking the server.
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { pathToFileURL } from 'url';
+import { Worker } from 'worker_threads';
+import {
+  CallToolResultSchema,
+  type CallToolResult,
+} from '@modelcontextprotocol/sdk/types.js';
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+import { getDocumentSymbols, type FlattenedSymbol } from '../types/lsp.js';
+import { CodedError } from '../utils/error-codes.js';
+import logger from '../utils/logger.js';
+
+export interface WasmPluginConfig {
+  // Module path, relative to the workspace
+  path: string;
+  // File extensions whose symbols come from `extract_symbols`
+  extensions: string[];
+  // Tools whose results pass through `enrich`; '*' for every tool
+  tools: string[];
+  // Each call is abandoned, and the plugin's worker terminated, after this
+  timeout_ms: number;
+}
+
+interface WasmPluginExports {
+  memory: WebAssembly.Memory;
+  alloc: (size: number) => number;
+  extract_symbols?: (ptr: number, len: number) => bigint;
+  enrich?: (ptr: number, len: number) => bigint;
+}
+
+export interface WasmPlugin {
+  config: WasmPluginConfig;
+  // Absolute module path
+  modulePath: string;
+  module: WebAssembly.Module;
+  exports: string[];
+}
+
+interface WasmPluginSettings {
+  workspace: string;
+  plugins: WasmPluginConfig[];
+}
+
+interface ExtractedSymbols {
+  mtimeMs: number;
+  size: number;
+  symbols: FlattenedSymbol[];
+}
+
+interface WasmPluginState {
+  settings: WasmPluginSettings;
+  compiled: Map<string, Promise<WasmPlugin>>;
+  // By module path
+  workers: Map<string, Worker>;
+  // Last call queued for each module path; calls run one at a time
+  calls: Map<string, Promise<unknown>>;
+  // By absolute file path, oldest first
+  extracted: Map<string, ExtractedSymbols>;
+}
+
+const getState = defineWorkspaceState<WasmPluginState>(() => ({
+  settings: { workspace: process.cwd(), plugins: [] },
+  compiled: new Map(),
+  workers: new Map(),
+  calls: new Map(),
+  extracted: new Map(),
+}));
+
+// Files whose extracted symbols are kept, so searches and scans don't call
+// the plugin again for files that haven't changed
+const MAX_EXTRACTED_FILES = 20000;
+
+// Runs in the worker thread. Kept as source rather than a module of its
+// own so the worker loads the same from the build and from TypeScript.
+const WORKER_SOURCE = `
+const { parentPort } = require('worker_threads');
+const describe = (error) => (error instanceof Error ? error.message : String(error));
+parentPort.on('message', async ({ module, name, input }) => {
+  try {
+    const { exports } = await WebAssembly.instantiate(module, {});
+    const fn = exports[name];
+    if (typeof fn !== 'function') {
+      parentPort.postMessage({ failure: 'does not export ' + name });
+      return;
+    }
+    const bytes = new TextEncoder().encode(input);
+    const inputPtr = exports.alloc(bytes.length);
+    new Uint8Array(exports.memory.buffer, inputPtr, bytes.length).set(bytes);
+    let packed;
+    try {
+      packed = fn(inputPtr, bytes.length);
+    } catch (error) {
+      parentPort.postMessage({ failure: 'trapped in ' + name + ': ' + describe(error) });
+      return;
+    }
+    if (packed === 0n) {
+      parentPort.postMessage({ output: null });
+      return;
+    }
+    const outputPtr = Number(BigInt.asUintN(64, packed) >> 32n);
+    const outputLength = Number(BigInt.asUintN(32, packed));
+    if (outputPtr + outputLength > exports.memory.buffer.byteLength) {
+      parentPort.postMessage({ failure: 'returned output outside its memory from ' + name });
+      return;
+    }
+    const output = new Uint8Array(exports.memory.buffer, outputPtr, outputLength);
+    parentPort.postMessage({ output: new TextDecoder().decode(output) });
+  } catch (error) {
+    parentPort.postMessage({ failure: 'failed in ' + name + ': ' + describe(error) });
+  }
+});
+`;
+
+/**
+ * Replace the configured plugins, e.g. after the config file is reloaded
+ */
+export function configureWasmPlugins(update: WasmPluginSettings): void {
+  const state = getState();
+  state.settings = update;
+  state.compiled.clear();
+  state.calls.clear();
+  state.extracted.clear();
+  for (const worker of state.workers.values()) {
+    void worker.terminate();
+  }
+  state.workers.clear();
+}
+
+function createPluginError(modulePath: string, reason: string): CodedError {
+  return new CodedError(
+    `WASM plugin ${path.basename(modulePath)} ${reason}`,
+    'PLUGIN_FAILED',
+    'config'
+  );
+}
+
+/**
+ * Compile a plugin module once and check it exports the plugin ABI
+ */
+export async function loadWasmPlugin(
+  config: WasmPluginConfig,
+  workspace: string
+): Promise<WasmPlugin> {
+  const modulePath = path.resolve(workspace, config.path);
+  const { compiled } = getState();
+  let pending = compiled.get(modulePath);
+  if (!pending) {
+    pending = (async () => {
+      const module = await WebAssembly.compile(
+        await fs.promises.readFile(modulePath)
+      );
+      const exports = WebAssembly.Module.exports(module).map(
+        (entry) => entry.name
+      );
+      for (const required of ['memory', 'alloc']) {
+        if (!exports.includes(required)) {
+          throw createPluginError(modulePath, `does not export ${required}`);
+        }
+      }
+      if (WebAssembly.Module.imports(module).length > 0) {
+        throw createPluginError(
+          modulePath,
+          'imports host functions; plugins must be self-contained'
+        );
+      }
+      return { config, modulePath, module, exports };
+    })();
+    compiled.set(modulePath, pending);
+    // A failed compile is retried on the next call
+    void pending.catch(() => compiled.delete(modulePath));
+  }
+  return await pending;
+}
+
+interface WorkerReply {
+  // JSON text, or null when the export returned nothing
+  output?: string | null;
+  failure?: string;
+}
+
+/**
+ * Run one call in the plugin's worker, terminating the worker when the
+ * call outlives the plugin's deadline
+ */
+function runInWorker(
+  plugin: WasmPlugin,
+  name: 'extract_symbols' | 'enrich',
+  input: string
+): Promise<WorkerReply> {
+  const { workers } = getState();
+  let worker = workers.get(plugin.modulePath);
+  if (!worker) {
+    const started = new Worker(WORKER_SOURCE, { eval: true });
+    started.unref();
+    started.once('exit', () => {
+      if (workers.get(plugin.modulePath) === started) {
+        workers.delete(plugin.modulePath);
+      }
+    });
+    workers.set(plugin.modulePath, started);
+    worker = started;
+  }
+  const active = worker;
+
+  return new Promise<WorkerReply>((resolve, reject) => {
+    const fail = (reason: string) => {
+      cleanup();
+      reject(createPluginError(plugin.modulePath, reason));
+    };
+    const onMessage = (reply: WorkerReply) => {
+      cleanup();
+      resolve(reply);
+    };
+    const onError = (error: Error) =>
+      fail(`failed in ${name}: ${error.message}`);
+    const onExit = () => fail(`exited during ${name}`);
+    const timer = setTimeout(() => {
+      fail(`timed out after ${plugin.config.timeout_ms}ms in ${name}`);
+      void active.terminate();
+    }, plugin.config.timeout_ms);
+    const cleanup = () => {
+      clearTimeout(timer);
+      active.off('message', onMessage);
+      active.off('error', onError);
+      active.off('exit', onExit);
+    };
+
+    active.on('message', onMessage);
+    active.on('error', onError);
+    active.on('exit', onExit);
+    active.postMessage({ module: plugin.module, name, input });
+  });
+}
+
+/**
+ * Call a plugin export with JSON input and parse its JSON output. Calls
+ * run one at a time in a worker thread per plugin, each with a fresh
+ * instance, so plugins need not free memory and can't block the server.
+ */
+export async function callWasmPlugin(
+  plugin: WasmPlugin,
+  name: 'extract_symbols' | 'enrich',
+  input: unknown
+): Promise<unknown> {
+  const { calls } = getState();
+  const previous = calls.get(plugin.modulePath) ?? Promise.resolve();
+  const call = previous
+    .catch(() => {})
+    .then(() => runInWorker(plugin, name, JSON.stringify(input)));
+  calls.set(plugin.modulePath, call);
+
+  const reply = await call;
+  if (reply.failure !== undefined) {
+    throw createPluginError(plugin.modulePath, reply.failure);
+  }
+  if (reply.output === null || reply.output === undefined) {
+    return null;
+  }
+  try {
+    return JSON.parse(reply.output) as unknown;
+  } catch {
+    throw createPluginError(
+      plugin.modulePath,
+      `returned invalid JSON from ${name}`
+    );
+  }
+}
+
+/**
+ * Plugin config that extracts symbols for a file, if any
+ */
+export function findSymbolPlugin(filePath: string): WasmPluginConfig | null {
+  const extension = path.extname(filePath).toLowerCase();
+  return (
+    getState().settings.plugins.find((plugin) =>
+      plugin.extensions.some(
+        (candidate) => candidate.toLowerCase() === extension
+      )
+    ) ?? null
+  );
+}
+
+/**
+ * Extensions of the files plugins extract symbols for, lower case
+ */
+export function getSymbolPluginExtensions(): string[] {
+  return [
+    ...new Set(
+      getState().settings.plugins.flatMap((plugin) =>
+        plugin.extensions.map((extension) => extension.toLowerCase())
+      )
+    ),
+  ];
+}
+
+/**
+ * Symbols for a file from its plugin, flattened like documentSymbol
+ * results. Kept until the file's size or modification time changes.
+ */
+export async function extractSymbolsWithPlugin(
+  config: WasmPluginConfig,
+  filePath: string
+): Promise<FlattenedSymbol[]> {
+  const state = getState();
+  const stats = await fs.promises.stat(filePath);
+  const cached = state.extracted.get(filePath);
+  if (
+    cached &&
+    cached.mtimeMs === stats.mtimeMs &&
+    cached.size === stats.size
+  ) {
+    return cached.symbols;
+  }
+
+  const { workspace } = state.settings;
+  const plugin = await loadWasmPlugin(config, workspace);
+  const text = await fs.promises.readFile(filePath, 'utf-8');
+  const symbols = await callWasmPlugin(plugin, 'extract_symbols', {
+    path: path.relative(workspace, filePath).split(path.sep).join('/'),
+    text,
+  });
+  if (symbols !== null && !Array.isArray(symbols)) {
+    throw createPluginError(
+      plugin.modulePath,
+      'returned symbols that are not an array from extract_symbols'
+    );
+  }
+  const flattened = await getDocumentSymbols(
+    async <TResult>() => (symbols ?? []) as TResult,
+    pathToFileURL(filePath).toString()
+  );
+
+  state.extracted.delete(filePath);
+  state.extracted.set(filePath, {
+    mtimeMs: stats.mtimeMs,
+    size: stats.size,
+    symbols: flattened,
+  });
+  if (state.extracted.size > MAX_EXTRACTED_FILES) {
+    const oldest = state.extracted.keys().next();
+    if (!oldest.done) {
+      state.extracted.delete(oldest.value);
+    }
+  }
+  return flattened;
+}
+
+/**
+ * Pass a tool result through every plugin that enriches the tool, in
+ * config order
+ */
+export async function applyWasmEnrichers(
+  tool: string,
+  args: unknown,
+  result: CallToolResult
+): Promise<CallToolResult> {
+  const { settings } = getState();
+  let current = result;
+  for (const config of settings.plugins) {
+    if (!config.tools.includes(tool) && !config.tools.includes('*')) {
+      continue;
+    }
+    const plugin = await loadWasmPlugin(config, settings.workspace);
+    if (!plugin.exports.includes('enrich')) {
+      continue;
+    }
+
+    const output = await callWasmPlugin(plugin, 'enrich', {
+      tool,
+      arguments: args,
+      result: current,
+    });
+    if (output !== null) {
+      const parsed = CallToolResultSchema.safeParse(output);
+      if (!parsed.success) {
+        throw createPluginError(
+          plugin.modulePath,
+          'returned an invalid tool result from enrich'
+        );
+      }
+      current = parsed.data;
+    }
+    logger.debug('Applied WASM enricher', { tool, plugin: config.path });
+  }
+  return current;
+}
@@ -0,0 +1,126 @@
+/**
+ * Navigation prompts - guided workflows that compose the existing tools,
+ * for clients that surface MCP prompts
+ */
+
+import { z } from 'zod';
+import type { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import type { GetPromptResult } from '@modelcontextprotocol/sdk/types.js';
+import { parseFilePosition } from '../map/symbol-lookup.js';
+
+const CALL_DIRECTIONS = ['incoming', 'outgoing', 'both'] as const;
+type CallDirection = (typeof CALL_DIRECTIONS)[number];
+
+const symbolArgument = z
+  .string()
+  .min(1)
+  .describe(
+    'Symbol name, such as UserRepository.save, or its position as file:line:character'
+  );
+
+/**
+ * First step of every workflow: pin the symbol down to a position. A
+ * file:line:character target is used as is, a name is searched for.
+ */
+function locateStep(symbol: string): string {
+  try {
+    const { file, line, character } = parseFilePosition(symbol);
+    return `The symbol is at \`${file}\` line ${line}, character ${character}. Use that position with the tools below.`;
+  } catch {
+    return `Call \`search\` with the query \`${symbol}\` to find where it is declared. If several symbols match, pick the best fit and say which one you chose; if none do, stop and report it.`;
+  }
+}
+
+function toPrompt(description: string, steps: string[]): GetPromptResult {
+  const text = steps.map((step, index) => `${index + 1}. ${step}`).join('\n');
+  return {
+    description,
+    messages: [{ role: 'user', content: { type: 'text', text } }],
+  };
+}
+
+export function buildExplainSymbolPrompt(symbol: string): GetPromptResult {
+  return toPrompt(`Explain ${symbol}`, [
+    `Explain what \`${symbol}\` does and how it fits into the codebase.`,
+    locateStep(symbol),
+    'Call `inspect` at its position for the signature, docs and declaration.',
+    'Call `outline` on its file to see the surrounding types and members.',
+    'Call `references` in `summary` mode to see how widely it is used, and `call_hierarchy` if it is callable.',
+    'Answer with its purpose, its inputs and outputs, notable side effects and its main callers. Cite file:line locations.',
+  ]);
+}
+
+export function buildCallGraphPrompt(
+  symbol: string,
+  direction: CallDirection = 'both',
+  depth = 2
+): GetPromptResult {
+  return toPrompt(`Map the call graph of ${symbol}`, [
+    `Map the call graph around \`${symbol}\`.`,
+    locateStep(symbol),
+    `Call \`call_hierarchy\` at its position with direction \`${direction}\` and depth ${depth}.`,
+    'Where a branch stops at the depth limit and looks important, call `call_hierarchy` again from that call site.',
+    'Draw the graph as an indented tree or a Mermaid flowchart, grouping calls by file, and point out entry points, cycles and calls into other modules.',
+  ]);
+}
+
+export function buildUsageSummaryPrompt(symbol: string): GetPromptResult {
+  return toPrompt(`Summarize the usages of ${symbol}`, [
+    `Find every usage of \`${symbol}\` and summarize them.`,
+    locateStep(symbol),
+    'Call `references` at its position, following the offset from any truncation marker until every reference is listed. For very widely used symbols start with `summary` mode and narrow with `directory`.',
+    'Read the surrounding code where a usage is not obvious from its line, using `outline` or `selection_range`.',
+    'Group the usages by how they use the symbol, such as construction, configuration, reads and writes, or overrides, and by module. Note tests separately and call out inconsistent or surprising usages.',
+  ]);
+}
+
+export function registerNavigationPrompts(server: McpServer): void {
+  server.registerPrompt(
+    'explain_symbol',
+    {
+      title: 'Explain Symbol',
+      description:
+        'Explain what a symbol does using its docs, surrounding outline, references and callers.',
+      argsSchema: { symbol: symbolArgument },
+    },
+    ({ symbol }) => buildExplainSymbolPrompt(symbol)
+  );
+
+  server.registerPrompt(
+    'map_call_graph',
+    {
+      title: 'Map Call Graph',
+      description:
+        'Map the callers and callees of a function through the call hierarchy.',
+      argsSchema: {
+        symbol: symbolArgument,
+        direction: z
+          .enum(CALL_DIRECTIONS)
+          .optional()
+          .describe('incoming, outgoing or both. Defaults to both.'),
+        depth: z
+          .string()
+          .regex(/^[1-9]\d*$/, 'Expected a positive integer')
+          .optional()
+          .describe('How many levels of calls to follow. Defaults to 2.'),
+      },
+    },
+    ({ symbol, direction, depth }) =>
+      buildCallGraphPrompt(
+        symbol,
+        direction,
+        depth ? Number(depth) : undefined
+      )
+  );
+
+  server.registerPrompt(
+    'summarize_usages',
+    {
+      title: 'Summarize Usages',
+      description:
+        'Find all references to a symbol and summarize how it is used across the codebase.',
+      argsSchema: { symbol: symbolArgument },
+    },
+    ({ symbol }) => buildUsageSummaryPrompt(symbol)
+  );
+}
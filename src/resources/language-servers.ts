@@ -10,6 +10,10 @@ import type {
 } from '../runtime/lsp-manager.js';
 import * as LspOperations from '../lsp/operations/index.js';
 import { formatWindowLogMessages } from '../utils/window-logs.js';
+import {
+  classifyErrorMessage,
+  type ErrorCategory,
+} from '../utils/error-codes.js';
 
 interface LanguageServerStatusResource {
   manager: {
@@ -46,6 +50,8 @@ interface LanguageServerProfileResource {
     state: string;
     pid: number | null;
     lastError: string | null;
+    lastErrorCode: string | null;
+    lastErrorCategory: ErrorCategory | null;
     workspaceReady: boolean | null;
     workspaceLoading: boolean | null;
     windowLogCount: number;
@@ -63,6 +69,10 @@ interface LanguageServerProfileDetailResource
 function toLanguageServerProfileResource(
   profile: LspManagerProfileStatus
 ): LanguageServerProfileResource {
+  const lastErrorClassification = profile.lastError
+    ? classifyErrorMessage(profile.lastError)
+    : null;
+
   return {
     name: profile.name,
     config: {
@@ -85,6 +95,8 @@ function toLanguageServerProfileResource(
       state: profile.state,
       pid: profile.pid,
       lastError: profile.lastError,
+      lastErrorCode: lastErrorClassification?.code ?? null,
+      lastErrorCategory: lastErrorClassification?.category ?? null,
       workspaceReady: profile.workspaceReady,
       workspaceLoading: profile.workspaceLoading,
       windowLogCount: profile.windowLogCount,
@@ -0,0 +1,306 @@
+import * as fs from 'fs';
+import * as path from 'path';
+import {
+  McpError,
+  ErrorCode,
+  SubscribeRequestSchema,
+  UnsubscribeRequestSchema,
+} from '@modelcontextprotocol/sdk/types.js';
+import {
+  McpServer,
+  ResourceTemplate,
+} from '@modelcontextprotocol/sdk/server/mcp.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import {
+  discoverWorkspaceFiles,
+  toWorkspacePath,
+} from '../map/workspace-scan.js';
+import { outlineFile } from '../map/symbol-lookup.js';
+import { findSymbolPlugin } from '../plugins/wasm-plugins.js';
+import { prepareFileRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { sortDiagnostics, toDiagnosticOutput } from '../tools/diagnostics.js';
+import logger from '../utils/logger.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+const RESOURCE_PREFIXES = {
+  files: 'workspace://files/',
+  symbols: 'workspace://symbols/',
+  diagnostics: 'workspace://diagnostics/',
+} as const;
+
+// Listing globs the whole workspace, so cap how many files it returns
+const MAX_LISTED_FILES = 1000;
+
+// Outlines are read whole rather than paged
+const MAX_OUTLINE_SYMBOLS = 10000;
+
+// Editors often write a file in several steps; notify once they settle
+const CHANGE_DEBOUNCE_MS = 100;
+
+type WorkspaceResourceKind = keyof typeof RESOURCE_PREFIXES;
+
+/**
+ * Resource URI of a workspace-relative file, e.g.
+ * workspace://files/src/index.ts
+ */
+export function toWorkspaceResourceUri(
+  kind: WorkspaceResourceKind,
+  relativePath: string
+): string {
+  const encoded = relativePath.split('/').map(encodeURIComponent).join('/');
+  return `${RESOURCE_PREFIXES[kind]}${encoded}`;
+}
+
+/**
+ * Absolute path of the file a workspace resource URI names. Throws for
+ * other URIs and for paths that leave the workspace.
+ */
+export function resolveWorkspaceResourceUri(
+  workspace: string,
+  uri: string
+): string {
+  const prefix = Object.values(RESOURCE_PREFIXES).find((candidate) =>
+    uri.startsWith(candidate)
+  );
+  if (!prefix) {
+    throw new McpError(ErrorCode.InvalidParams, `Unknown resource: ${uri}`);
+  }
+
+  let relativePath: string;
+  try {
+    relativePath = decodeURIComponent(uri.slice(prefix.length));
+  } catch {
+    throw new McpError(ErrorCode.InvalidParams, `Invalid resource: ${uri}`);
+  }
+  const filePath = path.resolve(workspace, relativePath);
+  if (toWorkspacePath(workspace, filePath) === null) {
+    throw new McpError(
+      ErrorCode.InvalidParams,
+      `Resource is outside the workspace: ${uri}`
+    );
+  }
+  return filePath;
+}
+
+function getWorkspaceFile(manager: LspManager, uri: string): string {
+  const filePath = resolveWorkspaceResourceUri(
+    manager.getStatus().workspacePath,
+    uri
+  );
+  if (!fs.existsSync(filePath) || !fs.statSync(filePath).isFile()) {
+    throw new McpError(ErrorCode.InvalidParams, `File not found: ${uri}`);
+  }
+  return filePath;
+}
+
+async function listWorkspaceFiles(
+  manager: LspManager,
+  kind: WorkspaceResourceKind
+) {
+  const { workspacePath } = manager.getStatus();
+  const { files } = await discoverWorkspaceFiles(
+    manager,
+    workspacePath,
+    MAX_LISTED_FILES
+  );
+  // Files handled by a WASM plugin have symbols but no language server
+  // reporting diagnostics
+  const listed =
+    kind === 'diagnostics'
+      ? files.filter(
+          (file) => !findSymbolPlugin(path.join(workspacePath, file))
+        )
+      : files;
+  return {
+    resources: listed.map((file) => ({
+      uri: toWorkspaceResourceUri(kind, file),
+      name: file,
+    })),
+  };
+}
+
+/**
+ * Diagnostics of a file as the language server reports them for its
+ * current content on disk, most severe first
+ */
+async function readFileDiagnostics(manager: LspManager, filePath: string) {
+  const session = await manager.getSessionForFile(filePath);
+  const prepared = prepareFileRequest(session, { file: filePath });
+  if (!prepared.ok) throw toCodedError(prepared.error);
+
+  const result = await LspOperations.getDiagnostics(session, prepared.data);
+  if (!result.ok) throw toCodedError(result.error);
+
+  return sortDiagnostics(result.data).map(toDiagnosticOutput);
+}
+
+/**
+ * Watch subscribed files and send resources/updated when they change.
+ * Watches the parent directory so files replaced on save keep notifying.
+ * Diagnostics resources are checked again after each change and only
+ * notify when the language server reports different diagnostics.
+ */
+function registerResourceSubscriptions(
+  server: McpServer,
+  manager: LspManager
+): void {
+  const watchers = new Map<string, fs.FSWatcher>();
+
+  server.server.registerCapabilities({ resources: { subscribe: true } });
+
+  server.server.setRequestHandler(SubscribeRequestSchema, (request) => {
+    const { uri } = request.params;
+    if (watchers.has(uri)) {
+      return {};
+    }
+
+    const filePath = getWorkspaceFile(manager, uri);
+    const watchesDiagnostics = uri.startsWith(RESOURCE_PREFIXES.diagnostics);
+    let lastDiagnostics: string | undefined;
+    const checkDiagnostics = async () => {
+      const diagnostics = JSON.stringify(
+        await readFileDiagnostics(manager, filePath)
+      );
+      const changed = diagnostics !== lastDiagnostics;
+      lastDiagnostics = diagnostics;
+      return changed;
+    };
+    const notify = async () => {
+      if (watchesDiagnostics && !(await checkDiagnostics())) {
+        return;
+      }
+      await server.server.sendResourceUpdated({ uri });
+    };
+    const logFailure = (error: unknown) => {
+      logger.debug('Failed to send resource update', {
+        uri,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    };
+
+    // Checks run one at a time, so each compares against the one before
+    let checks: Promise<void> = watchesDiagnostics
+      ? checkDiagnostics().then(() => undefined, logFailure)
+      : Promise.resolve();
+    let timer: NodeJS.Timeout | undefined;
+    const watcher = fs.watch(path.dirname(filePath), (_event, fileName) => {
+      if (fileName !== null && fileName !== path.basename(filePath)) {
+        return;
+      }
+      clearTimeout(timer);
+      timer = setTimeout(() => {
+        checks = checks.then(notify).catch(logFailure);
+      }, CHANGE_DEBOUNCE_MS);
+    });
+    watcher.on('error', (error) => {
+      logger.warn('Stopped watching subscribed resource', {
+        uri,
+        error: error.message,
+      });
+      watchers.delete(uri);
+    });
+    watchers.set(uri, watcher);
+    return {};
+  });
+
+  server.server.setRequestHandler(UnsubscribeRequestSchema, (request) => {
+    const { uri } = request.params;
+    watchers.get(uri)?.close();
+    watchers.delete(uri);
+    return {};
+  });
+
+  const onclose = server.server.onclose;
+  server.server.onclose = () => {
+    onclose?.();
+    for (const watcher of watchers.values()) {
+      watcher.close();
+    }
+    watchers.clear();
+  };
+}
+
+export function registerWorkspaceFileResources(
+  server: McpServer,
+  manager: LspManager
+): void {
+  server.registerResource(
+    'workspace-file',
+    new ResourceTemplate('workspace://files/{+path}', {
+      list: async () => await listWorkspaceFiles(manager, 'files'),
+    }),
+    {
+      title: 'Workspace File',
+      description:
+        'Contents of a source file in the workspace, for the extensions of the configured language servers.',
+      mimeType: 'text/plain',
+    },
+    async (uri) => {
+      const filePath = getWorkspaceFile(manager, uri.toString());
+      return {
+        contents: [
+          {
+            uri: uri.toString(),
+            mimeType: 'text/plain',
+            text: await fs.promises.readFile(filePath, 'utf-8'),
+          },
+        ],
+      };
+    }
+  );
+
+  server.registerResource(
+    'workspace-symbols',
+    new ResourceTemplate('workspace://symbols/{+path}', {
+      list: async () => await listWorkspaceFiles(manager, 'symbols'),
+    }),
+    {
+      title: 'Workspace File Symbols',
+      description:
+        'Outline of the symbols declared in a workspace file, with their kind, container and 1-based position.',
+      mimeType: 'application/json',
+    },
+    async (uri) => {
+      const filePath = getWorkspaceFile(manager, uri.toString());
+      const outline = await outlineFile(manager, filePath, MAX_OUTLINE_SYMBOLS);
+      return {
+        contents: [
+          {
+            uri: uri.toString(),
+            mimeType: 'application/json',
+            text: JSON.stringify(outline, null, 2),
+          },
+        ],
+      };
+    }
+  );
+
+  server.registerResource(
+    'workspace-diagnostics',
+    new ResourceTemplate('workspace://diagnostics/{+path}', {
+      list: async () => await listWorkspaceFiles(manager, 'diagnostics'),
+    }),
+    {
+      title: 'Workspace File Diagnostics',
+      description:
+        'Errors, warnings and hints the language server reports for a workspace file, with 1-based positions. Subscribe to be notified when they change after the file is edited.',
+      mimeType: 'application/json',
+    },
+    async (uri) => {
+      const filePath = getWorkspaceFile(manager, uri.toString());
+      const diagnostics = await readFileDiagnostics(manager, filePath);
+      return {
+        contents: [
+          {
+            uri: uri.toString(),
+            mimeType: 'application/json',
+            text: JSON.stringify({ diagnostics }, null, 2),
+          },
+        ],
+      };
+    }
+  );
+
+  registerResourceSubscriptions(server, manager);
+}
@@ -13,6 +13,23 @@ import {
   loadLspConfig,
 } from '../config/lsp-config.js';
 import logger from '../utils/logger.js';
+import {
+  asCodedError,
+  combineCodedErrors,
+  CodedError,
+} from '../utils/error-codes.js';
+import { configurePathOutput } from '../utils/path-format.js';
+import { configureCodeOwners } from '../utils/code-owners.js';
+import { configureSessionRecording } from '../lsp/session-recording.js';
+import { configureToolHooks } from '../tools/hooks.js';
+import { configureWasmPlugins } from '../plugins/wasm-plugins.js';
+import { configureRateLimits } from '../tools/rate-limits.js';
+import { configureSandbox } from '../lsp/sandbox.js';
+import { configureAuditLog } from '../tools/audit-log.js';
+import { configureHttpAuth } from '../tools/http-auth.js';
+import { configureSessionState } from './session-state.js';
+import { configureSymbolIndex } from '../map/symbol-index.js';
+import { configureWorkspaceScan } from '../map/workspace-scan.js';
 import {
   createLspSession,
   LspSession,
@@ -20,10 +37,18 @@ import {
   normalizeWorkspaceFilePath,
   SessionState,
 } from './lsp-session.js';
+import {
+  bindToWorkspace,
+  getWorkspaceContext,
+  type WorkspaceContext,
+} from './workspace-context.js';
 
 type ManagerMode = 'start' | 'run' | null;
 type ManagerState = 'idle' | 'ready' | 'uninitialized' | 'degraded';
 
+// Result of a tsserver command run through typescript-language-server
+type TsserverResponse = { body?: unknown } | null;
+
 export interface LspManagerProfileStatus {
   name: string;
   sessionKey: string;
@@ -96,6 +121,9 @@ export interface LspManager {
   getSessionForFile(this: void, filePath: string): Promise<LspSession>;
   getSearchSessions(this: void): Promise<LspSession[]>;
   getStartedSessions(this: void, profileName?: string): LspSession[];
+  // Start the servers of profiles configured with `preload`; the rest start
+  // on the first query for their files
+  preloadProfiles(this: void): Promise<void>;
 }
 
 function getNoProfilesMessage(): string {
@@ -144,7 +172,13 @@ function matchesWorkspaceFilePattern(
   return regex.test(workspaceFile);
 }
 
-export function createLspManager(): LspManager {
+/**
+ * Manager for the workspace of `context`. Its methods, and the servers
+ * they start, always see that workspace's configuration.
+ */
+export function createLspManager(
+  context: WorkspaceContext = getWorkspaceContext()
+): LspManager {
   let source: RuntimeSource | null = null;
   let mode: ManagerMode = null;
   let workspacePath = process.cwd();
@@ -170,6 +204,11 @@ export function createLspManager(): LspManager {
       const resolvedWorkspaceUri = `file://${resolvedWorkspacePath}`;
       const resolvedWorkspaceName = path.basename(resolvedWorkspacePath);
       applyLogLevel(resolved.loglevel);
+      configurePathOutput({
+        style: resolved.paths,
+        baseDirectory: resolvedWorkspacePath,
+      });
+      configureCodeOwners({ workspace: resolvedWorkspacePath });
 
       try {
         const parsedConfig = createConfigFromDirectCommand(
@@ -213,11 +252,57 @@ export function createLspManager(): LspManager {
     const resolvedWorkspaceUri = `file://${resolvedWorkspacePath}`;
     const resolvedWorkspaceName = path.basename(resolvedWorkspacePath);
     applyLogLevel(resolved.loglevel);
+    configurePathOutput({
+      style: resolved.paths,
+      baseDirectory: resolvedWorkspacePath,
+    });
+    configureSessionRecording({
+      record: resolved.record ?? null,
+      replay: resolved.replay ?? null,
+      timing: resolved.replayTiming,
+    });
 
     const configWithSource = loadLspConfig(
       resolved.configPath,
       resolved.workspace
     );
+    const ownersConfig = configWithSource.config.owners;
+    configureCodeOwners({
+      workspace: resolvedWorkspacePath,
+      ...(ownersConfig?.file && { file: ownersConfig.file }),
+      ...(ownersConfig && { teams: ownersConfig.teams }),
+    });
+    configureToolHooks({
+      workspace: resolvedWorkspacePath,
+      hooks: configWithSource.config.hooks ?? {},
+    });
+    configureWasmPlugins({
+      workspace: resolvedWorkspacePath,
+      plugins: configWithSource.config.plugins ?? [],
+    });
+    configureRateLimits(configWithSource.config.rate_limits ?? null);
+    configureSandbox({
+      workspace: resolvedWorkspacePath,
+      sandbox: configWithSource.config.sandbox ?? null,
+    });
+    configureAuditLog({
+      workspace: resolvedWorkspacePath,
+      audit: configWithSource.config.audit ?? null,
+    });
+    configureHttpAuth({
+      workspace: resolvedWorkspacePath,
+      auth: configWithSource.config.auth ?? null,
+    });
+    configureSessionState({
+      workspace: resolvedWorkspacePath,
+      resume: configWithSource.config.resume ?? null,
+    });
+    configureSymbolIndex({
+      workspace: resolvedWorkspacePath,
+      index: configWithSource.config.index ?? null,
+    });
+    configureWorkspaceScan(configWithSource.config.scan ?? null);
+
     const availableProfileNames = Object.keys(
       configWithSource.config['language-servers']
     );
@@ -347,6 +432,17 @@ export function createLspManager(): LspManager {
           }
         }
       },
+      async onTsserverRequest(profileName, command, payload) {
+        const tsserverSession = await startProfile(profileName);
+        const response = await tsserverSession.request<TsserverResponse>(
+          'workspace/executeCommand',
+          {
+            command: 'typescript.tsserverRequest',
+            arguments: [command, payload],
+          }
+        );
+        return response?.body;
+      },
     });
     sessions.set(sessionKey, session);
     return session;
@@ -667,7 +763,7 @@ export function createLspManager(): LspManager {
     };
   }
 
-  return {
+  return bindToWorkspace(context, {
     configureForStart(cliArgs: StartCommandArgs): Promise<void> {
       configureFromSource({ mode: 'start', cliArgs });
       return Promise.resolve();
@@ -778,27 +874,32 @@ export function createLspManager(): LspManager {
       }
 
       const startedSessions: LspSession[] = [];
-      const errors: string[] = [];
+      const errors: CodedError[] = [];
 
       for (const session of targetSessions) {
         try {
           startedSessions.push(await startSession(session));
         } catch (error) {
+          const failure = asCodedError(error);
           errors.push(
-            `${session.getProfile().name}: ${error instanceof Error ? error.message : String(error)}`
+            new CodedError(
+              `${session.getProfile().name}: ${failure.message}`,
+              failure.code,
+              failure.category
+            )
           );
         }
       }
 
       if (startedSessions.length === 0) {
-        throw new Error(errors.join('\n'));
+        throw combineCodedErrors(errors);
       }
 
       if (errors.length > 0) {
         logger.warn(
           'Some LSP profiles failed to start during workspace search',
           {
-            errors,
+            errors: errors.map((error) => error.message),
           }
         );
       }
@@ -811,5 +912,25 @@ export function createLspManager(): LspManager {
         session.isReady()
       );
     },
-  };
+
+    async preloadProfiles(): Promise<void> {
+      ensureProfilesLoaded();
+
+      const preloaded = [...profiles.values()].filter(
+        (profile) => profile.config.preload
+      );
+      await Promise.all(
+        preloaded.map(async (profile) => {
+          try {
+            await startSession(getOrCreateSession(profile));
+          } catch (error) {
+            logger.warn('Failed to preload language server', {
+              profile: profile.name,
+              error: error instanceof Error ? error.message : String(error),
+            });
+          }
+        })
+      );
+    },
+  });
 }
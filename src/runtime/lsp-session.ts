@@ -3,9 +3,17 @@ import * as fs from 'node:fs';
 import { ChildProcessWithoutNullStreams } from 'child_process';
 import * as path from 'path';
 import { pathToFileURL } from 'url';
+import type {
+  CancellationToken,
+  DocumentSymbolParams,
+  ServerCapabilities,
+  WorkspaceEdit,
+  WorkspaceSymbolParams,
+} from 'vscode-languageserver-protocol';
 import {
   createLspClient,
   initializeLspClient,
+  killServerProcess,
   shutdownLspClient,
 } from '../lsp-client.js';
 import {
@@ -17,6 +25,8 @@ import {
   createDiagnosticsStore,
   createDiagnosticProviderStore,
   createWindowLogStore,
+  createProgressStore,
+  createSymbolCacheStore,
   createWorkspaceLoaderStore,
 } from '../state/index.js';
 import {
@@ -26,8 +36,10 @@ import {
   LspClient,
   LspConfig,
   OneBasedPosition,
+  ProgressStore,
   Result,
   SessionDocuments,
+  SymbolCacheStore,
   WindowLogStore,
   WorkspaceLoaderStore,
   WorkspaceState,
@@ -47,6 +59,14 @@ import {
   resolvePreloadEntries,
 } from '../utils/preload-files.js';
 import logger, { upgradeToContextualLogger } from '../utils/logger.js';
+import { hashContent } from '../utils/content-hash.js';
+import { getResumeFiles } from './session-state.js';
+import { invalidateSymbolIndex } from '../map/symbol-index.js';
+import {
+  subscribeWorkspaceFiles,
+  type WorkspaceWatcher,
+} from './workspace-watcher.js';
+import { toCodedError } from '../utils/error-codes.js';
 
 export type SessionState =
   | 'not_started'
@@ -90,10 +110,19 @@ export interface CursorContextOperationResult<T> {
 
 export interface SessionDocumentScope {
   uri: string;
+  // Capabilities from the server's initialize response
+  serverCapabilities?: ServerCapabilities | undefined;
+  // Cancelling the token sends the server $/cancelRequest
   request<TResult, TParams = unknown>(
     method: string,
-    params: TParams
+    params: TParams,
+    token?: CancellationToken
   ): Promise<TResult>;
+  // Run requests while collecting the edits the server sends back through
+  // workspace/applyEdit, e.g. when executing a code action's command
+  collectWorkspaceEdits?<T>(
+    run: () => Promise<T>
+  ): Promise<{ result: T; edits: WorkspaceEdit[] }>;
 }
 
 interface SessionCursorContextScope extends SessionDocumentScope {
@@ -112,13 +141,17 @@ export interface LspSession {
   restart(): Promise<void>;
   request<TResult, TParams = unknown>(
     method: string,
-    params: TParams
+    params: TParams,
+    token?: CancellationToken
   ): Promise<TResult>;
+  // From the server's initialize response, null until it is ready
+  getServerCapabilities(): ServerCapabilities | null;
   getWorkspaceState(): WorkspaceState;
   getWorkspaceLoaderStore(): WorkspaceLoaderStore;
   getDiagnosticsStore(): DiagnosticsStore;
   getDiagnosticProviderStore(): DiagnosticProviderStore;
   getWindowLogStore(): WindowLogStore;
+  getProgressStore(): ProgressStore;
   executeWithCursorContext<T>(
     operationName: string,
     filePath: string,
@@ -131,6 +164,13 @@ export interface LspSession {
     strategy: FileLifecycleStrategy,
     operation: (scope: SessionDocumentScope) => Promise<Result<T>>
   ): Promise<Result<T>>;
+  isDocumentStale(filePath: string): Promise<boolean>;
+  // Send the server this content for the file instead of what is on disk,
+  // e.g. an editor's unsaved buffer, or go back to the disk with null
+  setDocumentOverlay(
+    filePath: string,
+    content: string | null
+  ): Promise<Result<void>>;
   claimDocument(filePath: string): string;
   releaseDocument(filePath: string): string | null;
   listOwnedDocuments(): string[];
@@ -150,6 +190,12 @@ export interface LspSessionOwnershipSink {
     uri: string
   ) => void;
   onSessionUnexpectedExit?: (sessionKey: string, reason: string) => void;
+  // Runs a tsserver command through the server of another profile
+  onTsserverRequest?: (
+    profileName: string,
+    command: string,
+    payload: unknown
+  ) => Promise<unknown>;
 }
 
 function createWorkspaceConfig(profile: LspSessionProfile): LspConfig {
@@ -158,23 +204,48 @@ function createWorkspaceConfig(profile: LspSessionProfile): LspConfig {
     workspaceName: profile.workspaceName,
     preloadFiles: profile.config.preload_files || [],
     workspaceReadyDelayMs: profile.config.workspace_ready_delay_ms,
+    ...(profile.config.readiness && {
+      readiness: {
+        progressTitle: profile.config.readiness.progress_title,
+        logMessage: profile.config.readiness.log_message,
+        timeoutMs: profile.config.readiness.timeout_ms,
+      },
+    }),
   };
 }
 
 function createStores(): {
   documents: SessionDocuments;
+  // Running operations per open document URI, so one MCP client closing or
+  // reopening a document can't pull it out from under another's query
+  documentUses: Map<string, number>;
+  // Documents to close once the last operation using them finishes
+  closeWhenUnused: Set<string>;
+  // Last open queued per document URI; opens of one document run one at a
+  // time, so two operations can't both find it closed and open it twice
+  documentOpens: Map<string, Promise<unknown>>;
+  // Content sent instead of the file on disk, by normalized path
+  overlays: Map<string, string>;
   diagnosticsStore: DiagnosticsStore;
   diagnosticProviderStore: DiagnosticProviderStore;
   windowLogStore: WindowLogStore;
+  progressStore: ProgressStore;
   workspaceLoaderStore: WorkspaceLoaderStore;
+  symbolCache: SymbolCacheStore;
   workspaceState: WorkspaceState;
 } {
   return {
     documents: new Map(),
+    documentUses: new Map(),
+    closeWhenUnused: new Set(),
+    documentOpens: new Map(),
+    overlays: new Map(),
     diagnosticsStore: createDiagnosticsStore(),
     diagnosticProviderStore: createDiagnosticProviderStore(),
     windowLogStore: createWindowLogStore(),
+    progressStore: createProgressStore(),
     workspaceLoaderStore: createWorkspaceLoaderStore(),
+    symbolCache: createSymbolCacheStore(),
     workspaceState: {
       isLoading: false,
       isReady: false,
@@ -201,13 +272,15 @@ async function terminateClientProcess(
   const alreadyExited =
     process.exitCode !== null || process.signalCode !== null;
   if (alreadyExited) {
+    // A launch script may exit while the server it started keeps running
+    killServerProcess(process, 'SIGTERM');
     return;
   }
 
   const exitPromise = once(process, 'exit').then(() => undefined);
 
   if (!process.killed) {
-    process.kill('SIGTERM');
+    killServerProcess(process, 'SIGTERM');
   }
 
   const exitedNaturally = await Promise.race([
@@ -218,7 +291,7 @@ async function terminateClientProcess(
   ]);
 
   if (!exitedNaturally && !process.killed) {
-    process.kill('SIGKILL');
+    killServerProcess(process, 'SIGKILL');
     await Promise.race([
       exitPromise,
       new Promise<void>((resolve) => setTimeout(resolve, 1_000)),
@@ -250,6 +323,9 @@ export function createLspSession(
   let startPromise: Promise<void> | null = null;
   let hasStartAttempt = false;
   let stores = createStores();
+  let workspaceWatcher: WorkspaceWatcher | null = null;
+  // Set once the watcher covers every directory of the workspace
+  let workspaceWatched = false;
   const ownedDocuments = new Set<string>();
 
   function getInactiveState(): SessionState {
@@ -298,6 +374,35 @@ export function createLspSession(
     return client;
   }
 
+  // workspace/applyEdit requests carry nothing tying them to the command
+  // that caused them, so commands collecting edits run one at a time and
+  // concurrent clients never receive each other's edits
+  let editCollection: Promise<void> = Promise.resolve();
+
+  async function collectWorkspaceEdits<T>(
+    activeClient: LspClient,
+    run: () => Promise<T>
+  ): Promise<{ result: T; edits: WorkspaceEdit[] }> {
+    const previousCollection = editCollection;
+    let finishCollection = () => {};
+    editCollection = new Promise((resolve) => {
+      finishCollection = resolve;
+    });
+    await previousCollection;
+
+    const edits: WorkspaceEdit[] = [];
+    const previousListener = activeClient.workspaceEditListener;
+    activeClient.workspaceEditListener = (edit) => {
+      edits.push(edit);
+    };
+    try {
+      return { result: await run(), edits };
+    } finally {
+      activeClient.workspaceEditListener = previousListener;
+      finishCollection();
+    }
+  }
+
   function requireSessionClient(): LspClient {
     if (!client) {
       throw new Error(`LSP session '${profile.name}' is not initialized`);
@@ -310,11 +415,125 @@ export function createLspSession(
     return client;
   }
 
+  /**
+   * Send a request, answering documentSymbol and workspace/symbol from the
+   * symbol cache while the files behind the answer are unchanged
+   */
+  async function sendRequest<TResult>(
+    activeClient: LspClient,
+    method: string,
+    params: unknown,
+    token?: CancellationToken
+  ): Promise<TResult> {
+    const { documents, symbolCache, workspaceState, progressStore } = stores;
+    // An undefined token would be sent as a second parameter
+    const send = async (): Promise<TResult> =>
+      token
+        ? await activeClient.connection.sendRequest(method, params, token)
+        : await activeClient.connection.sendRequest(method, params);
+
+    if (method === 'textDocument/documentSymbol') {
+      const { uri } = (params as DocumentSymbolParams).textDocument;
+      const document = documents.get(uri);
+      if (document?.isOpen) {
+        const { contentHash } = document;
+        const cached = symbolCache.getDocumentSymbols(uri, contentHash);
+        if (cached !== undefined) {
+          return cached as TResult;
+        }
+        const symbols = await send();
+        if (documents.get(uri)?.contentHash === contentHash) {
+          symbolCache.setDocumentSymbols(uri, contentHash, symbols);
+        }
+        return symbols;
+      }
+    }
+
+    // Searches depend on every file, so they are only kept while a watcher
+    // reports changes, and not while the server is still indexing
+    if (
+      method === 'workspace/symbol' &&
+      workspaceWatched &&
+      workspaceState.isReady &&
+      progressStore.getActive().length === 0
+    ) {
+      const { query } = params as WorkspaceSymbolParams;
+      const cached = symbolCache.getWorkspaceSymbols(query);
+      if (cached !== undefined) {
+        return cached as TResult;
+      }
+      const generation = symbolCache.generation;
+      const symbols = await send();
+      symbolCache.setWorkspaceSymbols(query, generation, symbols);
+      return symbols;
+    }
+
+    return await send();
+  }
+
   async function request<TResult, TParams = unknown>(
     method: string,
-    params: TParams
+    params: TParams,
+    token?: CancellationToken
   ): Promise<TResult> {
-    return await requireClient().connection.sendRequest(method, params);
+    return await sendRequest(requireClient(), method, params, token);
+  }
+
+  /**
+   * Drop cached symbols of files that change on disk and mark the symbol
+   * index for reconciling, through the watcher the workspace's sessions
+   * share. Directories workspace scans skip aren't watched. Until the
+   * watcher covers the whole workspace, or without one, document symbols
+   * are still cached by content, searches are not.
+   */
+  function watchWorkspace(): void {
+    const { workspacePath } = profile;
+    const { symbolCache } = stores;
+    const watcher = subscribeWorkspaceFiles(workspacePath, {
+      onChange: (changedPath) => {
+        if (changedPath === null) {
+          symbolCache.clear();
+          invalidateSymbolIndex(workspacePath);
+          return;
+        }
+        if (canHandleFile(changedPath)) {
+          symbolCache.invalidate(pathToFileURL(changedPath).toString());
+        }
+        // Directories too, so files under a moved or removed one are seen
+        invalidateSymbolIndex(changedPath);
+      },
+      onError: (error) => {
+        logger.warn('Stopped watching workspace for symbol changes', {
+          profile: profile.name,
+          error: error.message,
+        });
+        unwatchWorkspace();
+        symbolCache.clear();
+      },
+    });
+    workspaceWatcher = watcher;
+
+    watcher.ready.then(
+      () => {
+        workspaceWatched = workspaceWatcher === watcher;
+      },
+      (error: unknown) => {
+        if (workspaceWatcher !== watcher) {
+          return;
+        }
+        workspaceWatcher = null;
+        logger.warn('Cannot watch workspace, symbol searches are not cached', {
+          profile: profile.name,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+    );
+  }
+
+  function unwatchWorkspace(): void {
+    workspaceWatcher?.close();
+    workspaceWatcher = null;
+    workspaceWatched = false;
   }
 
   function notifyDocumentClaimed(filePath: string, uri: string): void {
@@ -354,8 +573,10 @@ export function createLspSession(
       version: number;
     }
   ): Promise<{ content: string; version: number }> {
-    if (strategy === 'transient') {
-      const content = await fs.promises.readFile(normalizedPath, 'utf8');
+    const overlay = stores.overlays.get(normalizedPath);
+    if (strategy === 'transient' || overlay !== undefined) {
+      const content =
+        overlay ?? (await fs.promises.readFile(normalizedPath, 'utf8'));
       return {
         content,
         version: existingDocument ? existingDocument.version + 1 : 1,
@@ -383,9 +604,41 @@ export function createLspSession(
     strategy: FileLifecycleStrategy;
   }
 
+  function getDocumentUri(filePath: string): string {
+    return pathToFileURL(
+      normalizeWorkspaceFilePath(profile.workspacePath, filePath)
+    ).toString();
+  }
+
+  /**
+   * Open a document after any open of it already under way. `usedByCaller`
+   * says the caller already counts as one of the document's uses.
+   */
   async function openDocument(
     filePath: string,
-    strategy: FileLifecycleStrategy
+    strategy: FileLifecycleStrategy,
+    usedByCaller = false
+  ): Promise<Result<OpenedDocument>> {
+    const uri = getDocumentUri(filePath);
+    const { documentOpens } = stores;
+    const previous = documentOpens.get(uri) ?? Promise.resolve();
+    const opening = previous.then(
+      async () => await openDocumentNow(filePath, strategy, usedByCaller)
+    );
+    documentOpens.set(uri, opening);
+    try {
+      return await opening;
+    } finally {
+      if (documentOpens.get(uri) === opening) {
+        documentOpens.delete(uri);
+      }
+    }
+  }
+
+  async function openDocumentNow(
+    filePath: string,
+    strategy: FileLifecycleStrategy,
+    usedByCaller: boolean
   ): Promise<Result<OpenedDocument>> {
     return tryResultAsync(
       async () => {
@@ -398,8 +651,19 @@ export function createLspSession(
         const existingDocument = stores.documents.get(uri);
         const isPreloaded = Boolean(existingDocument);
         const wasAlreadyOpen = existingDocument?.isOpen ?? false;
-
-        if (wasAlreadyOpen && strategy === 'respect_existing') {
+        const uses = stores.documentUses.get(uri) ?? 0;
+        const inUse = uses > (usedByCaller ? 1 : 0);
+        const overlay = stores.overlays.get(normalizedPath);
+        const isOutdated =
+          overlay !== undefined && existingDocument?.content !== overlay;
+
+        // A document another operation is using is shared as it is rather
+        // than reopened with the content on disk
+        if (
+          wasAlreadyOpen &&
+          !isOutdated &&
+          (strategy === 'respect_existing' || inUse)
+        ) {
           return {
             wasAlreadyOpen,
             isPreloaded,
@@ -422,7 +686,7 @@ export function createLspSession(
             stores.documents
           );
           if (!closeResult.ok) {
-            throw new Error(closeResult.error.message);
+            throw toCodedError(closeResult.error);
           }
           notifyDocumentReleased(normalizedPath, uri);
         }
@@ -440,7 +704,7 @@ export function createLspSession(
           stores.documents
         );
         if (!openResult.ok) {
-          throw new Error(openResult.error.message);
+          throw toCodedError(openResult.error);
         }
 
         notifyDocumentClaimed(normalizedPath, uri);
@@ -462,35 +726,81 @@ export function createLspSession(
     );
   }
 
-  async function closeDocument(
-    document: Pick<
-      OpenedDocument,
-      'filePath' | 'uri' | 'strategy' | 'wasAlreadyOpen' | 'isPreloaded'
-    >
+  function useDocument(uri: string): void {
+    stores.documentUses.set(uri, (stores.documentUses.get(uri) ?? 0) + 1);
+  }
+
+  /**
+   * Open a document for an operation. The use is counted before the open
+   * starts, so another operation finishing meanwhile can't close the
+   * document under it; a failed open gives the use back.
+   */
+  async function acquireDocument(
+    filePath: string,
+    strategy: FileLifecycleStrategy
+  ): Promise<Result<OpenedDocument>> {
+    const uri = getDocumentUri(filePath);
+    useDocument(uri);
+    const openResult = await openDocument(filePath, strategy, true);
+    if (!openResult.ok) {
+      await releaseDocumentUse(filePath, uri, false);
+    }
+    return openResult;
+  }
+
+  /**
+   * Give back one operation's use of a document, closing it once no
+   * operation uses it if this or an earlier operation wanted it closed
+   */
+  async function releaseDocumentUse(
+    filePath: string,
+    uri: string,
+    shouldClose: boolean
   ): Promise<Result<void>> {
-    if (
-      !shouldCloseDocument(
-        document.strategy,
-        document.wasAlreadyOpen,
-        document.isPreloaded
-      )
-    ) {
+    const remainingUses = (stores.documentUses.get(uri) ?? 1) - 1;
+    if (remainingUses > 0) {
+      stores.documentUses.set(uri, remainingUses);
+      if (shouldClose) {
+        stores.closeWhenUnused.add(uri);
+      }
+      return { ok: true, data: undefined };
+    }
+    stores.documentUses.delete(uri);
+
+    if (!stores.closeWhenUnused.delete(uri) && !shouldClose) {
       return { ok: true, data: undefined };
     }
 
     const closeResult = await closeFile(
       requireSessionClient(),
-      document.uri,
+      uri,
       stores.documents
     );
     if (!closeResult.ok) {
       return closeResult;
     }
 
-    notifyDocumentReleased(document.filePath, document.uri);
+    notifyDocumentReleased(filePath, uri);
     return { ok: true, data: undefined };
   }
 
+  async function closeDocument(
+    document: Pick<
+      OpenedDocument,
+      'filePath' | 'uri' | 'strategy' | 'wasAlreadyOpen' | 'isPreloaded'
+    >
+  ): Promise<Result<void>> {
+    return await releaseDocumentUse(
+      document.filePath,
+      document.uri,
+      shouldCloseDocument(
+        document.strategy,
+        document.wasAlreadyOpen,
+        document.isPreloaded
+      )
+    );
+  }
+
   async function executeWithSessionCursorContext<T>(
     operationName: string,
     filePath: string,
@@ -498,7 +808,7 @@ export function createLspSession(
     strategy: FileLifecycleStrategy,
     operation: (scope: SessionCursorContextScope) => Promise<Result<T>>
   ): Promise<Result<CursorContextOperationResult<T>>> {
-    const openResult = await openDocument(filePath, strategy);
+    const openResult = await acquireDocument(filePath, strategy);
     if (!openResult.ok) {
       return openResult;
     }
@@ -520,11 +830,14 @@ export function createLspSession(
         const operationResult = await operation({
           uri: document.uri,
           cursorContext: cursorContext || undefined,
-          request: async (method, params) =>
-            await activeClient.connection.sendRequest(method, params),
+          serverCapabilities: activeClient.serverCapabilities,
+          request: async (method, params, token) =>
+            await sendRequest(activeClient, method, params, token),
+          collectWorkspaceEdits: async (run) =>
+            await collectWorkspaceEdits(activeClient, run),
         });
         if (!operationResult.ok) {
-          throw new Error(operationResult.error.message);
+          throw toCodedError(operationResult.error);
         }
 
         const closeResult = await closeDocument(document);
@@ -571,7 +884,7 @@ export function createLspSession(
     strategy: FileLifecycleStrategy,
     operation: (scope: SessionDocumentScope) => Promise<Result<T>>
   ): Promise<Result<T>> {
-    const openResult = await openDocument(filePath, strategy);
+    const openResult = await acquireDocument(filePath, strategy);
     if (!openResult.ok) {
       return openResult;
     }
@@ -582,11 +895,14 @@ export function createLspSession(
         const activeClient = requireClient();
         const operationResult = await operation({
           uri: document.uri,
-          request: async (method, params) =>
-            await activeClient.connection.sendRequest(method, params),
+          serverCapabilities: activeClient.serverCapabilities,
+          request: async (method, params, token) =>
+            await sendRequest(activeClient, method, params, token),
+          collectWorkspaceEdits: async (run) =>
+            await collectWorkspaceEdits(activeClient, run),
         });
         if (!operationResult.ok) {
-          throw new Error(operationResult.error.message);
+          throw toCodedError(operationResult.error);
         }
 
         const closeResult = await closeDocument(document);
@@ -615,6 +931,58 @@ export function createLspSession(
     return executionResult;
   }
 
+  /**
+   * Check whether a file changed on disk since its content was last sent to
+   * the server. Unknown or unreadable files are not reported as stale.
+   */
+  async function isDocumentStale(filePath: string): Promise<boolean> {
+    const normalizedPath = normalizeWorkspaceFilePath(
+      profile.workspacePath,
+      filePath
+    );
+    const uri = pathToFileURL(normalizedPath).toString();
+    const document = stores.documents.get(uri);
+    if (!document) {
+      return false;
+    }
+
+    try {
+      const content =
+        stores.overlays.get(normalizedPath) ??
+        (await fs.promises.readFile(normalizedPath, 'utf8'));
+      return hashContent(content) !== document.contentHash;
+    } catch {
+      return false;
+    }
+  }
+
+  /**
+   * Replace or clear a file's overlay. An open document is reopened with
+   * the new content; one the overlay opens stays open, like preloaded files.
+   */
+  async function setDocumentOverlay(
+    filePath: string,
+    content: string | null
+  ): Promise<Result<void>> {
+    const normalizedPath = normalizeWorkspaceFilePath(
+      profile.workspacePath,
+      filePath
+    );
+    const uri = pathToFileURL(normalizedPath).toString();
+    stores.symbolCache.invalidate(uri);
+    if (content === null) {
+      stores.overlays.delete(normalizedPath);
+      if (!stores.documents.get(uri)?.isOpen) {
+        return { ok: true, data: undefined };
+      }
+    } else {
+      stores.overlays.set(normalizedPath, content);
+    }
+
+    const result = await openDocument(normalizedPath, 'transient');
+    return result.ok ? { ok: true, data: undefined } : result;
+  }
+
   async function initializeWorkspace(): Promise<void> {
     if (!client) {
       throw new Error('LSP client not initialized');
@@ -707,6 +1075,25 @@ export function createLspSession(
         openedFiles.push(preloadEntry.resolvedPath);
       }
 
+      // Files clients worked on before the server restarted
+      const resumeFiles = getResumeFiles().filter(
+        (filePath) =>
+          profile.config.extensions[path.extname(filePath)] &&
+          !openedFiles.includes(filePath)
+      );
+      for (const filePath of resumeFiles) {
+        const result = await openDocument(filePath, 'persistent');
+        if (result.ok) {
+          openedFiles.push(filePath);
+        } else {
+          logger.debug('Failed to reopen file from the previous session', {
+            profile: profile.name,
+            filePath,
+            error: result.error,
+          });
+        }
+      }
+
       const warmupDelayMs = profile.config.workspace_ready_delay_ms || 0;
 
       if (warmupDelayMs > 0) {
@@ -738,6 +1125,42 @@ export function createLspSession(
     }
   }
 
+  /**
+   * Answer a framework server's requests for TypeScript information, sent
+   * as [id, command, payload] tuples, from the profile running its
+   * TypeScript plugin. Failures are answered with a null body so the server
+   * doesn't wait for them.
+   */
+  async function forwardTsserverRequests(
+    activeClient: LspClient,
+    tsserverProfile: string,
+    params: unknown
+  ): Promise<void> {
+    const requests = Array.isArray(params)
+      ? (params as Array<[number, string, unknown]>)
+      : [];
+    for (const [id, command, payload] of requests) {
+      let body: unknown = null;
+      try {
+        body =
+          (await ownershipSink.onTsserverRequest?.(
+            tsserverProfile,
+            command,
+            payload
+          )) ?? null;
+      } catch (error) {
+        logger.warn('Forwarding tsserver request failed', {
+          command,
+          tsserverProfile,
+          error: error instanceof Error ? error.message : String(error),
+        });
+      }
+      await activeClient.connection.sendNotification('tsserver/response', [
+        [id, body],
+      ]);
+    }
+  }
+
   async function start(): Promise<void> {
     if (state === 'ready' && client) {
       return;
@@ -762,13 +1185,14 @@ export function createLspSession(
         stores.diagnosticsStore,
         stores.diagnosticProviderStore,
         stores.windowLogStore,
-        stores.workspaceLoaderStore
+        stores.workspaceLoaderStore,
+        stores.progressStore
       );
 
       if (!clientResult.ok) {
         state = 'error';
         lastError = clientResult.error.message;
-        throw new Error(clientResult.error.message);
+        throw toCodedError(clientResult.error);
       }
 
       const activeClient = clientResult.data.client;
@@ -777,6 +1201,16 @@ export function createLspSession(
       client = activeClient;
       process = activeProcess;
 
+      const tsserverProfile = profile.config.tsserver_profile;
+      if (tsserverProfile) {
+        activeClient.connection.onNotification(
+          'tsserver/request',
+          (params: unknown) => {
+            void forwardTsserverRequests(activeClient, tsserverProfile, params);
+          }
+        );
+      }
+
       activeProcess.once('exit', (code, signal) => {
         if (process !== activeProcess) {
           return;
@@ -785,6 +1219,7 @@ export function createLspSession(
         client = null;
         process = null;
         startPromise = null;
+        unwatchWorkspace();
         stores.workspaceState.isLoading = false;
         stores.workspaceState.isReady = false;
 
@@ -810,7 +1245,7 @@ export function createLspSession(
         state = 'error';
         lastError = initResult.error.message;
         await terminateClientProcess(activeClient, activeProcess);
-        throw new Error(initResult.error.message);
+        throw toCodedError(initResult.error);
       }
 
       try {
@@ -825,6 +1260,7 @@ export function createLspSession(
       }
 
       state = 'ready';
+      watchWorkspace();
     })().finally(() => {
       startPromise = null;
     });
@@ -853,6 +1289,7 @@ export function createLspSession(
 
     client = null;
     process = null;
+    unwatchWorkspace();
     stores.workspaceState.isLoading = false;
     stores.workspaceState.isReady = false;
 
@@ -927,6 +1364,9 @@ export function createLspSession(
     stop,
     restart,
     request,
+    getServerCapabilities(): ServerCapabilities | null {
+      return state === 'ready' ? (client?.serverCapabilities ?? null) : null;
+    },
     getWorkspaceState(): WorkspaceState {
       return stores.workspaceState;
     },
@@ -942,8 +1382,13 @@ export function createLspSession(
     getWindowLogStore(): WindowLogStore {
       return stores.windowLogStore;
     },
+    getProgressStore(): ProgressStore {
+      return stores.progressStore;
+    },
     executeWithCursorContext: executeWithSessionCursorContext,
     executeWithDocumentLifecycle: executeWithSessionDocumentLifecycle,
+    isDocumentStale,
+    setDocumentOverlay,
     claimDocument,
     releaseDocument,
     listOwnedDocuments,
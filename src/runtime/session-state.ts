@@ -0,0 +1,237 @@
+/**
+ * Session state - the files each client worked on, persisted per workspace
+ * so a restarted server reopens them and clients resume against warm
+ * language servers. Clients are known by a stable identity rather than
+ * their transport session, so a reconnecting client continues its entry.
+ */
+
+import { createHash } from 'crypto';
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+import { getAppPaths } from '../utils/app-paths.js';
+import logger from '../utils/logger.js';
+import { defineWorkspaceState } from './workspace-context.js';
+
+export interface SessionStateSettings {
+  // State file, relative to the workspace; defaults to the data directory
+  path?: string | undefined;
+  // Recent files kept per client and reopened on start
+  max_files: number;
+  // Forget clients not seen for this many days
+  max_age_days: number;
+  // Clients kept; the least recently seen are forgotten first
+  max_clients: number;
+}
+
+interface ClientSessionState {
+  // Workspace-relative, most recent first
+  files: string[];
+  last_seen: string;
+}
+
+interface SessionStateFile {
+  version: number;
+  workspace: string;
+  clients: Record<string, ClientSessionState>;
+}
+
+// Version 1 keyed clients by transport session
+const STATE_VERSION = 2;
+const WRITE_DELAY_MS = 1_000;
+// Clients that neither authenticated nor named themselves
+const LOCAL_SESSION_CLIENT = 'local';
+const DAY_MS = 24 * 60 * 60 * 1000;
+
+interface SessionState {
+  settings: (SessionStateSettings & { file: string }) | null;
+  workspacePath: string;
+  clients: Record<string, ClientSessionState>;
+  writeTimer: NodeJS.Timeout | null;
+}
+
+const getState = defineWorkspaceState<SessionState>(() => ({
+  settings: null,
+  workspacePath: process.cwd(),
+  clients: {},
+  writeTimer: null,
+}));
+
+function getDefaultStateFile(workspace: string): string {
+  const key = createHash('sha256').update(workspace).digest('hex');
+  return path.join(getAppPaths().data, 'sessions', `${key.slice(0, 16)}.json`);
+}
+
+/**
+ * Identity a client's state is kept under: the authenticated client, then
+ * the name the MCP client gave when it connected. Transport session IDs
+ * are new on every connection, so they never see their state again.
+ */
+export function getSessionStateClient(
+  authClientId: string | undefined,
+  clientName: string | undefined
+): string {
+  return authClientId || clientName || LOCAL_SESSION_CLIENT;
+}
+
+// The most recently seen clients, at most `maxClients`
+function keepRecentClients(
+  clients: Record<string, ClientSessionState>,
+  maxClients: number
+): Record<string, ClientSessionState> {
+  return Object.fromEntries(
+    Object.entries(clients)
+      .sort(([, left], [, right]) =>
+        right.last_seen.localeCompare(left.last_seen)
+      )
+      .slice(0, maxClients)
+  );
+}
+
+function readClients(
+  file: string,
+  workspace: string,
+  settings: SessionStateSettings
+): Record<string, ClientSessionState> {
+  let state: Partial<SessionStateFile>;
+  try {
+    state = JSON.parse(fs.readFileSync(file, 'utf-8')) as SessionStateFile;
+  } catch (error) {
+    if ((error as NodeJS.ErrnoException).code !== 'ENOENT') {
+      logger.warn('Ignoring unreadable session state', {
+        file,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+    return {};
+  }
+  if (state.version !== STATE_VERSION || state.workspace !== workspace) {
+    return {};
+  }
+
+  const cutoff = Date.now() - settings.max_age_days * DAY_MS;
+  const recent = Object.fromEntries(
+    Object.entries(state.clients ?? {}).filter(
+      ([, client]) => Date.parse(client.last_seen) >= cutoff
+    )
+  );
+  return keepRecentClients(recent, settings.max_clients);
+}
+
+/**
+ * Enable or disable session state and load what the previous server
+ * saved, e.g. at start or after the config file is reloaded
+ */
+export function configureSessionState(update: {
+  workspace: string;
+  resume: SessionStateSettings | null;
+}): void {
+  const state = getState();
+  if (state.writeTimer) {
+    flushSessionState();
+  }
+  state.workspacePath = update.workspace;
+  state.settings = update.resume && {
+    ...update.resume,
+    file: update.resume.path
+      ? path.resolve(update.workspace, update.resume.path)
+      : getDefaultStateFile(update.workspace),
+  };
+  state.clients = state.settings
+    ? readClients(state.settings.file, update.workspace, state.settings)
+    : {};
+}
+
+/**
+ * Write pending state now, e.g. before the process exits
+ */
+export function flushSessionState(): void {
+  const state = getState();
+  if (state.writeTimer) {
+    clearTimeout(state.writeTimer);
+    state.writeTimer = null;
+  }
+  const { settings } = state;
+  if (!settings) {
+    return;
+  }
+
+  const contents: SessionStateFile = {
+    version: STATE_VERSION,
+    workspace: state.workspacePath,
+    clients: state.clients,
+  };
+  const temporaryFile = `${settings.file}.${process.pid}.tmp`;
+  try {
+    fs.mkdirSync(path.dirname(settings.file), { recursive: true });
+    // Write then rename, so a crash mid-write keeps the previous state
+    fs.writeFileSync(temporaryFile, JSON.stringify(contents, null, 2), {
+      mode: 0o600,
+    });
+    fs.renameSync(temporaryFile, settings.file);
+  } catch (error) {
+    logger.error('Failed to write session state', {
+      file: settings.file,
+      error: error instanceof Error ? error.message : String(error),
+    });
+  }
+}
+
+function scheduleWrite(): void {
+  const state = getState();
+  if (state.writeTimer) {
+    return;
+  }
+  state.writeTimer = setTimeout(flushSessionState, WRITE_DELAY_MS);
+  state.writeTimer.unref();
+}
+
+/**
+ * Note a workspace file a client's tool call worked on
+ */
+export function recordSessionFile(client: string, file: string): void {
+  const state = getState();
+  const { settings, workspacePath, clients } = state;
+  if (!settings) {
+    return;
+  }
+
+  const absolutePath = file.startsWith('file://')
+    ? fileURLToPath(file)
+    : path.resolve(workspacePath, file);
+  const relativePath = path.relative(workspacePath, absolutePath);
+  if (
+    !relativePath ||
+    relativePath.startsWith('..') ||
+    path.isAbsolute(relativePath)
+  ) {
+    return;
+  }
+
+  const files = clients[client]?.files ?? [];
+  clients[client] = {
+    files: [
+      relativePath,
+      ...files.filter((entry) => entry !== relativePath),
+    ].slice(0, settings.max_files),
+    last_seen: new Date().toISOString(),
+  };
+  if (Object.keys(clients).length > settings.max_clients) {
+    state.clients = keepRecentClients(clients, settings.max_clients);
+  }
+  scheduleWrite();
+}
+
+/**
+ * Files to reopen on start: those of the most recently seen clients first,
+ * skipping any deleted since
+ */
+export function getResumeFiles(): string[] {
+  const { workspacePath, clients } = getState();
+  const files = Object.values(clients)
+    .sort((left, right) => right.last_seen.localeCompare(left.last_seen))
+    .flatMap((client) => client.files)
+    .map((file) => path.resolve(workspacePath, file));
+
+  return [...new Set(files)].filter((file) => fs.existsSync(file));
+}
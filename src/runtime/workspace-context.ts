@@ -0,0 +1,71 @@
+/**
+ * Workspace context - the configuration and caches one workspace owns.
+ * Modules keep their state in the active context rather than in module
+ * globals, so several workspaces opened in one process don't share paths,
+ * sandbox rules, indexes or limits.
+ */
+
+import { AsyncLocalStorage } from 'async_hooks';
+
+export interface WorkspaceContext {
+  readonly state: Map<symbol, unknown>;
+}
+
+export function createWorkspaceContext(): WorkspaceContext {
+  return { state: new Map() };
+}
+
+// Used by code running outside any workspace, such as the server and CLI
+// entry points, which only ever serve one workspace
+const defaultContext = createWorkspaceContext();
+const activeContext = new AsyncLocalStorage<WorkspaceContext>();
+
+/**
+ * The context of the workspace the current call runs for
+ */
+export function getWorkspaceContext(): WorkspaceContext {
+  return activeContext.getStore() ?? defaultContext;
+}
+
+/**
+ * Run `fn`, and everything it starts asynchronously, in `context`
+ */
+export function runInWorkspace<T>(context: WorkspaceContext, fn: () => T): T {
+  return activeContext.run(context, fn);
+}
+
+/**
+ * Copy of an object whose methods always run in `context`, whichever
+ * context they are called from
+ */
+export function bindToWorkspace<T extends object>(
+  context: WorkspaceContext,
+  target: T
+): T {
+  return Object.fromEntries(
+    Object.entries(target).map(([key, value]) => [
+      key,
+      typeof value === 'function'
+        ? (...args: unknown[]) =>
+            runInWorkspace(context, () =>
+              (value as (...args: unknown[]) => unknown)(...args)
+            )
+        : value,
+    ])
+  ) as T;
+}
+
+/**
+ * Declare a piece of per-workspace state. The returned getter gives the
+ * active context's copy, created with `create` on first use.
+ */
+export function defineWorkspaceState<T>(create: () => T): () => T {
+  const key = Symbol('workspace-state');
+  return () => {
+    const { state } = getWorkspaceContext();
+    if (!state.has(key)) {
+      state.set(key, create());
+    }
+    return state.get(key) as T;
+  };
+}
@@ -0,0 +1,216 @@
+/**
+ * Workspace watcher - report files changing under a workspace. Each
+ * directory is watched on its own, so the dependency, build output and
+ * hidden directories that scans skip take no watches. Sessions of one
+ * workspace share a single watcher.
+ */
+
+import * as fs from 'node:fs';
+import * as path from 'path';
+import { isIgnoredDirectory } from '../map/workspace-scan.js';
+import { defineWorkspaceState } from './workspace-context.js';
+
+// Past this many directories the workspace is not watched at all, rather
+// than exhausting the system's watch limit
+const MAX_WATCHED_DIRECTORIES = 10000;
+
+export interface WorkspaceWatcher {
+  // Settles once every directory is watched, or the watcher closed, and
+  // rejects when the workspace can't be watched
+  ready: Promise<void>;
+  close(): void;
+}
+
+export interface WorkspaceWatchListener {
+  onChange(changedPath: string | null): void;
+  // Watching failed after it started; no more changes will be reported
+  onError(error: Error): void;
+}
+
+interface SharedWatcher {
+  watcher: WorkspaceWatcher;
+  listeners: Set<WorkspaceWatchListener>;
+}
+
+const getState = defineWorkspaceState(() => ({
+  watchers: new Map<string, SharedWatcher>(),
+}));
+
+/**
+ * Watch the directories under `root`, including ones created later, and
+ * call `onChange` with each changed path, or null when the platform doesn't
+ * say which. Directories are listed asynchronously; changes are reported
+ * from the directories watched so far until `ready` settles. `onError` is
+ * called, after the watcher closed, when watching fails once started.
+ */
+export function watchWorkspaceFiles(
+  root: string,
+  onChange: (changedPath: string | null) => void,
+  onError: (error: Error) => void
+): WorkspaceWatcher {
+  const watchers = new Map<string, fs.FSWatcher>();
+  let closed = false;
+
+  const close = () => {
+    closed = true;
+    for (const watcher of watchers.values()) {
+      watcher.close();
+    }
+    watchers.clear();
+  };
+
+  const fail = (error: Error) => {
+    if (!closed) {
+      close();
+      onError(error);
+    }
+  };
+
+  // Stop watching a directory that was removed, along with its children
+  const unwatchTree = (directory: string) => {
+    for (const [watched, watcher] of watchers) {
+      if (
+        watched === directory ||
+        watched.startsWith(`${directory}${path.sep}`)
+      ) {
+        watcher.close();
+        watchers.delete(watched);
+      }
+    }
+  };
+
+  const onDirectoryChange = (directory: string, fileName: string | null) => {
+    if (fileName === null) {
+      onChange(null);
+      return;
+    }
+    const changedPath = path.join(directory, fileName);
+    onChange(changedPath);
+
+    if (isIgnoredDirectory(path.basename(changedPath))) {
+      return;
+    }
+    fs.stat(changedPath, (error, stats) => {
+      if (error) {
+        unwatchTree(changedPath);
+      } else if (stats.isDirectory() && !watchers.has(changedPath)) {
+        watchTree(changedPath).catch((watchError: unknown) =>
+          fail(
+            watchError instanceof Error
+              ? watchError
+              : new Error(String(watchError))
+          )
+        );
+      }
+    });
+  };
+
+  const watchDirectory = (directory: string) => {
+    if (watchers.size >= MAX_WATCHED_DIRECTORIES) {
+      throw new Error(
+        `More than ${MAX_WATCHED_DIRECTORIES} directories to watch`
+      );
+    }
+
+    const watcher = fs.watch(directory, (_event, fileName) =>
+      onDirectoryChange(directory, fileName)
+    );
+    watcher.on('error', (error) => {
+      // Removing a directory can end its watch before the parent reports it
+      if (fs.existsSync(directory)) {
+        fail(error);
+      } else {
+        unwatchTree(directory);
+      }
+    });
+    watchers.set(directory, watcher);
+  };
+
+  // Walk one directory at a time so large trees don't block the event loop
+  const watchTree = async (directory: string) => {
+    const pending = [directory];
+    let next: string | undefined;
+    while ((next = pending.pop()) !== undefined) {
+      if (closed) {
+        return;
+      }
+      if (watchers.has(next)) {
+        continue;
+      }
+      watchDirectory(next);
+
+      let entries: fs.Dirent[];
+      try {
+        entries = await fs.promises.readdir(next, { withFileTypes: true });
+      } catch {
+        continue;
+      }
+      for (const entry of entries) {
+        if (entry.isDirectory() && !isIgnoredDirectory(entry.name)) {
+          pending.push(path.join(next, entry.name));
+        }
+      }
+    }
+  };
+
+  const ready = watchTree(root).catch((error: unknown) => {
+    close();
+    throw error;
+  });
+  return { ready, close };
+}
+
+/**
+ * Report changes under `root` to `listener` through the workspace's shared
+ * watcher, starting it for the first listener and closing it after the
+ * last one leaves. A watcher that fails is dropped, so the next listener
+ * starts a fresh one.
+ */
+export function subscribeWorkspaceFiles(
+  root: string,
+  listener: WorkspaceWatchListener
+): WorkspaceWatcher {
+  const { watchers } = getState();
+  let shared = watchers.get(root);
+
+  if (!shared) {
+    const listeners = new Set<WorkspaceWatchListener>();
+    const drop = () => {
+      if (watchers.get(root)?.listeners === listeners) {
+        watchers.delete(root);
+      }
+    };
+    const watcher = watchWorkspaceFiles(
+      root,
+      (changedPath) => {
+        for (const current of [...listeners]) {
+          current.onChange(changedPath);
+        }
+      },
+      (error) => {
+        drop();
+        for (const current of [...listeners]) {
+          current.onError(error);
+        }
+      }
+    );
+    watcher.ready.catch(drop);
+    shared = { watcher, listeners };
+    watchers.set(root, shared);
+  }
+
+  const { watcher, listeners } = shared;
+  listeners.add(listener);
+  return {
+    ready: watcher.ready,
+    close: () => {
+      if (!listeners.delete(listener) || listeners.size > 0) {
+        return;
+      }
+      if (watchers.get(root)?.listeners === listeners) {
+        watchers.delete(root);
+      }
+      watcher.close();
+    },
+  };
+}
@@ -2,13 +2,18 @@
  * Store implementations for caching LSP data
  */
 
+import { setTimeout as delay } from 'node:timers/promises';
 import { Diagnostic } from 'vscode-languageserver-protocol';
 import {
+  ActiveProgress,
   DiagnosticsStore,
   DiagnosticProviderStore,
   DiagnosticProvider,
   WindowLogStore,
   LogMessage,
+  ProgressStore,
+  SymbolCacheStore,
+  WindowLogEntry,
   WorkspaceLoaderStore,
 } from '../types.js';
 import { WorkspaceLoaderState, WorkspaceLoader } from '../workspace/types.js';
@@ -98,16 +103,26 @@ export function createDiagnosticProviderStore(): DiagnosticProviderStore {
   };
 }
 
+// Oldest messages are dropped past this, so chatty servers stay bounded
+const MAX_WINDOW_LOG_MESSAGES = 1000;
+
 export function createWindowLogStore(): WindowLogStore {
-  const messages: LogMessage[] = [];
+  const messages: WindowLogEntry[] = [];
 
   return {
     messages,
-    addMessage(message: LogMessage) {
-      logger.info('[LSP-LOG]', message);
-      this.messages.push(message);
+    addMessage(message: LogMessage, shown = false) {
+      logger.info(shown ? '[LSP-SHOW]' : '[LSP-LOG]', message);
+      this.messages.push({
+        ...message,
+        ...(shown && { shown }),
+        receivedAt: new Date(),
+      });
+      if (this.messages.length > MAX_WINDOW_LOG_MESSAGES) {
+        this.messages.splice(0, this.messages.length - MAX_WINDOW_LOG_MESSAGES);
+      }
     },
-    getMessages(): LogMessage[] {
+    getMessages(): WindowLogEntry[] {
       return [...this.messages];
     },
     clear() {
@@ -116,9 +131,80 @@ export function createWindowLogStore(): WindowLogStore {
   };
 }
 
+// How often waitForIdle checks for progress to end
+const PROGRESS_POLL_MS = 100;
+// Servers often begin the next piece of work right after ending one
+const DEFAULT_PROGRESS_QUIET_MS = 500;
+
+export function createProgressStore(): ProgressStore {
+  const progress = new Map<string, ActiveProgress>();
+  let lastChangeAt = 0;
+
+  return {
+    progress,
+    handleProgress(params) {
+      const token = String(params.token);
+      const value = params.value;
+      lastChangeAt = Date.now();
+
+      if (value.kind === 'begin') {
+        progress.set(token, {
+          token,
+          title: value.title,
+          ...(value.message !== undefined && { message: value.message }),
+          ...(value.percentage !== undefined && {
+            percentage: value.percentage,
+          }),
+          startedAt: new Date(),
+        });
+        return;
+      }
+
+      const entry = progress.get(token);
+      if (value.kind === 'end' || !entry) {
+        progress.delete(token);
+        return;
+      }
+      progress.set(token, {
+        ...entry,
+        ...(value.message !== undefined && { message: value.message }),
+        ...(value.percentage !== undefined && { percentage: value.percentage }),
+      });
+    },
+    getActive(): ActiveProgress[] {
+      return [...progress.values()].sort(
+        (a, b) => a.startedAt.getTime() - b.startedAt.getTime()
+      );
+    },
+    async waitForIdle(
+      timeoutMs: number,
+      quietMs = DEFAULT_PROGRESS_QUIET_MS
+    ): Promise<boolean> {
+      const deadline = Date.now() + timeoutMs;
+      for (;;) {
+        const now = Date.now();
+        if (progress.size === 0 && now - lastChangeAt >= quietMs) {
+          return true;
+        }
+        if (now >= deadline) {
+          return false;
+        }
+        await delay(Math.min(PROGRESS_POLL_MS, deadline - now));
+      }
+    },
+    clear() {
+      progress.clear();
+    },
+  };
+}
+
+// Notifications kept while the loader initializes, replayed once it has
+const MAX_PENDING_LOADER_NOTIFICATIONS = 200;
+
 export function createWorkspaceLoaderStore(): WorkspaceLoaderStore {
   let state: WorkspaceLoaderState | null = null;
   let loader: WorkspaceLoader | null = null;
+  const pending: Array<{ method: string; params: unknown }> = [];
 
   return {
     state,
@@ -126,6 +212,9 @@ export function createWorkspaceLoaderStore(): WorkspaceLoaderStore {
     setState(newState: WorkspaceLoaderState) {
       state = newState;
       this.state = newState;
+      for (const { method, params } of pending.splice(0)) {
+        this.updateState(method, params);
+      }
     },
     setLoader(newLoader: WorkspaceLoader) {
       loader = newLoader;
@@ -138,7 +227,14 @@ export function createWorkspaceLoaderStore(): WorkspaceLoaderStore {
       return loader;
     },
     updateState(method: string, params?: unknown) {
-      if (state && loader?.handleNotification) {
+      // Servers may report loading before the loader has its initial state
+      if (!state) {
+        if (pending.length < MAX_PENDING_LOADER_NOTIFICATIONS) {
+          pending.push({ method, params });
+        }
+        return;
+      }
+      if (loader?.handleNotification) {
         const newState = loader.handleNotification(state, method, params);
         this.setState(newState);
       }
@@ -151,3 +247,79 @@ export function createWorkspaceLoaderStore(): WorkspaceLoaderStore {
     },
   };
 }
+
+const MAX_CACHED_DOCUMENT_SYMBOLS = 500;
+const MAX_CACHED_WORKSPACE_SEARCHES = 100;
+
+// Entries are re-inserted on use, so the first is the least recently used
+function touch<T>(
+  entries: Map<string, T>,
+  key: string,
+  value: T,
+  max: number
+): void {
+  entries.delete(key);
+  entries.set(key, value);
+  const oldest = entries.keys().next().value;
+  if (entries.size > max && oldest !== undefined) {
+    entries.delete(oldest);
+  }
+}
+
+export function createSymbolCacheStore(): SymbolCacheStore {
+  const documentSymbols = new Map<
+    string,
+    { contentHash: string; symbols: unknown }
+  >();
+  const workspaceSymbols = new Map<string, unknown>();
+
+  // Copies go out, so callers reshaping a result can't change the entry
+  return {
+    generation: 0,
+    getDocumentSymbols(uri: string, contentHash: string): unknown {
+      const entry = documentSymbols.get(uri);
+      if (entry?.contentHash !== contentHash) {
+        return undefined;
+      }
+      touch(documentSymbols, uri, entry, MAX_CACHED_DOCUMENT_SYMBOLS);
+      return structuredClone(entry.symbols);
+    },
+    setDocumentSymbols(uri: string, contentHash: string, symbols: unknown) {
+      touch(
+        documentSymbols,
+        uri,
+        { contentHash, symbols: structuredClone(symbols) },
+        MAX_CACHED_DOCUMENT_SYMBOLS
+      );
+    },
+    getWorkspaceSymbols(query: string): unknown {
+      if (!workspaceSymbols.has(query)) {
+        return undefined;
+      }
+      const symbols = workspaceSymbols.get(query);
+      touch(workspaceSymbols, query, symbols, MAX_CACHED_WORKSPACE_SEARCHES);
+      return structuredClone(symbols);
+    },
+    setWorkspaceSymbols(query: string, generation: number, symbols: unknown) {
+      if (generation !== this.generation) {
+        return;
+      }
+      touch(
+        workspaceSymbols,
+        query,
+        structuredClone(symbols),
+        MAX_CACHED_WORKSPACE_SEARCHES
+      );
+    },
+    invalidate(uri: string) {
+      this.generation++;
+      documentSymbols.delete(uri);
+      workspaceSymbols.clear();
+    },
+    clear() {
+      this.generation++;
+      documentSymbols.clear();
+      workspaceSymbols.clear();
+    },
+  };
+}
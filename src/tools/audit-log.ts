@@ -0,0 +1,177 @@
+/**
+ * Audit log - an append-only JSONL record of every tool call, with the
+ * files it touched and the edits it applied, rotated by size
+ */
+
+import { AsyncLocalStorage } from 'async_hooks';
+import { createHash } from 'crypto';
+import * as fs from 'fs';
+import * as path from 'path';
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+import { getAppPaths } from '../utils/app-paths.js';
+import logger from '../utils/logger.js';
+import { toRelativeFilePath } from '../utils/path-format.js';
+
+export interface AuditLogSettings {
+  // Log file, relative to the workspace; defaults to the log directory
+  path?: string | undefined;
+  // Rotate once the file would grow past this size
+  max_bytes: number;
+  // Rotated files kept as `<path>.1` (newest) to `<path>.<max_files>`
+  max_files: number;
+}
+
+export interface AuditEntry {
+  timestamp: string;
+  client: string;
+  tool: string;
+  // sha256 of the arguments with sorted keys, so entries don't leak them
+  params_hash: string;
+  files: string[];
+  // Applied edit counts by file
+  edits: Record<string, number>;
+  outcome: 'ok' | 'error';
+  duration_ms: number;
+}
+
+interface AuditDraft {
+  files: Set<string>;
+  edits: Map<string, number>;
+}
+
+const getState = defineWorkspaceState<{
+  settings: (AuditLogSettings & { file: string }) | null;
+}>(() => ({ settings: null }));
+const activeCall = new AsyncLocalStorage<AuditDraft>();
+
+/**
+ * Enable or disable the audit log, e.g. after the config file is reloaded
+ */
+export function configureAuditLog(update: {
+  workspace: string;
+  audit: AuditLogSettings | null;
+}): void {
+  getState().settings = update.audit && {
+    ...update.audit,
+    file: update.audit.path
+      ? path.resolve(update.workspace, update.audit.path)
+      : path.join(getAppPaths().log, 'audit.jsonl'),
+  };
+}
+
+function sortKeys(value: unknown): unknown {
+  if (Array.isArray(value)) {
+    return value.map(sortKeys);
+  }
+  if (value && typeof value === 'object') {
+    return Object.fromEntries(
+      Object.entries(value)
+        .sort(([left], [right]) => left.localeCompare(right))
+        .map(([key, entry]) => [key, sortKeys(entry)])
+    );
+  }
+  return value;
+}
+
+/**
+ * Stable hash of tool arguments, equal for equal arguments in any key order
+ */
+export function hashParams(args: unknown): string {
+  return createHash('sha256')
+    .update(JSON.stringify(sortKeys(args ?? {})))
+    .digest('hex');
+}
+
+/**
+ * Note edits the current tool call applied to a file
+ */
+export function recordAuditEdits(file: string, count: number): void {
+  const draft = activeCall.getStore();
+  if (!draft || count === 0) {
+    return;
+  }
+  const relativePath = toRelativeFilePath(file);
+  draft.files.add(relativePath);
+  draft.edits.set(relativePath, (draft.edits.get(relativePath) ?? 0) + count);
+}
+
+function rotate(file: string, maxFiles: number): void {
+  for (let index = maxFiles - 1; index >= 1; index--) {
+    if (fs.existsSync(`${file}.${index}`)) {
+      fs.renameSync(`${file}.${index}`, `${file}.${index + 1}`);
+    }
+  }
+  if (maxFiles >= 1) {
+    fs.renameSync(file, `${file}.1`);
+  } else {
+    fs.rmSync(file);
+  }
+}
+
+/**
+ * Append an entry, rotating first when the file would exceed its limit
+ */
+export function writeAuditEntry(entry: AuditEntry): void {
+  const { settings } = getState();
+  if (!settings) {
+    return;
+  }
+
+  const line = `${JSON.stringify(entry)}\n`;
+  try {
+    fs.mkdirSync(path.dirname(settings.file), { recursive: true });
+    const size = fs.existsSync(settings.file)
+      ? fs.statSync(settings.file).size
+      : 0;
+    if (size > 0 && size + Buffer.byteLength(line) > settings.max_bytes) {
+      rotate(settings.file, settings.max_files);
+    }
+    fs.appendFileSync(settings.file, line, { mode: 0o600 });
+  } catch (error) {
+    logger.error('Failed to write audit log entry', {
+      file: settings.file,
+      tool: entry.tool,
+      error: error instanceof Error ? error.message : String(error),
+    });
+  }
+}
+
+/**
+ * Run a tool call and append its audit entry. The file in the arguments
+ * counts as touched; tools record anything else while they run.
+ */
+export async function withAuditEntry<
+  T extends { isError?: boolean | undefined },
+>(
+  call: { client: string; tool: string; args: unknown },
+  fn: () => Promise<T>
+): Promise<T> {
+  if (!getState().settings) {
+    return await fn();
+  }
+
+  const draft: AuditDraft = { files: new Set(), edits: new Map() };
+  const file = (call.args as { file?: unknown } | null)?.file;
+  if (typeof file === 'string') {
+    draft.files.add(toRelativeFilePath(file));
+  }
+
+  const startedAt = Date.now();
+  let outcome: AuditEntry['outcome'] = 'error';
+  try {
+    const result = await activeCall.run(draft, fn);
+    outcome = result.isError ? 'error' : 'ok';
+    return result;
+  } finally {
+    writeAuditEntry({
+      timestamp: new Date(startedAt).toISOString(),
+      client: call.client,
+      tool: call.tool,
+      params_hash: hashParams(call.args),
+      files: [...draft.files].sort(),
+      edits: Object.fromEntries(draft.edits),
+      outcome,
+      duration_ms: Date.now() - startedAt,
+    });
+  }
+}
@@ -10,6 +10,7 @@ import * as LspOperations from '../lsp/operations/index.js';
 import { symbolPositionSchema } from './schemas.js';
 import { formatCursorContext } from '../utils/cursor-context.js';
 import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
 import { createSignaturePreview, enrichSymbolsWithCode } from './enrichment.js';
 import { formatFilePath, getSymbolKindName } from './utils.js';
 import type {
@@ -19,8 +20,21 @@ import type {
   CallHierarchyOutgoingCall,
   CallHierarchyResult,
   CallHierarchyTarget,
+  CallTreeNode,
   Range,
 } from '../types/lsp.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  hierarchyItemOutputSchema,
+  positionOutputShape,
+  symbolAtCursorOutputField,
+  toHierarchyItemOutput,
+  toSymbolAtCursorOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
 
 const MAX_TARGETS = 5;
 const MAX_FILES_PER_SECTION = 6;
@@ -29,6 +43,9 @@ const MAX_CALLS_PER_FILE = 5;
 // so broad call graphs leave headroom for more files before truncating.
 const MAX_CALLS_PER_SECTION = 24;
 const MAX_CALL_SITES_PER_ENTRY = 6;
+// Past the first level: functions expanded, and lines shown per tree
+const MAX_EXPLORED_ITEMS = 60;
+const MAX_TREE_LINES = 40;
 
 const callHierarchySchema = {
   ...symbolPositionSchema,
@@ -37,10 +54,50 @@ const callHierarchySchema = {
     .optional()
     .default('both')
     .describe('Which call relationships to include. Defaults to both.'),
+  depth: z
+    .number()
+    .int()
+    .min(1)
+    .max(5)
+    .optional()
+    .default(1)
+    .describe(
+      'How many levels of calls to follow, e.g. 2 includes the callers of each caller. Defaults to 1.'
+    ),
 } as const;
 
 const callHierarchyZodSchema = z.object(callHierarchySchema);
 
+const callOutputSchema = hierarchyItemOutputSchema.extend({
+  level: z
+    .number()
+    .int()
+    .describe('1 for direct calls, 2 for the calls of those, and so on.'),
+  callSites: z.array(z.object(positionOutputShape)),
+});
+
+type CallOutput = z.infer<typeof callOutputSchema>;
+
+const callHierarchyOutputSchema = {
+  symbol: symbolAtCursorOutputField,
+  targets: z.array(
+    hierarchyItemOutputSchema.extend({
+      incoming: z
+        .array(callOutputSchema)
+        .optional()
+        .describe('Callers, each followed by its own callers.'),
+      outgoing: z
+        .array(callOutputSchema)
+        .optional()
+        .describe('Callees, each followed by its own callees.'),
+    })
+  ),
+  limitReached: z
+    .boolean()
+    .describe('Whether expansion stopped before reaching the full depth.'),
+  warnings: warningsOutputField,
+} as const;
+
 type CallSectionEntry = CallHierarchyIncomingCall | CallHierarchyOutgoingCall;
 
 export function registerCallHierarchyTool(
@@ -54,8 +111,9 @@ export function registerCallHierarchyTool(
       description:
         'Inspect incoming and outgoing call relationships for the callable symbol at a file position.',
       inputSchema: callHierarchySchema,
+      outputSchema: callHierarchyOutputSchema,
     },
-    async (request) => {
+    withErrorCodes(async (request) => {
       const validatedRequest = callHierarchyZodSchema.parse(request);
       const session = await manager.getSessionForFile(validatedRequest.file);
 
@@ -71,14 +129,18 @@ export function registerCallHierarchyTool(
         session,
         symbolRequest
       );
-      if (!prepared.ok) throw new Error(prepared.error.message);
+      if (!prepared.ok) throw toCodedError(prepared.error);
 
       const result = await LspOperations.callHierarchy(
         session,
         prepared.data,
-        validatedRequest.direction
+        validatedRequest.direction,
+        {
+          depth: validatedRequest.depth,
+          maxExploredItems: MAX_EXPLORED_ITEMS,
+        }
       );
-      if (!result.ok) throw new Error(result.error.message);
+      if (!result.ok) throw toCodedError(result.error);
 
       const sections: string[] = [];
       const { cursorContext } = result.data;
@@ -89,18 +151,71 @@ export function registerCallHierarchyTool(
 
       sections.push(await formatCallHierarchyResult(result.data.result));
 
-      return {
-        content: [
-          {
-            type: 'text' as const,
-            text: sections.join('\n\n'),
-          },
-        ],
-      };
-    }
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      const { direction, limitReached, targets } = result.data.result;
+      return createToolResult(sections.join('\n\n'), {
+        symbol: toSymbolAtCursorOutput(cursorContext),
+        targets: targets.map((target) => ({
+          ...toHierarchyItemOutput(target.item),
+          ...(direction !== 'outgoing' && {
+            incoming: toCallOutputs(
+              target.incomingTree ??
+                (target.incomingCalls ?? []).map((call) => ({
+                  item: call.from,
+                  callSites: call.fromRanges,
+                }))
+            ),
+          }),
+          ...(direction !== 'incoming' && {
+            outgoing: toCallOutputs(
+              target.outgoingTree ??
+                (target.outgoingCalls ?? []).map((call) => ({
+                  item: call.to,
+                  callSites: call.fromRanges,
+                }))
+            ),
+          }),
+        })),
+        limitReached,
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
   );
 }
 
+/**
+ * Flatten call trees depth first, as the text renders them
+ */
+function toCallOutputs(
+  nodes: Array<{
+    item: CallHierarchyItem;
+    callSites: Range[];
+    children?: CallTreeNode[] | null;
+  }>,
+  level = 1
+): CallOutput[] {
+  return [...nodes]
+    .sort((left, right) => compareItems(left.item, right.item))
+    .flatMap((node) => [
+      {
+        ...toHierarchyItemOutput(node.item),
+        level,
+        callSites: node.callSites.map((range) => ({
+          line: range.start.line + 1,
+          character: range.start.character + 1,
+        })),
+      },
+      ...toCallOutputs(node.children ?? [], level + 1),
+    ]);
+}
+
 async function formatCallHierarchyResult(
   result: CallHierarchyResult
 ): Promise<string> {
@@ -136,6 +251,12 @@ async function formatCallHierarchyResult(
     );
   }
 
+  if (result.limitReached) {
+    sections.push(
+      `Stopped expanding after ${MAX_EXPLORED_ITEMS} functions; some calls below depth ${result.depth} may be missing`
+    );
+  }
+
   return sections.join('\n\n');
 }
 
@@ -149,7 +270,11 @@ async function formatCallHierarchyTarget(
     formatTargetSummary(target, direction),
   ];
 
-  if (direction !== 'outgoing') {
+  if (direction !== 'outgoing' && target.incomingTree) {
+    sections.push(
+      formatCallTree('Incoming Calls', target.incomingTree, 'calls at')
+    );
+  } else if (direction !== 'outgoing') {
     sections.push(
       await formatCallSection({
         title: 'Incoming Calls',
@@ -161,7 +286,11 @@ async function formatCallHierarchyTarget(
     );
   }
 
-  if (direction !== 'incoming') {
+  if (direction !== 'incoming' && target.outgoingTree) {
+    sections.push(
+      formatCallTree('Outgoing Calls', target.outgoingTree, 'called at')
+    );
+  } else if (direction !== 'incoming') {
     sections.push(
       await formatCallSection({
         title: 'Outgoing Calls',
@@ -327,6 +456,56 @@ async function formatCallSection<TCall extends CallSectionEntry>({
   return [header, ...sections].join('\n');
 }
 
+/**
+ * Indented tree of calls, each level the callers (or callees) of the one
+ * above it
+ */
+function formatCallTree(
+  title: string,
+  roots: CallTreeNode[],
+  callSiteLabel: string
+): string {
+  if (roots.length === 0) {
+    return `${title}\nNone`;
+  }
+
+  const lines: string[] = [];
+  let total = 0;
+
+  const visit = (nodes: CallTreeNode[], level: number) => {
+    const sorted = [...nodes].sort((left, right) =>
+      compareItems(left.item, right.item)
+    );
+    for (const node of sorted) {
+      total++;
+      if (lines.length < MAX_TREE_LINES) {
+        let line = `${'  '.repeat(level)}${formatCallHierarchyItem(node.item)}`;
+        if (node.callSites.length > 0) {
+          line += `, ${callSiteLabel} ${formatCallSiteRanges(node.callSites)}`;
+        }
+        lines.push(line);
+      }
+      if (node.children) {
+        visit(node.children, level + 1);
+      }
+    }
+  };
+  visit(roots, 1);
+
+  let header = `${title} (${total} in tree`;
+  if (lines.length < total) {
+    header += `, showing ${lines.length}`;
+  }
+  header += ')';
+
+  if (lines.length < total) {
+    const omitted = total - lines.length;
+    lines.push(`... ${omitted} more call${omitted === 1 ? '' : 's'} not shown`);
+  }
+
+  return [header, ...lines].join('\n');
+}
+
 function formatCallSiteRanges(ranges: Range[]): string {
   const positions = Array.from(
     new Set(
@@ -0,0 +1,239 @@
+/**
+ * Call Paths Tool - Check whether one function can reach another through the
+ * call graph and show example paths
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import { createOneBasedPosition } from '../types.js';
+import { prepareSymbolPositionRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { symbolPositionSchema } from './schemas.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import { formatFilePath, getSymbolKindName } from './utils.js';
+import type {
+  CallHierarchyItem,
+  CallPathResult,
+  CallPathStep,
+} from '../types/lsp.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  hierarchyItemOutputSchema,
+  locationOutputSchema,
+  toHierarchyItemOutput,
+  toLocationOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+// Bounds the number of outgoing-call requests a single search can issue
+const MAX_EXPLORED_ITEMS = 500;
+
+const callPathsSchema = {
+  ...symbolPositionSchema,
+  targetFile: z
+    .string()
+    .optional()
+    .describe(
+      'File containing the target function. Defaults to the source file.'
+    ),
+  targetLine: z
+    .number()
+    .int()
+    .min(1)
+    .describe('1-based line number of the target function.'),
+  targetCharacter: z
+    .number()
+    .int()
+    .min(1)
+    .describe('1-based character number of the target function.'),
+  maxDepth: z
+    .number()
+    .int()
+    .min(1)
+    .max(10)
+    .optional()
+    .default(5)
+    .describe('Maximum number of calls in a path. Defaults to 5.'),
+  maxPaths: z
+    .number()
+    .int()
+    .min(1)
+    .max(10)
+    .optional()
+    .default(3)
+    .describe('Maximum number of example paths to return. Defaults to 3.'),
+} as const;
+
+const callPathsOutputSchema = {
+  source: hierarchyItemOutputSchema.optional(),
+  target: hierarchyItemOutputSchema.optional(),
+  reachable: z.boolean(),
+  paths: z.array(
+    z.array(
+      hierarchyItemOutputSchema.extend({
+        calledAt: locationOutputSchema
+          .optional()
+          .describe('Where the previous step calls this one.'),
+      })
+    )
+  ),
+  exploredItems: z.number().int(),
+  limitReached: z
+    .boolean()
+    .describe('Whether the search stopped early; a longer path may exist.'),
+  warnings: warningsOutputField,
+} as const;
+
+const callPathsZodSchema = z.object(callPathsSchema);
+
+export function registerCallPathsTool(server: McpServer, manager: LspManager) {
+  server.registerTool(
+    'call_paths',
+    {
+      title: 'Call Paths',
+      description:
+        'Check whether the function at a file position can reach a target function through outgoing calls, returning the shortest example call paths.',
+      inputSchema: callPathsSchema,
+      outputSchema: callPathsOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = callPathsZodSchema.parse(request);
+      const targetFile = validatedRequest.targetFile ?? validatedRequest.file;
+      const session = await manager.getSessionForFile(validatedRequest.file);
+      const targetSession = await manager.getSessionForFile(targetFile);
+      if (targetSession.sessionKey !== session.sessionKey) {
+        throw new Error(
+          'Source and target must be handled by the same language server'
+        );
+      }
+
+      const source = await prepareSymbolPositionRequest(session, {
+        file: validatedRequest.file,
+        position: createOneBasedPosition(
+          validatedRequest.line,
+          validatedRequest.character
+        ),
+      });
+      if (!source.ok) throw toCodedError(source.error);
+
+      const target = await prepareSymbolPositionRequest(session, {
+        file: targetFile,
+        position: createOneBasedPosition(
+          validatedRequest.targetLine,
+          validatedRequest.targetCharacter
+        ),
+      });
+      if (!target.ok) throw toCodedError(target.error);
+
+      const result = await LspOperations.findCallPaths(
+        session,
+        source.data,
+        target.data,
+        {
+          maxDepth: validatedRequest.maxDepth,
+          maxPaths: validatedRequest.maxPaths,
+          maxExploredItems: MAX_EXPLORED_ITEMS,
+        }
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      const sections = [formatCallPathResult(result.data.result)];
+
+      const staleWarning = await getStaleContentWarning(
+        session,
+        source.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      const { sources, targets, paths, exploredItems, limitReached } =
+        result.data.result;
+      return createToolResult(sections.join('\n\n'), {
+        ...(sources[0] && { source: toHierarchyItemOutput(sources[0]) }),
+        ...(targets[0] && { target: toHierarchyItemOutput(targets[0]) }),
+        reachable: paths.length > 0,
+        paths: paths.map((path) =>
+          path.map((step, index) => {
+            const caller = path[index - 1];
+            const callSite = step.callSites[0];
+            return {
+              ...toHierarchyItemOutput(step.item),
+              ...(caller &&
+                callSite && {
+                  calledAt: toLocationOutput(caller.item.uri, callSite.start),
+                }),
+            };
+          })
+        ),
+        exploredItems,
+        limitReached,
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
+}
+
+function formatCallPathResult(result: CallPathResult): string {
+  const source = result.sources[0];
+  const target = result.targets[0];
+  if (!source) {
+    return 'No call hierarchy item found at the source position';
+  }
+  if (!target) {
+    return 'No call hierarchy item found at the target position';
+  }
+
+  const explored = `explored ${result.exploredItems} function${result.exploredItems === 1 ? '' : 's'}`;
+
+  if (result.paths.length === 0) {
+    const lines = [
+      `Not reachable: no call path from ${source.name} to ${target.name} within ${result.maxDepth} call${result.maxDepth === 1 ? '' : 's'} (${explored}).`,
+    ];
+    if (result.limitReached) {
+      lines.push(
+        'The search stopped early; a longer path may still exist. Try a function closer to the target.'
+      );
+    }
+    return lines.join('\n');
+  }
+
+  const sections = [
+    `Reachable: found ${result.paths.length} path${result.paths.length === 1 ? '' : 's'} from ${source.name} to ${target.name} (${explored}).`,
+    ...result.paths.map((path, index) => formatCallPath(path, index)),
+  ];
+
+  return sections.join('\n\n');
+}
+
+function formatCallPath(path: CallPathStep[], index: number): string {
+  const calls = path.length - 1;
+  const lines = [`Path ${index + 1} (${calls} call${calls === 1 ? '' : 's'})`];
+  let caller: CallHierarchyItem | undefined;
+
+  for (const step of path) {
+    const item = formatCallPathItem(step.item);
+    if (!caller) {
+      lines.push(`  ${item}`);
+    } else {
+      const callSite = step.callSites[0];
+      const calledAt = callSite
+        ? ` [called at ${formatFilePath(caller.uri)}:${callSite.start.line + 1}:${callSite.start.character + 1}]`
+        : '';
+      lines.push(`  -> ${item}${calledAt}`);
+    }
+    caller = step.item;
+  }
+
+  return lines.join('\n');
+}
+
+function formatCallPathItem(item: CallHierarchyItem): string {
+  // Some servers omit selectionRange despite the LSP spec requiring it.
+  const position = (item.selectionRange ?? item.range).start;
+  return `${item.name} (${getSymbolKindName(item.kind)}) - ${formatFilePath(item.uri)}:${position.line + 1}:${position.character + 1}`;
+}
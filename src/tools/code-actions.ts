@@ -0,0 +1,414 @@
+/**
+ * Code Actions Tools - List the quick fixes and refactorings at a position
+ * and apply one, writing its edits to disk
+ */
+
+import { createHash } from 'crypto';
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import { createOneBasedPosition, toZeroBased } from '../types.js';
+import { prepareSymbolPositionRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { formatEditsSchema, symbolPositionSchema } from './schemas.js';
+import { formatCursorContext } from '../utils/cursor-context.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import { CodedError, toCodedError } from '../utils/error-codes.js';
+import { createUnifiedDiff } from '../utils/unified-diff.js';
+import { applyWorkspaceChanges, formatFilePath } from './utils.js';
+import { formatWrittenChanges } from './format-edits.js';
+import {
+  isCodeActionCommand,
+  type CodeAction,
+  type Command,
+  type Range,
+} from '../types/lsp.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  fileChangeOutputSchema,
+  symbolAtCursorOutputField,
+  toFileChangeOutputs,
+  toSymbolAtCursorOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+
+const codeActionsSchema = {
+  ...symbolPositionSchema,
+  endLine: z
+    .number()
+    .int()
+    .min(1)
+    .optional()
+    .describe('1-based line where the range ends. Defaults to line.'),
+  endCharacter: z
+    .number()
+    .int()
+    .min(1)
+    .optional()
+    .describe('1-based character where the range ends. Defaults to character.'),
+  kinds: z
+    .array(z.string())
+    .optional()
+    .describe(
+      'Only return actions of these kinds or their sub-kinds, for example ["quickfix"] or ["refactor.extract"].'
+    ),
+} as const;
+
+const codeActionsZodSchema = z.object(codeActionsSchema);
+
+const codeActionsOutputSchema = {
+  symbol: symbolAtCursorOutputField,
+  actions: z.array(
+    z.object({
+      id: z.string().describe('Id to pass to apply_code_action.'),
+      title: z.string(),
+      kind: z.string().describe('Action kind, or command for bare commands.'),
+      preferred: z.boolean(),
+      disabled: z
+        .string()
+        .optional()
+        .describe('Why the action cannot be applied, when disabled.'),
+      fixes: z
+        .array(z.string())
+        .describe('Messages of the diagnostics the action fixes.'),
+    })
+  ),
+  warnings: warningsOutputField,
+} as const;
+
+const applyCodeActionSchema = {
+  id: z.string().describe('Id of the action, as listed by code_actions.'),
+  dryRun: z
+    .boolean()
+    .optional()
+    .default(false)
+    .describe(
+      'Return the diff without writing any file. Commands the action runs still execute on the language server. Defaults to false.'
+    ),
+  ...formatEditsSchema,
+} as const;
+
+const applyCodeActionZodSchema = z.object(applyCodeActionSchema);
+
+const applyCodeActionOutputSchema = {
+  title: z.string(),
+  written: z.boolean().describe('Whether the edits were written to disk.'),
+  executedCommands: z.array(z.string()),
+  skippedOperations: z
+    .number()
+    .int()
+    .describe('File creations, renames and deletions that were skipped.'),
+  files: z.array(fileChangeOutputSchema),
+} as const;
+
+// Recently listed actions by id, so they can be applied without listing again
+const MAX_LISTED_ACTIONS = 200;
+
+const listedActions = new Map<
+  string,
+  { filePath: string; action: CodeAction | Command }
+>();
+
+function rememberCodeAction(
+  id: string,
+  filePath: string,
+  action: CodeAction | Command
+): void {
+  listedActions.delete(id);
+  listedActions.set(id, { filePath, action });
+  const oldest = listedActions.keys().next().value;
+  if (listedActions.size > MAX_LISTED_ACTIONS && oldest !== undefined) {
+    listedActions.delete(oldest);
+  }
+}
+
+export function registerCodeActionsTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'code_actions',
+    {
+      title: 'Code Actions',
+      description:
+        'List the quick fixes, refactorings and source actions the language server offers for a position or range, including fixes for diagnostics there. Each action has an id that stays the same for the same file, range and action.',
+      inputSchema: codeActionsSchema,
+      outputSchema: codeActionsOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = codeActionsZodSchema.parse(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+
+      const prepared = await prepareSymbolPositionRequest(session, {
+        file: validatedRequest.file,
+        position: createOneBasedPosition(
+          validatedRequest.line,
+          validatedRequest.character
+        ),
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const range: Range = {
+        start: prepared.data.lspPosition,
+        end: toZeroBased(
+          createOneBasedPosition(
+            validatedRequest.endLine ?? validatedRequest.line,
+            validatedRequest.endCharacter ?? validatedRequest.character
+          )
+        ),
+      };
+
+      const result = await LspOperations.codeActions(
+        session,
+        prepared.data,
+        range,
+        validatedRequest.kinds
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      const sections: string[] = [];
+      const { cursorContext } = result.data;
+
+      if (cursorContext) {
+        sections.push(formatCursorContext(cursorContext));
+      }
+
+      const actions = listCodeActions(
+        prepared.data.filePath,
+        range,
+        result.data.result
+      );
+      sections.push(formatCodeActions(actions));
+
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      return createToolResult(sections.join('\n\n'), {
+        symbol: toSymbolAtCursorOutput(cursorContext),
+        actions,
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
+}
+
+function getCodeActionKind(action: CodeAction | Command): string {
+  return isCodeActionCommand(action) ? 'command' : (action.kind ?? 'unknown');
+}
+
+/**
+ * Identify an action by where it was offered and what it is, so listing
+ * the same range again yields the same id
+ */
+function getCodeActionId(
+  filePath: string,
+  range: Range,
+  action: CodeAction | Command
+): string {
+  return createHash('sha256')
+    .update(
+      JSON.stringify([
+        filePath,
+        range.start.line,
+        range.start.character,
+        range.end.line,
+        range.end.character,
+        getCodeActionKind(action),
+        action.title,
+      ])
+    )
+    .digest('hex')
+    .slice(0, 8);
+}
+
+interface ListedCodeAction {
+  id: string;
+  title: string;
+  kind: string;
+  preferred: boolean;
+  disabled?: string;
+  fixes: string[];
+}
+
+/**
+ * Give each action its id and remember it, so it can be applied by id
+ */
+function listCodeActions(
+  filePath: string,
+  range: Range,
+  actions: (CodeAction | Command)[]
+): ListedCodeAction[] {
+  return actions.map((action) => {
+    const id = getCodeActionId(filePath, range, action);
+    rememberCodeAction(id, filePath, action);
+
+    if (isCodeActionCommand(action)) {
+      return {
+        id,
+        title: action.title,
+        kind: getCodeActionKind(action),
+        preferred: false,
+        fixes: [],
+      };
+    }
+    return {
+      id,
+      title: action.title,
+      kind: getCodeActionKind(action),
+      preferred: Boolean(action.isPreferred),
+      ...(action.disabled && { disabled: action.disabled.reason }),
+      fixes: (action.diagnostics ?? []).map(
+        (diagnostic) => diagnostic.message.split('\n')[0] ?? ''
+      ),
+    };
+  });
+}
+
+function formatCodeActions(actions: ListedCodeAction[]): string {
+  if (actions.length === 0) {
+    return 'No code actions available at this position';
+  }
+
+  const lines = [`Code actions (${actions.length})`];
+  for (const action of actions) {
+    const tags = [action.kind];
+    if (action.preferred) {
+      tags.push('preferred');
+    }
+
+    let line = `[${action.id}] ${action.title} (${tags.join(', ')})`;
+    if (action.disabled !== undefined) {
+      line += ` - disabled: ${action.disabled}`;
+    }
+    lines.push(line);
+
+    for (const fix of action.fixes) {
+      lines.push(`    fixes: ${fix}`);
+    }
+  }
+
+  return lines.join('\n');
+}
+
+export function registerApplyCodeActionTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'apply_code_action',
+    {
+      title: 'Apply Code Action',
+      description:
+        'Apply a code action listed by code_actions: resolve it, run its command, write the resulting edits to disk and return a unified diff. Pass dryRun to preview the diff without writing.',
+      inputSchema: applyCodeActionSchema,
+      outputSchema: applyCodeActionOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = applyCodeActionZodSchema.parse(request);
+      const listed = listedActions.get(validatedRequest.id);
+      if (!listed) {
+        throw new CodedError(
+          `Unknown code action ${validatedRequest.id}; list code actions again to get current ids`,
+          'UNKNOWN_CODE_ACTION',
+          'validation'
+        );
+      }
+
+      const session = await manager.getSessionForFile(listed.filePath);
+      const result = await LspOperations.applyCodeAction(
+        session,
+        { filePath: listed.filePath },
+        listed.action
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      // Edits were computed against the content sent to the server; applying
+      // them to a file that changed since would corrupt it
+      if (await session.isDocumentStale(listed.filePath)) {
+        throw new CodedError(
+          `${formatFilePath(listed.filePath)} changed on disk while the code action was resolved. No edits were applied; list code actions again.`,
+          'STALE_CONTENT',
+          'workspace'
+        );
+      }
+
+      const { dryRun } = validatedRequest;
+      const changeResults = await applyWorkspaceChanges(result.data.changes, {
+        dryRun,
+      });
+      if (!dryRun) {
+        // The file changed, so the action no longer applies as listed
+        listedActions.delete(validatedRequest.id);
+      }
+      const formatNotes = dryRun
+        ? []
+        : await formatWrittenChanges(
+            manager,
+            changeResults,
+            validatedRequest.formatEdits
+          );
+
+      const appliedCount = changeResults.reduce(
+        (sum, change) => sum + change.appliedChanges.length,
+        0
+      );
+      const failedCount = changeResults.reduce(
+        (sum, change) => sum + change.failedChanges.length,
+        0
+      );
+
+      let summary = `${dryRun ? 'Dry run of' : 'Applied'} "${listed.action.title}": ${appliedCount} changes across ${changeResults.length} file(s)`;
+      if (failedCount > 0) {
+        summary += ` (${failedCount} failed)`;
+      }
+      const sections = [summary];
+
+      for (const command of result.data.executedCommands) {
+        sections.push(`Ran command ${command} on the language server`);
+      }
+      if (result.data.skippedOperations > 0) {
+        sections.push(
+          `Skipped ${result.data.skippedOperations} file creation, rename or deletion operation(s), which aren't supported`
+        );
+      }
+      sections.push(...formatNotes);
+
+      for (const change of changeResults) {
+        const filePath = formatFilePath(change.fileUri);
+        if (change.error) {
+          sections.push(`✘ ${filePath}: ${change.error}`);
+          continue;
+        }
+        for (const failed of change.failedChanges) {
+          sections.push(
+            `✘ ${filePath}:${failed.line}:${failed.character}: ${failed.error}`
+          );
+        }
+
+        const diff = createUnifiedDiff(
+          filePath,
+          change.originalContent ?? '',
+          change.updatedContent ?? change.originalContent ?? ''
+        );
+        if (diff) {
+          sections.push(`\`\`\`diff\n${diff}\n\`\`\``);
+        }
+      }
+
+      return createToolResult(sections.join('\n\n'), {
+        title: listed.action.title,
+        written: !dryRun,
+        executedCommands: result.data.executedCommands,
+        skippedOperations: result.data.skippedOperations,
+        files: toFileChangeOutputs(changeResults),
+      });
+    })
+  );
+}
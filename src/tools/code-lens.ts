@@ -0,0 +1,247 @@
+/**
+ * Code Lens Tools - List the code lenses in a file, such as reference counts
+ * and run test commands, and run the command behind one
+ */
+
+import { createHash } from 'crypto';
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import { prepareFileRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import type { CodeLensResult, Command } from '../types/lsp.js';
+import { getStaleContentWarning } from './staleness.js';
+import { CodedError, toCodedError } from '../utils/error-codes.js';
+import { formatFilePath } from './utils.js';
+import { formatEditsSchema } from './schemas.js';
+import {
+  commandRunOutputSchema,
+  runServerCommand,
+} from './execute-command.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  fileOutputField,
+  positionOutputShape,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+
+const codeLensSchema = {
+  file: z
+    .string()
+    .describe(
+      'File path to list code lenses for. Accepts either an absolute path or a path relative to the current workspace.'
+    ),
+} as const;
+
+const codeLensZodSchema = z.object(codeLensSchema);
+
+const codeLensOutputSchema = {
+  file: fileOutputField,
+  lenses: z.array(
+    z.object({
+      ...positionOutputShape,
+      title: z.string(),
+      command: z.string().optional(),
+      id: z
+        .string()
+        .optional()
+        .describe(
+          'Id to pass to run_code_lens, absent for lenses only the editor can run.'
+        ),
+    })
+  ),
+  warnings: warningsOutputField,
+} as const;
+
+const runCodeLensSchema = {
+  id: z.string().describe('Id of the code lens, as listed by code_lens.'),
+  dryRun: z
+    .boolean()
+    .optional()
+    .default(false)
+    .describe(
+      'Return the diff without writing any file. The command still runs on the language server. Defaults to false.'
+    ),
+  ...formatEditsSchema,
+} as const;
+
+const runCodeLensZodSchema = z.object(runCodeLensSchema);
+
+// Recently listed lens commands by id, so they can be run without listing
+const MAX_LISTED_LENSES = 200;
+
+const listedLenses = new Map<string, { filePath: string; command: Command }>();
+
+function rememberCodeLens(id: string, filePath: string, command: Command) {
+  listedLenses.delete(id);
+  listedLenses.set(id, { filePath, command });
+  const oldest = listedLenses.keys().next().value;
+  if (listedLenses.size > MAX_LISTED_LENSES && oldest !== undefined) {
+    listedLenses.delete(oldest);
+  }
+}
+
+export function registerCodeLensTool(server: McpServer, manager: LspManager) {
+  server.registerTool(
+    'code_lens',
+    {
+      title: 'Code Lens',
+      description:
+        'List the code lenses the language server shows in a file, such as reference counts, implementations and run or debug test commands. Lenses whose command the server can run have an id to pass to run_code_lens.',
+      inputSchema: codeLensSchema,
+      outputSchema: codeLensOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = codeLensZodSchema.parse(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+
+      const prepared = prepareFileRequest(session, {
+        file: validatedRequest.file,
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const result = await LspOperations.codeLenses(session, prepared.data);
+      if (!result.ok) throw toCodedError(result.error);
+
+      const lenses = listCodeLenses(prepared.data.filePath, result.data);
+      const sections = [formatCodeLenses(prepared.data.filePath, lenses)];
+
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      return createToolResult(sections.join('\n\n'), {
+        file: formatFilePath(prepared.data.filePath),
+        lenses,
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
+}
+
+/**
+ * Identify a lens command by where it was shown and what it runs, so
+ * listing the same file again yields the same id
+ */
+function getCodeLensId(
+  filePath: string,
+  line: number,
+  command: Command
+): string {
+  return createHash('sha256')
+    .update(
+      JSON.stringify([
+        filePath,
+        line,
+        command.command,
+        command.title,
+        command.arguments ?? [],
+      ])
+    )
+    .digest('hex')
+    .slice(0, 8);
+}
+
+interface ListedCodeLens {
+  line: number;
+  character: number;
+  title: string;
+  command?: string;
+  id?: string;
+}
+
+/**
+ * Lenses with a command, giving the ones the server can run an id and
+ * remembering them so they can be run by id
+ */
+function listCodeLenses(
+  filePath: string,
+  result: CodeLensResult
+): ListedCodeLens[] {
+  return result.lenses.flatMap((lens) => {
+    const command = lens.command;
+    if (!command) {
+      return [];
+    }
+    const listed: ListedCodeLens = {
+      line: lens.range.start.line + 1,
+      character: lens.range.start.character + 1,
+      title: command.title,
+      ...(command.command && { command: command.command }),
+    };
+    if (!command.command || !result.serverCommands.includes(command.command)) {
+      return [listed];
+    }
+
+    const id = getCodeLensId(filePath, lens.range.start.line, command);
+    rememberCodeLens(id, filePath, command);
+    return [{ ...listed, id }];
+  });
+}
+
+function formatCodeLenses(filePath: string, lenses: ListedCodeLens[]): string {
+  if (lenses.length === 0) {
+    return 'No code lenses in this file';
+  }
+
+  const lines = [
+    `Code lenses in ${formatFilePath(filePath)} (${lenses.length})`,
+  ];
+  for (const lens of lenses) {
+    const position = `@${lens.line}:${lens.character}`;
+    if (!lens.command) {
+      lines.push(`  ${position} ${lens.title}`);
+    } else if (!lens.id) {
+      lines.push(`  ${position} ${lens.title} (${lens.command}, editor only)`);
+    } else {
+      lines.push(`  [${lens.id}] ${position} ${lens.title} (${lens.command})`);
+    }
+  }
+
+  return lines.join('\n');
+}
+
+export function registerRunCodeLensTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'run_code_lens',
+    {
+      title: 'Run Code Lens',
+      description:
+        'Run the command behind a code lens listed by code_lens through workspace/executeCommand. Returns what the command reported and writes any edits it makes, showing them as a unified diff. Pass dryRun to preview the diff without writing.',
+      inputSchema: runCodeLensSchema,
+      outputSchema: commandRunOutputSchema.shape,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = runCodeLensZodSchema.parse(request);
+      const listed = listedLenses.get(validatedRequest.id);
+      if (!listed) {
+        throw new CodedError(
+          `Unknown code lens ${validatedRequest.id}; list code lenses again to get current ids`,
+          'UNKNOWN_CODE_LENS',
+          'validation'
+        );
+      }
+
+      const { text, output } = await runServerCommand(
+        manager,
+        listed.filePath,
+        listed.command,
+        {
+          dryRun: validatedRequest.dryRun,
+          formatEdits: validatedRequest.formatEdits,
+        }
+      );
+
+      return createToolResult(text, output);
+    })
+  );
+}
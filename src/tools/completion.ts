@@ -3,6 +3,7 @@
  */
 
 import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
 import { createOneBasedPosition } from '../types.js';
 import { prepareSymbolPositionRequest } from '../preparation.js';
 import * as LspOperations from '../lsp/operations/index.js';
@@ -10,8 +11,60 @@ import { symbolPositionSchema } from './schemas.js';
 import { formatCursorContext } from '../utils/cursor-context.js';
 import { getSymbolKindName } from './utils.js';
 import { CompletionResult } from '../types/lsp.js';
-import { validateSymbolPosition } from './validation.js';
+import { formatTruncationMarker } from './truncation.js';
 import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import { renderHoverMarkdown } from '../utils/hover-markdown.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  positionOutputShape,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+// Suggestions listed in the text, and in the structured content
+const MAX_COMPLETIONS = 100;
+
+const completionSchema = {
+  ...symbolPositionSchema,
+  resolve: z
+    .number()
+    .int()
+    .min(0)
+    .max(20)
+    .optional()
+    .default(5)
+    .describe(
+      'How many of the top suggestions to resolve for documentation and the extra edits, such as imports, accepting them makes. Defaults to 5.'
+    ),
+} as const;
+
+const completionZodSchema = z.object(completionSchema);
+
+const completionOutputSchema = {
+  total: z.number().int().describe('Suggestions before the top 100 cut.'),
+  suggestions: z
+    .array(
+      z.object({
+        label: z.string(),
+        kind: z.string(),
+        detail: z.string().optional(),
+        documentation: z.string().optional(),
+        insertText: z.string(),
+        resolved: z
+          .boolean()
+          .describe('Whether documentation and extra edits were resolved.'),
+        additionalEdits: z
+          .array(z.object({ ...positionOutputShape, newText: z.string() }))
+          .optional()
+          .describe('Other edits accepting it makes, such as an import.'),
+      })
+    )
+    .describe('Suggestions in the order the language server ranks them.'),
+  warnings: warningsOutputField,
+} as const;
 
 export function registerCompletionTool(server: McpServer, manager: LspManager) {
   server.registerTool(
@@ -19,11 +72,12 @@ export function registerCompletionTool(server: McpServer, manager: LspManager) {
     {
       title: 'Completion',
       description:
-        'Get context-aware code completions at a precise file position. For the best results, place the cursor immediately after the trigger point, for example right after `client.`.',
-      inputSchema: symbolPositionSchema,
+        'Get context-aware code completions at a precise file position, ranked by the language server, with documentation for the top ones. Use it to discover the members available on a type. For the best results, place the cursor immediately after the trigger point, for example right after `client.`.',
+      inputSchema: completionSchema,
+      outputSchema: completionOutputSchema,
     },
-    async (request) => {
-      const validatedRequest = validateSymbolPosition(request);
+    withErrorCodes(async (request) => {
+      const validatedRequest = completionZodSchema.parse(request);
       const session = await manager.getSessionForFile(validatedRequest.file);
 
       const symbolRequest = {
@@ -39,12 +93,14 @@ export function registerCompletionTool(server: McpServer, manager: LspManager) {
         symbolRequest
       );
       if (!prepared.ok) {
-        throw new Error(prepared.error.message);
+        throw toCodedError(prepared.error);
       }
 
-      const result = await LspOperations.completion(session, prepared.data);
+      const result = await LspOperations.completion(session, prepared.data, {
+        resolve: validatedRequest.resolve,
+      });
       if (!result.ok) {
-        throw new Error(result.error.message);
+        throw toCodedError(result.error);
       }
 
       const { result: completions, cursorContext } = result.data;
@@ -58,18 +114,81 @@ export function registerCompletionTool(server: McpServer, manager: LspManager) {
 
       sections.push(formattedText);
 
-      return {
-        content: [
-          {
-            type: 'text' as const,
-            text: sections.join('\n\n'),
-          },
-        ],
-      };
-    }
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      return createToolResult(sections.join('\n\n'), {
+        total: completions.length,
+        suggestions: completions.slice(0, MAX_COMPLETIONS).map((item) => {
+          const documentation = getDocumentationText(item.documentation);
+          return {
+            label: item.label,
+            kind: getSymbolKindName(item.kind),
+            ...(item.detail.trim() && { detail: item.detail.trim() }),
+            ...(documentation && { documentation }),
+            insertText: item.insertText,
+            resolved: Boolean(item.resolved),
+            ...(item.additionalTextEdits && {
+              additionalEdits: item.additionalTextEdits.map((edit) => ({
+                line: edit.range.start.line,
+                character: edit.range.start.character,
+                newText: edit.newText,
+              })),
+            }),
+          };
+        }),
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
   );
 }
 
+function getDocumentationText(
+  documentation: CompletionResult['documentation']
+): string {
+  if (typeof documentation === 'string') {
+    return documentation.trim();
+  }
+  return documentation.kind === 'markdown'
+    ? renderHoverMarkdown(documentation.value).trim()
+    : documentation.value.trim();
+}
+
+/**
+ * Resolved suggestions in rank order with their documentation and the
+ * extra edits accepting them makes
+ */
+function formatResolvedCompletions(completions: CompletionResult[]): string {
+  const lines = ['Top suggestions'];
+  for (const item of completions) {
+    const kindName = getSymbolKindName(item.kind).toLowerCase();
+    let line = `  ${item.label} (${kindName})`;
+    if (item.detail.trim() && item.detail.trim() !== item.label) {
+      line += ` - ${item.detail.trim()}`;
+    }
+    lines.push(line);
+
+    const paragraph = getDocumentationText(item.documentation).split(
+      /\n\s*\n/
+    )[0];
+    if (paragraph) {
+      lines.push(`    // ${paragraph.replace(/\s+/g, ' ')}`);
+    }
+    for (const edit of item.additionalTextEdits ?? []) {
+      const text = edit.newText.trim().split('\n')[0];
+      lines.push(
+        `    also edits ${edit.range.start.line}:${edit.range.start.character}${text ? `: ${text}` : ''}`
+      );
+    }
+  }
+  return lines.join('\n');
+}
+
 /**
  * Format completion results grouped by symbol kind with prioritization
  */
@@ -78,14 +197,8 @@ function formatCompletionResults(completions: CompletionResult[]): string {
     return 'Found no completion suggestions';
   }
 
-  const maxResults = 100;
-  const sortedCompletions = completions
-    .sort((a, b) => {
-      const sortA = a.sortText || a.label;
-      const sortB = b.sortText || b.label;
-      return sortA.localeCompare(sortB);
-    })
-    .slice(0, maxResults);
+  // Already ranked by the operation
+  const sortedCompletions = completions.slice(0, MAX_COMPLETIONS);
 
   const groupedByKind = new Map<number, CompletionResult[]>();
 
@@ -101,10 +214,21 @@ function formatCompletionResults(completions: CompletionResult[]): string {
 
   let result = `Found ${completions.length} completion suggestion${completions.length === 1 ? '' : 's'}`;
 
-  if (completions.length > maxResults) {
-    result += ` (showing top ${maxResults})`;
+  if (completions.length > MAX_COMPLETIONS) {
+    result += ` (showing top ${MAX_COMPLETIONS})`;
+  }
+
+  const resolved = completions.filter((item) => item.resolved);
+  if (resolved.length > 0) {
+    result += `\n\n${formatResolvedCompletions(resolved)}`;
   }
 
+  const omittedOverall = formatTruncationMarker(
+    completions.length - MAX_COMPLETIONS,
+    'suggestion',
+    'type more of the identifier to narrow the list'
+  );
+
   for (const kind of kindPriority) {
     const items = groupedByKind.get(kind);
     if (!items || items.length === 0) continue;
@@ -138,10 +262,18 @@ function formatCompletionResults(completions: CompletionResult[]): string {
       }
     }
 
-    if (items.length > 20) {
-      result += `\n  ... and ${items.length - 20} more`;
+    const omittedInKind = formatTruncationMarker(
+      items.length - 20,
+      kindName.toLowerCase()
+    );
+    if (omittedInKind) {
+      result += `\n  ${omittedInKind}`;
     }
   }
 
+  if (omittedOverall) {
+    result += `\n\n${omittedOverall}`;
+  }
+
   return result;
 }
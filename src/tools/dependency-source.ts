@@ -0,0 +1,113 @@
+/**
+ * Dependency Source Tool - Read the source of a class inside a dependency,
+ * which results point at with a server's own URI scheme such as jdt://
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { splitLines } from '../utils/text.js';
+import { formatPageMarker, paginate } from './truncation.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  pageOutputShape,
+  toPageOutput,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+const MAX_LINES = 300;
+
+const dependencySourceSchema = {
+  uri: z
+    .string()
+    .describe(
+      'URI of the document as it appears in a result, e.g. jdt://contents/rt.jar/java.util/List.class?=...'
+    ),
+  offset: z
+    .number()
+    .int()
+    .min(0)
+    .optional()
+    .default(0)
+    .describe(
+      'Lines to skip from the start of the document, as given in the truncation marker. Defaults to 0.'
+    ),
+} as const;
+
+const dependencySourceZodSchema = z.object(dependencySourceSchema);
+
+export function registerDependencySourceTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'dependency_source',
+    {
+      title: 'Dependency Source',
+      description:
+        'Read the source of a class inside a library, for results that point at a jdt:// (Java) or jar: (Scala) URI instead of a file. Comes from the source jar when there is one and is decompiled otherwise.',
+      inputSchema: dependencySourceSchema,
+      outputSchema: {
+        uri: z.string(),
+        ...pageOutputShape,
+        content: z
+          .string()
+          .describe('The returned lines, without line numbers.'),
+      },
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = dependencySourceZodSchema.parse(request);
+      const { uri } = validatedRequest;
+
+      const languageId = LspOperations.getVirtualDocumentLanguage(uri);
+      if (!languageId) {
+        throw new Error(
+          `Cannot read ${uri}: only jdt://, jar: and metalsDecode: URIs from results are supported`
+        );
+      }
+
+      // The URI came from a result, so the server that made it is running
+      const session = manager
+        .getStartedSessions()
+        .find((candidate) =>
+          Object.values(candidate.getProfile().config.extensions).includes(
+            languageId
+          )
+        );
+      if (!session) {
+        throw new Error(
+          `No running language server handles ${languageId}; query a ${languageId} file first`
+        );
+      }
+
+      const result = await LspOperations.virtualDocumentContents(session, uri);
+      if (!result.ok) throw toCodedError(result.error);
+
+      const lines = splitLines(result.data);
+      const page = paginate(lines, validatedRequest.offset, MAX_LINES);
+      const width = String(page.offset + page.items.length).length;
+      const sections = [
+        uri,
+        page.items
+          .map(
+            (line, index) =>
+              `${String(page.offset + index + 1).padStart(width)}| ${line}`
+          )
+          .join('\n'),
+      ];
+
+      const marker = formatPageMarker(page, 'line');
+      if (marker) {
+        sections.push(marker);
+      }
+
+      return createToolResult(sections.join('\n\n'), {
+        uri,
+        ...toPageOutput(page),
+        content: page.items.join('\n'),
+      });
+    })
+  );
+}
@@ -3,19 +3,67 @@
  */
 
 import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
 import { DiagnosticEntry } from '../types.js';
 import { prepareFileRequest } from '../preparation.js';
 import * as LspOperations from '../lsp/operations/index.js';
 import { diagnosticsSchema } from './schemas.js';
 import { validateDiagnostics } from './validation.js';
 import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import { withErrorCodes } from './errors.js';
+import { formatFilePath } from './utils.js';
+import {
+  createToolResult,
+  fileOutputField,
+  locationOutputSchema,
+  positionOutputShape,
+  toLocationOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
 
-function formatDiagnostics(diagnostics: DiagnosticEntry[]): string {
-  if (diagnostics.length === 0) {
-    return 'No diagnostics found for this file.';
-  }
+export const diagnosticOutputSchema = z.object({
+  ...positionOutputShape,
+  end: z.object(positionOutputShape),
+  severity: z.string().describe('Error, Warning, Info or Hint.'),
+  code: z.string(),
+  message: z.string(),
+  source: z.string(),
+  docs: z.string().optional().describe('Documentation link for the code.'),
+  related: z.array(locationOutputSchema.extend({ message: z.string() })),
+  fixes: z.array(z.string()).describe('Titles of the available quick fixes.'),
+});
+
+export function toDiagnosticOutput(
+  diagnostic: DiagnosticEntry
+): z.infer<typeof diagnosticOutputSchema> {
+  const { start, end } = diagnostic.range;
+  return {
+    line: start.line + 1,
+    character: start.character + 1,
+    end: { line: end.line + 1, character: end.character + 1 },
+    severity: getSeverityName(diagnostic.severity),
+    code: diagnostic.code,
+    message: diagnostic.message,
+    source: diagnostic.source,
+    ...(diagnostic.codeDescription && { docs: diagnostic.codeDescription }),
+    related: (diagnostic.relatedInformation ?? []).map((related) => ({
+      ...toLocationOutput(related.uri, related.range.start),
+      message: related.message,
+    })),
+    fixes: diagnostic.fixes ?? [],
+  };
+}
 
-  const sortedDiagnostics = [...diagnostics].sort((a, b) => {
+/**
+ * Most severe first, then in file order
+ */
+export function sortDiagnostics(
+  diagnostics: DiagnosticEntry[]
+): DiagnosticEntry[] {
+  return [...diagnostics].sort((a, b) => {
     if (a.severity !== b.severity) {
       return a.severity - b.severity;
     }
@@ -24,14 +72,38 @@ function formatDiagnostics(diagnostics: DiagnosticEntry[]): string {
     }
     return a.range.start.character - b.range.start.character;
   });
+}
 
-  return sortedDiagnostics
+export function formatDiagnostics(diagnostics: DiagnosticEntry[]): string {
+  if (diagnostics.length === 0) {
+    return 'No diagnostics found for this file.';
+  }
+
+  return sortDiagnostics(diagnostics)
     .map((diagnostic) => {
       const severitySymbol = getSeveritySymbol(diagnostic.severity);
       const severityName = getSeverityName(diagnostic.severity);
       const line = diagnostic.range.start.line + 1;
       const character = diagnostic.range.start.character + 1;
-      return `${severitySymbol} @${line}:${character} [${severityName}][${diagnostic.code}] ${diagnostic.message} (${diagnostic.source})`;
+      const lines = [
+        `${severitySymbol} @${line}:${character} [${severityName}][${diagnostic.code}] ${diagnostic.message} (${diagnostic.source})`,
+      ];
+
+      if (diagnostic.codeDescription) {
+        lines.push(`  docs: ${diagnostic.codeDescription}`);
+      }
+      for (const related of diagnostic.relatedInformation ?? []) {
+        const relatedLine = related.range.start.line + 1;
+        const relatedCharacter = related.range.start.character + 1;
+        lines.push(
+          `  related: ${formatFilePath(related.uri)}:${relatedLine}:${relatedCharacter} ${related.message}`
+        );
+      }
+      for (const fix of diagnostic.fixes ?? []) {
+        lines.push(`  fix: ${fix}`);
+      }
+
+      return lines.join('\n');
     })
     .join('\n');
 }
@@ -75,25 +147,43 @@ export function registerDiagnosticsTool(
     {
       title: 'Diagnostics',
       description:
-        'Return active diagnostics for a file, including errors, warnings, info, and hints reported by the language server.',
+        'Return active diagnostics for a file, including errors, warnings, info, and hints reported by the language server, with related locations and the quick fixes available for each. Use code_actions to see and apply a fix.',
       inputSchema: diagnosticsSchema,
+      outputSchema: {
+        file: fileOutputField,
+        diagnostics: z.array(diagnosticOutputSchema),
+        warnings: warningsOutputField,
+      },
     },
-    async (request) => {
+    withErrorCodes(async (request) => {
       const validatedRequest = validateDiagnostics(request);
       const session = await manager.getSessionForFile(validatedRequest.file);
       const prepared = prepareFileRequest(session, {
         file: validatedRequest.file,
       });
-      if (!prepared.ok) throw new Error(prepared.error.message);
+      if (!prepared.ok) throw toCodedError(prepared.error);
 
-      const result = await LspOperations.getDiagnostics(session, prepared.data);
-      if (!result.ok) throw new Error(result.error.message);
+      const result = await LspOperations.getDiagnostics(
+        session,
+        prepared.data,
+        { fixes: validatedRequest.fixes }
+      );
+      if (!result.ok) throw toCodedError(result.error);
 
-      return {
-        content: [
-          { type: 'text' as const, text: formatDiagnostics(result.data) },
-        ],
-      };
-    }
+      const formattedText = formatDiagnostics(result.data);
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+
+      return createToolResult(
+        staleWarning ? `${staleWarning}\n\n${formattedText}` : formattedText,
+        {
+          file: formatFilePath(prepared.data.filePath),
+          diagnostics: sortDiagnostics(result.data).map(toDiagnosticOutput),
+          warnings: toWarningsOutput(staleWarning),
+        }
+      );
+    })
   );
 }
@@ -10,7 +10,16 @@ import {
   SymbolInformation,
   DocumentSymbol,
   FlattenedSymbol,
+  InlayHint,
 } from '../types/lsp.js';
+import {
+  detectLineEnding,
+  type LineEnding,
+  sliceGraphemes,
+  splitLines,
+  truncateGraphemes,
+} from '../utils/text.js';
+import { interleaveInlayHints } from '../utils/inlay-hints.js';
 
 // Supported symbol types for enrichment
 type EnrichableSymbol =
@@ -28,8 +37,20 @@ interface EnrichedSymbol<T extends EnrichableSymbol = EnrichableSymbol> {
   error?: string;
 }
 
+interface CachedFile {
+  lines: string[];
+  lineEnding: LineEnding;
+}
+
 interface FileCache {
-  [filePath: string]: string[];
+  [filePath: string]: CachedFile;
+}
+
+/**
+ * Split file content into lines, remembering the original line ending
+ */
+function toCachedFile(content: string): CachedFile {
+  return { lines: splitLines(content), lineEnding: detectLineEnding(content) };
 }
 
 /**
@@ -54,7 +75,11 @@ function decodeFileUriToPath(uri: string): string {
  */
 export async function enrichSymbolsWithCode<T extends EnrichableSymbol>(
   symbols: T[],
-  options?: { extractFullDeclaration?: boolean }
+  options?: {
+    extractFullDeclaration?: boolean;
+    // Inlay hints to interleave into snippets, by file path
+    inlayHints?: ReadonlyMap<string, InlayHint[]>;
+  }
 ): Promise<EnrichedSymbol<T>[]> {
   const fileCache: FileCache = {};
   const enrichedSymbols: EnrichedSymbol<T>[] = [];
@@ -76,14 +101,31 @@ export async function enrichSymbolsWithCode<T extends EnrichableSymbol>(
   for (const [filePath, fileSymbols] of symbolsByFile) {
     try {
       const fileContent = await fs.readFile(filePath, 'utf-8');
-      const lines = fileContent.split('\n');
-      fileCache[filePath] = lines;
+      const file = toCachedFile(fileContent);
+      fileCache[filePath] = file;
+
+      const hints = options?.inlayHints?.get(filePath);
+      const hinted = hints?.length
+        ? interleaveInlayHints(file.lines, hints)
+        : null;
 
       // Extract code snippets for all symbols in this file
       for (const symbol of fileSymbols) {
-        const range = extractRangeFromSymbol(symbol);
+        const symbolRange = extractRangeFromSymbol(symbol);
+        const range =
+          symbolRange && hinted
+            ? {
+                start: hinted.mapPosition(symbolRange.start),
+                end: hinted.mapPosition(symbolRange.end),
+              }
+            : symbolRange;
         const snippet = range
-          ? extractCodeSnippet(lines, range, options?.extractFullDeclaration)
+          ? extractCodeSnippet(
+              hinted?.lines ?? file.lines,
+              range,
+              options?.extractFullDeclaration,
+              file.lineEnding
+            )
           : null;
         const enriched: EnrichedSymbol<T> = { symbol };
         if (snippet) {
@@ -133,11 +175,13 @@ function extractRangeFromSymbol(symbol: EnrichableSymbol): Range | null {
  * @param fileLines - Array of file lines
  * @param range - LSP range (0-based)
  * @param extractFullDeclaration - If true, extracts from character 0 to get full declaration with modifiers
+ * @param lineEnding - Line ending used to join multi-line snippets, matching the source file
  */
 function extractCodeSnippet(
   fileLines: string[],
   range: Range,
-  extractFullDeclaration?: boolean
+  extractFullDeclaration?: boolean,
+  lineEnding: LineEnding = '\n'
 ): string {
   const startLine = range.start.line;
   let endLine = range.end.line;
@@ -172,7 +216,7 @@ function extractCodeSnippet(
     // Single line snippet
     const line = fileLines[startLine];
     if (!line) return '// Code snippet unavailable - line not found';
-    return line.substring(startChar, endChar);
+    return sliceGraphemes(line, startChar, endChar);
   }
 
   // Multi-line snippet
@@ -181,7 +225,7 @@ function extractCodeSnippet(
   // First line (from startChar to end)
   const firstLine = fileLines[startLine];
   if (firstLine) {
-    snippetLines.push(firstLine.substring(startChar));
+    snippetLines.push(sliceGraphemes(firstLine, startChar));
   }
 
   // Middle lines (full lines)
@@ -196,11 +240,11 @@ function extractCodeSnippet(
   if (endLine < fileLines.length) {
     const lastLine = fileLines[endLine];
     if (lastLine !== undefined) {
-      snippetLines.push(lastLine.substring(0, endChar));
+      snippetLines.push(sliceGraphemes(lastLine, 0, endChar));
     }
   }
 
-  return snippetLines.join('\n');
+  return snippetLines.join(lineEnding);
 }
 
 /**
@@ -213,7 +257,7 @@ export function createSignaturePreview(
 ): string {
   // Normalize whitespace: replace newlines with spaces, collapse multiple spaces
   const normalized = codeSnippet
-    .replace(/\n/g, ' ') // Replace newlines with spaces
+    .replace(/\r?\n/g, ' ') // Replace newlines with spaces
     .replace(/\s+/g, ' ') // Collapse multiple whitespace to single space
     .trim(); // Remove leading/trailing whitespace
 
@@ -223,11 +267,12 @@ export function createSignaturePreview(
   }
 
   // Find a good truncation point (prefer to break at word boundaries)
-  const truncated = normalized.substring(0, maxChars - 3);
+  const truncated = truncateGraphemes(normalized, maxChars - 3);
   const lastSpace = truncated.lastIndexOf(' ');
 
-  // If we found a space near the end, break there; otherwise just cut at maxChars
-  const breakPoint = lastSpace > maxChars - 20 ? lastSpace : maxChars - 3;
+  // If we found a space near the end, break there; otherwise cut on the
+  // last whole grapheme so multi-byte characters are never split
+  const breakPoint = lastSpace > maxChars - 20 ? lastSpace : truncated.length;
   return normalized.substring(0, breakPoint) + '...';
 }
 
@@ -247,7 +292,7 @@ export async function enrichSymbolLocations(
       // Load file if not cached
       if (!fileCache[filePath]) {
         const fileContent = await fs.readFile(filePath, 'utf-8');
-        fileCache[filePath] = fileContent.split('\n');
+        fileCache[filePath] = toCachedFile(fileContent);
       }
 
       // Expand range to get full line context
@@ -256,7 +301,10 @@ export async function enrichSymbolLocations(
         end: { line: location.range.start.line, character: 1000 },
       };
 
-      const snippet = extractCodeSnippet(fileCache[filePath], expandedRange);
+      const snippet = extractCodeSnippet(
+        fileCache[filePath].lines,
+        expandedRange
+      );
       results.push({ codeSnippet: snippet });
     } catch {
       // If we can't read the file, just return null for the snippet
@@ -0,0 +1,54 @@
+/**
+ * Tool error results - attach stable error codes to failed tool calls
+ */
+
+import type { CallToolResult } from '@modelcontextprotocol/sdk/types.js';
+import { ZodError } from 'zod';
+import { classifyError } from '../utils/error-codes.js';
+
+function getErrorMessage(error: unknown): string {
+  if (error instanceof ZodError) {
+    return error.issues
+      .map((issue) =>
+        issue.path.length > 0
+          ? `${issue.path.join('.')}: ${issue.message}`
+          : issue.message
+      )
+      .join('\n');
+  }
+  return error instanceof Error ? error.message : String(error);
+}
+
+/**
+ * Build an error result whose text ends with the error code and whose
+ * `_meta` carries the code and category for programmatic callers
+ */
+export function createToolErrorResult(error: unknown): CallToolResult {
+  const { code, category } = classifyError(error);
+
+  return {
+    isError: true,
+    content: [
+      {
+        type: 'text' as const,
+        text: `${getErrorMessage(error)}\nError code: ${code} (${category})`,
+      },
+    ],
+    _meta: { errorCode: code, errorCategory: category },
+  };
+}
+
+/**
+ * Wrap a tool handler so thrown errors become coded error results
+ */
+export function withErrorCodes<Args extends unknown[]>(
+  handler: (...args: Args) => Promise<CallToolResult>
+): (...args: Args) => Promise<CallToolResult> {
+  return async (...args: Args) => {
+    try {
+      return await handler(...args);
+    } catch (error) {
+      return createToolErrorResult(error);
+    }
+  };
+}
@@ -0,0 +1,244 @@
+/**
+ * Execute Command Tool - List the commands a language server registers and
+ * run one through workspace/executeCommand
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import { prepareFileRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import type { Command } from '../types/lsp.js';
+import { CodedError, toCodedError } from '../utils/error-codes.js';
+import { createUnifiedDiff } from '../utils/unified-diff.js';
+import { applyWorkspaceChanges, formatFilePath } from './utils.js';
+import { formatEditsSchema } from './schemas.js';
+import { formatWrittenChanges, type FormatEditsMode } from './format-edits.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  fileChangeOutputSchema,
+  toFileChangeOutputs,
+} from './output-schemas.js';
+
+// Longest command result shown, in characters
+const MAX_RESULT_LENGTH = 2000;
+
+const executeCommandSchema = {
+  file: z
+    .string()
+    .describe(
+      'File whose language server runs the command. Accepts either an absolute path or a path relative to the current workspace.'
+    ),
+  command: z
+    .string()
+    .optional()
+    .describe(
+      'Command to run, as listed when this is omitted, for example rust-analyzer.expandMacro or gopls.gc_details.'
+    ),
+  arguments: z
+    .array(z.unknown())
+    .optional()
+    .describe(
+      'Arguments passed to the command as they are, usually objects with document URIs and positions.'
+    ),
+  dryRun: z
+    .boolean()
+    .optional()
+    .default(false)
+    .describe(
+      'Return the diff without writing any file. The command still runs on the language server. Defaults to false.'
+    ),
+  ...formatEditsSchema,
+} as const;
+
+const executeCommandZodSchema = z.object(executeCommandSchema);
+
+export const commandRunOutputSchema = z.object({
+  command: z.string(),
+  result: z.unknown().optional().describe('What the command returned.'),
+  written: z.boolean().describe('Whether its edits were written to disk.'),
+  skippedOperations: z
+    .number()
+    .int()
+    .describe('File creations, renames and deletions that were skipped.'),
+  files: z.array(fileChangeOutputSchema),
+});
+
+type CommandRunOutput = z.infer<typeof commandRunOutputSchema>;
+
+const executeCommandOutputSchema = {
+  commands: z
+    .array(z.string())
+    .optional()
+    .describe('Commands the server registers, when no command was given.'),
+  run: commandRunOutputSchema
+    .optional()
+    .describe('The command that ran, when one was given.'),
+} as const;
+
+function formatCommandResult(result: unknown): string | null {
+  if (result === null || result === undefined) {
+    return null;
+  }
+  const text =
+    typeof result === 'string' ? result : JSON.stringify(result, null, 2);
+  return text.length > MAX_RESULT_LENGTH
+    ? `${text.slice(0, MAX_RESULT_LENGTH)}\n... truncated`
+    : text;
+}
+
+/**
+ * Run a command on the language server for a file, write the edits it makes
+ * unless dryRun is set, and describe what it returned and changed
+ */
+export async function runServerCommand(
+  manager: LspManager,
+  filePath: string,
+  command: Command,
+  options: {
+    dryRun: boolean;
+    formatEdits: FormatEditsMode;
+  }
+): Promise<{ text: string; output: CommandRunOutput }> {
+  const { dryRun } = options;
+  const session = await manager.getSessionForFile(filePath);
+  const result = await LspOperations.executeCommand(
+    session,
+    { filePath },
+    command
+  );
+  if (!result.ok) throw toCodedError(result.error);
+
+  // Edits were computed against the content sent to the server; applying
+  // them to a file that changed since would corrupt it
+  if (await session.isDocumentStale(filePath)) {
+    throw new CodedError(
+      `${formatFilePath(filePath)} changed on disk while the command ran. No edits were applied; run the command again.`,
+      'STALE_CONTENT',
+      'workspace'
+    );
+  }
+
+  const changeResults = await applyWorkspaceChanges(result.data.changes, {
+    dryRun,
+  });
+  const formatNotes = dryRun
+    ? []
+    : await formatWrittenChanges(manager, changeResults, options.formatEdits);
+
+  const sections = [
+    command.title && command.title !== command.command
+      ? `Ran "${command.title}" (${command.command}) on the language server`
+      : `Ran ${command.command} on the language server`,
+  ];
+  const output = formatCommandResult(result.data.result);
+  if (output) {
+    sections.push(`Result:\n${output}`);
+  }
+
+  if (changeResults.length > 0) {
+    const appliedCount = changeResults.reduce(
+      (sum, change) => sum + change.appliedChanges.length,
+      0
+    );
+    sections.push(
+      `${dryRun ? 'Dry run of' : 'Applied'} ${appliedCount} changes across ${changeResults.length} file(s)`
+    );
+  }
+  if (result.data.skippedOperations > 0) {
+    sections.push(
+      `Skipped ${result.data.skippedOperations} file creation, rename or deletion operation(s), which aren't supported`
+    );
+  }
+  sections.push(...formatNotes);
+
+  for (const change of changeResults) {
+    const changedPath = formatFilePath(change.fileUri);
+    if (change.error) {
+      sections.push(`✘ ${changedPath}: ${change.error}`);
+      continue;
+    }
+    for (const failed of change.failedChanges) {
+      sections.push(
+        `✘ ${changedPath}:${failed.line}:${failed.character}: ${failed.error}`
+      );
+    }
+
+    const diff = createUnifiedDiff(
+      changedPath,
+      change.originalContent ?? '',
+      change.updatedContent ?? change.originalContent ?? ''
+    );
+    if (diff) {
+      sections.push(`\`\`\`diff\n${diff}\n\`\`\``);
+    }
+  }
+
+  return {
+    text: sections.join('\n\n'),
+    output: {
+      command: command.command,
+      ...(result.data.result !== null &&
+        result.data.result !== undefined && { result: result.data.result }),
+      written: !dryRun,
+      skippedOperations: result.data.skippedOperations,
+      files: toFileChangeOutputs(changeResults),
+    },
+  };
+}
+
+export function registerExecuteCommandTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'execute_command',
+    {
+      title: 'Execute Command',
+      description:
+        "Run a command the language server for a file registers through workspace/executeCommand, such as rust-analyzer's expand macro or gopls' gc_details, returning its result and writing any edits it makes as a diff. Omit command to list the commands the server offers.",
+      inputSchema: executeCommandSchema,
+      outputSchema: executeCommandOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = executeCommandZodSchema.parse(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+
+      const prepared = prepareFileRequest(session, {
+        file: validatedRequest.file,
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      if (validatedRequest.command) {
+        const { text, output } = await runServerCommand(
+          manager,
+          prepared.data.filePath,
+          {
+            title: validatedRequest.command,
+            command: validatedRequest.command,
+            ...(validatedRequest.arguments && {
+              arguments: validatedRequest.arguments,
+            }),
+          },
+          {
+            dryRun: validatedRequest.dryRun,
+            formatEdits: validatedRequest.formatEdits,
+          }
+        );
+        return createToolResult(text, { run: output });
+      }
+
+      const result = await LspOperations.serverCommands(session, prepared.data);
+      if (!result.ok) throw toCodedError(result.error);
+
+      const profile = session.getProfile().name;
+      return createToolResult(
+        result.data.length > 0
+          ? `Commands registered by ${profile} (${result.data.length})\n${result.data.map((command) => `  ${command}`).join('\n')}`
+          : `${profile} registers no commands`,
+        { commands: result.data }
+      );
+    })
+  );
+}
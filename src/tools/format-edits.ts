@@ -0,0 +1,98 @@
+/**
+ * Format after edits - run the language server's formatter over code an
+ * edit just wrote, so inserted code follows the project's style
+ */
+
+import type { Range } from 'vscode-languageserver-protocol';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { prepareFileRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { detectIndentation, splitLines } from '../utils/text.js';
+import { toAbsoluteFilePath } from '../utils/path-format.js';
+import { getChangedLineRanges } from '../utils/unified-diff.js';
+import {
+  applyWorkspaceChanges,
+  formatFilePath,
+  type ChangeResult,
+} from './utils.js';
+import { asCodedError, toCodedError } from '../utils/error-codes.js';
+
+export type FormatEditsMode = 'none' | 'edited' | 'file';
+
+/**
+ * Edited line ranges of a file, each spanning its lines in full
+ */
+function toEditedRanges(before: string, after: string): Range[] {
+  const lines = splitLines(after);
+  return getChangedLineRanges(before, after).map(({ start, end }) => ({
+    start: { line: start, character: 0 },
+    end: { line: end, character: lines[end]?.length ?? 0 },
+  }));
+}
+
+/**
+ * Format the files that `changes` wrote. Each result's updatedContent
+ * becomes the formatted content, so its diff covers both the edits and the
+ * formatting. Returns a note per file; a file that fails to format keeps
+ * its edits as written.
+ */
+export async function formatWrittenChanges(
+  manager: LspManager,
+  changes: ChangeResult[],
+  mode: FormatEditsMode
+): Promise<string[]> {
+  const notes: string[] = [];
+  if (mode === 'none') {
+    return notes;
+  }
+
+  for (const change of changes) {
+    const before = change.originalContent;
+    const after = change.updatedContent;
+    if (change.error || before === undefined || after === undefined) {
+      continue;
+    }
+    if (before === after) {
+      continue;
+    }
+
+    const filePath = formatFilePath(change.fileUri);
+    try {
+      const absolutePath = toAbsoluteFilePath(change.fileUri);
+      const session = await manager.getSessionForFile(absolutePath);
+      const prepared = prepareFileRequest(session, { file: absolutePath });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const options = detectIndentation(after);
+      const result =
+        mode === 'file'
+          ? await LspOperations.formatDocument(session, prepared.data, options)
+          : await LspOperations.formatEditedRanges(
+              session,
+              prepared.data,
+              options,
+              toEditedRanges(before, after),
+              splitLines(after)
+            );
+      if (!result.ok) throw toCodedError(result.error);
+
+      const [formatted] = await applyWorkspaceChanges(result.data);
+      if (formatted?.error) throw asCodedError(formatted.error);
+      if (!formatted || formatted.appliedChanges.length === 0) {
+        continue;
+      }
+
+      change.updatedContent = formatted.updatedContent ?? after;
+      notes.push(
+        `Formatted ${mode === 'file' ? '' : 'edited lines in '}${filePath}: ${formatted.appliedChanges.length} edits`
+      );
+    } catch (error) {
+      const failure = asCodedError(error);
+      notes.push(
+        `✘ Could not format ${filePath}: ${failure.message} (error code ${failure.code})`
+      );
+    }
+  }
+
+  return notes;
+}
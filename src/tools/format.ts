@@ -0,0 +1,129 @@
+/**
+ * Format Tool - Run the language server's formatter over a file or a range
+ */
+
+import * as fs from 'fs';
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import type { Range } from 'vscode-languageserver-protocol';
+import { z } from 'zod';
+import { prepareFileRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { formatSchema } from './schemas.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { CodedError, toCodedError } from '../utils/error-codes.js';
+import { detectIndentation, splitLines } from '../utils/text.js';
+import { createUnifiedDiff } from '../utils/unified-diff.js';
+import { applyWorkspaceChanges, formatFilePath } from './utils.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  fileChangeOutputSchema,
+  toFileChangeOutputs,
+} from './output-schemas.js';
+
+const formatZodSchema = z.object(formatSchema);
+
+const formatOutputSchema = {
+  written: z.boolean().describe('Whether the file was written.'),
+  files: z
+    .array(fileChangeOutputSchema)
+    .describe('The formatted file, empty when it was already formatted.'),
+} as const;
+
+export function registerFormatTool(server: McpServer, manager: LspManager) {
+  server.registerTool(
+    'format',
+    {
+      title: 'Format',
+      description:
+        "Format a file, or the lines between startLine and endLine, with the language server's formatter so edited code follows the project's style. Indentation defaults to what the file already uses. Writes the file and returns a unified diff; pass dryRun to preview the diff without writing.",
+      inputSchema: formatSchema,
+      outputSchema: formatOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = formatZodSchema.parse(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+      const prepared = prepareFileRequest(session, {
+        file: validatedRequest.file,
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const { filePath } = prepared.data;
+      const content = await fs.promises.readFile(filePath, 'utf-8');
+      const lines = splitLines(content);
+
+      let range: Range | undefined;
+      if (validatedRequest.startLine !== undefined) {
+        const startLine = validatedRequest.startLine;
+        const endLine = validatedRequest.endLine ?? startLine;
+        if (endLine < startLine || endLine > lines.length) {
+          throw new Error(
+            `Line range ${startLine}-${endLine} is out of bounds for ${formatFilePath(filePath)}, which has ${lines.length} lines`
+          );
+        }
+        range = {
+          start: { line: startLine - 1, character: 0 },
+          end: { line: endLine - 1, character: lines[endLine - 1]!.length },
+        };
+      }
+
+      const detected = detectIndentation(content);
+      const result = await LspOperations.formatDocument(
+        session,
+        prepared.data,
+        {
+          tabSize: validatedRequest.tabSize ?? detected.tabSize,
+          insertSpaces: validatedRequest.insertSpaces ?? detected.insertSpaces,
+        },
+        range
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      // Edits were computed against the content sent to the server; applying
+      // them to a file that changed since would corrupt it
+      if (await session.isDocumentStale(filePath)) {
+        throw new CodedError(
+          `${formatFilePath(filePath)} changed on disk while it was formatted. No edits were applied; run format again.`,
+          'STALE_CONTENT',
+          'workspace'
+        );
+      }
+
+      const { dryRun } = validatedRequest;
+      const [change] = await applyWorkspaceChanges(result.data, { dryRun });
+      if (change?.error) {
+        throw new Error(
+          `Failed to apply formatting to ${formatFilePath(filePath)}: ${change.error}`
+        );
+      }
+
+      const diff = change
+        ? createUnifiedDiff(
+            formatFilePath(filePath),
+            change.originalContent ?? '',
+            change.updatedContent ?? change.originalContent ?? ''
+          )
+        : '';
+
+      const sections: string[] = [];
+      if (!change || !diff) {
+        sections.push(`${formatFilePath(filePath)} is already formatted`);
+      } else {
+        sections.push(
+          `${dryRun ? 'Dry run of formatting' : 'Formatted'} ${formatFilePath(filePath)}: ${change.appliedChanges.length} edits`
+        );
+        for (const failed of change.failedChanges) {
+          sections.push(
+            `✘ @${failed.line}:${failed.character}: ${failed.error}`
+          );
+        }
+        sections.push(`\`\`\`diff\n${diff}\n\`\`\``);
+      }
+
+      return createToolResult(sections.join('\n\n'), {
+        written: !dryRun && Boolean(diff),
+        files: change && diff ? toFileChangeOutputs([change]) : [],
+      });
+    })
+  );
+}
@@ -0,0 +1,149 @@
+/**
+ * Highlights Tool - Find the occurrences of a symbol within its file, tagged
+ * as reads or writes
+ */
+
+import * as fs from 'fs';
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import { createOneBasedPosition } from '../types.js';
+import { prepareSymbolPositionRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { symbolPositionSchema } from './schemas.js';
+import { validateSymbolPosition } from './validation.js';
+import { formatCursorContext } from '../utils/cursor-context.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import type { HighlightEntry } from '../types/lsp.js';
+import { getStaleContentWarning } from './staleness.js';
+import { createSignaturePreview } from './enrichment.js';
+import { splitLines } from '../utils/text.js';
+import { formatFilePath } from './utils.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  fileOutputField,
+  positionOutputShape,
+  symbolAtCursorOutputField,
+  toSymbolAtCursorOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+const highlightsOutputSchema = {
+  symbol: symbolAtCursorOutputField,
+  file: fileOutputField,
+  occurrences: z.array(
+    z.object({
+      ...positionOutputShape,
+      access: z
+        .enum(['read', 'write', 'text'])
+        .describe('text when the server does not report the access.'),
+    })
+  ),
+  warnings: warningsOutputField,
+} as const;
+
+export function registerHighlightsTool(server: McpServer, manager: LspManager) {
+  server.registerTool(
+    'highlights',
+    {
+      title: 'Highlights',
+      description:
+        'List every occurrence of the symbol at a file position within that file, tagging each as a read or a write where the language server reports it. Use it to see where a variable or field is assigned versus only used.',
+      inputSchema: symbolPositionSchema,
+      outputSchema: highlightsOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = validateSymbolPosition(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+
+      const prepared = await prepareSymbolPositionRequest(session, {
+        file: validatedRequest.file,
+        position: createOneBasedPosition(
+          validatedRequest.line,
+          validatedRequest.character
+        ),
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const result = await LspOperations.documentHighlights(
+        session,
+        prepared.data
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      const sections: string[] = [];
+      const { cursorContext } = result.data;
+
+      if (cursorContext) {
+        sections.push(formatCursorContext(cursorContext));
+      }
+
+      const content = await fs.promises.readFile(
+        prepared.data.filePath,
+        'utf-8'
+      );
+      sections.push(
+        formatHighlights(
+          prepared.data.filePath,
+          splitLines(content),
+          result.data.result,
+          cursorContext?.symbolName || 'symbol'
+        )
+      );
+
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      return createToolResult(sections.join('\n\n'), {
+        symbol: toSymbolAtCursorOutput(cursorContext),
+        file: formatFilePath(prepared.data.filePath),
+        occurrences: result.data.result.map(({ range, access }) => ({
+          line: range.start.line + 1,
+          character: range.start.character + 1,
+          access,
+        })),
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
+}
+
+function formatHighlights(
+  filePath: string,
+  lines: string[],
+  highlights: HighlightEntry[],
+  symbolName: string
+): string {
+  if (highlights.length === 0) {
+    return 'Found no occurrences';
+  }
+
+  const writes = highlights.filter(({ access }) => access === 'write').length;
+  const reads = highlights.filter(({ access }) => access === 'read').length;
+  const counts =
+    writes + reads > 0
+      ? ` (${writes} write${writes === 1 ? '' : 's'}, ${reads} read${reads === 1 ? '' : 's'})`
+      : ' (read and write access not reported)';
+
+  const entries = highlights.map(({ range, access }) => {
+    const tag = access === 'text' ? '' : ` [${access}]`;
+    let entry = `  @${range.start.line + 1}:${range.start.character + 1}${tag} ${symbolName}`;
+    const line = lines[range.start.line];
+    if (line?.trim()) {
+      entry += `\n    \`${createSignaturePreview(line, 100)}\``;
+    }
+    return entry;
+  });
+
+  return [
+    `Found ${highlights.length} occurrence(s) in ${formatFilePath(filePath)}${counts}`,
+    ...entries,
+  ].join('\n');
+}
@@ -0,0 +1,196 @@
+/**
+ * Tool hooks - external commands that transform tool results before they
+ * are returned, configured per tool under `hooks` in the config file
+ */
+
+import * as cp from 'child_process';
+import {
+  CallToolResultSchema,
+  type CallToolResult,
+} from '@modelcontextprotocol/sdk/types.js';
+import { CodedError } from '../utils/error-codes.js';
+import logger from '../utils/logger.js';
+import { createToolErrorResult } from './errors.js';
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+
+// Hooks under this key run for every tool, after the tool's own hooks
+export const ALL_TOOLS_HOOK_KEY = '*';
+
+// A hook printing more than this fails rather than being buffered whole
+const MAX_HOOK_OUTPUT_BYTES = 4 * 1024 * 1024;
+// Only the start of a hook's stderr is kept for its error message
+const MAX_HOOK_STDERR_BYTES = 64 * 1024;
+
+export interface ToolHook {
+  // Shell command, run from the workspace directory
+  command: string;
+  timeout_ms: number;
+}
+
+// Written to the hook's stdin as JSON
+export interface ToolHookInput {
+  tool: string;
+  arguments: unknown;
+  result: CallToolResult;
+}
+
+interface ToolHookSettings {
+  workspace: string;
+  hooks: Record<string, ToolHook[]>;
+}
+
+const getState = defineWorkspaceState<{ settings: ToolHookSettings }>(() => ({
+  settings: { workspace: process.cwd(), hooks: {} },
+}));
+
+/**
+ * Replace the configured hooks, e.g. after the config file is reloaded
+ */
+export function configureToolHooks(update: ToolHookSettings): void {
+  getState().settings = update;
+}
+
+/**
+ * Hooks that apply to a tool, in the order they run
+ */
+export function getToolHooks(tool: string): ToolHook[] {
+  const { settings } = getState();
+  return [
+    ...(settings.hooks[tool] ?? []),
+    ...(settings.hooks[ALL_TOOLS_HOOK_KEY] ?? []),
+  ];
+}
+
+function createHookError(hook: ToolHook, reason: string): CodedError {
+  return new CodedError(
+    `Tool hook \`${hook.command}\` ${reason}`,
+    'HOOK_FAILED',
+    'config'
+  );
+}
+
+/**
+ * Run one hook. The hook reads a ToolHookInput from stdin and prints the
+ * tool result to return; printing nothing keeps the result unchanged.
+ */
+export async function runToolHook(
+  hook: ToolHook,
+  input: ToolHookInput,
+  cwd: string
+): Promise<CallToolResult> {
+  const stdout = await new Promise<string>((resolve, reject) => {
+    const child = cp.spawn(hook.command, {
+      cwd,
+      shell: true,
+      stdio: ['pipe', 'pipe', 'pipe'],
+    });
+    const output: Buffer[] = [];
+    let outputSize = 0;
+    const stderr: Buffer[] = [];
+    let stderrSize = 0;
+    const timer = setTimeout(() => {
+      child.kill('SIGKILL');
+      reject(createHookError(hook, `timed out after ${hook.timeout_ms}ms`));
+    }, hook.timeout_ms);
+
+    child.stdout.on('data', (data: Buffer) => {
+      if (outputSize > MAX_HOOK_OUTPUT_BYTES) {
+        return;
+      }
+      outputSize += data.length;
+      if (outputSize > MAX_HOOK_OUTPUT_BYTES) {
+        clearTimeout(timer);
+        child.kill('SIGKILL');
+        reject(
+          createHookError(
+            hook,
+            `printed more than ${MAX_HOOK_OUTPUT_BYTES} bytes`
+          )
+        );
+        return;
+      }
+      output.push(data);
+    });
+    child.stderr.on('data', (data: Buffer) => {
+      if (stderrSize < MAX_HOOK_STDERR_BYTES) {
+        stderr.push(data.subarray(0, MAX_HOOK_STDERR_BYTES - stderrSize));
+      }
+      stderrSize += data.length;
+    });
+    child.on('error', (error) => {
+      clearTimeout(timer);
+      reject(createHookError(hook, `could not start: ${error.message}`));
+    });
+    child.on('close', (code) => {
+      clearTimeout(timer);
+      if (code !== 0) {
+        const message = Buffer.concat(stderr).toString('utf8').trim();
+        const details = message ? `: ${message}` : '';
+        reject(createHookError(hook, `exited with code ${code}${details}`));
+        return;
+      }
+      resolve(Buffer.concat(output).toString('utf8'));
+    });
+
+    // Hooks that never read their input close stdin early
+    child.stdin.on('error', () => undefined);
+    child.stdin.end(JSON.stringify(input));
+  });
+
+  if (!stdout.trim()) {
+    return input.result;
+  }
+  try {
+    return CallToolResultSchema.parse(JSON.parse(stdout));
+  } catch (error) {
+    throw createHookError(
+      hook,
+      `printed an invalid tool result: ${error instanceof Error ? error.message : String(error)}`
+    );
+  }
+}
+
+/**
+ * Pass a tool result through the tool's hooks in order. A failing hook
+ * fails the call rather than returning the result it should have changed.
+ */
+export async function applyToolHooks(
+  tool: string,
+  args: unknown,
+  result: CallToolResult
+): Promise<CallToolResult> {
+  const { workspace } = getState().settings;
+  let current = result;
+  for (const hook of getToolHooks(tool)) {
+    try {
+      current = await runToolHook(
+        hook,
+        { tool, arguments: args, result: current },
+        workspace
+      );
+    } catch (error) {
+      logger.warn('Tool hook failed', {
+        tool,
+        command: hook.command,
+        error: error instanceof Error ? error.message : String(error),
+      });
+      return createToolErrorResult(error);
+    }
+  }
+  return current;
+}
+
+/**
+ * Carry the tool's structured content over to a successful result that
+ * enrichers or hooks rewrote without it, as the tool's output schema
+ * requires it
+ */
+export function keepStructuredContent(
+  original: CallToolResult,
+  final: CallToolResult
+): CallToolResult {
+  if (final.isError || final.structuredContent || !original.structuredContent) {
+    return final;
+  }
+  return { ...final, structuredContent: original.structuredContent };
+}
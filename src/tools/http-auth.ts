@@ -0,0 +1,220 @@
+/**
+ * HTTP authentication - bearer tokens and mTLS client certificates with
+ * per-client scopes, required before serving beyond localhost
+ */
+
+import { createHash, timingSafeEqual } from 'crypto';
+import * as fs from 'fs';
+import type { IncomingMessage } from 'http';
+import type { ServerOptions } from 'https';
+import * as path from 'path';
+import { TLSSocket } from 'tls';
+import type { AuthInfo } from '@modelcontextprotocol/sdk/server/auth/types.js';
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+import { CodedError } from '../utils/error-codes.js';
+import { expandEnvVars } from '../utils/env-expansion.js';
+import logger from '../utils/logger.js';
+
+// read covers tools that only look at code; edit adds those that change it
+const AUTH_SCOPES = ['read', 'edit'] as const;
+
+type AuthScope = (typeof AUTH_SCOPES)[number];
+
+// Tools that write to the workspace
+const EDIT_TOOLS = new Set([
+  'rename',
+  'apply_code_action',
+  'run_code_lens',
+  'execute_command',
+  'format',
+]);
+
+interface AuthTokenSettings {
+  // Client identity in rate limits and the audit log
+  name: string;
+  // Usually `${ENV_VAR}`, expanded when the config is loaded
+  token: string;
+  scopes: AuthScope[];
+}
+
+interface MtlsClientSettings {
+  // Common name of the client certificate subject
+  subject: string;
+  scopes: AuthScope[];
+}
+
+interface MtlsSettings {
+  // PEM files, relative to the workspace
+  cert: string;
+  key: string;
+  ca: string;
+  clients: MtlsClientSettings[];
+}
+
+interface HttpAuthSettings {
+  tokens: AuthTokenSettings[];
+  mtls?: MtlsSettings | undefined;
+}
+
+const getState = defineWorkspaceState<{ settings: HttpAuthSettings | null }>(
+  () => ({ settings: null })
+);
+
+/**
+ * Replace the authentication settings, e.g. after the config file is
+ * reloaded. Tokens referencing unset environment variables are dropped
+ * rather than accepted literally.
+ */
+export function configureHttpAuth(update: {
+  workspace: string;
+  auth: HttpAuthSettings | null;
+}): void {
+  if (!update.auth) {
+    getState().settings = null;
+    return;
+  }
+
+  const tokens = update.auth.tokens.flatMap((entry) => {
+    const token = expandEnvVars(entry.token, process.env);
+    if (!token || /\$\{[^}]+\}|\$[A-Za-z_]/.test(token)) {
+      logger.warn('Ignoring auth token with an unset variable', {
+        name: entry.name,
+      });
+      return [];
+    }
+    return [{ ...entry, token }];
+  });
+  const mtls = update.auth.mtls && {
+    ...update.auth.mtls,
+    cert: path.resolve(update.workspace, update.auth.mtls.cert),
+    key: path.resolve(update.workspace, update.auth.mtls.key),
+    ca: path.resolve(update.workspace, update.auth.mtls.ca),
+  };
+  getState().settings = { tokens, ...(mtls && { mtls }) };
+}
+
+function isAuthConfigured(): boolean {
+  const { settings } = getState();
+  return !!settings && (settings.tokens.length > 0 || !!settings.mtls);
+}
+
+/**
+ * Whether a bind address only accepts connections from this machine
+ */
+export function isLoopbackHost(host: string): boolean {
+  return (
+    host === 'localhost' ||
+    host === '::1' ||
+    host === '[::1]' ||
+    /^127(\.\d{1,3}){3}$/.test(host)
+  );
+}
+
+/**
+ * Refuse to listen beyond localhost unless clients must authenticate
+ */
+export function assertHttpAuthForHost(host: string): void {
+  if (!isLoopbackHost(host) && !isAuthConfigured()) {
+    throw new CodedError(
+      `Refusing to serve on ${host} without authentication; configure auth tokens or mTLS, or bind to localhost`,
+      'AUTH_REQUIRED',
+      'config'
+    );
+  }
+}
+
+/**
+ * TLS options for serving HTTPS when mTLS is configured, or null to serve
+ * plain HTTP. Client certificates are requested but not required, so
+ * clients can still present a bearer token instead.
+ */
+export function getHttpsServerOptions(): ServerOptions | null {
+  const { settings } = getState();
+  if (!settings?.mtls) {
+    return null;
+  }
+  return {
+    cert: fs.readFileSync(settings.mtls.cert),
+    key: fs.readFileSync(settings.mtls.key),
+    ca: fs.readFileSync(settings.mtls.ca),
+    requestCert: true,
+    rejectUnauthorized: false,
+  };
+}
+
+function digest(value: string): Buffer {
+  return createHash('sha256').update(value).digest();
+}
+
+function findToken(token: string): AuthTokenSettings | null {
+  const presented = digest(token);
+  // Check every token so timing doesn't reveal which one matched
+  let match: AuthTokenSettings | null = null;
+  for (const candidate of getState().settings?.tokens ?? []) {
+    if (timingSafeEqual(presented, digest(candidate.token))) {
+      match = candidate;
+    }
+  }
+  return match;
+}
+
+/**
+ * Identify the client behind an HTTP request from a verified client
+ * certificate or an `Authorization: Bearer` token. Returns null when
+ * authentication isn't configured.
+ */
+export function authenticateHttpRequest(
+  request: IncomingMessage
+): AuthInfo | null {
+  if (!isAuthConfigured()) {
+    return null;
+  }
+
+  const { settings } = getState();
+  const socket = request.socket;
+  if (settings?.mtls && socket instanceof TLSSocket && socket.authorized) {
+    const subject = socket.getPeerCertificate().subject?.CN;
+    const client = settings.mtls.clients.find(
+      (candidate) => candidate.subject === subject
+    );
+    if (client) {
+      return { token: '', clientId: client.subject, scopes: client.scopes };
+    }
+  }
+
+  const header = request.headers.authorization?.trim() ?? '';
+  const bearer = /^Bearer\s+(\S+)$/i.exec(header)?.[1];
+  if (!bearer) {
+    throw new CodedError(
+      'Missing bearer token or client certificate',
+      'UNAUTHORIZED',
+      'auth'
+    );
+  }
+  const token = findToken(bearer);
+  if (!token) {
+    throw new CodedError('Invalid bearer token', 'UNAUTHORIZED', 'auth');
+  }
+  return { token: bearer, clientId: token.name, scopes: token.scopes };
+}
+
+/**
+ * Check an authenticated client may call a tool. Calls without auth info,
+ * such as over stdio, are trusted.
+ */
+export function assertToolScope(
+  tool: string,
+  authInfo: AuthInfo | undefined
+): void {
+  if (!authInfo) {
+    return;
+  }
+  const required: AuthScope = EDIT_TOOLS.has(tool) ? 'edit' : 'read';
+  if (!authInfo.scopes.includes(required)) {
+    throw new CodedError(
+      `Client ${authInfo.clientId} lacks the ${required} scope required by ${tool}`,
+      'FORBIDDEN',
+      'auth'
+    );
+  }
+}
@@ -7,25 +7,76 @@ import type { LspManager } from '../runtime/lsp-manager.js';
 
 import { registerInspectTool } from './inspect.js';
 import { registerReferencesTool } from './references.js';
+import { registerHighlightsTool } from './highlights.js';
 import { registerCallHierarchyTool } from './call-hierarchy.js';
+import { registerCallPathsTool } from './call-paths.js';
+import { registerTypeHierarchyTool } from './type-hierarchy.js';
+import {
+  registerDeclarationTool,
+  registerImplementationTool,
+  registerTypeDefinitionTool,
+} from './locations.js';
+import { registerRelatedTestsTool } from './related-tests.js';
 import { registerCompletionTool } from './completion.js';
+import {
+  registerApplyCodeActionTool,
+  registerCodeActionsTool,
+} from './code-actions.js';
+import {
+  registerCodeLensTool,
+  registerRunCodeLensTool,
+} from './code-lens.js';
+import { registerExecuteCommandTool } from './execute-command.js';
 import { registerRenameTool } from './rename.js';
+import { registerFormatTool } from './format.js';
 import { registerSearchTool } from './search.js';
 import { registerOutlineTool } from './outline.js';
+import { registerSemanticTokensTool } from './semantic-tokens.js';
+import { registerSelectionRangeTool } from './selection-range.js';
 import { registerDiagnosticsTool } from './diagnostics.js';
+import { registerWorkspaceDiagnosticsTool } from './workspace-diagnostics.js';
 import { registerReloadTool } from './reload.js';
+import { registerIndexingStatusTool } from './indexing-status.js';
+import { registerServerLogsTool } from './server-logs.js';
+import { registerDependencySourceTool } from './dependency-source.js';
+import { registerSwitchSourceHeaderTool } from './switch-source-header.js';
+import { withToolMiddlewares } from './middleware.js';
 
 /**
  * Register all MCP tools with the server
  */
 export function registerAllTools(server: McpServer, manager: LspManager) {
-  registerReloadTool(server, manager);
-  registerInspectTool(server, manager);
-  registerReferencesTool(server, manager);
-  registerCallHierarchyTool(server, manager);
-  registerCompletionTool(server, manager);
-  registerRenameTool(server, manager);
-  registerSearchTool(server, manager);
-  registerOutlineTool(server, manager);
-  registerDiagnosticsTool(server, manager);
+  // Calls are traced, audited, rate limited and scope checked, and results
+  // pass through enrichers and hooks from the config file
+  const hookedServer = withToolMiddlewares(server);
+
+  registerReloadTool(hookedServer, manager);
+  registerIndexingStatusTool(hookedServer, manager);
+  registerServerLogsTool(hookedServer, manager);
+  registerInspectTool(hookedServer, manager);
+  registerImplementationTool(hookedServer, manager);
+  registerDeclarationTool(hookedServer, manager);
+  registerTypeDefinitionTool(hookedServer, manager);
+  registerDependencySourceTool(hookedServer, manager);
+  registerSwitchSourceHeaderTool(hookedServer, manager);
+  registerReferencesTool(hookedServer, manager);
+  registerHighlightsTool(hookedServer, manager);
+  registerCallHierarchyTool(hookedServer, manager);
+  registerCallPathsTool(hookedServer, manager);
+  registerTypeHierarchyTool(hookedServer, manager);
+  registerRelatedTestsTool(hookedServer, manager);
+  registerCompletionTool(hookedServer, manager);
+  registerRenameTool(hookedServer, manager);
+  registerFormatTool(hookedServer, manager);
+  registerCodeActionsTool(hookedServer, manager);
+  registerApplyCodeActionTool(hookedServer, manager);
+  registerCodeLensTool(hookedServer, manager);
+  registerRunCodeLensTool(hookedServer, manager);
+  registerExecuteCommandTool(hookedServer, manager);
+  registerSearchTool(hookedServer, manager);
+  registerOutlineTool(hookedServer, manager);
+  registerSemanticTokensTool(hookedServer, manager);
+  registerSelectionRangeTool(hookedServer, manager);
+  registerDiagnosticsTool(hookedServer, manager);
+  registerWorkspaceDiagnosticsTool(hookedServer, manager);
 }
@@ -0,0 +1,146 @@
+/**
+ * Indexing Status Tool - Report work the language servers announced through
+ * progress notifications, and wait for it to finish
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import type { LspSession } from '../runtime/lsp-session.js';
+import { formatProgress } from './staleness.js';
+import { withErrorCodes } from './errors.js';
+import { createToolResult } from './output-schemas.js';
+
+const indexingStatusSchema = {
+  file: z
+    .string()
+    .optional()
+    .describe(
+      'Only report the language server handling this file, starting it if needed. Defaults to every running server.'
+    ),
+  wait: z
+    .boolean()
+    .optional()
+    .default(false)
+    .describe(
+      'Wait until the servers report no work in progress before answering. Defaults to false.'
+    ),
+  timeoutSeconds: z
+    .number()
+    .int()
+    .min(1)
+    .max(600)
+    .optional()
+    .default(120)
+    .describe('Longest time to wait, in seconds. Defaults to 120.'),
+} as const;
+
+const indexingStatusZodSchema = z.object(indexingStatusSchema);
+
+const indexingStatusOutputSchema = {
+  idle: z
+    .boolean()
+    .describe('Whether every reported server has no work in progress.'),
+  waitedSeconds: z.number().optional().describe('Time spent waiting.'),
+  servers: z.array(
+    z.object({
+      name: z.string(),
+      busy: z.boolean(),
+      progress: z.array(
+        z.object({
+          title: z.string(),
+          message: z.string().optional(),
+          percentage: z.number().optional(),
+          seconds: z.number().int().describe('How long it has been running.'),
+        })
+      ),
+    })
+  ),
+} as const;
+
+export function registerIndexingStatusTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'indexing_status',
+    {
+      title: 'Indexing Status',
+      description:
+        'Show whether the language servers are still indexing or loading the workspace, from the progress they report. Results from a busy server may be incomplete; pass `wait` to block until it goes quiet.',
+      inputSchema: indexingStatusSchema,
+      outputSchema: indexingStatusOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = indexingStatusZodSchema.parse(request);
+      const sessions = validatedRequest.file
+        ? [await manager.getSessionForFile(validatedRequest.file)]
+        : manager.getStartedSessions();
+
+      if (sessions.length === 0) {
+        return createToolResult('No language servers are running', {
+          idle: true,
+          servers: [],
+        });
+      }
+
+      const sections: string[] = [];
+      let waitedSeconds: number | undefined;
+      if (validatedRequest.wait) {
+        const startedAt = Date.now();
+        const timeoutMs = validatedRequest.timeoutSeconds * 1000;
+        const idle = await Promise.all(
+          sessions.map((session) =>
+            session.getProgressStore().waitForIdle(timeoutMs)
+          )
+        );
+        waitedSeconds = (Date.now() - startedAt) / 1000;
+        const seconds = waitedSeconds.toFixed(1);
+        sections.push(
+          idle.every(Boolean)
+            ? `Idle after waiting ${seconds}s`
+            : `Still busy after waiting ${seconds}s`
+        );
+      }
+
+      sections.push(...sessions.map(formatSessionProgress));
+
+      const now = Date.now();
+      const servers = sessions.map((session) => {
+        const active = session.getProgressStore().getActive();
+        return {
+          name: session.getProfile().name,
+          busy: active.length > 0,
+          progress: active.map((progress) => ({
+            title: progress.title,
+            ...(progress.message && { message: progress.message }),
+            ...(progress.percentage !== undefined && {
+              percentage: progress.percentage,
+            }),
+            seconds: Math.round((now - progress.startedAt.getTime()) / 1000),
+          })),
+        };
+      });
+      return createToolResult(sections.join('\n\n'), {
+        idle: servers.every((server) => !server.busy),
+        ...(waitedSeconds !== undefined && { waitedSeconds }),
+        servers,
+      });
+    })
+  );
+}
+
+function formatSessionProgress(session: LspSession): string {
+  const name = session.getProfile().name;
+  const active = session.getProgressStore().getActive();
+  if (active.length === 0) {
+    return `${name}: idle`;
+  }
+
+  const now = Date.now();
+  const lines = active.map((progress) => {
+    const seconds = Math.round((now - progress.startedAt.getTime()) / 1000);
+    return `- ${formatProgress(progress)} (${seconds}s)`;
+  });
+  return [`${name}: busy`, ...lines].join('\n');
+}
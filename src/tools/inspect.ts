@@ -3,17 +3,44 @@
  */
 
 import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
 import { createOneBasedPosition } from '../types.js';
 import { prepareSymbolPositionRequest } from '../preparation.js';
 import * as LspOperations from '../lsp/operations/index.js';
-import { symbolPositionSchema } from './schemas.js';
+import { inspectSchema } from './schemas.js';
 import { formatCursorContext } from '../utils/cursor-context.js';
-import { enrichSymbolLocations } from './enrichment.js';
-import { createSignaturePreview } from './enrichment.js';
-import { formatFilePath } from './utils.js';
-import { Hover, Location } from 'vscode-languageserver-protocol';
-import { validateSymbolPosition } from './validation.js';
+import {
+  formatLocationGroup,
+  hasDistinctLocations,
+  toLocationOutputs,
+} from './locations.js';
+import { validateInspect } from './validation.js';
 import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import { withErrorCodes } from './errors.js';
+import { formatHoverContents } from '../utils/hover-markdown.js';
+import {
+  createToolResult,
+  locationOutputSchema,
+  symbolAtCursorOutputField,
+  toSymbolAtCursorOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+const inspectOutputSchema = {
+  symbol: symbolAtCursorOutputField,
+  documentation: z
+    .string()
+    .optional()
+    .describe('Hover documentation in the requested hoverFormat.'),
+  definitions: z.array(locationOutputSchema),
+  declarations: z.array(locationOutputSchema),
+  typeDefinitions: z.array(locationOutputSchema),
+  implementations: z.array(locationOutputSchema),
+  warnings: warningsOutputField,
+} as const;
 
 export function registerInspectTool(server: McpServer, manager: LspManager) {
   server.registerTool(
@@ -21,11 +48,12 @@ export function registerInspectTool(server: McpServer, manager: LspManager) {
     {
       title: 'Inspect',
       description:
-        'Inspect the symbol at a file position and return documentation, signature details, and related code locations such as definitions, implementations, and type declarations.',
-      inputSchema: symbolPositionSchema,
+        'Inspect the symbol at a file position and return documentation, signature details, and related code locations such as definitions, implementations, and type declarations. Set hoverFormat to markdown for the raw server documentation, or to sections for the signature, documentation and examples separately.',
+      inputSchema: inspectSchema,
+      outputSchema: inspectOutputSchema,
     },
-    async (request) => {
-      const validatedRequest = validateSymbolPosition(request);
+    withErrorCodes(async (request) => {
+      const validatedRequest = validateInspect(request);
       const session = await manager.getSessionForFile(validatedRequest.file);
 
       const symbolRequest = {
@@ -40,23 +68,33 @@ export function registerInspectTool(server: McpServer, manager: LspManager) {
         session,
         symbolRequest
       );
-      if (!prepared.ok) throw new Error(prepared.error.message);
+      if (!prepared.ok) throw toCodedError(prepared.error);
 
       const result = await LspOperations.inspectSymbol(session, prepared.data);
-      if (!result.ok) throw new Error(result.error.message);
+      if (!result.ok) throw toCodedError(result.error);
 
       const { result: inspectData, cursorContext } = result.data;
 
       const sections: string[] = [];
+      let documentation: string | undefined;
 
       if (cursorContext) {
         sections.push(formatCursorContext(cursorContext));
       }
 
       if (inspectData.hover && inspectData.hover.contents) {
-        const hoverContent = extractHoverContent(inspectData.hover);
+        const hoverContent = formatHoverContents(
+          inspectData.hover,
+          validatedRequest.hoverFormat
+        );
         if (hoverContent) {
-          sections.push(`Documentation\n${hoverContent}`);
+          documentation = hoverContent;
+          // Sections carry their own headings
+          sections.push(
+            validatedRequest.hoverFormat === 'sections'
+              ? hoverContent
+              : `Documentation\n${hoverContent}`
+          );
         }
       }
 
@@ -72,6 +110,18 @@ export function registerInspectTool(server: McpServer, manager: LspManager) {
         sections.push(definitionText);
       }
 
+      if (
+        Array.isArray(inspectData.declaration) &&
+        hasDistinctLocations(
+          inspectData.declaration,
+          Array.isArray(inspectData.definition) ? inspectData.definition : []
+        )
+      ) {
+        sections.push(
+          await formatLocationGroup(inspectData.declaration, 'Declaration')
+        );
+      }
+
       if (
         inspectData.typeDefinition &&
         Array.isArray(inspectData.typeDefinition) &&
@@ -96,139 +146,39 @@ export function registerInspectTool(server: McpServer, manager: LspManager) {
         sections.push(implText);
       }
 
-      return {
-        content: [
-          {
-            type: 'text' as const,
-            text: sections.join('\n\n'),
-          },
-        ],
-      };
-    }
-  );
-}
-
-/**
- * Extract readable content from LSP hover response
- */
-function extractHoverContent(hover: Hover): string | null {
-  if (!hover || !hover.contents) return null;
-
-  const contents = hover.contents;
-
-  if (typeof contents === 'string') {
-    return contents.trim();
-  }
-
-  if (
-    typeof contents === 'object' &&
-    'kind' in contents &&
-    'value' in contents
-  ) {
-    const markupContent = contents as { kind: string; value: string };
-    return markupContent.value.trim();
-  }
-
-  if (Array.isArray(contents)) {
-    return contents
-      .map((item) => {
-        if (typeof item === 'string') return item;
-        if (typeof item === 'object' && 'value' in item) {
-          const contentItem = item as { language?: string; value: string };
-          if (contentItem.language) {
-            return contentItem.value;
-          }
-          return contentItem.value;
-        }
-        return JSON.stringify(item);
-      })
-      .filter(Boolean)
-      .join('\n\n')
-      .trim();
-  }
-
-  if (typeof contents === 'object' && 'value' in contents) {
-    const contentObject = contents as { language?: string; value: string };
-    return contentObject.value.trim();
-  }
-
-  return null;
-}
-
-/**
- * Format a group of locations (definition, type definition, implementation)
- */
-async function formatLocationGroup(
-  locations: Location[],
-  groupTitle: string
-): Promise<string> {
-  if (!locations || locations.length === 0) return '';
-
-  const symbolLocations: Location[] = locations.map((location) => ({
-    uri: location.uri,
-    range: {
-      start: {
-        line: location.range.start.line - 1,
-        character: location.range.start.character - 1,
-      },
-      end: {
-        line: location.range.end.line - 1,
-        character: location.range.end.character - 1,
-      },
-    },
-  }));
-
-  const enrichmentResults = await enrichSymbolLocations(symbolLocations);
-
-  const fileGroups = new Map<
-    string,
-    Array<{
-      location: Location;
-      originalLocation: Location;
-      codeSnippet: string | null;
-    }>
-  >();
-
-  enrichmentResults.forEach((result, index: number) => {
-    const location = symbolLocations[index];
-    const originalLocation = locations[index];
-    if (!location || !originalLocation) return;
-
-    const filePath = formatFilePath(location.uri);
-
-    if (!fileGroups.has(filePath)) {
-      fileGroups.set(filePath, []);
-    }
-
-    fileGroups.get(filePath)!.push({
-      location,
-      originalLocation,
-      codeSnippet: result.codeSnippet,
-    });
-  });
-
-  let result = `${groupTitle} (${locations.length} location${locations.length === 1 ? '' : 's'})`;
-
-  for (const [filePath, fileLocations] of fileGroups) {
-    result += `\n\n${filePath} (${fileLocations.length})\n`;
-
-    fileLocations.sort(
-      (left, right) =>
-        left.location.range.start.line - right.location.range.start.line
-    );
-
-    for (const entry of fileLocations) {
-      const line = entry.originalLocation.range.start.line;
-      const character = entry.originalLocation.range.start.character;
-      result += `  @${line}:${character}`;
-
-      if (entry.codeSnippet) {
-        result += `\n    \`${createSignaturePreview(entry.codeSnippet.trim(), 100)}\``;
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
       }
 
-      result += '\n';
-    }
-  }
-
-  return result.trim();
+      const definitions = Array.isArray(inspectData.definition)
+        ? inspectData.definition
+        : [];
+      const declarations = Array.isArray(inspectData.declaration)
+        ? inspectData.declaration
+        : [];
+      return createToolResult(sections.join('\n\n'), {
+        symbol: toSymbolAtCursorOutput(cursorContext),
+        ...(documentation && { documentation }),
+        definitions: toLocationOutputs(definitions),
+        declarations: hasDistinctLocations(declarations, definitions)
+          ? toLocationOutputs(declarations)
+          : [],
+        typeDefinitions: toLocationOutputs(
+          Array.isArray(inspectData.typeDefinition)
+            ? inspectData.typeDefinition
+            : []
+        ),
+        implementations: toLocationOutputs(
+          Array.isArray(inspectData.implementation)
+            ? inspectData.implementation
+            : []
+        ),
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
 }
@@ -0,0 +1,301 @@
+/**
+ * Location Tools - Go to implementations, declarations or the type of a
+ * symbol, grouped by file
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import { Location } from 'vscode-languageserver-protocol';
+import { createOneBasedPosition } from '../types.js';
+import { prepareSymbolPositionRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { symbolPositionSchema } from './schemas.js';
+import { formatCursorContext } from '../utils/cursor-context.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import type { LocationRequestMethod } from '../types/lsp.js';
+import { getStaleContentWarning } from './staleness.js';
+import { createSignaturePreview, enrichSymbolLocations } from './enrichment.js';
+import { formatFilePath } from './utils.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  locationOutputSchema,
+  symbolAtCursorOutputField,
+  toSymbolAtCursorOutput,
+  toWarningsOutput,
+  warningsOutputField,
+  type LocationOutput,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+const symbolPositionZodSchema = z.object(symbolPositionSchema);
+
+const locationsOutputSchema = {
+  symbol: symbolAtCursorOutputField,
+  locations: z.array(locationOutputSchema),
+  related: z
+    .array(locationOutputSchema)
+    .optional()
+    .describe(
+      'Locations of the related request, e.g. the definition alongside the declaration, when they differ.'
+    ),
+  warnings: warningsOutputField,
+} as const;
+
+interface LocationToolOptions {
+  name: string;
+  title: string;
+  description: string;
+  method: LocationRequestMethod;
+  // Heading of the result, e.g. 'Implementations'
+  groupTitle: string;
+  emptyMessage: string;
+  // A related request shown too when its locations differ, e.g. the
+  // definition alongside the declaration
+  companion?: { method: LocationRequestMethod; groupTitle: string };
+}
+
+function getLocationKey(location: Location): string {
+  const { line, character } = location.range.start;
+  return `${location.uri}#${line}:${character}`;
+}
+
+/**
+ * Whether any of the locations is missing from the others
+ */
+export function hasDistinctLocations(
+  locations: Location[],
+  others: Location[]
+): boolean {
+  const otherKeys = new Set(others.map(getLocationKey));
+  return locations.some((location) => !otherKeys.has(getLocationKey(location)));
+}
+
+function registerLocationTool(
+  server: McpServer,
+  manager: LspManager,
+  options: LocationToolOptions
+) {
+  server.registerTool(
+    options.name,
+    {
+      title: options.title,
+      description: options.description,
+      inputSchema: symbolPositionSchema,
+      outputSchema: locationsOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = symbolPositionZodSchema.parse(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+
+      const prepared = await prepareSymbolPositionRequest(session, {
+        file: validatedRequest.file,
+        position: createOneBasedPosition(
+          validatedRequest.line,
+          validatedRequest.character
+        ),
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const result = await LspOperations.findLocations(
+        session,
+        prepared.data,
+        options.method
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      const sections: string[] = [];
+      const { result: locations, cursorContext } = result.data;
+
+      if (cursorContext) {
+        sections.push(formatCursorContext(cursorContext));
+      }
+
+      sections.push(
+        locations.length > 0
+          ? await formatLocationGroup(locations, options.groupTitle)
+          : options.emptyMessage
+      );
+
+      let related: Location[] | undefined;
+      if (options.companion) {
+        const companion = await LspOperations.findLocations(
+          session,
+          prepared.data,
+          options.companion.method
+        );
+        // The companion is extra context, so its failures aren't reported
+        if (
+          companion.ok &&
+          hasDistinctLocations(companion.data.result, locations)
+        ) {
+          related = companion.data.result;
+          sections.push(
+            await formatLocationGroup(
+              companion.data.result,
+              options.companion.groupTitle
+            )
+          );
+        }
+      }
+
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      return createToolResult(sections.join('\n\n'), {
+        symbol: toSymbolAtCursorOutput(cursorContext),
+        locations: toLocationOutputs(locations),
+        ...(related && { related: toLocationOutputs(related) }),
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
+}
+
+export function registerImplementationTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  registerLocationTool(server, manager, {
+    name: 'implementation',
+    title: 'Implementation',
+    description:
+      'Find the concrete implementations of the interface, trait, abstract method or virtual member at a file position, grouped by file.',
+    method: 'textDocument/implementation',
+    groupTitle: 'Implementations',
+    emptyMessage: 'No implementations found at this position',
+  });
+}
+
+export function registerDeclarationTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  registerLocationTool(server, manager, {
+    name: 'declaration',
+    title: 'Declaration',
+    description:
+      'Find where the symbol at a file position is declared, such as a C or C++ header prototype or a C# partial member, along with its definition when that is elsewhere.',
+    method: 'textDocument/declaration',
+    groupTitle: 'Declaration',
+    emptyMessage: 'No declaration found at this position',
+    companion: { method: 'textDocument/definition', groupTitle: 'Definition' },
+  });
+}
+
+export function registerTypeDefinitionTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  registerLocationTool(server, manager, {
+    name: 'type_definition',
+    title: 'Type Definition',
+    description:
+      'Find where the type of the variable, parameter or expression at a file position is defined, rather than where the variable itself is.',
+    method: 'textDocument/typeDefinition',
+    groupTitle: 'Type Definition',
+    emptyMessage: 'No type definition found at this position',
+  });
+}
+
+/**
+ * Structured form of 1-based locations, in file and line order
+ */
+export function toLocationOutputs(locations: Location[]): LocationOutput[] {
+  return locations
+    .map((location) => ({
+      file: formatFilePath(location.uri),
+      line: location.range.start.line,
+      character: location.range.start.character,
+    }))
+    .sort(
+      (left, right) =>
+        left.file.localeCompare(right.file) ||
+        left.line - right.line ||
+        left.character - right.character
+    );
+}
+
+/**
+ * Format a group of 1-based locations (definition, type definition,
+ * implementation) grouped by file with a preview of each
+ */
+export async function formatLocationGroup(
+  locations: Location[],
+  groupTitle: string
+): Promise<string> {
+  if (!locations || locations.length === 0) return '';
+
+  const symbolLocations: Location[] = locations.map((location) => ({
+    uri: location.uri,
+    range: {
+      start: {
+        line: location.range.start.line - 1,
+        character: location.range.start.character - 1,
+      },
+      end: {
+        line: location.range.end.line - 1,
+        character: location.range.end.character - 1,
+      },
+    },
+  }));
+
+  const enrichmentResults = await enrichSymbolLocations(symbolLocations);
+
+  const fileGroups = new Map<
+    string,
+    Array<{
+      location: Location;
+      originalLocation: Location;
+      codeSnippet: string | null;
+    }>
+  >();
+
+  enrichmentResults.forEach((result, index: number) => {
+    const location = symbolLocations[index];
+    const originalLocation = locations[index];
+    if (!location || !originalLocation) return;
+
+    const filePath = formatFilePath(location.uri);
+
+    if (!fileGroups.has(filePath)) {
+      fileGroups.set(filePath, []);
+    }
+
+    fileGroups.get(filePath)!.push({
+      location,
+      originalLocation,
+      codeSnippet: result.codeSnippet,
+    });
+  });
+
+  let result = `${groupTitle} (${locations.length} location${locations.length === 1 ? '' : 's'})`;
+
+  for (const [filePath, fileLocations] of fileGroups) {
+    result += `\n\n${filePath} (${fileLocations.length})\n`;
+
+    fileLocations.sort(
+      (left, right) =>
+        left.location.range.start.line - right.location.range.start.line
+    );
+
+    for (const entry of fileLocations) {
+      const line = entry.originalLocation.range.start.line;
+      const character = entry.originalLocation.range.start.character;
+      result += `  @${line}:${character}`;
+
+      if (entry.codeSnippet) {
+        result += `\n    \`${createSignaturePreview(entry.codeSnippet.trim(), 100)}\``;
+      }
+
+      result += '\n';
+    }
+  }
+
+  return result.trim();
+}
@@ -0,0 +1,223 @@
+/**
+ * Tool middleware - the steps every tool call passes through, one per
+ * concern: tracing, auditing, rate limits, auth scopes, session state,
+ * WASM enrichers and hooks. Each wraps the rest of the call, so they
+ * compose in any order and can be used on their own.
+ */
+
+import type { AuthInfo } from '@modelcontextprotocol/sdk/server/auth/types.js';
+import type { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import type { CallToolResult } from '@modelcontextprotocol/sdk/types.js';
+import { applyWasmEnrichers } from '../plugins/wasm-plugins.js';
+import {
+  getSessionStateClient,
+  recordSessionFile,
+} from '../runtime/session-state.js';
+import {
+  parseTraceparent,
+  SPAN_KIND_SERVER,
+  withSpan,
+  type SpanContext,
+} from '../utils/tracing.js';
+import { withAuditEntry } from './audit-log.js';
+import { createToolErrorResult } from './errors.js';
+import { applyToolHooks, keepStructuredContent } from './hooks.js';
+import { assertToolScope } from './http-auth.js';
+import { getClientId, withRateLimit } from './rate-limits.js';
+
+export interface ToolCall {
+  tool: string;
+  // Tools without an input schema receive only the request context
+  arguments: unknown;
+  // Request context the SDK passes as the callback's last argument
+  extra: unknown;
+  client: string;
+}
+
+export type ToolCallNext = () => Promise<CallToolResult>;
+
+export type ToolMiddleware = (
+  call: ToolCall,
+  next: ToolCallNext
+) => Promise<CallToolResult>;
+
+interface ToolRequestExtra {
+  _meta?: Record<string, unknown>;
+  authInfo?: AuthInfo;
+  requestInfo?: { headers?: Record<string, string | string[] | undefined> };
+}
+
+/**
+ * Trace context the client sent with a tool call, from the HTTP
+ * `traceparent` header or the request `_meta`
+ */
+function getClientTraceContext(extra: unknown): SpanContext | null {
+  const { _meta, requestInfo } = (extra ?? {}) as ToolRequestExtra;
+  const header = requestInfo?.headers?.traceparent;
+  return (
+    parseTraceparent(Array.isArray(header) ? header[0] : header) ??
+    parseTraceparent(_meta?.traceparent)
+  );
+}
+
+function getAuthInfo(extra: unknown): AuthInfo | undefined {
+  return (extra as ToolRequestExtra | undefined)?.authInfo;
+}
+
+/**
+ * Run the call in a server span, under the client's trace when it sent one
+ */
+export const tracingMiddleware: ToolMiddleware = async (call, next) =>
+  await withSpan(
+    `tools/call ${call.tool}`,
+    SPAN_KIND_SERVER,
+    { 'rpc.system': 'mcp', 'mcp.tool.name': call.tool },
+    next,
+    getClientTraceContext(call.extra)
+  );
+
+/**
+ * Append an audit entry for the call
+ */
+export const auditMiddleware: ToolMiddleware = async (call, next) =>
+  await withAuditEntry(
+    { client: call.client, tool: call.tool, args: call.arguments },
+    next
+  );
+
+/**
+ * Return errors thrown further in as tool error results, so the audit
+ * entry and span record the outcome the client sees
+ */
+export const errorResultMiddleware: ToolMiddleware = async (_call, next) =>
+  await next().catch((error: unknown) => createToolErrorResult(error));
+
+/**
+ * Run the call within the client's rate limit
+ */
+export const rateLimitMiddleware: ToolMiddleware = async (call, next) =>
+  await withRateLimit(call.client, next);
+
+/**
+ * Refuse the call when the client's auth scopes don't cover the tool
+ */
+export const authScopeMiddleware: ToolMiddleware = async (call, next) => {
+  assertToolScope(call.tool, getAuthInfo(call.extra));
+  return await next();
+};
+
+/**
+ * Remember the file a successful call looked at in the client's session
+ * state, so a later session can resume with it
+ */
+export function createSessionStateMiddleware(
+  server: McpServer
+): ToolMiddleware {
+  return async (call, next) => {
+    const result = await next();
+    const file = (call.arguments as { file?: unknown } | null)?.file;
+    if (typeof file === 'string' && !result.isError) {
+      recordSessionFile(
+        getSessionStateClient(
+          getAuthInfo(call.extra)?.clientId,
+          server.server.getClientVersion()?.name
+        ),
+        file
+      );
+    }
+    return result;
+  };
+}
+
+/**
+ * Pass the result through the WASM plugins' enrichers
+ */
+export const wasmEnrichmentMiddleware: ToolMiddleware = async (call, next) => {
+  const result = await next();
+  const enriched = await applyWasmEnrichers(
+    call.tool,
+    call.arguments,
+    result
+  ).catch((error: unknown) => createToolErrorResult(error));
+  return keepStructuredContent(result, enriched);
+};
+
+/**
+ * Pass the result through the hooks configured for the tool
+ */
+export const toolHooksMiddleware: ToolMiddleware = async (call, next) => {
+  const result = await next();
+  return keepStructuredContent(
+    result,
+    await applyToolHooks(call.tool, call.arguments, result)
+  );
+};
+
+/**
+ * One middleware running `middlewares` outermost first
+ */
+export function composeToolMiddlewares(
+  middlewares: ToolMiddleware[]
+): ToolMiddleware {
+  return async (call, next) =>
+    await middlewares.reduceRight<ToolCallNext>(
+      (inner, middleware) => () => middleware(call, inner),
+      next
+    )();
+}
+
+/**
+ * The middlewares of the MCP server's tools, outermost first. Results come
+ * back in reverse order, so session state sees the tool's own result and
+ * hooks see the text enrichers produced.
+ */
+export function getDefaultToolMiddlewares(
+  server: McpServer
+): ToolMiddleware[] {
+  return [
+    tracingMiddleware,
+    auditMiddleware,
+    errorResultMiddleware,
+    rateLimitMiddleware,
+    authScopeMiddleware,
+    toolHooksMiddleware,
+    wasmEnrichmentMiddleware,
+    createSessionStateMiddleware(server),
+  ];
+}
+
+/**
+ * Server whose registered tools run inside `middlewares`
+ */
+export function withToolMiddlewares(
+  server: McpServer,
+  middlewares: ToolMiddleware[] = getDefaultToolMiddlewares(server)
+): McpServer {
+  const middleware = composeToolMiddlewares(middlewares);
+
+  const registerTool: McpServer['registerTool'] = (name, config, callback) => {
+    const handler = callback as (
+      ...args: unknown[]
+    ) => Promise<CallToolResult>;
+    const wrapped = async (...args: unknown[]) => {
+      const extra = args.at(-1);
+      return await middleware(
+        {
+          tool: name,
+          arguments: config.inputSchema ? args[0] : {},
+          extra,
+          client: getClientId(extra),
+        },
+        async () => await handler(...args)
+      );
+    };
+    return server.registerTool(name, config, wrapped as typeof callback);
+  };
+
+  return new Proxy(server, {
+    get: (target, property, receiver) =>
+      property === 'registerTool'
+        ? registerTool
+        : (Reflect.get(target, property, receiver) as unknown),
+  });
+}
@@ -2,19 +2,83 @@
  * Outline Tool - Get hierarchical symbol outline of a code file
  */
 
+import * as fs from 'fs';
 import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
 import { prepareFileRequest } from '../preparation.js';
 import * as LspOperations from '../lsp/operations/index.js';
 import { fileSchema } from './schemas.js';
 import { getSymbolKindName, formatFilePath } from './utils.js';
 import { enrichSymbolsWithCode, createSignaturePreview } from './enrichment.js';
-import { FlattenedSymbol } from '../types/lsp.js';
+import { FlattenedSymbol, FoldingRange, InlayHint } from '../types/lsp.js';
 import { validateFile } from './validation.js';
 import {
   DEFAULT_CONTAINER_KINDS,
   isContainerKind,
 } from '../config/symbol-kinds.js';
 import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import { splitLines } from '../utils/text.js';
+import { buildSkeleton } from '../utils/skeleton.js';
+import {
+  formatSymbolModifiers,
+  getDeclarationPrefix,
+  inferSymbolModifiers,
+  matchesModifierFilter,
+  SYMBOL_MODIFIERS,
+  SYMBOL_VISIBILITIES,
+  type SymbolModifier,
+  type SymbolModifiers,
+  type SymbolVisibility,
+} from '../utils/symbol-modifiers.js';
+import {
+  isTestFile,
+  isTestSymbol,
+  matchesTestFilter,
+  type TestFilter,
+} from '../utils/test-classification.js';
+import { formatOwnerTag } from '../utils/code-owners.js';
+import { withErrorCodes } from './errors.js';
+import {
+  extractSymbolsWithPlugin,
+  findSymbolPlugin,
+} from '../plugins/wasm-plugins.js';
+import { toAbsoluteFilePath } from '../utils/path-format.js';
+import {
+  createToolResult,
+  fileOutputField,
+  symbolOutputSchema,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+const outlineSymbolOutputSchema = symbolOutputSchema.extend({
+  endLine: z.number().int().describe('1-based line the symbol ends on.'),
+  depth: z.number().int().describe('Nesting level, 0 for top-level symbols.'),
+  visibility: z.enum(SYMBOL_VISIBILITIES).optional(),
+  modifiers: z.array(z.enum(SYMBOL_MODIFIERS)),
+  test: z.boolean(),
+  preview: z
+    .string()
+    .optional()
+    .describe('Declaration preview, with preview or inlayHints.'),
+});
+
+type OutlineSymbolOutput = z.infer<typeof outlineSymbolOutputSchema>;
+
+const outlineOutputSchema = {
+  file: fileOutputField,
+  test: z.boolean().describe('Whether the whole file is a test file.'),
+  symbols: z
+    .array(outlineSymbolOutputSchema)
+    .describe('Symbols in file order, empty with skeleton.'),
+  skeleton: z
+    .string()
+    .optional()
+    .describe('The collapsed source, with skeleton.'),
+  warnings: warningsOutputField,
+} as const;
 
 export function registerOutlineTool(server: McpServer, manager: LspManager) {
   server.registerTool(
@@ -22,42 +86,145 @@ export function registerOutlineTool(server: McpServer, manager: LspManager) {
     {
       title: 'Outline',
       description:
-        'Return a hierarchical outline of symbols in a file, including names, kinds, and locations. Use `preview: true` to include short declaration snippets.',
+        'Return a hierarchical outline of symbols in a file, including names, kinds, locations, visibility, and modifiers. Use `preview: true` to include short declaration snippets, `inlayHints: true` to add inferred types and parameter names to them, and `visibility` or `modifiers` to filter, for example public async functions only. Use `skeleton: true` for the source with function bodies elided instead.',
       inputSchema: fileSchema,
+      outputSchema: outlineOutputSchema,
     },
-    async (request) => {
+    withErrorCodes(async (request) => {
       const validatedRequest = validateFile(request);
+      const modifierFilter = {
+        ...(validatedRequest.visibility && {
+          visibility: validatedRequest.visibility,
+        }),
+        ...(validatedRequest.modifiers && {
+          modifiers: validatedRequest.modifiers,
+        }),
+      };
+
+      // Files handled by a WASM plugin never reach a language server
+      const plugin = findSymbolPlugin(validatedRequest.file);
+      if (plugin) {
+        const filePath = toAbsoluteFilePath(validatedRequest.file);
+        const symbols = await extractSymbolsWithPlugin(plugin, filePath);
+        if (validatedRequest.skeleton) {
+          const text = await formatSkeleton(
+            filePath,
+            symbols,
+            [],
+            DEFAULT_CONTAINER_KINDS
+          );
+          return createToolResult(
+            text,
+            toSkeletonOutput(filePath, text, null)
+          );
+        }
+        const { text, output } = await formatOutlineResults(
+          { symbols },
+          filePath,
+          Boolean(validatedRequest.preview || validatedRequest.inlayHints),
+          DEFAULT_CONTAINER_KINDS,
+          modifierFilter,
+          validatedRequest.tests
+        );
+        return createToolResult(text, { ...output, warnings: [] });
+      }
+
       const session = await manager.getSessionForFile(validatedRequest.file);
       const prepared = prepareFileRequest(session, {
         file: validatedRequest.file,
       });
-      if (!prepared.ok) throw new Error(prepared.error.message);
+      if (!prepared.ok) throw toCodedError(prepared.error);
 
       const result = await LspOperations.outlineSymbols(session, prepared.data);
-      if (!result.ok) throw new Error(result.error.message);
+      if (!result.ok) throw toCodedError(result.error);
 
       const containerKinds =
         session.getProfile().config.symbols?.containerKinds ||
         DEFAULT_CONTAINER_KINDS;
 
-      const formattedText = await formatOutlineResults(
+      if (validatedRequest.skeleton) {
+        // Without folding ranges bodies are taken from the symbol ranges
+        const folds = await LspOperations.foldingRanges(session, prepared.data);
+        const text = await formatSkeleton(
+          prepared.data.filePath,
+          result.data,
+          folds.ok ? folds.data : [],
+          containerKinds
+        );
+        const staleWarning = await getStaleContentWarning(
+          session,
+          prepared.data.filePath
+        );
+        return createToolResult(
+          staleWarning ? `${staleWarning}\n\n${text}` : text,
+          toSkeletonOutput(prepared.data.filePath, text, staleWarning)
+        );
+      }
+
+      // Hints are a nice to have, so the outline doesn't fail without them
+      const inlayHints = new Map<string, InlayHint[]>();
+      if (validatedRequest.inlayHints) {
+        const hints = await LspOperations.inlayHints(session, prepared.data);
+        if (hints.ok) {
+          inlayHints.set(prepared.data.filePath, hints.data);
+        }
+      }
+
+      const { text, output } = await formatOutlineResults(
         { symbols: result.data },
-        validatedRequest.file,
-        Boolean(validatedRequest.preview),
-        containerKinds
+        prepared.data.filePath,
+        Boolean(validatedRequest.preview || validatedRequest.inlayHints),
+        containerKinds,
+        modifierFilter,
+        validatedRequest.tests,
+        inlayHints
       );
-      return {
-        content: [
-          {
-            type: 'text' as const,
-            text: formattedText,
-          },
-        ],
-      };
-    }
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      return createToolResult(
+        staleWarning ? `${staleWarning}\n\n${text}` : text,
+        { ...output, warnings: toWarningsOutput(staleWarning) }
+      );
+    })
   );
 }
 
+function toSkeletonOutput(
+  filePath: string,
+  skeleton: string,
+  staleWarning: string | null
+) {
+  return {
+    file: formatFilePath(filePath),
+    test: isTestFile(filePath),
+    symbols: [],
+    skeleton,
+    warnings: toWarningsOutput(staleWarning),
+  };
+}
+
+async function formatSkeleton(
+  filePath: string,
+  symbols: FlattenedSymbol[],
+  foldingRanges: FoldingRange[],
+  containerKinds: number[]
+): Promise<string> {
+  const sourceLines = splitLines(await fs.promises.readFile(filePath, 'utf-8'));
+  // No numbered empty line after the final newline
+  if (sourceLines[sourceLines.length - 1] === '') {
+    sourceLines.pop();
+  }
+  const lines = buildSkeleton(
+    sourceLines,
+    symbols,
+    foldingRanges,
+    containerKinds
+  );
+  return [`Skeleton of ${formatFilePath(filePath)}`, ...lines].join('\n');
+}
+
 interface EnrichedSymbol {
   symbol: FlattenedSymbol;
   codeSnippet?: string;
@@ -123,6 +290,45 @@ function filterSymbolsByContainerLeaf(
   });
 }
 
+interface SymbolMetadata {
+  modifiers: SymbolModifiers;
+  isTest: boolean;
+}
+
+/**
+ * Infer visibility, modifiers and test classification for every symbol
+ * from the file content
+ */
+async function getSymbolMetadata(
+  symbols: FlattenedSymbol[],
+  filePath: string
+): Promise<Map<FlattenedSymbol, SymbolMetadata>> {
+  let lines: string[] = [];
+  try {
+    lines = splitLines(await fs.promises.readFile(filePath, 'utf-8'));
+  } catch {
+    // Fall back to naming conventions only when the file can't be read
+  }
+
+  return new Map(
+    symbols.map((symbol) => {
+      const prefix = getDeclarationPrefix(lines, symbol);
+      return [
+        symbol,
+        {
+          modifiers: inferSymbolModifiers(
+            prefix,
+            symbol.name,
+            filePath,
+            symbol.deprecated
+          ),
+          isTest: isTestSymbol(filePath, symbol.containerPath, prefix),
+        },
+      ];
+    })
+  );
+}
+
 const MAX_CONTAINER_DEPTH = 10;
 
 function calculateDisplayDepth(
@@ -147,14 +353,49 @@ async function formatOutlineResults(
   data: { symbols: FlattenedSymbol[] },
   filePath: string,
   preview = false,
-  containerKinds: number[] = DEFAULT_CONTAINER_KINDS
-): Promise<string> {
+  containerKinds: number[] = DEFAULT_CONTAINER_KINDS,
+  modifierFilter: {
+    visibility?: SymbolVisibility[];
+    modifiers?: SymbolModifier[];
+  } = {},
+  tests: TestFilter = 'include',
+  inlayHints: ReadonlyMap<string, InlayHint[]> = new Map()
+): Promise<{
+  text: string;
+  output: { file: string; test: boolean; symbols: OutlineSymbolOutput[] };
+}> {
+  const testFile = isTestFile(filePath);
+  const empty = (text: string) => ({
+    text,
+    output: { file: formatFilePath(filePath), test: testFile, symbols: [] },
+  });
+
   if (!data.symbols || data.symbols.length === 0) {
-    return `No symbols found in ${formatFilePath(filePath)}`;
+    return empty(`No symbols found in ${formatFilePath(filePath)}`);
   }
 
   const symbols = data.symbols;
-  const filteredSymbols = filterSymbolsByContainerLeaf(symbols, containerKinds);
+  const symbolMetadata = await getSymbolMetadata(symbols, filePath);
+  const filteredSymbols = filterSymbolsByContainerLeaf(
+    symbols,
+    containerKinds
+  ).filter((symbol) => {
+    const metadata = symbolMetadata.get(symbol)!;
+    return (
+      matchesModifierFilter(metadata.modifiers, modifierFilter) &&
+      matchesTestFilter(metadata.isTest, tests)
+    );
+  });
+
+  const hasFilter =
+    Boolean(modifierFilter.visibility?.length) ||
+    Boolean(modifierFilter.modifiers?.length) ||
+    tests !== 'include';
+  if (filteredSymbols.length === 0 && hasFilter) {
+    return empty(
+      `No symbols matching the visibility, modifier, or test filter found in ${formatFilePath(filePath)}`
+    );
+  }
 
   const symbolsByName = new Map<string, FlattenedSymbol>();
   for (const symbol of filteredSymbols) {
@@ -166,6 +407,7 @@ async function formatOutlineResults(
   if (preview) {
     const enrichmentResults = await enrichSymbolsWithCode(filteredSymbols, {
       extractFullDeclaration: true,
+      inlayHints,
     });
 
     enrichedSymbols = filteredSymbols.map((symbol, index) => {
@@ -203,7 +445,7 @@ async function formatOutlineResults(
   const sections = [];
 
   sections.push(
-    `Found ${filteredSymbols.length} symbols in file: ${formatFilePath(filePath)}\nSymbol breakdown: ${typeBreakdown}`
+    `Found ${filteredSymbols.length} symbols in ${testFile ? 'test ' : ''}file: ${formatFilePath(filePath)}${formatOwnerTag(filePath)}\nSymbol breakdown: ${typeBreakdown}`
   );
 
   const rootContainers = new Map<string, EnrichedSymbol[]>();
@@ -268,7 +510,17 @@ async function formatOutlineResults(
       const line = enriched.symbol.range.start.line + 1;
       const character = enriched.symbol.range.start.character + 1;
       const kind = getSymbolKindName(enriched.symbol.kind);
+      const metadata = symbolMetadata.get(enriched.symbol)!;
+      const modifierTags = formatSymbolModifiers(metadata.modifiers);
+      // Whole test files are marked once in the header instead
+      const tags =
+        metadata.isTest && !testFile
+          ? `${modifierTags} [test]`.trim()
+          : modifierTags;
       let formatted = `${indent}@${line}:${character} ${kind} ${enriched.symbol.name}`;
+      if (tags) {
+        formatted += ` ${tags}`;
+      }
 
       if (enriched.signaturePreview) {
         formatted += `\n${indent}  \`${enriched.signaturePreview}\``;
@@ -282,5 +534,41 @@ async function formatOutlineResults(
     sections.push(lines.join('\n'));
   }
 
-  return sections.join('\n\n');
+  const symbolOutputs = [...enrichedSymbols]
+    .sort(
+      (left, right) =>
+        left.symbol.range.start.line - right.symbol.range.start.line ||
+        left.symbol.range.start.character - right.symbol.range.start.character
+    )
+    .map((enriched): OutlineSymbolOutput => {
+      const { symbol } = enriched;
+      const metadata = symbolMetadata.get(symbol)!;
+      return {
+        file: formatFilePath(filePath),
+        line: symbol.range.start.line + 1,
+        character: symbol.range.start.character + 1,
+        endLine: symbol.range.end.line + 1,
+        name: symbol.name,
+        kind: getSymbolKindName(symbol.kind),
+        ...(symbol.containerName && { container: symbol.containerName }),
+        depth: calculateDisplayDepth(symbol, symbolsByName),
+        ...(metadata.modifiers.visibility && {
+          visibility: metadata.modifiers.visibility,
+        }),
+        modifiers: metadata.modifiers.modifiers,
+        test: metadata.isTest,
+        ...(enriched.signaturePreview && {
+          preview: enriched.signaturePreview,
+        }),
+      };
+    });
+
+  return {
+    text: sections.join('\n\n'),
+    output: {
+      file: formatFilePath(filePath),
+      test: testFile,
+      symbols: symbolOutputs,
+    },
+  };
 }
@@ -0,0 +1,212 @@
+/**
+ * MCP Tool Output Schemas - structured content returned alongside the text
+ * rendering, so clients can read results without parsing the text
+ */
+
+import { z } from 'zod';
+import type { CallToolResult } from '@modelcontextprotocol/sdk/types.js';
+import type { CursorContext } from '../utils/cursor-context.js';
+import { createUnifiedDiff } from '../utils/unified-diff.js';
+import {
+  formatFilePath,
+  getSymbolKindName,
+  type ChangeResult,
+} from './utils.js';
+import type { Page } from './truncation.js';
+
+const filePathDescription =
+  'File path in the configured path style: workspace-relative by default, absolute or a file:// URI.';
+
+export const fileOutputField = z.string().describe(filePathDescription);
+
+export const positionOutputShape = {
+  line: z.number().int().describe('1-based line number.'),
+  character: z.number().int().describe('1-based character number.'),
+} as const;
+
+export const locationOutputSchema = z.object({
+  file: fileOutputField,
+  ...positionOutputShape,
+});
+
+export type LocationOutput = z.infer<typeof locationOutputSchema>;
+
+export const rangeOutputSchema = z.object({
+  start: z.object(positionOutputShape),
+  end: z.object(positionOutputShape),
+});
+
+export const symbolOutputSchema = locationOutputSchema.extend({
+  name: z.string(),
+  kind: z.string().describe('Symbol kind, e.g. Class or Method.'),
+  container: z.string().optional().describe('Name of the enclosing symbol.'),
+});
+
+export const hierarchyItemOutputSchema = symbolOutputSchema
+  .omit({ container: true })
+  .extend({ detail: z.string().optional() });
+
+export const fileChangeOutputSchema = z.object({
+  file: fileOutputField,
+  applied: z.number().int().describe('Edits applied to the file.'),
+  failed: z.array(z.object({ ...positionOutputShape, error: z.string() })),
+  error: z
+    .string()
+    .optional()
+    .describe('Why the file could not be edited at all.'),
+  diff: z
+    .string()
+    .optional()
+    .describe('Unified diff of the file, absent when it is unchanged.'),
+});
+
+type FileChangeOutput = z.infer<typeof fileChangeOutputSchema>;
+
+export const warningsOutputField = z
+  .array(z.string())
+  .describe(
+    'Warnings about the result, such as a file that changed on disk or a language server still indexing.'
+  );
+
+export const symbolAtCursorOutputField = z
+  .object({
+    name: z.string().optional(),
+    kind: z.string().optional(),
+    qualifiedName: z.string().optional(),
+  })
+  .optional()
+  .describe('The symbol the request position resolved to.');
+
+export const pageOutputShape = {
+  total: z.number().int().describe('Number of results before paging.'),
+  offset: z.number().int().describe('Offset of the first returned result.'),
+  nextOffset: z
+    .number()
+    .int()
+    .optional()
+    .describe('Offset to pass back to continue, absent on the last page.'),
+} as const;
+
+/**
+ * Location with a 1-based position, from a path or URI and 0-based LSP
+ * coordinates
+ */
+export function toLocationOutput(
+  pathOrUri: string,
+  position: { line: number; character: number }
+): LocationOutput {
+  return {
+    file: formatFilePath(pathOrUri),
+    line: position.line + 1,
+    character: position.character + 1,
+  };
+}
+
+interface HierarchyItem {
+  name: string;
+  kind: number;
+  uri: string;
+  detail?: string;
+  range: { start: { line: number; character: number } };
+  selectionRange?: { start: { line: number; character: number } };
+}
+
+/**
+ * Call or type hierarchy item, at the start of its name
+ */
+export function toHierarchyItemOutput(
+  item: HierarchyItem
+): z.infer<typeof hierarchyItemOutputSchema> {
+  // Some servers omit selectionRange despite the LSP spec requiring it.
+  const position = (item.selectionRange ?? item.range).start;
+  return {
+    ...toLocationOutput(item.uri, position),
+    name: item.name,
+    kind: getSymbolKindName(item.kind),
+    ...(item.detail && { detail: item.detail }),
+  };
+}
+
+export function toRangeOutput(range: {
+  start: { line: number; character: number };
+  end: { line: number; character: number };
+}): z.infer<typeof rangeOutputSchema> {
+  return {
+    start: { line: range.start.line + 1, character: range.start.character + 1 },
+    end: { line: range.end.line + 1, character: range.end.character + 1 },
+  };
+}
+
+/**
+ * Per-file edits, failures and diff of changes written (or, for dry runs,
+ * not written) to disk
+ */
+export function toFileChangeOutputs(
+  changes: ChangeResult[]
+): FileChangeOutput[] {
+  return changes.map((change) => {
+    const file = formatFilePath(change.fileUri);
+    const diff = createUnifiedDiff(
+      file,
+      change.originalContent ?? '',
+      change.updatedContent ?? change.originalContent ?? ''
+    );
+    return {
+      file,
+      applied: change.appliedChanges.length,
+      failed: change.failedChanges.map(({ line, character, error }) => ({
+        line,
+        character,
+        error,
+      })),
+      ...(change.error && { error: change.error }),
+      ...(diff && { diff }),
+    };
+  });
+}
+
+/**
+ * Warning lines from getStaleContentWarning
+ */
+export function toWarningsOutput(warning: string | null): string[] {
+  return warning ? warning.split('\n') : [];
+}
+
+export function toSymbolAtCursorOutput(
+  cursorContext: CursorContext | undefined
+): z.infer<typeof symbolAtCursorOutputField> {
+  if (!cursorContext?.symbolName) {
+    return undefined;
+  }
+  return {
+    name: cursorContext.symbolName,
+    ...(cursorContext.symbolKind && { kind: cursorContext.symbolKind }),
+    ...(cursorContext.qualifiedName && {
+      qualifiedName: cursorContext.qualifiedName,
+    }),
+  };
+}
+
+export function toPageOutput<T>(
+  page: Page<T>
+): Pick<Page<T>, 'total' | 'offset' | 'nextOffset'> {
+  return {
+    total: page.total,
+    offset: page.offset,
+    ...(page.nextOffset !== undefined && { nextOffset: page.nextOffset }),
+  };
+}
+
+/**
+ * Tool result carrying both the text rendering and the structured content
+ * described by the tool's output schema
+ */
+export function createToolResult(
+  text: string,
+  structuredContent: Record<string, unknown>
+): CallToolResult {
+  return {
+    content: [{ type: 'text' as const, text }],
+    structuredContent,
+  };
+}
@@ -0,0 +1,217 @@
+/**
+ * Rate limits - per-client request budgets and fair scheduling of tool
+ * calls, so one busy client can't starve the others or overload the
+ * language servers
+ */
+
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+import { CodedError } from '../utils/error-codes.js';
+import logger from '../utils/logger.js';
+
+// Tool calls without a session or authenticated client, e.g. over stdio
+export const LOCAL_CLIENT_ID = 'local';
+
+export interface RateLimitSettings {
+  // Sustained tool calls per minute for each client; null for unlimited
+  requests_per_minute: number | null;
+  // Calls a client may make at once before the per-minute rate applies
+  burst: number;
+  // Tool calls running at once across all clients; null for unlimited
+  max_concurrent: number | null;
+  // Calls a client may have waiting for a slot before more are rejected
+  max_queued_per_client: number;
+}
+
+interface TokenBucket {
+  tokens: number;
+  updatedAt: number;
+}
+
+// How often buckets that refilled are dropped; a full bucket is the same
+// as none, so forgetting it changes nothing
+const SWEEP_INTERVAL_MS = 60_000;
+
+const UNLIMITED: RateLimitSettings = {
+  requests_per_minute: null,
+  burst: 1,
+  max_concurrent: null,
+  max_queued_per_client: 0,
+};
+
+interface RateLimitState {
+  settings: RateLimitSettings;
+  buckets: Map<string, TokenBucket>;
+  // Waiting calls per client; the map order is the round-robin order
+  queues: Map<string, (() => void)[]>;
+  running: number;
+  sweptAt: number;
+}
+
+const getState = defineWorkspaceState<RateLimitState>(() => ({
+  settings: UNLIMITED,
+  buckets: new Map(),
+  queues: new Map(),
+  running: 0,
+  sweptAt: 0,
+}));
+
+/**
+ * Replace the rate limits, e.g. after the config file is reloaded
+ */
+export function configureRateLimits(update: RateLimitSettings | null): void {
+  const state = getState();
+  state.settings = update ?? UNLIMITED;
+  state.buckets.clear();
+}
+
+interface ToolRequestExtra {
+  sessionId?: string;
+  authInfo?: { clientId?: string };
+}
+
+/**
+ * Identity rate limits apply to: the authenticated client, then the
+ * transport session
+ */
+export function getClientId(extra: unknown): string {
+  const { sessionId, authInfo } = (extra ?? {}) as ToolRequestExtra;
+  return authInfo?.clientId || sessionId || LOCAL_CLIENT_ID;
+}
+
+/**
+ * Drop the budget of a client that won't call again, e.g. a transport
+ * session that closed
+ */
+export function forgetRateLimitClient(clientId: string): void {
+  getState().buckets.delete(clientId);
+}
+
+function createRateLimitError(message: string): CodedError {
+  return new CodedError(message, 'RATE_LIMITED', 'rate_limit');
+}
+
+// Tokens in a bucket once refilled up to `now`
+function refill(
+  bucket: TokenBucket,
+  burst: number,
+  perMinute: number,
+  now: number
+): number {
+  return Math.min(
+    burst,
+    bucket.tokens + ((now - bucket.updatedAt) * perMinute) / 60_000
+  );
+}
+
+function sweepBuckets(
+  state: RateLimitState,
+  perMinute: number,
+  now: number
+): void {
+  if (now - state.sweptAt < SWEEP_INTERVAL_MS) {
+    return;
+  }
+  state.sweptAt = now;
+  const { burst } = state.settings;
+  for (const [clientId, bucket] of state.buckets) {
+    if (refill(bucket, burst, perMinute, now) >= burst) {
+      state.buckets.delete(clientId);
+    }
+  }
+}
+
+function takeToken(clientId: string, now: number): boolean {
+  const state = getState();
+  const { settings, buckets } = state;
+  const perMinute = settings.requests_per_minute;
+  if (perMinute === null) {
+    return true;
+  }
+  sweepBuckets(state, perMinute, now);
+
+  const bucket = buckets.get(clientId) ?? {
+    tokens: settings.burst,
+    updatedAt: now,
+  };
+  bucket.tokens = refill(bucket, settings.burst, perMinute, now);
+  bucket.updatedAt = now;
+  buckets.set(clientId, bucket);
+
+  if (bucket.tokens < 1) {
+    return false;
+  }
+  bucket.tokens -= 1;
+  return true;
+}
+
+/**
+ * Hand a free slot to the next waiting client in round-robin order
+ */
+function dispatchNext(): void {
+  const state = getState();
+  const { queues } = state;
+  for (const [clientId, waiting] of queues) {
+    const next = waiting.shift();
+    queues.delete(clientId);
+    if (!next) {
+      continue;
+    }
+    if (waiting.length > 0) {
+      // Served clients go to the back of the rotation
+      queues.set(clientId, waiting);
+    }
+    state.running++;
+    next();
+    return;
+  }
+}
+
+async function acquireSlot(clientId: string): Promise<void> {
+  const state = getState();
+  const { settings, queues } = state;
+  const maxConcurrent = settings.max_concurrent;
+  if (maxConcurrent === null || state.running < maxConcurrent) {
+    state.running++;
+    return;
+  }
+
+  const waiting = queues.get(clientId) ?? [];
+  if (waiting.length >= settings.max_queued_per_client) {
+    throw createRateLimitError(
+      `Too many queued requests for client ${clientId}; retry once earlier calls finish`
+    );
+  }
+  await new Promise<void>((resolve) => {
+    waiting.push(resolve);
+    queues.set(clientId, waiting);
+  });
+}
+
+function releaseSlot(): void {
+  const state = getState();
+  state.running = Math.max(0, state.running - 1);
+  dispatchNext();
+}
+
+/**
+ * Run a tool call within the client's rate limit, waiting for a slot when
+ * too many calls are already running
+ */
+export async function withRateLimit<T>(
+  clientId: string,
+  fn: () => Promise<T>
+): Promise<T> {
+  if (!takeToken(clientId, Date.now())) {
+    logger.warn('Tool call rate limited', { clientId });
+    throw createRateLimitError(
+      `Rate limit exceeded for client ${clientId}: ${getState().settings.requests_per_minute} requests per minute`
+    );
+  }
+
+  await acquireSlot(clientId);
+  try {
+    return await fn();
+  } finally {
+    releaseSlot();
+  }
+}
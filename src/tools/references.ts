@@ -2,17 +2,92 @@
  * Find References Tool - Find all references of a symbol
  */
 
+import * as path from 'path';
 import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
 import { createOneBasedPosition } from '../types.js';
 import { prepareSymbolPositionRequest } from '../preparation.js';
 import * as LspOperations from '../lsp/operations/index.js';
-import { symbolPositionSchema } from './schemas.js';
+import {
+  REFERENCE_ACCESS,
+  REFERENCE_MODES,
+  referencesSchema,
+} from './schemas.js';
 import { formatCursorContext } from '../utils/cursor-context.js';
 import { enrichSymbolsWithCode, createSignaturePreview } from './enrichment.js';
-import { Location } from '../types/lsp.js';
-import { validateSymbolPosition } from './validation.js';
+import { Location, SymbolReference } from '../types/lsp.js';
+import { validateReferences } from './validation.js';
 import { formatFilePath } from './utils.js';
+import { formatPageMarker, formatPageRange, paginate } from './truncation.js';
 import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import {
+  isTestFile,
+  matchesTestFilter,
+  type TestFilter,
+} from '../utils/test-classification.js';
+import { formatOwnerTag } from '../utils/code-owners.js';
+import { toWorkspacePath } from '../map/workspace-scan.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  fileOutputField,
+  pageOutputShape,
+  positionOutputShape,
+  symbolAtCursorOutputField,
+  toPageOutput,
+  toSymbolAtCursorOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+// Enriching each reference reads its file, so cap a single response
+const MAX_REFERENCES = 100;
+// Compact output has no snippets to read, so it can show more per page
+const MAX_COMPACT_REFERENCES = 1000;
+// Filtering by access opens each file to ask for its highlights
+const MAX_CLASSIFIED_FILES = 50;
+
+type ReferenceMode = (typeof REFERENCE_MODES)[number];
+type ReferenceAccessFilter = (typeof REFERENCE_ACCESS)[number];
+
+const referenceFileOutputSchema = z.object({
+  file: fileOutputField,
+  total: z.number().int().describe('References in the file across pages.'),
+  test: z.boolean(),
+  references: z
+    .array(
+      z.object({
+        ...positionOutputShape,
+        access: z.enum(['read', 'write', 'text']).optional(),
+      })
+    )
+    .describe('References on this page, empty in summary mode.'),
+});
+
+type ReferenceFileOutput = z.infer<typeof referenceFileOutputSchema>;
+
+const referencesOutputSchema = {
+  symbol: symbolAtCursorOutputField,
+  ...pageOutputShape,
+  files: z.array(referenceFileOutputSchema),
+  filteredOut: z
+    .number()
+    .int()
+    .describe(
+      'References left out by the directory, tests and access filters.'
+    ),
+  warnings: warningsOutputField,
+} as const;
+
+interface ReferencesOutput {
+  total: number;
+  offset: number;
+  nextOffset?: number;
+  files: ReferenceFileOutput[];
+  filteredOut: number;
+}
 
 export function registerReferencesTool(server: McpServer, manager: LspManager) {
   server.registerTool(
@@ -20,11 +95,12 @@ export function registerReferencesTool(server: McpServer, manager: LspManager) {
     {
       title: 'References',
       description:
-        'Find semantic references to the symbol at a file position across the workspace.',
-      inputSchema: symbolPositionSchema,
+        'Find semantic references to the symbol at a file position across the workspace, grouped by file. Use mode compact or summary for large result sets; detailed results are paged, pass the offset from the truncation marker to continue. Pass access read or write to keep only the references that read or assign the symbol, and directory or tests to narrow large result sets.',
+      inputSchema: referencesSchema,
+      outputSchema: referencesOutputSchema,
     },
-    async (request) => {
-      const validatedRequest = validateSymbolPosition(request);
+    withErrorCodes(async (request) => {
+      const validatedRequest = validateReferences(request);
       const session = await manager.getSessionForFile(validatedRequest.file);
 
       const symbolRequest = {
@@ -39,16 +115,49 @@ export function registerReferencesTool(server: McpServer, manager: LspManager) {
         session,
         symbolRequest
       );
-      if (!prepared.ok) throw new Error(prepared.error.message);
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const result = await LspOperations.findReferences(session, prepared.data, {
+        includeDeclaration: validatedRequest.includeDeclaration,
+      });
+      if (!result.ok) throw toCodedError(result.error);
+
+      const { cursorContext } = result.data;
+      let references = result.data.result;
 
-      const result = await LspOperations.findReferences(session, prepared.data);
-      if (!result.ok) throw new Error(result.error.message);
+      // Narrow to the directory first, so fewer files are classified
+      const directory = validatedRequest.directory
+        ? path.resolve(
+            manager.getStatus().workspacePath,
+            validatedRequest.directory
+          )
+        : null;
+      let outsideDirectory = 0;
+      if (directory) {
+        const inside = references.filter(
+          (ref) => toWorkspacePath(directory, ref.uri) !== null
+        );
+        outsideDirectory = references.length - inside.length;
+        references = inside;
+      }
+      if (validatedRequest.access !== 'all') {
+        const classified = await LspOperations.classifyReferenceAccess(
+          session,
+          references,
+          MAX_CLASSIFIED_FILES
+        );
+        if (!classified.ok) throw toCodedError(classified.error);
+        references = classified.data;
+      }
 
-      const { result: references, cursorContext } = result.data;
       const symbolName = cursorContext?.symbolName || 'symbol';
-      const formattedText = await formatReferencesResults(
+      const formatted = await formatReferencesResults(
         references,
-        symbolName
+        symbolName,
+        validatedRequest.offset,
+        validatedRequest.tests,
+        validatedRequest.mode,
+        validatedRequest.access
       );
 
       const sections: string[] = [];
@@ -57,28 +166,227 @@ export function registerReferencesTool(server: McpServer, manager: LspManager) {
         sections.push(formatCursorContext(cursorContext));
       }
 
-      sections.push(formattedText);
+      sections.push(formatted.text);
+      if (directory && outsideDirectory > 0) {
+        sections.push(
+          `${outsideDirectory} reference(s) outside ${formatFilePath(directory)} not shown`
+        );
+      }
 
-      return {
-        content: [
-          {
-            type: 'text' as const,
-            text: sections.join('\n\n'),
-          },
-        ],
-      };
-    }
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      return createToolResult(sections.join('\n\n'), {
+        symbol: toSymbolAtCursorOutput(cursorContext),
+        ...formatted.output,
+        filteredOut: formatted.output.filteredOut + outsideDirectory,
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
+}
+
+/**
+ * Count references per file URI
+ */
+function countByFile(references: Location[]): Map<string, number> {
+  const counts = new Map<string, number>();
+  for (const ref of references) {
+    counts.set(ref.uri, (counts.get(ref.uri) ?? 0) + 1);
+  }
+  return counts;
+}
+
+/**
+ * Format the per-file count heading, noting when a page shows only part of
+ * a file's references
+ */
+function formatFileHeading(uri: string, shown: number, total: number): string {
+  const filePath = formatFilePath(uri);
+  const testTag = isTestFile(uri) ? ' [test]' : '';
+  const countText = shown === total ? `${total}` : `${shown} of ${total}`;
+  const noun = total === 1 ? 'reference' : 'references';
+  return `${filePath} (${countText} ${noun})${testTag}${formatOwnerTag(uri)}`;
+}
+
+/**
+ * Summary mode: totals per file, busiest files first, without paging
+ */
+function formatReferenceSummary(
+  fileTotals: Map<string, number>,
+  total: number
+): string {
+  const fileText = fileTotals.size === 1 ? 'file' : 'files';
+  const lines = [
+    `Found ${total} reference(s) across ${fileTotals.size} ${fileText}`,
+    '',
+  ];
+
+  const sortedFiles = [...fileTotals.entries()].sort(
+    ([uriA, countA], [uriB, countB]) =>
+      countB - countA || uriA.localeCompare(uriB)
   );
+  for (const [uri, count] of sortedFiles) {
+    lines.push(formatFileHeading(uri, count, count));
+  }
+
+  return lines.join('\n');
+}
+
+function toReferenceFileOutputs(
+  references: SymbolReference[],
+  fileTotals: Map<string, number>,
+  includeReferences: boolean
+): ReferenceFileOutput[] {
+  const files: Array<[string, SymbolReference[]]> = includeReferences
+    ? [...groupByFile(references)]
+    : [...fileTotals.keys()].map((uri) => [uri, []]);
+  return files.map(([uri, fileReferences]) => ({
+    file: formatFilePath(uri),
+    total: fileTotals.get(uri) ?? fileReferences.length,
+    test: isTestFile(uri),
+    references: fileReferences.map((ref) => ({
+      line: ref.range.start.line + 1,
+      character: ref.range.start.character + 1,
+      ...(ref.access && { access: ref.access }),
+    })),
+  }));
 }
 
 async function formatReferencesResults(
-  references: Location[],
-  symbolName: string
-): Promise<string> {
-  if (references.length === 0) {
-    return 'Found no references';
+  allReferences: SymbolReference[],
+  symbolName: string,
+  offset: number,
+  tests: TestFilter,
+  mode: ReferenceMode,
+  access: ReferenceAccessFilter
+): Promise<{ text: string; output: ReferencesOutput }> {
+  const empty = (text: string, filteredOut: number) => ({
+    text,
+    output: { total: 0, offset: 0, files: [], filteredOut },
+  });
+
+  if (allReferences.length === 0) {
+    return empty('Found no references', 0);
+  }
+
+  const testReferences = allReferences.filter((ref) =>
+    matchesTestFilter(isTestFile(ref.uri), tests)
+  );
+  if (testReferences.length === 0) {
+    return empty(
+      `Found no references (${allReferences.length} filtered out by tests: ${tests})`,
+      allReferences.length
+    );
+  }
+
+  const matchingReferences =
+    access === 'all'
+      ? testReferences
+      : testReferences.filter((ref) => ref.access === access);
+  if (matchingReferences.length === 0) {
+    return empty(
+      `Found no references (${testReferences.length} filtered out by access: ${access})`,
+      allReferences.length
+    );
+  }
+  const filteredOut = allReferences.length - matchingReferences.length;
+
+  // Sort up front so offsets stay stable between calls
+  const sortedAll = [...matchingReferences].sort(
+    (a, b) =>
+      a.uri.localeCompare(b.uri) ||
+      a.range.start.line - b.range.start.line ||
+      a.range.start.character - b.range.start.character
+  );
+  const fileTotals = countByFile(sortedAll);
+
+  if (mode === 'summary') {
+    return {
+      text: formatReferenceSummary(fileTotals, sortedAll.length),
+      output: {
+        total: sortedAll.length,
+        offset: 0,
+        files: toReferenceFileOutputs(sortedAll, fileTotals, false),
+        filteredOut,
+      },
+    };
+  }
+
+  const limit = mode === 'compact' ? MAX_COMPACT_REFERENCES : MAX_REFERENCES;
+  const page = paginate(sortedAll, offset, limit);
+  const references = page.items;
+
+  const fileText = fileTotals.size === 1 ? 'file' : 'files';
+  let result = `Found ${page.total} reference(s) across ${fileTotals.size} ${fileText}`;
+  const pageRange = formatPageRange(page);
+  if (pageRange) {
+    result += ` (${pageRange})`;
+  }
+
+  if (mode === 'compact') {
+    for (const [uri, fileReferences] of groupByFile(references)) {
+      const positions = fileReferences.map(
+        (ref) => `@${ref.range.start.line + 1}:${ref.range.start.character + 1}`
+      );
+      const fileTotal = fileTotals.get(uri) ?? fileReferences.length;
+      const heading = formatFileHeading(uri, fileReferences.length, fileTotal);
+      result += `\n\n${heading}\n  ${positions.join(' ')}`;
+    }
+  } else {
+    result += await formatDetailedReferences(
+      references,
+      symbolName,
+      fileTotals
+    );
+  }
+
+  const marker = formatPageMarker(page, 'reference');
+  if (marker) {
+    result = `${result.trim()}\n\n${marker}`;
+  }
+
+  return {
+    text: result.trim(),
+    output: {
+      ...toPageOutput(page),
+      files: toReferenceFileOutputs(references, fileTotals, true),
+      filteredOut,
+    },
+  };
+}
+
+/**
+ * Group references by file URI, keeping their sorted order
+ */
+function groupByFile<T extends Location>(references: T[]): Map<string, T[]> {
+  const grouped = new Map<string, T[]>();
+  for (const ref of references) {
+    if (!ref.uri || !ref.range) continue;
+
+    const fileReferences = grouped.get(ref.uri);
+    if (fileReferences) {
+      fileReferences.push(ref);
+    } else {
+      grouped.set(ref.uri, [ref]);
+    }
   }
+  return grouped;
+}
 
+/**
+ * Detailed mode: every reference on the page with a code snippet
+ */
+async function formatDetailedReferences(
+  references: SymbolReference[],
+  symbolName: string,
+  fileTotals: Map<string, number>
+): Promise<string> {
   const symbols = references.map((ref) => ({
     name: 'reference',
     kind: 1,
@@ -98,58 +406,33 @@ async function formatReferencesResults(
   }));
 
   const enrichmentResults = await enrichSymbolsWithCode(symbols);
-  const enrichedReferences = enrichmentResults.map((result, index) => ({
-    ...references[index],
-    signaturePreview: result.codeSnippet
-      ? createSignaturePreview(result.codeSnippet.trim(), 100)
-      : null,
-    error: result.error,
-  }));
-
-  type EnrichedReference = Location & {
-    signaturePreview?: string | null;
-    error?: string | undefined;
-  };
-  const groupedByFile = new Map<string, EnrichedReference[]>();
-
-  for (const ref of enrichedReferences) {
-    const uri = ref.uri;
-    if (!uri || !ref.range) continue;
-
-    if (!groupedByFile.has(uri)) {
-      groupedByFile.set(uri, []);
-    }
-    groupedByFile.get(uri)!.push({
-      uri,
+  const enrichedReferences = references.map((ref, index) => {
+    const enrichment = enrichmentResults[index];
+    return {
+      uri: ref.uri,
       range: ref.range,
-      signaturePreview: ref.signaturePreview,
-      error: ref.error,
-    });
-  }
-
-  const fileText = groupedByFile.size === 1 ? 'file' : 'files';
-  let result = `Found ${references.length} reference(s) across ${groupedByFile.size} ${fileText}`;
-
-  for (const [, fileReferences] of groupedByFile) {
-    const firstReference = fileReferences[0];
-    if (!firstReference) {
-      continue;
-    }
+      access: ref.access,
+      signaturePreview: enrichment?.codeSnippet
+        ? createSignaturePreview(enrichment.codeSnippet.trim(), 100)
+        : null,
+      error: enrichment?.error,
+    };
+  });
 
-    const filePath = formatFilePath(firstReference.uri);
-    result += `\n\n${filePath} (${fileReferences.length} references)\n`;
+  let result = '';
 
-    const sortedReferences = fileReferences.sort((a, b) => {
-      const lineA = a.range.start.line;
-      const lineB = b.range.start.line;
-      return lineA - lineB;
-    });
+  for (const [uri, fileReferences] of groupByFile(enrichedReferences)) {
+    const fileTotal = fileTotals.get(uri) ?? fileReferences.length;
+    const heading = formatFileHeading(uri, fileReferences.length, fileTotal);
+    result += `\n\n${heading}\n`;
 
-    for (const ref of sortedReferences) {
+    for (const ref of fileReferences) {
       const line = ref.range.start.line + 1;
       const char = ref.range.start.character + 1;
 
-      result += `  @${line}:${char} ${symbolName}`;
+      const tag =
+        ref.access && ref.access !== 'text' ? ` [${ref.access}]` : '';
+      result += `  @${line}:${char}${tag} ${symbolName}`;
 
       if (ref.signaturePreview) {
         result += `\n    \`${ref.signaturePreview}\``;
@@ -160,5 +443,5 @@ async function formatReferencesResults(
     }
   }
 
-  return result.trim();
+  return result;
 }
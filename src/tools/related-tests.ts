@@ -0,0 +1,252 @@
+/**
+ * Related Tests Tool - Find the tests that exercise a symbol, directly or
+ * through the call graph
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import { createOneBasedPosition } from '../types.js';
+import { prepareSymbolPositionRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { symbolPositionSchema } from './schemas.js';
+import { formatCursorContext } from '../utils/cursor-context.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import { formatFilePath, getSymbolKindName } from './utils.js';
+import { isTestFile } from '../utils/test-classification.js';
+import type {
+  CallHierarchyItem,
+  Location,
+  RelatedTestsResult,
+  TestCaller,
+} from '../types/lsp.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  hierarchyItemOutputSchema,
+  locationOutputSchema,
+  symbolAtCursorOutputField,
+  toHierarchyItemOutput,
+  toLocationOutput,
+  toSymbolAtCursorOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+// Bounds the number of incoming-call requests a single search can issue
+const MAX_EXPLORED_ITEMS = 300;
+const MAX_TEST_CALLERS = 50;
+
+const relatedTestsSchema = {
+  ...symbolPositionSchema,
+  depth: z
+    .number()
+    .int()
+    .min(1)
+    .max(5)
+    .optional()
+    .default(3)
+    .describe(
+      'Maximum number of call-graph hops between a test and the symbol. 1 only returns tests calling it directly. Defaults to 3.'
+    ),
+} as const;
+
+const relatedTestsZodSchema = z.object(relatedTestsSchema);
+
+const relatedTestsOutputSchema = {
+  symbol: symbolAtCursorOutputField,
+  testFiles: z.array(z.string()).describe('Test files related to the symbol.'),
+  references: z
+    .array(locationOutputSchema)
+    .describe('References to the symbol from test files.'),
+  callers: z.array(
+    hierarchyItemOutputSchema.extend({
+      hops: z.number().int().describe('Calls between the test and symbol.'),
+      via: z
+        .array(z.string())
+        .describe('Functions in between, nearest to the test first.'),
+    })
+  ),
+  limitReached: z
+    .boolean()
+    .describe('Whether the search stopped early; more tests may exist.'),
+  warnings: warningsOutputField,
+} as const;
+
+export function registerRelatedTestsTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'related_tests',
+    {
+      title: 'Related Tests',
+      description:
+        'Find the tests that reference the symbol at a file position, directly or through a chain of calls, to see which tests cover a function before changing it.',
+      inputSchema: relatedTestsSchema,
+      outputSchema: relatedTestsOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = relatedTestsZodSchema.parse(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+
+      const prepared = await prepareSymbolPositionRequest(session, {
+        file: validatedRequest.file,
+        position: createOneBasedPosition(
+          validatedRequest.line,
+          validatedRequest.character
+        ),
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const result = await LspOperations.findRelatedTests(
+        session,
+        prepared.data,
+        {
+          maxDepth: validatedRequest.depth,
+          maxExploredItems: MAX_EXPLORED_ITEMS,
+          isTestFile,
+        }
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      const sections: string[] = [];
+      const { cursorContext } = result.data;
+      if (cursorContext) {
+        sections.push(formatCursorContext(cursorContext));
+      }
+      sections.push(formatRelatedTests(result.data.result));
+
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      const { references, callers, limitReached } = result.data.result;
+      return createToolResult(sections.join('\n\n'), {
+        symbol: toSymbolAtCursorOutput(cursorContext),
+        testFiles: [
+          ...new Set(
+            [
+              ...references.map((reference) => reference.uri),
+              ...callers.map((caller) => caller.item.uri),
+            ].map(formatFilePath)
+          ),
+        ].sort(),
+        references: references.map((reference) =>
+          toLocationOutput(reference.uri, reference.range.start)
+        ),
+        callers: [...callers]
+          .sort((a, b) => a.hops - b.hops)
+          .map((caller) => ({
+            ...toHierarchyItemOutput(caller.item),
+            hops: caller.hops,
+            via: caller.via.map((item) => item.name),
+          })),
+        limitReached,
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
+}
+
+function formatRelatedTests(result: RelatedTestsResult): string {
+  const testFiles = new Set([
+    ...result.references.map((reference) => reference.uri),
+    ...result.callers.map((caller) => caller.item.uri),
+  ]);
+  const hops = `${result.maxDepth} call${result.maxDepth === 1 ? '' : 's'}`;
+
+  if (testFiles.size === 0) {
+    const lines = [`No tests reference this symbol within ${hops}`];
+    if (result.targets.length === 0) {
+      lines.push(
+        'The language server returned no call hierarchy item here, so only direct references were checked.'
+      );
+    }
+    if (result.limitReached) {
+      lines.push(
+        `The search stopped after ${result.exploredItems} functions; tests further away may still exist.`
+      );
+    }
+    return lines.join('\n');
+  }
+
+  const sections = [
+    `Found ${testFiles.size} test file${testFiles.size === 1 ? '' : 's'} related to this symbol within ${hops}`,
+  ];
+
+  if (result.references.length > 0) {
+    sections.push(formatDirectReferences(result.references));
+  }
+  if (result.callers.length > 0) {
+    const targetName = result.targets[0]?.name ?? 'symbol';
+    sections.push(formatTestCallers(result.callers, targetName));
+  }
+  if (result.limitReached) {
+    sections.push(
+      `The search stopped after ${result.exploredItems} functions; more tests may exist.`
+    );
+  }
+
+  return sections.join('\n\n');
+}
+
+function formatDirectReferences(references: Location[]): string {
+  const byFile = new Map<string, Location[]>();
+  for (const reference of references) {
+    const existing = byFile.get(reference.uri);
+    if (existing) {
+      existing.push(reference);
+    } else {
+      byFile.set(reference.uri, [reference]);
+    }
+  }
+
+  const lines = ['Direct references'];
+  const sortedFiles = [...byFile.entries()].sort(([a], [b]) =>
+    a.localeCompare(b)
+  );
+  for (const [uri, fileReferences] of sortedFiles) {
+    const positions = fileReferences.map(
+      ({ range }) => `@${range.start.line + 1}:${range.start.character + 1}`
+    );
+    lines.push(`${formatFilePath(uri)} (${fileReferences.length})`);
+    lines.push(`  ${positions.join(', ')}`);
+  }
+  return lines.join('\n');
+}
+
+function formatTestCallers(callers: TestCaller[], targetName: string): string {
+  const sorted = [...callers].sort(
+    (a, b) => a.hops - b.hops || a.item.uri.localeCompare(b.item.uri)
+  );
+  const shown = sorted.slice(0, MAX_TEST_CALLERS);
+  const lines = ['Test functions calling it'];
+
+  for (const caller of shown) {
+    const distance = `${caller.hops} call${caller.hops === 1 ? '' : 's'} away`;
+    const chain = [caller.item, ...caller.via].map((item) => item.name);
+    lines.push(
+      caller.via.length > 0
+        ? `${formatCallerItem(caller.item)} - ${distance}: ${[...chain, targetName].join(' -> ')}`
+        : `${formatCallerItem(caller.item)} - ${distance}`
+    );
+  }
+
+  const omitted = sorted.length - shown.length;
+  if (omitted > 0) {
+    lines.push(`... ${omitted} more test function${omitted === 1 ? '' : 's'}`);
+  }
+  return lines.join('\n');
+}
+
+function formatCallerItem(item: CallHierarchyItem): string {
+  // Some servers omit selectionRange despite the LSP spec requiring it.
+  const position = (item.selectionRange ?? item.range).start;
+  return `${item.name} (${getSymbolKindName(item.kind)}) - ${formatFilePath(item.uri)}:${position.line + 1}:${position.character + 1}`;
+}
@@ -1,9 +1,64 @@
 import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
 import type {
   LspManager,
   LspManagerProfileStatus,
   LspManagerStatus,
 } from '../runtime/lsp-manager.js';
+import { withErrorCodes } from './errors.js';
+import { createToolResult } from './output-schemas.js';
+
+const reloadOutputSchema = {
+  state: z.string(),
+  mode: z.string().nullable(),
+  workspace: z.string(),
+  configPath: z.string().nullable(),
+  detectedProfile: z.string().nullable(),
+  issues: z.array(z.string()),
+  profiles: z.array(
+    z.object({
+      name: z.string(),
+      state: z.string(),
+      command: z.string(),
+      configured: z.boolean(),
+      workspace: z.string(),
+      diagnostics: z.enum(['push', 'pull']),
+      workspaceLoader: z.string().nullable(),
+      preloadFiles: z.array(z.string()),
+      extensions: z.array(z.string()),
+      pid: z.number().int().nullable(),
+      workspaceReady: z.boolean().nullable(),
+      lastError: z.string().nullable(),
+    })
+  ),
+} as const;
+
+function toStatusOutput(
+  status: LspManagerStatus
+): z.infer<z.ZodObject<typeof reloadOutputSchema>> {
+  return {
+    state: status.state,
+    mode: status.mode || null,
+    workspace: status.workspacePath,
+    configPath: status.configPath,
+    detectedProfile: status.detectedProfileName,
+    issues: status.issues,
+    profiles: status.profiles.map((profile) => ({
+      name: profile.name,
+      state: profile.state,
+      command: profile.command,
+      configured: profile.configured,
+      workspace: profile.workspacePath,
+      diagnostics: profile.diagnosticsStrategy,
+      workspaceLoader: profile.workspaceLoader,
+      preloadFiles: profile.preloadFiles,
+      extensions: profile.extensions,
+      pid: profile.pid,
+      workspaceReady: profile.workspaceReady,
+      lastError: profile.lastError,
+    })),
+  };
+}
 
 function formatProfileSummary(profile: LspManagerProfileStatus): string {
   const workspaceReadyText =
@@ -78,12 +133,11 @@ export function registerReloadTool(
       description:
         'Reload the active language-server configuration and reapply it to currently running LSP sessions.',
       inputSchema: {},
+      outputSchema: reloadOutputSchema,
     },
-    async () => {
+    withErrorCodes(async () => {
       const status = await manager.reload();
-      return {
-        content: [{ type: 'text' as const, text: formatStatus(status) }],
-      };
-    }
+      return createToolResult(formatStatus(status), toStatusOutput(status));
+    })
   );
 }
@@ -3,14 +3,41 @@
  */
 
 import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
 import { createOneBasedPosition } from '../types.js';
 import { prepareRenameRequest } from '../preparation.js';
 import * as LspOperations from '../lsp/operations/index.js';
 import { renameSchema } from './schemas.js';
 import { formatCursorContext } from '../utils/cursor-context.js';
-import { applyWorkspaceChanges, formatRenameResults } from './utils.js';
+import { CodedError, toCodedError } from '../utils/error-codes.js';
+import {
+  applyWorkspaceChanges,
+  applyWorkspaceChangesAtomically,
+  formatFilePath,
+  formatRenameResults,
+} from './utils.js';
+import { createUnifiedDiff } from '../utils/unified-diff.js';
 import { validateRename } from './validation.js';
+import { findStaleFiles } from './staleness.js';
 import type { LspManager } from '../runtime/lsp-manager.js';
+import { withErrorCodes } from './errors.js';
+import {
+  fileChangeOutputSchema,
+  symbolAtCursorOutputField,
+  toFileChangeOutputs,
+  toSymbolAtCursorOutput,
+} from './output-schemas.js';
+
+const renameOutputSchema = {
+  symbol: symbolAtCursorOutputField,
+  newName: z.string(),
+  written: z
+    .boolean()
+    .describe(
+      'Whether the files were written: false for dry runs and when any edit failed.'
+    ),
+  files: z.array(fileChangeOutputSchema),
+} as const;
 
 export function registerRenameTool(server: McpServer, manager: LspManager) {
   server.registerTool(
@@ -18,10 +45,11 @@ export function registerRenameTool(server: McpServer, manager: LspManager) {
     {
       title: 'Rename',
       description:
-        'Rename the symbol at a file position across the workspace using language-server rename support.',
+        'Rename the symbol at a file position across the workspace using language-server rename support. The server first checks the position can be renamed. Edits are written all or nothing and every changed location is listed; pass dryRun to get a unified diff patch instead of writing files.',
       inputSchema: renameSchema,
+      outputSchema: renameOutputSchema,
     },
-    async (request) => {
+    withErrorCodes(async (request) => {
       const validatedRequest = validateRename(request);
       const session = await manager.getSessionForFile(validatedRequest.file);
 
@@ -35,20 +63,62 @@ export function registerRenameTool(server: McpServer, manager: LspManager) {
       };
 
       const prepared = await prepareRenameRequest(session, renameRequest);
-      if (!prepared.ok) throw new Error(prepared.error.message);
+      if (!prepared.ok) throw toCodedError(prepared.error);
 
       const result = await LspOperations.rename(session, prepared.data);
-      if (!result.ok) throw new Error(result.error.message);
+      if (!result.ok) throw toCodedError(result.error);
 
       const { result: renameResult, cursorContext } = result.data;
-      const changeResults = await applyWorkspaceChanges(renameResult);
+
+      // Edits were computed against the content sent to the server; applying
+      // them to any file that changed since would corrupt it
+      const staleFiles = await findStaleFiles(session, [
+        prepared.data.filePath,
+        ...Object.keys(renameResult),
+      ]);
+      if (staleFiles.length > 0) {
+        throw new CodedError(
+          `${staleFiles.map((file) => formatFilePath(file)).join(', ')} changed on disk while the rename was computed. No edits were applied; run rename again.`,
+          'STALE_CONTENT',
+          'workspace'
+        );
+      }
+
       const symbolName = cursorContext?.symbolName || 'symbol';
       const newName = validatedRequest.newName;
-      const formattedResults = await formatRenameResults(
-        changeResults,
-        symbolName,
-        newName
-      );
+      const { dryRun } = validatedRequest;
+
+      const { results: changeResults, applied } = dryRun
+        ? {
+            results: await applyWorkspaceChanges(renameResult, {
+              dryRun: true,
+            }),
+            applied: false,
+          }
+        : await applyWorkspaceChangesAtomically(renameResult);
+
+      const sections = [
+        await formatRenameResults(changeResults, symbolName, newName),
+      ];
+      if (dryRun) {
+        sections.unshift('Dry run: no files were written');
+        for (const change of changeResults) {
+          const diff = createUnifiedDiff(
+            formatFilePath(change.fileUri),
+            change.originalContent ?? '',
+            change.updatedContent ?? change.originalContent ?? ''
+          );
+          if (diff) {
+            sections.push(`\`\`\`diff\n${diff}\n\`\`\``);
+          }
+        }
+      } else if (!applied) {
+        sections.unshift(
+          'Some edits could not be applied, so no files were written; fix the failures below and run rename again'
+        );
+      }
+
+      const formattedResults = sections.join('\n\n');
 
       const content: Array<{ type: 'text'; text: string }> = [];
 
@@ -61,7 +131,15 @@ export function registerRenameTool(server: McpServer, manager: LspManager) {
 
       content.push({ type: 'text' as const, text: formattedResults });
 
-      return { content };
-    }
+      return {
+        content,
+        structuredContent: {
+          symbol: toSymbolAtCursorOutput(cursorContext),
+          newName,
+          written: !dryRun && applied,
+          files: toFileChangeOutputs(changeResults),
+        },
+      };
+    })
   );
 }
@@ -3,6 +3,13 @@
  */
 
 import { z } from 'zod';
+import {
+  SYMBOL_MODIFIERS,
+  SYMBOL_VISIBILITIES,
+} from '../utils/symbol-modifiers.js';
+import { TEST_FILTERS } from '../utils/test-classification.js';
+import { HOVER_FORMATS } from '../utils/hover-markdown.js';
+import { MATCH_MODES } from '../utils/fuzzy-match.js';
 
 const fileDescription =
   'File path to inspect. Accepts either an absolute path or a path relative to the current workspace.';
@@ -10,6 +17,24 @@ const lineDescription =
   '1-based line number for the target symbol or cursor position.';
 const charDescription =
   '1-based character number for the target symbol or cursor position.';
+const offsetDescription =
+  'Number of results to skip. Use the offset from a previous truncation marker to continue where it stopped.';
+
+const offsetField = z
+  .number()
+  .int()
+  .min(0)
+  .optional()
+  .default(0)
+  .describe(offsetDescription);
+
+const testsField = z
+  .enum(TEST_FILTERS)
+  .optional()
+  .default('include')
+  .describe(
+    'Whether to include test code (test files, test directories, inline test modules): include, exclude, or only. Defaults to include.'
+  );
 
 export const symbolPositionSchema = {
   file: z.string().describe(fileDescription),
@@ -28,17 +53,139 @@ export const fileSchema = {
     .describe(
       'Include short declaration previews for symbols. Defaults to false for more compact output.'
     ),
+  inlayHints: z
+    .boolean()
+    .optional()
+    .default(false)
+    .describe(
+      'Interleave inlay hints from the language server, such as inferred types and parameter names, into the previews. Implies preview. Defaults to false.'
+    ),
+  skeleton: z
+    .boolean()
+    .optional()
+    .default(false)
+    .describe(
+      'Return the source collapsed to a skeleton instead of the symbol list: declarations and signatures kept, function bodies and import blocks elided, with line numbers. A cheap overview of a large file. Defaults to false.'
+    ),
+  visibility: z
+    .array(z.enum(SYMBOL_VISIBILITIES))
+    .optional()
+    .describe(
+      'Only include symbols with one of these visibilities, for example ["public"].'
+    ),
+  modifiers: z
+    .array(z.enum(SYMBOL_MODIFIERS))
+    .optional()
+    .describe(
+      'Only include symbols that have all of these modifiers, for example ["async"].'
+    ),
+  tests: testsField,
 } as const;
 
 export const diagnosticsSchema = {
   file: z.string().describe(fileDescription),
+  fixes: z
+    .boolean()
+    .optional()
+    .default(true)
+    .describe(
+      'Ask the language server for the quick fixes available for each diagnostic. Defaults to true.'
+    ),
+} as const;
+
+export const workspaceDiagnosticsSchema = {
+  severity: z
+    .enum(['error', 'warning', 'information', 'hint'])
+    .optional()
+    .default('warning')
+    .describe(
+      'Least severe level to report: error, warning, information or hint. Defaults to warning.'
+    ),
+  paths: z
+    .array(z.string())
+    .optional()
+    .default([])
+    .describe(
+      'Workspace-relative globs limiting which files are checked, e.g. `src/**`. Defaults to the whole workspace.'
+    ),
+  offset: offsetField,
 } as const;
 
 export const searchSchema = {
   query: z
     .string()
     .describe(
-      'Workspace symbol query. Prefer a symbol name, prefix, or API term rather than full-text code.'
+      'Workspace symbol query. Prefer a symbol name, prefix, or API term rather than full-text code. Optional filters: `kind:function,method` (symbol kinds, or `type`), `vis:pub` (public, protected, internal, crate, private), `path:src/**` (workspace-relative glob), `name:~^create` (regex, or `name:text` for a substring), `refs:>10` (reference count, at most 50 candidates) and `owner:@team` (CODEOWNERS owner).'
+    ),
+  offset: offsetField,
+  tests: testsField,
+  match: z
+    .enum(MATCH_MODES)
+    .optional()
+    .default('fuzzy')
+    .describe(
+      'How results are matched: fuzzy ranks them by camel humps (URB finds UserRepositoryBuilder), snake_case initials and common abbreviations (cfg finds Config) and shows each match score, widening the search when the server returns no match; server keeps the language server order. Defaults to fuzzy.'
+    ),
+  kinds: z
+    .array(z.string())
+    .optional()
+    .describe(
+      'Only return symbols of these kinds, for example ["class", "function", "constant"], or "type" for every type-like kind. Same as a `kind:` filter in the query.'
+    ),
+  limit: z
+    .number()
+    .int()
+    .min(1)
+    .optional()
+    .describe(
+      'Return at most this many matches, best first. Defaults to every match, paged.'
+    ),
+} as const;
+
+export const inspectSchema = {
+  ...symbolPositionSchema,
+  hoverFormat: z
+    .enum(HOVER_FORMATS)
+    .optional()
+    .default('text')
+    .describe(
+      'How to render documentation: text keeps code fences and signatures but strips other markdown, markdown returns the server markdown unchanged, sections splits it into signature, documentation and examples the same way for every language server. Defaults to text.'
+    ),
+} as const;
+
+export const REFERENCE_MODES = ['detailed', 'compact', 'summary'] as const;
+export const REFERENCE_ACCESS = ['all', 'read', 'write'] as const;
+
+export const referencesSchema = {
+  ...symbolPositionSchema,
+  offset: offsetField,
+  tests: testsField,
+  mode: z
+    .enum(REFERENCE_MODES)
+    .optional()
+    .default('detailed')
+    .describe(
+      'Output mode: detailed lists each reference with a code snippet, compact lists positions grouped by file without snippets, summary only returns per-file counts. Defaults to detailed.'
+    ),
+  access: z
+    .enum(REFERENCE_ACCESS)
+    .optional()
+    .default('all')
+    .describe(
+      'Keep only references that read or that write the symbol, as reported by document highlights. References whose access the server does not report are left out. Defaults to all.'
+    ),
+  includeDeclaration: z
+    .boolean()
+    .optional()
+    .default(true)
+    .describe(
+      'Count the declaration of the symbol among its references. Defaults to true.'
+    ),
+  directory: z
+    .string()
+    .optional()
+    .describe(
+      'Only return references in files under this directory, absolute or relative to the workspace.'
     ),
 } as const;
 
@@ -49,4 +196,76 @@ export const renameSchema = {
   newName: z
     .string()
     .describe('Replacement name to use for the symbol at the given position.'),
+  dryRun: z
+    .boolean()
+    .optional()
+    .default(false)
+    .describe(
+      'Return the edits as a unified diff patch without writing any file. Defaults to false.'
+    ),
+} as const;
+
+// Shared by tools that write edits a language server computed
+export const formatEditsSchema = {
+  formatEdits: z
+    .enum(['none', 'edited', 'file'])
+    .optional()
+    .default('none')
+    .describe(
+      "Format code after writing the edits so it follows the project's style: edited formats only the changed lines, file formats each changed file. Not applied in a dry run. Defaults to none."
+    ),
+} as const;
+
+export const formatSchema = {
+  file: z.string().describe(fileDescription),
+  startLine: z
+    .number()
+    .int()
+    .min(1)
+    .optional()
+    .describe(
+      '1-based first line to format. Set with endLine to format only those lines.'
+    ),
+  endLine: z
+    .number()
+    .int()
+    .min(1)
+    .optional()
+    .describe('1-based last line to format. Defaults to startLine.'),
+  tabSize: z
+    .number()
+    .int()
+    .min(1)
+    .optional()
+    .describe(
+      'Spaces per indentation level. Defaults to what the file already uses.'
+    ),
+  insertSpaces: z
+    .boolean()
+    .optional()
+    .describe(
+      'Indent with spaces rather than tabs. Defaults to what the file already uses.'
+    ),
+  dryRun: z
+    .boolean()
+    .optional()
+    .default(false)
+    .describe('Return the diff without writing the file. Defaults to false.'),
+} as const;
+
+export const semanticTokensSchema = {
+  file: z.string().describe(fileDescription),
+  startLine: z
+    .number()
+    .int()
+    .min(1)
+    .optional()
+    .describe('1-based first line to include. Defaults to the start of the file.'),
+  endLine: z
+    .number()
+    .int()
+    .min(1)
+    .optional()
+    .describe('1-based last line to include. Defaults to the end of the file.'),
+  offset: offsetField,
 } as const;
@@ -3,14 +3,78 @@
  */
 
 import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
-import { prepareWorkspaceRequest } from '../preparation.js';
-import * as LspOperations from '../lsp/operations/index.js';
+import { z } from 'zod';
 import { searchSchema } from './schemas.js';
 import { getSymbolKindName, formatFilePath } from './utils.js';
 import { enrichSymbolsWithCode, createSignaturePreview } from './enrichment.js';
 import { SymbolSearchResult } from '../types/lsp.js';
 import { validateSearch } from './validation.js';
+import {
+  formatQualifiedName,
+  getModulePath,
+} from '../utils/qualified-names.js';
+import { toRelativeFilePath } from '../utils/path-format.js';
+import {
+  isTestFile,
+  matchesTestFilter,
+  type TestFilter,
+} from '../utils/test-classification.js';
+import { formatOwnerTag } from '../utils/code-owners.js';
+import {
+  getServerQuery,
+  parseSymbolQuery,
+  type SymbolQuery,
+} from '../utils/symbol-query.js';
+import {
+  filterWorkspaceSymbols,
+  searchWorkspaceSymbols,
+} from '../map/workspace-symbols.js';
+import { fuzzyMatch, type FuzzyMatch } from '../utils/fuzzy-match.js';
+import { formatPageMarker, formatPageRange, paginate } from './truncation.js';
 import type { LspManager } from '../runtime/lsp-manager.js';
+import { withErrorCodes } from './errors.js';
+import { combineCodedErrors } from '../utils/error-codes.js';
+import {
+  createToolResult,
+  pageOutputShape,
+  symbolOutputSchema,
+  toLocationOutput,
+  toPageOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+
+// Enriching each match reads its file, so cap a single response
+const MAX_SEARCH_RESULTS = 100;
+
+const searchMatchOutputSchema = symbolOutputSchema.extend({
+  test: z.boolean(),
+  score: z
+    .number()
+    .optional()
+    .describe('Fuzzy match score, higher first; absent with match server.'),
+  references: z
+    .number()
+    .int()
+    .optional()
+    .describe('Reference count, when the query filters on refs.'),
+});
+
+type SearchMatchOutput = z.infer<typeof searchMatchOutputSchema>;
+
+const searchOutputSchema = {
+  ...pageOutputShape,
+  matches: z.array(searchMatchOutputSchema),
+  warnings: warningsOutputField.describe(
+    'Language servers that failed to answer the search, or that the results came from the saved symbol index.'
+  ),
+} as const;
+
+interface SearchOutput {
+  total: number;
+  offset: number;
+  nextOffset?: number;
+  matches: SearchMatchOutput[];
+}
 
 export function registerSearchTool(server: McpServer, manager: LspManager) {
   server.registerTool(
@@ -18,78 +82,141 @@ export function registerSearchTool(server: McpServer, manager: LspManager) {
     {
       title: 'Search',
       description:
-        'Search workspace symbols by name or pattern. Result quality depends on the language server and current indexing state.',
+        'Search workspace symbols by name or pattern, ranked by a fuzzy match that understands camel humps and abbreviations. The query can mix in filters such as `kind:function vis:pub path:src/** name:~^create refs:>10 owner:@team` to narrow results in one call. Result quality depends on the language server and current indexing state. Large result sets are paged; pass the offset from the truncation marker to continue.',
       inputSchema: searchSchema,
+      outputSchema: searchOutputSchema,
     },
-    async (request) => {
+    withErrorCodes(async (request) => {
       const validatedRequest = validateSearch(request);
-      const sessions = await manager.getSearchSessions();
-
-      const settledResults = await Promise.allSettled(
-        sessions.map(async (session) => {
-          const prepared = prepareWorkspaceRequest(session, validatedRequest);
-          if (!prepared.ok) {
-            throw new Error(prepared.error.message);
-          }
-
-          return await LspOperations.searchSymbols(session, prepared.data);
-        })
+      const symbolQuery = parseSymbolQuery(
+        validatedRequest.kinds?.length
+          ? `${validatedRequest.query} kind:${validatedRequest.kinds.join(',')}`
+          : validatedRequest.query
       );
+      const query = getServerQuery(symbolQuery);
 
-      const allSymbols: SymbolSearchResult[] = [];
-      const errors: string[] = [];
-
-      for (const settled of settledResults) {
-        if (settled.status === 'rejected') {
-          errors.push(
-            settled.reason instanceof Error
-              ? settled.reason.message
-              : String(settled.reason)
-          );
-          continue;
-        }
+      let {
+        symbols: allSymbols,
+        errors,
+        indexedAt,
+      } = await searchWorkspaceSymbols(manager, query);
 
-        if (!settled.value.ok) {
-          errors.push(settled.value.error.message);
-          continue;
+      // Servers without hump matching miss `URB`; widen to the first
+      // character and let the fuzzy matcher pick
+      if (
+        validatedRequest.match === 'fuzzy' &&
+        query.length > 1 &&
+        !allSymbols.some((symbol) => fuzzyMatch(query, symbol.name))
+      ) {
+        const widened = await searchWorkspaceSymbols(manager, query.charAt(0));
+        const matches = widened.symbols.filter((symbol) =>
+          fuzzyMatch(query, symbol.name)
+        );
+        if (matches.length > 0) {
+          allSymbols = matches;
+          errors = widened.errors;
+          indexedAt = widened.indexedAt;
         }
-
-        allSymbols.push(...settled.value.data);
       }
 
       if (allSymbols.length === 0 && errors.length > 0) {
-        throw new Error(errors.join('\n'));
+        throw combineCodedErrors(errors);
       }
 
-      let formattedText = await formatSearchResults(
+      const formatted = await formatSearchResults(
+        manager,
         allSymbols,
-        validatedRequest.query
+        symbolQuery,
+        validatedRequest.query,
+        validatedRequest.offset,
+        validatedRequest.tests,
+        validatedRequest.match === 'fuzzy' && query ? query : null,
+        validatedRequest.limit
       );
 
-      if (errors.length > 0) {
-        formattedText = `Warnings:\n${errors.map((error) => `- ${error}`).join('\n')}\n\n${formattedText}`;
+      const warnings = [
+        ...(indexedAt
+          ? [
+              `Results are from the symbol index updated ${indexedAt}; language servers are still starting or indexing, so recent changes may be missing.`,
+            ]
+          : []),
+        ...errors.map((error) => error.message),
+      ];
+      let formattedText = formatted.text;
+      if (warnings.length > 0) {
+        formattedText = `Warnings:\n${warnings.map((warning) => `- ${warning}`).join('\n')}\n\n${formattedText}`;
       }
 
-      return {
-        content: [
-          {
-            type: 'text' as const,
-            text: formattedText,
-          },
-        ],
-      };
-    }
+      return createToolResult(formattedText, {
+        ...formatted.output,
+        warnings,
+      });
+    })
   );
 }
 
 async function formatSearchResults(
-  symbols: SymbolSearchResult[],
-  query: string
-): Promise<string> {
-  if (symbols.length === 0) {
-    return `Found no matches for query "${query}"`;
+  manager: LspManager,
+  allSymbols: SymbolSearchResult[],
+  symbolQuery: SymbolQuery,
+  query: string,
+  offset: number,
+  tests: TestFilter,
+  // Query to score matches against, null to keep the server's matching
+  fuzzyQuery: string | null,
+  limit?: number
+): Promise<{ text: string; output: SearchOutput }> {
+  const empty = (text: string) => ({
+    text,
+    output: { total: 0, offset: 0, matches: [] },
+  });
+
+  if (allSymbols.length === 0) {
+    return empty(`Found no matches for query "${query}"`);
   }
 
+  const testFiltered = allSymbols.filter((symbol) =>
+    matchesTestFilter(isTestFile(symbol.location.uri), tests)
+  );
+  const filtered = await filterWorkspaceSymbols(
+    manager,
+    testFiltered,
+    symbolQuery.filters
+  );
+  const matchingSymbols = filtered.symbols;
+  if (matchingSymbols.length === 0) {
+    const filters = [
+      ...(tests !== 'include' ? [`tests: ${tests}`] : []),
+      ...(symbolQuery.filterTokens.length > 0
+        ? [symbolQuery.filterTokens.join(' ')]
+        : []),
+    ];
+    return empty(
+      `Found no matches for query "${query}" (${allSymbols.length} filtered out by ${filters.join('; ')})`
+    );
+  }
+
+  const matches = new Map<SymbolSearchResult, FuzzyMatch | null>();
+  if (fuzzyQuery !== null) {
+    for (const symbol of matchingSymbols) {
+      matches.set(symbol, fuzzyMatch(fuzzyQuery, symbol.name));
+    }
+  }
+  const getScore = (symbol: SymbolSearchResult) =>
+    matches.get(symbol)?.score ?? 0;
+
+  // Sort up front so offsets stay stable between calls
+  const sortedAll = [...matchingSymbols].sort(
+    (a, b) =>
+      getScore(b) - getScore(a) ||
+      a.location.uri.localeCompare(b.location.uri) ||
+      a.location.range.start.line - b.location.range.start.line ||
+      a.name.localeCompare(b.name)
+  );
+  const limited = limit !== undefined ? sortedAll.slice(0, limit) : sortedAll;
+  const page = paginate(limited, offset, MAX_SEARCH_RESULTS);
+  const symbols = page.items;
+
   const enrichmentResults = await enrichSymbolsWithCode(symbols);
   const enrichedSymbols = enrichmentResults.map((result) => ({
     ...result.symbol,
@@ -97,6 +224,8 @@ async function formatSearchResults(
       ? createSignaturePreview(result.codeSnippet, 100)
       : null,
     error: result.error,
+    match: matches.get(result.symbol) ?? null,
+    references: filtered.references.get(result.symbol),
   }));
 
   const groupedByFile = new Map<string, typeof enrichedSymbols>();
@@ -109,15 +238,32 @@ async function formatSearchResults(
     groupedByFile.get(uri)!.push(symbol);
   }
 
+  // Names shared by symbols in different files get their module path
+  const nameKey = (symbol: SymbolSearchResult) =>
+    `${symbol.containerName ?? ''}\0${symbol.name}`;
+  const filesByName = new Map<string, Set<string>>();
+  for (const symbol of limited) {
+    const files = filesByName.get(nameKey(symbol)) ?? new Set<string>();
+    filesByName.set(nameKey(symbol), files.add(symbol.location.uri));
+  }
+  const isAmbiguous = (symbol: SymbolSearchResult) =>
+    (filesByName.get(nameKey(symbol))?.size ?? 0) > 1;
+
   const sections = [];
-  const fileCount = groupedByFile.size;
+  const fileCount = new Set(limited.map((symbol) => symbol.location.uri)).size;
+  const pageRange = formatPageRange(page);
+  const limitNote =
+    limited.length < sortedAll.length
+      ? `, limited from ${sortedAll.length}`
+      : '';
   sections.push(
-    `Found ${symbols.length} matches for query "${query}" across ${fileCount} files`
+    `Found ${page.total} matches for query "${query}" across ${fileCount} files${limitNote}${pageRange ? ` (${pageRange})` : ''}`
   );
 
   for (const [uri, fileSymbols] of groupedByFile) {
     const filePath = formatFilePath(uri);
-    let fileContent = `${filePath} (${fileSymbols.length} results)\n`;
+    const testTag = isTestFile(uri) ? ' [test]' : '';
+    let fileContent = `${filePath} (${fileSymbols.length} results)${testTag}${formatOwnerTag(uri)}\n`;
 
     const sortedSymbols = fileSymbols.sort((a, b) => {
       const lineA = a.location.range.start.line;
@@ -130,7 +276,26 @@ async function formatSearchResults(
       const char = symbol.location.range.start.character + 1;
       const kind = getSymbolKindName(symbol.kind);
 
-      fileContent += `  @${line}:${char} ${kind} - ${symbol.name}\n`;
+      const name = formatQualifiedName(
+        symbol.name,
+        [
+          ...(isAmbiguous(symbol)
+            ? getModulePath(toRelativeFilePath(uri))
+            : []),
+          ...(symbol.containerName ? [symbol.containerName] : []),
+        ],
+        filePath
+      );
+
+      const matchTag = symbol.match
+        ? ` (match ${symbol.match.score}, ${symbol.match.kind})`
+        : '';
+      const referencesTag =
+        symbol.references !== undefined
+          ? ` [${symbol.references} references]`
+          : '';
+
+      fileContent += `  @${line}:${char} ${kind} - ${name}${matchTag}${referencesTag}\n`;
 
       if (symbol.signaturePreview) {
         fileContent += `    \`${symbol.signaturePreview}\`\n`;
@@ -142,5 +307,28 @@ async function formatSearchResults(
     sections.push(fileContent.trim());
   }
 
-  return sections.join('\n\n');
+  const marker = formatPageMarker(page, 'match');
+  if (marker) {
+    sections.push(marker);
+  }
+
+  return {
+    text: sections.join('\n\n'),
+    output: {
+      ...toPageOutput(page),
+      matches: symbols.map((symbol) => {
+        const match = matches.get(symbol);
+        const references = filtered.references.get(symbol);
+        return {
+          ...toLocationOutput(symbol.location.uri, symbol.location.range.start),
+          name: symbol.name,
+          kind: getSymbolKindName(symbol.kind),
+          ...(symbol.containerName && { container: symbol.containerName }),
+          test: isTestFile(symbol.location.uri),
+          ...(match && { score: match.score }),
+          ...(references !== undefined && { references }),
+        };
+      }),
+    },
+  };
 }
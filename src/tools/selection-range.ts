@@ -0,0 +1,215 @@
+/**
+ * Selection Range Tool - Grow a position into the expression, statement,
+ * function and class around it
+ */
+
+import * as fs from 'fs';
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import type { Range } from 'vscode-languageserver-protocol';
+import { createOneBasedPosition } from '../types.js';
+import { prepareSymbolPositionRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { symbolPositionSchema } from './schemas.js';
+import { formatCursorContext } from '../utils/cursor-context.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import { splitLines } from '../utils/text.js';
+import { formatFilePath } from './utils.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  fileOutputField,
+  rangeOutputSchema,
+  symbolAtCursorOutputField,
+  toRangeOutput,
+  toSymbolAtCursorOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+// Single-line ranges longer than this are cut in the level list
+const MAX_INLINE_LENGTH = 80;
+
+const selectionRangeSchema = {
+  ...symbolPositionSchema,
+  maxLines: z
+    .number()
+    .int()
+    .min(1)
+    .max(500)
+    .optional()
+    .default(40)
+    .describe(
+      'Return the code of the largest enclosing range that fits in this many lines. Defaults to 40.'
+    ),
+} as const;
+
+const selectionRangeZodSchema = z.object(selectionRangeSchema);
+
+const selectionRangeOutputSchema = {
+  symbol: symbolAtCursorOutputField,
+  file: fileOutputField,
+  ranges: z
+    .array(rangeOutputSchema.extend({ lines: z.number().int() }))
+    .describe('Enclosing ranges, innermost first.'),
+  largest: z
+    .object({
+      level: z.number().int().describe('1-based index into ranges.'),
+      code: z.string().describe('Whole lines of the range.'),
+    })
+    .optional()
+    .describe('The largest range within maxLines.'),
+  warnings: warningsOutputField,
+} as const;
+
+export function registerSelectionRangeTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'selection_range',
+    {
+      title: 'Selection Range',
+      description:
+        'List the progressively larger syntactic ranges enclosing a file position, such as expression, statement, function and class, and return the code of the largest one within maxLines. Use it to extract just enough context around a position.',
+      inputSchema: selectionRangeSchema,
+      outputSchema: selectionRangeOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = selectionRangeZodSchema.parse(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+
+      const prepared = await prepareSymbolPositionRequest(session, {
+        file: validatedRequest.file,
+        position: createOneBasedPosition(
+          validatedRequest.line,
+          validatedRequest.character
+        ),
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const result = await LspOperations.selectionRanges(
+        session,
+        prepared.data
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      const sections: string[] = [];
+      const { cursorContext } = result.data;
+
+      if (cursorContext) {
+        sections.push(formatCursorContext(cursorContext));
+      }
+
+      const content = await fs.promises.readFile(
+        prepared.data.filePath,
+        'utf-8'
+      );
+      const lines = splitLines(content);
+      const ranges = result.data.result;
+      const largest = findLargestRange(ranges, validatedRequest.maxLines);
+      sections.push(
+        formatSelectionRanges(
+          prepared.data.filePath,
+          lines,
+          ranges,
+          validatedRequest.maxLines
+        )
+      );
+
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      const largestRange = ranges[largest];
+      return createToolResult(sections.join('\n\n'), {
+        symbol: toSymbolAtCursorOutput(cursorContext),
+        file: formatFilePath(prepared.data.filePath),
+        ranges: ranges.map((range) => ({
+          ...toRangeOutput(range),
+          lines: range.end.line - range.start.line + 1,
+        })),
+        ...(largestRange && {
+          largest: {
+            level: largest + 1,
+            code: getRangeLines(lines, largestRange),
+          },
+        }),
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
+}
+
+function getRangeText(lines: string[], range: Range): string {
+  const selected = lines.slice(range.start.line, range.end.line + 1);
+  if (selected.length === 0) {
+    return '';
+  }
+  selected[selected.length - 1] = selected[selected.length - 1]!.slice(
+    0,
+    range.end.character
+  );
+  selected[0] = selected[0]!.slice(range.start.character);
+  return selected.join('\n');
+}
+
+// Whole lines, so the code keeps its indentation
+function getRangeLines(lines: string[], range: Range): string {
+  return lines.slice(range.start.line, range.end.line + 1).join('\n');
+}
+
+/**
+ * Index of the largest range spanning at most maxLines, or -1
+ */
+function findLargestRange(ranges: Range[], maxLines: number): number {
+  let largest = -1;
+  ranges.forEach((range, index) => {
+    if (range.end.line - range.start.line + 1 <= maxLines) {
+      largest = index;
+    }
+  });
+  return largest;
+}
+
+function formatSelectionRanges(
+  filePath: string,
+  lines: string[],
+  ranges: Range[],
+  maxLines: number
+): string {
+  if (ranges.length === 0) {
+    return 'No selection ranges found at this position';
+  }
+
+  const levels = ranges.map((range, index) => {
+    const { start, end } = range;
+    const lineCount = end.line - start.line + 1;
+    let level = `${index + 1}. ${start.line + 1}:${start.character + 1}-${end.line + 1}:${end.character + 1} (${lineCount} line${lineCount === 1 ? '' : 's'})`;
+    if (lineCount === 1) {
+      const text = getRangeText(lines, range).trim();
+      level += ` \`${text.length > MAX_INLINE_LENGTH ? `${text.slice(0, MAX_INLINE_LENGTH)}...` : text}\``;
+    }
+    return level;
+  });
+  const sections = [
+    `Selection ranges in ${formatFilePath(filePath)}, innermost first\n${levels.join('\n')}`,
+  ];
+
+  const largest = findLargestRange(ranges, maxLines);
+  const range = ranges[largest];
+  if (range) {
+    const code = getRangeLines(lines, range);
+    sections.push(
+      `Largest range within ${maxLines} lines (level ${largest + 1}), lines ${range.start.line + 1}-${range.end.line + 1}:\n\`\`\`\n${code}\n\`\`\``
+    );
+  }
+
+  return sections.join('\n\n');
+}
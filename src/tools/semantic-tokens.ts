@@ -0,0 +1,136 @@
+/**
+ * Semantic Tokens Tool - Classify the identifiers and keywords in a file
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import { prepareFileRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { semanticTokensSchema } from './schemas.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import { formatFilePath } from './utils.js';
+import type { SemanticToken } from '../types/lsp.js';
+import {
+  formatPageMarker,
+  formatPageRange,
+  paginate,
+  type Page,
+} from './truncation.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  fileOutputField,
+  pageOutputShape,
+  positionOutputShape,
+  toPageOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+const MAX_TOKENS = 200;
+
+const semanticTokensZodSchema = z.object(semanticTokensSchema);
+
+const semanticTokensOutputSchema = {
+  file: fileOutputField,
+  ...pageOutputShape,
+  tokens: z.array(
+    z.object({
+      ...positionOutputShape,
+      text: z.string(),
+      type: z.string().describe('Token type, e.g. class or parameter.'),
+      modifiers: z.array(z.string()),
+    })
+  ),
+  warnings: warningsOutputField,
+} as const;
+
+export function registerSemanticTokensTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'semantic_tokens',
+    {
+      title: 'Semantic Tokens',
+      description:
+        'List the semantic tokens of a file as the language server classifies them, with their type (class, parameter, property, ...) and modifiers (declaration, readonly, ...). Use startLine and endLine to narrow the range; results are paged, pass the offset from the truncation marker to continue.',
+      inputSchema: semanticTokensSchema,
+      outputSchema: semanticTokensOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = semanticTokensZodSchema.parse(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+      const prepared = prepareFileRequest(session, {
+        file: validatedRequest.file,
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const result = await LspOperations.semanticTokens(
+        session,
+        prepared.data
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      // Token lines are 0-based, the requested range is 1-based
+      const startLine = (validatedRequest.startLine ?? 1) - 1;
+      const endLine = (validatedRequest.endLine ?? Infinity) - 1;
+      const tokens = result.data.filter(
+        (token) => token.line >= startLine && token.line <= endLine
+      );
+
+      const page = paginate(tokens, validatedRequest.offset, MAX_TOKENS);
+      const sections = [formatSemanticTokens(prepared.data.filePath, page)];
+
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      return createToolResult(sections.join('\n\n'), {
+        file: formatFilePath(prepared.data.filePath),
+        ...toPageOutput(page),
+        tokens: page.items.map((token) => ({
+          line: token.line + 1,
+          character: token.character + 1,
+          text: token.text,
+          type: token.tokenType,
+          modifiers: token.tokenModifiers,
+        })),
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
+}
+
+function formatSemanticTokens(
+  filePath: string,
+  page: Page<SemanticToken>
+): string {
+  if (page.total === 0) {
+    return `No semantic tokens found in ${formatFilePath(filePath)}`;
+  }
+
+  const pageRange = formatPageRange(page);
+  const header = `Semantic tokens in ${formatFilePath(filePath)} (${page.total}${pageRange ? `, ${pageRange}` : ''})`;
+
+  const lines = page.items.map((token) => {
+    const modifiers =
+      token.tokenModifiers.length > 0
+        ? ` [${token.tokenModifiers.join(', ')}]`
+        : '';
+    return `${token.line + 1}:${token.character + 1} ${token.text} - ${token.tokenType}${modifiers}`;
+  });
+
+  const marker = formatPageMarker(page, 'token');
+  if (marker) {
+    lines.push(marker);
+  }
+
+  return [header, ...lines].join('\n');
+}
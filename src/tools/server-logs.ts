@@ -0,0 +1,139 @@
+/**
+ * Server Logs Tool - Read the recent messages language servers logged or
+ * asked to show, such as a missing toolchain or a failed build
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { formatWindowLogMessages } from '../utils/window-logs.js';
+import { getLogLevelName } from '../utils/log-level.js';
+import { withErrorCodes } from './errors.js';
+import { createToolResult } from './output-schemas.js';
+
+// Most severe level first, as window/logMessage numbers them
+const LOG_LEVELS = {
+  error: 1,
+  warning: 2,
+  info: 3,
+  log: 4,
+} as const;
+
+const serverLogsSchema = {
+  profile: z
+    .string()
+    .optional()
+    .describe(
+      'Language server profile to read, e.g. rust. Defaults to every running server.'
+    ),
+  level: z
+    .enum(['error', 'warning', 'info', 'log'])
+    .optional()
+    .default('log')
+    .describe(
+      'Least severe level to include. Defaults to log, which includes everything.'
+    ),
+  contains: z
+    .string()
+    .optional()
+    .describe('Only include messages containing this text, ignoring case.'),
+  limit: z
+    .number()
+    .int()
+    .min(1)
+    .max(500)
+    .optional()
+    .default(50)
+    .describe(
+      'How many of the most recent messages to return. Defaults to 50.'
+    ),
+} as const;
+
+const serverLogsZodSchema = z.object(serverLogsSchema);
+
+const serverLogOutputSchema = z.object({
+  name: z.string(),
+  matching: z
+    .number()
+    .int()
+    .describe('Messages matching level and contains, before limit.'),
+  messages: z.array(
+    z.object({
+      level: z.string(),
+      message: z.string(),
+      shown: z.boolean().describe('Whether the server asked to show it.'),
+    })
+  ),
+  error: z.string().optional().describe('Why the logs could not be read.'),
+});
+
+export function registerServerLogsTool(server: McpServer, manager: LspManager) {
+  server.registerTool(
+    'server_logs',
+    {
+      title: 'Server Logs',
+      description:
+        'Read the recent messages running language servers logged or asked the editor to show, for example a missing toolchain, a failed build script or a project that failed to load. Check it when results look empty or incomplete.',
+      inputSchema: serverLogsSchema,
+      outputSchema: { servers: z.array(serverLogOutputSchema) },
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = serverLogsZodSchema.parse(request);
+      const sessions = manager.getStartedSessions(validatedRequest.profile);
+
+      if (sessions.length === 0) {
+        const target = validatedRequest.profile
+          ? `The ${validatedRequest.profile} language server is not running`
+          : 'No language servers are running';
+        return createToolResult(
+          `${target}. Run an LSP-backed tool on a matching file to launch it.`,
+          { servers: [] }
+        );
+      }
+
+      const maxLevel = LOG_LEVELS[validatedRequest.level];
+      const needle = validatedRequest.contains?.toLowerCase();
+      const sections: string[] = [];
+      const servers: z.infer<typeof serverLogOutputSchema>[] = [];
+
+      for (const session of sessions) {
+        const name = session.getProfile().name;
+        const result = LspOperations.logs(session);
+        if (!result.ok) {
+          sections.push(`${name}\n${result.error.message}`);
+          servers.push({
+            name,
+            matching: 0,
+            messages: [],
+            error: result.error.message,
+          });
+          continue;
+        }
+
+        const matching = result.data.filter(
+          (message) =>
+            message.type <= maxLevel &&
+            (!needle || message.message.toLowerCase().includes(needle))
+        );
+        const recent = matching.slice(-validatedRequest.limit);
+        const header =
+          recent.length < matching.length
+            ? `${name} (last ${recent.length} of ${matching.length} messages)`
+            : `${name} (${matching.length} message${matching.length === 1 ? '' : 's'})`;
+        sections.push(`${header}\n${formatWindowLogMessages(recent)}`);
+        servers.push({
+          name,
+          matching: matching.length,
+          messages: recent.map((message) => ({
+            level: getLogLevelName(message.type),
+            message: message.message.trim(),
+            shown: Boolean(message.shown),
+          })),
+        });
+      }
+
+      return createToolResult(sections.join('\n\n'), { servers });
+    })
+  );
+}
@@ -0,0 +1,67 @@
+/**
+ * Stale content detection - flag results computed against outdated file
+ * content, or by a language server that hasn't finished indexing
+ */
+
+import type { LspSession } from '../runtime/lsp-session.js';
+import type { ActiveProgress } from '../types.js';
+import { toAbsoluteFilePath } from '../utils/path-format.js';
+import { formatFilePath } from './utils.js';
+
+/**
+ * One line for work in progress, e.g. "Indexing: 120/300 crates (40%)"
+ */
+export function formatProgress(progress: ActiveProgress): string {
+  const details = [
+    progress.message,
+    progress.percentage !== undefined ? `${progress.percentage}%` : undefined,
+  ].filter((detail) => detail !== undefined && detail !== '');
+  return details.length > 0
+    ? `${progress.title}: ${details.join(' ')}`
+    : progress.title;
+}
+
+function getIndexingWarning(session: LspSession): string | null {
+  const [first, ...rest] = session.getProgressStore().getActive();
+  if (!first) {
+    return null;
+  }
+
+  const more = rest.length > 0 ? ` and ${rest.length} more` : '';
+  return `⚠ ${session.getProfile().name} still indexing (${formatProgress(first)}${more}): results may be incomplete. Call indexing_status with wait to wait until it finishes.`;
+}
+
+/**
+ * Returns a warning when the file changed on disk after its content was sent
+ * to the language server, so positions and snippets may no longer line up,
+ * or when the server reports work in progress that can leave results partial
+ */
+export async function getStaleContentWarning(
+  session: LspSession,
+  filePath: string
+): Promise<string | null> {
+  const warnings = [getIndexingWarning(session)];
+  if (await session.isDocumentStale(filePath)) {
+    warnings.unshift(
+      `⚠ Stale: ${formatFilePath(filePath)} changed on disk while this request ran. Line numbers and snippets may not match the current file; run the request again.`
+    );
+  }
+
+  const present = warnings.filter((warning) => warning !== null);
+  return present.length > 0 ? present.join('\n') : null;
+}
+
+/**
+ * Files among those an edit touches, by path or URI, that changed on disk
+ * after their content was sent to the language server
+ */
+export async function findStaleFiles(
+  session: LspSession,
+  files: string[]
+): Promise<string[]> {
+  const paths = [...new Set(files.map((file) => toAbsoluteFilePath(file)))];
+  const stale = await Promise.all(
+    paths.map((filePath) => session.isDocumentStale(filePath))
+  );
+  return paths.filter((_, index) => stale[index]);
+}
@@ -0,0 +1,69 @@
+/**
+ * Switch Source/Header Tool - Find the header of a C or C++ source file, or
+ * the source file of a header
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import { prepareFileRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { formatFilePath } from './utils.js';
+import { withErrorCodes } from './errors.js';
+import { createToolResult, fileOutputField } from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+const switchSourceHeaderSchema = {
+  file: z
+    .string()
+    .describe(
+      'Source or header file. Accepts either an absolute path or a path relative to the current workspace.'
+    ),
+} as const;
+
+const switchSourceHeaderZodSchema = z.object(switchSourceHeaderSchema);
+
+export function registerSwitchSourceHeaderTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'switch_source_header',
+    {
+      title: 'Switch Source/Header',
+      description:
+        'Find the header a C or C++ source file implements, or the source file implementing a header, as clangd pairs them from the index and file names.',
+      inputSchema: switchSourceHeaderSchema,
+      outputSchema: {
+        file: fileOutputField,
+        counterpart: fileOutputField
+          .optional()
+          .describe('The matching header or source file, if any.'),
+      },
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = switchSourceHeaderZodSchema.parse(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+
+      const prepared = prepareFileRequest(session, {
+        file: validatedRequest.file,
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const result = await LspOperations.switchSourceHeader(
+        session,
+        prepared.data
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      const file = formatFilePath(prepared.data.filePath);
+      const counterpart = result.data && formatFilePath(result.data);
+      return createToolResult(
+        counterpart
+          ? `${file} -> ${counterpart}`
+          : `No matching header or source file found for ${file}`,
+        { file, ...(counterpart && { counterpart }) }
+      );
+    })
+  );
+}
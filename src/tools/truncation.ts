@@ -0,0 +1,94 @@
+/**
+ * Truncation helpers - explicit omission markers and offset-based continuation
+ */
+
+export interface Page<T> {
+  items: T[];
+  total: number;
+  offset: number;
+  // Offset to pass back to the tool to continue, absent on the last page
+  nextOffset?: number;
+}
+
+/**
+ * Slice a stable, already sorted list into a single page
+ */
+export function paginate<T>(
+  items: T[],
+  offset: number,
+  limit: number
+): Page<T> {
+  const start = Math.min(Math.max(offset, 0), items.length);
+  const end = Math.min(start + limit, items.length);
+
+  return {
+    items: items.slice(start, end),
+    total: items.length,
+    offset: start,
+    ...(end < items.length && { nextOffset: end }),
+  };
+}
+
+/**
+ * Pluralize a noun for marker output
+ */
+function pluralize(count: number, noun: string): string {
+  if (count === 1) {
+    return `${count} ${noun}`;
+  }
+
+  if (/[^aeiou]y$/.test(noun)) {
+    return `${count} ${noun.slice(0, -1)}ies`;
+  }
+
+  return `${count} ${noun}${/(s|x|ch|sh)$/.test(noun) ? 'es' : 's'}`;
+}
+
+/**
+ * Build an explicit marker for items left out of the response.
+ * Returns undefined when nothing was omitted.
+ */
+export function formatTruncationMarker(
+  omitted: number,
+  noun: string,
+  continuation?: string
+): string | undefined {
+  if (omitted <= 0) {
+    return undefined;
+  }
+
+  const marker = `... ${pluralize(omitted, noun)} not shown`;
+  return continuation ? `${marker} (${continuation})` : marker;
+}
+
+/**
+ * Build the marker for the remainder of a page, including the continuation handle
+ */
+export function formatPageMarker<T>(
+  page: Page<T>,
+  noun: string
+): string | undefined {
+  const shownEnd = page.offset + page.items.length;
+  return formatTruncationMarker(
+    page.total - shownEnd,
+    noun,
+    page.nextOffset !== undefined
+      ? `truncated: continue with offset ${page.nextOffset}`
+      : undefined
+  );
+}
+
+/**
+ * Describe which slice of the results is shown, e.g. "showing 101-200"
+ */
+export function formatPageRange<T>(page: Page<T>): string | undefined {
+  if (page.items.length === page.total) {
+    return undefined;
+  }
+
+  if (page.items.length === 0) {
+    return `offset ${page.offset} is past the end`;
+  }
+
+  return `showing ${page.offset + 1}-${page.offset + page.items.length}`;
+}
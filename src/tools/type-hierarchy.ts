@@ -0,0 +1,252 @@
+/**
+ * Type Hierarchy Tool - Walk supertypes and subtypes of a class or interface
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import { createOneBasedPosition } from '../types.js';
+import { prepareSymbolPositionRequest } from '../preparation.js';
+import * as LspOperations from '../lsp/operations/index.js';
+import { symbolPositionSchema } from './schemas.js';
+import { formatCursorContext } from '../utils/cursor-context.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import { getStaleContentWarning } from './staleness.js';
+import { formatFilePath, getSymbolKindName } from './utils.js';
+import type {
+  TypeHierarchyItem,
+  TypeHierarchyResult,
+  TypeHierarchyTarget,
+  TypeTreeNode,
+} from '../types/lsp.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  hierarchyItemOutputSchema,
+  symbolAtCursorOutputField,
+  toHierarchyItemOutput,
+  toSymbolAtCursorOutput,
+  toWarningsOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+import { toCodedError } from '../utils/error-codes.js';
+
+const MAX_TARGETS = 3;
+const MAX_EXPLORED_ITEMS = 60;
+const MAX_TREE_LINES = 40;
+
+const typeHierarchySchema = {
+  ...symbolPositionSchema,
+  direction: z
+    .enum(['supertypes', 'subtypes', 'both'])
+    .optional()
+    .default('both')
+    .describe(
+      'Walk up to supertypes, down to subtypes, or both. Defaults to both.'
+    ),
+  depth: z
+    .number()
+    .int()
+    .min(1)
+    .max(10)
+    .optional()
+    .default(3)
+    .describe(
+      'How many levels of the inheritance chain to follow. Defaults to 3.'
+    ),
+} as const;
+
+const typeHierarchyZodSchema = z.object(typeHierarchySchema);
+
+const typeOutputSchema = hierarchyItemOutputSchema.extend({
+  level: z
+    .number()
+    .int()
+    .describe('1 for direct supertypes or subtypes, 2 for theirs, and so on.'),
+});
+
+type TypeOutput = z.infer<typeof typeOutputSchema>;
+
+const typeHierarchyOutputSchema = {
+  symbol: symbolAtCursorOutputField,
+  targets: z.array(
+    hierarchyItemOutputSchema.extend({
+      supertypes: z.array(typeOutputSchema).optional(),
+      subtypes: z.array(typeOutputSchema).optional(),
+    })
+  ),
+  limitReached: z
+    .boolean()
+    .describe('Whether expansion stopped before reaching the full depth.'),
+  warnings: warningsOutputField,
+} as const;
+
+export function registerTypeHierarchyTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'type_hierarchy',
+    {
+      title: 'Type Hierarchy',
+      description:
+        'Walk the supertypes and subtypes of the class, interface or trait at a file position.',
+      inputSchema: typeHierarchySchema,
+      outputSchema: typeHierarchyOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = typeHierarchyZodSchema.parse(request);
+      const session = await manager.getSessionForFile(validatedRequest.file);
+
+      const prepared = await prepareSymbolPositionRequest(session, {
+        file: validatedRequest.file,
+        position: createOneBasedPosition(
+          validatedRequest.line,
+          validatedRequest.character
+        ),
+      });
+      if (!prepared.ok) throw toCodedError(prepared.error);
+
+      const result = await LspOperations.typeHierarchy(
+        session,
+        prepared.data,
+        validatedRequest.direction,
+        {
+          depth: validatedRequest.depth,
+          maxExploredItems: MAX_EXPLORED_ITEMS,
+        }
+      );
+      if (!result.ok) throw toCodedError(result.error);
+
+      const sections: string[] = [];
+      const { cursorContext } = result.data;
+
+      if (cursorContext) {
+        sections.push(formatCursorContext(cursorContext));
+      }
+
+      sections.push(formatTypeHierarchyResult(result.data.result));
+
+      const staleWarning = await getStaleContentWarning(
+        session,
+        prepared.data.filePath
+      );
+      if (staleWarning) {
+        sections.unshift(staleWarning);
+      }
+
+      const { targets, limitReached } = result.data.result;
+      return createToolResult(sections.join('\n\n'), {
+        symbol: toSymbolAtCursorOutput(cursorContext),
+        targets: targets.map((target) => ({
+          ...toHierarchyItemOutput(target.item),
+          ...(target.supertypes && {
+            supertypes: toTypeOutputs(target.supertypes),
+          }),
+          ...(target.subtypes && { subtypes: toTypeOutputs(target.subtypes) }),
+        })),
+        limitReached,
+        warnings: toWarningsOutput(staleWarning),
+      });
+    })
+  );
+}
+
+/**
+ * Flatten type trees depth first, as the text renders them
+ */
+function toTypeOutputs(nodes: TypeTreeNode[], level = 1): TypeOutput[] {
+  return nodes.flatMap((node) => [
+    { ...toHierarchyItemOutput(node.item), level },
+    ...toTypeOutputs(node.children ?? [], level + 1),
+  ]);
+}
+
+function formatTypeHierarchyResult(result: TypeHierarchyResult): string {
+  if (result.targets.length === 0) {
+    return 'No type hierarchy item found at this position';
+  }
+
+  const displayedTargets = result.targets.slice(0, MAX_TARGETS);
+  const sections = displayedTargets.map((target, index) =>
+    formatTypeHierarchyTarget(target, index)
+  );
+
+  const omittedTargets = result.targets.length - displayedTargets.length;
+  if (omittedTargets > 0) {
+    sections.push(
+      `... ${omittedTargets} more target${omittedTargets === 1 ? '' : 's'} not shown`
+    );
+  }
+
+  if (result.limitReached) {
+    sections.push(
+      `Stopped expanding after ${MAX_EXPLORED_ITEMS} types; some types below depth ${result.depth} may be missing`
+    );
+  }
+
+  return sections.join('\n\n');
+}
+
+function formatTypeHierarchyTarget(
+  target: TypeHierarchyTarget,
+  index: number
+): string {
+  const sections = [`Target ${index + 1}: ${formatTypeItem(target.item)}`];
+
+  if (target.supertypes) {
+    sections.push(formatTypeTree('Supertypes', target.supertypes));
+  }
+  if (target.subtypes) {
+    sections.push(formatTypeTree('Subtypes', target.subtypes));
+  }
+
+  return sections.join('\n\n');
+}
+
+/**
+ * Indented tree, each level the supertypes (or subtypes) of the one above
+ */
+function formatTypeTree(title: string, roots: TypeTreeNode[]): string {
+  if (roots.length === 0) {
+    return `${title}\nNone`;
+  }
+
+  const lines: string[] = [];
+  let total = 0;
+
+  const visit = (nodes: TypeTreeNode[], level: number) => {
+    for (const node of nodes) {
+      total++;
+      if (lines.length < MAX_TREE_LINES) {
+        lines.push(`${'  '.repeat(level)}${formatTypeItem(node.item)}`);
+      }
+      if (node.children) {
+        visit(node.children, level + 1);
+      }
+    }
+  };
+  visit(roots, 1);
+
+  const header =
+    lines.length < total
+      ? `${title} (${total}, showing ${lines.length})`
+      : `${title} (${total})`;
+  if (lines.length < total) {
+    const omitted = total - lines.length;
+    lines.push(`... ${omitted} more type${omitted === 1 ? '' : 's'} not shown`);
+  }
+
+  return [header, ...lines].join('\n');
+}
+
+function formatTypeItem(item: TypeHierarchyItem): string {
+  // Some servers omit selectionRange despite the LSP spec requiring it.
+  const position = (item.selectionRange ?? item.range).start;
+  let result = `${item.name} (${getSymbolKindName(item.kind)}) - ${formatFilePath(item.uri)}:${position.line + 1}:${position.character + 1}`;
+
+  if (item.detail) {
+    result += ` [${item.detail}]`;
+  }
+
+  return result;
+}
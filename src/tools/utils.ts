@@ -4,6 +4,15 @@
 
 import * as fs from 'fs';
 import { FileChange } from '../types/lsp.js';
+import {
+  parseTextLines,
+  replaceTextLines,
+  serializeTextLines,
+  splitLines,
+} from '../utils/text.js';
+import { formatOutputPath, toAbsoluteFilePath } from '../utils/path-format.js';
+import { recordAuditEdits } from './audit-log.js';
+import logger from '../utils/logger.js';
 
 export function getSymbolKindName(kind: number): string {
   const symbolKinds: { [key: number]: string } = {
@@ -38,37 +47,23 @@ export function getSymbolKindName(kind: number): string {
   return symbolKinds[kind] || 'Unknown';
 }
 
+/**
+ * Format a file path or URI for output in the configured path style
+ */
 export function formatFilePath(path: string): string {
-  // Remove file:// prefix if present
-  let cleanPath = path.replace('file://', '');
-
-  // Decode URL encoding (like %40 -> @)
-  try {
-    cleanPath = decodeURIComponent(cleanPath);
-  } catch {
-    // If decoding fails, use the original path
-  }
-
-  // Make path relative to current working directory if it starts with it
-  const cwd = process.cwd();
-  if (cleanPath.startsWith(cwd)) {
-    let relativePath = cleanPath.substring(cwd.length);
-    if (relativePath.startsWith('/')) {
-      relativePath = relativePath.substring(1);
-    }
-    return relativePath;
-  }
-
-  return cleanPath;
+  return formatOutputPath(path);
 }
 
 // Rename operation result types
-interface ChangeResult {
+export interface ChangeResult {
   fileUri: string;
   success: boolean;
   appliedChanges: AppliedChange[];
   failedChanges: FailedChange[];
   error?: string;
+  // File content before and after the changes, for diffs
+  originalContent?: string;
+  updatedContent?: string;
 }
 
 interface AppliedChange {
@@ -98,9 +93,10 @@ interface FailedChange {
  */
 async function applyFileChanges(
   fileUri: string,
-  changes: FileChange[]
+  changes: FileChange[],
+  dryRun: boolean
 ): Promise<ChangeResult> {
-  const filePath = formatFilePath(fileUri);
+  const filePath = toAbsoluteFilePath(fileUri);
   const result: ChangeResult = {
     fileUri,
     success: false,
@@ -109,9 +105,12 @@ async function applyFileChanges(
   };
 
   try {
-    // Read current file content from disk
+    // Read current file content from disk, keeping BOM and line endings
+    // so untouched lines are written back byte-for-byte
     const fileContent = await fs.promises.readFile(filePath, 'utf-8');
-    const lines = fileContent.split('\n');
+    const document = parseTextLines(fileContent);
+    const lines = document.lines;
+    result.originalContent = fileContent;
 
     // Sort changes by end position (descending) to apply bottom-up and avoid position shifting
     const sortedChanges = [...changes].sort((a, b) => {
@@ -163,11 +162,15 @@ async function applyFileChanges(
           }
           oldText = line.substring(startChar, endChar);
 
-          // Apply the change
-          lines[startLine] =
+          // Apply the change; inserted line breaks follow the file's style
+          replaceTextLines(
+            document,
+            startLine,
+            endLine,
             line.substring(0, startChar) +
-            change.newText +
-            line.substring(endChar);
+              change.newText +
+              line.substring(endChar)
+          );
         } else {
           // Multi-line change
           const firstLine = lines[startLine]!; // Safe after bounds check
@@ -201,10 +204,9 @@ async function applyFileChanges(
             firstLine.substring(0, startChar) +
             change.newText +
             lastLine.substring(endChar);
-          const newLines = newContent.split('\n');
 
-          // Replace the affected lines
-          lines.splice(startLine, endLine - startLine + 1, ...newLines);
+          // Replace the affected lines, keeping the last line's terminator
+          replaceTextLines(document, startLine, endLine, newContent);
         }
 
         // Record successful change
@@ -226,9 +228,13 @@ async function applyFileChanges(
       }
     }
 
+    result.updatedContent = serializeTextLines(document);
+
     // Write the modified content back to disk if any changes were applied
     if (result.appliedChanges.length > 0) {
-      await fs.promises.writeFile(filePath, lines.join('\n'), 'utf-8');
+      if (!dryRun) {
+        await fs.promises.writeFile(filePath, result.updatedContent, 'utf-8');
+      }
       result.success = true;
     } else if (result.failedChanges.length === 0) {
       // No changes to apply - this is also success
@@ -245,22 +251,86 @@ async function applyFileChanges(
 }
 
 /**
- * Applies workspace changes across multiple files with best-effort approach
+ * Applies workspace changes across multiple files with best-effort approach.
+ * A dry run computes the results without writing any file.
  */
 export async function applyWorkspaceChanges(
-  renameResult: Record<string, FileChange[]>
+  renameResult: Record<string, FileChange[]>,
+  options: { dryRun?: boolean } = {}
 ): Promise<ChangeResult[]> {
   const results: ChangeResult[] = [];
+  const dryRun = options.dryRun ?? false;
 
   // Process each file independently (best-effort approach)
   for (const [fileUri, changes] of Object.entries(renameResult)) {
-    const result = await applyFileChanges(fileUri, changes);
+    const result = await applyFileChanges(fileUri, changes, dryRun);
+    if (!dryRun) {
+      recordAuditEdits(fileUri, result.appliedChanges.length);
+    }
     results.push(result);
   }
 
   return results;
 }
 
+/**
+ * Applies workspace changes all or nothing: every file is computed first and
+ * nothing is written when any change fails. Files already written are
+ * restored if a later write fails.
+ */
+export async function applyWorkspaceChangesAtomically(
+  renameResult: Record<string, FileChange[]>
+): Promise<{ results: ChangeResult[]; applied: boolean }> {
+  const results = await applyWorkspaceChanges(renameResult, { dryRun: true });
+  if (
+    results.some((result) => result.error || result.failedChanges.length > 0)
+  ) {
+    return { results, applied: false };
+  }
+
+  const changed = results.filter(
+    (result) =>
+      result.appliedChanges.length > 0 &&
+      result.updatedContent !== result.originalContent
+  );
+  const written: ChangeResult[] = [];
+  try {
+    for (const result of changed) {
+      // Counted before writing, so a partial write is restored too
+      written.push(result);
+      await fs.promises.writeFile(
+        toAbsoluteFilePath(result.fileUri),
+        result.updatedContent ?? '',
+        'utf-8'
+      );
+    }
+  } catch (error) {
+    for (const result of written) {
+      try {
+        await fs.promises.writeFile(
+          toAbsoluteFilePath(result.fileUri),
+          result.originalContent ?? '',
+          'utf-8'
+        );
+      } catch (restoreError) {
+        logger.error('Failed to restore file after a failed edit', {
+          fileUri: result.fileUri,
+          error:
+            restoreError instanceof Error
+              ? restoreError.message
+              : String(restoreError),
+        });
+      }
+    }
+    throw error;
+  }
+
+  for (const result of results) {
+    recordAuditEdits(result.fileUri, result.appliedChanges.length);
+  }
+  return { results, applied: true };
+}
+
 /**
  * Formats rename operation results with symbols like diagnostics
  */
@@ -302,11 +372,13 @@ export async function formatRenameResults(
     for (const change of result.appliedChanges) {
       output += `\n  ✓ @${change.line}:${change.character} ${symbolName} → ${newName}`;
 
-      // Read the actual line from the file to show context (after change was applied)
+      // Show the line as it reads after the change, also for dry runs
       try {
-        const filePath = formatFilePath(result.fileUri);
-        const fileContent = await fs.promises.readFile(filePath, 'utf-8');
-        const lines = fileContent.split('\n');
+        const filePath = toAbsoluteFilePath(result.fileUri);
+        const fileContent =
+          result.updatedContent ??
+          (await fs.promises.readFile(filePath, 'utf-8'));
+        const lines = splitLines(fileContent);
         const contextLine = lines[change.line - 1]; // Convert back to 0-based
 
         if (contextLine) {
@@ -6,6 +6,8 @@ import { z } from 'zod';
 import {
   diagnosticsSchema,
   fileSchema,
+  inspectSchema,
+  referencesSchema,
   renameSchema,
   searchSchema,
   symbolPositionSchema,
@@ -15,6 +17,8 @@ import {
 const symbolPositionZodSchema = z.object(symbolPositionSchema);
 const fileZodSchema = z.object(fileSchema);
 const searchZodSchema = z.object(searchSchema);
+const inspectZodSchema = z.object(inspectSchema);
+const referencesZodSchema = z.object(referencesSchema);
 const renameZodSchema = z.object(renameSchema);
 const diagnosticsZodSchema = z.object(diagnosticsSchema);
 
@@ -22,6 +26,8 @@ const diagnosticsZodSchema = z.object(diagnosticsSchema);
 type SymbolPositionRequest = z.infer<typeof symbolPositionZodSchema>;
 type FileRequest = z.infer<typeof fileZodSchema>;
 type SearchRequest = z.infer<typeof searchZodSchema>;
+type InspectRequest = z.infer<typeof inspectZodSchema>;
+type ReferencesRequest = z.infer<typeof referencesZodSchema>;
 type RenameRequest = z.infer<typeof renameZodSchema>;
 type DiagnosticsRequest = z.infer<typeof diagnosticsZodSchema>;
 
@@ -48,6 +54,20 @@ export function validateSearch(request: unknown): SearchRequest {
   return searchZodSchema.parse(request);
 }
 
+/**
+ * Validates and parses inspect arguments
+ */
+export function validateInspect(request: unknown): InspectRequest {
+  return inspectZodSchema.parse(request);
+}
+
+/**
+ * Validates and parses references arguments
+ */
+export function validateReferences(request: unknown): ReferencesRequest {
+  return referencesZodSchema.parse(request);
+}
+
 /**
  * Validates and parses rename arguments
  */
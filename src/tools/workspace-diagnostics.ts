@@ -0,0 +1,198 @@
+/**
+ * Workspace Diagnostics Tool - Run the language servers' checks across the
+ * whole project and report what they find
+ */
+
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { z } from 'zod';
+import type { DiagnosticEntry } from '../types.js';
+import type { LspManager } from '../runtime/lsp-manager.js';
+import {
+  collectWorkspaceDiagnostics,
+  type WorkspaceDiagnosticsReport,
+} from '../map/workspace-diagnostics.js';
+import {
+  diagnosticOutputSchema,
+  formatDiagnostics,
+  toDiagnosticOutput,
+} from './diagnostics.js';
+import { workspaceDiagnosticsSchema } from './schemas.js';
+import { formatPageMarker, formatPageRange, paginate } from './truncation.js';
+import { withErrorCodes } from './errors.js';
+import {
+  createToolResult,
+  fileOutputField,
+  pageOutputShape,
+  toPageOutput,
+  warningsOutputField,
+} from './output-schemas.js';
+
+// Files opened one by one for servers without workspace diagnostics
+const MAX_CHECKED_FILES = 500;
+const MAX_DIAGNOSTICS = 100;
+const MAX_ERRORS = 10;
+
+const SEVERITIES = {
+  error: 1,
+  warning: 2,
+  information: 3,
+  hint: 4,
+} as const;
+
+const workspaceDiagnosticsZodSchema = z.object(workspaceDiagnosticsSchema);
+
+const workspaceDiagnosticsOutputSchema = {
+  counts: z.object({
+    errors: z.number().int(),
+    warnings: z.number().int(),
+    infos: z.number().int(),
+    hints: z.number().int(),
+  }),
+  files: z.number().int().describe('Files with diagnostics.'),
+  checkedFiles: z.number().int().describe('Files opened one by one.'),
+  pulledProfiles: z
+    .array(z.string())
+    .describe('Language servers that reported the whole workspace at once.'),
+  truncated: z
+    .boolean()
+    .describe('More files matched than were checked; narrow with paths.'),
+  ...pageOutputShape,
+  diagnostics: z.array(
+    diagnosticOutputSchema.extend({ file: fileOutputField })
+  ),
+  warnings: warningsOutputField,
+} as const;
+
+type WorkspaceDiagnosticsOutput = z.infer<
+  z.ZodObject<typeof workspaceDiagnosticsOutputSchema>
+>;
+
+export function registerWorkspaceDiagnosticsTool(
+  server: McpServer,
+  manager: LspManager
+) {
+  server.registerTool(
+    'workspace_diagnostics',
+    {
+      title: 'Workspace Diagnostics',
+      description:
+        "Run the language servers' checks across the whole workspace, like a compiler pass, and report diagnostics grouped by file. Uses workspace diagnostics where the server supports them and otherwise opens each source file in batches, so large workspaces take a while; narrow with `paths`. Large reports are paged; pass the offset from the truncation marker to continue.",
+      inputSchema: workspaceDiagnosticsSchema,
+      outputSchema: workspaceDiagnosticsOutputSchema,
+    },
+    withErrorCodes(async (request) => {
+      const validatedRequest = workspaceDiagnosticsZodSchema.parse(request);
+
+      const report = await collectWorkspaceDiagnostics(manager, {
+        workspace: manager.getStatus().workspacePath,
+        severity: SEVERITIES[validatedRequest.severity],
+        paths: validatedRequest.paths,
+        maxFiles: MAX_CHECKED_FILES,
+      });
+
+      const { text, output } = formatWorkspaceDiagnostics(
+        report,
+        validatedRequest.offset
+      );
+      return createToolResult(text, output);
+    })
+  );
+}
+
+function formatCount(count: number, noun: string): string {
+  return `${count} ${noun}${count === 1 ? '' : 's'}`;
+}
+
+function formatWorkspaceDiagnostics(
+  report: WorkspaceDiagnosticsReport,
+  offset: number
+): { text: string; output: WorkspaceDiagnosticsOutput } {
+  const entries = report.files.flatMap(({ file, diagnostics }) =>
+    diagnostics.map((diagnostic) => ({ file, diagnostic }))
+  );
+  const countSeverity = (severity: number) =>
+    entries.filter(({ diagnostic }) => diagnostic.severity === severity).length;
+  const [errors, warnings, infos, hints] = [1, 2, 3, 4].map(countSeverity);
+
+  const checked = [
+    report.pulledProfiles.length > 0
+      ? `workspace diagnostics from ${report.pulledProfiles.join(', ')}`
+      : undefined,
+    report.checkedFiles > 0
+      ? `${formatCount(report.checkedFiles, 'file')} checked`
+      : undefined,
+  ].filter((part) => part !== undefined);
+
+  const sections: string[] = [];
+  const summary = [
+    formatCount(errors!, 'error'),
+    formatCount(warnings!, 'warning'),
+    ...(infos ? [formatCount(infos, 'info')] : []),
+    ...(hints ? [formatCount(hints, 'hint')] : []),
+  ].join(', ');
+  sections.push(
+    `Found ${summary} in ${formatCount(report.files.length, 'file')}${checked.length > 0 ? ` (${checked.join('; ')})` : ''}`
+  );
+
+  if (report.truncated) {
+    sections.push(
+      `Stopped after checking ${MAX_CHECKED_FILES} files; narrow the check with paths to cover the rest`
+    );
+  }
+
+  if (report.errors.length > 0) {
+    const shown = report.errors.slice(0, MAX_ERRORS);
+    const omitted = report.errors.length - shown.length;
+    sections.push(
+      [
+        'Warnings:',
+        ...shown.map((error) => `- ${error}`),
+        ...(omitted > 0 ? [`... ${omitted} more not shown`] : []),
+      ].join('\n')
+    );
+  }
+
+  const page = paginate(entries, offset, MAX_DIAGNOSTICS);
+  const output: WorkspaceDiagnosticsOutput = {
+    counts: {
+      errors: errors!,
+      warnings: warnings!,
+      infos: infos!,
+      hints: hints!,
+    },
+    files: report.files.length,
+    checkedFiles: report.checkedFiles,
+    pulledProfiles: report.pulledProfiles,
+    truncated: report.truncated,
+    ...toPageOutput(page),
+    diagnostics: page.items.map(({ file, diagnostic }) => ({
+      file,
+      ...toDiagnosticOutput(diagnostic),
+    })),
+    warnings: report.errors,
+  };
+
+  if (entries.length === 0) {
+    return { text: sections.join('\n\n'), output };
+  }
+
+  const pageRange = formatPageRange(page);
+  if (pageRange) {
+    sections.push(`Diagnostics (${entries.length}, ${pageRange})`);
+  }
+
+  const byFile = new Map<string, DiagnosticEntry[]>();
+  for (const { file, diagnostic } of page.items) {
+    byFile.set(file, [...(byFile.get(file) ?? []), diagnostic]);
+  }
+  for (const [file, diagnostics] of byFile) {
+    sections.push(`${file}\n${formatDiagnostics(diagnostics)}`);
+  }
+
+  const marker = formatPageMarker(page, 'diagnostic');
+  if (marker) {
+    sections.push(marker);
+  }
+
+  return { text: sections.join('\n\n'), output };
+}
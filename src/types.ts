@@ -4,11 +4,17 @@ import {
   ClientCapabilities,
   Diagnostic,
   LogMessageParams,
+  ProgressParams,
   Range,
   ServerCapabilities,
+  WorkDoneProgressBegin,
+  WorkDoneProgressEnd,
+  WorkDoneProgressReport,
+  WorkspaceEdit,
 } from 'vscode-languageserver-protocol';
 // Import the position helpers used across the runtime.
 import { createOneBasedPosition, toZeroBased } from './types/position.js';
+import { withErrorHint } from './utils/error-hints.js';
 
 // Import types separately so they are erased at runtime. This prevents
 // Node from expecting the corresponding value exports during `pnpm dev`
@@ -25,11 +31,13 @@ export enum ErrorCode {
 }
 
 // Helper functions to create different error types
+// Known server error strings get an actionable hint appended to the message
 export function createLspError(
   errorCode: ErrorCode,
-  message: string,
+  rawMessage: string,
   originalError?: Error
 ): LspOperationError {
+  const message = withErrorHint(rawMessage);
   return originalError
     ? { message, errorCode, originalError }
     : { message, errorCode };
@@ -88,6 +96,8 @@ export interface LspClient {
   clientCapabilities?: ClientCapabilities;
   serverCapabilities?: ServerCapabilities;
   processId?: number;
+  // Receives workspace/applyEdit requests while a code action is applied
+  workspaceEditListener?: ((edit: WorkspaceEdit) => void) | undefined;
 }
 
 // LSP Client creation result with child process for shutdown handling
@@ -104,6 +114,13 @@ export interface WorkspaceState {
   readyAt?: Date;
 }
 
+// What the default workspace loader waits for, from the profile's readiness
+export interface WorkspaceReadiness {
+  progressTitle?: string | undefined;
+  logMessage?: string | undefined;
+  timeoutMs: number;
+}
+
 // Configuration for LSP initialization
 export interface LspConfig {
   workspaceUri: string;
@@ -111,6 +128,7 @@ export interface LspConfig {
   clientCapabilities?: ClientCapabilities;
   preloadFiles?: string[]; // Array of file paths to open during initialization
   workspaceReadyDelayMs?: number; // Optional wait before marking workspace ready
+  readiness?: WorkspaceReadiness; // Notifications the workspace is ready after
 }
 
 // Session-scoped document state for documents currently known to the LSP session.
@@ -119,6 +137,8 @@ interface SessionDocument {
   content: string;
   version: number;
   isOpen: boolean;
+  // Hash of the content last sent to the server, used to detect stale results
+  contentHash: string;
 }
 
 export type SessionDocuments = Map<string, SessionDocument>;
@@ -157,10 +177,39 @@ export interface DiagnosticProviderStore {
 // Use the official LogMessageParams type
 export type LogMessage = LogMessageParams;
 
+// A window/logMessage as received, or a window/showMessage popup when shown
+export interface WindowLogEntry extends LogMessage {
+  shown?: boolean;
+  receivedAt: Date;
+}
+
 export interface WindowLogStore {
-  messages: LogMessage[];
-  addMessage(message: LogMessage): void;
-  getMessages(): LogMessage[];
+  messages: WindowLogEntry[];
+  addMessage(message: LogMessage, shown?: boolean): void;
+  getMessages(): WindowLogEntry[];
+  clear(): void;
+}
+
+// Work a server announced through $/progress and hasn't ended yet
+export interface ActiveProgress {
+  token: string;
+  title: string;
+  message?: string;
+  percentage?: number;
+  startedAt: Date;
+}
+
+export interface ProgressStore {
+  progress: Map<string, ActiveProgress>;
+  handleProgress(
+    params: ProgressParams<
+      WorkDoneProgressBegin | WorkDoneProgressReport | WorkDoneProgressEnd
+    >
+  ): void;
+  getActive(): ActiveProgress[];
+  // Resolves true once nothing has been in progress for quietMs, or false
+  // when timeoutMs passes first
+  waitForIdle(timeoutMs: number, quietMs?: number): Promise<boolean>;
   clear(): void;
 }
 
@@ -175,6 +224,24 @@ export interface WorkspaceLoaderStore {
   isReady(): boolean;
 }
 
+// Symbol answers reused until the files they came from change: document
+// symbols by the content hash the server saw, workspace symbol searches by
+// query until any file changes
+export interface SymbolCacheStore {
+  // Bumped by every invalidation, so searches that raced one aren't kept
+  generation: number;
+  getDocumentSymbols(uri: string, contentHash: string): unknown;
+  setDocumentSymbols(uri: string, contentHash: string, symbols: unknown): void;
+  getWorkspaceSymbols(query: string): unknown;
+  setWorkspaceSymbols(
+    query: string,
+    generation: number,
+    symbols: unknown
+  ): void;
+  invalidate(uri: string): void;
+  clear(): void;
+}
+
 export type { OneBasedPosition, ZeroBasedPosition };
 
 export { createOneBasedPosition, toZeroBased };
@@ -232,4 +299,16 @@ export interface DiagnosticEntry {
   severity: number;
   range: Range;
   source: string;
+  // Link to documentation for the code
+  codeDescription?: string | undefined;
+  // Other locations the diagnostic refers to, e.g. a conflicting declaration
+  relatedInformation?: DiagnosticRelatedEntry[] | undefined;
+  // Titles of the quick fixes the server offers for it
+  fixes?: string[] | undefined;
+}
+
+interface DiagnosticRelatedEntry {
+  uri: string;
+  range: Range;
+  message: string;
 }
@@ -35,6 +35,8 @@ import type {
   DocumentSymbolParams,
   WorkspaceSymbolParams,
   RenameParams,
+  PrepareRenameParams,
+  PrepareRenameResult,
   WorkspaceEdit,
 
   // Response types
@@ -42,6 +44,7 @@ import type {
   CallHierarchyItem,
   CallHierarchyIncomingCall,
   CallHierarchyOutgoingCall,
+  TypeHierarchyItem,
 
   // Notification parameter types
   DidOpenTextDocumentParams,
@@ -54,13 +57,55 @@ import type {
   DocumentDiagnosticParams,
   DocumentDiagnosticReport,
   RelatedFullDocumentDiagnosticReport,
+  WorkspaceDiagnosticParams,
+  WorkspaceDiagnosticReport,
+
+  // Code action types
+  CodeAction,
+  CodeActionParams,
+  Command,
+  Diagnostic,
+
+  // Code lens types
+  CodeLens,
+  CodeLensParams,
+  ExecuteCommandParams,
+
+  // Formatting types
+  DocumentFormattingParams,
+  DocumentRangeFormattingParams,
+  DocumentOnTypeFormattingParams,
+  FormattingOptions,
+  TextEdit,
+
+  // Folding range types
+  FoldingRange,
+  FoldingRangeParams,
+
+  // Selection range types
+  SelectionRange,
+  SelectionRangeParams,
+
+  // Document highlight types
+  DocumentHighlight,
+  DocumentHighlightParams,
+
+  // Inlay hint types
+  InlayHint,
+  InlayHintParams,
 
   // Semantic tokens types
   SemanticTokensParams,
   SemanticTokens,
+  SemanticTokensDelta,
+  SemanticTokensDeltaParams,
+  SemanticTokensEdit,
+  SemanticTokensLegend,
+  ServerCapabilities,
 } from 'vscode-languageserver-protocol';
 import logger from '../utils/logger.js';
 import type { LspClient } from '../types.js';
+import { splitLines } from '../utils/text.js';
 
 type SendLspRequest = <TResult, TParams = unknown>(
   method: string,
@@ -88,11 +133,14 @@ export type {
   DocumentSymbolParams,
   WorkspaceSymbolParams,
   RenameParams,
+  PrepareRenameParams,
+  PrepareRenameResult,
   WorkspaceEdit,
   Hover,
   CallHierarchyItem,
   CallHierarchyIncomingCall,
   CallHierarchyOutgoingCall,
+  TypeHierarchyItem,
   DidOpenTextDocumentParams,
   DidCloseTextDocumentParams,
   DidChangeTextDocumentParams,
@@ -102,6 +150,28 @@ export type {
   DocumentDiagnosticParams,
   DocumentDiagnosticReport,
   RelatedFullDocumentDiagnosticReport,
+  WorkspaceDiagnosticParams,
+  WorkspaceDiagnosticReport,
+  CodeAction,
+  CodeActionParams,
+  Command,
+  Diagnostic,
+  CodeLens,
+  CodeLensParams,
+  ExecuteCommandParams,
+  DocumentFormattingParams,
+  DocumentRangeFormattingParams,
+  DocumentOnTypeFormattingParams,
+  FormattingOptions,
+  TextEdit,
+  FoldingRange,
+  FoldingRangeParams,
+  SelectionRange,
+  SelectionRangeParams,
+  DocumentHighlight,
+  DocumentHighlightParams,
+  InlayHint,
+  InlayHintParams,
   SemanticTokensParams,
   SemanticTokens,
 };
@@ -134,22 +204,44 @@ interface DisplayLocation {
   range: DisplayRange;
 }
 
+/**
+ * How an occurrence uses a symbol, from document highlights. Servers that
+ * don't tell reads from writes report text.
+ */
+export type ReferenceAccess = 'read' | 'write' | 'text';
+
 /** Symbol reference result */
 export interface SymbolReference {
   uri: string;
   range: Range; // 0-based LSP range
   line: number; // 1-based display line
   character: number; // 1-based display character
+  access?: ReferenceAccess;
+}
+
+/** Occurrence of a symbol within one document */
+export interface HighlightEntry {
+  range: Range; // 0-based LSP range
+  access: ReferenceAccess;
 }
 
 /** Symbol inspection result */
 export interface SymbolInspection {
   hover: Hover | null;
   definition: Location | Location[] | null;
+  // Differs from the definition in C, C++ and C# headers or partial members
+  declaration: Location | Location[] | null;
   typeDefinition: Location | Location[] | null;
   implementation: Location | Location[] | null;
 }
 
+// Definition-style requests answered with locations
+export type LocationRequestMethod =
+  | 'textDocument/definition'
+  | 'textDocument/declaration'
+  | 'textDocument/typeDefinition'
+  | 'textDocument/implementation';
+
 export type CallHierarchyDirection = 'incoming' | 'outgoing' | 'both';
 
 export interface CallHierarchyTarget {
@@ -158,11 +250,105 @@ export interface CallHierarchyTarget {
   // direction. An empty array means it was fetched and no calls were found.
   incomingCalls: CallHierarchyIncomingCall[] | null;
   outgoingCalls: CallHierarchyOutgoingCall[] | null;
+  // Calls followed past the first level, when the depth is above 1
+  incomingTree?: CallTreeNode[];
+  outgoingTree?: CallTreeNode[];
+}
+
+export interface CallTreeNode {
+  item: CallHierarchyItem;
+  // Where the caller calls the callee, in the caller's file
+  callSites: Range[];
+  // Null when not expanded: at the depth limit, already shown elsewhere in
+  // the tree, or past the exploration budget
+  children: CallTreeNode[] | null;
+}
+
+export interface CallHierarchyOptions {
+  depth: number;
+  maxExploredItems: number;
 }
 
 export interface CallHierarchyResult {
   direction: CallHierarchyDirection;
   targets: CallHierarchyTarget[];
+  depth: number;
+  // The exploration budget ran out before the depth limit was reached
+  limitReached: boolean;
+}
+
+export type TypeHierarchyDirection = 'supertypes' | 'subtypes' | 'both';
+
+export interface TypeTreeNode {
+  item: TypeHierarchyItem;
+  // Null when not expanded: at the depth limit, already shown elsewhere in
+  // the tree, or past the exploration budget
+  children: TypeTreeNode[] | null;
+}
+
+export interface TypeHierarchyTarget {
+  item: TypeHierarchyItem;
+  // Null means this side wasn't requested
+  supertypes: TypeTreeNode[] | null;
+  subtypes: TypeTreeNode[] | null;
+}
+
+export interface TypeHierarchyOptions {
+  depth: number;
+  maxExploredItems: number;
+}
+
+export interface TypeHierarchyResult {
+  direction: TypeHierarchyDirection;
+  targets: TypeHierarchyTarget[];
+  depth: number;
+  // The exploration budget ran out before the depth limit was reached
+  limitReached: boolean;
+}
+
+export interface CallPathStep {
+  item: CallHierarchyItem;
+  // Where the previous step calls this item; empty for the first step
+  callSites: Range[];
+}
+
+export interface CallPathOptions {
+  maxDepth: number;
+  maxPaths: number;
+  maxExploredItems: number;
+}
+
+export interface CallPathResult {
+  sources: CallHierarchyItem[];
+  targets: CallHierarchyItem[];
+  paths: CallPathStep[][];
+  maxDepth: number;
+  exploredItems: number;
+  // The exploration budget ran out before the depth limit was reached
+  limitReached: boolean;
+}
+
+export interface TestCaller {
+  item: CallHierarchyItem;
+  // Functions between the test and the symbol, nearest to the test first
+  via: CallHierarchyItem[];
+  hops: number;
+}
+
+export interface RelatedTestsOptions {
+  maxDepth: number;
+  maxExploredItems: number;
+  isTestFile: (uri: string) => boolean;
+}
+
+export interface RelatedTestsResult {
+  targets: CallHierarchyItem[];
+  // References to the symbol from test files
+  references: Location[];
+  callers: TestCaller[];
+  maxDepth: number;
+  exploredItems: number;
+  limitReached: boolean;
 }
 
 /** Completion result item */
@@ -178,6 +364,14 @@ export interface CompletionResult {
     range: DisplayRange;
     newText: string;
   };
+  // Other edits accepting the item makes, such as adding an import
+  additionalTextEdits?: Array<{
+    range: DisplayRange;
+    newText: string;
+  }>;
+  preselect?: boolean;
+  // Filled in through completionItem/resolve
+  resolved?: boolean;
 }
 
 /** Symbol search result */
@@ -203,10 +397,44 @@ export interface RenameResult {
   [fileUri: string]: FileChange[];
 }
 
+/**
+ * Whether a code action response entry is a bare command rather than a
+ * code action literal
+ */
+export function isCodeActionCommand(
+  action: CodeAction | Command
+): action is Command {
+  return typeof action.command === 'string';
+}
+
+/** Text edits a code action makes, collected before they are written */
+export interface CodeActionEdits {
+  changes: RenameResult;
+  // File creations, renames and deletions, which aren't applied
+  skippedOperations: number;
+  // Commands that ran on the server as part of the action
+  executedCommands: string[];
+}
+
+/** Code lenses in a document and the commands the server can run */
+export interface CodeLensResult {
+  lenses: CodeLens[];
+  // Commands from executeCommandProvider; others are handled by editors
+  serverCommands: string[];
+}
+
+/** Edits a command sent back while it ran, and what it returned */
+export interface CommandExecution extends CodeActionEdits {
+  result: unknown;
+}
+
 /** Log message result */
 export interface LogMessageResult {
   type: number;
   message: string;
+  // Sent through window/showMessage for display rather than only logged
+  shown?: boolean;
+  receivedAt?: Date;
 }
 
 /** Semantic token at a specific position */
@@ -268,6 +496,8 @@ export interface FlattenedSymbol {
   kind: SymbolKindValue;
   range: Range;
   containerName?: string;
+  // Ancestor names from the outermost container down to the direct parent
+  containerPath?: string[];
   uri?: string;
   selectionRange?: Range;
   detail?: string;
@@ -298,7 +528,10 @@ export async function getDocumentSymbols(
         name: symbol.name,
         kind: symbol.kind,
         range: symbol.location.range,
-        ...(symbol.containerName && { containerName: symbol.containerName }),
+        ...(symbol.containerName && {
+          containerName: symbol.containerName,
+          containerPath: [symbol.containerName],
+        }),
         uri: symbol.location.uri,
         ...(symbol.deprecated && { deprecated: symbol.deprecated }),
       })
@@ -309,8 +542,9 @@ export async function getDocumentSymbols(
 
     function flattenDocumentSymbols(
       symbols: DocumentSymbol[],
-      container?: string
+      containerPath: string[] = []
     ): void {
+      const container = containerPath[containerPath.length - 1];
       for (const symbol of symbols) {
         results.push({
           name: symbol.name,
@@ -320,12 +554,15 @@ export async function getDocumentSymbols(
             selectionRange: symbol.selectionRange,
           }),
           ...(symbol.detail && { detail: symbol.detail }),
-          ...(container && { containerName: container }),
+          ...(container && { containerName: container, containerPath }),
           ...(symbol.deprecated && { deprecated: symbol.deprecated }),
         });
 
         if (symbol.children) {
-          flattenDocumentSymbols(symbol.children, symbol.name);
+          flattenDocumentSymbols(symbol.children, [
+            ...containerPath,
+            symbol.name,
+          ]);
         }
       }
     }
@@ -338,14 +575,14 @@ export async function getDocumentSymbols(
 /**
  * Decodes semantic tokens from the LSP relative format into absolute positions
  */
-function decodeSemanticTokens(
+export function decodeSemanticTokens(
   data: number[],
   tokenTypes: string[],
   tokenModifiers: string[],
   fileContent: string
 ): SemanticToken[] {
   const tokens: SemanticToken[] = [];
-  const lines = fileContent.split('\n');
+  const lines = splitLines(fileContent);
 
   let currentLine = 0;
   let currentChar = 0;
@@ -432,8 +669,132 @@ export function findSemanticTokenAtPosition(
   return null;
 }
 
+// Used when a server advertises semantic tokens without a legend
+const FALLBACK_SEMANTIC_TOKENS_LEGEND: SemanticTokensLegend = {
+  tokenTypes: [
+    'class',
+    'enum',
+    'interface',
+    'namespace',
+    'typeParameter',
+    'type',
+    'parameter',
+    'variable',
+    'enumMember',
+    'property',
+    'function',
+    'member',
+  ],
+  tokenModifiers: [
+    'declaration',
+    'static',
+    'async',
+    'readonly',
+    'defaultLibrary',
+    'local',
+  ],
+};
+
 /**
- * Gets semantic tokens for a document and caches token type/modifier legends
+ * The token type and modifier names a server's token indices refer to
+ */
+export function getSemanticTokensLegend(
+  capabilities: ServerCapabilities | undefined
+): SemanticTokensLegend {
+  return (
+    capabilities?.semanticTokensProvider?.legend ??
+    FALLBACK_SEMANTIC_TOKENS_LEGEND
+  );
+}
+
+/** Last full token data for a document, the base for delta requests */
+export interface SemanticTokensCacheEntry {
+  resultId: string;
+  data: number[];
+}
+
+/**
+ * Applies a semantic tokens delta to the previous token data
+ */
+function applySemanticTokensEdits(
+  data: number[],
+  edits: SemanticTokensEdit[]
+): number[] {
+  const result = [...data];
+  // Edits refer to offsets in the previous data, so apply the last first
+  const sorted = [...edits].sort((left, right) => right.start - left.start);
+  for (const edit of sorted) {
+    result.splice(edit.start, edit.deleteCount, ...(edit.data ?? []));
+  }
+  return result;
+}
+
+function supportsSemanticTokensDelta(
+  capabilities: ServerCapabilities | undefined
+): boolean {
+  const full = capabilities?.semanticTokensProvider?.full;
+  return typeof full === 'object' && full.delta === true;
+}
+
+/**
+ * Requests the raw token data for a whole document. With a cache and a
+ * server that supports deltas, only the changes since the cached result
+ * are transferred; any delta failure falls back to a full request.
+ */
+export async function requestSemanticTokenData(
+  request: SendLspRequest,
+  uri: string,
+  capabilities: ServerCapabilities | undefined,
+  cache?: Map<string, SemanticTokensCacheEntry>
+): Promise<number[] | null> {
+  const previous = cache?.get(uri);
+  const remember = (response: { resultId?: string }, data: number[]) => {
+    cache?.delete(uri);
+    if (cache && response.resultId) {
+      cache.set(uri, { resultId: response.resultId, data });
+    }
+  };
+
+  if (previous && supportsSemanticTokensDelta(capabilities)) {
+    try {
+      const response = await request<
+        SemanticTokens | SemanticTokensDelta | null,
+        SemanticTokensDeltaParams
+      >('textDocument/semanticTokens/full/delta', {
+        textDocument: { uri },
+        previousResultId: previous.resultId,
+      });
+      if (response) {
+        // Servers may answer a delta request with full tokens
+        const data =
+          'edits' in response
+            ? applySemanticTokensEdits(previous.data, response.edits)
+            : response.data;
+        remember(response, data);
+        return data;
+      }
+    } catch (error) {
+      logger.debug('Semantic tokens delta failed, requesting full tokens', {
+        uri,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }
+
+  const response = await request<SemanticTokens | null, SemanticTokensParams>(
+    'textDocument/semanticTokens/full',
+    { textDocument: { uri } }
+  );
+  if (!response) {
+    cache?.delete(uri);
+    return null;
+  }
+  remember(response, response.data);
+  return response.data;
+}
+
+/**
+ * Gets semantic tokens for a document, decoded with the server's legend
  */
 export async function getSemanticTokens(
   client: LspClient,
@@ -446,51 +807,23 @@ export async function getSemanticTokens(
   try {
     logger.info(`getSemanticTokens called for ${uri}`);
 
-    const params: SemanticTokensParams = {
-      textDocument: { uri },
-    };
-
-    const response: SemanticTokens = await client.connection.sendRequest(
-      'textDocument/semanticTokens/full',
-      params
+    const data = await requestSemanticTokenData(
+      async (method, params) =>
+        await client.connection.sendRequest(method, params),
+      uri,
+      client.serverCapabilities
     );
 
-    if (!response?.data || response.data.length === 0) {
+    if (!data || data.length === 0) {
       logger.info('No semantic tokens data received');
       return null;
     }
 
-    // Get the legend from the server capabilities (should be cached from initialization)
-    // For now, we'll use TypeScript's common token types and modifiers
-    // This should ideally come from the server's initialize response
-    const tokenTypes = [
-      'class',
-      'enum',
-      'interface',
-      'namespace',
-      'typeParameter',
-      'type',
-      'parameter',
-      'variable',
-      'enumMember',
-      'property',
-      'function',
-      'member',
-    ];
-
-    const tokenModifiers = [
-      'declaration',
-      'static',
-      'async',
-      'readonly',
-      'defaultLibrary',
-      'local',
-    ];
-
+    const legend = getSemanticTokensLegend(client.serverCapabilities);
     const decodedTokens = decodeSemanticTokens(
-      response.data,
-      tokenTypes,
-      tokenModifiers,
+      data,
+      legend.tokenTypes,
+      legend.tokenModifiers,
       fileContent
     );
 
@@ -498,7 +831,7 @@ export async function getSemanticTokens(
 
     return {
       tokens: decodedTokens,
-      legend: { tokenTypes, tokenModifiers },
+      legend,
     };
   } catch (error) {
     logger.error(
@@ -0,0 +1,55 @@
+/**
+ * C/C++ Workspace Detection Utilities
+ * Finds the compile_commands.json clangd reads compile flags from
+ */
+
+import fs from 'fs';
+import path from 'path';
+import { globSync } from 'glob';
+import logger from './logger.js';
+
+// Where CMake, Meson and Bazel setups usually write the compilation
+// database, relative to the workspace
+const COMPILE_COMMANDS_PATTERNS = [
+  'compile_commands.json',
+  '{build,out,builddir,_build}/compile_commands.json',
+  '{build,out}/*/compile_commands.json',
+  'cmake-build-*/compile_commands.json',
+];
+
+/**
+ * Directory holding the workspace's compile_commands.json. With several
+ * build directories the most recently generated database wins.
+ */
+export function findCompileCommandsDir(workspacePath: string): string | null {
+  try {
+    const root = path.resolve(workspacePath);
+    const databases = globSync(COMPILE_COMMANDS_PATTERNS, {
+      cwd: root,
+      absolute: true,
+    });
+
+    const newest = databases
+      .map((file) => ({ file, modifiedAt: fs.statSync(file).mtimeMs }))
+      .sort(
+        (a, b) => b.modifiedAt - a.modifiedAt || a.file.localeCompare(b.file)
+      )[0];
+    if (!newest) {
+      logger.debug('No compile_commands.json found', { workspacePath });
+      return null;
+    }
+
+    const directory = path.dirname(newest.file);
+    logger.info('Found compilation database', {
+      directory,
+      candidates: databases.length,
+    });
+    return directory;
+  } catch (error) {
+    logger.error('Failed to look for compile_commands.json', {
+      workspacePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return null;
+  }
+}
@@ -2,7 +2,7 @@
  * Command-line argument parsing utilities using yargs
  */
 
-import yargs from 'yargs';
+import yargs, { type Argv } from 'yargs';
 import { hideBin } from 'yargs/helpers';
 import * as fs from 'fs';
 import * as path from 'path';
@@ -11,9 +11,51 @@ import * as yaml from 'js-yaml';
 import logger from './logger.js';
 import { listAvailableLsps, loadLspConfig } from '../config/lsp-config.js';
 import { getAppPaths } from './app-paths.js';
+import {
+  COLOR_MODES,
+  highlightJson,
+  highlightYaml,
+  shouldUseColor,
+  type ColorMode,
+} from './highlight.js';
+import { PATH_STYLES, type PathStyle } from './path-format.js';
+import { REPORT_FORMATS, type ReportFormat } from '../map/workspace-scan.js';
+import { ENTRY_POINT_KINDS, type EntryPointKind } from '../map/dead-code.js';
+import { USAGE_FORMATS, type UsageFormat } from '../map/usage-report.js';
+import { parseSymbolQuery } from './symbol-query.js';
+import {
+  parseFilePosition,
+  type LookupCommand,
+} from '../map/symbol-lookup.js';
+import {
+  REPLAY_TIMINGS,
+  type ReplayTiming,
+} from '../lsp/session-recording.js';
+import {
+  DEFAULT_HTTP_HOST,
+  DEFAULT_HTTP_PORT,
+  TRANSPORTS,
+  type Transport,
+} from '../main/http-transport.js';
 
 // Command types
-type CommandType = 'start' | 'run' | 'config' | null;
+type CommandType =
+  | 'start'
+  | 'run'
+  | 'config'
+  | 'map'
+  | 'dead-code'
+  | 'api'
+  | 'usage'
+  | 'cycles'
+  | 'doc-coverage'
+  | 'query'
+  | LookupCommand
+  | 'batch'
+  | 'gateway'
+  | 'export'
+  | 'replay'
+  | null;
 
 interface BaseCliArgs {
   command: CommandType;
@@ -28,6 +70,16 @@ export interface StartCommandArgs extends BaseCliArgs {
   loglevel?: string;
   configPath?: string;
   console?: boolean;
+  paths?: PathStyle;
+  // Directory to record language server sessions into
+  record?: string;
+  // Directory to replay recorded sessions from instead of real servers
+  replay?: string;
+  replayTiming?: ReplayTiming;
+  transport?: Transport;
+  host?: string;
+  port?: number;
+  socket?: string;
 }
 
 export interface RunCommandArgs extends BaseCliArgs {
@@ -35,12 +87,192 @@ export interface RunCommandArgs extends BaseCliArgs {
   workspace?: string;
   loglevel?: string;
   console?: boolean;
+  paths?: PathStyle;
+  transport?: Transport;
+  host?: string;
+  port?: number;
+  socket?: string;
   directCommand: {
     commandName: string;
     commandArgs: string[];
   };
 }
 
+export interface MapCommandArgs extends BaseCliArgs {
+  command: 'map';
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+  paths?: PathStyle;
+  console?: boolean;
+  format: ReportFormat;
+  color?: ColorMode;
+  depth: number;
+  maxFiles: number;
+}
+
+export interface DeadCodeCommandArgs extends BaseCliArgs {
+  command: 'dead-code';
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+  paths?: PathStyle;
+  console?: boolean;
+  format: ReportFormat;
+  entryPoints: EntryPointKind[];
+  entrySymbols: string[];
+  maxFiles: number;
+}
+
+export interface UsageCommandArgs extends BaseCliArgs {
+  command: 'usage';
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+  paths?: PathStyle;
+  console?: boolean;
+  format: UsageFormat;
+  depth: number;
+  since: string;
+  top: number;
+  maxFiles: number;
+  maxSymbols: number;
+}
+
+export interface CyclesCommandArgs extends BaseCliArgs {
+  command: 'cycles';
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+  paths?: PathStyle;
+  console?: boolean;
+  format: ReportFormat;
+  // Omitted for a file-level graph
+  depth?: number;
+  maxFiles: number;
+}
+
+export interface DocCoverageCommandArgs extends BaseCliArgs {
+  command: 'doc-coverage';
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+  paths?: PathStyle;
+  console?: boolean;
+  format: ReportFormat;
+  depth: number;
+  // Percentage below which the command exits non-zero
+  failUnder?: number;
+  maxFiles: number;
+}
+
+export interface QueryCommandArgs extends BaseCliArgs {
+  command: 'query';
+  // Free text and filters, e.g. `kind:function vis:pub refs:>10`
+  query: string;
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+  paths?: PathStyle;
+  console?: boolean;
+  format: ReportFormat;
+  limit: number;
+}
+
+export interface LookupCommandArgs extends BaseCliArgs {
+  command: LookupCommand;
+  // Symbol name for find, file for outline, file:line:character otherwise
+  target: string;
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+  paths?: PathStyle;
+  console?: boolean;
+  format: ReportFormat;
+  color?: ColorMode;
+  limit: number;
+}
+
+export interface BatchCommandArgs extends BaseCliArgs {
+  command: 'batch';
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+  paths?: PathStyle;
+  console?: boolean;
+  // Applies to queries that don't set their own limit
+  limit: number;
+}
+
+export interface GatewayCommandArgs extends BaseCliArgs {
+  command: 'gateway';
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+}
+
+export interface ApiSnapshotArgs {
+  subcommand: 'snapshot';
+  output?: string;
+  release?: string;
+}
+
+export interface ApiCheckArgs {
+  subcommand: 'check';
+  // Snapshot file or git revision
+  baseline: string;
+  baselineVersion?: string;
+  release?: string;
+  format: ReportFormat;
+}
+
+export interface ApiCommandArgs extends BaseCliArgs {
+  command: 'api';
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+  paths?: PathStyle;
+  console?: boolean;
+  maxFiles: number;
+  subcommandArgs: ApiSnapshotArgs | ApiCheckArgs;
+}
+
+export interface ExportChunksArgs {
+  subcommand: 'chunks';
+  output?: string;
+  maxChars: number;
+  includeTests: boolean;
+  docs: boolean;
+}
+
+export interface ExportCommandArgs extends BaseCliArgs {
+  command: 'export';
+  workspace?: string;
+  lsp?: string;
+  loglevel?: string;
+  configPath?: string;
+  paths?: PathStyle;
+  console?: boolean;
+  maxFiles: number;
+  subcommandArgs: ExportChunksArgs;
+}
+
+export interface ReplayCommandArgs extends BaseCliArgs {
+  command: 'replay';
+  recording: string;
+  timing: ReplayTiming;
+}
+
 interface ConfigInitArgs {
   subcommand: 'init';
   global?: boolean;
@@ -54,6 +286,7 @@ interface ConfigShowArgs {
   configPath?: string;
   workspace?: string;
   format?: 'yaml' | 'json';
+  color?: ColorMode;
 }
 
 interface ConfigPathArgs {
@@ -73,8 +306,114 @@ type CliArgs =
   | BaseCliArgs
   | StartCommandArgs
   | RunCommandArgs
+  | MapCommandArgs
+  | DeadCodeCommandArgs
+  | ApiCommandArgs
+  | UsageCommandArgs
+  | CyclesCommandArgs
+  | DocCoverageCommandArgs
+  | QueryCommandArgs
+  | LookupCommandArgs
+  | BatchCommandArgs
+  | GatewayCommandArgs
+  | ExportCommandArgs
+  | ReplayCommandArgs
   | ConfigCommandArgs;
 
+/**
+ * Options shared by the find, refs, def and outline commands, with a check
+ * that the workspace and any file being looked up exist
+ */
+function withLookupOptions<T>(
+  yargs: Argv<T>,
+  target: 'name' | 'file' | 'position'
+) {
+  return yargs
+    .option('config', {
+      alias: 'c',
+      type: 'string',
+      describe: 'Path to configuration file',
+      requiresArg: true,
+    })
+    .option('lsp', {
+      alias: 'l',
+      type: 'string',
+      describe: 'Explicitly specify name of LSP to use from config',
+      requiresArg: true,
+    })
+    .option('workspace', {
+      alias: 'w',
+      type: 'string',
+      describe: 'Workspace directory (default: current directory)',
+      requiresArg: true,
+    })
+    .option('paths', {
+      type: 'string',
+      describe:
+        'How file paths appear in reports (default: relative to the workspace)',
+      choices: PATH_STYLES,
+      requiresArg: true,
+    })
+    .option('format', {
+      type: 'string',
+      describe: 'Output format',
+      choices: REPORT_FORMATS,
+      default: 'markdown',
+    })
+    .option('color', {
+      type: 'string',
+      describe:
+        'Syntax highlighting (auto disables it when stdout is not a TTY)',
+      choices: COLOR_MODES,
+      default: 'auto',
+    })
+    .option('limit', {
+      type: 'number',
+      describe: 'Maximum number of locations to print',
+      default: 100,
+    })
+    .option('loglevel', {
+      type: 'string',
+      describe: 'LSP server log level',
+      choices: ['debug', 'info', 'warn', 'error'],
+      requiresArg: true,
+    })
+    .option('console', {
+      type: 'boolean',
+      describe:
+        'Output logs to console instead of log files (for troubleshooting only)',
+      default: false,
+    })
+    .strictOptions()
+    .check((argv) => {
+      if (argv.workspace) {
+        const workspacePath = path.resolve(argv.workspace);
+        if (
+          !fs.existsSync(workspacePath) ||
+          !fs.statSync(workspacePath).isDirectory()
+        ) {
+          throw new Error(
+            `Workspace directory does not exist: ${argv.workspace}`
+          );
+        }
+      }
+      if (!Number.isInteger(argv.limit) || argv.limit < 1) {
+        throw new Error('--limit must be a positive integer');
+      }
+      if (target === 'name') {
+        return true;
+      }
+      const value = String((argv as { target?: unknown }).target);
+      // Throws when the position isn't file:line:character
+      const file =
+        target === 'position' ? parseFilePosition(value).file : value;
+      if (!fs.existsSync(file)) {
+        throw new Error(`File does not exist: ${file}`);
+      }
+      return true;
+    });
+}
+
 /**
  * Parse command-line arguments using yargs with subcommands
  */
@@ -112,6 +451,37 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
           choices: ['debug', 'info', 'warn', 'error'],
           requiresArg: true,
         })
+        .option('paths', {
+          type: 'string',
+          describe:
+            'How file paths appear in results (default: relative to the workspace)',
+          choices: PATH_STYLES,
+          requiresArg: true,
+        })
+        .option('transport', {
+          type: 'string',
+          describe: 'How MCP clients connect (default: stdio)',
+          choices: TRANSPORTS,
+          requiresArg: true,
+        })
+        .option('host', {
+          type: 'string',
+          describe:
+            `Address the HTTP transport listens on (default: ${DEFAULT_HTTP_HOST})`,
+          requiresArg: true,
+        })
+        .option('port', {
+          type: 'number',
+          describe:
+            `Port the HTTP transport listens on (default: ${DEFAULT_HTTP_PORT})`,
+          requiresArg: true,
+        })
+        .option('socket', {
+          type: 'string',
+          describe:
+            'Unix socket or Windows named pipe to listen on instead of a port (implies --transport http)',
+          requiresArg: true,
+        })
         .option('console', {
           type: 'boolean',
           describe:
@@ -150,8 +520,1012 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
         });
     })
     .command(
-      'start',
-      'Start MCP server with Language Server auto-detection using configuration',
+      'start',
+      'Start MCP server with Language Server auto-detection using configuration',
+      (yargs) => {
+        return yargs
+          .option('config', {
+            alias: 'c',
+            type: 'string',
+            describe: 'Path to configuration file',
+            requiresArg: true,
+          })
+          .option('lsp', {
+            alias: 'l',
+            type: 'string',
+            describe: 'Explicitly specify name of LSP to use from config',
+            requiresArg: true,
+          })
+          .option('workspace', {
+            alias: 'w',
+            type: 'string',
+            describe: 'Workspace directory (default: current directory)',
+            requiresArg: true,
+          })
+          .option('loglevel', {
+            type: 'string',
+            describe: 'LSP server log level',
+            choices: ['debug', 'info', 'warn', 'error'],
+            requiresArg: true,
+          })
+          .option('paths', {
+            type: 'string',
+            describe:
+              'How file paths appear in results (default: relative to the workspace)',
+            choices: PATH_STYLES,
+            requiresArg: true,
+          })
+          .option('record', {
+            type: 'string',
+            describe:
+              'Record each language server session to <dir>/<profile>.jsonl',
+            requiresArg: true,
+            conflicts: 'replay',
+          })
+          .option('replay', {
+            type: 'string',
+            describe:
+              'Serve sessions recorded with --record instead of starting language servers',
+            requiresArg: true,
+          })
+          .option('replay-timing', {
+            type: 'string',
+            describe:
+              'Answer replayed requests instantly or with the recorded latency',
+            choices: REPLAY_TIMINGS,
+            requiresArg: true,
+          })
+          .option('transport', {
+            type: 'string',
+            describe: 'How MCP clients connect (default: stdio)',
+            choices: TRANSPORTS,
+            requiresArg: true,
+          })
+          .option('host', {
+            type: 'string',
+            describe:
+              `Address the HTTP transport listens on (default: ${DEFAULT_HTTP_HOST})`,
+            requiresArg: true,
+          })
+          .option('port', {
+            type: 'number',
+            describe:
+              `Port the HTTP transport listens on (default: ${DEFAULT_HTTP_PORT})`,
+            requiresArg: true,
+          })
+          .option('socket', {
+            type: 'string',
+            describe:
+              'Unix socket or Windows named pipe to listen on instead of a port (implies --transport http)',
+            requiresArg: true,
+          })
+          .option('console', {
+            type: 'boolean',
+            describe:
+              'Output logs to console instead of log files (for troubleshooting only - do not use when running as MCP server)',
+            default: false,
+          })
+          .example('$0 start', 'Start with auto-detection')
+          .example(
+            '$0 start --lsp typescript --workspace ./my-project',
+            'Start with specific LSP'
+          )
+          .example(
+            '$0 start --config ./language-servers.yaml',
+            'Start with custom configuration'
+          )
+          .example(
+            '$0 start --record ./recordings',
+            'Record language server traffic for hermetic tests'
+          )
+          .example(
+            '$0 start --replay ./recordings',
+            'Answer from recorded sessions without language servers'
+          )
+          .example(
+            '$0 start --transport http --port 3000',
+            'Serve MCP clients over HTTP at http://127.0.0.1:3000/mcp'
+          )
+          .strictOptions() // Strict validation for start command
+          .check((argv) => {
+            if (argv.workspace) {
+              const workspacePath = path.resolve(argv.workspace);
+              if (!fs.existsSync(workspacePath)) {
+                throw new Error(
+                  `Workspace directory does not exist: ${argv.workspace}`
+                );
+              }
+              if (!fs.statSync(workspacePath).isDirectory()) {
+                throw new Error(
+                  `Workspace path is not a directory: ${argv.workspace}`
+                );
+              }
+            }
+            if (argv.replay) {
+              const replayPath = path.resolve(argv.replay);
+              if (
+                !fs.existsSync(replayPath) ||
+                !fs.statSync(replayPath).isDirectory()
+              ) {
+                throw new Error(
+                  `Replay directory does not exist: ${argv.replay}`
+                );
+              }
+            }
+            if (argv.config) {
+              const configPath = path.resolve(argv.config);
+              if (!fs.existsSync(configPath)) {
+                throw new Error(`Config file does not exist: ${argv.config}`);
+              }
+              if (!fs.statSync(configPath).isFile()) {
+                throw new Error(`Config path is not a file: ${argv.config}`);
+              }
+            }
+            if (argv.lsp) {
+              const availableLsps = listAvailableLsps(argv.config);
+              if (availableLsps.length === 0) {
+                throw new Error(
+                  `No LSP servers configured. Run "symbols config init" to create a configuration file.`
+                );
+              }
+              if (!availableLsps.includes(argv.lsp)) {
+                throw new Error(
+                  `Unknown LSP server: ${argv.lsp}\nAvailable: ${availableLsps.join(', ')}`
+                );
+              }
+            }
+            return true;
+          });
+      }
+    )
+    .command(
+      'map',
+      'Print an architecture map of the workspace: components, public symbols and dependencies',
+      (yargs) => {
+        return yargs
+          .option('config', {
+            alias: 'c',
+            type: 'string',
+            describe: 'Path to configuration file',
+            requiresArg: true,
+          })
+          .option('lsp', {
+            alias: 'l',
+            type: 'string',
+            describe: 'Explicitly specify name of LSP to use from config',
+            requiresArg: true,
+          })
+          .option('workspace', {
+            alias: 'w',
+            type: 'string',
+            describe: 'Workspace directory (default: current directory)',
+            requiresArg: true,
+          })
+          .option('paths', {
+            type: 'string',
+            describe:
+              'How file paths appear in reports (default: relative to the workspace)',
+            choices: PATH_STYLES,
+            requiresArg: true,
+          })
+          .option('format', {
+            type: 'string',
+            describe: 'Output format',
+            choices: REPORT_FORMATS,
+            default: 'markdown',
+          })
+          .option('color', {
+            type: 'string',
+            describe:
+              'Syntax highlighting (auto disables it when stdout is not a TTY)',
+            choices: COLOR_MODES,
+            default: 'auto',
+          })
+          .option('depth', {
+            type: 'number',
+            describe: 'Directory levels that make up a component',
+            default: 1,
+          })
+          .option('max-files', {
+            type: 'number',
+            describe: 'Maximum number of source files to analyze',
+            default: 500,
+          })
+          .option('loglevel', {
+            type: 'string',
+            describe: 'LSP server log level',
+            choices: ['debug', 'info', 'warn', 'error'],
+            requiresArg: true,
+          })
+          .option('console', {
+            type: 'boolean',
+            describe:
+              'Output logs to console instead of log files (for troubleshooting only)',
+            default: false,
+          })
+          .example('$0 map', 'Print a markdown map of the current directory')
+          .example(
+            '$0 map --format json --depth 2',
+            'Print a JSON map grouping files by two directory levels'
+          )
+          .example(
+            '$0 map --color never',
+            'Print the map without syntax highlighting'
+          )
+          .strictOptions()
+          .check((argv) => {
+            if (argv.workspace) {
+              const workspacePath = path.resolve(argv.workspace);
+              if (
+                !fs.existsSync(workspacePath) ||
+                !fs.statSync(workspacePath).isDirectory()
+              ) {
+                throw new Error(
+                  `Workspace directory does not exist: ${argv.workspace}`
+                );
+              }
+            }
+            if (!Number.isInteger(argv.depth) || argv.depth < 1) {
+              throw new Error('--depth must be a positive integer');
+            }
+            if (!Number.isInteger(argv['max-files']) || argv['max-files'] < 1) {
+              throw new Error('--max-files must be a positive integer');
+            }
+            return true;
+          });
+      }
+    )
+    .command(
+      'dead-code',
+      'Report symbols unreachable from entry points through the reference graph',
+      (yargs) => {
+        return yargs
+          .option('config', {
+            alias: 'c',
+            type: 'string',
+            describe: 'Path to configuration file',
+            requiresArg: true,
+          })
+          .option('lsp', {
+            alias: 'l',
+            type: 'string',
+            describe: 'Explicitly specify name of LSP to use from config',
+            requiresArg: true,
+          })
+          .option('workspace', {
+            alias: 'w',
+            type: 'string',
+            describe: 'Workspace directory (default: current directory)',
+            requiresArg: true,
+          })
+          .option('paths', {
+            type: 'string',
+            describe:
+              'How file paths appear in reports (default: relative to the workspace)',
+            choices: PATH_STYLES,
+            requiresArg: true,
+          })
+          .option('format', {
+            type: 'string',
+            describe: 'Output format',
+            choices: REPORT_FORMATS,
+            default: 'markdown',
+          })
+          .option('entry', {
+            type: 'string',
+            array: true,
+            describe: 'Kinds of symbols treated as entry points',
+            choices: ENTRY_POINT_KINDS,
+            default: [...ENTRY_POINT_KINDS],
+          })
+          .option('entry-symbol', {
+            type: 'string',
+            array: true,
+            describe: 'Additional symbol names treated as entry points',
+            default: [],
+          })
+          .option('max-files', {
+            type: 'number',
+            describe: 'Maximum number of source files to analyze',
+            default: 500,
+          })
+          .option('loglevel', {
+            type: 'string',
+            describe: 'LSP server log level',
+            choices: ['debug', 'info', 'warn', 'error'],
+            requiresArg: true,
+          })
+          .option('console', {
+            type: 'boolean',
+            describe:
+              'Output logs to console instead of log files (for troubleshooting only)',
+            default: false,
+          })
+          .example('$0 dead-code', 'Report unreachable symbols')
+          .example(
+            '$0 dead-code --entry main tests --entry-symbol handler',
+            'Treat only main, tests and handler as entry points, so unused exports are reported too'
+          )
+          .strictOptions()
+          .check((argv) => {
+            if (argv.workspace) {
+              const workspacePath = path.resolve(argv.workspace);
+              if (
+                !fs.existsSync(workspacePath) ||
+                !fs.statSync(workspacePath).isDirectory()
+              ) {
+                throw new Error(
+                  `Workspace directory does not exist: ${argv.workspace}`
+                );
+              }
+            }
+            if (!Number.isInteger(argv['max-files']) || argv['max-files'] < 1) {
+              throw new Error('--max-files must be a positive integer');
+            }
+            return true;
+          });
+      }
+    )
+    .command('api', 'Snapshot and check the public API surface', (yargs) => {
+      return yargs
+        .option('config', {
+          alias: 'c',
+          type: 'string',
+          describe: 'Path to configuration file',
+          requiresArg: true,
+        })
+        .option('lsp', {
+          alias: 'l',
+          type: 'string',
+          describe: 'Explicitly specify name of LSP to use from config',
+          requiresArg: true,
+        })
+        .option('workspace', {
+          alias: 'w',
+          type: 'string',
+          describe: 'Workspace directory (default: current directory)',
+          requiresArg: true,
+        })
+        .option('paths', {
+          type: 'string',
+          describe:
+            'How file paths appear in reports (default: relative to the workspace)',
+          choices: PATH_STYLES,
+          requiresArg: true,
+        })
+        .option('max-files', {
+          type: 'number',
+          describe: 'Maximum number of source files to analyze',
+          default: 500,
+        })
+        .option('loglevel', {
+          type: 'string',
+          describe: 'LSP server log level',
+          choices: ['debug', 'info', 'warn', 'error'],
+          requiresArg: true,
+        })
+        .option('console', {
+          type: 'boolean',
+          describe:
+            'Output logs to console instead of log files (for troubleshooting only)',
+          default: false,
+        })
+        .command('snapshot', 'Record the public API surface', (yargs) => {
+          return yargs
+            .option('output', {
+              alias: 'o',
+              type: 'string',
+              describe: 'Write the snapshot to a file instead of stdout',
+              requiresArg: true,
+            })
+            .option('release', {
+              type: 'string',
+              describe: 'Version recorded in the snapshot',
+              requiresArg: true,
+            })
+            .example(
+              '$0 api snapshot --release 1.4.0 -o api-snapshot.json',
+              'Record the API of release 1.4.0'
+            );
+        })
+        .command(
+          'check',
+          'Classify API changes against a baseline as major, minor or patch',
+          (yargs) => {
+            return yargs
+              .option('baseline', {
+                alias: 'b',
+                type: 'string',
+                describe: 'Baseline snapshot file or git revision',
+                demandOption: true,
+                requiresArg: true,
+              })
+              .option('baseline-version', {
+                type: 'string',
+                describe:
+                  'Version of the baseline (default: from the snapshot or a vX.Y.Z tag)',
+                requiresArg: true,
+              })
+              .option('release', {
+                type: 'string',
+                describe:
+                  'Version being released; the check fails when its bump is too small',
+                requiresArg: true,
+              })
+              .option('format', {
+                type: 'string',
+                describe: 'Output format',
+                choices: REPORT_FORMATS,
+                default: 'markdown',
+              })
+              .example(
+                '$0 api check --baseline v1.4.0 --release 1.4.1',
+                'Fail if the API changed more than a patch release allows'
+              )
+              .example(
+                '$0 api check --baseline api-snapshot.json',
+                'Report the bump required since a stored snapshot'
+              );
+          }
+        )
+        .demandCommand(1, 'Please specify an api subcommand')
+        .check((argv) => {
+          if (!Number.isInteger(argv['max-files']) || argv['max-files'] < 1) {
+            throw new Error('--max-files must be a positive integer');
+          }
+          return true;
+        });
+    })
+    .command('export', 'Export workspace symbols for other tools', (yargs) => {
+      return yargs
+        .option('config', {
+          alias: 'c',
+          type: 'string',
+          describe: 'Path to configuration file',
+          requiresArg: true,
+        })
+        .option('lsp', {
+          alias: 'l',
+          type: 'string',
+          describe: 'Explicitly specify name of LSP to use from config',
+          requiresArg: true,
+        })
+        .option('workspace', {
+          alias: 'w',
+          type: 'string',
+          describe: 'Workspace directory (default: current directory)',
+          requiresArg: true,
+        })
+        .option('paths', {
+          type: 'string',
+          describe:
+            'How file paths appear in reports (default: relative to the workspace)',
+          choices: PATH_STYLES,
+          requiresArg: true,
+        })
+        .option('max-files', {
+          type: 'number',
+          describe: 'Maximum number of source files to export',
+          default: 500,
+        })
+        .option('loglevel', {
+          type: 'string',
+          describe: 'LSP server log level',
+          choices: ['debug', 'info', 'warn', 'error'],
+          requiresArg: true,
+        })
+        .option('console', {
+          type: 'boolean',
+          describe:
+            'Output logs to console instead of log files (for troubleshooting only)',
+          default: false,
+        })
+        .command(
+          'chunks',
+          'Emit one JSONL record per symbol for embedding pipelines',
+          (yargs) => {
+            return yargs
+              .option('output', {
+                alias: 'o',
+                type: 'string',
+                describe: 'Write the records to a file instead of stdout',
+                requiresArg: true,
+              })
+              .option('max-chars', {
+                type: 'number',
+                describe:
+                  'Maximum body length of a record; longer bodies are split',
+                default: 4000,
+              })
+              .option('include-tests', {
+                type: 'boolean',
+                describe: 'Also export symbols from test files',
+                default: false,
+              })
+              .option('docs', {
+                type: 'boolean',
+                describe:
+                  'Look up doc comments through hover (--no-docs skips the extra requests)',
+                default: true,
+              })
+              .example(
+                '$0 export chunks -o chunks.jsonl',
+                'Write a chunk per function, method and type'
+              )
+              .example(
+                '$0 export chunks --max-chars 2000 --no-docs',
+                'Smaller chunks without hover lookups'
+              );
+          }
+        )
+        .demandCommand(1, 'Please specify an export subcommand')
+        .check((argv) => {
+          for (const name of ['max-files', 'max-chars']) {
+            const value = argv[name];
+            if (
+              value !== undefined &&
+              (typeof value !== 'number' ||
+                !Number.isInteger(value) ||
+                value < 1)
+            ) {
+              throw new Error(`--${name} must be a positive integer`);
+            }
+          }
+          return true;
+        });
+    })
+    .command(
+      'usage',
+      'Report most-referenced symbols, module fan-in/fan-out and hotspot files',
+      (yargs) => {
+        return yargs
+          .option('config', {
+            alias: 'c',
+            type: 'string',
+            describe: 'Path to configuration file',
+            requiresArg: true,
+          })
+          .option('lsp', {
+            alias: 'l',
+            type: 'string',
+            describe: 'Explicitly specify name of LSP to use from config',
+            requiresArg: true,
+          })
+          .option('workspace', {
+            alias: 'w',
+            type: 'string',
+            describe: 'Workspace directory (default: current directory)',
+            requiresArg: true,
+          })
+          .option('paths', {
+            type: 'string',
+            describe:
+              'How file paths appear in reports (default: relative to the workspace)',
+            choices: PATH_STYLES,
+            requiresArg: true,
+          })
+          .option('format', {
+            type: 'string',
+            describe: 'Output format',
+            choices: USAGE_FORMATS,
+            default: 'markdown',
+          })
+          .option('depth', {
+            type: 'number',
+            describe: 'Directory levels that make up a module',
+            default: 1,
+          })
+          .option('since', {
+            type: 'string',
+            describe: 'Start of the git churn window (any git date)',
+            default: '90 days ago',
+          })
+          .option('top', {
+            type: 'number',
+            describe: 'Symbols and hotspots listed in markdown output',
+            default: 20,
+          })
+          .option('max-files', {
+            type: 'number',
+            describe: 'Maximum number of source files to analyze',
+            default: 500,
+          })
+          .option('max-symbols', {
+            type: 'number',
+            describe: 'Maximum number of symbols to count references for',
+            default: 2000,
+          })
+          .option('loglevel', {
+            type: 'string',
+            describe: 'LSP server log level',
+            choices: ['debug', 'info', 'warn', 'error'],
+            requiresArg: true,
+          })
+          .option('console', {
+            type: 'boolean',
+            describe:
+              'Output logs to console instead of log files (for troubleshooting only)',
+            default: false,
+          })
+          .example('$0 usage', 'Print the usage report as markdown')
+          .example(
+            '$0 usage --format csv --since "30 days ago" > usage.csv',
+            'Export every row for a dashboard'
+          )
+          .strictOptions()
+          .check((argv) => {
+            if (argv.workspace) {
+              const workspacePath = path.resolve(argv.workspace);
+              if (
+                !fs.existsSync(workspacePath) ||
+                !fs.statSync(workspacePath).isDirectory()
+              ) {
+                throw new Error(
+                  `Workspace directory does not exist: ${argv.workspace}`
+                );
+              }
+            }
+            for (const name of ['depth', 'top', 'max-files', 'max-symbols']) {
+              const value = argv[name];
+              if (
+                typeof value !== 'number' ||
+                !Number.isInteger(value) ||
+                value < 1
+              ) {
+                throw new Error(`--${name} must be a positive integer`);
+              }
+            }
+            return true;
+          });
+      }
+    )
+    .command(
+      'cycles',
+      'Report import cycles between files or modules',
+      (yargs) => {
+        return yargs
+          .option('config', {
+            alias: 'c',
+            type: 'string',
+            describe: 'Path to configuration file',
+            requiresArg: true,
+          })
+          .option('lsp', {
+            alias: 'l',
+            type: 'string',
+            describe: 'Explicitly specify name of LSP to use from config',
+            requiresArg: true,
+          })
+          .option('workspace', {
+            alias: 'w',
+            type: 'string',
+            describe: 'Workspace directory (default: current directory)',
+            requiresArg: true,
+          })
+          .option('paths', {
+            type: 'string',
+            describe:
+              'How file paths appear in reports (default: relative to the workspace)',
+            choices: PATH_STYLES,
+            requiresArg: true,
+          })
+          .option('format', {
+            type: 'string',
+            describe: 'Output format',
+            choices: REPORT_FORMATS,
+            default: 'markdown',
+          })
+          .option('depth', {
+            type: 'number',
+            describe:
+              'Group files into modules of this many directory levels (default: file-level graph)',
+          })
+          .option('max-files', {
+            type: 'number',
+            describe: 'Maximum number of source files to analyze',
+            default: 2000,
+          })
+          .option('loglevel', {
+            type: 'string',
+            describe: 'LSP server log level',
+            choices: ['debug', 'info', 'warn', 'error'],
+            requiresArg: true,
+          })
+          .option('console', {
+            type: 'boolean',
+            describe:
+              'Output logs to console instead of log files (for troubleshooting only)',
+            default: false,
+          })
+          .example('$0 cycles', 'Report import cycles between files')
+          .example(
+            '$0 cycles --depth 2',
+            'Report cycles between src/<module> directories'
+          )
+          .strictOptions()
+          .check((argv) => {
+            if (argv.workspace) {
+              const workspacePath = path.resolve(argv.workspace);
+              if (
+                !fs.existsSync(workspacePath) ||
+                !fs.statSync(workspacePath).isDirectory()
+              ) {
+                throw new Error(
+                  `Workspace directory does not exist: ${argv.workspace}`
+                );
+              }
+            }
+            for (const name of ['depth', 'max-files']) {
+              const value = argv[name];
+              if (
+                value !== undefined &&
+                (typeof value !== 'number' ||
+                  !Number.isInteger(value) ||
+                  value < 1)
+              ) {
+                throw new Error(`--${name} must be a positive integer`);
+              }
+            }
+            return true;
+          });
+      }
+    )
+    .command(
+      'doc-coverage',
+      'Report public symbols without documentation, per module',
+      (yargs) => {
+        return yargs
+          .option('config', {
+            alias: 'c',
+            type: 'string',
+            describe: 'Path to configuration file',
+            requiresArg: true,
+          })
+          .option('lsp', {
+            alias: 'l',
+            type: 'string',
+            describe: 'Explicitly specify name of LSP to use from config',
+            requiresArg: true,
+          })
+          .option('workspace', {
+            alias: 'w',
+            type: 'string',
+            describe: 'Workspace directory (default: current directory)',
+            requiresArg: true,
+          })
+          .option('paths', {
+            type: 'string',
+            describe:
+              'How file paths appear in reports (default: relative to the workspace)',
+            choices: PATH_STYLES,
+            requiresArg: true,
+          })
+          .option('format', {
+            type: 'string',
+            describe: 'Output format',
+            choices: REPORT_FORMATS,
+            default: 'markdown',
+          })
+          .option('depth', {
+            type: 'number',
+            describe: 'Directory levels that make up a module',
+            default: 1,
+          })
+          .option('fail-under', {
+            type: 'number',
+            describe:
+              'Exit with status 1 when coverage is below this percentage (0-100)',
+          })
+          .option('max-files', {
+            type: 'number',
+            describe: 'Maximum number of source files to analyze',
+            default: 500,
+          })
+          .option('loglevel', {
+            type: 'string',
+            describe: 'LSP server log level',
+            choices: ['debug', 'info', 'warn', 'error'],
+            requiresArg: true,
+          })
+          .option('console', {
+            type: 'boolean',
+            describe:
+              'Output logs to console instead of log files (for troubleshooting only)',
+            default: false,
+          })
+          .example('$0 doc-coverage', 'Print documentation coverage per module')
+          .example(
+            '$0 doc-coverage --fail-under 80',
+            'Fail in CI when less than 80% of public symbols are documented'
+          )
+          .strictOptions()
+          .check((argv) => {
+            if (argv.workspace) {
+              const workspacePath = path.resolve(argv.workspace);
+              if (
+                !fs.existsSync(workspacePath) ||
+                !fs.statSync(workspacePath).isDirectory()
+              ) {
+                throw new Error(
+                  `Workspace directory does not exist: ${argv.workspace}`
+                );
+              }
+            }
+            for (const name of ['depth', 'max-files']) {
+              const value = argv[name];
+              if (
+                typeof value !== 'number' ||
+                !Number.isInteger(value) ||
+                value < 1
+              ) {
+                throw new Error(`--${name} must be a positive integer`);
+              }
+            }
+            const failUnder = argv['fail-under'];
+            if (
+              failUnder !== undefined &&
+              (typeof failUnder !== 'number' ||
+                Number.isNaN(failUnder) ||
+                failUnder < 0 ||
+                failUnder > 100)
+            ) {
+              throw new Error('--fail-under must be between 0 and 100');
+            }
+            return true;
+          });
+      }
+    )
+    .command(
+      'query <query>',
+      'List workspace symbols matching a query with kind:, vis:, path:, name:, refs: and owner: filters',
+      (yargs) => {
+        return yargs
+          .positional('query', {
+            type: 'string',
+            describe: 'Symbol query, e.g. "kind:function vis:pub refs:>10"',
+          })
+          .option('config', {
+            alias: 'c',
+            type: 'string',
+            describe: 'Path to configuration file',
+            requiresArg: true,
+          })
+          .option('lsp', {
+            alias: 'l',
+            type: 'string',
+            describe: 'Explicitly specify name of LSP to use from config',
+            requiresArg: true,
+          })
+          .option('workspace', {
+            alias: 'w',
+            type: 'string',
+            describe: 'Workspace directory (default: current directory)',
+            requiresArg: true,
+          })
+          .option('paths', {
+            type: 'string',
+            describe:
+              'How file paths appear in reports (default: relative to the workspace)',
+            choices: PATH_STYLES,
+            requiresArg: true,
+          })
+          .option('format', {
+            type: 'string',
+            describe: 'Output format',
+            choices: REPORT_FORMATS,
+            default: 'markdown',
+          })
+          .option('limit', {
+            type: 'number',
+            describe: 'Maximum number of symbols to print',
+            default: 100,
+          })
+          .option('loglevel', {
+            type: 'string',
+            describe: 'LSP server log level',
+            choices: ['debug', 'info', 'warn', 'error'],
+            requiresArg: true,
+          })
+          .option('console', {
+            type: 'boolean',
+            describe:
+              'Output logs to console instead of log files (for troubleshooting only)',
+            default: false,
+          })
+          .example(
+            '$0 query "kind:function vis:pub path:src/** name:~^create"',
+            'List public functions under src/ whose name starts with create'
+          )
+          .example(
+            '$0 query "kind:class refs:>10" --format json',
+            'List classes referenced more than 10 times as JSON'
+          )
+          .strictOptions()
+          .check((argv) => {
+            if (argv.workspace) {
+              const workspacePath = path.resolve(argv.workspace);
+              if (
+                !fs.existsSync(workspacePath) ||
+                !fs.statSync(workspacePath).isDirectory()
+              ) {
+                throw new Error(
+                  `Workspace directory does not exist: ${argv.workspace}`
+                );
+              }
+            }
+            if (!Number.isInteger(argv.limit) || argv.limit < 1) {
+              throw new Error('--limit must be a positive integer');
+            }
+            // Throws on unknown kinds, visibilities and bad patterns
+            parseSymbolQuery(String(argv.query));
+            return true;
+          });
+      }
+    )
+    .command(
+      'find <target>',
+      'Find workspace symbols by name and print where they are declared',
+      (yargs) => {
+        return withLookupOptions(
+          yargs.positional('target', {
+            type: 'string',
+            describe: 'Symbol name, matched fuzzily like the search tool',
+          }),
+          'name'
+        ).example('$0 find createServer', 'Find symbols named createServer');
+      }
+    )
+    .command(
+      'refs <target>',
+      'Print the references to the symbol at a file position',
+      (yargs) => {
+        return withLookupOptions(
+          yargs.positional('target', {
+            type: 'string',
+            describe: 'Symbol position as file:line:character, 1-based',
+          }),
+          'position'
+        ).example(
+          '$0 refs src/server.ts:12:17 --format json',
+          'List references to the symbol at line 12, column 17 as JSON'
+        ).example(
+          '$0 refs src/server.ts:12:17 --color always | less -R',
+          'Keep the highlighting when paging the references'
+        );
+      }
+    )
+    .command(
+      'def <target>',
+      'Print where the symbol at a file position is defined',
+      (yargs) => {
+        return withLookupOptions(
+          yargs.positional('target', {
+            type: 'string',
+            describe: 'Symbol position as file:line:character, 1-based',
+          }),
+          'position'
+        ).example(
+          '$0 def src/main.ts:40:9',
+          'Go to the definition of the symbol at line 40, column 9'
+        );
+      }
+    )
+    .command(
+      'outline <target>',
+      'Print the symbols declared in a file',
+      (yargs) => {
+        return withLookupOptions(
+          yargs.positional('target', {
+            type: 'string',
+            describe: 'File to outline',
+          }),
+          'file'
+        ).example('$0 outline src/index.ts', 'List the symbols in a file');
+      }
+    )
+    .command(
+      'batch',
+      'Answer newline-delimited JSON queries from stdin with one JSON result per line',
       (yargs) => {
         return yargs
           .option('config', {
@@ -172,6 +1546,18 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
             describe: 'Workspace directory (default: current directory)',
             requiresArg: true,
           })
+          .option('paths', {
+            type: 'string',
+            describe:
+              'How file paths appear in reports (default: relative to the workspace)',
+            choices: PATH_STYLES,
+            requiresArg: true,
+          })
+          .option('limit', {
+            type: 'number',
+            describe: 'Maximum number of results per query without a limit',
+            default: 100,
+          })
           .option('loglevel', {
             type: 'string',
             describe: 'LSP server log level',
@@ -181,52 +1567,80 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
           .option('console', {
             type: 'boolean',
             describe:
-              'Output logs to console instead of log files (for troubleshooting only - do not use when running as MCP server)',
+              'Output logs to console instead of log files (for troubleshooting only)',
             default: false,
           })
-          .example('$0 start', 'Start with auto-detection')
           .example(
-            '$0 start --lsp typescript --workspace ./my-project',
-            'Start with specific LSP'
+            `echo '{"id":1,"command":"refs","target":"src/a.ts:3:17"}' | $0 batch`,
+            'List the references of one symbol as an NDJSON result'
           )
           .example(
-            '$0 start --config ./language-servers.yaml',
-            'Start with custom configuration'
+            '$0 batch < queries.ndjson > results.ndjson',
+            'Answer a file of find, refs, def, outline and query lines'
           )
-          .strictOptions() // Strict validation for start command
+          .strictOptions()
           .check((argv) => {
             if (argv.workspace) {
               const workspacePath = path.resolve(argv.workspace);
-              if (!fs.existsSync(workspacePath)) {
+              if (
+                !fs.existsSync(workspacePath) ||
+                !fs.statSync(workspacePath).isDirectory()
+              ) {
                 throw new Error(
                   `Workspace directory does not exist: ${argv.workspace}`
                 );
               }
-              if (!fs.statSync(workspacePath).isDirectory()) {
-                throw new Error(
-                  `Workspace path is not a directory: ${argv.workspace}`
-                );
-              }
             }
-            if (argv.config) {
-              const configPath = path.resolve(argv.config);
-              if (!fs.existsSync(configPath)) {
-                throw new Error(`Config file does not exist: ${argv.config}`);
-              }
-              if (!fs.statSync(configPath).isFile()) {
-                throw new Error(`Config path is not a file: ${argv.config}`);
-              }
+            if (!Number.isInteger(argv.limit) || argv.limit < 1) {
+              throw new Error('--limit must be a positive integer');
             }
-            if (argv.lsp) {
-              const availableLsps = listAvailableLsps(argv.config);
-              if (availableLsps.length === 0) {
-                throw new Error(
-                  `No LSP servers configured. Run "symbols config init" to create a configuration file.`
-                );
-              }
-              if (!availableLsps.includes(argv.lsp)) {
+            return true;
+          });
+      }
+    )
+    .command(
+      'gateway',
+      'Act as a language server over stdio, routing each file to the language server configured for it',
+      (yargs) => {
+        return yargs
+          .option('config', {
+            alias: 'c',
+            type: 'string',
+            describe: 'Path to configuration file',
+            requiresArg: true,
+          })
+          .option('lsp', {
+            alias: 'l',
+            type: 'string',
+            describe: 'Explicitly specify name of LSP to use from config',
+            requiresArg: true,
+          })
+          .option('workspace', {
+            alias: 'w',
+            type: 'string',
+            describe: 'Workspace directory (default: current directory)',
+            requiresArg: true,
+          })
+          .option('loglevel', {
+            type: 'string',
+            describe: 'LSP server log level',
+            choices: ['debug', 'info', 'warn', 'error'],
+            requiresArg: true,
+          })
+          .example(
+            '$0 gateway --workspace ./my-project',
+            'Serve every configured language to an editor on stdin/stdout'
+          )
+          .strictOptions()
+          .check((argv) => {
+            if (argv.workspace) {
+              const workspacePath = path.resolve(argv.workspace);
+              if (
+                !fs.existsSync(workspacePath) ||
+                !fs.statSync(workspacePath).isDirectory()
+              ) {
                 throw new Error(
-                  `Unknown LSP server: ${argv.lsp}\nAvailable: ${availableLsps.join(', ')}`
+                  `Workspace directory does not exist: ${argv.workspace}`
                 );
               }
             }
@@ -234,6 +1648,41 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
           });
       }
     )
+    .command(
+      'replay <recording>',
+      'Act as a language server over stdio, answering from a session recorded with start --record',
+      (yargs) => {
+        return yargs
+          .positional('recording', {
+            type: 'string',
+            describe: 'Recording file (<profile>.jsonl)',
+          })
+          .option('timing', {
+            type: 'string',
+            describe:
+              'Answer instantly or with the latency the real server had',
+            choices: REPLAY_TIMINGS,
+            default: 'instant',
+          })
+          .example(
+            '$0 replay ./recordings/typescript.jsonl',
+            'Serve a recorded TypeScript session on stdin/stdout'
+          )
+          .strictOptions()
+          .check((argv) => {
+            const recordingPath = path.resolve(String(argv.recording));
+            if (
+              !fs.existsSync(recordingPath) ||
+              !fs.statSync(recordingPath).isFile()
+            ) {
+              throw new Error(
+                `Recording file does not exist: ${String(argv.recording)}`
+              );
+            }
+            return true;
+          });
+      }
+    )
     .command('config', 'Manage configuration files', (yargs) => {
       return yargs
         .command('init', 'Initialize a new configuration file', (yargs) => {
@@ -292,6 +1741,13 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
               choices: ['yaml', 'json'],
               default: 'yaml',
             })
+            .option('color', {
+              type: 'string',
+              describe:
+                'Syntax highlighting (auto disables it when stdout is not a TTY)',
+              choices: COLOR_MODES,
+              default: 'auto',
+            })
             .example(
               '$0 config show',
               'Show effective configuration for current directory'
@@ -303,6 +1759,10 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
             .example(
               '$0 config show --format json',
               'Show configuration as JSON'
+            )
+            .example(
+              '$0 config show --color never',
+              'Show configuration without syntax highlighting'
             );
         })
         .command('path', 'Show configuration file location', (yargs) => {
@@ -357,6 +1817,11 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
         '--workspace',
         '-w',
         '--loglevel',
+        '--paths',
+        '--transport',
+        '--host',
+        '--port',
+        '--socket',
         '--console',
         '--help',
         '-h',
@@ -386,8 +1851,17 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
 
         // Check if it's a known flag
         if (knownFlags.has(arg)) {
-          // Flags that require arguments: --workspace, -w, --loglevel
-          if (arg === '--workspace' || arg === '-w' || arg === '--loglevel') {
+          // Flags that require arguments
+          if (
+            arg === '--workspace' ||
+            arg === '-w' ||
+            arg === '--loglevel' ||
+            arg === '--paths' ||
+            arg === '--transport' ||
+            arg === '--host' ||
+            arg === '--port' ||
+            arg === '--socket'
+          ) {
             skipNext = true;
           }
           continue;
@@ -444,6 +1918,11 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
       workspace: argv.workspace,
       loglevel: argv.loglevel,
       console: Boolean(argv.console),
+      paths: argv.paths,
+      transport: argv.transport,
+      host: argv.host,
+      port: argv.port,
+      socket: argv.socket,
       directCommand: { commandName, commandArgs },
     } as RunCommandArgs;
   }
@@ -457,9 +1936,231 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
       loglevel: argv.loglevel,
       configPath: argv.config,
       console: Boolean(argv.console),
+      paths: argv.paths,
+      record: argv.record,
+      replay: argv.replay,
+      replayTiming: argv['replay-timing'],
+      transport: argv.transport,
+      host: argv.host,
+      port: argv.port,
+      socket: argv.socket,
     } as StartCommandArgs;
   }
 
+  // Handle 'replay' command
+  if (command === 'replay') {
+    return {
+      command: 'replay',
+      recording: String(argv.recording),
+      timing: (argv.timing as ReplayTiming) || 'instant',
+    } as ReplayCommandArgs;
+  }
+
+  // Handle 'map' command
+  if (command === 'map') {
+    return {
+      command: 'map',
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+      paths: argv.paths,
+      console: Boolean(argv.console),
+      format: (argv.format as ReportFormat) || 'markdown',
+      color: (argv.color as ColorMode) || 'auto',
+      depth: (argv.depth as number) || 1,
+      maxFiles: (argv['max-files'] as number) || 500,
+    } as MapCommandArgs;
+  }
+
+  // Handle 'dead-code' command
+  if (command === 'dead-code') {
+    return {
+      command: 'dead-code',
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+      paths: argv.paths,
+      console: Boolean(argv.console),
+      format: (argv.format as ReportFormat) || 'markdown',
+      entryPoints: (argv.entry as EntryPointKind[] | undefined) ?? [
+        ...ENTRY_POINT_KINDS,
+      ],
+      entrySymbols: (argv['entry-symbol'] as string[] | undefined) ?? [],
+      maxFiles: (argv['max-files'] as number) || 500,
+    } as DeadCodeCommandArgs;
+  }
+
+  // Handle 'usage' command
+  if (command === 'usage') {
+    return {
+      command: 'usage',
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+      paths: argv.paths,
+      console: Boolean(argv.console),
+      format: (argv.format as UsageFormat) || 'markdown',
+      depth: (argv.depth as number) || 1,
+      since: (argv.since as string) || '90 days ago',
+      top: (argv.top as number) || 20,
+      maxFiles: (argv['max-files'] as number) || 500,
+      maxSymbols: (argv['max-symbols'] as number) || 2000,
+    } as UsageCommandArgs;
+  }
+
+  // Handle 'cycles' command
+  if (command === 'cycles') {
+    const depth = argv.depth as number | undefined;
+    return {
+      command: 'cycles',
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+      paths: argv.paths,
+      console: Boolean(argv.console),
+      format: (argv.format as ReportFormat) || 'markdown',
+      ...(depth !== undefined && { depth }),
+      maxFiles: (argv['max-files'] as number) || 2000,
+    } as CyclesCommandArgs;
+  }
+
+  // Handle 'doc-coverage' command
+  if (command === 'doc-coverage') {
+    const failUnder = argv['fail-under'] as number | undefined;
+    return {
+      command: 'doc-coverage',
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+      paths: argv.paths,
+      console: Boolean(argv.console),
+      format: (argv.format as ReportFormat) || 'markdown',
+      depth: (argv.depth as number) || 1,
+      ...(failUnder !== undefined && { failUnder }),
+      maxFiles: (argv['max-files'] as number) || 500,
+    } as DocCoverageCommandArgs;
+  }
+
+  // Handle 'query' command
+  if (command === 'query') {
+    return {
+      command: 'query',
+      query: String(argv.query),
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+      paths: argv.paths,
+      console: Boolean(argv.console),
+      format: (argv.format as ReportFormat) || 'markdown',
+      limit: (argv.limit as number) || 100,
+    } as QueryCommandArgs;
+  }
+
+  // Handle 'find', 'refs', 'def' and 'outline' commands
+  if (
+    command === 'find' ||
+    command === 'refs' ||
+    command === 'def' ||
+    command === 'outline'
+  ) {
+    return {
+      command,
+      target: String(argv.target),
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+      paths: argv.paths,
+      console: Boolean(argv.console),
+      format: (argv.format as ReportFormat) || 'markdown',
+      color: (argv.color as ColorMode) || 'auto',
+      limit: (argv.limit as number) || 100,
+    } as LookupCommandArgs;
+  }
+
+  // Handle 'batch' command
+  if (command === 'batch') {
+    return {
+      command: 'batch',
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+      paths: argv.paths,
+      console: Boolean(argv.console),
+      limit: (argv.limit as number) || 100,
+    } as BatchCommandArgs;
+  }
+
+  // Handle 'gateway' command
+  if (command === 'gateway') {
+    return {
+      command: 'gateway',
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+    } as GatewayCommandArgs;
+  }
+
+  // Handle 'api' command
+  if (command === 'api') {
+    const subcommand = argv._[1] as string;
+    const subcommandArgs =
+      subcommand === 'snapshot'
+        ? {
+            subcommand: 'snapshot',
+            output: argv.output,
+            release: argv.release,
+          }
+        : {
+            subcommand: 'check',
+            baseline: argv.baseline,
+            baselineVersion: argv['baseline-version'],
+            release: argv.release,
+            format: (argv.format as ReportFormat) || 'markdown',
+          };
+
+    return {
+      command: 'api',
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+      paths: argv.paths,
+      console: Boolean(argv.console),
+      maxFiles: (argv['max-files'] as number) || 500,
+      subcommandArgs,
+    } as ApiCommandArgs;
+  }
+
+  // Handle 'export' command
+  if (command === 'export') {
+    return {
+      command: 'export',
+      workspace: argv.workspace,
+      lsp: argv.lsp,
+      loglevel: argv.loglevel,
+      configPath: argv.config,
+      paths: argv.paths,
+      console: Boolean(argv.console),
+      maxFiles: (argv['max-files'] as number) || 500,
+      subcommandArgs: {
+        subcommand: 'chunks',
+        output: argv.output,
+        maxChars: (argv['max-chars'] as number) || 4000,
+        includeTests: Boolean(argv['include-tests']),
+        docs: argv.docs !== false,
+      },
+    } as ExportCommandArgs;
+  }
+
   // Handle 'config' command
   if (command === 'config') {
     const subcommand = argv._[1] as string;
@@ -490,6 +2191,7 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
           configPath: argv.config,
           workspace: argv.workspace,
           format: (argv.format as 'yaml' | 'json') || 'yaml',
+          color: (argv.color as ColorMode) || 'auto',
         },
       } as ConfigCommandArgs;
     }
@@ -513,6 +2215,22 @@ export function parseCliArgs(args: string[] = process.argv): CliArgs {
   } as BaseCliArgs;
 }
 
+/**
+ * Resolve the output path style from the CLI flag or SYMBOLS_PATHS
+ */
+function resolvePathStyle(cliValue?: PathStyle): PathStyle {
+  const value = cliValue || process.env.SYMBOLS_PATHS;
+  if (!value) {
+    return 'relative';
+  }
+  if (!(PATH_STYLES as readonly string[]).includes(value)) {
+    throw new Error(
+      `Invalid SYMBOLS_PATHS: ${value}. Must be one of: ${PATH_STYLES.join(', ')}.`
+    );
+  }
+  return value as PathStyle;
+}
+
 /**
  * Resolve configuration from start command args and environment variables
  */
@@ -522,9 +2240,15 @@ export function resolveStartConfig(cliArgs: StartCommandArgs): {
   loglevel: string;
   configPath?: string;
   console: boolean;
+  paths: PathStyle;
+  record?: string;
+  replay?: string;
+  replayTiming: ReplayTiming;
 } {
   const lsp = cliArgs.lsp || process.env.SYMBOLS_LSP;
   const configPath = cliArgs.configPath || process.env.SYMBOLS_CONFIG_PATH;
+  const record = cliArgs.record || process.env.SYMBOLS_RECORD;
+  const replay = cliArgs.replay || process.env.SYMBOLS_REPLAY;
 
   const result: {
     workspace: string;
@@ -532,11 +2256,17 @@ export function resolveStartConfig(cliArgs: StartCommandArgs): {
     loglevel: string;
     configPath?: string;
     console: boolean;
+    paths: PathStyle;
+    record?: string;
+    replay?: string;
+    replayTiming: ReplayTiming;
   } = {
     workspace:
       cliArgs.workspace || process.env.SYMBOLS_WORKSPACE || process.cwd(),
     loglevel: cliArgs.loglevel || process.env.SYMBOLS_LOGLEVEL || 'info',
     console: cliArgs.console || false,
+    paths: resolvePathStyle(cliArgs.paths),
+    replayTiming: resolveReplayTiming(cliArgs.replayTiming),
   };
 
   if (lsp) {
@@ -547,9 +2277,37 @@ export function resolveStartConfig(cliArgs: StartCommandArgs): {
     result.configPath = configPath;
   }
 
+  if (record && replay) {
+    throw new Error('Sessions cannot be recorded and replayed at once.');
+  }
+
+  if (record) {
+    result.record = record;
+  }
+
+  if (replay) {
+    result.replay = replay;
+  }
+
   return result;
 }
 
+/**
+ * Resolve the replay timing from the CLI flag or SYMBOLS_REPLAY_TIMING
+ */
+function resolveReplayTiming(cliValue?: ReplayTiming): ReplayTiming {
+  const value = cliValue || process.env.SYMBOLS_REPLAY_TIMING;
+  if (!value) {
+    return 'instant';
+  }
+  if (!(REPLAY_TIMINGS as readonly string[]).includes(value)) {
+    throw new Error(
+      `Invalid SYMBOLS_REPLAY_TIMING: ${value}. Must be one of: ${REPLAY_TIMINGS.join(', ')}.`
+    );
+  }
+  return value as ReplayTiming;
+}
+
 /**
  * Resolve configuration from run command args and environment variables
  */
@@ -557,12 +2315,97 @@ export function resolveRunConfig(cliArgs: RunCommandArgs): {
   workspace: string;
   loglevel: string;
   console: boolean;
+  paths: PathStyle;
 } {
   return {
     workspace:
       cliArgs.workspace || process.env.SYMBOLS_WORKSPACE || process.cwd(),
     loglevel: cliArgs.loglevel || process.env.SYMBOLS_LOGLEVEL || 'info',
     console: cliArgs.console || false,
+    paths: resolvePathStyle(cliArgs.paths),
+  };
+}
+
+/**
+ * Parse an optional positive integer from an environment variable
+ */
+function parsePositiveInteger(
+  name: string,
+  value: string | undefined
+): number | undefined {
+  if (!value) {
+    return undefined;
+  }
+  const parsed = Number(value);
+  if (!Number.isInteger(parsed) || parsed < 1) {
+    throw new Error(`Invalid ${name}: ${value}. Must be a positive integer.`);
+  }
+  return parsed;
+}
+
+/**
+ * Resolve how MCP clients connect from the CLI flags or SYMBOLS_TRANSPORT,
+ * SYMBOLS_HOST, SYMBOLS_PORT and SYMBOLS_SOCKET. A socket implies the http
+ * transport unless sse is chosen. SYMBOLS_ALLOWED_ORIGINS lists, comma
+ * separated, the browser origins allowed to open WebSocket sessions.
+ * SYMBOLS_MAX_SESSIONS caps the open client sessions and
+ * SYMBOLS_SESSION_IDLE_TIMEOUT closes sessions idle for that many seconds.
+ */
+export function resolveTransportConfig(
+  cliArgs: StartCommandArgs | RunCommandArgs
+): {
+  transport: Transport;
+  host: string;
+  port: number;
+  socket: string | undefined;
+  allowedOrigins: string[] | undefined;
+  maxSessions: number | undefined;
+  sessionIdleTimeoutMs: number | undefined;
+} {
+  const transport = cliArgs.transport || process.env.SYMBOLS_TRANSPORT;
+  if (transport && !(TRANSPORTS as readonly string[]).includes(transport)) {
+    throw new Error(
+      `Invalid SYMBOLS_TRANSPORT: ${transport}. Must be one of: ${TRANSPORTS.join(', ')}.`
+    );
+  }
+
+  const socket = cliArgs.socket || process.env.SYMBOLS_SOCKET || undefined;
+  if (socket && transport === 'stdio') {
+    throw new Error('A socket needs the http or sse transport, not stdio.');
+  }
+
+  const port =
+    cliArgs.port ?? Number(process.env.SYMBOLS_PORT || DEFAULT_HTTP_PORT);
+  if (!Number.isInteger(port) || port < 0 || port > 65535) {
+    throw new Error(
+      `Invalid port: ${cliArgs.port ?? process.env.SYMBOLS_PORT}. Must be a number between 0 and 65535.`
+    );
+  }
+
+  const allowedOrigins = (process.env.SYMBOLS_ALLOWED_ORIGINS ?? '')
+    .split(',')
+    .map((origin) => origin.trim())
+    .filter(Boolean);
+
+  const maxSessions = parsePositiveInteger(
+    'SYMBOLS_MAX_SESSIONS',
+    process.env.SYMBOLS_MAX_SESSIONS
+  );
+  const idleTimeout = parsePositiveInteger(
+    'SYMBOLS_SESSION_IDLE_TIMEOUT',
+    process.env.SYMBOLS_SESSION_IDLE_TIMEOUT
+  );
+
+  return {
+    transport:
+      (transport as Transport | undefined) || (socket ? 'http' : 'stdio'),
+    host: cliArgs.host || process.env.SYMBOLS_HOST || DEFAULT_HTTP_HOST,
+    port,
+    socket,
+    allowedOrigins: allowedOrigins.length > 0 ? allowedOrigins : undefined,
+    maxSessions,
+    sessionIdleTimeoutMs:
+      idleTimeout !== undefined ? idleTimeout * 1000 : undefined,
   };
 }
 
@@ -612,7 +2455,7 @@ export function handleConfigInit(args: ConfigInitArgs): void {
 
     console.log(`Configuration file created: ${configPath}`);
     console.log(
-      '\nThe configuration enables TypeScript and Pyright by default.'
+      '\nThe configuration enables TypeScript, Pyright and Go by default.'
     );
     console.log(
       'It also includes commented examples for additional language servers you can enable as needed.'
@@ -632,9 +2475,11 @@ export function handleConfigInit(args: ConfigInitArgs): void {
 export function handleConfigShow(args: ConfigShowArgs): void {
   try {
     const configWithSource = loadLspConfig(args.configPath);
+    const useColor = shouldUseColor(args.color ?? 'auto');
 
     if (args.format === 'json') {
-      console.log(JSON.stringify(configWithSource.config, null, 2));
+      const jsonOutput = JSON.stringify(configWithSource.config, null, 2);
+      console.log(useColor ? highlightJson(jsonOutput) : jsonOutput);
     } else {
       const yamlOutput = yaml.dump(configWithSource.config, {
         indent: 2,
@@ -643,16 +2488,17 @@ export function handleConfigShow(args: ConfigShowArgs): void {
         sortKeys: true,
       });
 
-      console.log('# Active Configuration');
-      console.log(`# Source: ${configWithSource.source.description}`);
-      if (configWithSource.source.path !== 'default') {
-        console.log(`# Config file: ${configWithSource.source.path}`);
-      }
-      console.log(
-        '# Use --config <file> to specify a custom configuration file'
-      );
-      console.log();
-      console.log(yamlOutput);
+      const headerLines = [
+        '# Active Configuration',
+        `# Source: ${configWithSource.source.description}`,
+        ...(configWithSource.source.path !== 'default'
+          ? [`# Config file: ${configWithSource.source.path}`]
+          : []),
+        '# Use --config <file> to specify a custom configuration file',
+        '',
+      ];
+      const output = [...headerLines, yamlOutput].join('\n');
+      console.log(useColor ? highlightYaml(output) : output);
     }
   } catch (error) {
     console.error(
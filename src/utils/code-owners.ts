@@ -0,0 +1,262 @@
+/**
+ * Code owners - attribute files to owners from a CODEOWNERS file, optionally
+ * mapping individual owners to teams
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+import { toAbsoluteFilePath } from './path-format.js';
+import logger from './logger.js';
+
+// Locations GitHub and GitLab read CODEOWNERS from, relative to the root
+const CODEOWNERS_LOCATIONS = [
+  '.github/CODEOWNERS',
+  'CODEOWNERS',
+  'docs/CODEOWNERS',
+  '.gitlab/CODEOWNERS',
+];
+
+export interface CodeOwnersRule {
+  pattern: string;
+  matcher: RegExp;
+  owners: string[];
+}
+
+export interface CodeOwnersSettings {
+  workspace: string;
+  // Explicit CODEOWNERS path; discovered from the workspace upwards otherwise
+  file?: string;
+  // Owner -> team, e.g. `@alice: '@payments-team'`
+  teams?: Record<string, string>;
+}
+
+interface LoadedCodeOwners {
+  // Directory CODEOWNERS patterns are relative to
+  root: string;
+  rules: CodeOwnersRule[];
+}
+
+interface CodeOwnersState {
+  settings: CodeOwnersSettings | null;
+  // Undefined until CODEOWNERS is read, null when there is none
+  loaded: LoadedCodeOwners | null | undefined;
+}
+
+const getState = defineWorkspaceState<CodeOwnersState>(() => ({
+  settings: null,
+  loaded: undefined,
+}));
+
+function escapeRegExp(text: string): string {
+  return text.replace(/[.+^${}()|[\]\\]/g, '\\$&');
+}
+
+/**
+ * Compile a CODEOWNERS pattern with gitignore semantics: patterns without
+ * a leading or inner slash match at any depth, and a matching directory
+ * owns everything below it except for `dir/*`, which is one level only.
+ */
+export function compileCodeOwnersPattern(pattern: string): RegExp {
+  const anchored = pattern.slice(0, -1).includes('/');
+  const directoryOnly = pattern.endsWith('/');
+  const trimmed = pattern.replace(/^\//, '').replace(/\/$/, '');
+  const singleLevel = trimmed.endsWith('/*') && !trimmed.endsWith('**/*');
+
+  let body = '';
+  for (let i = 0; i < trimmed.length; i++) {
+    const char = trimmed.charAt(i);
+    if (char === '*' && trimmed.charAt(i + 1) === '*') {
+      if (trimmed.charAt(i + 2) === '/') {
+        body += '(?:.*/)?';
+        i += 2;
+      } else {
+        body += '.*';
+        i += 1;
+      }
+    } else if (char === '*') {
+      body += '[^/]*';
+    } else if (char === '?') {
+      body += '[^/]';
+    } else {
+      body += escapeRegExp(char);
+    }
+  }
+
+  const prefix = anchored ? '^' : '^(?:.*/)?';
+  const suffix = directoryOnly ? '/.*' : singleLevel ? '' : '(?:/.*)?';
+  return new RegExp(`${prefix}${body}${suffix}$`);
+}
+
+export function parseCodeOwners(content: string): CodeOwnersRule[] {
+  const rules: CodeOwnersRule[] = [];
+
+  for (const rawLine of content.split('\n')) {
+    const line = rawLine.replace(/\s#.*$/, '').trim();
+    if (!line || line.startsWith('#') || line.startsWith('[')) {
+      continue;
+    }
+    const [pattern, ...owners] = line.split(/\s+/);
+    if (pattern) {
+      rules.push({
+        pattern,
+        matcher: compileCodeOwnersPattern(pattern),
+        owners,
+      });
+    }
+  }
+  return rules;
+}
+
+/**
+ * Owners of a root-relative path; the last matching rule wins
+ */
+export function findCodeOwners(
+  rules: CodeOwnersRule[],
+  relativePath: string
+): string[] {
+  for (let i = rules.length - 1; i >= 0; i--) {
+    const rule = rules[i];
+    if (rule?.matcher.test(relativePath)) {
+      return rule.owners;
+    }
+  }
+  return [];
+}
+
+function findCodeOwnersFile(workspace: string): string | null {
+  let directory = path.resolve(workspace);
+  for (;;) {
+    for (const location of CODEOWNERS_LOCATIONS) {
+      const candidate = path.join(directory, location);
+      if (fs.existsSync(candidate)) {
+        return candidate;
+      }
+    }
+    // Stop at the repository root
+    if (fs.existsSync(path.join(directory, '.git'))) {
+      return null;
+    }
+    const parent = path.dirname(directory);
+    if (parent === directory) {
+      return null;
+    }
+    directory = parent;
+  }
+}
+
+function loadCodeOwners(): LoadedCodeOwners | null {
+  const state = getState();
+  if (state.loaded !== undefined) {
+    return state.loaded;
+  }
+  state.loaded = null;
+  const { settings } = state;
+  if (!settings) {
+    return null;
+  }
+
+  const file = settings.file
+    ? path.resolve(settings.workspace, settings.file)
+    : findCodeOwnersFile(settings.workspace);
+  if (!file) {
+    return null;
+  }
+
+  try {
+    const locationDirectory = path.basename(path.dirname(file));
+    // .github/, docs/ and .gitlab/ CODEOWNERS describe their parent
+    const root = ['.github', 'docs', '.gitlab'].includes(locationDirectory)
+      ? path.dirname(path.dirname(file))
+      : path.dirname(file);
+    const rules = parseCodeOwners(fs.readFileSync(file, 'utf-8'));
+    state.loaded = { root, rules };
+    logger.debug('Loaded CODEOWNERS', { file, rules: rules.length });
+  } catch (error) {
+    logger.warn('Failed to read CODEOWNERS', {
+      file,
+      error: error instanceof Error ? error.message : String(error),
+    });
+  }
+  return state.loaded;
+}
+
+/**
+ * Set the workspace and team mapping; CODEOWNERS is re-read on next use
+ */
+export function configureCodeOwners(update: CodeOwnersSettings): void {
+  const state = getState();
+  state.settings = update;
+  state.loaded = undefined;
+}
+
+/**
+ * Owners of a file, mapped to teams where configured
+ */
+export function getFileOwners(pathOrUri: string): string[] {
+  const codeOwners = loadCodeOwners();
+  if (!codeOwners) {
+    return [];
+  }
+
+  const relativePath = path
+    .relative(codeOwners.root, toAbsoluteFilePath(pathOrUri))
+    .split(path.sep)
+    .join('/');
+  if (relativePath.startsWith('../') || path.isAbsolute(relativePath)) {
+    return [];
+  }
+
+  const teams = getState().settings?.teams ?? {};
+  const owners = findCodeOwners(codeOwners.rules, relativePath).map(
+    (owner) => teams[owner] ?? owner
+  );
+  return [...new Set(owners)];
+}
+
+/**
+ * ` [owner: @team]` tag for file headings, empty when nobody owns the file
+ */
+export function formatOwnerTag(pathOrUri: string): string {
+  const owners = getFileOwners(pathOrUri);
+  return owners.length > 0 ? ` [owner: ${owners.join(', ')}]` : '';
+}
+
+/**
+ * Split `owner:@team` filters out of a free-text query
+ */
+export function parseOwnerFilter(query: string): {
+  query: string;
+  owners: string[];
+} {
+  const owners: string[] = [];
+  const remaining = query
+    .split(/\s+/)
+    .filter((token) => {
+      const match = /^owner:(\S+)$/.exec(token);
+      if (match?.[1]) {
+        owners.push(match[1]);
+        return false;
+      }
+      return true;
+    })
+    .join(' ')
+    .trim();
+  return { query: remaining, owners };
+}
+
+/**
+ * Whether any owner of the file is in `owners` (case-insensitive)
+ */
+export function matchesOwnerFilter(
+  pathOrUri: string,
+  owners: string[]
+): boolean {
+  if (owners.length === 0) {
+    return true;
+  }
+  const wanted = new Set(owners.map((owner) => owner.toLowerCase()));
+  return getFileOwners(pathOrUri).some((owner) =>
+    wanted.has(owner.toLowerCase())
+  );
+}
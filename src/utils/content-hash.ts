@@ -0,0 +1,12 @@
+/**
+ * Content hashing used to detect files that changed underneath a query
+ */
+
+import { createHash } from 'crypto';
+
+/**
+ * Hash file content; short hex digests are enough to detect changes
+ */
+export function hashContent(content: string): string {
+  return createHash('sha256').update(content).digest('hex').slice(0, 16);
+}
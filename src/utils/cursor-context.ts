@@ -20,6 +20,8 @@ import {
 } from '../types/lsp.js';
 import { formatFilePath } from '../tools/utils.js';
 import logger from './logger.js';
+import { sliceGraphemes, splitLines } from './text.js';
+import { formatQualifiedName } from './qualified-names.js';
 
 export interface CursorContext {
   operation: string;
@@ -27,6 +29,8 @@ export interface CursorContext {
   position: OneBasedPosition; // 1-based user position
   symbolName?: string;
   symbolKind?: string;
+  // Symbol name qualified by its containers, e.g. `Type::method`
+  qualifiedName?: string;
   tokenName?: string;
   tokenType?: string;
   tokenModifiers?: string[];
@@ -37,6 +41,7 @@ interface SymbolAtPosition {
   name: string;
   kind: SymbolKindValue;
   containerName?: string;
+  containerPath?: string[];
 }
 
 /**
@@ -244,6 +249,9 @@ async function findSymbolAtPosition(
       ...(bestMatch.containerName && {
         containerName: bestMatch.containerName,
       }),
+      ...(bestMatch.containerPath && {
+        containerPath: bestMatch.containerPath,
+      }),
     };
   } catch (error) {
     logger.error(
@@ -261,7 +269,7 @@ function createTextSnippet(
   position: OneBasedPosition, // 1-based user position
   contextChars: number = 10
 ): string {
-  const lines = fileContent.split('\n');
+  const lines = splitLines(fileContent);
   const lineIndex = position.line - 1; // Convert to 0-based
   const charIndex = position.character - 1; // Convert to 0-based
 
@@ -278,8 +286,8 @@ function createTextSnippet(
   const start = Math.max(0, charIndex - contextChars);
   const end = Math.min(line.length, charIndex + contextChars);
 
-  const beforeCursor = line.substring(start, charIndex);
-  const afterCursor = line.substring(charIndex, end);
+  const beforeCursor = sliceGraphemes(line, start, charIndex);
+  const afterCursor = sliceGraphemes(line, charIndex, end);
 
   // Add ellipsis if we truncated
   const prefix = start > 0 ? '...' : '';
@@ -352,6 +360,13 @@ export async function generateCursorContext(
 
   if (symbolAtPosition?.name) {
     result.symbolName = symbolAtPosition.name;
+    if (symbolAtPosition.containerPath?.length) {
+      result.qualifiedName = formatQualifiedName(
+        symbolAtPosition.name,
+        symbolAtPosition.containerPath,
+        filePath
+      );
+    }
   }
 
   if (symbolAtPosition) {
@@ -383,7 +398,7 @@ export function formatCursorContext(context: CursorContext): string {
   // Document symbol info (broader structural context)
   const symbolInfo =
     context.symbolName && context.symbolKind
-      ? `(${context.symbolKind}) ${context.symbolName}`
+      ? `(${context.symbolKind}) ${context.qualifiedName ?? context.symbolName}`
       : 'n/a';
 
   // Semantic token info (precise clicked token)
@@ -0,0 +1,125 @@
+/**
+ * Dart Workspace Detection Utilities
+ * Finds the pubspec.yaml of the workspace and the Flutter SDK a Flutter app
+ * is analyzed with
+ */
+
+import fs from 'fs';
+import path from 'path';
+import { globSync } from 'glob';
+import which from 'which';
+import logger from './logger.js';
+
+// pubspec.yaml files nested deeper than this aren't looked for
+const MAX_PUBSPEC_DEPTH = 2;
+
+// A dependency on the Flutter SDK, `flutter:` followed by `sdk: flutter`
+const FLUTTER_DEPENDENCY_PATTERN = /^\s+flutter:\s*\n\s+sdk:\s*flutter\b/m;
+
+/**
+ * The pubspec.yaml at the workspace root, or the only one in a directory
+ * below it, skipping build output and generated platform projects
+ */
+export function findPubspec(workspacePath: string): string | null {
+  try {
+    const root = path.resolve(workspacePath);
+    const rootPubspec = path.join(root, 'pubspec.yaml');
+    if (fs.existsSync(rootPubspec)) {
+      return rootPubspec;
+    }
+
+    const nested = globSync('**/pubspec.yaml', {
+      cwd: root,
+      absolute: true,
+      maxDepth: MAX_PUBSPEC_DEPTH + 1,
+      ignore: [
+        '**/build/**',
+        '**/.dart_tool/**',
+        '**/ios/**',
+        '**/android/**',
+        '**/.git/**',
+      ],
+    });
+    if (nested.length === 1) {
+      logger.info('Found nested pubspec.yaml', { pubspec: nested[0] });
+      return nested[0]!;
+    }
+
+    logger.debug('No single pubspec.yaml found', {
+      workspacePath,
+      candidates: nested.length,
+    });
+    return null;
+  } catch (error) {
+    logger.error('Failed to look for pubspec.yaml', {
+      workspacePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return null;
+  }
+}
+
+/**
+ * Whether the package depends on the Flutter SDK
+ */
+export function isFlutterPackage(pubspecPath: string): boolean {
+  try {
+    return FLUTTER_DEPENDENCY_PATTERN.test(
+      fs.readFileSync(pubspecPath, 'utf8')
+    );
+  } catch {
+    return false;
+  }
+}
+
+/**
+ * The dart executable bundled with a Flutter SDK, if it has been downloaded
+ */
+export function getFlutterDart(flutterRoot: string): string | null {
+  const dart = path.join(
+    flutterRoot,
+    'bin',
+    'cache',
+    'dart-sdk',
+    'bin',
+    process.platform === 'win32' ? 'dart.exe' : 'dart'
+  );
+  return fs.existsSync(dart) ? dart : null;
+}
+
+/**
+ * The Flutter SDK for a package: the one FVM pins in the package or the
+ * workspace, then FLUTTER_ROOT, then the flutter command on the PATH
+ */
+export function findFlutterSdk(
+  packageDir: string,
+  workspacePath: string,
+  env: NodeJS.ProcessEnv = process.env
+): string | null {
+  const candidates = [
+    path.join(packageDir, '.fvm', 'flutter_sdk'),
+    path.join(workspacePath, '.fvm', 'flutter_sdk'),
+    env.FLUTTER_ROOT,
+  ];
+
+  const flutter = which.sync('flutter', {
+    nothrow: true,
+    ...(env.PATH ? { path: env.PATH } : {}),
+  });
+  if (flutter) {
+    // bin/flutter inside the SDK, often reached through a symlink
+    candidates.push(path.dirname(path.dirname(fs.realpathSync(flutter))));
+  }
+
+  for (const candidate of candidates) {
+    if (candidate && fs.existsSync(candidate)) {
+      const flutterRoot = fs.realpathSync(candidate);
+      if (getFlutterDart(flutterRoot)) {
+        return flutterRoot;
+      }
+    }
+  }
+
+  logger.debug('No Flutter SDK found', { packageDir });
+  return null;
+}
@@ -0,0 +1,89 @@
+/**
+ * Elixir Workspace Detection Utilities
+ * Finds the Mix project ElixirLS builds and the settings it waits for
+ */
+
+import fs from 'fs';
+import path from 'path';
+import { globSync } from 'glob';
+import logger from './logger.js';
+
+// mix.exs files nested deeper than this below the workspace aren't looked for
+const MAX_MIX_DEPTH = 2;
+
+export interface MixProjectInfo {
+  // Directory holding the project's mix.exs
+  projectDir: string;
+  // Umbrella projects list their apps under apps_path
+  umbrella: boolean;
+}
+
+/**
+ * The Mix project at the workspace root, or the only one in a directory
+ * below it. Umbrella apps under the root project don't count as others.
+ */
+export function detectMixProject(workspacePath: string): MixProjectInfo | null {
+  try {
+    const root = path.resolve(workspacePath);
+    let mixFile = path.join(root, 'mix.exs');
+
+    if (!fs.existsSync(mixFile)) {
+      const nested = globSync('**/mix.exs', {
+        cwd: root,
+        absolute: true,
+        maxDepth: MAX_MIX_DEPTH + 1,
+        ignore: ['**/deps/**', '**/_build/**', '**/.git/**'],
+      });
+      if (nested.length !== 1) {
+        logger.debug('No single mix.exs found', {
+          workspacePath,
+          candidates: nested.length,
+        });
+        return null;
+      }
+      mixFile = nested[0]!;
+      logger.info('Found nested Mix project', { mixFile });
+    }
+
+    return {
+      projectDir: path.dirname(mixFile),
+      umbrella: /\bapps_path:/.test(fs.readFileSync(mixFile, 'utf8')),
+    };
+  } catch (error) {
+    logger.error('Failed to look for mix.exs', {
+      workspacePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return null;
+  }
+}
+
+/**
+ * Creates the configuration notification ElixirLS waits for before it
+ * builds the project, pointing it at a nested project directory. Dialyzer
+ * is left off since its first analysis takes minutes.
+ */
+export function createElixirConfigurationNotification(
+  workspacePath: string,
+  projectInfo: MixProjectInfo
+): {
+  method: string;
+  params: unknown;
+} {
+  const projectDir = path
+    .relative(path.resolve(workspacePath), projectInfo.projectDir)
+    .split(path.sep)
+    .join('/');
+
+  return {
+    method: 'workspace/didChangeConfiguration',
+    params: {
+      settings: {
+        elixirLS: {
+          ...(projectDir ? { projectDir } : {}),
+          dialyzerEnabled: false,
+        },
+      },
+    },
+  };
+}
@@ -23,3 +23,33 @@ export function expandEnvVars(
     }
   );
 }
+
+/**
+ * Expands environment variables in every string of a JSON-like value.
+ * A string that is only a reference to an unset variable, such as
+ * `$LICENCE_KEY`, is left out rather than passed on literally.
+ */
+export function expandEnvVarsInValue(
+  value: unknown,
+  env: NodeJS.ProcessEnv = process.env
+): unknown {
+  if (typeof value === 'string') {
+    const expanded = expandEnvVars(value, env);
+    return /^\$(\{[^}]+\}|[A-Za-z_][A-Za-z0-9_]*)$/.test(expanded)
+      ? undefined
+      : expanded;
+  }
+  if (Array.isArray(value)) {
+    return value
+      .map((item) => expandEnvVarsInValue(item, env))
+      .filter((item) => item !== undefined);
+  }
+  if (value && typeof value === 'object') {
+    return Object.fromEntries(
+      Object.entries(value)
+        .map(([key, item]) => [key, expandEnvVarsInValue(item, env)])
+        .filter(([, item]) => item !== undefined)
+    );
+  }
+  return value;
+}
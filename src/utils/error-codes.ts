@@ -0,0 +1,302 @@
+/**
+ * Error codes - stable machine-readable codes and categories for failures
+ */
+
+import * as rpc from 'vscode-jsonrpc';
+import { LSPErrorCodes } from 'vscode-languageserver-protocol';
+import { ZodError } from 'zod';
+
+export const ERROR_CATEGORIES = [
+  'config',
+  'spawn',
+  'protocol',
+  'timeout',
+  'capability',
+  'workspace',
+  'validation',
+  'rate_limit',
+  'auth',
+  'internal',
+] as const;
+
+export type ErrorCategory = (typeof ERROR_CATEGORIES)[number];
+
+export interface ErrorClassification {
+  code: string;
+  category: ErrorCategory;
+}
+
+interface ErrorCodeRule extends ErrorClassification {
+  pattern: RegExp;
+}
+
+// Fallback for errors thrown without a code, matched against the message.
+// Ordered most specific first; the first matching rule wins
+const ERROR_CODE_RULES: ErrorCodeRule[] = [
+  {
+    pattern: /workspace is (still loading|not ready)/i,
+    code: 'WORKSPACE_NOT_READY',
+    category: 'workspace',
+  },
+  {
+    pattern: /changed on disk|^⚠ Stale/i,
+    code: 'STALE_CONTENT',
+    category: 'workspace',
+  },
+  {
+    pattern:
+      /file (is )?not included|not part of (the )?(project|workspace)/i,
+    code: 'FILE_NOT_IN_PROJECT',
+    category: 'workspace',
+  },
+  {
+    pattern:
+      /project (import|build) failed|build path is incomplete|no (project|workspace) (is )?(loaded|found)|no active workspace/i,
+    code: 'PROJECT_NOT_LOADED',
+    category: 'workspace',
+  },
+  {
+    pattern: /file not found|path is not a file|invalid file path/i,
+    code: 'FILE_NOT_FOUND',
+    category: 'validation',
+  },
+  {
+    pattern: /out of bounds|does not exist in file/i,
+    code: 'POSITION_OUT_OF_BOUNDS',
+    category: 'validation',
+  },
+  {
+    pattern: /cannot be renamed/i,
+    code: 'RENAME_INVALID',
+    category: 'validation',
+  },
+  {
+    pattern: /no configured LSP profile handles/i,
+    code: 'UNSUPPORTED_FILE',
+    category: 'config',
+  },
+  {
+    pattern: /unknown LSP profile/i,
+    code: 'UNKNOWN_PROFILE',
+    category: 'config',
+  },
+  {
+    pattern:
+      /invalid configuration|config (file|path)|invalid command for LSP|no language servers? (are )?configured/i,
+    code: 'CONFIG_INVALID',
+    category: 'config',
+  },
+  {
+    pattern: /sandbox is enabled but/i,
+    code: 'SANDBOX_UNAVAILABLE',
+    category: 'spawn',
+  },
+  {
+    pattern:
+      /binary not (found|executable)|command (not found|is not executable)|spawn (\S+ )?ENOENT|EACCES/i,
+    code: 'SPAWN_FAILED',
+    category: 'spawn',
+  },
+  {
+    pattern:
+      /connection (is )?(closed|disposed)|stream (was )?destroyed|write after end|exited with code|terminated by signal/i,
+    code: 'SERVER_EXITED',
+    category: 'spawn',
+  },
+  {
+    pattern:
+      /failed to initialize LSP client|not initialized|is not available/i,
+    code: 'SERVER_NOT_INITIALIZED',
+    category: 'spawn',
+  },
+  {
+    pattern: /timed? ?out|timeout/i,
+    code: 'TIMEOUT',
+    category: 'timeout',
+  },
+  {
+    pattern:
+      /unhandled method|method not found|\(-32601\)|not supported by|does not support/i,
+    code: 'CAPABILITY_UNSUPPORTED',
+    category: 'capability',
+  },
+  {
+    pattern: /content modified|\(-32801\)/i,
+    code: 'CONTENT_MODIFIED',
+    category: 'protocol',
+  },
+  {
+    pattern: /server cancelled|request cancelled|\(-32800\)|\(-32802\)/i,
+    code: 'REQUEST_CANCELLED',
+    category: 'protocol',
+  },
+  {
+    pattern: /failed:/i,
+    code: 'LSP_REQUEST_FAILED',
+    category: 'protocol',
+  },
+];
+
+const INTERNAL_ERROR: ErrorClassification = {
+  code: 'INTERNAL_ERROR',
+  category: 'internal',
+};
+
+// Codes validation gives a request it refuses
+const VALIDATION_ERROR_CODES: Record<string, ErrorClassification> = {
+  INVALID_PATH: { code: 'FILE_NOT_FOUND', category: 'validation' },
+  POSITION_OUT_OF_BOUNDS: {
+    code: 'POSITION_OUT_OF_BOUNDS',
+    category: 'validation',
+  },
+  WORKSPACE_NOT_READY: { code: 'WORKSPACE_NOT_READY', category: 'workspace' },
+};
+
+// JSON-RPC error codes a language server answers a request with
+const RESPONSE_ERROR_CODES: Record<number, ErrorClassification> = {
+  [rpc.ErrorCodes.MethodNotFound]: {
+    code: 'CAPABILITY_UNSUPPORTED',
+    category: 'capability',
+  },
+  [LSPErrorCodes.ContentModified]: {
+    code: 'CONTENT_MODIFIED',
+    category: 'protocol',
+  },
+  [LSPErrorCodes.RequestCancelled]: {
+    code: 'REQUEST_CANCELLED',
+    category: 'protocol',
+  },
+  [LSPErrorCodes.ServerCancelled]: {
+    code: 'REQUEST_CANCELLED',
+    category: 'protocol',
+  },
+};
+
+/**
+ * Error of a failed operation result, as returned by validation and the
+ * LSP operations
+ */
+export interface OperationError {
+  message: string;
+  errorCode: string;
+  originalError?: Error | undefined;
+}
+
+/**
+ * An error that carries its own code and category
+ */
+export class CodedError extends Error {
+  constructor(
+    message: string,
+    readonly code: string,
+    readonly category: ErrorCategory
+  ) {
+    super(message);
+    this.name = 'CodedError';
+  }
+}
+
+/**
+ * Classify an error message. Appended hints are ignored so their wording
+ * can't change the code.
+ */
+export function classifyErrorMessage(message: string): ErrorClassification {
+  const [rawMessage = message] = message.split('\nHint: ');
+  const rule = ERROR_CODE_RULES.find((candidate) =>
+    candidate.pattern.test(rawMessage)
+  );
+  return rule ? { code: rule.code, category: rule.category } : INTERNAL_ERROR;
+}
+
+/**
+ * The code an error was raised with, or null when it has none
+ */
+function getOriginClassification(error: unknown): ErrorClassification | null {
+  if (error instanceof CodedError) {
+    return { code: error.code, category: error.category };
+  }
+  // Servers report most failures with generic codes, whose message says
+  // more than the code does
+  if (error instanceof rpc.ResponseError) {
+    return RESPONSE_ERROR_CODES[error.code] ?? null;
+  }
+  if (error instanceof rpc.ConnectionError) {
+    return { code: 'SERVER_EXITED', category: 'spawn' };
+  }
+  return null;
+}
+
+/**
+ * Classify any thrown value into a stable code and category
+ */
+export function classifyError(error: unknown): ErrorClassification {
+  const origin = getOriginClassification(error);
+  if (origin) {
+    return origin;
+  }
+
+  if (error instanceof ZodError) {
+    return { code: 'INVALID_ARGUMENTS', category: 'validation' };
+  }
+
+  return classifyErrorMessage(
+    error instanceof Error ? error.message : String(error)
+  );
+}
+
+/**
+ * Turn a failed operation result into an error to throw, keeping the code
+ * it failed with: the validation code, or that of the error the language
+ * server or connection raised. Only errors without one are classified by
+ * their message.
+ */
+export function toCodedError(error: OperationError): CodedError {
+  const { code, category } =
+    VALIDATION_ERROR_CODES[error.errorCode] ??
+    getOriginClassification(error.originalError) ??
+    classifyErrorMessage(error.message);
+  return new CodedError(error.message, code, category);
+}
+
+/**
+ * A thrown value as a CodedError, classified when it carries no code
+ */
+export function asCodedError(error: unknown): CodedError {
+  if (error instanceof CodedError) {
+    return error;
+  }
+  const { code, category } = classifyError(error);
+  return new CodedError(
+    error instanceof Error ? error.message : String(error),
+    code,
+    category
+  );
+}
+
+// Codes that say less about a failure than any other
+const GENERIC_ERROR_CODES = new Set([
+  INTERNAL_ERROR.code,
+  'LSP_REQUEST_FAILED',
+]);
+
+/**
+ * One error for several failures, e.g. of each language server a search
+ * asked: a single failure as it is, otherwise every message under the code
+ * of the first failure with a specific one
+ */
+export function combineCodedErrors(errors: CodedError[]): CodedError {
+  const [first] = errors;
+  if (!first) {
+    return new CodedError('Unknown error', INTERNAL_ERROR.code, 'internal');
+  }
+  if (errors.length === 1) {
+    return first;
+  }
+  const specific =
+    errors.find((error) => !GENERIC_ERROR_CODES.has(error.code)) ?? first;
+  return new CodedError(
+    errors.map((error) => error.message).join('\n'),
+    specific.code,
+    specific.category
+  );
+}
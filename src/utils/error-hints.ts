@@ -0,0 +1,62 @@
+/**
+ * Actionable hints for well-known language server error messages
+ */
+
+interface ErrorHintRule {
+  pattern: RegExp;
+  hint: string;
+}
+
+const ERROR_HINT_RULES: ErrorHintRule[] = [
+  {
+    // rust-analyzer (and others) cancel requests when the document changes mid-flight
+    pattern: /content modified|\(-32801\)/i,
+    hint: 'The server cancelled the request because the file changed while it was being analyzed. Retry the same call once indexing settles.',
+  },
+  {
+    pattern: /server cancelled|request cancelled|\(-32800\)|\(-32802\)/i,
+    hint: 'The request was cancelled before completing. The server may still be indexing; retry in a few seconds.',
+  },
+  {
+    // pyright reports files outside its configured roots this way
+    pattern: /file (is )?not included|not part of (the )?(project|workspace)/i,
+    hint: 'The file is excluded by the project configuration (e.g. pyrightconfig.json or [tool.pyright] include/exclude globs). Add it to the include list or open a file inside the configured roots.',
+  },
+  {
+    // jdt.ls surfaces Maven/Gradle import problems like this
+    pattern:
+      /project (import|build) failed|build path is incomplete|cannot (be )?import (the )?project|not on the classpath/i,
+    hint: 'jdt.ls could not import the project. Check that Maven/Gradle can build it from the command line and inspect the profile logs for the underlying import error.',
+  },
+  {
+    pattern: /unhandled method|method not found|\(-32601\)/i,
+    hint: 'This language server does not implement the requested LSP method. Try an alternative tool or a different server for this language.',
+  },
+  {
+    pattern: /no (project|workspace) (is )?(loaded|found)|no active workspace/i,
+    hint: 'The server has no project loaded for this file. Check the profile workspace_files markers and the --workspace path.',
+  },
+  {
+    pattern: /connection (is )?(closed|disposed)|stream (was )?destroyed|write after end/i,
+    hint: 'The language server process exited. Inspect language-servers://profiles/{name}/logs and run reload once the cause is fixed.',
+  },
+];
+
+/**
+ * Find an actionable hint for a raw server error message, if one is known
+ */
+export function getErrorHint(message: string): string | undefined {
+  return ERROR_HINT_RULES.find((rule) => rule.pattern.test(message))?.hint;
+}
+
+/**
+ * Append a hint to the message when the error matches a known pattern
+ */
+export function withErrorHint(message: string): string {
+  const hint = getErrorHint(message);
+  if (!hint || message.includes(hint)) {
+    return message;
+  }
+
+  return `${message}\nHint: ${hint}`;
+}
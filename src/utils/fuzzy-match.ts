@@ -0,0 +1,216 @@
+/**
+ * Fuzzy symbol matching - camel humps (`URB` -> `UserRepositoryBuilder`),
+ * snake_case initials and common abbreviations, scored so results can be
+ * ranked independently of the language server's own matching
+ */
+
+export const MATCH_MODES = ['fuzzy', 'server'] as const;
+
+export type MatchMode = (typeof MATCH_MODES)[number];
+
+export type FuzzyMatchKind =
+  | 'exact'
+  | 'prefix'
+  | 'humps'
+  | 'abbreviation'
+  | 'substring'
+  | 'subsequence';
+
+export interface FuzzyMatch {
+  // 1-100, higher is better
+  score: number;
+  kind: FuzzyMatchKind;
+}
+
+// Common short forms in identifiers and the words they stand for
+const ABBREVIATIONS: Record<string, string[]> = {
+  arg: ['argument'],
+  args: ['arguments'],
+  auth: ['authentication', 'authorization', 'authenticator'],
+  btn: ['button'],
+  cb: ['callback'],
+  cfg: ['config', 'configuration'],
+  conf: ['config', 'configuration'],
+  config: ['configuration'],
+  ctx: ['context'],
+  db: ['database'],
+  del: ['delete'],
+  dest: ['destination'],
+  dir: ['directory'],
+  doc: ['document', 'documentation'],
+  dst: ['destination'],
+  env: ['environment'],
+  err: ['error'],
+  fn: ['function'],
+  func: ['function'],
+  gen: ['generator', 'generate'],
+  idx: ['index'],
+  impl: ['implementation'],
+  info: ['information'],
+  init: ['initialize', 'initializer', 'initialization'],
+  len: ['length'],
+  msg: ['message'],
+  mgr: ['manager'],
+  num: ['number'],
+  param: ['parameter'],
+  params: ['parameters'],
+  pkg: ['package'],
+  ptr: ['pointer'],
+  ref: ['reference'],
+  repo: ['repository'],
+  req: ['request'],
+  res: ['response', 'result', 'resource'],
+  resp: ['response'],
+  spec: ['specification'],
+  src: ['source'],
+  str: ['string'],
+  svc: ['service'],
+  tmp: ['temporary'],
+  util: ['utility', 'utilities'],
+  utils: ['utilities'],
+  val: ['value'],
+  var: ['variable'],
+};
+
+// Abbreviations indexed by the word they expand to
+const ABBREVIATIONS_BY_WORD = new Map<string, string[]>();
+for (const [abbreviation, words] of Object.entries(ABBREVIATIONS)) {
+  for (const word of words) {
+    const existing = ABBREVIATIONS_BY_WORD.get(word) ?? [];
+    existing.push(abbreviation);
+    ABBREVIATIONS_BY_WORD.set(word, existing);
+  }
+}
+
+/**
+ * Split an identifier into lowercase words at camel humps, acronym
+ * boundaries (`HTTPServer` -> `http`, `server`), digits and separators
+ */
+export function splitIdentifierWords(name: string): string[] {
+  return (
+    name
+      .replace(/([a-z0-9])([A-Z])/g, '$1 $2')
+      .replace(/([A-Z]+)([A-Z][a-z])/g, '$1 $2')
+      .replace(/([A-Za-z])(\d)/g, '$1 $2')
+      .match(/[A-Za-z0-9]+/g) ?? []
+  ).map((word) => word.toLowerCase());
+}
+
+interface HumpResult {
+  skipped: number;
+  abbreviated: boolean;
+}
+
+function isBetterHumpResult(a: HumpResult, b: HumpResult | null): boolean {
+  return (
+    !b ||
+    a.skipped < b.skipped ||
+    (a.skipped === b.skipped && !a.abbreviated && b.abbreviated)
+  );
+}
+
+/**
+ * Match the query against word prefixes in order: every word consumes a
+ * prefix of itself or an abbreviation of itself from the query, and words
+ * may be skipped. Returns the match skipping the fewest words.
+ */
+function matchHumps(query: string, words: string[]): HumpResult | null {
+  const memo = new Map<string, HumpResult | null>();
+
+  const match = (queryIndex: number, wordIndex: number): HumpResult | null => {
+    if (queryIndex === query.length) {
+      return { skipped: words.length - wordIndex, abbreviated: false };
+    }
+    const word = words[wordIndex];
+    if (word === undefined) {
+      return null;
+    }
+    const key = `${queryIndex}:${wordIndex}`;
+    const cached = memo.get(key);
+    if (cached !== undefined) {
+      return cached;
+    }
+
+    let best: HumpResult | null = null;
+    for (let length = 1; length <= word.length; length++) {
+      if (query[queryIndex + length - 1] !== word[length - 1]) {
+        break;
+      }
+      const rest = match(queryIndex + length, wordIndex + 1);
+      if (rest && isBetterHumpResult(rest, best)) {
+        best = rest;
+      }
+    }
+    for (const abbreviation of ABBREVIATIONS_BY_WORD.get(word) ?? []) {
+      if (query.startsWith(abbreviation, queryIndex)) {
+        const rest = match(queryIndex + abbreviation.length, wordIndex + 1);
+        const result = rest && { ...rest, abbreviated: true };
+        if (result && isBetterHumpResult(result, best)) {
+          best = result;
+        }
+      }
+    }
+    const skipped = match(queryIndex, wordIndex + 1);
+    const result = skipped && { ...skipped, skipped: skipped.skipped + 1 };
+    if (result && isBetterHumpResult(result, best)) {
+      best = result;
+    }
+
+    memo.set(key, best);
+    return best;
+  };
+
+  return match(0, 0);
+}
+
+// Separators don't take part in matching: `user_repo` matches `UserRepo`
+function normalizeForMatch(text: string): string {
+  return text.replace(/[\s_-]/g, '').toLowerCase();
+}
+
+function isSubsequence(query: string, text: string): boolean {
+  let index = 0;
+  for (const char of text) {
+    if (char === query[index]) {
+      index++;
+    }
+  }
+  return index === query.length;
+}
+
+/**
+ * Score a symbol name against a query, or null when it doesn't match.
+ * Qualified queries (`Repo.find`, `repo::find`) match on their last segment.
+ */
+export function fuzzyMatch(query: string, name: string): FuzzyMatch | null {
+  const lastSegment = query.split(/\.|::|#|\//).at(-1) ?? query;
+  const normalizedQuery = normalizeForMatch(lastSegment);
+  const normalizedName = normalizeForMatch(name);
+  if (normalizedQuery.length === 0) {
+    return null;
+  }
+
+  if (normalizedName === normalizedQuery) {
+    return { score: name === lastSegment ? 100 : 95, kind: 'exact' };
+  }
+  if (normalizedName.startsWith(normalizedQuery)) {
+    return { score: 90, kind: 'prefix' };
+  }
+
+  const words = splitIdentifierWords(name);
+  const humps = matchHumps(normalizedQuery, words);
+  if (humps) {
+    const penalty = Math.min(15, humps.skipped * 3);
+    return humps.abbreviated
+      ? { score: 75 - penalty, kind: 'abbreviation' }
+      : { score: 85 - penalty, kind: 'humps' };
+  }
+
+  if (normalizedName.includes(normalizedQuery)) {
+    return { score: 55, kind: 'substring' };
+  }
+  if (isSubsequence(normalizedQuery, normalizedName)) {
+    return { score: 30, kind: 'subsequence' };
+  }
+  return null;
+}
@@ -0,0 +1,105 @@
+/**
+ * Git helpers for analyses that compare the workspace against history
+ */
+
+import * as cp from 'child_process';
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import logger from './logger.js';
+
+function runGit(cwd: string, args: string[]): string {
+  return cp.execFileSync('git', args, {
+    cwd,
+    encoding: 'utf-8',
+    stdio: ['ignore', 'pipe', 'pipe'],
+    maxBuffer: 64 * 1024 * 1024,
+  });
+}
+
+/**
+ * Check out `ref` into a temporary worktree and run `fn` with the path that
+ * corresponds to `workspace` inside it. The worktree is always removed.
+ */
+export async function withGitWorktree<T>(
+  workspace: string,
+  ref: string,
+  fn: (worktreeWorkspace: string) => Promise<T>
+): Promise<T> {
+  let repositoryRoot: string;
+  try {
+    repositoryRoot = runGit(workspace, ['rev-parse', '--show-toplevel']).trim();
+    runGit(workspace, ['rev-parse', '--verify', '--quiet', `${ref}^{commit}`]);
+  } catch {
+    throw new Error(
+      `Baseline is neither a snapshot file nor a git revision: ${ref}`
+    );
+  }
+
+  const worktreePath = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-'));
+  runGit(repositoryRoot, ['worktree', 'add', '--detach', worktreePath, ref]);
+
+  try {
+    const subdirectory = path.relative(
+      fs.realpathSync(repositoryRoot),
+      fs.realpathSync(workspace)
+    );
+    return await fn(path.join(worktreePath, subdirectory));
+  } finally {
+    try {
+      runGit(repositoryRoot, ['worktree', 'remove', '--force', worktreePath]);
+    } catch (error) {
+      logger.warn('Failed to remove baseline worktree', {
+        worktreePath,
+        error: error instanceof Error ? error.message : String(error),
+      });
+    }
+  }
+}
+
+/**
+ * Number of commits touching each file since `since` (any git date, e.g.
+ * `90 days ago`), keyed by workspace-relative path. Empty outside a repo.
+ */
+export function getFileChurn(
+  workspace: string,
+  since: string
+): Map<string, number> {
+  const churn = new Map<string, number>();
+  let output: string;
+  let repositoryRoot: string;
+  try {
+    repositoryRoot = runGit(workspace, ['rev-parse', '--show-toplevel']).trim();
+    output = runGit(workspace, [
+      'log',
+      `--since=${since}`,
+      '--name-only',
+      '--format=',
+      '--',
+      '.',
+    ]);
+  } catch (error) {
+    logger.debug('Git history unavailable for churn', {
+      workspace,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return churn;
+  }
+
+  const workspaceRoot = fs.realpathSync(workspace);
+  const root = fs.realpathSync(repositoryRoot);
+  for (const line of output.split('\n')) {
+    const file = line.trim();
+    if (!file) {
+      continue;
+    }
+    const relativePath = path
+      .relative(workspaceRoot, path.join(root, file))
+      .split(path.sep)
+      .join('/');
+    if (!relativePath.startsWith('../')) {
+      churn.set(relativePath, (churn.get(relativePath) ?? 0) + 1);
+    }
+  }
+  return churn;
+}
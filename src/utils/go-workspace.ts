@@ -0,0 +1,115 @@
+/**
+ * Go Workspace Detection Utilities
+ * Finds the go.work file or go.mod module roots gopls should load
+ */
+
+import path from 'path';
+import { pathToFileURL } from 'url';
+import { glob } from 'glob';
+import logger from './logger.js';
+
+// Modules nested deeper than this below the workspace aren't looked for
+const MAX_MODULE_DEPTH = 5;
+
+export type GoWorkspaceInfo =
+  | {
+      type: 'workspace';
+      workFilePath: string;
+      moduleRoots?: undefined;
+    }
+  | {
+      type: 'modules';
+      workFilePath?: undefined;
+      moduleRoots: string[];
+    };
+
+/**
+ * Detects a go.work file at the workspace root, otherwise the directories
+ * holding a go.mod file
+ */
+export async function detectGoWorkspace(
+  workspacePath: string
+): Promise<GoWorkspaceInfo | null> {
+  try {
+    logger.debug('Detecting Go workspace', { workspacePath });
+    const root = path.resolve(workspacePath);
+
+    // gopls reads the modules a go.work file lists by itself
+    const workFiles = await glob('go.work', { cwd: root, absolute: true });
+    if (workFiles.length > 0) {
+      logger.info('Found Go workspace file', { workFilePath: workFiles[0] });
+      return {
+        type: 'workspace' as const,
+        workFilePath: workFiles[0]!, // Safe because workFiles.length > 0
+      };
+    }
+
+    const moduleFiles = await glob('**/go.mod', {
+      cwd: root,
+      absolute: true,
+      maxDepth: MAX_MODULE_DEPTH + 1,
+      ignore: [
+        '**/node_modules/**',
+        '**/vendor/**',
+        '**/testdata/**',
+        '**/.git/**',
+      ],
+    });
+
+    if (moduleFiles.length > 0) {
+      const moduleRoots = moduleFiles.map((file) => path.dirname(file)).sort();
+      logger.info('Found Go modules', { moduleRoots });
+      return {
+        type: 'modules' as const,
+        moduleRoots,
+      };
+    }
+
+    logger.debug('No Go workspace files found');
+    return null;
+  } catch (error) {
+    logger.error('Failed to detect Go workspace', {
+      workspacePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return null;
+  }
+}
+
+/**
+ * Creates the notification adding modules below the workspace root as
+ * workspace folders, since gopls only loads the module at a folder's root.
+ * Returns null when a go.work file or the root module covers everything.
+ */
+export function createGoWorkspaceFoldersNotification(
+  workspacePath: string,
+  workspaceInfo: GoWorkspaceInfo
+): {
+  method: string;
+  params: unknown;
+} | null {
+  if (workspaceInfo.type === 'workspace') {
+    return null;
+  }
+
+  const root = path.resolve(workspacePath);
+  const nestedRoots = workspaceInfo.moduleRoots.filter(
+    (moduleRoot) => moduleRoot !== root
+  );
+  if (nestedRoots.length === 0) {
+    return null;
+  }
+
+  return {
+    method: 'workspace/didChangeWorkspaceFolders',
+    params: {
+      event: {
+        added: nestedRoots.map((moduleRoot) => ({
+          uri: pathToFileURL(moduleRoot).toString(),
+          name: path.relative(root, moduleRoot).split(path.sep).join('/'),
+        })),
+        removed: [],
+      },
+    },
+  };
+}
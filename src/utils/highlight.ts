@@ -0,0 +1,164 @@
+/**
+ * Syntax highlighting - ANSI colors for YAML, JSON and markdown reports
+ * printed by CLI commands
+ */
+
+export const COLOR_MODES = ['auto', 'always', 'never'] as const;
+
+export type ColorMode = (typeof COLOR_MODES)[number];
+
+const ANSI = {
+  reset: '\x1b[0m',
+  gray: '\x1b[90m',
+  cyan: '\x1b[36m',
+  green: '\x1b[32m',
+  yellow: '\x1b[33m',
+  magenta: '\x1b[35m',
+};
+
+type Color = Exclude<keyof typeof ANSI, 'reset'>;
+
+// indent, optional list marker, optional `key:` and the remaining value
+const YAML_LINE_PATTERN =
+  /^(\s*)(- )?(?:('[^']*'|"[^"]*"|[^\s:#'"][^:#]*?):(\s|$))?(.*)$/;
+
+// A `file:line` or `file:line:character` location, with the symbol kind that
+// lookup results print right after it
+const LOCATION_PATTERN = /(\S+?:\d+(?::\d+)?)(?=\s|$)(?: ([A-Z]\w*)(?= ))?/g;
+
+function paint(text: string, color: Color): string {
+  return text.length > 0 ? `${ANSI[color]}${text}${ANSI.reset}` : text;
+}
+
+/**
+ * Decide whether to emit colors. `auto` only colors interactive terminals
+ * and honors the NO_COLOR convention.
+ */
+export function shouldUseColor(
+  mode: ColorMode,
+  stream: { isTTY?: boolean } = process.stdout,
+  env: NodeJS.ProcessEnv = process.env
+): boolean {
+  switch (mode) {
+    case 'always':
+      return true;
+    case 'never':
+      return false;
+    default:
+      return Boolean(stream.isTTY) && !env.NO_COLOR && env.TERM !== 'dumb';
+  }
+}
+
+/**
+ * Color a scalar value by its apparent type
+ */
+function highlightScalar(value: string): string {
+  const trimmed = value.trim();
+  if (/^(['"]).*\1$/.test(trimmed)) {
+    return paint(value, 'green');
+  }
+  if (/^(true|false|null|~)$/.test(trimmed)) {
+    return paint(value, 'magenta');
+  }
+  if (/^-?\d+(\.\d+)?([eE][-+]?\d+)?$/.test(trimmed)) {
+    return paint(value, 'yellow');
+  }
+  return value;
+}
+
+/**
+ * Highlight YAML line by line: comments, keys, list markers and scalars
+ */
+export function highlightYaml(text: string): string {
+  return text
+    .split('\n')
+    .map((line) => {
+      if (/^\s*#/.test(line)) {
+        return paint(line, 'gray');
+      }
+
+      const match = YAML_LINE_PATTERN.exec(line);
+      if (!match) {
+        return line;
+      }
+
+      const [, indent = '', marker = '', key, separator = '', rest = ''] =
+        match;
+      const commentIndex = rest.search(/\s#/);
+      const value = commentIndex >= 0 ? rest.slice(0, commentIndex) : rest;
+      const comment = commentIndex >= 0 ? rest.slice(commentIndex) : '';
+
+      return (
+        indent +
+        paint(marker, 'gray') +
+        (key !== undefined ? `${paint(key, 'cyan')}:${separator}` : '') +
+        highlightScalar(value) +
+        paint(comment, 'gray')
+      );
+    })
+    .join('\n');
+}
+
+/**
+ * Highlight JSON tokens: keys, strings, numbers and literals
+ */
+export function highlightJson(text: string): string {
+  return text.replace(
+    /("(?:\\.|[^"\\])*")(\s*:)?|\b(true|false|null)\b|-?\d+(?:\.\d+)?(?:[eE][-+]?\d+)?/g,
+    (token: string, str?: string, colon?: string, literal?: string) => {
+      if (str !== undefined) {
+        return colon !== undefined
+          ? `${paint(str, 'cyan')}${colon}`
+          : paint(str, 'green');
+      }
+      if (literal !== undefined) {
+        return paint(token, 'magenta');
+      }
+      return paint(token, 'yellow');
+    }
+  );
+}
+
+/**
+ * Highlight a markdown report line by line: headings, list markers,
+ * locations and symbol kinds
+ */
+export function highlightMarkdown(text: string): string {
+  return text
+    .split('\n')
+    .map((line) => {
+      if (/^#+ /.test(line)) {
+        return paint(line, 'cyan');
+      }
+      const [, marker = '', rest = ''] = /^(\s*- )?(.*)$/.exec(line) ?? [];
+      return (
+        paint(marker, 'gray') +
+        rest
+          .replace(
+            LOCATION_PATTERN,
+            (_match, location: string, kind?: string) =>
+              paint(location, 'green') +
+              (kind !== undefined ? ` ${paint(kind, 'magenta')}` : '')
+          )
+          .replace(
+            / \(([A-Z]\w*)\)/g,
+            (_match, kind: string) => ` (${paint(kind, 'magenta')})`
+          )
+      );
+    })
+    .join('\n');
+}
+
+/**
+ * Highlight a report for the terminal when `mode` allows colors
+ */
+export function highlightReport(
+  text: string,
+  format: 'markdown' | 'json',
+  mode: ColorMode
+): string {
+  if (!shouldUseColor(mode)) {
+    return text;
+  }
+  return format === 'json' ? highlightJson(text) : highlightMarkdown(text);
+}
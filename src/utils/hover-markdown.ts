@@ -0,0 +1,306 @@
+/**
+ * Hover markdown - normalize hover contents and render them as readable text
+ */
+
+import type { Hover, MarkedString } from 'vscode-languageserver-protocol';
+
+export const HOVER_FORMATS = ['text', 'markdown', 'sections'] as const;
+
+export type HoverFormat = (typeof HOVER_FORMATS)[number];
+
+const FENCE_PATTERN = /^\s*(```|~~~)/;
+// Headings and doc comment tags that start examples, e.g. `# Examples` or
+// `*@example*`; any other heading or tag ends them
+const EXAMPLES_START_PATTERN = /^\s*(#{1,6}\s+examples?\b|[*_]*@example\b)/i;
+const SECTION_START_PATTERN = /^\s*(#{1,6}\s|[*_]*@\w)/;
+
+interface HoverSections {
+  // Code fences before any prose, without the fences
+  signature: string | null;
+  signatureLanguage: string;
+  documentation: string | null;
+  examples: string[];
+}
+
+const HTML_ENTITIES: Record<string, string> = {
+  '&nbsp;': ' ',
+  '&lt;': '<',
+  '&gt;': '>',
+  '&quot;': '"',
+  '&#39;': "'",
+  '&amp;': '&',
+};
+
+/**
+ * Turn a MarkedString into markdown, fencing language-tagged code
+ */
+function markedStringToMarkdown(item: MarkedString): string {
+  if (typeof item === 'string') {
+    return item;
+  }
+  return `\`\`\`${item.language}\n${item.value}\n\`\`\``;
+}
+
+/**
+ * Normalize every shape of hover contents into a single markdown string
+ */
+export function hoverToMarkdown(hover: Hover): string {
+  const contents = hover.contents;
+
+  if (Array.isArray(contents)) {
+    return contents
+      .map((item) => markedStringToMarkdown(item).trim())
+      .filter(Boolean)
+      .join('\n\n');
+  }
+
+  if (typeof contents === 'object' && 'kind' in contents) {
+    return contents.value;
+  }
+
+  return markedStringToMarkdown(contents).trim();
+}
+
+/**
+ * Strip markdown syntax from a prose line while keeping inline code intact
+ */
+function renderProseLine(line: string): string {
+  if (/^\s*([-*_])(\s*\1){2,}\s*$/.test(line)) {
+    return '';
+  }
+
+  return line
+    .split(/(`[^`]*`)/)
+    .map((part) => {
+      if (part.startsWith('`')) {
+        return part;
+      }
+      return part
+        .replace(/!\[[^\]]*\]\([^)]*\)/g, '')
+        .replace(/\[([^\]]+)\]\([^)]*\)/g, '$1')
+        .replace(/<br\s*\/?>/gi, '')
+        .replace(/\*\*(\S(?:.*?\S)?)\*\*/g, '$1')
+        .replace(/\*(\S(?:.*?\S)?)\*/g, '$1')
+        .replace(
+          /&(nbsp|lt|gt|quot|#39|amp);/g,
+          (entity) => HTML_ENTITIES[entity] ?? entity
+        )
+        .replace(/\\([\\`*_{}[\]()#+.!|<>-])/g, '$1');
+    })
+    .join('')
+    .replace(/^(\s*)#{1,6}\s+/, '$1');
+}
+
+/**
+ * Render hover markdown as text. Code fences are kept verbatim so
+ * signatures stay on their own lines; prose loses markdown syntax.
+ */
+export function renderHoverMarkdown(markdown: string): string {
+  const output: string[] = [];
+  let inFence = false;
+
+  for (const line of markdown.split(/\r?\n/)) {
+    if (FENCE_PATTERN.test(line)) {
+      inFence = !inFence;
+      output.push(line.trim());
+      continue;
+    }
+
+    output.push(inFence ? line : renderProseLine(line).trimEnd());
+  }
+
+  return output
+    .join('\n')
+    .replace(/\n{3,}/g, '\n\n')
+    .trim();
+}
+
+function isPlainTextHover(hover: Hover): boolean {
+  return (
+    typeof hover.contents === 'object' &&
+    'kind' in hover.contents &&
+    hover.contents.kind === 'plaintext'
+  );
+}
+
+/**
+ * Split a hover into its signature, its documentation and its examples.
+ * The signature is the code fenced before any prose; examples run from an
+ * examples heading or `@example` tag to the next heading or tag.
+ */
+function splitHoverSections(hover: Hover): HoverSections {
+  const markdown = hoverToMarkdown(hover).trim();
+
+  if (isPlainTextHover(hover)) {
+    const [signature, ...paragraphs] = markdown.split(/\r?\n\s*\r?\n/);
+    return {
+      signature: signature || null,
+      signatureLanguage: '',
+      documentation: paragraphs.join('\n\n').trim() || null,
+      examples: [],
+    };
+  }
+
+  const signature: string[] = [];
+  let signatureLanguage = '';
+  const documentation: string[] = [];
+  const examples: string[][] = [];
+  let fence: string[] | null = null;
+  let seenProse = false;
+  let inExamples = false;
+
+  for (const line of markdown.split(/\r?\n/)) {
+    const target = inExamples ? examples[examples.length - 1]! : documentation;
+
+    if (FENCE_PATTERN.test(line)) {
+      if (!fence) {
+        fence = [line.trim()];
+        continue;
+      }
+      fence.push(line.trim());
+      if (seenProse) {
+        target.push(...fence);
+      } else {
+        signature.push(...fence.slice(1, -1));
+        signatureLanguage = fence[0]!.replace(FENCE_PATTERN, '').trim();
+      }
+      fence = null;
+      continue;
+    }
+    if (fence) {
+      fence.push(line);
+      continue;
+    }
+
+    if (EXAMPLES_START_PATTERN.test(line)) {
+      seenProse = true;
+      inExamples = true;
+      // Text after an `@example` tag starts the example, maybe with a fence
+      const rest = line
+        .replace(EXAMPLES_START_PATTERN, '')
+        .replace(/^[\s*_:—-]+/, '');
+      if (FENCE_PATTERN.test(rest)) {
+        examples.push([]);
+        fence = [rest.trim()];
+      } else {
+        examples.push(rest ? [renderProseLine(rest).trimEnd()] : []);
+      }
+      continue;
+    }
+    if (SECTION_START_PATTERN.test(line)) {
+      inExamples = false;
+    }
+
+    const prose = renderProseLine(line).trimEnd();
+    if (prose.trim()) {
+      seenProse = true;
+    }
+    (inExamples ? examples[examples.length - 1]! : documentation).push(prose);
+  }
+  // An unclosed fence runs to the end of the hover
+  if (fence) {
+    (inExamples ? examples[examples.length - 1]! : documentation).push(
+      ...fence
+    );
+  }
+
+  const join = (lines: string[]) =>
+    lines
+      .join('\n')
+      .replace(/\n{3,}/g, '\n\n')
+      .trim();
+  return {
+    signature: join(signature) || null,
+    signatureLanguage,
+    documentation: join(documentation) || null,
+    examples: examples.map(join).filter(Boolean),
+  };
+}
+
+function formatHoverSections(hover: Hover): string | null {
+  const { signature, signatureLanguage, documentation, examples } =
+    splitHoverSections(hover);
+
+  const sections: string[] = [];
+  if (signature) {
+    sections.push(
+      `Signature\n\`\`\`${signatureLanguage}\n${signature}\n\`\`\``
+    );
+  }
+  if (documentation) {
+    sections.push(`Documentation\n${documentation}`);
+  }
+  if (examples.length > 0) {
+    sections.push(`Examples\n${examples.join('\n\n')}`);
+  }
+  return sections.length > 0 ? sections.join('\n\n') : null;
+}
+
+/**
+ * Format hover contents for output as rendered text, raw markdown, or
+ * signature, documentation and examples sections
+ */
+export function formatHoverContents(
+  hover: Hover,
+  format: HoverFormat = 'text'
+): string | null {
+  const markdown = hoverToMarkdown(hover);
+  if (markdown.trim().length === 0) {
+    return null;
+  }
+
+  if (format === 'sections') {
+    return formatHoverSections(hover);
+  }
+
+  if (format === 'markdown' || isPlainTextHover(hover)) {
+    return markdown.trim();
+  }
+
+  return renderHoverMarkdown(markdown);
+}
+
+/**
+ * Documentation text of a hover without its signature: the prose left once
+ * code fences, headings and horizontal rules are removed. Plaintext hovers
+ * have no fences, so their first paragraph is taken as the signature.
+ */
+export function extractHoverDocumentation(hover: Hover): string | null {
+  const markdown = hoverToMarkdown(hover);
+  let documentation: string;
+
+  if (
+    typeof hover.contents === 'object' &&
+    'kind' in hover.contents &&
+    hover.contents.kind === 'plaintext'
+  ) {
+    documentation = markdown
+      .trim()
+      .split(/\r?\n\s*\r?\n/)
+      .slice(1)
+      .join('\n\n');
+  } else {
+    const prose: string[] = [];
+    let inFence = false;
+    for (const line of markdown.split(/\r?\n/)) {
+      if (FENCE_PATTERN.test(line)) {
+        inFence = !inFence;
+        continue;
+      }
+      if (!inFence && !/^\s*#/.test(line)) {
+        prose.push(renderProseLine(line).trimEnd());
+      }
+    }
+    documentation = prose.join('\n').replace(/\n{3,}/g, '\n\n');
+  }
+
+  documentation = documentation.trim();
+  return documentation.length > 0 ? documentation : null;
+}
+
+/**
+ * Whether a hover carries documentation beyond the signature
+ */
+export function hasHoverDocumentation(hover: Hover): boolean {
+  return extractHoverDocumentation(hover) !== null;
+}
@@ -0,0 +1,77 @@
+/**
+ * Inlay hints - interleave inferred types and parameter names into source
+ * lines so code reads carry what an editor would show inline
+ */
+
+import type { InlayHint, Position } from 'vscode-languageserver-protocol';
+
+/**
+ * Render a hint label with the padding the server asked for
+ */
+function formatInlayHintLabel(hint: InlayHint): string {
+  const label =
+    typeof hint.label === 'string'
+      ? hint.label
+      : hint.label.map((part) => part.value).join('');
+
+  return `${hint.paddingLeft ? ' ' : ''}${label}${hint.paddingRight ? ' ' : ''}`;
+}
+
+export interface HintedLines {
+  lines: string[];
+  // Move an original position to where it ended up after interleaving
+  mapPosition(position: Position): Position;
+}
+
+/**
+ * Insert hint labels into the lines at their positions. Hints at the same
+ * position keep the server's order.
+ */
+export function interleaveInlayHints(
+  lines: string[],
+  hints: InlayHint[]
+): HintedLines {
+  const hintsByLine = new Map<number, { character: number; text: string }[]>();
+  for (const hint of hints) {
+    const { line, character } = hint.position;
+    const lineHints = hintsByLine.get(line) ?? [];
+    lineHints.push({ character, text: formatInlayHintLabel(hint) });
+    hintsByLine.set(line, lineHints);
+  }
+
+  const hintedLines = lines.map((line, index) => {
+    const lineHints = hintsByLine.get(index);
+    if (!lineHints) {
+      return line;
+    }
+
+    // Stable sort, so equal positions keep the server's order
+    const sorted = [...lineHints].sort(
+      (left, right) => left.character - right.character
+    );
+    let result = '';
+    let cursor = 0;
+    for (const hint of sorted) {
+      const character = Math.min(
+        Math.max(hint.character, cursor),
+        line.length
+      );
+      result += line.substring(cursor, character) + hint.text;
+      cursor = character;
+    }
+    return result + line.substring(cursor);
+  });
+
+  return {
+    lines: hintedLines,
+    mapPosition: (position) => {
+      const shift = (hintsByLine.get(position.line) ?? [])
+        .filter((hint) => hint.character < position.character)
+        .reduce((total, hint) => total + hint.text.length, 0);
+      return {
+        line: position.line,
+        character: position.character + shift,
+      };
+    },
+  };
+}
@@ -0,0 +1,52 @@
+/**
+ * Julia Workspace Detection Utilities
+ * Finds the Project.toml environment LanguageServer.jl resolves packages in
+ */
+
+import fs from 'fs';
+import path from 'path';
+import { globSync } from 'glob';
+import logger from './logger.js';
+
+// Project files nested deeper than this below the workspace aren't looked for
+const MAX_PROJECT_DEPTH = 2;
+
+const PROJECT_FILES = ['JuliaProject.toml', 'Project.toml'];
+
+/**
+ * Directory of the Julia environment for the workspace: the one at the root,
+ * or the only one below it. A package's docs/ and test/ environments don't
+ * count as others when the root has one.
+ */
+export function findJuliaProject(workspacePath: string): string | null {
+  try {
+    const root = path.resolve(workspacePath);
+    if (PROJECT_FILES.some((name) => fs.existsSync(path.join(root, name)))) {
+      return root;
+    }
+
+    const nested = globSync(`**/{${PROJECT_FILES.join(',')}}`, {
+      cwd: root,
+      absolute: true,
+      maxDepth: MAX_PROJECT_DEPTH + 1,
+      ignore: ['**/docs/**', '**/test/**', '**/.git/**'],
+    });
+    const projectDirs = [...new Set(nested.map((file) => path.dirname(file)))];
+    if (projectDirs.length === 1) {
+      logger.info('Found nested Julia project', { project: projectDirs[0] });
+      return projectDirs[0]!;
+    }
+
+    logger.debug('No single Julia project found', {
+      workspacePath,
+      candidates: projectDirs.length,
+    });
+    return null;
+  } catch (error) {
+    logger.error('Failed to look for Project.toml', {
+      workspacePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return null;
+  }
+}
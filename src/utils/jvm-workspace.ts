@@ -0,0 +1,78 @@
+/**
+ * JVM Workspace Detection Utilities
+ * Finds the build files Java, Kotlin and Scala servers import
+ */
+
+import path from 'path';
+import { glob } from 'glob';
+import logger from './logger.js';
+
+// Build files nested deeper than this below the workspace aren't looked for
+const MAX_BUILD_FILE_DEPTH = 4;
+
+const BUILD_FILES = {
+  maven: ['pom.xml'],
+  gradle: [
+    'build.gradle',
+    'build.gradle.kts',
+    'settings.gradle',
+    'settings.gradle.kts',
+  ],
+  sbt: ['build.sbt'],
+  mill: ['build.sc', 'build.mill'],
+} as const;
+
+export type JvmBuildTool = keyof typeof BUILD_FILES;
+
+export interface JvmWorkspaceInfo {
+  buildTools: JvmBuildTool[];
+  // Absolute paths, sorted
+  buildFiles: string[];
+}
+
+/**
+ * Detects the Maven, Gradle, sbt and Mill build files in the workspace,
+ * skipping build output and dependency directories
+ */
+export async function detectJvmWorkspace(
+  workspacePath: string
+): Promise<JvmWorkspaceInfo | null> {
+  try {
+    logger.debug('Detecting JVM workspace', { workspacePath });
+    const root = path.resolve(workspacePath);
+    const names = Object.values(BUILD_FILES).flat();
+
+    const buildFiles = await glob(`**/{${names.join(',')}}`, {
+      cwd: root,
+      absolute: true,
+      maxDepth: MAX_BUILD_FILE_DEPTH + 1,
+      ignore: [
+        '**/node_modules/**',
+        '**/target/**',
+        '**/build/**',
+        '**/.gradle/**',
+        '**/.bloop/**',
+        '**/.metals/**',
+        '**/.git/**',
+      ],
+    });
+
+    if (buildFiles.length === 0) {
+      logger.debug('No JVM build files found');
+      return null;
+    }
+
+    const found = new Set(buildFiles.map((file) => path.basename(file)));
+    const buildTools = (Object.keys(BUILD_FILES) as JvmBuildTool[]).filter(
+      (tool) => BUILD_FILES[tool].some((name) => found.has(name))
+    );
+    logger.info('Found JVM build files', { buildTools, buildFiles });
+    return { buildTools, buildFiles: buildFiles.sort() };
+  } catch (error) {
+    logger.error('Failed to detect JVM workspace', {
+      workspacePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return null;
+  }
+}
@@ -0,0 +1,117 @@
+/**
+ * Path output - render file paths in results in one consistent form
+ */
+
+import * as path from 'path';
+import { pathToFileURL } from 'url';
+import { defineWorkspaceState } from '../runtime/workspace-context.js';
+
+export const PATH_STYLES = ['relative', 'absolute', 'uri'] as const;
+
+export type PathStyle = (typeof PATH_STYLES)[number];
+
+interface PathOutputSettings {
+  style: PathStyle;
+  // Directory relative paths are resolved against, normally the workspace
+  baseDirectory: string;
+}
+
+const getSettings = defineWorkspaceState<PathOutputSettings>(() => ({
+  style: 'relative',
+  baseDirectory: process.cwd(),
+}));
+
+// URIs with a scheme other than file, such as jdt:// for classes inside
+// dependency jars. Two characters at least, so drive letters don't match.
+const VIRTUAL_URI_PATTERN = /^(?!file:)[a-z][a-z0-9+.-]+:/i;
+
+/**
+ * Whether a server result points at a document that isn't a file on disk
+ */
+export function isVirtualUri(pathOrUri: string): boolean {
+  return VIRTUAL_URI_PATTERN.test(pathOrUri);
+}
+
+/**
+ * Update the path style and base directory used for output
+ */
+export function configurePathOutput(update: Partial<PathOutputSettings>): void {
+  const settings = getSettings();
+  if (update.style) {
+    settings.style = update.style;
+  }
+  if (update.baseDirectory) {
+    settings.baseDirectory = path.resolve(update.baseDirectory);
+  }
+}
+
+/**
+ * Convert a path or file:// URI into a decoded absolute path
+ */
+export function toAbsoluteFilePath(pathOrUri: string): string {
+  let cleanPath = pathOrUri.replace('file://', '');
+
+  // Decode URL encoding (like %40 -> @)
+  try {
+    cleanPath = decodeURIComponent(cleanPath);
+  } catch {
+    // If decoding fails, use the original path
+  }
+
+  return path.resolve(getSettings().baseDirectory, cleanPath);
+}
+
+/**
+ * Path relative to the base directory, or the absolute path when the file
+ * lives outside it
+ */
+export function toRelativeFilePath(pathOrUri: string): string {
+  const absolutePath = toAbsoluteFilePath(pathOrUri);
+  const relativePath = path.relative(
+    getSettings().baseDirectory,
+    absolutePath
+  );
+
+  if (
+    relativePath === '' ||
+    relativePath === '..' ||
+    relativePath.startsWith(`..${path.sep}`) ||
+    path.isAbsolute(relativePath)
+  ) {
+    return absolutePath;
+  }
+  return relativePath;
+}
+
+/**
+ * Format a path or URI for output using the configured style. Virtual URIs
+ * are kept as they are, since there is no file to point at.
+ */
+export function formatOutputPath(
+  pathOrUri: string,
+  style: PathStyle = getSettings().style
+): string {
+  if (isVirtualUri(pathOrUri)) {
+    return pathOrUri;
+  }
+  switch (style) {
+    case 'absolute':
+      return toAbsoluteFilePath(pathOrUri);
+    case 'uri':
+      return pathToFileURL(toAbsoluteFilePath(pathOrUri)).href;
+    default:
+      return toRelativeFilePath(pathOrUri);
+  }
+}
+
+/**
+ * Format a path for reports using the configured style. Relative paths use
+ * forward slashes, so reports read the same on every platform.
+ */
+export function formatReportPath(pathOrUri: string): string {
+  const formatted = formatOutputPath(pathOrUri);
+  if (getSettings().style !== 'relative' || isVirtualUri(pathOrUri)) {
+    return formatted;
+  }
+  return formatted.split(path.sep).join('/');
+}
@@ -0,0 +1,38 @@
+/**
+ * PHP Workspace Detection Utilities
+ * Finds the Composer projects whose dependencies Intelephense indexes
+ */
+
+import path from 'path';
+import { glob } from 'glob';
+import logger from './logger.js';
+
+// composer.json files nested deeper than this aren't looked for
+const MAX_COMPOSER_DEPTH = 3;
+
+/**
+ * Directories holding a composer.json, skipping installed dependencies
+ */
+export async function findComposerRoots(
+  workspacePath: string
+): Promise<string[]> {
+  try {
+    const root = path.resolve(workspacePath);
+    const composerFiles = await glob('**/composer.json', {
+      cwd: root,
+      absolute: true,
+      maxDepth: MAX_COMPOSER_DEPTH + 1,
+      ignore: ['**/vendor/**', '**/node_modules/**', '**/.git/**'],
+    });
+
+    const composerRoots = composerFiles.map((file) => path.dirname(file));
+    logger.debug('Found Composer projects', { composerRoots });
+    return composerRoots.sort();
+  } catch (error) {
+    logger.error('Failed to look for composer.json', {
+      workspacePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return [];
+  }
+}
@@ -0,0 +1,73 @@
+/**
+ * Qualified symbol names built from the document symbol hierarchy
+ */
+
+import * as path from 'path';
+
+// Languages whose paths are conventionally written with `::`
+const DOUBLE_COLON_EXTENSIONS = new Set([
+  '.rs',
+  '.c',
+  '.h',
+  '.cpp',
+  '.cc',
+  '.cxx',
+  '.hpp',
+  '.hh',
+  '.hxx',
+  '.C',
+  '.H',
+  '.rb',
+  '.rake',
+]);
+
+// Directories that hold sources rather than name a module
+const SOURCE_ROOTS = new Set(['src', 'lib', 'source']);
+// Files that stand for their directory's module
+const MODULE_INDEX_FILES = new Set(['index', 'mod', '__init__', 'lib']);
+
+/**
+ * Module path of a workspace-relative file, e.g. `models/user` for
+ * `src/models/user.ts` or `models` for `models/__init__.py`
+ */
+export function getModulePath(filePath: string): string[] {
+  const parsed = path.posix.parse(filePath.replace(/\\/g, '/'));
+  const segments = parsed.dir.split('/').filter(Boolean);
+  if (!MODULE_INDEX_FILES.has(parsed.name)) {
+    segments.push(parsed.name);
+  }
+  if (SOURCE_ROOTS.has(segments[0] ?? '')) {
+    segments.shift();
+  }
+  return segments;
+}
+
+/**
+ * Pick the separator used to join qualified names for a file's language
+ */
+export function getQualifiedNameSeparator(filePath: string): string {
+  const extension = path.extname(filePath);
+  if (DOUBLE_COLON_EXTENSIONS.has(extension)) {
+    return '::';
+  }
+  if (extension === '.php') {
+    return '\\';
+  }
+  return '.';
+}
+
+/**
+ * Join a symbol name with its container path, e.g. `module::Type::method`
+ * or `package.Class.method`
+ */
+export function formatQualifiedName(
+  name: string,
+  containerPath: string[] | undefined,
+  filePath: string
+): string {
+  if (!containerPath || containerPath.length === 0) {
+    return name;
+  }
+
+  return [...containerPath, name].join(getQualifiedNameSeparator(filePath));
+}
@@ -0,0 +1,70 @@
+/**
+ * Ruby Workspace Detection Utilities
+ * Finds the Gemfile of the workspace and whether it bundles a server
+ */
+
+import fs from 'fs';
+import path from 'path';
+import { globSync } from 'glob';
+import logger from './logger.js';
+
+// Gemfiles nested deeper than this below the workspace aren't looked for
+const MAX_GEMFILE_DEPTH = 2;
+
+/**
+ * The Gemfile at the workspace root, or the only one in a directory below
+ * it, as in a repository with the Rails app under backend/
+ */
+export function findGemfile(workspacePath: string): string | null {
+  try {
+    const root = path.resolve(workspacePath);
+    const rootGemfile = path.join(root, 'Gemfile');
+    if (fs.existsSync(rootGemfile)) {
+      return rootGemfile;
+    }
+
+    const nested = globSync('**/Gemfile', {
+      cwd: root,
+      absolute: true,
+      maxDepth: MAX_GEMFILE_DEPTH + 1,
+      ignore: ['**/node_modules/**', '**/vendor/**', '**/.git/**'],
+    });
+    if (nested.length === 1) {
+      logger.info('Found nested Gemfile', { gemfile: nested[0] });
+      return nested[0]!;
+    }
+
+    logger.debug('No single Gemfile found', {
+      workspacePath,
+      candidates: nested.length,
+    });
+    return null;
+  } catch (error) {
+    logger.error('Failed to look for a Gemfile', {
+      workspacePath,
+      error: error instanceof Error ? error.message : String(error),
+    });
+    return null;
+  }
+}
+
+/**
+ * Whether the bundle includes a gem, from Gemfile.lock when it exists and
+ * the Gemfile's gem lines otherwise
+ */
+export function isGemBundled(gemfilePath: string, gem: string): boolean {
+  const escaped = gem.replace(/[.*+?^${}()|[\]\\]/g, '\\$&');
+  try {
+    const lockfile = `${gemfilePath}.lock`;
+    if (fs.existsSync(lockfile)) {
+      return new RegExp(`^ {4}${escaped} \\(`, 'm').test(
+        fs.readFileSync(lockfile, 'utf-8')
+      );
+    }
+    return new RegExp(`^\\s*gem\\s+['"]${escaped}['"]`, 'm').test(
+      fs.readFileSync(gemfilePath, 'utf-8')
+    );
+  } catch {
+    return false;
+  }
+}
@@ -0,0 +1,100 @@
+/**
+ * File skeleton - collapse the bodies of functions, methods and other leaf
+ * symbols so a file reads as its declarations only
+ */
+
+import type { FoldingRange } from 'vscode-languageserver-protocol';
+import type { FlattenedSymbol } from '../types/lsp.js';
+import { isContainerKind } from '../config/symbol-kinds.js';
+
+// Lines hidden from one line after `start` through `end`, both 0-based
+interface Fold {
+  start: number;
+  end: number;
+}
+
+/**
+ * The fold that holds a leaf symbol's body: the first folding range opening
+ * on or after its name within the symbol. Without folding ranges the body is
+ * taken as everything between the name's line and the last line.
+ */
+function findBodyFold(
+  symbol: FlattenedSymbol,
+  foldingRanges: FoldingRange[]
+): Fold | null {
+  const nameLine = (symbol.selectionRange ?? symbol.range).start.line;
+  const lastLine = symbol.range.end.line;
+
+  const candidates = foldingRanges
+    .filter(
+      (range) =>
+        range.kind !== 'comment' &&
+        range.startLine >= nameLine &&
+        range.endLine <= lastLine &&
+        range.endLine > range.startLine
+    )
+    .sort((a, b) => a.startLine - b.startLine || b.endLine - a.endLine);
+  const fold = candidates[0];
+  if (fold) {
+    return { start: fold.startLine, end: fold.endLine };
+  }
+
+  // Keep the closing line, which usually holds the closing brace
+  return foldingRanges.length === 0 && lastLine - nameLine > 2
+    ? { start: nameLine, end: lastLine - 1 }
+    : null;
+}
+
+/**
+ * Source lines with leaf symbol bodies and import blocks elided, each kept
+ * line prefixed with its 1-based number
+ */
+export function buildSkeleton(
+  lines: string[],
+  symbols: FlattenedSymbol[],
+  foldingRanges: FoldingRange[],
+  containerKinds?: number[]
+): string[] {
+  const folds: Fold[] = foldingRanges
+    .filter((range) => range.kind === 'imports')
+    .map((range) => ({ start: range.startLine, end: range.endLine }));
+  for (const symbol of symbols) {
+    if (isContainerKind(symbol.kind, containerKinds)) {
+      continue;
+    }
+    const fold = findBodyFold(symbol, foldingRanges);
+    if (fold) {
+      folds.push(fold);
+    }
+  }
+
+  // Outer folds first, so folds inside a hidden body are skipped. A single
+  // hidden line would be replaced by a marker just as long, so it stays.
+  folds.sort((a, b) => a.start - b.start || b.end - a.end);
+  const hiddenUntil = new Map<number, number>();
+  let coveredUntil = -1;
+  for (const fold of folds) {
+    if (fold.start + 1 <= coveredUntil || fold.end - fold.start < 2) {
+      continue;
+    }
+    hiddenUntil.set(fold.start + 1, Math.min(fold.end, lines.length - 1));
+    coveredUntil = fold.end;
+  }
+
+  const width = String(lines.length).length;
+  const output: string[] = [];
+  for (let index = 0; index < lines.length; index++) {
+    const end = hiddenUntil.get(index);
+    if (end !== undefined && end >= index) {
+      const hidden = end - index + 1;
+      const indent = /^\s*/.exec(lines[index]!)![0];
+      output.push(
+        `${' '.repeat(width)}| ${indent}... ${hidden} line${hidden === 1 ? '' : 's'}`
+      );
+      index = end;
+      continue;
+    }
+    output.push(`${String(index + 1).padStart(width)}| ${lines[index]}`);
+  }
+  return output;
+}
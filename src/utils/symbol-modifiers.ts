@@ -0,0 +1,172 @@
+/**
+ * Symbol modifiers - visibility and modifier metadata inferred from declarations
+ */
+
+import * as path from 'path';
+import type { FlattenedSymbol } from '../types/lsp.js';
+
+export const SYMBOL_VISIBILITIES = [
+  'public',
+  'protected',
+  'internal',
+  'crate',
+  'private',
+] as const;
+
+export const SYMBOL_MODIFIERS = [
+  'static',
+  'async',
+  'abstract',
+  'readonly',
+  'const',
+  'override',
+  'deprecated',
+] as const;
+
+export type SymbolVisibility = (typeof SYMBOL_VISIBILITIES)[number];
+export type SymbolModifier = (typeof SYMBOL_MODIFIERS)[number];
+
+export interface SymbolModifiers {
+  visibility?: SymbolVisibility;
+  modifiers: SymbolModifier[];
+}
+
+const KEYWORD_MODIFIERS = new Map<string, SymbolModifier>([
+  ['static', 'static'],
+  ['async', 'async'],
+  ['abstract', 'abstract'],
+  ['readonly', 'readonly'],
+  ['const', 'const'],
+  ['final', 'readonly'],
+  ['override', 'override'],
+]);
+
+/**
+ * Infer visibility from naming conventions for languages without keywords
+ */
+function inferVisibilityFromName(
+  name: string,
+  extension: string
+): SymbolVisibility | undefined {
+  switch (extension) {
+    case '.go':
+      return /^\p{Lu}/u.test(name) ? 'public' : 'private';
+    case '.py':
+    case '.pyi':
+      if (/^__.*__$/.test(name)) return 'public';
+      return name.startsWith('_') ? 'private' : 'public';
+    default:
+      return name.startsWith('#') ? 'private' : undefined;
+  }
+}
+
+/**
+ * Infer visibility from declaration keywords
+ */
+function inferVisibilityFromKeywords(
+  prefix: string,
+  extension: string
+): SymbolVisibility | undefined {
+  if (extension === '.rs') {
+    if (/\bpub\s*\(\s*(crate|super|in\b[^)]*)\s*\)/.test(prefix)) {
+      return 'crate';
+    }
+    if (/\bpub\b/.test(prefix)) return 'public';
+    return 'private';
+  }
+
+  if (/\bprivate\b/.test(prefix)) return 'private';
+  if (/\bprotected\b/.test(prefix)) return 'protected';
+  if (/\b(internal|fileprivate)\b/.test(prefix)) return 'internal';
+  if (/\b(public|open|export)\b/.test(prefix)) return 'public';
+  return undefined;
+}
+
+/**
+ * Text of the declaration that precedes the symbol name, including any
+ * attributes or decorators between the symbol range start and its name
+ */
+export function getDeclarationPrefix(
+  lines: string[],
+  symbol: FlattenedSymbol
+): string {
+  const nameRange = symbol.selectionRange ?? symbol.range;
+  const nameLine = lines[nameRange.start.line] ?? '';
+
+  if (symbol.selectionRange) {
+    const leadingLines = lines.slice(
+      symbol.range.start.line,
+      nameRange.start.line
+    );
+    const namePrefix = nameLine.slice(0, nameRange.start.character);
+    return [...leadingLines, namePrefix].join(' ');
+  }
+
+  const nameIndex = nameLine.indexOf(symbol.name, nameRange.start.character);
+  return nameIndex >= 0 ? nameLine.slice(0, nameIndex) : nameLine;
+}
+
+/**
+ * Infer visibility and modifiers for a symbol from the declaration text that
+ * precedes its name (attributes, decorators, keywords), falling back to
+ * naming conventions (Go exports, Python underscores).
+ */
+export function inferSymbolModifiers(
+  prefix: string,
+  name: string,
+  filePath: string,
+  deprecated?: boolean
+): SymbolModifiers {
+  const extension = path.extname(filePath);
+
+  const modifiers = new Set<SymbolModifier>();
+  for (const word of prefix.split(/[^A-Za-z_]+/)) {
+    const modifier = KEYWORD_MODIFIERS.get(word);
+    if (modifier) modifiers.add(modifier);
+  }
+  if (/@staticmethod\b/.test(prefix)) modifiers.add('static');
+  if (/@deprecated\b|#\[deprecated/i.test(prefix) || deprecated) {
+    modifiers.add('deprecated');
+  }
+
+  const visibility =
+    inferVisibilityFromKeywords(prefix, extension) ??
+    inferVisibilityFromName(name, extension);
+
+  return {
+    ...(visibility && { visibility }),
+    modifiers: SYMBOL_MODIFIERS.filter((modifier) => modifiers.has(modifier)),
+  };
+}
+
+/**
+ * Format modifiers as a compact tag list, e.g. `[public async]`
+ */
+export function formatSymbolModifiers(info: SymbolModifiers): string {
+  const parts = [
+    ...(info.visibility ? [info.visibility] : []),
+    ...info.modifiers,
+  ];
+  return parts.length > 0 ? `[${parts.join(' ')}]` : '';
+}
+
+/**
+ * Check whether a symbol matches visibility and modifier filters.
+ * All requested modifiers must be present.
+ */
+export function matchesModifierFilter(
+  info: SymbolModifiers,
+  filter: { visibility?: SymbolVisibility[]; modifiers?: SymbolModifier[] }
+): boolean {
+  if (
+    filter.visibility &&
+    filter.visibility.length > 0 &&
+    (!info.visibility || !filter.visibility.includes(info.visibility))
+  ) {
+    return false;
+  }
+
+  return (filter.modifiers ?? []).every((modifier) =>
+    info.modifiers.includes(modifier)
+  );
+}
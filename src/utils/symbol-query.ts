@@ -0,0 +1,250 @@
+/**
+ * Symbol query language - `key:value` filters mixed into a search query,
+ * e.g. `kind:function vis:pub path:src/** name:~^create refs:>10`
+ */
+
+import { SymbolKind } from '../types/lsp.js';
+import { SYMBOL_KIND_NAMES } from '../config/symbol-kinds.js';
+import { compileCodeOwnersPattern } from './code-owners.js';
+import {
+  SYMBOL_VISIBILITIES,
+  type SymbolVisibility,
+} from './symbol-modifiers.js';
+
+export const QUERY_FILTER_KEYS = [
+  'kind',
+  'vis',
+  'path',
+  'name',
+  'refs',
+  'owner',
+] as const;
+
+export type QueryFilterKey = (typeof QUERY_FILTER_KEYS)[number];
+
+export type CountOperator = '>' | '>=' | '<' | '<=' | '=';
+
+export interface CountFilter {
+  operator: CountOperator;
+  count: number;
+}
+
+export interface SymbolQueryFilters {
+  kinds: number[];
+  visibility: SymbolVisibility[];
+  // Workspace-relative patterns with CODEOWNERS/gitignore semantics
+  paths: string[];
+  name?: { pattern: string; regex: boolean };
+  refs?: CountFilter;
+  owners: string[];
+}
+
+export interface SymbolQuery {
+  // Free text left once filters are removed
+  text: string;
+  filters: SymbolQueryFilters;
+  // Filter tokens as written, for messages
+  filterTokens: string[];
+}
+
+// Shorthands accepted for kind:
+const KIND_ALIASES: Record<string, number[]> = {
+  fn: [SymbolKind.Function],
+  func: [SymbolKind.Function],
+  var: [SymbolKind.Variable],
+  const: [SymbolKind.Constant],
+  prop: [SymbolKind.Property],
+  ctor: [SymbolKind.Constructor],
+  type: [
+    SymbolKind.Class,
+    SymbolKind.Interface,
+    SymbolKind.Struct,
+    SymbolKind.Enum,
+    SymbolKind.TypeParameter,
+  ],
+};
+
+// Shorthands accepted for vis:
+const VISIBILITY_ALIASES: Record<string, SymbolVisibility> = {
+  pub: 'public',
+  priv: 'private',
+  prot: 'protected',
+};
+
+function parseKinds(value: string): number[] {
+  const kindsByName = new Map(
+    Object.entries(SYMBOL_KIND_NAMES).map(([name, kind]) => [
+      name.toLowerCase(),
+      [kind],
+    ])
+  );
+  return value.split(',').flatMap((name) => {
+    const kinds =
+      KIND_ALIASES[name.toLowerCase()] ?? kindsByName.get(name.toLowerCase());
+    if (!kinds) {
+      throw new Error(`Unknown symbol kind in query: ${name}`);
+    }
+    return kinds;
+  });
+}
+
+function parseVisibility(value: string): SymbolVisibility[] {
+  return value.split(',').map((name) => {
+    const normalized = name.toLowerCase();
+    const visibility =
+      VISIBILITY_ALIASES[normalized] ??
+      SYMBOL_VISIBILITIES.find((candidate) => candidate === normalized);
+    if (!visibility) {
+      throw new Error(
+        `Unknown visibility in query: ${name} (expected ${SYMBOL_VISIBILITIES.join(', ')})`
+      );
+    }
+    return visibility;
+  });
+}
+
+function parseRefs(value: string): CountFilter {
+  const match = /^(>=|<=|>|<|=)?(\d+)$/.exec(value);
+  if (!match?.[2]) {
+    throw new Error(
+      `Invalid refs filter in query: ${value} (expected e.g. refs:>10)`
+    );
+  }
+  return {
+    operator: (match[1] as CountOperator | undefined) ?? '=',
+    count: Number(match[2]),
+  };
+}
+
+/**
+ * Split `key:value` filters out of a query. Tokens with unknown keys, such
+ * as `std::vector`, stay part of the free text.
+ */
+export function parseSymbolQuery(query: string): SymbolQuery {
+  const filters: SymbolQueryFilters = {
+    kinds: [],
+    visibility: [],
+    paths: [],
+    owners: [],
+  };
+  const text: string[] = [];
+  const filterTokens: string[] = [];
+
+  for (const token of query.split(/\s+/).filter(Boolean)) {
+    const match = /^([a-z]+):(.+)$/.exec(token);
+    const key = match?.[1] as QueryFilterKey | undefined;
+    const value = match?.[2];
+    if (!key || !value || !QUERY_FILTER_KEYS.includes(key)) {
+      text.push(token);
+      continue;
+    }
+
+    filterTokens.push(token);
+    switch (key) {
+      case 'kind':
+        filters.kinds.push(...parseKinds(value));
+        break;
+      case 'vis':
+        filters.visibility.push(...parseVisibility(value));
+        break;
+      case 'path':
+        filters.paths.push(value);
+        break;
+      case 'name':
+        if (value.startsWith('~')) {
+          try {
+            new RegExp(value.slice(1));
+          } catch {
+            throw new Error(`Invalid name pattern in query: ${value.slice(1)}`);
+          }
+          filters.name = { pattern: value.slice(1), regex: true };
+        } else {
+          filters.name = { pattern: value, regex: false };
+        }
+        break;
+      case 'refs':
+        filters.refs = parseRefs(value);
+        break;
+      case 'owner':
+        filters.owners.push(value);
+        break;
+    }
+  }
+
+  return { text: text.join(' '), filters, filterTokens };
+}
+
+/**
+ * Query to send to the language server: the free text, or the longest
+ * literal run of a name filter when there is none. Patterns with
+ * alternatives send an empty query so no branch is missed.
+ */
+export function getServerQuery(query: SymbolQuery): string {
+  if (query.text || !query.filters.name) {
+    return query.text;
+  }
+  const { pattern, regex } = query.filters.name;
+  if (!regex) {
+    return pattern;
+  }
+  if (pattern.includes('|')) {
+    return '';
+  }
+  const literals = pattern.replace(/\\./g, ' ').match(/[A-Za-z0-9_]+/g) ?? [];
+  return literals.reduce(
+    (longest, literal) => (literal.length > longest.length ? literal : longest),
+    ''
+  );
+}
+
+/**
+ * Whether a symbol passes the filters that need nothing beyond its name,
+ * kind and workspace-relative path
+ */
+export function matchesSymbolQuery(
+  symbol: { name: string; kind: number; path: string },
+  filters: SymbolQueryFilters
+): boolean {
+  if (filters.kinds.length > 0 && !filters.kinds.includes(symbol.kind)) {
+    return false;
+  }
+  if (
+    filters.paths.length > 0 &&
+    !filters.paths.some((pattern) =>
+      compileCodeOwnersPattern(pattern).test(symbol.path)
+    )
+  ) {
+    return false;
+  }
+  if (filters.name) {
+    const { pattern, regex } = filters.name;
+    const matches = regex
+      ? new RegExp(pattern).test(symbol.name)
+      : symbol.name.toLowerCase().includes(pattern.toLowerCase());
+    if (!matches) {
+      return false;
+    }
+  }
+  return true;
+}
+
+/**
+ * Whether a count satisfies a `refs:` comparison
+ */
+export function compareCount(
+  count: number,
+  { operator, count: expected }: CountFilter
+): boolean {
+  switch (operator) {
+    case '>':
+      return count > expected;
+    case '>=':
+      return count >= expected;
+    case '<':
+      return count < expected;
+    case '<=':
+      return count <= expected;
+    default:
+      return count === expected;
+  }
+}
@@ -0,0 +1,82 @@
+/**
+ * Test-code classification - tell test files and symbols apart from production code
+ */
+
+import * as path from 'path';
+import { toRelativeFilePath } from './path-format.js';
+
+export const TEST_FILTERS = ['include', 'exclude', 'only'] as const;
+
+export type TestFilter = (typeof TEST_FILTERS)[number];
+
+// Directories that hold tests by convention (__tests__, src/test/java, spec/)
+const TEST_DIRECTORY_PATTERN =
+  /(^|\/)(__tests__|__mocks__|tests?|spec|specs|testdata|src\/test)(\/|$)/;
+
+// File names that mark tests by convention per language
+const TEST_FILE_PATTERNS = [
+  /_test\.go$/,
+  /\.(test|spec)\.[cm]?[jt]sx?$/,
+  /(^|\/)test_[^/]+\.py$/,
+  /_test\.py$/,
+  /(^|\/)conftest\.py$/,
+  /Tests?\.(java|kt|cs|swift)$/,
+  /_spec\.rb$/,
+  /_test\.rb$/,
+  /Test\.php$/,
+  /_test\.(c|cc|cpp)$/,
+];
+
+/**
+ * Check whether a file is test code based on path conventions.
+ * Paths are classified relative to the workspace so a checkout that itself
+ * lives under a `tests/` directory isn't treated as test code.
+ */
+export function isTestFile(filePath: string): boolean {
+  const normalized = toRelativeFilePath(filePath).split(path.sep).join('/');
+  return (
+    TEST_DIRECTORY_PATTERN.test(path.posix.dirname(normalized)) ||
+    TEST_FILE_PATTERNS.some((pattern) => pattern.test(normalized))
+  );
+}
+
+/**
+ * Check whether a symbol is test code, either because its file is a test file
+ * or because it sits in an inline test module (Rust `#[cfg(test)] mod tests`)
+ * or carries a test attribute
+ */
+export function isTestSymbol(
+  filePath: string,
+  containerPath: string[] = [],
+  declarationPrefix = ''
+): boolean {
+  if (isTestFile(filePath)) {
+    return true;
+  }
+
+  if (path.extname(filePath) === '.rs') {
+    return (
+      containerPath.includes('tests') ||
+      /#\[(cfg\(test\)|test|tokio::test)\]/.test(declarationPrefix)
+    );
+  }
+
+  return false;
+}
+
+/**
+ * Apply a test filter to a classification
+ */
+export function matchesTestFilter(
+  isTest: boolean,
+  filter: TestFilter
+): boolean {
+  switch (filter) {
+    case 'exclude':
+      return !isTest;
+    case 'only':
+      return isTest;
+    default:
+      return true;
+  }
+}
@@ -0,0 +1,213 @@
+/**
+ * Text helpers - line splitting and grapheme-safe slicing for file content
+ */
+
+export type LineEnding = '\n' | '\r\n';
+
+const graphemeSegmenter = new Intl.Segmenter(undefined, {
+  granularity: 'grapheme',
+});
+
+/**
+ * Detect the dominant line ending of a file, defaulting to LF
+ */
+export function detectLineEnding(content: string): LineEnding {
+  const crlf = content.match(/\r\n/g)?.length ?? 0;
+  const lf = (content.match(/\n/g)?.length ?? 0) - crlf;
+  return crlf > lf ? '\r\n' : '\n';
+}
+
+/**
+ * Detect whether a file indents with tabs or spaces, and by how many spaces
+ * a nested line steps in. Defaults to 2 spaces when nothing is indented, and
+ * to a tab size of 4 for tab-indented files.
+ */
+export function detectIndentation(content: string): {
+  tabSize: number;
+  insertSpaces: boolean;
+} {
+  let tabs = 0;
+  let spaces = 0;
+  const steps = new Map<number, number>();
+  let previous = 0;
+
+  for (const line of splitLines(content)) {
+    if (line.trim() === '') {
+      continue;
+    }
+    const indent = /^[ \t]*/.exec(line)![0];
+    if (indent.startsWith('\t')) {
+      tabs++;
+      continue;
+    }
+    if (indent.length > 0) {
+      spaces++;
+    }
+    // Steps into a deeper level give the indent size; one-space steps, such
+    // as the ` * ` lines of doc comments, are alignment rather than nesting
+    const step = indent.length - previous;
+    if (step > 1) {
+      steps.set(step, (steps.get(step) ?? 0) + 1);
+    }
+    previous = indent.length;
+  }
+
+  if (tabs > spaces) {
+    return { tabSize: 4, insertSpaces: false };
+  }
+
+  let tabSize = 2;
+  let count = 0;
+  for (const [step, occurrences] of steps) {
+    if (occurrences > count) {
+      tabSize = step;
+      count = occurrences;
+    }
+  }
+  return { tabSize, insertSpaces: true };
+}
+
+/**
+ * Split file content into lines without their terminators so both LF and CRLF
+ * files produce the same line/character coordinates as the language server
+ */
+export function splitLines(content: string): string[] {
+  return content.split(/\r?\n/);
+}
+
+/**
+ * Return the UTF-16 offsets where grapheme clusters start in a line,
+ * including the end of the line
+ */
+function graphemeBoundaries(line: string): number[] {
+  const boundaries: number[] = [];
+  for (const segment of graphemeSegmenter.segment(line)) {
+    boundaries.push(segment.index);
+  }
+  boundaries.push(line.length);
+  return boundaries;
+}
+
+/**
+ * Slice a line by UTF-16 character offsets, widening the range so it never
+ * cuts through a surrogate pair or a combined grapheme cluster
+ */
+export function sliceGraphemes(
+  line: string,
+  start: number,
+  end: number = line.length
+): string {
+  const clampedStart = Math.min(Math.max(start, 0), line.length);
+  const clampedEnd = Math.min(Math.max(end, clampedStart), line.length);
+
+  // Pure ASCII lines can't contain multi-unit clusters
+  // eslint-disable-next-line no-control-regex
+  if (/^[\x00-\x7f]*$/.test(line)) {
+    return line.substring(clampedStart, clampedEnd);
+  }
+
+  const boundaries = graphemeBoundaries(line);
+  let safeStart = 0;
+  let safeEnd = line.length;
+
+  for (const boundary of boundaries) {
+    if (boundary <= clampedStart) {
+      safeStart = boundary;
+    }
+    if (boundary >= clampedEnd) {
+      safeEnd = boundary;
+      break;
+    }
+  }
+
+  return line.substring(safeStart, safeEnd);
+}
+
+/**
+ * Truncate text to at most maxLength UTF-16 units without splitting a
+ * grapheme cluster
+ */
+export function truncateGraphemes(text: string, maxLength: number): string {
+  if (text.length <= maxLength) {
+    return text;
+  }
+
+  let end = 0;
+  for (const boundary of graphemeBoundaries(text)) {
+    if (boundary > maxLength) break;
+    end = boundary;
+  }
+
+  return text.substring(0, end);
+}
+
+/**
+ * A file split into lines, keeping each line's own terminator and any BOM so
+ * it can be written back without churning untouched lines
+ */
+export interface TextDocumentLines {
+  bom: boolean;
+  lines: string[];
+  // Terminator following each line, '' for the final line
+  terminators: string[];
+  // Dominant line ending, used for line breaks introduced by edits
+  lineEnding: LineEnding;
+}
+
+/**
+ * Parse file content into lines while recording BOM and per-line terminators
+ */
+export function parseTextLines(content: string): TextDocumentLines {
+  const bom = content.startsWith('\uFEFF');
+  const body = bom ? content.slice(1) : content;
+  const lines: string[] = [];
+  const terminators: string[] = [];
+
+  const pattern = /\r?\n/g;
+  let lastIndex = 0;
+  let match: RegExpExecArray | null;
+  while ((match = pattern.exec(body)) !== null) {
+    lines.push(body.substring(lastIndex, match.index));
+    terminators.push(match[0]);
+    lastIndex = match.index + match[0].length;
+  }
+  lines.push(body.substring(lastIndex));
+  terminators.push('');
+
+  return { bom, lines, terminators, lineEnding: detectLineEnding(body) };
+}
+
+/**
+ * Replace lines [startLine, endLine] with new content. Line breaks inside the
+ * new content use the document's dominant ending and the last replaced line
+ * keeps its original terminator.
+ */
+export function replaceTextLines(
+  document: TextDocumentLines,
+  startLine: number,
+  endLine: number,
+  newContent: string
+): void {
+  const newLines = splitLines(newContent);
+  const trailingTerminator = document.terminators[endLine] ?? '';
+  const count = endLine - startLine + 1;
+
+  document.lines.splice(startLine, count, ...newLines);
+  document.terminators.splice(
+    startLine,
+    count,
+    ...newLines.map((_, index) =>
+      index === newLines.length - 1 ? trailingTerminator : document.lineEnding
+    )
+  );
+}
+
+/**
+ * Serialize lines back to file content, restoring BOM and terminators
+ */
+export function serializeTextLines(document: TextDocumentLines): string {
+  const body = document.lines
+    .map((line, index) => line + (document.terminators[index] ?? ''))
+    .join('');
+  return document.bom ? `\uFEFF${body}` : body;
+}
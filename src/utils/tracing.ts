@@ -0,0 +1,287 @@
+/**
+ * Tracing - OpenTelemetry spans for tool calls and the language server
+ * requests they make, exported to an OTLP/HTTP collector as JSON.
+ * Configured with the standard OTEL_* environment variables.
+ */
+
+import { AsyncLocalStorage } from 'async_hooks';
+import { randomBytes } from 'crypto';
+import type * as rpc from 'vscode-jsonrpc';
+import logger from './logger.js';
+
+// OTLP SpanKind values
+export const SPAN_KIND_SERVER = 2;
+export const SPAN_KIND_CLIENT = 3;
+
+// OTLP StatusCode values
+const STATUS_OK = 1;
+const STATUS_ERROR = 2;
+
+// Spans are sent in batches, or when the interval passes
+const MAX_BATCH_SIZE = 100;
+const EXPORT_INTERVAL_MS = 5_000;
+
+export interface TracingSettings {
+  // OTLP/HTTP traces endpoint; null disables tracing
+  endpoint: string | null;
+  serviceName: string;
+  headers: Record<string, string>;
+}
+
+export interface SpanContext {
+  traceId: string;
+  spanId: string;
+}
+
+type SpanAttributes = Record<string, string | number | boolean>;
+
+export interface RecordedSpan extends SpanContext {
+  parentSpanId?: string;
+  name: string;
+  kind: number;
+  startTimeUnixNano: string;
+  endTimeUnixNano: string;
+  attributes: SpanAttributes;
+  error?: string;
+}
+
+let settings: TracingSettings = {
+  endpoint: null,
+  serviceName: 'symbols',
+  headers: {},
+};
+const activeSpan = new AsyncLocalStorage<SpanContext>();
+let pending: RecordedSpan[] = [];
+let exportTimer: NodeJS.Timeout | null = null;
+
+/**
+ * Tracing settings from OTEL_EXPORTER_OTLP_TRACES_ENDPOINT (or
+ * OTEL_EXPORTER_OTLP_ENDPOINT), OTEL_EXPORTER_OTLP_HEADERS,
+ * OTEL_SERVICE_NAME and OTEL_SDK_DISABLED
+ */
+export function resolveTracingSettings(
+  env: NodeJS.ProcessEnv
+): TracingSettings {
+  const baseEndpoint = env.OTEL_EXPORTER_OTLP_ENDPOINT?.replace(/\/+$/, '');
+  const endpoint =
+    env.OTEL_EXPORTER_OTLP_TRACES_ENDPOINT ||
+    (baseEndpoint ? `${baseEndpoint}/v1/traces` : null);
+
+  const headers: Record<string, string> = {};
+  for (const pair of (env.OTEL_EXPORTER_OTLP_HEADERS ?? '').split(',')) {
+    const separator = pair.indexOf('=');
+    if (separator > 0) {
+      headers[decodeURIComponent(pair.slice(0, separator).trim())] =
+        decodeURIComponent(pair.slice(separator + 1).trim());
+    }
+  }
+
+  return {
+    endpoint: env.OTEL_SDK_DISABLED === 'true' ? null : endpoint,
+    serviceName: env.OTEL_SERVICE_NAME || 'symbols',
+    headers,
+  };
+}
+
+/**
+ * Replace the tracing settings; spans are only recorded with an endpoint
+ */
+export function configureTracing(update: TracingSettings): void {
+  settings = update;
+  if (!settings.endpoint) {
+    pending = [];
+  }
+}
+
+export function isTracingEnabled(): boolean {
+  return settings.endpoint !== null;
+}
+
+/**
+ * Parse a W3C `traceparent` header, e.g.
+ * `00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01`
+ */
+export function parseTraceparent(value: unknown): SpanContext | null {
+  if (typeof value !== 'string') {
+    return null;
+  }
+  const match = /^[\da-f]{2}-([\da-f]{32})-([\da-f]{16})-[\da-f]{2}$/.exec(
+    value.trim().toLowerCase()
+  );
+  if (!match || /^0+$/.test(match[1]!) || /^0+$/.test(match[2]!)) {
+    return null;
+  }
+  return { traceId: match[1]!, spanId: match[2]! };
+}
+
+function nowUnixNano(): bigint {
+  return BigInt(Date.now()) * 1_000_000n;
+}
+
+/**
+ * Run `fn` inside a span. The span is the parent of spans started while
+ * `fn` runs; without an active span it continues `parent`, typically the
+ * trace context the client sent.
+ */
+export async function withSpan<T>(
+  name: string,
+  kind: number,
+  attributes: SpanAttributes,
+  fn: () => Promise<T>,
+  parent: SpanContext | null = null
+): Promise<T> {
+  if (!isTracingEnabled()) {
+    return await fn();
+  }
+
+  const parentContext = activeSpan.getStore() ?? parent;
+  const context: SpanContext = {
+    traceId: parentContext?.traceId ?? randomBytes(16).toString('hex'),
+    spanId: randomBytes(8).toString('hex'),
+  };
+  const startTime = nowUnixNano();
+  const record = (error?: unknown) => {
+    recordSpan({
+      ...context,
+      ...(parentContext && { parentSpanId: parentContext.spanId }),
+      name,
+      kind,
+      startTimeUnixNano: startTime.toString(),
+      endTimeUnixNano: nowUnixNano().toString(),
+      attributes,
+      ...(error !== undefined && {
+        error: error instanceof Error ? error.message : String(error),
+      }),
+    });
+  };
+
+  try {
+    const result = await activeSpan.run(context, fn);
+    record();
+    return result;
+  } catch (error) {
+    record(error);
+    throw error;
+  }
+}
+
+function recordSpan(span: RecordedSpan): void {
+  pending.push(span);
+  if (pending.length >= MAX_BATCH_SIZE) {
+    void flushTraces();
+  } else if (!exportTimer) {
+    exportTimer = setTimeout(() => void flushTraces(), EXPORT_INTERVAL_MS);
+    exportTimer.unref();
+  }
+}
+
+/**
+ * OTLP/HTTP JSON request body for a batch of spans
+ */
+export function buildOtlpPayload(
+  spans: RecordedSpan[],
+  serviceName: string
+): unknown {
+  return {
+    resourceSpans: [
+      {
+        resource: {
+          attributes: [
+            { key: 'service.name', value: { stringValue: serviceName } },
+          ],
+        },
+        scopeSpans: [
+          {
+            scope: { name: 'symbols' },
+            spans: spans.map((span) => ({
+              traceId: span.traceId,
+              spanId: span.spanId,
+              ...(span.parentSpanId && { parentSpanId: span.parentSpanId }),
+              name: span.name,
+              kind: span.kind,
+              startTimeUnixNano: span.startTimeUnixNano,
+              endTimeUnixNano: span.endTimeUnixNano,
+              attributes: Object.entries(span.attributes).map(
+                ([key, value]) => ({
+                  key,
+                  value:
+                    typeof value === 'string'
+                      ? { stringValue: value }
+                      : typeof value === 'boolean'
+                        ? { boolValue: value }
+                        : Number.isInteger(value)
+                          ? { intValue: String(value) }
+                          : { doubleValue: value },
+                })
+              ),
+              status: span.error
+                ? { code: STATUS_ERROR, message: span.error }
+                : { code: STATUS_OK },
+            })),
+          },
+        ],
+      },
+    ],
+  };
+}
+
+/**
+ * Send recorded spans to the collector. Export failures are logged and the
+ * spans dropped, so tracing never fails a tool call.
+ */
+export async function flushTraces(): Promise<void> {
+  if (exportTimer) {
+    clearTimeout(exportTimer);
+    exportTimer = null;
+  }
+  const endpoint = settings.endpoint;
+  if (!endpoint || pending.length === 0) {
+    return;
+  }
+
+  const spans = pending;
+  pending = [];
+  try {
+    const response = await fetch(endpoint, {
+      method: 'POST',
+      headers: { 'content-type': 'application/json', ...settings.headers },
+      body: JSON.stringify(buildOtlpPayload(spans, settings.serviceName)),
+    });
+    if (!response.ok) {
+      throw new Error(`collector responded ${response.status}`);
+    }
+  } catch (error) {
+    logger.warn('Failed to export trace spans', {
+      endpoint,
+      spans: spans.length,
+      error: error instanceof Error ? error.message : String(error),
+    });
+  }
+}
+
+/**
+ * Connection whose outgoing requests are traced as client spans
+ */
+export function traceConnection(
+  connection: rpc.MessageConnection,
+  profileName: string
+): rpc.MessageConnection {
+  const sendRequest = async (method: string, ...params: unknown[]) =>
+    await withSpan(
+      method,
+      SPAN_KIND_CLIENT,
+      {
+        'rpc.system': 'jsonrpc',
+        'rpc.method': method,
+        'lsp.profile': profileName,
+      },
+      async () => await connection.sendRequest<unknown>(method, ...params)
+    );
+
+  return new Proxy(connection, {
+    get: (target, property, receiver) =>
+      property === 'sendRequest'
+        ? sendRequest
+        : (Reflect.get(target, property, receiver) as unknown),
+  });
+}
@@ -0,0 +1,198 @@
+/**
+ * Unified diff - render the line changes between two versions of a file
+ */
+
+import { splitLines } from './text.js';
+
+interface DiffLine {
+  type: ' ' | '-' | '+';
+  text: string;
+}
+
+// Above this many cells the changed middle is shown as removed then added
+const MAX_LCS_CELLS = 4_000_000;
+
+/**
+ * Line operations turning `before` into `after`, from a longest common
+ * subsequence of the lines between the common prefix and suffix
+ */
+function diffLines(before: string[], after: string[]): DiffLine[] {
+  let prefix = 0;
+  while (
+    prefix < before.length &&
+    prefix < after.length &&
+    before[prefix] === after[prefix]
+  ) {
+    prefix++;
+  }
+
+  let suffix = 0;
+  while (
+    suffix < before.length - prefix &&
+    suffix < after.length - prefix &&
+    before[before.length - 1 - suffix] === after[after.length - 1 - suffix]
+  ) {
+    suffix++;
+  }
+
+  const oldMiddle = before.slice(prefix, before.length - suffix);
+  const newMiddle = after.slice(prefix, after.length - suffix);
+  const lines: DiffLine[] = before
+    .slice(0, prefix)
+    .map((text) => ({ type: ' ' as const, text }));
+
+  if (oldMiddle.length * newMiddle.length > MAX_LCS_CELLS) {
+    lines.push(...oldMiddle.map((text) => ({ type: '-' as const, text })));
+    lines.push(...newMiddle.map((text) => ({ type: '+' as const, text })));
+  } else {
+    // lengths[i][j] is the LCS length of oldMiddle[i..] and newMiddle[j..]
+    const width = newMiddle.length + 1;
+    const lengths = new Uint32Array((oldMiddle.length + 1) * width);
+    for (let i = oldMiddle.length - 1; i >= 0; i--) {
+      for (let j = newMiddle.length - 1; j >= 0; j--) {
+        lengths[i * width + j] =
+          oldMiddle[i] === newMiddle[j]
+            ? lengths[(i + 1) * width + j + 1]! + 1
+            : Math.max(
+                lengths[(i + 1) * width + j]!,
+                lengths[i * width + j + 1]!
+              );
+      }
+    }
+
+    let i = 0;
+    let j = 0;
+    while (i < oldMiddle.length || j < newMiddle.length) {
+      if (
+        i < oldMiddle.length &&
+        j < newMiddle.length &&
+        oldMiddle[i] === newMiddle[j]
+      ) {
+        lines.push({ type: ' ', text: oldMiddle[i++]! });
+        j++;
+      } else if (
+        i < oldMiddle.length &&
+        (j === newMiddle.length ||
+          lengths[(i + 1) * width + j]! >= lengths[i * width + j + 1]!)
+      ) {
+        lines.push({ type: '-', text: oldMiddle[i++]! });
+      } else {
+        lines.push({ type: '+', text: newMiddle[j++]! });
+      }
+    }
+  }
+
+  lines.push(
+    ...before
+      .slice(before.length - suffix)
+      .map((text) => ({ type: ' ' as const, text }))
+  );
+  return lines;
+}
+
+/**
+ * Split content into lines, without the empty one after a final newline
+ */
+function toLines(content: string): string[] {
+  const lines = splitLines(content);
+  if (lines[lines.length - 1] === '') {
+    lines.pop();
+  }
+  return lines;
+}
+
+/**
+ * 0-based inclusive line ranges of `after` that were added or changed from
+ * `before`. Lines that were only removed leave no range.
+ */
+export function getChangedLineRanges(
+  before: string,
+  after: string
+): Array<{ start: number; end: number }> {
+  const ranges: Array<{ start: number; end: number }> = [];
+  let newLine = 0;
+  for (const line of diffLines(toLines(before), toLines(after))) {
+    if (line.type === '-') {
+      continue;
+    }
+    if (line.type === '+') {
+      const last = ranges[ranges.length - 1];
+      if (last && last.end === newLine - 1) {
+        last.end = newLine;
+      } else {
+        ranges.push({ start: newLine, end: newLine });
+      }
+    }
+    newLine++;
+  }
+  return ranges;
+}
+
+/**
+ * Unified diff of two file contents with `context` unchanged lines around
+ * each change. Returns an empty string when nothing changed.
+ */
+export function createUnifiedDiff(
+  fileName: string,
+  before: string,
+  after: string,
+  context = 3
+): string {
+  if (before === after) {
+    return '';
+  }
+
+  const lines = diffLines(toLines(before), toLines(after));
+
+  // Line numbers in each version where every diff line starts
+  const oldNumbers: number[] = [];
+  const newNumbers: number[] = [];
+  let oldLine = 1;
+  let newLine = 1;
+  for (const line of lines) {
+    oldNumbers.push(oldLine);
+    newNumbers.push(newLine);
+    if (line.type !== '+') {
+      oldLine++;
+    }
+    if (line.type !== '-') {
+      newLine++;
+    }
+  }
+
+  const output = [`--- a/${fileName}`, `+++ b/${fileName}`];
+  let index = 0;
+  while (index < lines.length) {
+    if (lines[index]!.type === ' ') {
+      index++;
+      continue;
+    }
+
+    // Extend the hunk while the next change is within twice the context
+    const start = Math.max(0, index - context);
+    let end = index;
+    for (let next = index; next < lines.length; next++) {
+      if (lines[next]!.type === ' ') {
+        continue;
+      }
+      if (next - end > context * 2) {
+        break;
+      }
+      end = next;
+    }
+    const stop = Math.min(lines.length, end + context + 1);
+
+    const hunk = lines.slice(start, stop);
+    const oldCount = hunk.filter((line) => line.type !== '+').length;
+    const newCount = hunk.filter((line) => line.type !== '-').length;
+    // Empty ranges are numbered from the line before them
+    const oldStart = oldNumbers[start]! - (oldCount > 0 ? 0 : 1);
+    const newStart = newNumbers[start]! - (newCount > 0 ? 0 : 1);
+
+    output.push(`@@ -${oldStart},${oldCount} +${newStart},${newCount} @@`);
+    output.push(...hunk.map((line) => `${line.type}${line.text}`));
+    index = stop;
+  }
+
+  return output.join('\n');
+}
@@ -3,6 +3,7 @@ import { getLogLevelName } from './log-level.js';
 interface WindowLogMessage {
   type: number;
   message: string;
+  shown?: boolean;
 }
 
 function getLogLevelSymbol(type: number): string {
@@ -28,7 +29,9 @@ export function formatWindowLogMessages(messages: WindowLogMessage[]): string {
   return messages
     .map((message) => {
       const symbol = getLogLevelSymbol(message.type);
-      const level = getLogLevelName(message.type);
+      const level = message.shown
+        ? `${getLogLevelName(message.type)}, shown`
+        : getLogLevelName(message.type);
       const trimmedMessage = message.message.trim();
       const contextMatch = trimmedMessage.match(/^\[([^\]]+)\]/);
       const context = contextMatch ? contextMatch[1] : '';
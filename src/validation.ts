@@ -15,6 +15,7 @@ import {
   toZeroBased,
 } from './types.js';
 import type { LspSession } from './runtime/lsp-session.js';
+import { splitLines } from './utils/text.js';
 
 const WORKSPACE_LOADING_MESSAGE_PREFIX = 'Workspace is still loading';
 
@@ -128,7 +129,7 @@ export async function validatePosition(
 ): Promise<ValidationResult> {
   try {
     const content = await fs.promises.readFile(filePath, 'utf8');
-    const lines = content.split('\n');
+    const lines = splitLines(content);
 
     // Convert to 0-based for validation (LSP coordinates)
     const zeroBasedPosition = toZeroBased(position);
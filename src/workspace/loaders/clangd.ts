@@ -0,0 +1,68 @@
+/**
+ * clangd Workspace Loader - Pure Functional Implementation
+ * Points clangd at the compile_commands.json found in a build directory
+ */
+
+import {
+  WorkspaceCommand,
+  WorkspaceLoader,
+  WorkspaceLoaderState,
+} from '../types.js';
+import { findCompileCommandsDir } from '../../utils/clangd-workspace.js';
+
+const COMPILE_COMMANDS_FLAG = '--compile-commands-dir';
+
+/**
+ * Pure function: Initialize C/C++ workspace. clangd indexes in the
+ * background and answers from what it has, so requests aren't held.
+ */
+const initialize = async (): Promise<WorkspaceLoaderState> => {
+  // Await a resolved promise to satisfy eslint require-await
+  await Promise.resolve();
+
+  return {
+    type: 'clangd' as const,
+    ready: true,
+  };
+};
+
+/**
+ * Pure function: Check if C/C++ workspace is ready (always true)
+ */
+const isReady = (): boolean => true;
+
+/**
+ * Pure function: Add the compilation database directory to the command,
+ * unless the profile already sets one. clangd only looks next to each
+ * file's parents and in their build/ directory by itself.
+ */
+const prepareCommand = (
+  workspacePath: string,
+  command: WorkspaceCommand
+): WorkspaceCommand => {
+  if (
+    command.commandArgs.some((arg) => arg.startsWith(COMPILE_COMMANDS_FLAG))
+  ) {
+    return command;
+  }
+
+  const directory = findCompileCommandsDir(workspacePath);
+  return directory
+    ? {
+        ...command,
+        commandArgs: [
+          ...command.commandArgs,
+          `${COMPILE_COMMANDS_FLAG}=${directory}`,
+        ],
+      }
+    : command;
+};
+
+/**
+ * Factory function to create clangd workspace loader
+ */
+export const createClangdLoader = (): WorkspaceLoader => ({
+  initialize,
+  isReady,
+  prepareCommand,
+});
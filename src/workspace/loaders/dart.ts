@@ -0,0 +1,158 @@
+/**
+ * Dart Workspace Loader - Pure Functional Implementation
+ * Runs Flutter apps' analysis server from their Flutter SDK and waits for
+ * the initial analysis
+ */
+
+import path from 'path';
+import { fileURLToPath } from 'url';
+import type { ProgressParams } from 'vscode-languageserver-protocol';
+import {
+  WorkspaceCommand,
+  WorkspaceLoader,
+  WorkspaceLoaderState,
+} from '../types.js';
+import { LspClient, LspConfig } from '../../types.js';
+import {
+  findFlutterSdk,
+  findPubspec,
+  getFlutterDart,
+  isFlutterPackage,
+} from '../../utils/dart-workspace.js';
+import logger from '../../utils/logger.js';
+
+// Treated as ready after this long, in case analysis never reports its end
+const MAX_ANALYSIS_MS = 120_000;
+
+/**
+ * Dart workspace state data
+ */
+type DartWorkspaceData = {
+  readonly pubspecPath: string;
+  readonly analysisStartedAt: number;
+};
+
+/**
+ * Pure function: Initialize Dart workspace. Without a package there are no
+ * dependencies to resolve, so requests aren't held.
+ */
+const initialize = async (
+  _client: LspClient,
+  config: LspConfig
+): Promise<WorkspaceLoaderState> => {
+  // Await a resolved promise to satisfy eslint require-await
+  await Promise.resolve();
+
+  const pubspecPath = findPubspec(fileURLToPath(config.workspaceUri));
+  if (!pubspecPath) {
+    logger.debug('No pubspec.yaml detected, defaulting to ready state');
+    return {
+      type: 'dart' as const,
+      ready: true,
+    };
+  }
+
+  return {
+    type: 'dart' as const,
+    ready: false,
+    data: {
+      pubspecPath,
+      analysisStartedAt: Date.now(),
+    } satisfies DartWorkspaceData,
+  };
+};
+
+/**
+ * Pure function: Check if Dart workspace is ready
+ */
+const isReady = (state: WorkspaceLoaderState): boolean => {
+  const data = state.data as DartWorkspaceData | undefined;
+  return (
+    state.ready ||
+    (data !== undefined &&
+      Date.now() - data.analysisStartedAt > MAX_ANALYSIS_MS)
+  );
+};
+
+/**
+ * Pure function: Handle workspace notifications (immutable state update).
+ * The analysis server reports analysis as `$/progress` to clients that
+ * support it and as `$/analyzerStatus` otherwise.
+ */
+const handleNotification = (
+  state: WorkspaceLoaderState,
+  method: string,
+  params?: unknown
+): WorkspaceLoaderState => {
+  if (state.ready) {
+    return state;
+  }
+
+  if (method === '$/analyzerStatus') {
+    const { isAnalyzing } = (params ?? {}) as { isAnalyzing?: boolean };
+    if (isAnalyzing === false) {
+      logger.info('Dart workspace analyzed');
+      return { ...state, ready: true };
+    }
+    return state;
+  }
+
+  if (method === '$/progress') {
+    const { value } = params as ProgressParams<{ kind?: string }>;
+    // Analysis is the only work reported as progress, and may begin before
+    // the loader is initialized
+    if (value.kind === 'end') {
+      logger.info('Dart workspace analyzed');
+      return { ...state, ready: true };
+    }
+  }
+
+  // Return unchanged state for other notifications
+  return state;
+};
+
+/**
+ * Pure function: Run the analysis server of a Flutter app from its Flutter
+ * SDK, whose Dart SDK matches the framework, rather than the dart on the
+ * PATH. Commands naming another executable are left as they are.
+ */
+const prepareCommand = (
+  workspacePath: string,
+  command: WorkspaceCommand
+): WorkspaceCommand => {
+  const executable = path.basename(command.commandName);
+  if (executable !== 'dart' && executable !== 'dart.exe') {
+    return command;
+  }
+
+  const pubspecPath = findPubspec(workspacePath);
+  if (!pubspecPath || !isFlutterPackage(pubspecPath)) {
+    return command;
+  }
+
+  const flutterRoot = findFlutterSdk(path.dirname(pubspecPath), workspacePath);
+  const dart = flutterRoot ? getFlutterDart(flutterRoot) : null;
+  if (!flutterRoot || !dart) {
+    logger.warn('Flutter app found without a Flutter SDK', { pubspecPath });
+    return command;
+  }
+
+  logger.info('Running the Dart analysis server from the Flutter SDK', {
+    flutterRoot,
+  });
+  return {
+    ...command,
+    commandName: dart,
+    environment: { ...command.environment, FLUTTER_ROOT: flutterRoot },
+  };
+};
+
+/**
+ * Factory function to create Dart workspace loader
+ */
+export const createDartLoader = (): WorkspaceLoader => ({
+  initialize,
+  isReady,
+  handleNotification,
+  prepareCommand,
+});
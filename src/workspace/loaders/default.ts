@@ -1,27 +1,125 @@
 /**
  * Default Workspace Loader - Pure Functional Implementation
- * Always ready, no special initialization needed
+ * Always ready, unless the profile's readiness says what to wait for
  */
 
+import type {
+  LogMessageParams,
+  ProgressParams,
+} from 'vscode-languageserver-protocol';
 import { WorkspaceLoader, WorkspaceLoaderState } from '../types.js';
+import { LspClient, LspConfig, WorkspaceReadiness } from '../../types.js';
+import logger from '../../utils/logger.js';
 
 /**
- * Pure function: Initialize default workspace (always ready)
+ * Default workspace state data, when readiness is configured
  */
-const initialize = async (): Promise<WorkspaceLoaderState> => {
+type DefaultWorkspaceData = {
+  readonly readiness: WorkspaceReadiness;
+  readonly startedAt: number;
+  // Progress tokens with a matching title begun so far
+  readonly progressTokens: readonly string[];
+};
+
+/**
+ * Pure function: Initialize default workspace. Ready straight away unless
+ * the profile configures a progress title or log message to wait for.
+ */
+const initialize = async (
+  _client: LspClient,
+  config: LspConfig
+): Promise<WorkspaceLoaderState> => {
   // Await a resolved promise to satisfy eslint require-await
   await Promise.resolve();
 
+  const readiness = config.readiness;
+  if (!readiness || (!readiness.progressTitle && !readiness.logMessage)) {
+    return {
+      type: 'default' as const,
+      ready: true,
+    };
+  }
+
   return {
     type: 'default' as const,
-    ready: true,
+    ready: false,
+    data: {
+      readiness,
+      startedAt: Date.now(),
+      progressTokens: [],
+    } satisfies DefaultWorkspaceData,
   };
 };
 
 /**
- * Pure function: Check if default workspace is ready (always true)
+ * Pure function: Check if default workspace is ready, which it is once the
+ * configured readiness timeout has passed
+ */
+const isReady = (state: WorkspaceLoaderState): boolean => {
+  const data = state.data as DefaultWorkspaceData | undefined;
+  return (
+    state.ready ||
+    (data !== undefined &&
+      Date.now() - data.startedAt > data.readiness.timeoutMs)
+  );
+};
+
+/**
+ * Pure function: Handle workspace notifications (immutable state update).
+ * Ready when progress with a matching title ends or a matching message is
+ * logged.
  */
-const isReady = (): boolean => true;
+const handleNotification = (
+  state: WorkspaceLoaderState,
+  method: string,
+  params?: unknown
+): WorkspaceLoaderState => {
+  const data = state.data as DefaultWorkspaceData | undefined;
+  if (state.ready || !data) {
+    return state;
+  }
+
+  const { progressTitle, logMessage } = data.readiness;
+
+  if (method === 'window/logMessage' && logMessage) {
+    const { message } = params as LogMessageParams;
+    if (new RegExp(logMessage, 'i').test(message)) {
+      logger.info('Workspace ready after log message', { message });
+      return { ...state, ready: true };
+    }
+    return state;
+  }
+
+  if (method !== '$/progress' || !progressTitle) {
+    return state;
+  }
+
+  const { token, value } = params as ProgressParams<{
+    kind?: string;
+    title?: string;
+  }>;
+  const key = String(token);
+
+  if (
+    value.kind === 'begin' &&
+    new RegExp(progressTitle, 'i').test(value.title ?? '')
+  ) {
+    return {
+      ...state,
+      data: {
+        ...data,
+        progressTokens: [...data.progressTokens, key],
+      } satisfies DefaultWorkspaceData,
+    };
+  }
+
+  if (value.kind === 'end' && data.progressTokens.includes(key)) {
+    logger.info('Workspace ready after progress ended', { token: key });
+    return { ...state, ready: true };
+  }
+
+  return state;
+};
 
 /**
  * Factory function to create default workspace loader
@@ -29,5 +127,5 @@ const isReady = (): boolean => true;
 export const createDefaultLoader = (): WorkspaceLoader => ({
   initialize,
   isReady,
-  // No handleNotification needed - default workspace doesn't handle notifications
+  handleNotification,
 });
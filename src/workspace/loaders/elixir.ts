@@ -0,0 +1,182 @@
+/**
+ * ElixirLS Workspace Loader - Pure Functional Implementation
+ * Sends the settings ElixirLS waits for and holds requests until the Mix
+ * project has compiled
+ */
+
+import { fileURLToPath } from 'url';
+import type {
+  LogMessageParams,
+  ProgressParams,
+} from 'vscode-languageserver-protocol';
+import {
+  WorkspaceCommand,
+  WorkspaceLoader,
+  WorkspaceLoaderState,
+} from '../types.js';
+import { LspClient, LspConfig } from '../../types.js';
+import {
+  createElixirConfigurationNotification,
+  detectMixProject,
+  MixProjectInfo,
+} from '../../utils/elixir-workspace.js';
+import logger from '../../utils/logger.js';
+
+// Progress titles ElixirLS uses while it fetches deps and compiles
+const BUILD_TITLE_PATTERN = /compil|build/i;
+
+// Logged by ElixirLS at the end of each build
+const BUILD_DONE_PATTERN = /compile took \d+ ms/i;
+
+// Treated as ready after this long; a first build fetching deps is slow
+const MAX_BUILD_MS = 180_000;
+
+/**
+ * Elixir workspace state data
+ */
+type ElixirWorkspaceData = {
+  readonly projectInfo: MixProjectInfo;
+  readonly buildStartedAt: number;
+  // Progress tokens of the build begun so far
+  readonly buildTokens: readonly string[];
+};
+
+/**
+ * Pure function: Initialize Elixir workspace. ElixirLS doesn't build until
+ * it has received its settings, so they are sent even without a project.
+ */
+const initialize = async (
+  client: LspClient,
+  config: LspConfig
+): Promise<WorkspaceLoaderState> => {
+  try {
+    const workspacePath = fileURLToPath(config.workspaceUri);
+    const projectInfo = detectMixProject(workspacePath);
+
+    const notification = createElixirConfigurationNotification(
+      workspacePath,
+      projectInfo ?? { projectDir: workspacePath, umbrella: false }
+    );
+    logger.info('Sending ElixirLS settings', {
+      method: notification.method,
+      params: notification.params,
+    });
+    await client.connection.sendNotification(
+      notification.method,
+      notification.params
+    );
+
+    if (!projectInfo) {
+      logger.debug('No Mix project detected, defaulting to ready state');
+      return {
+        type: 'elixir' as const,
+        ready: true,
+      };
+    }
+
+    return {
+      type: 'elixir' as const,
+      ready: false,
+      data: {
+        projectInfo,
+        buildStartedAt: Date.now(),
+        buildTokens: [],
+      } satisfies ElixirWorkspaceData,
+    };
+  } catch (error) {
+    logger.error('Failed to initialize Elixir workspace', {
+      error: error instanceof Error ? error.message : String(error),
+      stack: error instanceof Error ? error.stack : undefined,
+    });
+
+    // On error, default to ready state to not block operations
+    return {
+      type: 'elixir' as const,
+      ready: true,
+    };
+  }
+};
+
+/**
+ * Pure function: Check if Elixir workspace is ready
+ */
+const isReady = (state: WorkspaceLoaderState): boolean => {
+  const data = state.data as ElixirWorkspaceData | undefined;
+  return (
+    state.ready ||
+    (data !== undefined && Date.now() - data.buildStartedAt > MAX_BUILD_MS)
+  );
+};
+
+/**
+ * Pure function: Handle workspace notifications (immutable state update).
+ * The build is done when a build progress ends or ElixirLS logs its
+ * compile time, whichever its version reports.
+ */
+const handleNotification = (
+  state: WorkspaceLoaderState,
+  method: string,
+  params?: unknown
+): WorkspaceLoaderState => {
+  const data = state.data as ElixirWorkspaceData | undefined;
+  if (state.ready || !data) {
+    return state;
+  }
+
+  if (method === 'window/logMessage') {
+    const { message } = params as LogMessageParams;
+    if (BUILD_DONE_PATTERN.test(message)) {
+      logger.info('Elixir project compiled');
+      return { ...state, ready: true };
+    }
+    return state;
+  }
+
+  if (method !== '$/progress') {
+    return state;
+  }
+
+  const { token, value } = params as ProgressParams<{
+    kind?: string;
+    title?: string;
+  }>;
+  const key = String(token);
+
+  if (value.kind === 'begin' && BUILD_TITLE_PATTERN.test(value.title ?? '')) {
+    return {
+      ...state,
+      data: {
+        ...data,
+        buildTokens: [...data.buildTokens, key],
+      } satisfies ElixirWorkspaceData,
+    };
+  }
+
+  if (value.kind === 'end' && data.buildTokens.includes(key)) {
+    logger.info('Elixir project compiled');
+    return { ...state, ready: true };
+  }
+
+  // Return unchanged state for other notifications
+  return state;
+};
+
+/**
+ * Pure function: ElixirLS starts through a launch script that runs the
+ * server as a child of its own, which stopping the script alone would leave
+ * behind, so both are started in one process group
+ */
+const prepareCommand = (
+  _workspacePath: string,
+  command: WorkspaceCommand
+): WorkspaceCommand => ({ ...command, processGroup: true });
+
+/**
+ * Factory function to create ElixirLS workspace loader
+ */
+export const createElixirLoader = (): WorkspaceLoader => ({
+  initialize,
+  isReady,
+  handleNotification,
+  prepareCommand,
+});
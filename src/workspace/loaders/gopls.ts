@@ -0,0 +1,152 @@
+/**
+ * gopls Workspace Loader - Pure Functional Implementation
+ * Handles go.mod/go.work detection and waits for gopls to load packages
+ */
+
+import { fileURLToPath } from 'url';
+import type { ProgressParams } from 'vscode-languageserver-protocol';
+import { WorkspaceLoader, WorkspaceLoaderState } from '../types.js';
+import { LspClient, LspConfig } from '../../types.js';
+import {
+  detectGoWorkspace,
+  createGoWorkspaceFoldersNotification,
+  GoWorkspaceInfo,
+} from '../../utils/go-workspace.js';
+import logger from '../../utils/logger.js';
+
+// Progress titles gopls uses while it loads the workspace's packages
+const LOADING_TITLE_PATTERN = /setting up workspace|loading packages/i;
+
+// Treated as ready after this long, for gopls versions without progress
+const MAX_LOADING_MS = 120_000;
+
+/**
+ * Go workspace state data
+ */
+type GoWorkspaceData = {
+  readonly workspaceInfo: GoWorkspaceInfo;
+  readonly loadingStartedAt: number;
+  // Progress tokens of work other than loading, such as a later build
+  readonly otherTokens: readonly string[];
+};
+
+/**
+ * Pure function: Initialize Go workspace
+ */
+const initialize = async (
+  client: LspClient,
+  config: LspConfig
+): Promise<WorkspaceLoaderState> => {
+  try {
+    const workspacePath = fileURLToPath(config.workspaceUri);
+    const goWorkspace = await detectGoWorkspace(workspacePath);
+
+    if (!goWorkspace) {
+      logger.debug('No Go workspace detected, defaulting to ready state');
+      return {
+        type: 'gopls' as const,
+        ready: true,
+      };
+    }
+
+    const notification = createGoWorkspaceFoldersNotification(
+      workspacePath,
+      goWorkspace
+    );
+    if (notification) {
+      logger.info('Adding nested Go modules as workspace folders', {
+        method: notification.method,
+        params: notification.params,
+      });
+      await client.connection.sendNotification(
+        notification.method,
+        notification.params
+      );
+    }
+
+    // Loading started with the initialized notification
+    return {
+      type: 'gopls' as const,
+      ready: false,
+      data: {
+        workspaceInfo: goWorkspace,
+        loadingStartedAt: Date.now(),
+        otherTokens: [],
+      } satisfies GoWorkspaceData,
+    };
+  } catch (error) {
+    logger.error('Failed to initialize Go workspace', {
+      error: error instanceof Error ? error.message : String(error),
+      stack: error instanceof Error ? error.stack : undefined,
+    });
+
+    // On error, default to ready state to not block operations
+    return {
+      type: 'gopls' as const,
+      ready: true,
+    };
+  }
+};
+
+/**
+ * Pure function: Check if Go workspace is ready
+ */
+const isReady = (state: WorkspaceLoaderState): boolean => {
+  const data = state.data as GoWorkspaceData | undefined;
+  return (
+    state.ready ||
+    (data !== undefined && Date.now() - data.loadingStartedAt > MAX_LOADING_MS)
+  );
+};
+
+/**
+ * Pure function: Handle workspace notifications (immutable state update).
+ * The loading progress may begin before the loader is initialized, so any
+ * progress that ends without having begun as other work marks it loaded.
+ */
+const handleNotification = (
+  state: WorkspaceLoaderState,
+  method: string,
+  params?: unknown
+): WorkspaceLoaderState => {
+  const data = state.data as GoWorkspaceData | undefined;
+  if (method !== '$/progress' || state.ready || !data) {
+    return state;
+  }
+
+  const { token, value } = params as ProgressParams<{
+    kind?: string;
+    title?: string;
+  }>;
+  const key = String(token);
+
+  if (
+    value.kind === 'begin' &&
+    !LOADING_TITLE_PATTERN.test(value.title ?? '')
+  ) {
+    return {
+      ...state,
+      data: {
+        ...data,
+        otherTokens: [...data.otherTokens, key],
+      } satisfies GoWorkspaceData,
+    };
+  }
+
+  if (value.kind === 'end' && !data.otherTokens.includes(key)) {
+    logger.info('Go workspace packages loaded');
+    return { ...state, ready: true };
+  }
+
+  // Return unchanged state for other notifications
+  return state;
+};
+
+/**
+ * Factory function to create gopls workspace loader
+ */
+export const createGoplsLoader = (): WorkspaceLoader => ({
+  initialize,
+  isReady,
+  handleNotification,
+});
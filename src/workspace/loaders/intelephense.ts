@@ -0,0 +1,101 @@
+/**
+ * Intelephense Workspace Loader - Pure Functional Implementation
+ * Waits for Intelephense to index Composer projects and their vendor code
+ */
+
+import { fileURLToPath } from 'url';
+import { WorkspaceLoader, WorkspaceLoaderState } from '../types.js';
+import { LspClient, LspConfig } from '../../types.js';
+import { findComposerRoots } from '../../utils/php-workspace.js';
+import logger from '../../utils/logger.js';
+
+// Treated as ready after this long, in case indexing never reports its end
+const MAX_INDEXING_MS = 120_000;
+
+/**
+ * PHP workspace state data
+ */
+type PhpWorkspaceData = {
+  readonly composerRoots: readonly string[];
+  readonly indexingStartedAt: number;
+};
+
+/**
+ * Pure function: Initialize PHP workspace. Without Composer there is no
+ * vendor code to index, so requests aren't held.
+ */
+const initialize = async (
+  _client: LspClient,
+  config: LspConfig
+): Promise<WorkspaceLoaderState> => {
+  try {
+    const workspacePath = fileURLToPath(config.workspaceUri);
+    const composerRoots = await findComposerRoots(workspacePath);
+
+    if (composerRoots.length === 0) {
+      logger.debug('No Composer project detected, defaulting to ready state');
+      return {
+        type: 'intelephense' as const,
+        ready: true,
+      };
+    }
+
+    return {
+      type: 'intelephense' as const,
+      ready: false,
+      data: {
+        composerRoots,
+        indexingStartedAt: Date.now(),
+      } satisfies PhpWorkspaceData,
+    };
+  } catch (error) {
+    logger.error('Failed to initialize PHP workspace', {
+      error: error instanceof Error ? error.message : String(error),
+      stack: error instanceof Error ? error.stack : undefined,
+    });
+
+    // On error, default to ready state to not block operations
+    return {
+      type: 'intelephense' as const,
+      ready: true,
+    };
+  }
+};
+
+/**
+ * Pure function: Check if PHP workspace is ready
+ */
+const isReady = (state: WorkspaceLoaderState): boolean => {
+  const data = state.data as PhpWorkspaceData | undefined;
+  return (
+    state.ready ||
+    (data !== undefined &&
+      Date.now() - data.indexingStartedAt > MAX_INDEXING_MS)
+  );
+};
+
+/**
+ * Pure function: Handle workspace notifications (immutable state update).
+ * Intelephense sends indexingStarted and indexingEnded around each index.
+ */
+const handleNotification = (
+  state: WorkspaceLoaderState,
+  method: string
+): WorkspaceLoaderState => {
+  if (method === 'indexingEnded' && !state.ready) {
+    logger.info('PHP workspace indexed');
+    return { ...state, ready: true };
+  }
+
+  // Return unchanged state for other notifications
+  return state;
+};
+
+/**
+ * Factory function to create Intelephense workspace loader
+ */
+export const createIntelephenseLoader = (): WorkspaceLoader => ({
+  initialize,
+  isReady,
+  handleNotification,
+});
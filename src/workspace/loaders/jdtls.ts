@@ -0,0 +1,35 @@
+/**
+ * JDT LS Workspace Loader - Pure Functional Implementation
+ * Waits for JDT LS to import the Maven/Gradle projects
+ */
+
+import { WorkspaceLoader } from '../types.js';
+import { createJvmLoader, JvmImportHandler } from './jvm.js';
+
+type LanguageStatusParams = {
+  type?: string;
+  message?: string;
+};
+
+/**
+ * Pure function: JDT LS reports `language/status` Started once the projects
+ * are imported, or Error when the import failed; either way requests can go
+ * ahead
+ */
+const handleImport: JvmImportHandler = (data, method, params) => {
+  if (method !== 'language/status') {
+    return data;
+  }
+
+  const { type } = (params ?? {}) as LanguageStatusParams;
+  if (type === 'Started' || type === 'ServiceReady') {
+    return 'imported';
+  }
+  return type === 'Error' ? 'failed' : data;
+};
+
+/**
+ * Factory function to create JDT LS workspace loader
+ */
+export const createJdtlsLoader = (): WorkspaceLoader =>
+  createJvmLoader('jdtls', handleImport);
@@ -0,0 +1,114 @@
+/**
+ * JVM Workspace Loaders - Pure Functional Implementation
+ * Wait logic shared by the Java, Kotlin and Scala servers, which import
+ * the build before they can answer requests
+ */
+
+import { fileURLToPath } from 'url';
+import { WorkspaceLoader, WorkspaceLoaderState } from '../types.js';
+import { LspClient, LspConfig } from '../../types.js';
+import {
+  detectJvmWorkspace,
+  JvmWorkspaceInfo,
+} from '../../utils/jvm-workspace.js';
+import logger from '../../utils/logger.js';
+
+// Treated as ready after this long, in case the server never says so. The
+// first import downloads dependencies and takes minutes.
+const MAX_IMPORT_MS = 300_000;
+const MAX_STANDALONE_MS = 60_000;
+
+type JvmLoaderType = 'jdtls' | 'kotlin' | 'metals';
+
+/**
+ * JVM workspace state data
+ */
+export type JvmWorkspaceData = {
+  readonly workspaceInfo: JvmWorkspaceInfo | null;
+  readonly loadingStartedAt: number;
+  // Progress tokens begun and not ended yet
+  readonly activeTokens: readonly string[];
+};
+
+/**
+ * Pure function type reading a server notification during the import:
+ * updated data while it goes on, or whether it finished or failed
+ */
+export type JvmImportHandler = (
+  data: JvmWorkspaceData,
+  method: string,
+  params: unknown
+) => JvmWorkspaceData | 'imported' | 'failed';
+
+/**
+ * Factory function to create a loader for a JVM language server. Servers
+ * open standalone files without a build file too, so they are waited for
+ * either way, for less long.
+ */
+export const createJvmLoader = (
+  type: JvmLoaderType,
+  handleImport: JvmImportHandler
+): WorkspaceLoader => {
+  const initialize = async (
+    _client: LspClient,
+    config: LspConfig
+  ): Promise<WorkspaceLoaderState> => {
+    try {
+      const workspacePath = fileURLToPath(config.workspaceUri);
+      const workspaceInfo = await detectJvmWorkspace(workspacePath);
+
+      // Importing started with the initialized notification
+      return {
+        type,
+        ready: false,
+        data: {
+          workspaceInfo,
+          loadingStartedAt: Date.now(),
+          activeTokens: [],
+        } satisfies JvmWorkspaceData,
+      };
+    } catch (error) {
+      logger.error('Failed to initialize JVM workspace', {
+        type,
+        error: error instanceof Error ? error.message : String(error),
+        stack: error instanceof Error ? error.stack : undefined,
+      });
+
+      // On error, default to ready state to not block operations
+      return { type, ready: true };
+    }
+  };
+
+  const isReady = (state: WorkspaceLoaderState): boolean => {
+    const data = state.data as JvmWorkspaceData | undefined;
+    if (state.ready || !data) {
+      return state.ready;
+    }
+    const maxLoadingMs = data.workspaceInfo ? MAX_IMPORT_MS : MAX_STANDALONE_MS;
+    return Date.now() - data.loadingStartedAt > maxLoadingMs;
+  };
+
+  const handleNotification = (
+    state: WorkspaceLoaderState,
+    method: string,
+    params?: unknown
+  ): WorkspaceLoaderState => {
+    const data = state.data as JvmWorkspaceData | undefined;
+    if (state.ready || !data) {
+      return state;
+    }
+
+    const result = handleImport(data, method, params);
+    if (result === 'imported') {
+      logger.info('JVM build imported', { type });
+      return { ...state, ready: true };
+    }
+    if (result === 'failed') {
+      logger.warn('JVM build import failed', { type, params });
+      return { ...state, ready: true };
+    }
+    return result === data ? state : { ...state, data: result };
+  };
+
+  return { initialize, isReady, handleNotification };
+};
@@ -0,0 +1,39 @@
+/**
+ * Kotlin Workspace Loader - Pure Functional Implementation
+ * Waits for the Kotlin language server to import the Gradle or Maven build
+ */
+
+import type { ProgressParams } from 'vscode-languageserver-protocol';
+import { WorkspaceLoader } from '../types.js';
+import { createJvmLoader, JvmImportHandler } from './jvm.js';
+
+/**
+ * Pure function: the Kotlin servers report the import as `$/progress`, so
+ * it is done once every progress begun has ended. The first one may begin
+ * before the loader is initialized, so an end with nothing else running
+ * counts too.
+ */
+const handleImport: JvmImportHandler = (data, method, params) => {
+  if (method !== '$/progress') {
+    return data;
+  }
+
+  const { token, value } = params as ProgressParams<{ kind?: string }>;
+  const key = String(token);
+
+  if (value.kind === 'begin') {
+    return { ...data, activeTokens: [...data.activeTokens, key] };
+  }
+  if (value.kind !== 'end') {
+    return data;
+  }
+
+  const activeTokens = data.activeTokens.filter((active) => active !== key);
+  return activeTokens.length === 0 ? 'imported' : { ...data, activeTokens };
+};
+
+/**
+ * Factory function to create Kotlin workspace loader
+ */
+export const createKotlinLoader = (): WorkspaceLoader =>
+  createJvmLoader('kotlin', handleImport);
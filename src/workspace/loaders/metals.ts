@@ -0,0 +1,68 @@
+/**
+ * Metals Workspace Loader - Pure Functional Implementation
+ * Accepts the offer to import the sbt, Mill, Gradle or Maven build and
+ * waits for Metals to index it
+ */
+
+import type { ProgressParams } from 'vscode-languageserver-protocol';
+import { WorkspaceLoader } from '../types.js';
+import { createJvmLoader, JvmImportHandler } from './jvm.js';
+
+// Prompt actions that import a new or changed build
+const IMPORT_ACTION_PATTERN = /^import (build|changes)$/i;
+
+// Progress and status titles of the indexing that follows an import
+const INDEXING_PATTERN = /index/i;
+
+type MetalsStatusParams = {
+  text?: string;
+  hide?: boolean;
+};
+
+/**
+ * Pure function: Metals indexes once the build is imported, reporting it
+ * as `$/progress` and in `metals/status` when the status bar is enabled.
+ * A status about a failed import stops the wait too.
+ */
+const handleImport: JvmImportHandler = (data, method, params) => {
+  if (method === 'metals/status') {
+    const { text = '', hide } = (params ?? {}) as MetalsStatusParams;
+    if (hide) {
+      return data;
+    }
+    if (/indexing complete/i.test(text)) {
+      return 'imported';
+    }
+    return /fail/i.test(text) ? 'failed' : data;
+  }
+
+  if (method !== '$/progress') {
+    return data;
+  }
+
+  const { token, value } = params as ProgressParams<{
+    kind?: string;
+    title?: string;
+  }>;
+  const key = String(token);
+
+  if (value.kind === 'begin' && INDEXING_PATTERN.test(value.title ?? '')) {
+    return { ...data, activeTokens: [...data.activeTokens, key] };
+  }
+  if (value.kind === 'end' && data.activeTokens.includes(key)) {
+    return 'imported';
+  }
+  return data;
+};
+
+/**
+ * Factory function to create Metals workspace loader. Metals asks before
+ * importing a build it hasn't seen, and nothing resolves until it has.
+ */
+export const createMetalsLoader = (): WorkspaceLoader => ({
+  ...createJvmLoader('metals', handleImport),
+  chooseMessageAction: (params) =>
+    params.actions?.find((action) =>
+      IMPORT_ACTION_PATTERN.test(action.title)
+    ) ?? null,
+});
@@ -0,0 +1,74 @@
+/**
+ * Ruby Workspace Loader - Pure Functional Implementation
+ * Runs ruby-lsp or Solargraph through Bundler when the project bundles it
+ */
+
+import path from 'path';
+import {
+  WorkspaceCommand,
+  WorkspaceLoader,
+  WorkspaceLoaderState,
+} from '../types.js';
+import { findGemfile, isGemBundled } from '../../utils/ruby-workspace.js';
+import logger from '../../utils/logger.js';
+
+// Servers that have to run inside the project's bundle when it pins them
+const BUNDLED_SERVERS = ['ruby-lsp', 'solargraph'];
+
+/**
+ * Pure function: Initialize Ruby workspace. The servers index in the
+ * background and answer from what they have, so requests aren't held.
+ */
+const initialize = async (): Promise<WorkspaceLoaderState> => {
+  // Await a resolved promise to satisfy eslint require-await
+  await Promise.resolve();
+
+  return {
+    type: 'ruby' as const,
+    ready: true,
+  };
+};
+
+/**
+ * Pure function: Check if Ruby workspace is ready (always true)
+ */
+const isReady = (): boolean => true;
+
+/**
+ * Pure function: Wrap the server in `bundle exec` when the Gemfile bundles
+ * it. BUNDLE_GEMFILE names the Gemfile, since the server isn't started from
+ * the workspace directory and the Gemfile may be nested.
+ */
+const prepareCommand = (
+  workspacePath: string,
+  command: WorkspaceCommand
+): WorkspaceCommand => {
+  const server = path.basename(command.commandName);
+  if (!BUNDLED_SERVERS.includes(server)) {
+    return command;
+  }
+
+  const gemfile = findGemfile(workspacePath);
+  if (!gemfile || !isGemBundled(gemfile, server)) {
+    return command;
+  }
+
+  logger.info('Running Ruby language server through Bundler', {
+    server,
+    gemfile,
+  });
+  return {
+    commandName: 'bundle',
+    commandArgs: ['exec', server, ...command.commandArgs],
+    environment: { ...command.environment, BUNDLE_GEMFILE: gemfile },
+  };
+};
+
+/**
+ * Factory function to create Ruby workspace loader
+ */
+export const createRubyLoader = (): WorkspaceLoader => ({
+  initialize,
+  isReady,
+  prepareCommand,
+});
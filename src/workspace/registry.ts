@@ -6,6 +6,16 @@
 import { WorkspaceLoader, WorkspaceLoaderRegistry } from './types.js';
 import { createDefaultLoader } from './loaders/default.js';
 import { createRoslynLoader } from './loaders/roslyn.js';
+import { createGoplsLoader } from './loaders/gopls.js';
+import { createJdtlsLoader } from './loaders/jdtls.js';
+import { createKotlinLoader } from './loaders/kotlin.js';
+import { createClangdLoader } from './loaders/clangd.js';
+import { createRubyLoader } from './loaders/ruby.js';
+import { createIntelephenseLoader } from './loaders/intelephense.js';
+import { createElixirLoader } from './loaders/elixir.js';
+import { createMetalsLoader } from './loaders/metals.js';
+import { createDartLoader } from './loaders/dart.js';
+import { createJuliaLoader } from './loaders/julia.js';
 
 /**
  * Registry of workspace loader factories
@@ -14,8 +24,17 @@ import { createRoslynLoader } from './loaders/roslyn.js';
 const WORKSPACE_LOADERS: WorkspaceLoaderRegistry = {
   default: createDefaultLoader,
   roslyn: createRoslynLoader,
+  gopls: createGoplsLoader,
+  jdtls: createJdtlsLoader,
+  kotlin: createKotlinLoader,
+  clangd: createClangdLoader,
+  ruby: createRubyLoader,
+  intelephense: createIntelephenseLoader,
+  elixir: createElixirLoader,
+  metals: createMetalsLoader,
+  dart: createDartLoader,
+  julia: createJuliaLoader,
   // Future loaders can be added here:
-  // 'java': createJavaLoader,
   // 'rust': createRustLoader,
 } as const;
 
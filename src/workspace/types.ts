@@ -3,13 +3,29 @@
  * Defines types for workspace initialization and state management without classes or mutable state
  */
 
+import type {
+  MessageActionItem,
+  ShowMessageRequestParams,
+} from 'vscode-languageserver-protocol';
 import { LspClient, LspConfig } from '../types.js';
 
 /**
  * Immutable workspace loader state - holds workspace-specific information
  */
 export type WorkspaceLoaderState = {
-  readonly type: 'default' | 'roslyn';
+  readonly type:
+    | 'default'
+    | 'roslyn'
+    | 'gopls'
+    | 'jdtls'
+    | 'kotlin'
+    | 'clangd'
+    | 'ruby'
+    | 'intelephense'
+    | 'elixir'
+    | 'metals'
+    | 'dart'
+    | 'julia';
   readonly ready: boolean;
   readonly data?: unknown;
 };
@@ -37,6 +53,36 @@ type HandleWorkspaceNotification = (
   params?: unknown
 ) => WorkspaceLoaderState;
 
+/**
+ * Language server command, after environment variable expansion
+ */
+export type WorkspaceCommand = {
+  readonly commandName: string;
+  readonly commandArgs: readonly string[];
+  // Added to the server process environment
+  readonly environment?: Readonly<Record<string, string>>;
+  // Runs the server in its own process group, stopped as a whole, for
+  // launch scripts that start the server as a child process
+  readonly processGroup?: boolean;
+};
+
+/**
+ * Pure function type for adapting the server command to the workspace
+ * Runs before the server is spawned and returns the command to spawn
+ */
+type PrepareWorkspaceCommand = (
+  workspacePath: string,
+  command: WorkspaceCommand
+) => WorkspaceCommand;
+
+/**
+ * Pure function type for answering a server prompt, such as an offer to
+ * import the build. Returns null to leave it unanswered.
+ */
+type ChooseMessageAction = (
+  params: ShowMessageRequestParams
+) => MessageActionItem | null;
+
 /**
  * Workspace loader - bundle of pure functions
  * No mutable state, just behavior
@@ -45,6 +91,8 @@ export type WorkspaceLoader = {
   readonly initialize: InitializeWorkspace;
   readonly isReady: IsWorkspaceReady;
   readonly handleNotification?: HandleWorkspaceNotification;
+  readonly prepareCommand?: PrepareWorkspaceCommand;
+  readonly chooseMessageAction?: ChooseMessageAction;
 };
 
 /**
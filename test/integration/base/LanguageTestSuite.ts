@@ -97,18 +97,42 @@ export abstract class LanguageTestSuite {
   protected addCommonTests(): void {
     test('Should list all tools', async () => {
       const tools = await this.client.listTools();
-      const expectedCount = this.config.expectedToolCount || 9;
+      const expectedCount = this.config.expectedToolCount || 29;
 
       expect(tools).toHaveLength(expectedCount);
-      expect(tools.map((t) => t.name)).toContain('reload');
-      expect(tools.map((t) => t.name)).toContain('inspect');
-      expect(tools.map((t) => t.name)).toContain('diagnostics');
-      expect(tools.map((t) => t.name)).toContain('outline');
-      expect(tools.map((t) => t.name)).toContain('references');
-      expect(tools.map((t) => t.name)).toContain('call_hierarchy');
-      expect(tools.map((t) => t.name)).toContain('completion');
-      expect(tools.map((t) => t.name)).toContain('search');
-      expect(tools.map((t) => t.name)).toContain('rename');
+      expect(tools.map((t) => t.name)).toEqual(
+        expect.arrayContaining([
+          'apply_code_action',
+          'call_hierarchy',
+          'call_paths',
+          'code_actions',
+          'code_lens',
+          'completion',
+          'declaration',
+          'dependency_source',
+          'diagnostics',
+          'execute_command',
+          'format',
+          'highlights',
+          'implementation',
+          'indexing_status',
+          'inspect',
+          'outline',
+          'references',
+          'related_tests',
+          'reload',
+          'rename',
+          'run_code_lens',
+          'search',
+          'selection_range',
+          'semantic_tokens',
+          'server_logs',
+          'switch_source_header',
+          'type_definition',
+          'type_hierarchy',
+          'workspace_diagnostics',
+        ])
+      );
     });
 
     test('Should read file symbols', async () => {
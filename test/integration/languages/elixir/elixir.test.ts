@@ -0,0 +1,52 @@
+import { LanguageTestSuite, type LanguageConfig } from '../../base/index.js';
+import { test } from 'vitest';
+import { assertSymbolInspection } from '../../base/index.js';
+
+class ElixirTestSuite extends LanguageTestSuite {
+  constructor() {
+    const config: LanguageConfig = {
+      name: 'Elixir',
+      testProjectPath: 'test/integration/languages/elixir/test-project',
+      mainFile: 'lib/greeter.ex',
+      testPosition: { file: '', line: 11, character: 7 }, // on "greet"
+      expectDiagnostics: false, // Elixir project should have no errors
+      customTests: () => {
+        // Elixir-specific tests
+        test('Should inspect Elixir function', async () => {
+          const result = await this.client.inspect({
+            file: this.getMainFilePath(),
+            line: 11, // def greet(%User{name: name}) do
+            character: 7, // on "greet"
+          });
+
+          assertSymbolInspection(result, {
+            symbolName: 'greet',
+          });
+        });
+
+        test('Should find Elixir modules', async () => {
+          const result = await this.client.searchSymbols('Greeter');
+
+          this.assertToolResult(result);
+          this.assertSymbolExists(result, 'Greeter');
+        });
+
+        test('Should read Elixir file symbols', async () => {
+          const result = await this.client.outline(
+            this.getMainFilePath(),
+            true
+          );
+
+          this.assertToolResult(result);
+          this.assertSymbolExists(result, 'greet_all');
+        });
+      },
+    };
+
+    super(config);
+  }
+}
+
+// Create and run the test suite
+const elixirSuite = new ElixirTestSuite();
+elixirSuite.createTestSuite();
@@ -0,0 +1,18 @@
+defmodule Greeter do
+  @moduledoc """
+  Builds greetings for users.
+  """
+
+  alias Greeter.User
+
+  @doc """
+  Greets a user by name.
+  """
+  def greet(%User{name: name}) do
+    "Hello, #{name}!"
+  end
+
+  def greet_all(users) do
+    Enum.map(users, &greet/1)
+  end
+end
@@ -0,0 +1,11 @@
+defmodule Greeter.User do
+  @moduledoc """
+  A user to greet.
+  """
+
+  defstruct [:name, :email]
+
+  def new(name, email) do
+    %__MODULE__{name: name, email: email}
+  end
+end
@@ -83,6 +83,20 @@ class GoTestSuite extends LanguageTestSuite {
           }
         });
 
+        test('Should find symbols in a nested Go module', async () => {
+          const result = await this.client.searchSymbols('BuildVersion');
+
+          expect(result.isError).toBe(false);
+          const contentText = Array.isArray(result.content)
+            ? (result.content.find(
+                (item) => typeof item === 'object' && item && 'text' in item
+              ) as { text: string } | undefined)
+            : undefined;
+
+          // The nested module is only loaded once added as a workspace folder
+          expect(contentText?.text).toContain('BuildVersion');
+        });
+
         test('Should inspect Go utility function', async () => {
           const position = {
             file: 'utils.go',
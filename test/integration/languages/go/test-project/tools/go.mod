@@ -0,0 +1,3 @@
+module test-project/tools
+
+go 1.21
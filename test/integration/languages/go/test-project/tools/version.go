@@ -0,0 +1,7 @@
+// Package tools is a separate module nested in the test project
+package tools
+
+// BuildVersion returns the version stamped into release builds
+func BuildVersion() string {
+	return "0.1.0"
+}
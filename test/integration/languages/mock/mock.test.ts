@@ -0,0 +1,139 @@
+import { LanguageTestSuite, type LanguageConfig } from '../../base/index.js';
+import type { ToolCallResult } from '../../base/index.js';
+import { test, expect } from 'vitest';
+
+function getText(result: ToolCallResult): string {
+  if (result.content instanceof Error) {
+    return result.content.message;
+  }
+  if (!Array.isArray(result.content)) {
+    return JSON.stringify(result.content);
+  }
+  return result.content
+    .map((item: unknown) =>
+      item && typeof item === 'object' && 'text' in item
+        ? String(item.text)
+        : ''
+    )
+    .join('\n');
+}
+
+/**
+ * Runs the tools against the scriptable mock server so responses and faults
+ * are deterministic. Scripted results live in test-project/mock-lsp.json.
+ */
+class MockTestSuite extends LanguageTestSuite {
+  constructor() {
+    const config: LanguageConfig = {
+      name: 'Mock',
+      testProjectPath: 'test/integration/languages/mock/test-project',
+      mainFile: 'main.mock',
+      testPosition: { file: '', line: 1, character: 7 }, // on 'Greeter'
+      expectDiagnostics: true,
+      customTests: () => {
+        test('Should outline scripted document symbols', async () => {
+          const result = await this.client.outline(this.getMainFilePath());
+
+          expect(result.isError).toBe(false);
+          expect(getText(result)).toContain('Greeter');
+          expect(getText(result)).toContain('greet');
+        });
+
+        test('Should show scripted hover content', async () => {
+          const result = await this.client.inspect({
+            file: this.getMainFilePath(),
+            line: 1,
+            character: 7,
+          });
+
+          expect(result.isError).toBe(false);
+          expect(getText(result)).toContain('Greets people by name.');
+        });
+
+        test('Should report partial reference results as returned', async () => {
+          const result = await this.client.getReferences({
+            file: this.getMainFilePath(),
+            line: 1,
+            character: 7,
+          });
+
+          expect(result.isError).toBe(false);
+          expect(getText(result)).toContain('Found 2 reference(s)');
+        });
+
+        test('Should summarize references per file', async () => {
+          const result = await this.client.callTool('references', {
+            file: this.getMainFilePath(),
+            line: 1,
+            character: 7,
+            mode: 'summary',
+          });
+
+          expect(result.isError).toBe(false);
+          expect(getText(result)).toContain('main.mock (2 references)');
+          expect(getText(result)).not.toContain('@');
+        });
+
+        test('Should list compact reference positions', async () => {
+          const result = await this.client.callTool('references', {
+            file: this.getMainFilePath(),
+            line: 1,
+            character: 7,
+            mode: 'compact',
+          });
+
+          expect(result.isError).toBe(false);
+          expect(getText(result)).toContain('@1:7 @7:15');
+        });
+
+        test('Should wait for slow responses', async () => {
+          const result = await this.client.getCompletion({
+            file: this.getMainFilePath(),
+            line: 8,
+            character: 9,
+          });
+
+          expect(result.isError).toBe(false);
+          expect(getText(result)).toContain('greet');
+        });
+
+        test('Should surface server errors with a hint', async () => {
+          const result = await this.client.searchSymbols('Greeter');
+
+          expect(result.isError).toBe(true);
+          expect(getText(result)).toContain('content modified');
+          expect(getText(result)).toContain('Hint:');
+        });
+
+        test('Should publish scripted diagnostics', async () => {
+          const result = await this.client.getDiagnostics(
+            this.getMainFilePath()
+          );
+
+          expect(result.isError).toBe(false);
+          expect(getText(result)).toContain('Greeting the whole world');
+        });
+
+        // Keep last: the scripted crash takes the server down
+        test('Should fail cleanly when the server crashes', async () => {
+          const result = await this.client.renameSymbol(
+            {
+              file: this.getMainFilePath(),
+              line: 1,
+              character: 7,
+            },
+            'Welcomer'
+          );
+
+          expect(result.isError).toBe(true);
+        });
+      },
+    };
+
+    super(config);
+  }
+}
+
+// Create and run the test suite
+const mockSuite = new MockTestSuite();
+mockSuite.createTestSuite();
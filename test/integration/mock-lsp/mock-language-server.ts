@@ -0,0 +1,181 @@
+/**
+ * Mock Language Server - scriptable LSP server for deterministic integration tests
+ *
+ * Responses are read from a `mock-lsp.json` scenario in the workspace root:
+ *
+ * {
+ *   "capabilities": { ... },             // merged over the defaults
+ *   "responses": {
+ *     "textDocument/hover": { "result": { ... } },
+ *     "textDocument/references": { "result": [...], "partial": 1 },
+ *     "workspace/symbol": { "error": { "code": -32801, "message": "..." } },
+ *     "textDocument/definition": { "delayMs": 60000 },
+ *     "textDocument/completion": { "crash": true }
+ *   },
+ *   "diagnostics": [ ... ]               // published for every opened file
+ * }
+ *
+ * The strings `$DOCUMENT_URI` and `$WORKSPACE_URI` in results are replaced
+ * with the request's document URI and the workspace root URI.
+ *
+ * Run with: node --import tsx test/integration/mock-lsp/mock-language-server.ts
+ */
+
+import * as fs from 'fs';
+import * as path from 'path';
+import { fileURLToPath } from 'url';
+import {
+  createMessageConnection,
+  ResponseError,
+  StreamMessageReader,
+  StreamMessageWriter,
+} from 'vscode-jsonrpc/node.js';
+
+interface MockResponse {
+  result?: unknown;
+  error?: { code: number; message: string; data?: unknown };
+  // Delay before responding, longer than the client timeout to simulate hangs
+  delayMs?: number;
+  // Exit the process instead of responding
+  crash?: boolean;
+  // Return only the first N items of an array result
+  partial?: number;
+}
+
+interface MockScenario {
+  capabilities?: Record<string, unknown>;
+  responses?: Record<string, MockResponse>;
+  diagnostics?: unknown[];
+}
+
+const SCENARIO_FILE = 'mock-lsp.json';
+
+const DEFAULT_CAPABILITIES = {
+  textDocumentSync: 1,
+  hoverProvider: true,
+  definitionProvider: true,
+  referencesProvider: true,
+  documentSymbolProvider: true,
+  workspaceSymbolProvider: true,
+  completionProvider: { triggerCharacters: ['.'] },
+  signatureHelpProvider: { triggerCharacters: ['('] },
+  renameProvider: true,
+  callHierarchyProvider: true,
+};
+
+// Results for methods the scenario doesn't script
+const DEFAULT_RESULTS: Record<string, unknown> = {
+  'textDocument/documentSymbol': [],
+  'textDocument/references': [],
+  'textDocument/completion': [],
+  'workspace/symbol': [],
+};
+
+const connection = createMessageConnection(
+  new StreamMessageReader(process.stdin),
+  new StreamMessageWriter(process.stdout)
+);
+
+let scenario: MockScenario = {};
+let workspaceUri = '';
+
+function loadScenario(rootUri: string | null | undefined): MockScenario {
+  if (!rootUri) {
+    return {};
+  }
+
+  const scenarioPath = path.join(fileURLToPath(rootUri), SCENARIO_FILE);
+  if (!fs.existsSync(scenarioPath)) {
+    return {};
+  }
+
+  return JSON.parse(fs.readFileSync(scenarioPath, 'utf8')) as MockScenario;
+}
+
+function getDocumentUri(params: unknown): string | undefined {
+  if (params && typeof params === 'object') {
+    const textDocument = (params as { textDocument?: { uri?: unknown } })
+      .textDocument;
+    if (typeof textDocument?.uri === 'string') {
+      return textDocument.uri;
+    }
+    const item = (params as { item?: { uri?: unknown } }).item;
+    if (typeof item?.uri === 'string') {
+      return item.uri;
+    }
+  }
+  return undefined;
+}
+
+function substitute(value: unknown, documentUri: string | undefined): unknown {
+  const json = JSON.stringify(value ?? null)
+    .replaceAll('$DOCUMENT_URI', documentUri ?? '')
+    .replaceAll('$WORKSPACE_URI', workspaceUri);
+  return JSON.parse(json) as unknown;
+}
+
+function delay(ms: number): Promise<void> {
+  return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+connection.onRequest('initialize', (params: { rootUri?: string | null }) => {
+  workspaceUri = params.rootUri ?? '';
+  scenario = loadScenario(params.rootUri);
+
+  return {
+    capabilities: { ...DEFAULT_CAPABILITIES, ...scenario.capabilities },
+    serverInfo: { name: 'mock-language-server', version: '1.0.0' },
+  };
+});
+
+connection.onRequest('shutdown', () => null);
+
+connection.onNotification('exit', () => process.exit(0));
+
+connection.onNotification(
+  'textDocument/didOpen',
+  (params: { textDocument: { uri: string } }) => {
+    if (!scenario.diagnostics) {
+      return;
+    }
+
+    void connection.sendNotification('textDocument/publishDiagnostics', {
+      uri: params.textDocument.uri,
+      diagnostics: substitute(scenario.diagnostics, params.textDocument.uri),
+    });
+  }
+);
+
+connection.onRequest(async (method: string, params: unknown) => {
+  const response = scenario.responses?.[method];
+  if (!response) {
+    return DEFAULT_RESULTS[method] ?? null;
+  }
+
+  if (response.delayMs) {
+    await delay(response.delayMs);
+  }
+
+  if (response.crash) {
+    process.exit(1);
+  }
+
+  if (response.error) {
+    throw new ResponseError(
+      response.error.code,
+      response.error.message,
+      response.error.data
+    );
+  }
+
+  const result = substitute(response.result, getDocumentUri(params));
+  if (response.partial !== undefined && Array.isArray(result)) {
+    return result.slice(0, response.partial);
+  }
+  return result;
+});
+
+// Ignore every other notification (didChange, didClose, initialized, ...)
+connection.onNotification(() => {});
+
+connection.listen();
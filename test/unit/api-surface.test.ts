@@ -0,0 +1,171 @@
+/**
+ * API Surface Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  checkApiCompatibility,
+  classifySignatureChange,
+  compareApiSurfaces,
+  formatApiCheckReport,
+  getApiModule,
+  getDeclarationSignature,
+  getVersionBump,
+  type ApiSymbol,
+} from '../../src/map/api-surface.js';
+import type { FlattenedSymbol } from '../../src/types/lsp.js';
+
+function apiSymbol(
+  id: string,
+  signature: string,
+  kind = 'Function'
+): ApiSymbol {
+  return { id, name: id, kind, file: 'src/index.ts', line: 1, signature };
+}
+
+describe('API surface', () => {
+  test('should address symbols by file, package or namespace', () => {
+    expect(getApiModule('src/parser.ts')).toBe('src/parser');
+    expect(getApiModule('pkg/server/handler.go')).toBe('pkg/server');
+    expect(getApiModule('src/Services/Parser.cs')).toBe('');
+  });
+
+  test('should read declaration signatures up to the body', () => {
+    const lines = [
+      'export function parse(',
+      '  input: string,',
+      '  options?: Options',
+      '): Ast {',
+    ];
+    const symbol: FlattenedSymbol = {
+      name: 'parse',
+      kind: 12,
+      range: {
+        start: { line: 0, character: 0 },
+        end: { line: 6, character: 1 },
+      },
+      selectionRange: {
+        start: { line: 0, character: 16 },
+        end: { line: 0, character: 21 },
+      },
+    };
+
+    expect(getDeclarationSignature(lines, symbol)).toBe(
+      'parse( input: string, options?: Options ): Ast'
+    );
+
+    const method: FlattenedSymbol = {
+      ...symbol,
+      name: 'run',
+      selectionRange: {
+        start: { line: 0, character: 4 },
+        end: { line: 0, character: 7 },
+      },
+    };
+    expect(getDeclarationSignature(['def run(self, job):'], method)).toBe(
+      'run(self, job)'
+    );
+  });
+
+  test('should treat appended optional parameters as minor', () => {
+    expect(
+      classifySignatureChange(
+        'parse(input: string)',
+        'parse(input: string, strict?: boolean)'
+      )
+    ).toBe('minor');
+    expect(
+      classifySignatureChange('run(self, job)', 'run(self, job, retries=3)')
+    ).toBe('minor');
+    expect(
+      classifySignatureChange(
+        'parse(input: string)',
+        'parse(input: string, strict: boolean)'
+      )
+    ).toBe('major');
+    expect(
+      classifySignatureChange(
+        'parse(input: string): Ast',
+        'parse(input: string): Tree'
+      )
+    ).toBe('major');
+  });
+
+  test('should classify removals, changes and additions', () => {
+    const changes = compareApiSurfaces(
+      [
+        apiSymbol('parse', 'parse(input)'),
+        apiSymbol('format', 'format(ast)'),
+        apiSymbol('Options', 'Options', 'Interface'),
+      ],
+      [
+        apiSymbol('parse', 'parse(input, strict=False)'),
+        apiSymbol('Options', 'Options', 'Class'),
+        apiSymbol('lint', 'lint(ast)'),
+      ]
+    );
+
+    expect(
+      changes.map((change) => [change.level, change.change, change.id])
+    ).toEqual([
+      ['major', 'removed', 'format'],
+      ['major', 'changed', 'Options'],
+      ['minor', 'added', 'lint'],
+      ['minor', 'changed', 'parse'],
+    ]);
+  });
+
+  test.each([
+    ['1.2.3', '2.0.0', 'major'],
+    ['1.2.3', '1.3.0', 'minor'],
+    ['1.2.3', '1.2.4', 'patch'],
+    ['v0.4.1', '0.5.0', 'major'],
+    ['0.4.1', '0.4.2', 'minor'],
+    ['1.2.3', '1.2.3', null],
+    ['1.2.3', 'next', null],
+  ])('should read the bump from %s to %s as %s', (from, to, bump) => {
+    expect(getVersionBump(from, to)).toBe(bump);
+  });
+
+  test('should fail when the declared bump is smaller than required', () => {
+    const report = checkApiCompatibility(
+      { version: '1.2.3', symbols: [apiSymbol('parse', 'parse(input)')] },
+      { version: '1.3.0', symbols: [] },
+      'v1.2.3'
+    );
+
+    expect(report.required).toBe('major');
+    expect(report.declared).toBe('minor');
+    expect(report.sufficient).toBe(false);
+
+    const text = formatApiCheckReport(report, 'markdown');
+    expect(text).toContain('Baseline: v1.2.3 (1.2.3)');
+    expect(text).toContain('Declared bump: minor (1.3.0) - insufficient');
+    expect(text).toContain('## Major (1)');
+    expect(text).toContain('- removed Function parse');
+  });
+
+  test('should pass without a declared release and report the required bump', () => {
+    const report = checkApiCompatibility(
+      { symbols: [apiSymbol('parse', 'parse(input)')] },
+      { symbols: [apiSymbol('parse', 'parse(input)')] },
+      'api-snapshot.json'
+    );
+
+    expect(report.required).toBe('patch');
+    expect(report.sufficient).toBe(true);
+    expect(formatApiCheckReport(report, 'markdown')).toContain(
+      'No public API changes'
+    );
+  });
+
+  test('should reject a release that does not increase the version', () => {
+    expect(() =>
+      checkApiCompatibility(
+        { version: '2.0.0', symbols: [] },
+        { version: '1.9.0', symbols: [] },
+        'v2.0.0'
+      )
+    ).toThrow('is not a semver increase');
+  });
+});
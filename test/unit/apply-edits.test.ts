@@ -0,0 +1,174 @@
+import fs from 'node:fs';
+import os from 'node:os';
+import path from 'node:path';
+import { afterEach, describe, expect, it } from 'vitest';
+import {
+  applyWorkspaceChanges,
+  applyWorkspaceChangesAtomically,
+} from '../../src/tools/utils.js';
+import { configurePathOutput } from '../../src/utils/path-format.js';
+import { parseTextLines, serializeTextLines } from '../../src/utils/text.js';
+
+const tempDirs: string[] = [];
+
+function writeTempFile(content: string): string {
+  const tempDir = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-edits-'));
+  tempDirs.push(tempDir);
+  const filePath = path.join(tempDir, 'sample.ts');
+  fs.writeFileSync(filePath, content, 'utf-8');
+  return filePath;
+}
+
+function rangeOf(
+  startLine: number,
+  startCharacter: number,
+  endLine: number,
+  endCharacter: number
+) {
+  return {
+    start: { line: startLine, character: startCharacter },
+    end: { line: endLine, character: endCharacter },
+  };
+}
+
+afterEach(() => {
+  configurePathOutput({ style: 'relative', baseDirectory: process.cwd() });
+  for (const tempDir of tempDirs.splice(0)) {
+    fs.rmSync(tempDir, { recursive: true, force: true });
+  }
+});
+
+describe('text line round-tripping', () => {
+  it('serializes mixed line endings and BOM unchanged', () => {
+    const content = '\uFEFFconst a = 1;\r\nconst b = 2;\nconst c = 3;\r\n';
+    expect(serializeTextLines(parseTextLines(content))).toBe(content);
+  });
+
+  it('picks the dominant line ending for new line breaks', () => {
+    expect(parseTextLines('a\r\nb\r\nc\n').lineEnding).toBe('\r\n');
+    expect(parseTextLines('a\nb\nc\r\n').lineEnding).toBe('\n');
+  });
+});
+
+describe('applyWorkspaceChanges', () => {
+  it('preserves CRLF line endings and BOM on single-line edits', async () => {
+    const filePath = writeTempFile(
+      '\uFEFFconst value = 1;\r\nconsole.log(value);\r\n'
+    );
+
+    const [result] = await applyWorkspaceChanges({
+      [`file://${filePath}`]: [
+        { range: rangeOf(0, 6, 0, 11), newText: 'total' },
+        { range: rangeOf(1, 12, 1, 17), newText: 'total' },
+      ],
+    });
+
+    expect(result?.success).toBe(true);
+    expect(fs.readFileSync(filePath, 'utf-8')).toBe(
+      '\uFEFFconst total = 1;\r\nconsole.log(total);\r\n'
+    );
+  });
+
+  it('only touches edited lines in mixed-EOL files', async () => {
+    const filePath = writeTempFile('one\r\ntwo\nthree\r\nfour\n');
+
+    await applyWorkspaceChanges({
+      [`file://${filePath}`]: [{ range: rangeOf(2, 0, 2, 5), newText: '3' }],
+    });
+
+    expect(fs.readFileSync(filePath, 'utf-8')).toBe('one\r\ntwo\n3\r\nfour\n');
+  });
+
+  it('uses the file line ending for breaks inserted by multi-line edits', async () => {
+    const filePath = writeTempFile('function a() {\r\n  return 1;\r\n}\r\n');
+
+    await applyWorkspaceChanges({
+      [`file://${filePath}`]: [
+        {
+          range: rangeOf(0, 14, 2, 0),
+          newText: '\n  const x = 1;\n  return x;\n',
+        },
+      ],
+    });
+
+    expect(fs.readFileSync(filePath, 'utf-8')).toBe(
+      'function a() {\r\n  const x = 1;\r\n  return x;\r\n}\r\n'
+    );
+  });
+
+  it('writes through the absolute path whatever the output path style', async () => {
+    const filePath = writeTempFile('const a = 1;\n');
+
+    // Relative paths resolve against the workspace, not the process cwd
+    const edits = { uri: 'b', relative: 'c' } as const;
+    for (const [style, newText] of Object.entries(edits)) {
+      configurePathOutput({
+        style: style as keyof typeof edits,
+        baseDirectory: path.dirname(filePath),
+      });
+      const [result] = await applyWorkspaceChanges({
+        [`file://${filePath}`]: [{ range: rangeOf(0, 6, 0, 7), newText }],
+      });
+      expect(result?.success).toBe(true);
+    }
+
+    expect(fs.readFileSync(filePath, 'utf-8')).toBe('const c = 1;\n');
+  });
+
+  it('leaves files untouched on a dry run', async () => {
+    const filePath = writeTempFile('const a = 1;\n');
+
+    const [result] = await applyWorkspaceChanges(
+      {
+        [`file://${filePath}`]: [
+          { range: rangeOf(0, 6, 0, 7), newText: 'total' },
+        ],
+      },
+      { dryRun: true }
+    );
+
+    expect(result?.updatedContent).toBe('const total = 1;\n');
+    expect(fs.readFileSync(filePath, 'utf-8')).toBe('const a = 1;\n');
+  });
+
+  it('writes nothing when any change of an atomic edit fails', async () => {
+    const first = writeTempFile('const a = 1;\n');
+    const second = writeTempFile('a();\n');
+
+    const { results, applied } = await applyWorkspaceChangesAtomically({
+      [`file://${first}`]: [{ range: rangeOf(0, 6, 0, 7), newText: 'total' }],
+      [`file://${second}`]: [{ range: rangeOf(4, 0, 4, 1), newText: 'total' }],
+    });
+
+    expect(applied).toBe(false);
+    expect(results[1]?.failedChanges).toHaveLength(1);
+    expect(fs.readFileSync(first, 'utf-8')).toBe('const a = 1;\n');
+    expect(fs.readFileSync(second, 'utf-8')).toBe('a();\n');
+  });
+
+  it('writes every file of an atomic edit that applies cleanly', async () => {
+    const first = writeTempFile('const a = 1;\n');
+    const second = writeTempFile('a();\n');
+
+    const { applied } = await applyWorkspaceChangesAtomically({
+      [`file://${first}`]: [{ range: rangeOf(0, 6, 0, 7), newText: 'total' }],
+      [`file://${second}`]: [{ range: rangeOf(0, 0, 0, 1), newText: 'total' }],
+    });
+
+    expect(applied).toBe(true);
+    expect(fs.readFileSync(first, 'utf-8')).toBe('const total = 1;\n');
+    expect(fs.readFileSync(second, 'utf-8')).toBe('total();\n');
+  });
+
+  it('writes an atomic edit to the files it names under uri output', async () => {
+    const filePath = writeTempFile('const a = 1;\n');
+    configurePathOutput({ style: 'uri', baseDirectory: path.dirname(filePath) });
+
+    const { applied } = await applyWorkspaceChangesAtomically({
+      [`file://${filePath}`]: [{ range: rangeOf(0, 6, 0, 7), newText: 'b' }],
+    });
+
+    expect(applied).toBe(true);
+    expect(fs.readFileSync(filePath, 'utf-8')).toBe('const b = 1;\n');
+  });
+});
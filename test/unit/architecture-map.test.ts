@@ -0,0 +1,120 @@
+/**
+ * Architecture Map Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  formatArchitectureMap,
+  getComponentName,
+  ROOT_COMPONENT,
+  type ArchitectureMap,
+} from '../../src/map/architecture-map.js';
+import { selectPublicSymbols } from '../../src/map/workspace-scan.js';
+import type { FlattenedSymbol } from '../../src/types/lsp.js';
+
+function symbol(
+  name: string,
+  line: number,
+  character: number,
+  containerPath?: string[]
+): FlattenedSymbol {
+  return {
+    name,
+    kind: 12,
+    range: {
+      start: { line, character: 0 },
+      end: { line, character: 40 },
+    },
+    selectionRange: {
+      start: { line, character },
+      end: { line, character: character + name.length },
+    },
+    ...(containerPath && { containerPath }),
+  };
+}
+
+describe('Architecture map', () => {
+  test('should group files into components by leading directories', () => {
+    expect(getComponentName('src/tools/outline.ts', 1)).toBe('src');
+    expect(getComponentName('src/tools/outline.ts', 2)).toBe('src/tools');
+    expect(getComponentName('src/index.ts', 3)).toBe('src');
+    expect(getComponentName('main.go', 1)).toBe(ROOT_COMPONENT);
+  });
+
+  test('should keep exported top-level TypeScript symbols', () => {
+    const lines = [
+      'export function parse() {}',
+      'function helper() {}',
+      'export class Parser {',
+      '  run() {}',
+      '}',
+    ];
+    const symbols = [
+      symbol('parse', 0, 16),
+      symbol('helper', 1, 9),
+      symbol('Parser', 2, 13),
+      symbol('run', 3, 2, ['Parser']),
+    ];
+
+    const selected = selectPublicSymbols(symbols, lines, 'src/parser.ts');
+
+    expect(selected.map((s) => s.name)).toEqual(['parse', 'Parser']);
+  });
+
+  test('should use Go naming rules for exports', () => {
+    const lines = ['func Serve() {}', 'func handle() {}'];
+    const symbols = [symbol('Serve', 0, 5), symbol('handle', 1, 5)];
+
+    const selected = selectPublicSymbols(symbols, lines, 'server/serve.go');
+
+    expect(selected.map((s) => s.name)).toEqual(['Serve']);
+  });
+
+  test('should treat all top-level symbols as public without visibility info', () => {
+    const lines = ['int add(int a, int b);', 'int sub(int a, int b);'];
+    const symbols = [symbol('add', 0, 4), symbol('sub', 1, 4)];
+
+    const selected = selectPublicSymbols(symbols, lines, 'src/math.c');
+
+    expect(selected.map((s) => s.name)).toEqual(['add', 'sub']);
+  });
+
+  const map: ArchitectureMap = {
+    workspace: '/project',
+    components: [
+      {
+        name: 'src',
+        files: 3,
+        testFiles: 1,
+        publicSymbols: [
+          { name: 'parse', kind: 'Function', file: 'src/parse.ts', line: 1 },
+        ],
+      },
+      { name: 'scripts', files: 1, testFiles: 0, publicSymbols: [] },
+    ],
+    edges: [{ from: 'scripts', to: 'src', references: 2 }],
+    truncated: false,
+  };
+
+  test('should render components, dependencies and public symbols as markdown', () => {
+    const text = formatArchitectureMap(map, 'markdown');
+
+    expect(text).toContain('# Architecture map: /project');
+    expect(text).toContain('2 component(s), 4 file(s), 1 dependency edge(s)');
+    expect(text).toContain(
+      '- src - 3 file(s), 1 test file(s), 1 public symbol(s)'
+    );
+    expect(text).toContain('- scripts -> src (2 reference(s))');
+    expect(text).toContain('- parse (Function) - src/parse.ts:1');
+    expect(text).not.toContain('## scripts');
+  });
+
+  test('should render the map as JSON', () => {
+    const parsed = JSON.parse(
+      formatArchitectureMap(map, 'json')
+    ) as ArchitectureMap;
+
+    expect(parsed.edges).toEqual(map.edges);
+    expect(parsed.components[0]?.publicSymbols[0]?.name).toBe('parse');
+  });
+});
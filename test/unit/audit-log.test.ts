@@ -0,0 +1,125 @@
+/**
+ * Audit Log Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import {
+  configureAuditLog,
+  hashParams,
+  recordAuditEdits,
+  withAuditEntry,
+  writeAuditEntry,
+  type AuditEntry,
+} from '../../src/tools/audit-log.js';
+import { configurePathOutput } from '../../src/utils/path-format.js';
+
+function readEntries(file: string): AuditEntry[] {
+  return fs
+    .readFileSync(file, 'utf-8')
+    .split('\n')
+    .filter((line) => line.trim())
+    .map((line) => JSON.parse(line) as AuditEntry);
+}
+
+describe('Audit log', () => {
+  let workspace: string;
+  let logFile: string;
+
+  beforeEach(() => {
+    workspace = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-audit-'));
+    logFile = path.join(workspace, 'audit', 'tools.jsonl');
+    configurePathOutput({ baseDirectory: workspace });
+    configureAuditLog({
+      workspace,
+      audit: { path: 'audit/tools.jsonl', max_bytes: 4096, max_files: 2 },
+    });
+  });
+
+  afterEach(() => {
+    configureAuditLog({ workspace: process.cwd(), audit: null });
+    configurePathOutput({ baseDirectory: process.cwd() });
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('should hash arguments regardless of key order', () => {
+    expect(hashParams({ file: 'a.ts', line: 3 })).toBe(
+      hashParams({ line: 3, file: 'a.ts' })
+    );
+    expect(hashParams({ file: 'a.ts' })).not.toBe(hashParams({ file: 'b.ts' }));
+  });
+
+  test('should record the files and edits of a tool call', async () => {
+    const result = await withAuditEntry(
+      {
+        client: 'agent-1',
+        tool: 'rename',
+        args: { file: 'src/a.ts', line: 1, character: 5, newName: 'b' },
+      },
+      async () => {
+        recordAuditEdits(path.join(workspace, 'src/a.ts'), 2);
+        recordAuditEdits(`file://${workspace}/src/b.ts`, 1);
+        return { content: [] };
+      }
+    );
+
+    expect(result).toEqual({ content: [] });
+    const [entry] = readEntries(logFile);
+    expect(entry).toMatchObject({
+      client: 'agent-1',
+      tool: 'rename',
+      params_hash: hashParams({
+        file: 'src/a.ts',
+        line: 1,
+        character: 5,
+        newName: 'b',
+      }),
+      files: ['src/a.ts', 'src/b.ts'],
+      edits: { 'src/a.ts': 2, 'src/b.ts': 1 },
+      outcome: 'ok',
+    });
+  });
+
+  test('should record failed and erroring calls', async () => {
+    await withAuditEntry(
+      { client: 'local', tool: 'outline', args: { file: 'missing.ts' } },
+      async () => ({ content: [], isError: true })
+    );
+    await expect(
+      withAuditEntry(
+        { client: 'local', tool: 'search', args: { query: 'x' } },
+        async () => {
+          throw new Error('boom');
+        }
+      )
+    ).rejects.toThrow('boom');
+
+    expect(readEntries(logFile).map((entry) => entry.outcome)).toEqual([
+      'error',
+      'error',
+    ]);
+  });
+
+  test('should rotate once the file is full', () => {
+    const entry: AuditEntry = {
+      timestamp: '2026-10-01T00:00:00.000Z',
+      client: 'local',
+      tool: 'search',
+      params_hash: 'x'.repeat(1500),
+      files: [],
+      edits: {},
+      outcome: 'ok',
+      duration_ms: 1,
+    };
+    for (let index = 0; index < 7; index++) {
+      writeAuditEntry(entry);
+    }
+
+    expect(readEntries(logFile)).toHaveLength(1);
+    expect(readEntries(`${logFile}.1`)).toHaveLength(2);
+    expect(readEntries(`${logFile}.2`)).toHaveLength(2);
+    expect(fs.existsSync(`${logFile}.3`)).toBe(false);
+  });
+});
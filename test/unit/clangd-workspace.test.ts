@@ -0,0 +1,70 @@
+/**
+ * C/C++ Workspace Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import { findCompileCommandsDir } from '../../src/utils/clangd-workspace.js';
+import { createClangdLoader } from '../../src/workspace/loaders/clangd.js';
+
+describe('compile_commands.json discovery', () => {
+  let workspace: string;
+
+  function write(file: string, modifiedAt = new Date()): void {
+    const filePath = path.join(workspace, file);
+    fs.mkdirSync(path.dirname(filePath), { recursive: true });
+    fs.writeFileSync(filePath, '[]\n');
+    fs.utimesSync(filePath, modifiedAt, modifiedAt);
+  }
+
+  beforeEach(() => {
+    workspace = fs.realpathSync(
+      fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-clangd-'))
+    );
+  });
+
+  afterEach(() => {
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('prefers the most recently generated database', () => {
+    write('build/Debug/compile_commands.json', new Date('2024-01-01'));
+    write('cmake-build-release/compile_commands.json', new Date('2024-02-01'));
+
+    expect(findCompileCommandsDir(workspace)).toBe(
+      path.join(workspace, 'cmake-build-release')
+    );
+  });
+
+  test('ignores databases outside known build directories', () => {
+    write('third_party/lib/compile_commands.json');
+
+    expect(findCompileCommandsDir(workspace)).toBeNull();
+  });
+
+  test('adds the directory to the clangd command once', () => {
+    write('out/compile_commands.json');
+    const loader = createClangdLoader();
+
+    expect(
+      loader.prepareCommand!(workspace, {
+        commandName: 'clangd',
+        commandArgs: ['--background-index'],
+      })
+    ).toEqual({
+      commandName: 'clangd',
+      commandArgs: [
+        '--background-index',
+        `--compile-commands-dir=${path.join(workspace, 'out')}`,
+      ],
+    });
+
+    const configured = {
+      commandName: 'clangd',
+      commandArgs: ['--compile-commands-dir=custom'],
+    };
+    expect(loader.prepareCommand!(workspace, configured)).toBe(configured);
+  });
+});
@@ -1,10 +1,24 @@
 /* eslint-disable @typescript-eslint/no-unsafe-member-access, @typescript-eslint/no-unsafe-assignment */
 import { describe, it, expect, vi, beforeEach } from 'vitest';
-import { parseCliArgs } from '../../src/utils/cli.js';
+import {
+  parseCliArgs,
+  resolveTransportConfig,
+} from '../../src/utils/cli.js';
 import type {
+  ApiCommandArgs,
+  BatchCommandArgs,
+  GatewayCommandArgs,
   RunCommandArgs,
   StartCommandArgs,
+  UsageCommandArgs,
   ConfigCommandArgs,
+  CyclesCommandArgs,
+  DocCoverageCommandArgs,
+  QueryCommandArgs,
+  LookupCommandArgs,
+  ExportCommandArgs,
+  DeadCodeCommandArgs,
+  MapCommandArgs,
 } from '../../src/utils/cli.js';
 
 // Mock dependencies
@@ -287,6 +301,66 @@ describe('CLI Argument Parsing', () => {
       });
     });
 
+    describe('transport flags', () => {
+      it('should not treat transport flags as the command', () => {
+        const result = parseCliArgs([
+          'node',
+          'symbols',
+          'run',
+          '--transport',
+          'http',
+          '--port',
+          '4000',
+          'gopls',
+        ]);
+
+        if (result.command === 'run') {
+          expect(result.transport).toBe('http');
+          expect(result.port).toBe(4000);
+          expect(result.directCommand.commandName).toBe('gopls');
+          expect(result.directCommand.commandArgs).toEqual([]);
+        }
+      });
+
+      it('should listen on a socket over http by default', () => {
+        const result = parseCliArgs([
+          'node',
+          'symbols',
+          'run',
+          '--socket',
+          '/tmp/symbols.sock',
+          'gopls',
+        ]) as RunCommandArgs;
+
+        expect(result.directCommand.commandName).toBe('gopls');
+        expect(resolveTransportConfig(result)).toMatchObject({
+          transport: 'http',
+          socket: '/tmp/symbols.sock',
+        });
+        expect(() =>
+          resolveTransportConfig({ ...result, transport: 'stdio' })
+        ).toThrow('needs the http or sse transport');
+      });
+    });
+
+    describe('paths flag', () => {
+      it('should parse --paths flag before the command', () => {
+        const result = parseCliArgs([
+          'node',
+          'symbols',
+          'run',
+          '--paths',
+          'absolute',
+          'gopls',
+        ]);
+
+        if (result.command === 'run') {
+          expect(result.paths).toBe('absolute');
+          expect(result.directCommand.commandName).toBe('gopls');
+        }
+      });
+    });
+
     describe('console flag', () => {
       it('should parse --console flag', () => {
         const result = parseCliArgs([
@@ -496,6 +570,27 @@ describe('CLI Argument Parsing', () => {
   });
 
   describe('start command', () => {
+    it('should parse the HTTP transport flags', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'start',
+        '--transport',
+        'http',
+        '--host',
+        '0.0.0.0',
+        '--port',
+        '4000',
+      ]);
+
+      expect(result).toMatchObject({
+        command: 'start',
+        transport: 'http',
+        host: '0.0.0.0',
+        port: 4000,
+      });
+    });
+
     it('should parse basic start command', () => {
       const result = parseCliArgs(['node', 'symbols', 'start']);
 
@@ -559,6 +654,20 @@ describe('CLI Argument Parsing', () => {
       }
     });
 
+    it('should parse start with --paths flag', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'start',
+        '--paths',
+        'uri',
+      ]);
+
+      if (result.command === 'start') {
+        expect(result.paths).toBe('uri');
+      }
+    });
+
     it('should parse start with --console flag', () => {
       const result = parseCliArgs(['node', 'symbols', 'start', '--console']);
 
@@ -609,6 +718,427 @@ describe('CLI Argument Parsing', () => {
     });
   });
 
+  describe('map command', () => {
+    it('should default to a markdown map of one directory level', () => {
+      const result = parseCliArgs(['node', 'symbols', 'map']) as MapCommandArgs;
+
+      expect(result.command).toBe('map');
+      expect(result.format).toBe('markdown');
+      expect(result.depth).toBe(1);
+      expect(result.maxFiles).toBe(500);
+      expect(result.color).toBe('auto');
+      expect(result.console).toBe(false);
+    });
+
+    it('should parse map options', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'map',
+        '--workspace',
+        '/project',
+        '--lsp',
+        'typescript',
+        '--format',
+        'json',
+        '--depth',
+        '2',
+        '--max-files',
+        '50',
+        '--paths',
+        'uri',
+        '--color',
+        'never',
+      ]) as MapCommandArgs;
+
+      expect(result.workspace).toBe('/project');
+      expect(result.lsp).toBe('typescript');
+      expect(result.format).toBe('json');
+      expect(result.depth).toBe(2);
+      expect(result.maxFiles).toBe(50);
+      expect(result.paths).toBe('uri');
+      expect(result.color).toBe('never');
+    });
+
+    it('should reject a non-positive depth', () => {
+      expect(() =>
+        parseCliArgs(['node', 'symbols', 'map', '--depth', '0'])
+      ).toThrow('process.exit(1)');
+    });
+  });
+
+  describe('dead-code command', () => {
+    it('should default to every entry point kind', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'dead-code',
+      ]) as DeadCodeCommandArgs;
+
+      expect(result.command).toBe('dead-code');
+      expect(result.entryPoints).toEqual(['main', 'exported', 'tests']);
+      expect(result.entrySymbols).toEqual([]);
+      expect(result.format).toBe('markdown');
+    });
+
+    it('should parse entry points and extra entry symbols', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'dead-code',
+        '--entry',
+        'main',
+        'tests',
+        '--entry-symbol',
+        'handler',
+        '--format',
+        'json',
+      ]) as DeadCodeCommandArgs;
+
+      expect(result.entryPoints).toEqual(['main', 'tests']);
+      expect(result.entrySymbols).toEqual(['handler']);
+      expect(result.format).toBe('json');
+    });
+
+    it('should reject unknown entry point kinds', () => {
+      expect(() =>
+        parseCliArgs(['node', 'symbols', 'dead-code', '--entry', 'everything'])
+      ).toThrow('process.exit(1)');
+    });
+  });
+
+  describe('usage command', () => {
+    it('should default to a 90 day churn window', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'usage',
+      ]) as UsageCommandArgs;
+
+      expect(result.command).toBe('usage');
+      expect(result.since).toBe('90 days ago');
+      expect(result.top).toBe(20);
+      expect(result.maxSymbols).toBe(2000);
+    });
+
+    it('should parse CSV export options', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'usage',
+        '--format',
+        'csv',
+        '--since',
+        '30 days ago',
+        '--depth',
+        '2',
+      ]) as UsageCommandArgs;
+
+      expect(result.format).toBe('csv');
+      expect(result.since).toBe('30 days ago');
+      expect(result.depth).toBe(2);
+    });
+
+    it('should reject a non-positive top', () => {
+      expect(() =>
+        parseCliArgs(['node', 'symbols', 'usage', '--top', '0'])
+      ).toThrow('process.exit(1)');
+    });
+  });
+
+  describe('cycles command', () => {
+    it('should default to a file-level graph', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'cycles',
+      ]) as CyclesCommandArgs;
+
+      expect(result.command).toBe('cycles');
+      expect(result.format).toBe('markdown');
+      expect(result.depth).toBeUndefined();
+      expect(result.maxFiles).toBe(2000);
+    });
+
+    it('should parse module grouping', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'cycles',
+        '--depth',
+        '2',
+        '--format',
+        'json',
+      ]) as CyclesCommandArgs;
+
+      expect(result.depth).toBe(2);
+      expect(result.format).toBe('json');
+    });
+
+    it('should reject a non-positive depth', () => {
+      expect(() =>
+        parseCliArgs(['node', 'symbols', 'cycles', '--depth', '0'])
+      ).toThrow('process.exit(1)');
+    });
+  });
+
+  describe('doc-coverage command', () => {
+    it('should default to no threshold', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'doc-coverage',
+      ]) as DocCoverageCommandArgs;
+
+      expect(result.command).toBe('doc-coverage');
+      expect(result.format).toBe('markdown');
+      expect(result.depth).toBe(1);
+      expect(result.failUnder).toBeUndefined();
+      expect(result.maxFiles).toBe(500);
+    });
+
+    it('should parse the fail-under threshold', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'doc-coverage',
+        '--fail-under',
+        '75.5',
+        '--depth',
+        '2',
+      ]) as DocCoverageCommandArgs;
+
+      expect(result.failUnder).toBe(75.5);
+      expect(result.depth).toBe(2);
+    });
+
+    it('should reject a threshold above 100', () => {
+      expect(() =>
+        parseCliArgs(['node', 'symbols', 'doc-coverage', '--fail-under', '120'])
+      ).toThrow('process.exit(1)');
+    });
+  });
+
+  describe('query command', () => {
+    it('should parse the query and defaults', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'query',
+        'kind:function vis:pub refs:>10',
+      ]) as QueryCommandArgs;
+
+      expect(result.command).toBe('query');
+      expect(result.query).toBe('kind:function vis:pub refs:>10');
+      expect(result.format).toBe('markdown');
+      expect(result.limit).toBe(100);
+    });
+
+    it('should reject an unknown symbol kind', () => {
+      expect(() =>
+        parseCliArgs(['node', 'symbols', 'query', 'kind:widget'])
+      ).toThrow('process.exit(1)');
+    });
+  });
+
+  describe('lookup commands', () => {
+    it('should parse find with defaults', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'find',
+        'createServer',
+      ]) as LookupCommandArgs;
+
+      expect(result.command).toBe('find');
+      expect(result.target).toBe('createServer');
+      expect(result.format).toBe('markdown');
+      expect(result.color).toBe('auto');
+      expect(result.limit).toBe(100);
+    });
+
+    it('should parse refs, def and outline targets', () => {
+      const refs = parseCliArgs([
+        'node',
+        'symbols',
+        'refs',
+        'src/server.ts:12:17',
+        '--format',
+        'json',
+        '--paths',
+        'absolute',
+        '--color',
+        'always',
+      ]) as LookupCommandArgs;
+      const def = parseCliArgs([
+        'node',
+        'symbols',
+        'def',
+        'src/main.ts:40:9',
+      ]) as LookupCommandArgs;
+      const outline = parseCliArgs([
+        'node',
+        'symbols',
+        'outline',
+        'src/index.ts',
+      ]) as LookupCommandArgs;
+
+      expect(refs).toMatchObject({
+        command: 'refs',
+        target: 'src/server.ts:12:17',
+        format: 'json',
+        paths: 'absolute',
+        color: 'always',
+      });
+      expect(def).toMatchObject({ command: 'def', target: 'src/main.ts:40:9' });
+      expect(outline).toMatchObject({
+        command: 'outline',
+        target: 'src/index.ts',
+      });
+    });
+
+    it('should reject a position without a character', () => {
+      expect(() =>
+        parseCliArgs(['node', 'symbols', 'refs', 'src/server.ts:12'])
+      ).toThrow('process.exit(1)');
+    });
+  });
+
+  describe('batch command', () => {
+    it('should parse the query limit', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'batch',
+        '--limit',
+        '20',
+      ]) as BatchCommandArgs;
+
+      expect(result.command).toBe('batch');
+      expect(result.limit).toBe(20);
+    });
+  });
+
+  describe('gateway command', () => {
+    it('should parse the language server selection', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'gateway',
+        '--lsp',
+        'typescript',
+        '--loglevel',
+        'debug',
+      ]) as GatewayCommandArgs;
+
+      expect(result.command).toBe('gateway');
+      expect(result.lsp).toBe('typescript');
+      expect(result.loglevel).toBe('debug');
+    });
+  });
+
+  describe('api command', () => {
+    it('should parse api snapshot options', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'api',
+        'snapshot',
+        '--release',
+        '1.4.0',
+        '-o',
+        'api.json',
+      ]) as ApiCommandArgs;
+
+      expect(result.command).toBe('api');
+      expect(result.maxFiles).toBe(500);
+      expect(result.subcommandArgs).toEqual({
+        subcommand: 'snapshot',
+        output: 'api.json',
+        release: '1.4.0',
+      });
+    });
+
+    it('should parse api check options', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'api',
+        'check',
+        '--baseline',
+        'v1.4.0',
+        '--release',
+        '1.4.1',
+        '--format',
+        'json',
+      ]) as ApiCommandArgs;
+
+      expect(result.subcommandArgs).toEqual({
+        subcommand: 'check',
+        baseline: 'v1.4.0',
+        baselineVersion: undefined,
+        release: '1.4.1',
+        format: 'json',
+      });
+    });
+
+    it('should require a baseline for api check', () => {
+      expect(() => parseCliArgs(['node', 'symbols', 'api', 'check'])).toThrow(
+        'process.exit(1)'
+      );
+    });
+  });
+
+  describe('export command', () => {
+    it('should parse export chunks defaults', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'export',
+        'chunks',
+      ]) as ExportCommandArgs;
+
+      expect(result.command).toBe('export');
+      expect(result.maxFiles).toBe(500);
+      expect(result.subcommandArgs).toEqual({
+        subcommand: 'chunks',
+        output: undefined,
+        maxChars: 4000,
+        includeTests: false,
+        docs: true,
+      });
+    });
+
+    it('should parse export chunks options', () => {
+      const result = parseCliArgs([
+        'node',
+        'symbols',
+        'export',
+        'chunks',
+        '-o',
+        'chunks.jsonl',
+        '--max-chars',
+        '2000',
+        '--include-tests',
+        '--no-docs',
+      ]) as ExportCommandArgs;
+
+      expect(result.subcommandArgs).toEqual({
+        subcommand: 'chunks',
+        output: 'chunks.jsonl',
+        maxChars: 2000,
+        includeTests: true,
+        docs: false,
+      });
+    });
+
+    it('should require an export subcommand', () => {
+      expect(() => parseCliArgs(['node', 'symbols', 'export'])).toThrow(
+        'process.exit(1)'
+      );
+    });
+  });
+
   describe('config command', () => {
     describe('config init', () => {
       it('should parse config init', () => {
@@ -768,6 +1298,24 @@ describe('CLI Argument Parsing', () => {
         }
       });
 
+      it('should parse config show --color', () => {
+        const result = parseCliArgs([
+          'node',
+          'symbols',
+          'config',
+          'show',
+          '--color',
+          'never',
+        ]);
+
+        if (result.command === 'config') {
+          const subArgs = result.subcommandArgs;
+          if (subArgs.subcommand === 'show') {
+            expect(subArgs.color).toBe('never');
+          }
+        }
+      });
+
       it('should parse config show with --config', () => {
         const result = parseCliArgs([
           'node',
@@ -0,0 +1,113 @@
+/**
+ * Code Owners Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, describe, test, expect } from 'vitest';
+import {
+  configureCodeOwners,
+  findCodeOwners,
+  formatOwnerTag,
+  getFileOwners,
+  matchesOwnerFilter,
+  parseCodeOwners,
+  parseOwnerFilter,
+} from '../../src/utils/code-owners.js';
+
+const CODEOWNERS = `
+# Default owners
+*                   @org/platform
+*.md                @docs-team
+/src/payments/      @alice @bob  # payments
+docs/*              @writers
+**/generated        @bots
+/scripts/deploy.sh
+`;
+
+describe('Code owners', () => {
+  const rules = parseCodeOwners(CODEOWNERS);
+
+  test('should let the last matching rule win', () => {
+    expect(findCodeOwners(rules, 'src/index.ts')).toEqual(['@org/platform']);
+    expect(findCodeOwners(rules, 'src/payments/charge.ts')).toEqual([
+      '@alice',
+      '@bob',
+    ]);
+    expect(findCodeOwners(rules, 'src/payments/README.md')).toEqual([
+      '@alice',
+      '@bob',
+    ]);
+    expect(findCodeOwners(rules, 'guides/setup.md')).toEqual(['@docs-team']);
+  });
+
+  test('should match unanchored patterns at any depth', () => {
+    expect(findCodeOwners(rules, 'api/generated/client.ts')).toEqual([
+      '@bots',
+    ]);
+    expect(findCodeOwners(rules, 'lib/src/payments/charge.ts')).toEqual([
+      '@org/platform',
+    ]);
+  });
+
+  test('should only match one level for dir/* patterns', () => {
+    expect(findCodeOwners(rules, 'docs/intro.txt')).toEqual(['@writers']);
+    expect(findCodeOwners(rules, 'docs/api/intro.txt')).toEqual([
+      '@org/platform',
+    ]);
+  });
+
+  test('should leave files of rules without owners unowned', () => {
+    expect(findCodeOwners(rules, 'scripts/deploy.sh')).toEqual([]);
+  });
+
+  test('should split owner filters out of a query', () => {
+    expect(parseOwnerFilter('Payment owner:@payments-team')).toEqual({
+      query: 'Payment',
+      owners: ['@payments-team'],
+    });
+    expect(parseOwnerFilter('Payment')).toEqual({
+      query: 'Payment',
+      owners: [],
+    });
+  });
+
+  describe('workspace lookup', () => {
+    let workspace: string;
+
+    afterEach(() => {
+      fs.rmSync(workspace, { recursive: true, force: true });
+    });
+
+    test('should discover .github/CODEOWNERS and map owners to teams', () => {
+      workspace = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-owners-'));
+      fs.mkdirSync(path.join(workspace, '.github'));
+      fs.writeFileSync(
+        path.join(workspace, '.github', 'CODEOWNERS'),
+        CODEOWNERS
+      );
+
+      configureCodeOwners({
+        workspace,
+        teams: { '@alice': '@payments-team', '@bob': '@payments-team' },
+      });
+      const file = path.join(workspace, 'src', 'payments', 'charge.ts');
+
+      expect(getFileOwners(file)).toEqual(['@payments-team']);
+      expect(formatOwnerTag(file)).toBe(' [owner: @payments-team]');
+      expect(matchesOwnerFilter(file, ['@Payments-Team'])).toBe(true);
+      expect(matchesOwnerFilter(file, ['@docs-team'])).toBe(false);
+      expect(matchesOwnerFilter(file, [])).toBe(true);
+    });
+
+    test('should not tag files when there is no CODEOWNERS', () => {
+      workspace = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-owners-'));
+      fs.mkdirSync(path.join(workspace, '.git'));
+
+      configureCodeOwners({ workspace });
+
+      expect(formatOwnerTag(path.join(workspace, 'src', 'a.ts'))).toBe('');
+    });
+  });
+});
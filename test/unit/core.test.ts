@@ -0,0 +1,124 @@
+/**
+ * Core Library API Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, describe, expect, test } from 'vitest';
+import { openWorkspace, type Workspace } from '../../src/core.js';
+
+const MOCK_SERVER = path.resolve(
+  'test/integration/mock-lsp/mock-language-server.ts'
+);
+
+const RANGE = {
+  start: { line: 0, character: 6 },
+  end: { line: 0, character: 11 },
+};
+
+const KEYWORD_RANGE = {
+  start: { line: 0, character: 0 },
+  end: { line: 0, character: 5 },
+};
+
+function createWorkspace(symbolName: string): string {
+  const root = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-core-'));
+  fs.writeFileSync(
+    path.join(root, 'language-servers.yaml'),
+    [
+      'language-servers:',
+      '  mock:',
+      `    command: node --import tsx ${MOCK_SERVER}`,
+      '    extensions:',
+      "      '.mock': 'mock'",
+      '',
+    ].join('\n')
+  );
+  fs.writeFileSync(
+    path.join(root, 'mock-lsp.json'),
+    JSON.stringify({
+      responses: {
+        'textDocument/documentSymbol': {
+          result: [
+            { name: symbolName, kind: 5, range: RANGE, selectionRange: RANGE },
+          ],
+        },
+        // The file asked about, and the same file by the server's root
+        'textDocument/references': {
+          result: [
+            { uri: '$DOCUMENT_URI', range: RANGE },
+            { uri: '$WORKSPACE_URI/main.mock', range: KEYWORD_RANGE },
+          ],
+        },
+      },
+    })
+  );
+  fs.writeFileSync(path.join(root, 'main.mock'), `class ${symbolName} {}\n`);
+  return root;
+}
+
+describe('Core library API', () => {
+  const opened: Workspace[] = [];
+  const roots: string[] = [];
+
+  afterEach(async () => {
+    await Promise.all(opened.map((workspace) => workspace.close()));
+    opened.length = 0;
+    for (const root of roots.splice(0)) {
+      fs.rmSync(root, { recursive: true, force: true });
+    }
+  });
+
+  const open = async (symbolName: string) => {
+    const root = createWorkspace(symbolName);
+    roots.push(root);
+    const workspace = await openWorkspace({
+      path: root,
+      configPath: path.join(root, 'language-servers.yaml'),
+    });
+    opened.push(workspace);
+    return workspace;
+  };
+
+  test('should keep workspaces opened at once apart', async () => {
+    const first = await open('Alpha');
+    const second = await open('Beta');
+
+    expect(first.status().workspacePath).toBe(first.path);
+    expect(second.status().workspacePath).toBe(second.path);
+
+    const [firstOutline, secondOutline] = await Promise.all([
+      first.outline('main.mock'),
+      second.outline('main.mock'),
+    ]);
+    expect(firstOutline.map((symbol) => symbol.name)).toEqual(['Alpha']);
+    expect(secondOutline.map((symbol) => symbol.name)).toEqual(['Beta']);
+  });
+
+  test('should resolve and report paths against their own workspace', async () => {
+    const first = await open('Alpha');
+    const second = await open('Beta');
+    const position = { file: 'main.mock', line: 1, character: 7 };
+
+    const [firstRefs, secondRefs] = await Promise.all([
+      first.references(position),
+      second.references(position),
+    ]);
+    expect(firstRefs.locations.map((location) => location.file)).toEqual([
+      'main.mock',
+      'main.mock',
+    ]);
+    expect(secondRefs.locations.map((location) => location.file)).toEqual([
+      'main.mock',
+      'main.mock',
+    ]);
+
+    const client = await first.client('main.mock');
+    const symbols = await client.request<{ name: string }[]>(
+      'textDocument/documentSymbol',
+      { textDocument: { uri: `file://${path.join(first.path, 'main.mock')}` } }
+    );
+    expect(symbols.map((symbol) => symbol.name)).toEqual(['Alpha']);
+  });
+});
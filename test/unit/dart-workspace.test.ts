@@ -0,0 +1,112 @@
+/**
+ * Dart Workspace Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import {
+  findFlutterSdk,
+  findPubspec,
+  isFlutterPackage,
+} from '../../src/utils/dart-workspace.js';
+import { createDartLoader } from '../../src/workspace/loaders/dart.js';
+
+const FLUTTER_PUBSPEC = `name: app
+environment:
+  sdk: ^3.4.0
+dependencies:
+  flutter:
+    sdk: flutter
+`;
+
+describe('Dart workspace detection', () => {
+  let workspace: string;
+
+  function write(file: string, content = ''): void {
+    const filePath = path.join(workspace, file);
+    fs.mkdirSync(path.dirname(filePath), { recursive: true });
+    fs.writeFileSync(filePath, content);
+  }
+
+  beforeEach(() => {
+    workspace = fs.realpathSync(
+      fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-dart-'))
+    );
+  });
+
+  afterEach(() => {
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('uses the only nested pubspec outside generated projects', () => {
+    write('app/pubspec.yaml', FLUTTER_PUBSPEC);
+    write('app/build/web/pubspec.yaml', 'name: copy\n');
+
+    const pubspec = findPubspec(workspace);
+    expect(pubspec).toBe(path.join(workspace, 'app', 'pubspec.yaml'));
+    expect(isFlutterPackage(pubspec!)).toBe(true);
+
+    write('server/pubspec.yaml', 'name: server\n');
+    const serverPubspec = path.join(workspace, 'server', 'pubspec.yaml');
+    expect(findPubspec(workspace)).toBeNull();
+    expect(isFlutterPackage(serverPubspec)).toBe(false);
+  });
+
+  test('prefers the Flutter SDK pinned by FVM', () => {
+    write('sdks/pinned/bin/cache/dart-sdk/bin/dart');
+    write('sdks/global/bin/cache/dart-sdk/bin/dart');
+    fs.mkdirSync(path.join(workspace, '.fvm'));
+    fs.symlinkSync(
+      path.join(workspace, 'sdks', 'pinned'),
+      path.join(workspace, '.fvm', 'flutter_sdk')
+    );
+    const env = { FLUTTER_ROOT: path.join(workspace, 'sdks', 'global') };
+
+    expect(findFlutterSdk(workspace, workspace, env)).toBe(
+      path.join(workspace, 'sdks', 'pinned')
+    );
+
+    fs.rmSync(path.join(workspace, '.fvm'), { recursive: true });
+    expect(findFlutterSdk(workspace, workspace, env)).toBe(
+      path.join(workspace, 'sdks', 'global')
+    );
+  });
+
+  test('runs Flutter apps with the dart of their Flutter SDK', () => {
+    write('pubspec.yaml', FLUTTER_PUBSPEC);
+    write('.fvm/flutter_sdk/bin/cache/dart-sdk/bin/dart');
+    const flutterRoot = path.join(workspace, '.fvm', 'flutter_sdk');
+
+    expect(
+      createDartLoader().prepareCommand!(workspace, {
+        commandName: 'dart',
+        commandArgs: ['language-server', '--protocol=lsp'],
+      })
+    ).toEqual({
+      commandName: path.join(flutterRoot, 'bin/cache/dart-sdk/bin/dart'),
+      commandArgs: ['language-server', '--protocol=lsp'],
+      environment: { FLUTTER_ROOT: flutterRoot },
+    });
+  });
+
+  test('becomes ready when analysis finishes', () => {
+    const loader = createDartLoader();
+    const analyzing = {
+      type: 'dart' as const,
+      ready: false,
+      data: { pubspecPath: 'pubspec.yaml', analysisStartedAt: Date.now() },
+    };
+
+    const started = loader.handleNotification!(analyzing, '$/analyzerStatus', {
+      isAnalyzing: true,
+    });
+    expect(loader.isReady(started)).toBe(false);
+
+    const finished = loader.handleNotification!(started, '$/analyzerStatus', {
+      isAnalyzing: false,
+    });
+    expect(loader.isReady(finished)).toBe(true);
+  });
+});
@@ -0,0 +1,141 @@
+/**
+ * Dead Code Analysis Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  findUnreachableSymbols,
+  formatDeadCodeReport,
+  type DeadCodeReport,
+  type SymbolNode,
+} from '../../src/map/dead-code.js';
+
+function node(id: string, overrides: Partial<SymbolNode> = {}): SymbolNode {
+  return {
+    id,
+    name: id,
+    kind: 'Function',
+    file: 'src/app.ts',
+    line: 1,
+    referenced: false,
+    ...overrides,
+  };
+}
+
+describe('Dead code analysis', () => {
+  test('should keep symbols reachable from entry points and module code', () => {
+    const nodes = [
+      node('main', { entry: 'main' }),
+      node('run', { referenced: true }),
+      node('setup', { referenced: true }),
+      node('orphan'),
+    ];
+    const edges = [
+      { from: 'main', to: 'run' },
+      { from: null, to: 'setup' },
+    ];
+
+    const dead = findUnreachableSymbols(nodes, edges);
+
+    expect(dead.map((symbol) => symbol.name)).toEqual(['orphan']);
+    expect(dead[0]?.confidence).toBe('high');
+    expect(dead[0]?.reason).toBe('no references');
+  });
+
+  test('should report code referenced only from dead code with medium confidence', () => {
+    const nodes = [
+      node('main', { entry: 'main' }),
+      node('legacy'),
+      node('legacyHelper', { referenced: true }),
+    ];
+    const edges = [{ from: 'legacy', to: 'legacyHelper' }];
+
+    const dead = findUnreachableSymbols(nodes, edges);
+
+    expect(dead).toEqual([
+      expect.objectContaining({ name: 'legacy', confidence: 'high' }),
+      expect.objectContaining({ name: 'legacyHelper', confidence: 'medium' }),
+    ]);
+  });
+
+  test('should follow cycles without marking them reachable', () => {
+    const nodes = [
+      node('ping', { referenced: true }),
+      node('pong', { referenced: true }),
+    ];
+    const edges = [
+      { from: 'ping', to: 'pong' },
+      { from: 'pong', to: 'ping' },
+    ];
+
+    const dead = findUnreachableSymbols(nodes, edges);
+
+    expect(dead.map((symbol) => symbol.confidence)).toEqual([
+      'medium',
+      'medium',
+    ]);
+  });
+
+  test('should downgrade possibly dynamic symbols to low confidence', () => {
+    const nodes = [
+      node('Service', { kind: 'Class', entry: 'exported API' }),
+      node('handle', { kind: 'Method', containerId: 'Service' }),
+      node('__repr__', { kind: 'Method', dynamicHint: 'special method' }),
+    ];
+
+    const dead = findUnreachableSymbols(nodes, []);
+
+    expect(dead).toEqual([
+      expect.objectContaining({ name: 'handle', confidence: 'low' }),
+      expect.objectContaining({
+        name: '__repr__',
+        confidence: 'low',
+        reason: 'special method',
+      }),
+    ]);
+  });
+
+  const report: DeadCodeReport = {
+    workspace: '/project',
+    entryPointKinds: ['main', 'exported', 'tests'],
+    files: 4,
+    analyzedSymbols: 12,
+    entryPoints: 3,
+    failedLookups: 1,
+    unreachable: [
+      {
+        name: 'orphan',
+        kind: 'Function',
+        file: 'src/app.ts',
+        line: 7,
+        confidence: 'high',
+        reason: 'no references',
+      },
+    ],
+    truncated: false,
+  };
+
+  test('should render the report grouped by confidence', () => {
+    const text = formatDeadCodeReport(report, 'markdown');
+
+    expect(text).toContain(
+      'Analyzed 12 symbol(s) in 4 file(s) from 3 entry point(s) (main, exported, tests); 1 unreachable'
+    );
+    expect(text).toContain(
+      '1 symbol(s) skipped because reference lookup failed'
+    );
+    expect(text).toContain('## High confidence (1)');
+    expect(text).toContain(
+      '- orphan (Function) - src/app.ts:7 - no references'
+    );
+    expect(text).not.toContain('## Low confidence');
+  });
+
+  test('should render the report as JSON', () => {
+    const parsed = JSON.parse(
+      formatDeadCodeReport(report, 'json')
+    ) as DeadCodeReport;
+
+    expect(parsed.unreachable).toEqual(report.unreachable);
+  });
+});
@@ -0,0 +1,105 @@
+/**
+ * Documentation Coverage Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  computeCoveragePercent,
+  formatDocCoverageReport,
+  meetsDocCoverageThreshold,
+  summarizeDocCoverage,
+  type CheckedDocSymbol,
+  type DocCoverageReport,
+} from '../../src/map/doc-coverage.js';
+
+const symbols: CheckedDocSymbol[] = [
+  {
+    name: 'createServer',
+    kind: 'Function',
+    file: 'src/server/index.ts',
+    line: 3,
+    documented: true,
+  },
+  {
+    name: 'Router',
+    kind: 'Class',
+    file: 'src/server/router.ts',
+    line: 10,
+    documented: false,
+  },
+  {
+    name: 'parse',
+    kind: 'Function',
+    file: 'lib/parse.ts',
+    line: 1,
+    documented: true,
+  },
+  {
+    name: 'listen',
+    kind: 'Function',
+    file: 'src/server/index.ts',
+    line: 1,
+    documented: false,
+  },
+];
+
+describe('Documentation coverage', () => {
+  test('should round to one decimal and treat nothing as covered', () => {
+    expect(computeCoveragePercent(1, 3)).toBe(33.3);
+    expect(computeCoveragePercent(0, 0)).toBe(100);
+  });
+
+  test('should group symbols by module, least documented first', () => {
+    const modules = summarizeDocCoverage(symbols, 2);
+
+    expect(modules.map((entry) => [entry.module, entry.percent])).toEqual([
+      ['src/server', 33.3],
+      ['lib', 100],
+    ]);
+    expect(modules[0]?.undocumented).toEqual([
+      {
+        name: 'listen',
+        kind: 'Function',
+        file: 'src/server/index.ts',
+        line: 1,
+      },
+      { name: 'Router', kind: 'Class', file: 'src/server/router.ts', line: 10 },
+    ]);
+  });
+
+  test('should compare the threshold before rounding', () => {
+    expect(meetsDocCoverageThreshold({ documented: 2, total: 3 }, 66.7)).toBe(
+      false
+    );
+    expect(meetsDocCoverageThreshold({ documented: 2, total: 3 }, 66)).toBe(
+      true
+    );
+    expect(meetsDocCoverageThreshold({ documented: 0, total: 0 }, 100)).toBe(
+      true
+    );
+  });
+
+  test('should render a module table and undocumented symbols', () => {
+    const report: DocCoverageReport = {
+      workspace: '/project',
+      files: 3,
+      total: 4,
+      documented: 2,
+      percent: 50,
+      modules: summarizeDocCoverage(symbols, 2),
+      failedLookups: 1,
+      truncated: false,
+    };
+
+    const text = formatDocCoverageReport(report, 'markdown');
+
+    expect(text).toContain(
+      '2 of 4 public symbol(s) documented in 3 file(s) (50%)'
+    );
+    expect(text).toContain('1 symbol(s) skipped because hover failed');
+    expect(text).toContain('| src/server | 1 | 3 | 33.3% |');
+    expect(text).toContain('## src/server (2 undocumented)');
+    expect(text).toContain('- Router (Class) - src/server/router.ts:10');
+    expect(text).not.toContain('## lib');
+  });
+});
@@ -0,0 +1,123 @@
+/**
+ * Elixir Workspace Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import {
+  createElixirConfigurationNotification,
+  detectMixProject,
+} from '../../src/utils/elixir-workspace.js';
+import { createElixirLoader } from '../../src/workspace/loaders/elixir.js';
+import type { WorkspaceLoaderState } from '../../src/workspace/types.js';
+
+describe('Elixir workspace detection', () => {
+  let workspace: string;
+
+  function write(file: string, content = ''): void {
+    const filePath = path.join(workspace, file);
+    fs.mkdirSync(path.dirname(filePath), { recursive: true });
+    fs.writeFileSync(filePath, content);
+  }
+
+  beforeEach(() => {
+    workspace = fs.realpathSync(
+      fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-elixir-'))
+    );
+  });
+
+  afterEach(() => {
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('prefers the root project and detects umbrellas', () => {
+    write('mix.exs', 'def project, do: [apps_path: "apps"]\n');
+    write('apps/web/mix.exs', 'def project, do: [app: :web]\n');
+
+    expect(detectMixProject(workspace)).toEqual({
+      projectDir: workspace,
+      umbrella: true,
+    });
+  });
+
+  test('uses the only nested project, ignoring dependencies', () => {
+    write('backend/mix.exs', 'def project, do: [app: :backend]\n');
+    write('backend/deps/jason/mix.exs', 'def project, do: [app: :jason]\n');
+
+    const projectInfo = detectMixProject(workspace);
+    expect(projectInfo).toEqual({
+      projectDir: path.join(workspace, 'backend'),
+      umbrella: false,
+    });
+    expect(
+      createElixirConfigurationNotification(workspace, projectInfo!)
+    ).toEqual({
+      method: 'workspace/didChangeConfiguration',
+      params: {
+        settings: {
+          elixirLS: { projectDir: 'backend', dialyzerEnabled: false },
+        },
+      },
+    });
+
+    write('tools/mix.exs', 'def project, do: [app: :tools]\n');
+    expect(detectMixProject(workspace)).toBeNull();
+  });
+});
+
+describe('ElixirLS workspace loader', () => {
+  const building: WorkspaceLoaderState = {
+    type: 'elixir',
+    ready: false,
+    data: {
+      projectInfo: { projectDir: '/workspace', umbrella: false },
+      buildStartedAt: Date.now(),
+      buildTokens: [],
+    },
+  };
+
+  test('is ready when the build progress ends', () => {
+    const loader = createElixirLoader();
+    const otherEnded = loader.handleNotification!(building, '$/progress', {
+      token: 'other',
+      value: { kind: 'end' },
+    });
+    expect(otherEnded.ready).toBe(false);
+
+    const begun = loader.handleNotification!(building, '$/progress', {
+      token: 'build',
+      value: { kind: 'begin', title: 'Compiling' },
+    });
+    expect(
+      loader.handleNotification!(begun, '$/progress', {
+        token: 'build',
+        value: { kind: 'end' },
+      }).ready
+    ).toBe(true);
+  });
+
+  test('is ready when the compile time is logged', () => {
+    const state = createElixirLoader().handleNotification!(
+      building,
+      'window/logMessage',
+      { type: 4, message: '[ElixirLS] Compile took 1523 ms' }
+    );
+
+    expect(state.ready).toBe(true);
+  });
+
+  test('runs the launch script in its own process group', () => {
+    expect(
+      createElixirLoader().prepareCommand!('/workspace', {
+        commandName: 'language_server.sh',
+        commandArgs: [],
+      })
+    ).toEqual({
+      commandName: 'language_server.sh',
+      commandArgs: [],
+      processGroup: true,
+    });
+  });
+});
@@ -0,0 +1,174 @@
+/**
+ * Error Codes Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import * as rpc from 'vscode-jsonrpc';
+import { LSPErrorCodes } from 'vscode-languageserver-protocol';
+import { z } from 'zod';
+import {
+  classifyError,
+  classifyErrorMessage,
+  CodedError,
+  combineCodedErrors,
+  ERROR_CATEGORIES,
+  toCodedError,
+} from '../../src/utils/error-codes.js';
+import {
+  createToolErrorResult,
+  withErrorCodes,
+} from '../../src/tools/errors.js';
+import { withErrorHint } from '../../src/utils/error-hints.js';
+
+describe('Error Codes', () => {
+  test('should classify failures by category', () => {
+    expect(
+      classifyErrorMessage(
+        'Workspace is still loading. Please wait for initialization to complete.'
+      )
+    ).toEqual({ code: 'WORKSPACE_NOT_READY', category: 'workspace' });
+    expect(
+      classifyErrorMessage('Invalid configuration in /tmp/lsps.yaml: bad')
+    ).toEqual({ code: 'CONFIG_INVALID', category: 'config' });
+    expect(classifyErrorMessage('Binary not found: /opt/gopls')).toEqual({
+      code: 'SPAWN_FAILED',
+      category: 'spawn',
+    });
+    expect(classifyErrorMessage('Request timed out after 30s')).toEqual({
+      code: 'TIMEOUT',
+      category: 'timeout',
+    });
+    expect(
+      classifyErrorMessage('Unhandled method textDocument/prepareCallHierarchy')
+    ).toEqual({ code: 'CAPABILITY_UNSUPPORTED', category: 'capability' });
+    expect(
+      classifyErrorMessage('Find references failed: content modified')
+    ).toEqual({ code: 'CONTENT_MODIFIED', category: 'protocol' });
+    expect(
+      classifyErrorMessage(
+        'Rename symbol failed: The symbol at 3:5 cannot be renamed'
+      )
+    ).toEqual({ code: 'RENAME_INVALID', category: 'validation' });
+  });
+
+  test('should ignore appended hints when classifying', () => {
+    const message = withErrorHint('Find references failed: connection closed');
+    expect(message).toContain('Hint:');
+    expect(classifyErrorMessage(message).code).toBe('SERVER_EXITED');
+  });
+
+  test('should prefer explicit codes and recognize argument errors', () => {
+    expect(
+      classifyError(new CodedError('changed', 'STALE_CONTENT', 'workspace'))
+    ).toEqual({ code: 'STALE_CONTENT', category: 'workspace' });
+
+    const parsed = z.object({ line: z.number() }).safeParse({ line: 'x' });
+    expect(parsed.success).toBe(false);
+    expect(classifyError(parsed.error)).toEqual({
+      code: 'INVALID_ARGUMENTS',
+      category: 'validation',
+    });
+
+    expect(classifyError('Something unexpected')).toEqual({
+      code: 'INTERNAL_ERROR',
+      category: 'internal',
+    });
+  });
+
+  test('should keep the code an operation failed with', () => {
+    const outOfBounds = toCodedError({
+      message: 'Line 40 is out of bounds. File has 12 lines.',
+      errorCode: 'POSITION_OUT_OF_BOUNDS',
+    });
+    expect(outOfBounds).toBeInstanceOf(CodedError);
+    expect(classifyError(outOfBounds)).toEqual({
+      code: 'POSITION_OUT_OF_BOUNDS',
+      category: 'validation',
+    });
+
+    // The message alone would read as a timeout
+    const modified = toCodedError({
+      message: 'Hover failed: request timed out',
+      errorCode: 'LSP_ERROR',
+      originalError: new rpc.ResponseError(
+        LSPErrorCodes.ContentModified,
+        'request timed out'
+      ),
+    });
+    expect(modified.code).toBe('CONTENT_MODIFIED');
+    expect(modified.message).toBe('Hover failed: request timed out');
+
+    const exited = toCodedError({
+      message: 'Operation failed: changed on disk',
+      errorCode: 'LSP_ERROR',
+      originalError: new CodedError('gone', 'SERVER_EXITED', 'spawn'),
+    });
+    expect(exited.code).toBe('SERVER_EXITED');
+
+    expect(
+      toCodedError({
+        message: 'Find references failed: boom',
+        errorCode: 'LSP_ERROR',
+        originalError: new Error('boom'),
+      }).code
+    ).toBe('LSP_REQUEST_FAILED');
+  });
+
+  test('should keep the most specific code when combining failures', () => {
+    const timeout = new CodedError('gopls: timed out', 'TIMEOUT', 'timeout');
+    expect(combineCodedErrors([timeout])).toBe(timeout);
+
+    const combined = combineCodedErrors([
+      new CodedError('pyright: boom', 'INTERNAL_ERROR', 'internal'),
+      timeout,
+      new CodedError('clangd: exited', 'SERVER_EXITED', 'spawn'),
+    ]);
+    expect(combined.message).toBe(
+      'pyright: boom\ngopls: timed out\nclangd: exited'
+    );
+    expect(classifyError(combined)).toEqual({
+      code: 'TIMEOUT',
+      category: 'timeout',
+    });
+  });
+
+  test('should only use known categories', () => {
+    const classification = classifyError(new Error('Line 40 is out of bounds'));
+    expect(ERROR_CATEGORIES).toContain(classification.category);
+  });
+});
+
+describe('Tool Error Results', () => {
+  test('should attach the code to the text and metadata', () => {
+    const result = createToolErrorResult(new Error('File not found: /a.ts'));
+
+    expect(result.isError).toBe(true);
+    expect(result._meta).toEqual({
+      errorCode: 'FILE_NOT_FOUND',
+      errorCategory: 'validation',
+    });
+    expect(result.content[0]).toEqual({
+      type: 'text',
+      text: 'File not found: /a.ts\nError code: FILE_NOT_FOUND (validation)',
+    });
+  });
+
+  test('should turn thrown handler errors into coded results', async () => {
+    const handler = withErrorCodes((file: string) =>
+      Promise.reject(new Error(`Path is not a file: ${file}`))
+    );
+
+    const result = await handler('/tmp');
+    expect(result._meta?.errorCode).toBe('FILE_NOT_FOUND');
+  });
+
+  test('should pass successful results through', async () => {
+    const handler = withErrorCodes(() =>
+      Promise.resolve({ content: [{ type: 'text' as const, text: 'ok' }] })
+    );
+
+    await expect(handler()).resolves.toEqual({
+      content: [{ type: 'text', text: 'ok' }],
+    });
+  });
+});
@@ -0,0 +1,48 @@
+/**
+ * Error Hints Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import { getErrorHint, withErrorHint } from '../../src/utils/error-hints.js';
+import { createLspError, ErrorCode } from '../../src/types.js';
+
+describe('Error Hints', () => {
+  test('should translate rust-analyzer content modified errors', () => {
+    expect(
+      getErrorHint('Request textDocument/hover failed: content modified')
+    ).toMatch(/file changed/);
+  });
+
+  test('should translate pyright excluded file errors', () => {
+    expect(getErrorHint('File not included in project')).toMatch(
+      /pyrightconfig\.json/
+    );
+  });
+
+  test('should translate jdt.ls project import failures', () => {
+    expect(getErrorHint('Project import failed for /work/app')).toMatch(
+      /jdt\.ls/
+    );
+  });
+
+  test('should return undefined for unknown errors', () => {
+    expect(getErrorHint('Something unexpected')).toBeUndefined();
+    expect(withErrorHint('Something unexpected')).toBe('Something unexpected');
+  });
+
+  test('should append the hint only once', () => {
+    const once = withErrorHint('content modified');
+    expect(once).toContain('\nHint: ');
+    expect(withErrorHint(once)).toBe(once);
+  });
+
+  test('should include hints in LSP operation errors', () => {
+    const error = createLspError(
+      ErrorCode.LSPError,
+      'Inspect failed: Unhandled method textDocument/implementation'
+    );
+    expect(error.message).toContain(
+      'Hint: This language server does not implement'
+    );
+  });
+});
@@ -0,0 +1,74 @@
+/**
+ * Fuzzy Match Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  fuzzyMatch,
+  splitIdentifierWords,
+} from '../../src/utils/fuzzy-match.js';
+
+describe('Fuzzy matching', () => {
+  test('should split identifiers at humps, acronyms and separators', () => {
+    expect(splitIdentifierWords('UserRepositoryBuilder')).toEqual([
+      'user',
+      'repository',
+      'builder',
+    ]);
+    expect(splitIdentifierWords('parseHTTPResponse2')).toEqual([
+      'parse',
+      'http',
+      'response',
+      '2',
+    ]);
+    expect(splitIdentifierWords('get_user_data')).toEqual([
+      'get',
+      'user',
+      'data',
+    ]);
+  });
+
+  test('should rank exact and prefix matches first', () => {
+    expect(fuzzyMatch('UserRepository', 'UserRepository')).toEqual({
+      score: 100,
+      kind: 'exact',
+    });
+    expect(fuzzyMatch('userrepository', 'UserRepository')?.score).toBe(95);
+    expect(fuzzyMatch('UserRepo', 'UserRepository')?.kind).toBe('prefix');
+  });
+
+  test('should match camel humps and snake_case initials', () => {
+    expect(fuzzyMatch('URB', 'UserRepositoryBuilder')).toEqual({
+      score: 85,
+      kind: 'humps',
+    });
+    expect(fuzzyMatch('UsRepBui', 'UserRepositoryBuilder')?.kind).toBe(
+      'humps'
+    );
+    expect(fuzzyMatch('gud', 'get_user_data')?.kind).toBe('humps');
+    // Skipped words lower the score
+    expect(fuzzyMatch('UB', 'UserRepositoryBuilder')?.score).toBe(82);
+  });
+
+  test('should expand common abbreviations', () => {
+    expect(fuzzyMatch('cfgLoader', 'ConfigLoader')).toEqual({
+      score: 75,
+      kind: 'abbreviation',
+    });
+    expect(fuzzyMatch('repoMgr', 'RepositoryManager')?.kind).toBe(
+      'abbreviation'
+    );
+  });
+
+  test('should fall back to substrings and subsequences', () => {
+    expect(fuzzyMatch('pository', 'UserRepository')?.kind).toBe('substring');
+    expect(fuzzyMatch('usry', 'UserRepository')?.kind).toBe('subsequence');
+    expect(fuzzyMatch('xyz', 'UserRepository')).toBeNull();
+  });
+
+  test('should match qualified queries on their last segment', () => {
+    expect(fuzzyMatch('repo.URB', 'UserRepositoryBuilder')?.kind).toBe(
+      'humps'
+    );
+  });
+});
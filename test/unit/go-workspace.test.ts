@@ -0,0 +1,129 @@
+/**
+ * Go Workspace Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { pathToFileURL } from 'url';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import {
+  createGoWorkspaceFoldersNotification,
+  detectGoWorkspace,
+} from '../../src/utils/go-workspace.js';
+import { createGoplsLoader } from '../../src/workspace/loaders/gopls.js';
+
+describe('Go workspace detection', () => {
+  let workspace: string;
+
+  function write(file: string, content = ''): void {
+    const filePath = path.join(workspace, file);
+    fs.mkdirSync(path.dirname(filePath), { recursive: true });
+    fs.writeFileSync(filePath, content);
+  }
+
+  beforeEach(() => {
+    workspace = fs.realpathSync(
+      fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-go-'))
+    );
+  });
+
+  afterEach(() => {
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('adds modules nested below the root module as workspace folders', async () => {
+    write('go.mod', 'module example.com/app\n');
+    write('tools/go.mod', 'module example.com/app/tools\n');
+    write('vendor/example.com/lib/go.mod', 'module example.com/lib\n');
+
+    const info = await detectGoWorkspace(workspace);
+
+    expect(info).toEqual({
+      type: 'modules',
+      moduleRoots: [workspace, path.join(workspace, 'tools')],
+    });
+    expect(createGoWorkspaceFoldersNotification(workspace, info!)).toEqual({
+      method: 'workspace/didChangeWorkspaceFolders',
+      params: {
+        event: {
+          added: [
+            {
+              uri: pathToFileURL(path.join(workspace, 'tools')).toString(),
+              name: 'tools',
+            },
+          ],
+          removed: [],
+        },
+      },
+    });
+  });
+
+  test('leaves modules to a go.work file', async () => {
+    write('go.work', 'go 1.21\n\nuse ./tools\n');
+    write('tools/go.mod', 'module example.com/tools\n');
+
+    const info = await detectGoWorkspace(workspace);
+
+    expect(info).toEqual({
+      type: 'workspace',
+      workFilePath: path.join(workspace, 'go.work'),
+    });
+    expect(createGoWorkspaceFoldersNotification(workspace, info!)).toBeNull();
+  });
+
+  test('finds nothing outside Go projects', async () => {
+    write('package.json', '{}');
+
+    expect(await detectGoWorkspace(workspace)).toBeNull();
+  });
+});
+
+describe('gopls workspace loader', () => {
+  const loading = {
+    type: 'gopls' as const,
+    ready: false,
+    data: {
+      workspaceInfo: { type: 'modules' as const, moduleRoots: ['/app'] },
+      loadingStartedAt: Date.now(),
+      otherTokens: [],
+    },
+  };
+
+  test('becomes ready when loading packages ends', () => {
+    const loader = createGoplsLoader();
+
+    const begun = loader.handleNotification!(loading, '$/progress', {
+      token: 1,
+      value: { kind: 'begin', title: 'Setting up workspace' },
+    });
+    expect(loader.isReady(begun)).toBe(false);
+
+    const ended = loader.handleNotification!(begun, '$/progress', {
+      token: 1,
+      value: { kind: 'end', message: 'Finished loading packages.' },
+    });
+    expect(loader.isReady(ended)).toBe(true);
+  });
+
+  test('ignores other work ending while packages load', () => {
+    const loader = createGoplsLoader();
+
+    const begun = loader.handleNotification!(loading, '$/progress', {
+      token: 'diagnose',
+      value: { kind: 'begin', title: 'Diagnosing' },
+    });
+    const ended = loader.handleNotification!(begun, '$/progress', {
+      token: 'diagnose',
+      value: { kind: 'end' },
+    });
+    expect(loader.isReady(ended)).toBe(false);
+
+    // Loading began before the loader was initialized, so only its end shows
+    const loaded = loader.handleNotification!(ended, '$/progress', {
+      token: 1,
+      value: { kind: 'end' },
+    });
+    expect(loader.isReady(loaded)).toBe(true);
+  });
+});
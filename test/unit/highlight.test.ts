@@ -0,0 +1,100 @@
+/**
+ * Syntax Highlighting Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  highlightJson,
+  highlightMarkdown,
+  highlightReport,
+  highlightYaml,
+  shouldUseColor,
+} from '../../src/utils/highlight.js';
+
+// eslint-disable-next-line no-control-regex
+const stripAnsi = (text: string) => text.replace(/\x1b\[\d+m/g, '');
+
+describe('Syntax Highlighting', () => {
+  test('should only color interactive terminals in auto mode', () => {
+    expect(shouldUseColor('auto', { isTTY: true }, {})).toBe(true);
+    expect(shouldUseColor('auto', { isTTY: false }, {})).toBe(false);
+    expect(shouldUseColor('auto', { isTTY: true }, { NO_COLOR: '1' })).toBe(
+      false
+    );
+    expect(shouldUseColor('always', { isTTY: false }, {})).toBe(true);
+    expect(shouldUseColor('never', { isTTY: true }, {})).toBe(false);
+  });
+
+  test('should color YAML keys, values and comments', () => {
+    const yamlText = [
+      '# Active Configuration',
+      'language-servers:',
+      '  go:',
+      '    command: gopls # from PATH',
+      '    extensions:',
+      "      '.go': go",
+      '    workspace_files:',
+      "      - 'go.mod'",
+      '    wait_timeout_ms: 2000',
+    ].join('\n');
+
+    const highlighted = highlightYaml(yamlText);
+
+    expect(stripAnsi(highlighted)).toBe(yamlText);
+    expect(highlighted).toContain('\x1b[90m# Active Configuration\x1b[0m');
+    expect(highlighted).toContain('\x1b[36mcommand\x1b[0m: gopls');
+    expect(highlighted).toContain('\x1b[90m # from PATH\x1b[0m');
+    expect(highlighted).toContain("\x1b[32m'go.mod'\x1b[0m");
+    expect(highlighted).toContain('\x1b[33m2000\x1b[0m');
+  });
+
+  test('should color JSON keys and values', () => {
+    const jsonText = JSON.stringify(
+      { command: 'gopls', timeout: 2000, push: true },
+      null,
+      2
+    );
+
+    const highlighted = highlightJson(jsonText);
+
+    expect(stripAnsi(highlighted)).toBe(jsonText);
+    expect(highlighted).toContain('\x1b[36m"command"\x1b[0m:');
+    expect(highlighted).toContain('\x1b[32m"gopls"\x1b[0m');
+    expect(highlighted).toContain('\x1b[33m2000\x1b[0m');
+    expect(highlighted).toContain('\x1b[35mtrue\x1b[0m');
+  });
+
+  test('should color report headings, locations and symbol kinds', () => {
+    const markdown = [
+      '# References src/server.ts:12:17',
+      '2 location(s)',
+      '- src/server.ts:12:17 Function createServer',
+      '- src/main.ts:40:9',
+      '- Server (Class) - src/server.ts:3',
+    ].join('\n');
+
+    const highlighted = highlightMarkdown(markdown);
+
+    expect(stripAnsi(highlighted)).toBe(markdown);
+    expect(highlighted).toContain(
+      '\x1b[36m# References src/server.ts:12:17\x1b[0m'
+    );
+    expect(highlighted).toContain('\n2 location(s)\n');
+    expect(highlighted).toContain(
+      '\x1b[32msrc/server.ts:12:17\x1b[0m \x1b[35mFunction\x1b[0m createServer'
+    );
+    expect(highlighted).toContain('\x1b[32msrc/main.ts:40:9\x1b[0m');
+    expect(highlighted).toContain(
+      'Server (\x1b[35mClass\x1b[0m) - \x1b[32msrc/server.ts:3\x1b[0m'
+    );
+  });
+
+  test('should leave reports alone when colors are off', () => {
+    const markdown = '- src/main.ts:40:9';
+
+    expect(highlightReport(markdown, 'markdown', 'never')).toBe(markdown);
+    expect(highlightReport('{"a": 1}', 'json', 'always')).toContain(
+      '\x1b[36m"a"\x1b[0m'
+    );
+  });
+});
@@ -0,0 +1,233 @@
+/**
+ * Hover Markdown Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  extractHoverDocumentation,
+  formatHoverContents,
+  hasHoverDocumentation,
+  hoverToMarkdown,
+  renderHoverMarkdown,
+} from '../../src/utils/hover-markdown.js';
+
+const pyrightHover = [
+  '```python',
+  '(function) def greet(',
+  '    name: str,',
+  '    excited: bool = False',
+  ') -> str',
+  '```',
+  '---',
+  'Return a **friendly** greeting for `name`.',
+  '',
+  'See [the docs](https://example.com/greet) for more\\_details.',
+].join('\n');
+
+describe('Hover Markdown', () => {
+  test('should keep code fences and multi-line signatures', () => {
+    const rendered = renderHoverMarkdown(pyrightHover);
+
+    expect(rendered).toBe(
+      [
+        '```python',
+        '(function) def greet(',
+        '    name: str,',
+        '    excited: bool = False',
+        ') -> str',
+        '```',
+        '',
+        'Return a friendly greeting for `name`.',
+        '',
+        'See the docs for more_details.',
+      ].join('\n')
+    );
+  });
+
+  test('should not strip markdown inside code fences or inline code', () => {
+    const rendered = renderHoverMarkdown(
+      '```rust\nfn **x**(a: &[u8])\n```\nUse `a * b * c` here'
+    );
+
+    expect(rendered).toContain('fn **x**(a: &[u8])');
+    expect(rendered).toContain('`a * b * c`');
+  });
+
+  test('should fence language-tagged marked strings', () => {
+    expect(
+      hoverToMarkdown({
+        contents: [
+          { language: 'java', value: 'String getName()' },
+          'Returns the name.',
+        ],
+      })
+    ).toBe('```java\nString getName()\n```\n\nReturns the name.');
+  });
+
+  test('should return raw markdown in markdown mode', () => {
+    expect(
+      formatHoverContents(
+        { contents: { kind: 'markdown', value: pyrightHover } },
+        'markdown'
+      )
+    ).toBe(pyrightHover);
+  });
+
+  test('should leave plaintext hovers untouched', () => {
+    expect(
+      formatHoverContents({
+        contents: { kind: 'plaintext', value: 'a * b * c' },
+      })
+    ).toBe('a * b * c');
+  });
+
+  test('should return null for empty hovers', () => {
+    expect(formatHoverContents({ contents: [] })).toBeNull();
+    expect(
+      formatHoverContents({ contents: { kind: 'markdown', value: '  ' } })
+    ).toBeNull();
+  });
+
+  test('should detect documentation beyond the signature', () => {
+    expect(
+      hasHoverDocumentation({
+        contents: { kind: 'markdown', value: pyrightHover },
+      })
+    ).toBe(true);
+    expect(
+      hasHoverDocumentation({
+        contents: {
+          kind: 'markdown',
+          value: '```typescript\nfunction greet(): void\n```\n---\n',
+        },
+      })
+    ).toBe(false);
+    expect(
+      hasHoverDocumentation({
+        contents: [{ language: 'go', value: 'func Greet() string' }],
+      })
+    ).toBe(false);
+  });
+
+  test('should treat the first plaintext paragraph as the signature', () => {
+    expect(
+      hasHoverDocumentation({
+        contents: { kind: 'plaintext', value: 'greet(name: str) -> str' },
+      })
+    ).toBe(false);
+    expect(
+      hasHoverDocumentation({
+        contents: {
+          kind: 'plaintext',
+          value: 'greet(name: str) -> str\n\nReturn a greeting.',
+        },
+      })
+    ).toBe(true);
+  });
+
+  test('should split hovers into signature, documentation and examples', () => {
+    const typescriptHover = [
+      '```typescript',
+      'function parse(input: string): Ast',
+      '```',
+      'Parse source text into a **syntax tree**.',
+      '',
+      '*@param* `input` — the source',
+      '',
+      '*@example* — ```ts',
+      "parse('a + b');",
+      '```',
+      '',
+      '*@returns* the tree',
+    ].join('\n');
+
+    expect(
+      formatHoverContents(
+        { contents: { kind: 'markdown', value: typescriptHover } },
+        'sections'
+      )
+    ).toBe(
+      [
+        'Signature',
+        '```typescript',
+        'function parse(input: string): Ast',
+        '```',
+        '',
+        'Documentation',
+        'Parse source text into a syntax tree.',
+        '',
+        '@param `input` — the source',
+        '',
+        '@returns the tree',
+        '',
+        'Examples',
+        '```ts',
+        "parse('a + b');",
+        '```',
+      ].join('\n')
+    );
+  });
+
+  test('should join leading fences into the signature and end examples at a heading', () => {
+    const rustHover = [
+      '```rust',
+      'my_crate',
+      '```',
+      '',
+      '```rust',
+      'pub fn parse(input: &str) -> Ast',
+      '```',
+      '',
+      '---',
+      '',
+      'Parse source.',
+      '',
+      '# Examples',
+      '',
+      '```',
+      'let ast = parse("1");',
+      '```',
+      '',
+      '# Panics',
+      '',
+      'Never.',
+    ].join('\n');
+
+    expect(
+      formatHoverContents(
+        { contents: { kind: 'markdown', value: rustHover } },
+        'sections'
+      )
+    ).toBe(
+      [
+        'Signature',
+        '```rust',
+        'my_crate',
+        'pub fn parse(input: &str) -> Ast',
+        '```',
+        '',
+        'Documentation',
+        'Parse source.',
+        '',
+        'Panics',
+        '',
+        'Never.',
+        '',
+        'Examples',
+        '```',
+        'let ast = parse("1");',
+        '```',
+      ].join('\n')
+    );
+  });
+
+  test('should extract documentation without the signature', () => {
+    expect(
+      extractHoverDocumentation({
+        contents: { kind: 'markdown', value: pyrightHover },
+      })
+    ).toBe(
+      'Return a friendly greeting for `name`.\n\nSee the docs for more_details.'
+    );
+  });
+});
@@ -0,0 +1,90 @@
+/**
+ * HTTP Auth Tests
+ */
+
+import type { IncomingMessage } from 'http';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import {
+  assertHttpAuthForHost,
+  assertToolScope,
+  authenticateHttpRequest,
+  configureHttpAuth,
+} from '../../src/tools/http-auth.js';
+
+function request(authorization?: string): IncomingMessage {
+  return {
+    headers: authorization ? { authorization } : {},
+    socket: {},
+  } as IncomingMessage;
+}
+
+describe('HTTP auth', () => {
+  beforeEach(() => {
+    process.env.SYMBOLS_TEST_TOKEN = 'secret-edit-token';
+    configureHttpAuth({
+      workspace: process.cwd(),
+      auth: {
+        tokens: [
+          { name: 'reader', token: 'secret-read-token', scopes: ['read'] },
+          {
+            name: 'editor',
+            token: '${SYMBOLS_TEST_TOKEN}',
+            scopes: ['read', 'edit'],
+          },
+          {
+            name: 'unset',
+            token: '${SYMBOLS_TEST_UNSET_TOKEN}',
+            scopes: ['read'],
+          },
+        ],
+      },
+    });
+  });
+
+  afterEach(() => {
+    configureHttpAuth({ workspace: process.cwd(), auth: null });
+    delete process.env.SYMBOLS_TEST_TOKEN;
+  });
+
+  test('should identify clients by bearer token', () => {
+    expect(
+      authenticateHttpRequest(request('Bearer secret-read-token'))
+    ).toMatchObject({ clientId: 'reader', scopes: ['read'] });
+    expect(
+      authenticateHttpRequest(request('bearer secret-edit-token'))
+    ).toMatchObject({ clientId: 'editor', scopes: ['read', 'edit'] });
+  });
+
+  test('should reject missing, unknown and unexpanded tokens', () => {
+    expect(() => authenticateHttpRequest(request())).toThrow(
+      'Missing bearer token'
+    );
+    expect(() => authenticateHttpRequest(request('Bearer nope'))).toThrow(
+      'Invalid bearer token'
+    );
+    expect(() =>
+      authenticateHttpRequest(request('Bearer ${SYMBOLS_TEST_UNSET_TOKEN}'))
+    ).toThrow('Invalid bearer token');
+  });
+
+  test('should require the edit scope for editing tools', () => {
+    const reader = { token: 't', clientId: 'reader', scopes: ['read'] };
+
+    expect(() => assertToolScope('outline', reader)).not.toThrow();
+    expect(() => assertToolScope('rename', reader)).toThrow(
+      'lacks the edit scope'
+    );
+    expect(() => assertToolScope('rename', undefined)).not.toThrow();
+  });
+
+  test('should only serve beyond localhost with auth configured', () => {
+    expect(() => assertHttpAuthForHost('0.0.0.0')).not.toThrow();
+
+    configureHttpAuth({ workspace: process.cwd(), auth: null });
+    expect(() => assertHttpAuthForHost('127.0.0.1')).not.toThrow();
+    expect(() => assertHttpAuthForHost('0.0.0.0')).toThrow(
+      'without authentication'
+    );
+    expect(authenticateHttpRequest(request())).toBeNull();
+  });
+});
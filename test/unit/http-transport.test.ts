@@ -0,0 +1,261 @@
+/**
+ * HTTP Transport Tests
+ */
+
+import * as fs from 'fs';
+import * as http from 'http';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, describe, expect, test } from 'vitest';
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import {
+  startHttpServer,
+  type HttpServerHandle,
+} from '../../src/main/http-transport.js';
+import { configureHttpAuth } from '../../src/tools/http-auth.js';
+
+const INITIALIZE_REQUEST = {
+  jsonrpc: '2.0',
+  id: 1,
+  method: 'initialize',
+  params: {
+    protocolVersion: '2025-03-26',
+    capabilities: {},
+    clientInfo: { name: 'test-client', version: '1.0.0' },
+  },
+};
+
+function post(
+  url: string,
+  body: unknown,
+  headers: Record<string, string> = {}
+): Promise<Response> {
+  return fetch(url, {
+    method: 'POST',
+    headers: {
+      'Content-Type': 'application/json',
+      Accept: 'application/json, text/event-stream',
+      ...headers,
+    },
+    body: JSON.stringify(body),
+  });
+}
+
+describe('HTTP transport', () => {
+  let handle: HttpServerHandle | null = null;
+
+  afterEach(async () => {
+    await handle?.close();
+    handle = null;
+    configureHttpAuth({ workspace: process.cwd(), auth: null });
+  });
+
+  const start = async (transport: 'http' | 'sse' | 'websocket' = 'http') => {
+    handle = await startHttpServer(
+      () => new McpServer({ name: 'test', version: '1.0.0' }),
+      { transport, host: '127.0.0.1', port: 0 }
+    );
+    return handle;
+  };
+
+  test('should open a session with an initialize request', async () => {
+    const { url } = await start();
+
+    const response = await post(url, INITIALIZE_REQUEST);
+
+    expect(response.status).toBe(200);
+    expect(response.headers.get('mcp-session-id')).toBeTruthy();
+    expect(await response.text()).toContain('"serverInfo"');
+  });
+
+  test('should reject requests without a known session', async () => {
+    const { url } = await start();
+    const ping = { jsonrpc: '2.0', id: 2, method: 'ping' };
+
+    const missing = await post(url, ping);
+    expect(missing.status).toBe(400);
+
+    const unknown = await post(url, ping, { 'mcp-session-id': 'nope' });
+    expect(unknown.status).toBe(404);
+
+    const elsewhere = await post(url.replace('/mcp', '/other'), ping);
+    expect(elsewhere.status).toBe(404);
+  });
+
+  test('should require a bearer token when auth is configured', async () => {
+    configureHttpAuth({
+      workspace: process.cwd(),
+      auth: {
+        tokens: [{ name: 'agent', token: 'secret-token', scopes: ['read'] }],
+      },
+    });
+    const { url } = await start();
+
+    const anonymous = await post(url, INITIALIZE_REQUEST);
+    expect(anonymous.status).toBe(401);
+    expect(anonymous.headers.get('www-authenticate')).toBe('Bearer');
+
+    const authenticated = await post(url, INITIALIZE_REQUEST, {
+      Authorization: 'Bearer secret-token',
+    });
+    expect(authenticated.status).toBe(200);
+    await authenticated.text();
+  });
+
+  test('should accept messages posted to an SSE session', async () => {
+    const { url } = await start('sse');
+    expect(url).toMatch(/\/sse$/);
+
+    const abort = new AbortController();
+    const stream = await fetch(url, {
+      headers: { Accept: 'text/event-stream' },
+      signal: abort.signal,
+    });
+    expect(stream.status).toBe(200);
+
+    const reader = stream.body!.getReader();
+    const { value } = await reader.read();
+    const endpoint = /data: (\S+)/.exec(new TextDecoder().decode(value))?.[1];
+    expect(endpoint).toMatch(/^\/messages\?sessionId=/);
+
+    const response = await post(
+      new URL(endpoint!, url).href,
+      INITIALIZE_REQUEST
+    );
+    expect(response.status).toBe(202);
+
+    const wrongSession = await post(
+      new URL('/messages?sessionId=nope', url).href,
+      INITIALIZE_REQUEST
+    );
+    expect(wrongSession.status).toBe(404);
+
+    abort.abort();
+  });
+
+  test.skipIf(typeof WebSocket === 'undefined')(
+    'should answer MCP messages over a WebSocket',
+    async () => {
+      const { url } = await start('websocket');
+      expect(url).toMatch(/^ws:\/\/127\.0\.0\.1:\d+\/ws$/);
+
+      const plain = await post(url.replace('ws:', 'http:'), INITIALIZE_REQUEST);
+      expect(plain.status).toBe(426);
+
+      const socket = new WebSocket(url, 'mcp');
+      const reply = await new Promise<string>((resolve, reject) => {
+        socket.onopen = () => socket.send(JSON.stringify(INITIALIZE_REQUEST));
+        socket.onmessage = (event) => resolve(String(event.data));
+        socket.onerror = () => reject(new Error('WebSocket failed'));
+      });
+      expect(socket.protocol).toBe('mcp');
+      expect(reply).toContain('"serverInfo"');
+
+      socket.close();
+    }
+  );
+
+  test('should refuse WebSocket handshakes from foreign origins', async () => {
+    const { url } = await start('websocket');
+    const upgrade = (origin: string) =>
+      new Promise<number | undefined>((resolve, reject) => {
+        const request = http.request(url.replace('ws:', 'http:'), {
+          headers: {
+            Connection: 'Upgrade',
+            Upgrade: 'websocket',
+            'Sec-WebSocket-Version': '13',
+            'Sec-WebSocket-Key': 'dGhlIHNhbXBsZSBub25jZQ==',
+            Origin: origin,
+          },
+        });
+        request.on('upgrade', (response, socket) => {
+          socket.destroy();
+          resolve(response.statusCode);
+        });
+        request.on('response', (response) => {
+          response.resume();
+          resolve(response.statusCode);
+        });
+        request.on('error', reject);
+        request.end();
+      });
+
+    await expect(upgrade('https://attacker.example')).resolves.toBe(403);
+    const port = new URL(url).port;
+    await expect(upgrade(`http://127.0.0.1:${port}`)).resolves.toBe(101);
+  });
+
+  test('should cap open sessions and close idle ones', async () => {
+    handle = await startHttpServer(
+      () => new McpServer({ name: 'test', version: '1.0.0' }),
+      {
+        transport: 'http',
+        host: '127.0.0.1',
+        port: 0,
+        maxSessions: 1,
+        sessionIdleTimeoutMs: 100,
+      }
+    );
+    const { url } = handle;
+
+    const first = await post(url, INITIALIZE_REQUEST);
+    expect(first.status).toBe(200);
+    await first.text();
+    const sessionId = first.headers.get('mcp-session-id')!;
+
+    const refused = await post(url, INITIALIZE_REQUEST);
+    expect(refused.status).toBe(503);
+
+    await new Promise((resolve) => setTimeout(resolve, 250));
+
+    const ping = { jsonrpc: '2.0', id: 2, method: 'ping' };
+    const expired = await post(url, ping, { 'mcp-session-id': sessionId });
+    expect(expired.status).toBe(404);
+
+    const second = await post(url, INITIALIZE_REQUEST);
+    expect(second.status).toBe(200);
+    await second.text();
+  });
+
+  test.skipIf(process.platform === 'win32')(
+    'should serve sessions on a unix socket only the owner can open',
+    async () => {
+      const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-socket-'));
+      const socket = path.join(dir, 'symbols.sock');
+      handle = await startHttpServer(
+        () => new McpServer({ name: 'test', version: '1.0.0' }),
+        { transport: 'http', host: '127.0.0.1', port: 0, socket }
+      );
+
+      expect(handle.url).toBe(`http+unix://${encodeURIComponent(socket)}/mcp`);
+      expect(fs.statSync(socket).mode & 0o777).toBe(0o600);
+
+      const status = await new Promise<number | undefined>(
+        (resolve, reject) => {
+          const request = http.request(
+            {
+              socketPath: socket,
+              path: '/mcp',
+              method: 'POST',
+              headers: {
+                'Content-Type': 'application/json',
+                Accept: 'application/json, text/event-stream',
+              },
+            },
+            (response) => {
+              response.resume();
+              resolve(response.statusCode);
+            }
+          );
+          request.on('error', reject);
+          request.end(JSON.stringify(INITIALIZE_REQUEST));
+        }
+      );
+      expect(status).toBe(200);
+
+      await handle.close();
+      handle = null;
+      fs.rmSync(dir, { recursive: true, force: true });
+    }
+  );
+});
@@ -0,0 +1,172 @@
+/**
+ * Import Cycle Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  extractImports,
+  findImportCycles,
+  findStronglyConnectedComponents,
+  formatImportCycleReport,
+  type ImportCycleReport,
+} from '../../src/map/import-cycles.js';
+
+describe('Import cycles', () => {
+  test('should resolve relative TypeScript imports and skip type-only ones', () => {
+    const files = new Set(['src/a.ts', 'src/b.ts', 'src/c/index.ts', 'src/types.ts']);
+    const source = [
+      "import { b } from './b.js';",
+      "import type { Shape } from './types.js';",
+      'import {',
+      '  c,',
+      "} from './c';",
+      "import * as fs from 'fs';",
+      "const lazy = await import('./b');",
+    ].join('\n');
+
+    const edges = extractImports('src/a.ts', source, files);
+
+    expect(edges).toEqual([
+      { from: 'src/a.ts', to: 'src/b.ts', line: 1, specifier: './b.js' },
+      { from: 'src/a.ts', to: 'src/c/index.ts', line: 3, specifier: './c' },
+      { from: 'src/a.ts', to: 'src/b.ts', line: 7, specifier: './b' },
+    ]);
+  });
+
+  test('should resolve absolute, relative and submodule Python imports', () => {
+    const files = new Set([
+      'app/__init__.py',
+      'app/models.py',
+      'app/views.py',
+      'app/utils/format.py',
+    ]);
+    const source = [
+      'import os',
+      'from app import models',
+      'from .utils import format',
+      'from .models import User, Group',
+    ].join('\n');
+
+    const edges = extractImports('app/views.py', source, files);
+
+    expect(edges.map((edge) => [edge.to, edge.line])).toEqual([
+      ['app/models.py', 2],
+      ['app/utils/format.py', 3],
+      ['app/models.py', 4],
+    ]);
+  });
+
+  test('should resolve single-type, static and wildcard Java imports', () => {
+    const files = new Set([
+      'src/main/java/com/acme/App.java',
+      'src/main/java/com/acme/util/Strings.java',
+      'src/main/java/com/acme/util/Numbers.java',
+    ]);
+    const source = [
+      'import java.util.List;',
+      'import com.acme.util.Strings;',
+      'import static com.acme.util.Numbers.parse;',
+      'import com.acme.util.*;',
+    ].join('\n');
+
+    const edges = extractImports(
+      'src/main/java/com/acme/App.java',
+      source,
+      files
+    );
+
+    expect(edges.map((edge) => [edge.to, edge.line])).toEqual([
+      ['src/main/java/com/acme/util/Strings.java', 2],
+      ['src/main/java/com/acme/util/Numbers.java', 3],
+      ['src/main/java/com/acme/util/Strings.java', 4],
+      ['src/main/java/com/acme/util/Numbers.java', 4],
+    ]);
+  });
+
+  test('should find strongly connected components', () => {
+    const components = findStronglyConnectedComponents(
+      ['a', 'b', 'c', 'd'],
+      [
+        { from: 'a', to: 'b' },
+        { from: 'b', to: 'c' },
+        { from: 'c', to: 'a' },
+        { from: 'c', to: 'd' },
+      ]
+    );
+
+    expect(components).toContainEqual(['a', 'b', 'c']);
+    expect(components).toContainEqual(['d']);
+  });
+
+  const edges = [
+    {
+      from: 'src/api/routes.ts',
+      to: 'src/core/engine.ts',
+      line: 1,
+      specifier: '../core/engine.js',
+    },
+    {
+      from: 'src/core/engine.ts',
+      to: 'src/core/state.ts',
+      line: 2,
+      specifier: './state.js',
+    },
+    {
+      from: 'src/core/state.ts',
+      to: 'src/api/types.ts',
+      line: 1,
+      specifier: '../api/types.js',
+    },
+    {
+      from: 'src/api/types.ts',
+      to: 'src/api/routes.ts',
+      line: 3,
+      specifier: './routes.js',
+    },
+  ];
+
+  test('should report file cycles with the imports involved', () => {
+    const cycles = findImportCycles('typescript', edges);
+
+    expect(cycles).toHaveLength(1);
+    expect(cycles[0]?.members).toEqual([
+      'src/api/routes.ts',
+      'src/api/types.ts',
+      'src/core/engine.ts',
+      'src/core/state.ts',
+    ]);
+    expect(cycles[0]?.imports).toHaveLength(4);
+  });
+
+  test('should report module cycles from cross-module imports only', () => {
+    const cycles = findImportCycles('typescript', edges, 2);
+
+    expect(cycles).toHaveLength(1);
+    expect(cycles[0]?.members).toEqual(['src/api', 'src/core']);
+    expect(cycles[0]?.imports.map((edge) => edge.from)).toEqual([
+      'src/api/routes.ts',
+      'src/core/state.ts',
+    ]);
+  });
+
+  test('should render cycles as markdown', () => {
+    const report: ImportCycleReport = {
+      workspace: '/project',
+      depth: null,
+      files: 4,
+      imports: 4,
+      cycles: findImportCycles('typescript', edges),
+      unsupportedExtensions: ['.go'],
+      truncated: false,
+    };
+
+    const text = formatImportCycleReport(report, 'markdown');
+
+    expect(text).toContain('4 file(s), 4 import(s), 1 cycle(s) (file graph)');
+    expect(text).toContain('Imports not analyzed for: .go');
+    expect(text).toContain('## Cycle 1 (typescript, 4 members)');
+    expect(text).toContain(
+      "- src/core/state.ts:1 -> src/api/types.ts ('../api/types.js')"
+    );
+  });
+});
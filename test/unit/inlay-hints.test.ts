@@ -0,0 +1,44 @@
+/**
+ * Inlay Hints Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import { interleaveInlayHints } from '../../src/utils/inlay-hints.js';
+
+describe('Inlay hints', () => {
+  test('should interleave types and parameter names with padding', () => {
+    const lines = ['const total = add(1, 2);', 'return total;'];
+    const hinted = interleaveInlayHints(lines, [
+      { position: { line: 0, character: 21 }, label: 'b:', paddingRight: true },
+      { position: { line: 0, character: 11 }, label: ': number' },
+      { position: { line: 0, character: 18 }, label: 'a:', paddingRight: true },
+    ]);
+
+    expect(hinted.lines).toEqual([
+      'const total: number = add(a: 1, b: 2);',
+      'return total;',
+    ]);
+  });
+
+  test('should join label parts and map positions past earlier hints', () => {
+    const hinted = interleaveInlayHints(
+      ['let items = load();'],
+      [
+        {
+          position: { line: 0, character: 9 },
+          label: [{ value: ': ' }, { value: 'Vec<Item>' }],
+        },
+      ]
+    );
+
+    expect(hinted.lines).toEqual(['let items: Vec<Item> = load();']);
+    expect(hinted.mapPosition({ line: 0, character: 4 })).toEqual({
+      line: 0,
+      character: 4,
+    });
+    expect(hinted.mapPosition({ line: 0, character: 12 })).toEqual({
+      line: 0,
+      character: 23,
+    });
+  });
+});
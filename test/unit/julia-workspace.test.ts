@@ -0,0 +1,75 @@
+/**
+ * Julia Workspace Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import { findJuliaProject } from '../../src/utils/julia-workspace.js';
+import { createJuliaLoader } from '../../src/workspace/loaders/julia.js';
+
+describe('Julia workspace detection', () => {
+  let workspace: string;
+
+  function write(file: string, content = ''): void {
+    const filePath = path.join(workspace, file);
+    fs.mkdirSync(path.dirname(filePath), { recursive: true });
+    fs.writeFileSync(filePath, content);
+  }
+
+  beforeEach(() => {
+    workspace = fs.realpathSync(
+      fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-julia-'))
+    );
+  });
+
+  afterEach(() => {
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('uses the only nested project, ignoring docs and test environments', () => {
+    write('Analysis/Project.toml', 'name = "Analysis"\n');
+    write('Analysis/docs/Project.toml', '[deps]\n');
+    write('Analysis/test/Project.toml', '[deps]\n');
+
+    expect(findJuliaProject(workspace)).toBe(path.join(workspace, 'Analysis'));
+
+    write('Project.toml', 'name = "Root"\n');
+    expect(findJuliaProject(workspace)).toBe(workspace);
+  });
+
+  test('completes a bare julia command with the startup script', () => {
+    write('Project.toml', 'name = "App"\n');
+
+    expect(
+      createJuliaLoader().prepareCommand!(workspace, {
+        commandName: 'julia',
+        commandArgs: ['--threads=2'],
+      })
+    ).toEqual({
+      commandName: 'julia',
+      commandArgs: [
+        '--startup-file=no',
+        '--history-file=no',
+        '--project=@languageserver',
+        '--threads=2',
+        '-e',
+        'using LanguageServer; runserver(stdin, stdout, ENV["SYMBOLS_JULIA_PROJECT"])',
+      ],
+      environment: { SYMBOLS_JULIA_PROJECT: workspace },
+    });
+  });
+
+  test('keeps commands with their own script', () => {
+    const command = {
+      commandName: 'julia',
+      commandArgs: ['--project=/opt/ls', '/opt/ls/start.jl'],
+    };
+
+    expect(createJuliaLoader().prepareCommand!(workspace, command)).toEqual({
+      ...command,
+      environment: { SYMBOLS_JULIA_PROJECT: workspace },
+    });
+  });
+});
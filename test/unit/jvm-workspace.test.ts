@@ -0,0 +1,225 @@
+/**
+ * JVM Workspace Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import { detectJvmWorkspace } from '../../src/utils/jvm-workspace.js';
+import { createJdtlsLoader } from '../../src/workspace/loaders/jdtls.js';
+import { createKotlinLoader } from '../../src/workspace/loaders/kotlin.js';
+import { createMetalsLoader } from '../../src/workspace/loaders/metals.js';
+
+describe('JVM workspace detection', () => {
+  let workspace: string;
+
+  function write(file: string, content = ''): void {
+    const filePath = path.join(workspace, file);
+    fs.mkdirSync(path.dirname(filePath), { recursive: true });
+    fs.writeFileSync(filePath, content);
+  }
+
+  beforeEach(() => {
+    workspace = fs.realpathSync(
+      fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-jvm-'))
+    );
+  });
+
+  afterEach(() => {
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('finds Maven and Gradle build files outside build output', async () => {
+    write('pom.xml', '<project/>\n');
+    write('tools/build.gradle.kts', 'plugins { java }\n');
+    write('target/classes/META-INF/maven/app/pom.xml', '<project/>\n');
+
+    expect(await detectJvmWorkspace(workspace)).toEqual({
+      buildTools: ['maven', 'gradle'],
+      buildFiles: [
+        path.join(workspace, 'pom.xml'),
+        path.join(workspace, 'tools', 'build.gradle.kts'),
+      ],
+    });
+  });
+
+  test('finds sbt builds outside Metals and Bloop directories', async () => {
+    write('build.sbt', 'scalaVersion := "3.3.1"\n');
+    write('.bloop/root/build.sbt', '');
+
+    expect(await detectJvmWorkspace(workspace)).toEqual({
+      buildTools: ['sbt'],
+      buildFiles: [path.join(workspace, 'build.sbt')],
+    });
+  });
+
+  test('finds nothing without a build file', async () => {
+    write('src/Main.java', 'class Main {}\n');
+
+    expect(await detectJvmWorkspace(workspace)).toBeNull();
+  });
+});
+
+describe('jdtls workspace loader', () => {
+  const importing = {
+    type: 'jdtls' as const,
+    ready: false,
+    data: {
+      workspaceInfo: { buildTools: ['maven' as const], buildFiles: [] },
+      loadingStartedAt: Date.now(),
+      activeTokens: [],
+    },
+  };
+
+  test('becomes ready once the projects are imported', () => {
+    const loader = createJdtlsLoader();
+
+    const progress = loader.handleNotification!(importing, 'language/status', {
+      type: 'Starting',
+      message: '40% Starting Java Language Server',
+    });
+    expect(loader.isReady(progress)).toBe(false);
+
+    const started = loader.handleNotification!(progress, 'language/status', {
+      type: 'Started',
+      message: 'Ready',
+    });
+    expect(loader.isReady(started)).toBe(true);
+  });
+
+  test('stops waiting when the import fails', () => {
+    const loader = createJdtlsLoader();
+
+    const failed = loader.handleNotification!(importing, 'language/status', {
+      type: 'Error',
+      message: 'Initialization failed',
+    });
+    expect(loader.isReady(failed)).toBe(true);
+  });
+
+  test('gives up waiting after the import timeout', () => {
+    const loader = createJdtlsLoader();
+
+    expect(
+      loader.isReady({
+        ...importing,
+        data: { ...importing.data, loadingStartedAt: Date.now() - 301_000 },
+      })
+    ).toBe(true);
+  });
+});
+
+describe('kotlin workspace loader', () => {
+  const importing = {
+    type: 'kotlin' as const,
+    ready: false,
+    data: {
+      workspaceInfo: { buildTools: ['gradle' as const], buildFiles: [] },
+      loadingStartedAt: Date.now(),
+      activeTokens: [],
+    },
+  };
+
+  test('becomes ready once all progress has ended', () => {
+    const loader = createKotlinLoader();
+
+    const importStarted = loader.handleNotification!(importing, '$/progress', {
+      token: 'import',
+      value: { kind: 'begin', title: 'Importing Gradle project' },
+    });
+    const indexStarted = loader.handleNotification!(
+      importStarted,
+      '$/progress',
+      { token: 'index', value: { kind: 'begin', title: 'Indexing' } }
+    );
+    const imported = loader.handleNotification!(indexStarted, '$/progress', {
+      token: 'import',
+      value: { kind: 'end' },
+    });
+    expect(loader.isReady(imported)).toBe(false);
+
+    const indexed = loader.handleNotification!(imported, '$/progress', {
+      token: 'index',
+      value: { kind: 'end' },
+    });
+    expect(loader.isReady(indexed)).toBe(true);
+  });
+
+  test('counts progress begun before the loader was initialized', () => {
+    const loader = createKotlinLoader();
+
+    const ended = loader.handleNotification!(importing, '$/progress', {
+      token: 1,
+      value: { kind: 'end' },
+    });
+    expect(loader.isReady(ended)).toBe(true);
+  });
+});
+
+describe('metals workspace loader', () => {
+  const importing = {
+    type: 'metals' as const,
+    ready: false,
+    data: {
+      workspaceInfo: { buildTools: ['sbt' as const], buildFiles: [] },
+      loadingStartedAt: Date.now(),
+      activeTokens: [],
+    },
+  };
+
+  test('accepts the offer to import the build', () => {
+    const loader = createMetalsLoader();
+    const importBuild = { title: 'Import build' };
+
+    expect(
+      loader.chooseMessageAction!({
+        type: 3,
+        message: 'New sbt workspace detected, would you like to import the build?',
+        actions: [importBuild, { title: 'Not now' }],
+      })
+    ).toBe(importBuild);
+    expect(
+      loader.chooseMessageAction!({
+        type: 3,
+        message: 'Would you like to enable semanticdb?',
+        actions: [{ title: 'Yes' }],
+      })
+    ).toBeNull();
+  });
+
+  test('becomes ready once indexing has ended', () => {
+    const loader = createMetalsLoader();
+
+    const compiling = loader.handleNotification!(importing, '$/progress', {
+      token: 'compile',
+      value: { kind: 'begin', title: 'Compiling root' },
+    });
+    const indexing = loader.handleNotification!(compiling, '$/progress', {
+      token: 'index',
+      value: { kind: 'begin', title: 'Indexing' },
+    });
+    const compiled = loader.handleNotification!(indexing, '$/progress', {
+      token: 'compile',
+      value: { kind: 'end' },
+    });
+    expect(loader.isReady(compiled)).toBe(false);
+
+    const indexed = loader.handleNotification!(compiled, '$/progress', {
+      token: 'index',
+      value: { kind: 'end' },
+    });
+    expect(loader.isReady(indexed)).toBe(true);
+  });
+
+  test('reads the import outcome from the status bar', () => {
+    const loader = createMetalsLoader();
+
+    const status = (text: string) =>
+      loader.handleNotification!(importing, 'metals/status', { text });
+
+    expect(loader.isReady(status('$(sync~spin) Importing build'))).toBe(false);
+    expect(loader.isReady(status('$(rocket) Indexing complete!'))).toBe(true);
+    expect(loader.isReady(status('$(alert) Build import failed'))).toBe(true);
+  });
+});
@@ -106,6 +106,8 @@ describe('language server resources', () => {
       status.profiles[0]
     );
 
+    expect(detail.runtime.lastErrorCode).toBe('SPAWN_FAILED');
+    expect(detail.runtime.lastErrorCategory).toBe('spawn');
     expect(detail.hints).toContain(
       'Session is in an error state. Check the logs resource, then verify the configured command and install path.'
     );
@@ -167,4 +169,12 @@ describe('window log formatting', () => {
 
     expect(formatted).toBe('ℹ [Info] [csharp] Workspace loaded successfully');
   });
+
+  it('marks messages the server asked to show', () => {
+    const formatted = formatWindowLogMessages([
+      { type: 1, message: 'rustc not found on PATH', shown: true },
+    ]);
+
+    expect(formatted).toBe('✘ [Error, shown] rustc not found on PATH');
+  });
 });
@@ -1,7 +1,7 @@
 import * as fs from 'node:fs';
 import * as os from 'node:os';
 import * as path from 'node:path';
-import { afterEach, describe, expect, it } from 'vitest';
+import { afterEach, describe, expect, it, vi } from 'vitest';
 import { getLspConfig } from '../../src/config/lsp-config.js';
 
 const tempDirs: string[] = [];
@@ -16,6 +16,7 @@ function writeConfig(yaml: string): string {
 }
 
 afterEach(() => {
+  vi.unstubAllEnvs();
   for (const tempDir of tempDirs.splice(0)) {
     fs.rmSync(tempDir, { recursive: true, force: true });
   }
@@ -83,4 +84,85 @@ language-servers:
     expect(config?.extensions['.C']).toBe('cpp');
     expect(config?.extensions['.H']).toBe('cpp');
   });
+
+  it('reads initialization options from the profile or SYMBOLS_INITIALIZATION_OPTIONS', () => {
+    const configPath = writeConfig(`
+language-servers:
+  php:
+    command: intelephense --stdio
+    initialization_options:
+      licenceKey: $INTELEPHENSE_LICENCE_KEY
+      clearCache: false
+`);
+
+    expect(getLspConfig('php', configPath)?.initialization_options).toEqual({
+      licenceKey: '$INTELEPHENSE_LICENCE_KEY',
+      clearCache: false,
+    });
+
+    vi.stubEnv('SYMBOLS_INITIALIZATION_OPTIONS', '{"licenceKey":"KEY"}');
+    expect(getLspConfig('php', configPath)?.initialization_options).toEqual({
+      licenceKey: 'KEY',
+    });
+
+    vi.stubEnv('SYMBOLS_INITIALIZATION_OPTIONS', '["KEY"]');
+    expect(() => getLspConfig('php', configPath)).toThrow(
+      'Invalid SYMBOLS_INITIALIZATION_OPTIONS. Must be a JSON object.'
+    );
+  });
+
+  it('reads settings from the profile or SYMBOLS_SETTINGS', () => {
+    const configPath = writeConfig(`
+language-servers:
+  yaml:
+    command: yaml-language-server --stdio
+    settings:
+      yaml:
+        schemas:
+          kubernetes: 'k8s/*.yaml'
+`);
+
+    expect(getLspConfig('yaml', configPath)?.settings).toEqual({
+      yaml: { schemas: { kubernetes: 'k8s/*.yaml' } },
+    });
+
+    vi.stubEnv('SYMBOLS_SETTINGS', '{"yaml":{"validate":false}}');
+    expect(getLspConfig('yaml', configPath)?.settings).toEqual({
+      yaml: { validate: false },
+    });
+
+    vi.stubEnv('SYMBOLS_SETTINGS', 'validate');
+    expect(() => getLspConfig('yaml', configPath)).toThrow(
+      'Invalid SYMBOLS_SETTINGS. Must be a JSON object.'
+    );
+  });
+
+  it('reads readiness patterns for servers without a workspace loader', () => {
+    const configPath = writeConfig(`
+language-servers:
+  zls:
+    command: zls
+    extensions:
+      '.zig': 'zig'
+    readiness:
+      progress_title: 'build|index'
+`);
+
+    expect(getLspConfig('zls', configPath)?.readiness).toEqual({
+      progress_title: 'build|index',
+      timeout_ms: 120000,
+    });
+
+    const invalidPath = writeConfig(`
+language-servers:
+  zls:
+    command: zls
+    readiness:
+      log_message: 'loaded ('
+`);
+
+    expect(() => getLspConfig('zls', invalidPath)).toThrow(
+      'Must be a valid regular expression'
+    );
+  });
 });
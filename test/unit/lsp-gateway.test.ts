@@ -0,0 +1,272 @@
+import { PassThrough } from 'stream';
+import * as rpc from 'vscode-jsonrpc';
+import {
+  LSPErrorCodes,
+  type ServerCapabilities,
+} from 'vscode-languageserver-protocol';
+import { describe, expect, it, vi } from 'vitest';
+import type { LspManager } from '../../src/runtime/lsp-manager.js';
+import * as LspOperations from '../../src/lsp/operations/index.js';
+import {
+  getRequestDocumentUri,
+  serveLspGateway,
+} from '../../src/lsp/lsp-gateway.js';
+
+vi.mock('../../src/lsp/operations/index.js', async () => {
+  const actual = await vi.importActual('../../src/lsp/operations/index.js');
+  return {
+    ...actual,
+    getDiagnostics: vi.fn(),
+  };
+});
+
+vi.mock('../../src/preparation.js', async () => {
+  const actual = await vi.importActual('../../src/preparation.js');
+  return {
+    ...actual,
+    prepareFileRequest: vi.fn((_session, { file }: { file: string }) => ({
+      ok: true,
+      data: { filePath: file },
+    })),
+  };
+});
+
+const mockGetDiagnostics = vi.mocked(LspOperations.getDiagnostics);
+
+function createSession(name: string, capabilities: ServerCapabilities) {
+  const request = vi.fn(
+    async (
+      method: string,
+      _params?: unknown,
+      _token?: rpc.CancellationToken
+    ): Promise<unknown> => `${name} ${method}`
+  );
+  return {
+    request,
+    getProfile: () => ({ name }),
+    getServerCapabilities: () => capabilities,
+    setDocumentOverlay: vi.fn(
+      async (_filePath: string, _content: string | null) => ({
+        ok: true as const,
+        data: undefined,
+      })
+    ),
+    executeWithDocumentLifecycle: vi.fn(
+      async (
+        _filePath: string,
+        _strategy: string,
+        operation: (scope: { request: typeof request }) => Promise<unknown>
+      ) => await operation({ request })
+    ),
+  };
+}
+
+function connect() {
+  const typescript = createSession('typescript', {
+    hoverProvider: true,
+    completionProvider: { triggerCharacters: ['.'], resolveProvider: true },
+  });
+  const python = createSession('python', {
+    hoverProvider: true,
+    completionProvider: { triggerCharacters: ['.', ':'] },
+    renameProvider: { prepareProvider: true },
+    semanticTokensProvider: {
+      legend: { tokenTypes: [], tokenModifiers: [] },
+    },
+  });
+  const manager = {
+    getSessionForFile: vi.fn(async (filePath: string) =>
+      filePath.endsWith('.py') ? python : typescript
+    ),
+    getSearchSessions: vi.fn(async () => [typescript, python]),
+    shutdown: vi.fn(async () => {}),
+  };
+
+  const toServer = new PassThrough();
+  const toClient = new PassThrough();
+  const served = serveLspGateway(
+    manager as unknown as LspManager,
+    new rpc.StreamMessageReader(toServer),
+    new rpc.StreamMessageWriter(toClient)
+  );
+  const connection = rpc.createMessageConnection(
+    new rpc.StreamMessageReader(toClient),
+    new rpc.StreamMessageWriter(toServer)
+  );
+  return { typescript, python, manager, served, connection };
+}
+
+describe('LSP gateway', () => {
+  it('finds the document a request is about', () => {
+    expect(
+      getRequestDocumentUri({ textDocument: { uri: 'file:///repo/a.ts' } })
+    ).toBe('file:///repo/a.ts');
+    expect(getRequestDocumentUri({ item: { uri: 'file:///repo/a.py' } })).toBe(
+      'file:///repo/a.py'
+    );
+    expect(getRequestDocumentUri({ query: 'User' })).toBeUndefined();
+  });
+
+  it('routes requests to the server for each file', async () => {
+    const { typescript, python, served, connection } = connect();
+    connection.listen();
+
+    const initialized = await connection.sendRequest<{
+      capabilities: ServerCapabilities;
+    }>('initialize', { rootUri: 'file:///repo' });
+    expect(initialized.capabilities).toEqual({
+      textDocumentSync: { openClose: true, change: 2 },
+      hoverProvider: true,
+      completionProvider: {
+        triggerCharacters: ['.', ':'],
+        resolveProvider: true,
+      },
+      renameProvider: { prepareProvider: true },
+      workspaceSymbolProvider: false,
+    });
+
+    await expect(
+      connection.sendRequest('textDocument/hover', {
+        textDocument: { uri: 'file:///repo/app.py' },
+        position: { line: 0, character: 4 },
+      })
+    ).resolves.toBe('python textDocument/hover');
+    expect(python.executeWithDocumentLifecycle).toHaveBeenCalledWith(
+      '/repo/app.py',
+      'respect_existing',
+      expect.any(Function)
+    );
+
+    await connection.sendRequest('textDocument/completion', {
+      textDocument: { uri: 'file:///repo/app.ts' },
+      position: { line: 0, character: 4 },
+    });
+    await expect(
+      connection.sendRequest('completionItem/resolve', { label: 'save' })
+    ).resolves.toBe('typescript completionItem/resolve');
+
+    typescript.request.mockResolvedValueOnce([{ name: 'User' }] as never);
+    python.request.mockResolvedValueOnce([{ name: 'user' }] as never);
+    await expect(
+      connection.sendRequest('workspace/symbol', { query: 'user' })
+    ).resolves.toEqual([{ name: 'User' }, { name: 'user' }]);
+
+    await connection.sendRequest('shutdown');
+    await connection.sendNotification('exit');
+    await expect(served).resolves.toBe(0);
+    connection.dispose();
+  });
+
+  it('passes server errors and cancellations through', async () => {
+    const { typescript, served, connection } = connect();
+    connection.listen();
+    const hover = {
+      textDocument: { uri: 'file:///repo/app.ts' },
+      position: { line: 0, character: 4 },
+    };
+
+    typescript.request.mockRejectedValueOnce(
+      new rpc.ResponseError(LSPErrorCodes.ContentModified, 'Content modified')
+    );
+    await expect(
+      connection.sendRequest('textDocument/hover', hover)
+    ).rejects.toMatchObject({
+      code: LSPErrorCodes.ContentModified,
+      message: 'Content modified',
+    });
+
+    typescript.request.mockImplementationOnce(
+      async (_method, _params, token) =>
+        await new Promise((_resolve, reject) => {
+          token?.onCancellationRequested(() =>
+            reject(
+              new rpc.ResponseError(
+                LSPErrorCodes.RequestCancelled,
+                'Request cancelled'
+              )
+            )
+          );
+        })
+    );
+    const cancellation = new rpc.CancellationTokenSource();
+    const pending = connection.sendRequest(
+      'textDocument/hover',
+      hover,
+      cancellation.token
+    );
+    await vi.waitFor(() => expect(typescript.request).toHaveBeenCalledTimes(2));
+    cancellation.cancel();
+    await expect(pending).rejects.toMatchObject({
+      code: LSPErrorCodes.RequestCancelled,
+    });
+
+    await connection.sendNotification('exit');
+    await expect(served).resolves.toBe(1);
+    connection.dispose();
+  });
+
+  it('overlays open documents and publishes their diagnostics', async () => {
+    const { typescript, served, connection } = connect();
+    const published = vi.fn();
+    connection.onNotification(
+      'textDocument/publishDiagnostics',
+      (params: unknown) => published(params)
+    );
+    connection.listen();
+    mockGetDiagnostics.mockResolvedValue({
+      ok: true,
+      data: [
+        {
+          code: '2304',
+          message: "Cannot find name 'x'.",
+          severity: 1,
+          range: {
+            start: { line: 0, character: 0 },
+            end: { line: 0, character: 1 },
+          },
+          source: 'typescript',
+        },
+      ],
+    });
+
+    await connection.sendNotification('textDocument/didOpen', {
+      textDocument: {
+        uri: 'file:///repo/app.ts',
+        languageId: 'typescript',
+        version: 1,
+        text: 'x;\n',
+      },
+    });
+    await connection.sendNotification('textDocument/didChange', {
+      textDocument: { uri: 'file:///repo/app.ts', version: 2 },
+      contentChanges: [{ text: 'x();\n' }],
+    });
+
+    await vi.waitFor(() => expect(published).toHaveBeenCalled(), {
+      timeout: 2000,
+    });
+    expect(typescript.setDocumentOverlay.mock.calls).toEqual([
+      ['/repo/app.ts', 'x;\n'],
+      ['/repo/app.ts', 'x();\n'],
+    ]);
+    expect(published).toHaveBeenCalledWith({
+      uri: 'file:///repo/app.ts',
+      diagnostics: [expect.objectContaining({ code: '2304', severity: 1 })],
+    });
+
+    await connection.sendNotification('textDocument/didClose', {
+      textDocument: { uri: 'file:///repo/app.ts' },
+    });
+    await vi.waitFor(() =>
+      expect(typescript.setDocumentOverlay).toHaveBeenLastCalledWith(
+        '/repo/app.ts',
+        null
+      )
+    );
+
+    await connection.sendRequest('shutdown');
+    await connection.sendNotification('exit');
+    await expect(served).resolves.toBe(0);
+    connection.dispose();
+  });
+});
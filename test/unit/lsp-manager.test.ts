@@ -134,6 +134,7 @@ function createMockSessionRecord(
     stop: stopMock,
     restart: restartMock,
     request: vi.fn(),
+    getServerCapabilities: () => null,
     getWorkspaceState: vi.fn(() => ({
       isReady: true,
       isLoading: false,
@@ -153,8 +154,13 @@ function createMockSessionRecord(
     getWindowLogStore: vi.fn(() => ({
       getMessages: () => [],
     })),
+    getProgressStore: vi.fn(() => ({ getActive: () => [] }) as never),
     executeWithCursorContext: vi.fn(),
     executeWithDocumentLifecycle: vi.fn(),
+    isDocumentStale: vi.fn(() => Promise.resolve(false)),
+    setDocumentOverlay: vi.fn(() =>
+      Promise.resolve({ ok: true as const, data: undefined })
+    ),
     claimDocument: (filePath) => {
       const normalizedPath = path.isAbsolute(filePath)
         ? path.normalize(filePath)
@@ -559,6 +565,28 @@ describe('LspManager routing', () => {
     });
   });
 
+  it('starts only the profiles configured to preload before any query', async () => {
+    configuredProfiles = {
+      ...configuredProfiles,
+      beta: {
+        ...createParsedConfig('beta', {
+          '.py': 'python',
+        }),
+        preload: true,
+      },
+    };
+
+    const manager = createLspManager();
+    await manager.configureForStart({
+      command: 'start',
+      workspace: workspacePath,
+    } as StartCommandArgs);
+    await manager.preloadProfiles();
+
+    expect(sessionRecords.get('beta')?.startMock).toHaveBeenCalledTimes(1);
+    expect(manager.getProfileStatus('alpha')?.state).toBe('not_started');
+  });
+
   it('supports destructured status helpers without relying on this binding', async () => {
     const manager = createLspManager();
     await manager.configureForStart({
@@ -1,14 +1,37 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
 import { beforeEach, describe, expect, it, vi } from 'vitest';
+import type { ServerCapabilities } from 'vscode-languageserver-protocol';
 import {
+  applyCodeAction,
   callHierarchy,
+  classifyReferenceAccess,
+  codeActions,
+  codeLenses,
   completion,
+  documentHighlights,
+  executeCommand,
+  findCallPaths,
+  findLocations,
   findReferences,
+  findRelatedTests,
+  formatDocument,
+  formatEditedRanges,
   getDiagnostics,
+  getVirtualDocumentLanguage,
   inspectSymbol,
   logs,
   outlineSymbols,
   rename,
   searchSymbols,
+  selectionRanges,
+  semanticTokens,
+  serverCommands,
+  switchSourceHeader,
+  typeHierarchy,
+  virtualDocumentContents,
+  workspaceDiagnostics,
 } from '../../src/lsp/operations/operations.js';
 import { createOneBasedPosition, type LogMessage } from '../../src/types.js';
 import type { LspSession } from '../../src/runtime/lsp-session.js';
@@ -19,7 +42,7 @@ const TEST_FILE_PATH = '/test/workspace/src/test.ts';
 interface MockSessionOptions {
   requestImpl?: (method: string, params: unknown) => Promise<unknown>;
   diagnosticsStrategy?: 'push' | 'pull';
-  providers?: Array<{ id: string }>;
+  providers?: Array<{ id: string; workspaceDiagnostics?: boolean }>;
   logMessages?: LogMessage[];
   workspaceState?: {
     isReady: boolean;
@@ -27,12 +50,14 @@ interface MockSessionOptions {
     readyAt?: Date;
   };
   searchWarmupWindowMs?: number;
+  serverCapabilities?: ServerCapabilities;
 }
 function createMockSession(options: MockSessionOptions = {}): {
   session: LspSession;
   request: ReturnType<typeof vi.fn>;
   diagnosticsStore: { getDiagnostics: ReturnType<typeof vi.fn> };
   diagnosticProviderStore: {
+    getProviders: ReturnType<typeof vi.fn>;
     getProvidersForDocument: ReturnType<typeof vi.fn>;
   };
   windowLogStore: { getMessages: ReturnType<typeof vi.fn> };
@@ -48,6 +73,7 @@ function createMockSession(options: MockSessionOptions = {}): {
     getDiagnostics: vi.fn(() => []),
   };
   const diagnosticProviderStore = {
+    getProviders: vi.fn(() => options.providers ?? []),
     getProvidersForDocument: vi.fn(() => options.providers ?? []),
   };
   const windowLogStore = {
@@ -83,6 +109,7 @@ function createMockSession(options: MockSessionOptions = {}): {
     async (_name, _filePath, _position, _strategy, operation) => {
       const result = await operation({
         uri: TEST_URI,
+        serverCapabilities: options.serverCapabilities,
         request,
       });
 
@@ -102,6 +129,7 @@ function createMockSession(options: MockSessionOptions = {}): {
     async (_filePath, _strategy, operation) =>
       await operation({
         uri: TEST_URI,
+        serverCapabilities: options.serverCapabilities,
         request,
       });
 
@@ -116,6 +144,7 @@ function createMockSession(options: MockSessionOptions = {}): {
     stop: vi.fn(() => Promise.resolve()),
     restart: vi.fn(() => Promise.resolve()),
     request,
+    getServerCapabilities: vi.fn(() => null),
     getWorkspaceState: vi.fn(() => ({
       isReady: options.workspaceState?.isReady ?? true,
       isLoading: options.workspaceState?.isLoading ?? false,
@@ -136,8 +165,13 @@ function createMockSession(options: MockSessionOptions = {}): {
     getDiagnosticsStore: vi.fn(() => diagnosticsStore),
     getDiagnosticProviderStore: vi.fn(() => diagnosticProviderStore),
     getWindowLogStore: vi.fn(() => windowLogStore),
+    getProgressStore: vi.fn(() => ({ getActive: () => [] }) as never),
     executeWithCursorContext,
     executeWithDocumentLifecycle,
+    isDocumentStale: vi.fn(() => Promise.resolve(false)),
+    setDocumentOverlay: vi.fn(() =>
+      Promise.resolve({ ok: true as const, data: undefined })
+    ),
     claimDocument: vi.fn((filePath: string) => filePath),
     releaseDocument: vi.fn(() => null),
     listOwnedDocuments: vi.fn(() => []),
@@ -181,6 +215,7 @@ describe('LSP operations', () => {
           case 'textDocument/hover':
             return Promise.resolve({ contents: 'hover' });
           case 'textDocument/definition':
+          case 'textDocument/declaration':
           case 'textDocument/typeDefinition':
           case 'textDocument/implementation':
             return Promise.resolve([]);
@@ -201,7 +236,7 @@ describe('LSP operations', () => {
       textDocument: { uri: TEST_URI },
       position: { line: 4, character: 9 },
     });
-    expect(request).toHaveBeenCalledTimes(4);
+    expect(request).toHaveBeenCalledTimes(5);
   });
 
   it('findReferences uses the prepared LSP position and includeDeclaration=true', async () => {
@@ -232,6 +267,198 @@ describe('LSP operations', () => {
     });
   });
 
+  it('findReferences can leave out the declaration', async () => {
+    const { session, request } = createMockSession({
+      requestImpl: () => Promise.resolve([]),
+    });
+
+    await findReferences(
+      session,
+      {
+        filePath: TEST_FILE_PATH,
+        position: createOneBasedPosition(3, 4),
+        lspPosition: { line: 2, character: 3 },
+      },
+      { includeDeclaration: false }
+    );
+
+    expect(request).toHaveBeenCalledWith('textDocument/references', {
+      textDocument: { uri: TEST_URI },
+      position: { line: 2, character: 3 },
+      context: { includeDeclaration: false },
+    });
+  });
+
+  it('documentHighlights tags occurrences by access in document order', async () => {
+    const range = (line: number) => ({
+      start: { line, character: 4 },
+      end: { line, character: 9 },
+    });
+    const { session, request } = createMockSession({
+      serverCapabilities: { documentHighlightProvider: true },
+      requestImpl: () =>
+        Promise.resolve([
+          { range: range(6), kind: 2 },
+          { range: range(2), kind: 3 },
+          { range: range(9) },
+        ]),
+    });
+
+    const result = await documentHighlights(session, {
+      filePath: TEST_FILE_PATH,
+      position: createOneBasedPosition(3, 5),
+      lspPosition: { line: 2, character: 4 },
+    });
+
+    expect(result.ok && result.data.result).toEqual([
+      { range: range(2), access: 'write' },
+      { range: range(6), access: 'read' },
+      { range: range(9), access: 'text' },
+    ]);
+    expect(request).toHaveBeenCalledWith('textDocument/documentHighlight', {
+      textDocument: { uri: TEST_URI },
+      position: { line: 2, character: 4 },
+    });
+  });
+
+  it('classifyReferenceAccess tags references matching a highlight', async () => {
+    const reference = (line: number) => ({
+      uri: TEST_URI,
+      range: {
+        start: { line, character: 4 },
+        end: { line, character: 9 },
+      },
+      line: line + 1,
+      character: 5,
+    });
+    const { session, request } = createMockSession({
+      serverCapabilities: { documentHighlightProvider: true },
+      requestImpl: () =>
+        Promise.resolve([
+          { range: reference(2).range, kind: 3 },
+          { range: reference(6).range, kind: 2 },
+        ]),
+    });
+
+    const result = await classifyReferenceAccess(
+      session,
+      [reference(2), reference(6), reference(8)],
+      10
+    );
+
+    expect(result.ok && result.data).toEqual([
+      { ...reference(2), access: 'write' },
+      { ...reference(6), access: 'read' },
+      reference(8),
+    ]);
+    expect(request).toHaveBeenCalledTimes(1);
+  });
+
+  it('classifyReferenceAccess reports servers without highlights', async () => {
+    const { session } = createMockSession({ serverCapabilities: {} });
+
+    const result = await classifyReferenceAccess(
+      session,
+      [
+        {
+          uri: TEST_URI,
+          range: {
+            start: { line: 0, character: 0 },
+            end: { line: 0, character: 3 },
+          },
+          line: 1,
+          character: 1,
+        },
+      ],
+      10
+    );
+
+    expect(result.ok).toBe(false);
+    if (result.ok) {
+      return;
+    }
+    expect(result.error.message).toContain(
+      'typescript does not support document highlights'
+    );
+  });
+
+  describe('findLocations', () => {
+    const prepared = {
+      filePath: TEST_FILE_PATH,
+      position: createOneBasedPosition(5, 10),
+      lspPosition: { line: 4, character: 9 },
+    };
+
+    it('normalizes location links and returns 1-based locations', async () => {
+      const { session, request } = createMockSession({
+        requestImpl: () =>
+          Promise.resolve([
+            {
+              targetUri: 'file:///workspace/src/impl.ts',
+              targetRange: {
+                start: { line: 10, character: 0 },
+                end: { line: 20, character: 1 },
+              },
+              targetSelectionRange: {
+                start: { line: 10, character: 13 },
+                end: { line: 10, character: 21 },
+              },
+            },
+          ]),
+      });
+
+      const result = await findLocations(
+        session,
+        prepared,
+        'textDocument/implementation'
+      );
+
+      expect(request).toHaveBeenCalledWith('textDocument/implementation', {
+        textDocument: { uri: TEST_URI },
+        position: { line: 4, character: 9 },
+      });
+      expect(result.ok && result.data.result).toEqual([
+        {
+          uri: 'file:///workspace/src/impl.ts',
+          range: {
+            start: { line: 11, character: 14 },
+            end: { line: 11, character: 22 },
+          },
+        },
+      ]);
+    });
+
+    it('accepts a single location or none', async () => {
+      const single = createMockSession({
+        requestImpl: () =>
+          Promise.resolve({
+            uri: TEST_URI,
+            range: {
+              start: { line: 0, character: 0 },
+              end: { line: 0, character: 5 },
+            },
+          }),
+      });
+      const none = createMockSession({
+        requestImpl: () => Promise.resolve(null),
+      });
+
+      const singleResult = await findLocations(
+        single.session,
+        prepared,
+        'textDocument/declaration'
+      );
+      const noneResult = await findLocations(
+        none.session,
+        prepared,
+        'textDocument/declaration'
+      );
+
+      expect(singleResult.ok && singleResult.data.result).toHaveLength(1);
+      expect(noneResult.ok && noneResult.data.result).toEqual([]);
+    });
+  });
+
   describe('callHierarchy', () => {
     it('prepares call hierarchy items and resolves incoming/outgoing calls for each target', async () => {
       const preparedItems = [
@@ -511,137 +738,797 @@ describe('LSP operations', () => {
         expect(request).toHaveBeenCalledTimes(1);
       }
     );
-  });
+    it('follows outgoing calls past the first level without revisiting', async () => {
+      const item = (name: string, line: number) => ({
+        name,
+        kind: 12,
+        uri: TEST_URI,
+        range: {
+          start: { line, character: 0 },
+          end: { line: line + 3, character: 1 },
+        },
+        selectionRange: {
+          start: { line, character: 9 },
+          end: { line, character: 9 + name.length },
+        },
+      });
+      const items = {
+        main: item('main', 0),
+        parse: item('parse', 10),
+        write: item('write', 20),
+      };
+      const callees: Record<string, Array<keyof typeof items>> = {
+        main: ['parse'],
+        parse: ['write', 'main'],
+        write: [],
+      };
+      const { session, request } = createMockSession({
+        requestImpl: (method, params) => {
+          if (method === 'textDocument/prepareCallHierarchy') {
+            return Promise.resolve([items.main]);
+          }
+          if (method === 'callHierarchy/outgoingCalls') {
+            const { item } = params as { item: { name: string } };
+            return Promise.resolve(
+              (callees[item.name] ?? []).map((name) => ({
+                to: items[name],
+                fromRanges: [],
+              }))
+            );
+          }
+          return Promise.reject(new Error(`unexpected method ${method}`));
+        },
+      });
 
-  it('completion handles CompletionList responses through the scoped request API', async () => {
-    const { session, request } = createMockSession({
-      requestImpl: () =>
-        Promise.resolve({
-          isIncomplete: false,
-          items: [
-            {
-              label: 'testFunction',
-              kind: 3,
-              detail: 'void',
-              documentation: 'docs',
-            },
-          ],
-        }),
-    });
+      const result = await callHierarchy(
+        session,
+        {
+          filePath: TEST_FILE_PATH,
+          position: createOneBasedPosition(1, 10),
+          lspPosition: { line: 0, character: 9 },
+        },
+        'outgoing',
+        { depth: 3, maxExploredItems: 10 }
+      );
 
-    const result = await completion(session, {
-      filePath: TEST_FILE_PATH,
-      position: createOneBasedPosition(1, 1),
-      lspPosition: { line: 0, character: 0 },
-    });
+      if (!result.ok) {
+        throw new Error('expected call hierarchy result');
+      }
 
-    expect(result.ok).toBe(true);
-    expect(request).toHaveBeenCalledWith('textDocument/completion', {
-      textDocument: { uri: TEST_URI },
-      position: { line: 0, character: 0 },
-      context: { triggerKind: 1 },
+      const [parse] = result.data.result.targets[0]?.outgoingTree ?? [];
+      expect(parse?.item.name).toBe('parse');
+      expect(parse?.children?.map((node) => node.item.name)).toEqual([
+        'write',
+        'main',
+      ]);
+      // main is the target itself, so it isn't expanded again
+      expect(parse?.children?.[1]?.children).toBeNull();
+      expect(parse?.children?.[0]?.children).toEqual([]);
+      expect(result.data.result.limitReached).toBe(false);
+      expect(request).toHaveBeenCalledTimes(4);
     });
   });
 
-  it('searchSymbols uses the session-level request API', async () => {
-    const { session, request } = createMockSession({
-      requestImpl: () =>
-        Promise.resolve([
-          {
-            name: 'TestClass',
-            kind: 5,
-            location: {
-              uri: TEST_URI,
-              range: {
-                start: { line: 0, character: 0 },
-                end: { line: 0, character: 10 },
-              },
-            },
-          },
-        ]),
-    });
-
-    const result = await searchSymbols(session, {
-      query: 'TestClass',
-    });
+  describe('findCallPaths', () => {
+    function callItem(name: string, line: number) {
+      return {
+        name,
+        kind: 12,
+        uri: TEST_URI,
+        range: {
+          start: { line, character: 0 },
+          end: { line: line + 3, character: 1 },
+        },
+        selectionRange: {
+          start: { line, character: 9 },
+          end: { line, character: 9 + name.length },
+        },
+      };
+    }
 
-    expect(result.ok).toBe(true);
-    expect(request).toHaveBeenCalledWith('workspace/symbol', {
-      query: 'TestClass',
-    });
-  });
+    const items = {
+      main: callItem('main', 0),
+      parse: callItem('parse', 10),
+      load: callItem('load', 20),
+      write: callItem('write', 30),
+      unrelated: callItem('unrelated', 40),
+    };
+    const callGraph: Record<string, Array<keyof typeof items>> = {
+      main: ['parse', 'load'],
+      parse: ['write'],
+      load: ['parse', 'write'],
+      write: ['main'],
+      unrelated: [],
+    };
 
-  it('searchSymbols retries empty results during the search warm-up window', async () => {
-    vi.useFakeTimers();
+    function createGraphSession() {
+      return createMockSession({
+        requestImpl: (method, params) => {
+          const { position, item } = params as {
+            position?: { line: number };
+            item?: { name: keyof typeof items };
+          };
 
-    try {
-      vi.setSystemTime(new Date('2026-04-10T07:31:13.000Z'));
+          if (method === 'textDocument/prepareCallHierarchy') {
+            const found = Object.values(items).find(
+              (candidate) => candidate.range.start.line === position?.line
+            );
+            return Promise.resolve(found ? [found] : []);
+          }
 
-      const requestResponses = [
-        [],
-        [
-          {
-            name: 'registerAllTools',
-            kind: 12,
-            location: {
-              uri: TEST_URI,
-              range: {
-                start: { line: 20, character: 0 },
-                end: { line: 20, character: 16 },
-              },
-            },
-          },
-        ],
-      ];
+          if (method === 'callHierarchy/outgoingCalls' && item) {
+            const line = items[item.name].range.start.line + 1;
+            return Promise.resolve(
+              (callGraph[item.name] ?? []).map((name) => ({
+                to: items[name],
+                fromRanges: [
+                  {
+                    start: { line, character: 2 },
+                    end: { line, character: 8 },
+                  },
+                ],
+              }))
+            );
+          }
 
-      const { session, request } = createMockSession({
-        requestImpl: () => Promise.resolve(requestResponses.shift() ?? []),
-        workspaceState: {
-          isReady: true,
-          isLoading: false,
-          readyAt: new Date('2026-04-10T07:31:12.500Z'),
+          return Promise.reject(new Error(`unexpected method ${method}`));
         },
-        searchWarmupWindowMs: 5000,
       });
+    }
 
-      const resultPromise = searchSymbols(session, {
-        query: 'registerAllTools',
+    function position(line: number) {
+      return {
+        filePath: TEST_FILE_PATH,
+        position: createOneBasedPosition(line + 1, 10),
+        lspPosition: { line, character: 9 },
+      };
+    }
+
+    it('returns the shortest paths through distinct callers of the target', async () => {
+      const { session } = createGraphSession();
+
+      const result = await findCallPaths(session, position(0), position(30), {
+        maxDepth: 5,
+        maxPaths: 3,
+        maxExploredItems: 100,
       });
 
-      await vi.runAllTimersAsync();
-      const result = await resultPromise;
+      if (!result.ok) {
+        throw new Error('expected call path result');
+      }
 
-      expect(result.ok).toBe(true);
-      expect(result.ok && result.data).toHaveLength(1);
-      expect(request).toHaveBeenCalledTimes(2);
-      expect(request).toHaveBeenNthCalledWith(1, 'workspace/symbol', {
-        query: 'registerAllTools',
+      const paths = result.data.result.paths.map((path) =>
+        path.map((step) => step.item.name)
+      );
+      expect(paths).toEqual([
+        ['main', 'parse', 'write'],
+        ['main', 'load', 'write'],
+      ]);
+      expect(result.data.result.paths[0]?.[1]?.callSites).toEqual([
+        {
+          start: { line: 1, character: 2 },
+          end: { line: 1, character: 8 },
+        },
+      ]);
+      expect(result.data.result.exploredItems).toBe(3);
+      expect(result.data.result.limitReached).toBe(false);
+    });
+
+    it('stops at the depth limit and the requested number of paths', async () => {
+      const { session } = createGraphSession();
+
+      const shallow = await findCallPaths(session, position(0), position(30), {
+        maxDepth: 1,
+        maxPaths: 3,
+        maxExploredItems: 100,
       });
-      expect(request).toHaveBeenNthCalledWith(2, 'workspace/symbol', {
-        query: 'registerAllTools',
+      const single = await findCallPaths(session, position(0), position(30), {
+        maxDepth: 5,
+        maxPaths: 1,
+        maxExploredItems: 100,
       });
-    } finally {
-      vi.useRealTimers();
-    }
-  });
 
-  it('searchSymbols does not retry empty results after the warm-up window expires', async () => {
-    vi.useFakeTimers();
+      if (!shallow.ok || !single.ok) {
+        throw new Error('expected call path results');
+      }
 
-    try {
-      vi.setSystemTime(new Date('2026-04-10T07:31:20.000Z'));
+      expect(shallow.data.result.paths).toEqual([]);
+      expect(shallow.data.result.exploredItems).toBe(1);
+      expect(single.data.result.paths).toHaveLength(1);
+    });
 
-      const { session, request } = createMockSession({
-        requestImpl: () => Promise.resolve([]),
-        workspaceState: {
-          isReady: true,
-          isLoading: false,
-          readyAt: new Date('2026-04-10T07:31:10.000Z'),
-        },
-        searchWarmupWindowMs: 5000,
+    it('reports when the exploration budget runs out', async () => {
+      const { session } = createGraphSession();
+
+      const result = await findCallPaths(session, position(0), position(40), {
+        maxDepth: 5,
+        maxPaths: 3,
+        maxExploredItems: 2,
       });
 
-      const result = await searchSymbols(session, {
+      if (!result.ok) {
+        throw new Error('expected call path result');
+      }
+
+      expect(result.data.result.paths).toEqual([]);
+      expect(result.data.result.exploredItems).toBe(2);
+      expect(result.data.result.limitReached).toBe(true);
+    });
+  });
+
+  describe('typeHierarchy', () => {
+    function typeItem(name: string, line: number) {
+      return {
+        name,
+        kind: 5,
+        uri: TEST_URI,
+        range: {
+          start: { line, character: 0 },
+          end: { line: line + 3, character: 1 },
+        },
+        selectionRange: {
+          start: { line, character: 6 },
+          end: { line, character: 6 + name.length },
+        },
+      };
+    }
+
+    const types = {
+      Animal: typeItem('Animal', 0),
+      Dog: typeItem('Dog', 10),
+      Puppy: typeItem('Puppy', 20),
+    };
+    const supertypes: Record<string, Array<keyof typeof types>> = {
+      Animal: [],
+      Dog: ['Animal'],
+      Puppy: ['Dog'],
+    };
+
+    function createTypeSession() {
+      return createMockSession({
+        requestImpl: (method, params) => {
+          const { item } = params as { item?: { name: keyof typeof types } };
+
+          if (method === 'textDocument/prepareTypeHierarchy') {
+            return Promise.resolve([types.Puppy]);
+          }
+          if (method === 'typeHierarchy/supertypes' && item) {
+            return Promise.resolve(
+              supertypes[item.name]?.map((name) => types[name]) ?? []
+            );
+          }
+          if (method === 'typeHierarchy/subtypes') {
+            return Promise.resolve([]);
+          }
+
+          return Promise.reject(new Error(`unexpected method ${method}`));
+        },
+      });
+    }
+
+    const prepared = {
+      filePath: TEST_FILE_PATH,
+      position: createOneBasedPosition(21, 7),
+      lspPosition: { line: 20, character: 6 },
+    };
+
+    it('walks up the inheritance chain to the requested depth', async () => {
+      const { session } = createTypeSession();
+
+      const result = await typeHierarchy(session, prepared, 'both', {
+        depth: 3,
+        maxExploredItems: 10,
+      });
+
+      if (!result.ok) {
+        throw new Error('expected type hierarchy result');
+      }
+
+      const [target] = result.data.result.targets;
+      expect(target?.item.name).toBe('Puppy');
+      expect(target?.subtypes).toEqual([]);
+      const [dog] = target?.supertypes ?? [];
+      expect(dog?.item.name).toBe('Dog');
+      expect(dog?.children?.map((node) => node.item.name)).toEqual([
+        'Animal',
+      ]);
+      expect(result.data.result.limitReached).toBe(false);
+    });
+
+    it('stops at the depth limit and skips unrequested directions', async () => {
+      const { session, request } = createTypeSession();
+
+      const result = await typeHierarchy(session, prepared, 'supertypes', {
+        depth: 1,
+        maxExploredItems: 10,
+      });
+
+      if (!result.ok) {
+        throw new Error('expected type hierarchy result');
+      }
+
+      const [target] = result.data.result.targets;
+      expect(target?.subtypes).toBeNull();
+      expect(target?.supertypes?.[0]?.children).toBeNull();
+      expect(request).not.toHaveBeenCalledWith(
+        'typeHierarchy/subtypes',
+        expect.anything()
+      );
+    });
+  });
+
+  describe('semanticTokens', () => {
+    it('decodes tokens with the server legend and applies deltas', async () => {
+      const directory = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-'));
+      const filePath = path.join(directory, 'test.ts');
+      fs.writeFileSync(filePath, 'class Foo {\n  bar() {}\n}\n');
+
+      const { session, request } = createMockSession({
+        serverCapabilities: {
+          semanticTokensProvider: {
+            legend: {
+              tokenTypes: ['class', 'method'],
+              tokenModifiers: ['declaration', 'static'],
+            },
+            full: { delta: true },
+          },
+        },
+        requestImpl: (method) => {
+          switch (method) {
+            case 'textDocument/semanticTokens/full':
+              return Promise.resolve({
+                resultId: '1',
+                data: [0, 6, 3, 0, 1, 1, 2, 3, 1, 1],
+              });
+            case 'textDocument/semanticTokens/full/delta':
+              return Promise.resolve({
+                resultId: '2',
+                edits: [{ start: 9, deleteCount: 1, data: [3] }],
+              });
+            default:
+              return Promise.reject(new Error(`unexpected method ${method}`));
+          }
+        },
+      });
+
+      try {
+        const first = await semanticTokens(session, { filePath });
+        const second = await semanticTokens(session, { filePath });
+
+        expect(first.ok && first.data).toEqual([
+          {
+            line: 0,
+            character: 6,
+            length: 3,
+            tokenType: 'class',
+            tokenModifiers: ['declaration'],
+            text: 'Foo',
+          },
+          {
+            line: 1,
+            character: 2,
+            length: 3,
+            tokenType: 'method',
+            tokenModifiers: ['declaration'],
+            text: 'bar',
+          },
+        ]);
+        expect(request).toHaveBeenCalledWith(
+          'textDocument/semanticTokens/full/delta',
+          { textDocument: { uri: TEST_URI }, previousResultId: '1' }
+        );
+        expect(second.ok && second.data[1]?.tokenModifiers).toEqual([
+          'declaration',
+          'static',
+        ]);
+      } finally {
+        fs.rmSync(directory, { recursive: true, force: true });
+      }
+    });
+  });
+
+  describe('findRelatedTests', () => {
+    const TEST_SPEC_URI = 'file:///test/workspace/test/parse.test.ts';
+
+    function callItem(name: string, uri: string, line: number) {
+      return {
+        name,
+        kind: 12,
+        uri,
+        range: {
+          start: { line, character: 0 },
+          end: { line: line + 3, character: 1 },
+        },
+        selectionRange: {
+          start: { line, character: 9 },
+          end: { line, character: 9 + name.length },
+        },
+      };
+    }
+
+    const parse = callItem('parse', TEST_URI, 10);
+    const helper = callItem('helper', TEST_URI, 20);
+    const direct = callItem('parsesInput', TEST_SPEC_URI, 5);
+    const indirect = callItem('usesHelper', TEST_SPEC_URI, 15);
+    const incoming: Record<string, Array<typeof parse>> = {
+      parse: [helper, direct],
+      helper: [indirect, parse],
+      parsesInput: [],
+      usesHelper: [],
+    };
+
+    function createTestSession() {
+      return createMockSession({
+        requestImpl: (method, params) => {
+          if (method === 'textDocument/references') {
+            return Promise.resolve([
+              { uri: TEST_SPEC_URI, range: direct.selectionRange },
+              { uri: TEST_URI, range: helper.selectionRange },
+            ]);
+          }
+          if (method === 'textDocument/prepareCallHierarchy') {
+            return Promise.resolve([parse]);
+          }
+          if (method === 'callHierarchy/incomingCalls') {
+            const { item } = params as { item: { name: string } };
+            return Promise.resolve(
+              (incoming[item.name] ?? []).map((from) => ({
+                from,
+                fromRanges: [],
+              }))
+            );
+          }
+          return Promise.reject(new Error(`unexpected method ${method}`));
+        },
+      });
+    }
+
+    const prepared = {
+      filePath: TEST_FILE_PATH,
+      position: createOneBasedPosition(11, 10),
+      lspPosition: { line: 10, character: 9 },
+    };
+    const isTestFile = (uri: string) => uri === TEST_SPEC_URI;
+
+    it('finds test references and test callers through non-test functions', async () => {
+      const { session } = createTestSession();
+
+      const result = await findRelatedTests(session, prepared, {
+        maxDepth: 3,
+        maxExploredItems: 100,
+        isTestFile,
+      });
+
+      if (!result.ok) {
+        throw new Error('expected related tests result');
+      }
+
+      expect(result.data.result.references).toEqual([
+        { uri: TEST_SPEC_URI, range: direct.selectionRange },
+      ]);
+      expect(
+        result.data.result.callers.map((caller) => [
+          caller.item.name,
+          caller.hops,
+          caller.via.map((item) => item.name),
+        ])
+      ).toEqual([
+        ['parsesInput', 1, []],
+        ['usesHelper', 2, ['helper']],
+      ]);
+      expect(result.data.result.exploredItems).toBe(2);
+    });
+
+    it('only returns direct test callers at depth 1', async () => {
+      const { session } = createTestSession();
+
+      const result = await findRelatedTests(session, prepared, {
+        maxDepth: 1,
+        maxExploredItems: 100,
+        isTestFile,
+      });
+
+      if (!result.ok) {
+        throw new Error('expected related tests result');
+      }
+
+      expect(
+        result.data.result.callers.map((caller) => caller.item.name)
+      ).toEqual(['parsesInput']);
+    });
+  });
+
+  it('completion handles CompletionList responses through the scoped request API', async () => {
+    const { session, request } = createMockSession({
+      requestImpl: () =>
+        Promise.resolve({
+          isIncomplete: false,
+          items: [
+            {
+              label: 'testFunction',
+              kind: 3,
+              detail: 'void',
+              documentation: 'docs',
+            },
+          ],
+        }),
+    });
+
+    const result = await completion(session, {
+      filePath: TEST_FILE_PATH,
+      position: createOneBasedPosition(1, 1),
+      lspPosition: { line: 0, character: 0 },
+    });
+
+    expect(result.ok).toBe(true);
+    expect(request).toHaveBeenCalledWith('textDocument/completion', {
+      textDocument: { uri: TEST_URI },
+      position: { line: 0, character: 0 },
+      context: { triggerKind: 1 },
+    });
+  });
+
+  it('completion ranks preselected items first and resolves the top ones', async () => {
+    const { session, request } = createMockSession({
+      serverCapabilities: { completionProvider: { resolveProvider: true } },
+      requestImpl: (method, params) => {
+        if (method === 'completionItem/resolve') {
+          return Promise.resolve({
+            ...(params as object),
+            documentation: 'Parses the input',
+            additionalTextEdits: [
+              {
+                range: {
+                  start: { line: 0, character: 0 },
+                  end: { line: 0, character: 0 },
+                },
+                newText: "import { parse } from './parse';\n",
+              },
+            ],
+          });
+        }
+        return Promise.resolve({
+          isIncomplete: false,
+          items: [
+            { label: 'alpha', sortText: '1' },
+            { label: 'parse', sortText: '2', preselect: true },
+            { label: 'beta', sortText: '0' },
+          ],
+        });
+      },
+    });
+
+    const result = await completion(
+      session,
+      {
+        filePath: TEST_FILE_PATH,
+        position: createOneBasedPosition(1, 1),
+        lspPosition: { line: 0, character: 0 },
+      },
+      { resolve: 1 }
+    );
+
+    expect(result.ok).toBe(true);
+    if (!result.ok) {
+      return;
+    }
+    const items = result.data.result;
+    expect(items.map((item) => item.label)).toEqual(['parse', 'beta', 'alpha']);
+    expect(items[0]).toMatchObject({
+      resolved: true,
+      documentation: 'Parses the input',
+      additionalTextEdits: [
+        {
+          range: {
+            start: { line: 1, character: 1 },
+            end: { line: 1, character: 1 },
+          },
+          newText: "import { parse } from './parse';\n",
+        },
+      ],
+    });
+    expect(items[1]?.resolved).toBeUndefined();
+    expect(request).toHaveBeenCalledTimes(2);
+  });
+
+  it('codeActions sends the diagnostics overlapping the range', async () => {
+    const { session, request, diagnosticsStore } = createMockSession({
+      requestImpl: () =>
+        Promise.resolve([{ title: 'Add missing import', kind: 'quickfix' }]),
+    });
+    const overlapping = {
+      range: {
+        start: { line: 2, character: 4 },
+        end: { line: 2, character: 9 },
+      },
+      message: "Cannot find name 'parse'.",
+      data: { fixId: 'fixMissingImport' },
+    };
+    diagnosticsStore.getDiagnostics.mockReturnValue([
+      overlapping,
+      {
+        range: {
+          start: { line: 7, character: 0 },
+          end: { line: 7, character: 3 },
+        },
+        message: 'Unused variable',
+      },
+    ]);
+
+    const range = {
+      start: { line: 2, character: 6 },
+      end: { line: 2, character: 6 },
+    };
+    const result = await codeActions(
+      session,
+      {
+        filePath: TEST_FILE_PATH,
+        position: createOneBasedPosition(3, 7),
+        lspPosition: range.start,
+      },
+      range,
+      ['quickfix']
+    );
+
+    expect(result.ok && result.data.result).toEqual([
+      { title: 'Add missing import', kind: 'quickfix' },
+    ]);
+    expect(request).toHaveBeenCalledWith('textDocument/codeAction', {
+      textDocument: { uri: TEST_URI },
+      range,
+      context: {
+        diagnostics: [overlapping],
+        triggerKind: 1,
+        only: ['quickfix'],
+      },
+    });
+  });
+
+  it('applyCodeAction resolves actions without edits into file changes', async () => {
+    const edit = {
+      range: {
+        start: { line: 0, character: 0 },
+        end: { line: 0, character: 0 },
+      },
+      newText: "import { parse } from './parser';\n",
+    };
+    const { session, request } = createMockSession({
+      serverCapabilities: { codeActionProvider: { resolveProvider: true } },
+      requestImpl: (_method, params) =>
+        Promise.resolve({
+          ...(params as object),
+          edit: { changes: { [TEST_URI]: [edit] } },
+        }),
+    });
+    const action = {
+      title: 'Add missing import',
+      kind: 'quickfix',
+      data: { fixId: 'fixMissingImport' },
+    };
+
+    const result = await applyCodeAction(
+      session,
+      { filePath: TEST_FILE_PATH },
+      action
+    );
+
+    expect(request).toHaveBeenCalledWith('codeAction/resolve', action);
+    expect(result.ok && result.data).toEqual({
+      changes: {
+        [TEST_URI]: [
+          {
+            ...edit,
+            startLine: 1,
+            startCharacter: 1,
+            endLine: 1,
+            endCharacter: 1,
+          },
+        ],
+      },
+      skippedOperations: 0,
+      executedCommands: [],
+    });
+  });
+
+  it('searchSymbols uses the session-level request API', async () => {
+    const { session, request } = createMockSession({
+      requestImpl: () =>
+        Promise.resolve([
+          {
+            name: 'TestClass',
+            kind: 5,
+            location: {
+              uri: TEST_URI,
+              range: {
+                start: { line: 0, character: 0 },
+                end: { line: 0, character: 10 },
+              },
+            },
+          },
+        ]),
+    });
+
+    const result = await searchSymbols(session, {
+      query: 'TestClass',
+    });
+
+    expect(result.ok).toBe(true);
+    expect(request).toHaveBeenCalledWith('workspace/symbol', {
+      query: 'TestClass',
+    });
+  });
+
+  it('searchSymbols retries empty results during the search warm-up window', async () => {
+    vi.useFakeTimers();
+
+    try {
+      vi.setSystemTime(new Date('2026-04-10T07:31:13.000Z'));
+
+      const requestResponses = [
+        [],
+        [
+          {
+            name: 'registerAllTools',
+            kind: 12,
+            location: {
+              uri: TEST_URI,
+              range: {
+                start: { line: 20, character: 0 },
+                end: { line: 20, character: 16 },
+              },
+            },
+          },
+        ],
+      ];
+
+      const { session, request } = createMockSession({
+        requestImpl: () => Promise.resolve(requestResponses.shift() ?? []),
+        workspaceState: {
+          isReady: true,
+          isLoading: false,
+          readyAt: new Date('2026-04-10T07:31:12.500Z'),
+        },
+        searchWarmupWindowMs: 5000,
+      });
+
+      const resultPromise = searchSymbols(session, {
+        query: 'registerAllTools',
+      });
+
+      await vi.runAllTimersAsync();
+      const result = await resultPromise;
+
+      expect(result.ok).toBe(true);
+      expect(result.ok && result.data).toHaveLength(1);
+      expect(request).toHaveBeenCalledTimes(2);
+      expect(request).toHaveBeenNthCalledWith(1, 'workspace/symbol', {
+        query: 'registerAllTools',
+      });
+      expect(request).toHaveBeenNthCalledWith(2, 'workspace/symbol', {
+        query: 'registerAllTools',
+      });
+    } finally {
+      vi.useRealTimers();
+    }
+  });
+
+  it('searchSymbols does not retry empty results after the warm-up window expires', async () => {
+    vi.useFakeTimers();
+
+    try {
+      vi.setSystemTime(new Date('2026-04-10T07:31:20.000Z'));
+
+      const { session, request } = createMockSession({
+        requestImpl: () => Promise.resolve([]),
+        workspaceState: {
+          isReady: true,
+          isLoading: false,
+          readyAt: new Date('2026-04-10T07:31:10.000Z'),
+        },
+        searchWarmupWindowMs: 5000,
+      });
+
+      const result = await searchSymbols(session, {
         query: 'MissingSymbol',
       });
 
@@ -786,6 +1673,471 @@ describe('LSP operations', () => {
     });
   });
 
+  it('getDiagnostics falls back to published diagnostics with quick fixes', async () => {
+    const diagnostic = {
+      code: 2304,
+      codeDescription: { href: 'https://example.com/2304' },
+      message: "Cannot find name 'foo'.",
+      severity: 1,
+      range: {
+        start: { line: 3, character: 4 },
+        end: { line: 3, character: 7 },
+      },
+      relatedInformation: [
+        {
+          location: {
+            uri: 'file:///test/workspace/other.ts',
+            range: {
+              start: { line: 0, character: 0 },
+              end: { line: 0, character: 3 },
+            },
+          },
+          message: 'Did you mean this?',
+        },
+      ],
+    };
+    const { session, request, diagnosticsStore } = createMockSession({
+      serverCapabilities: { codeActionProvider: true },
+      requestImpl: () =>
+        Promise.resolve([
+          { title: "Add import from './foo'", kind: 'quickfix' },
+          { title: 'Disabled fix', disabled: { reason: 'no' } },
+        ]),
+    });
+    diagnosticsStore.getDiagnostics.mockReturnValue([diagnostic]);
+
+    const result = await getDiagnostics(
+      session,
+      { filePath: TEST_FILE_PATH },
+      { fixes: true }
+    );
+
+    expect(result.ok).toBe(true);
+    if (!result.ok) {
+      return;
+    }
+    expect(result.data).toEqual([
+      {
+        code: '2304',
+        codeDescription: 'https://example.com/2304',
+        message: "Cannot find name 'foo'.",
+        severity: 1,
+        range: diagnostic.range,
+        source: 'unknown',
+        relatedInformation: [
+          {
+            uri: 'file:///test/workspace/other.ts',
+            range: diagnostic.relatedInformation[0]!.location.range,
+            message: 'Did you mean this?',
+          },
+        ],
+        fixes: ["Add import from './foo'"],
+      },
+    ]);
+    expect(request).toHaveBeenCalledTimes(1);
+    expect(request).toHaveBeenCalledWith(
+      'textDocument/codeAction',
+      expect.objectContaining({
+        range: diagnostic.range,
+        context: expect.objectContaining({
+          diagnostics: [diagnostic],
+          only: ['quickfix'],
+        }),
+      })
+    );
+  });
+
+  it('workspaceDiagnostics pulls full reports from workspace providers', async () => {
+    const range = {
+      start: { line: 0, character: 0 },
+      end: { line: 0, character: 1 },
+    };
+    const { session, request } = createMockSession({
+      providers: [{ id: 'roslyn', workspaceDiagnostics: true }],
+      requestImpl: () =>
+        Promise.resolve({
+          items: [
+            {
+              kind: 'full',
+              uri: 'file:///test/workspace/a.cs',
+              version: null,
+              items: [{ code: 'CS0103', message: 'missing', range }],
+            },
+            {
+              kind: 'unchanged',
+              uri: 'file:///test/workspace/b.cs',
+              version: null,
+              resultId: '1',
+            },
+          ],
+        }),
+    });
+
+    const result = await workspaceDiagnostics(session);
+
+    expect(result.ok).toBe(true);
+    if (!result.ok) {
+      return;
+    }
+    expect(request).toHaveBeenCalledWith('workspace/diagnostic', {
+      identifier: 'roslyn',
+      previousResultIds: [],
+    });
+    expect([...result.data!.entries()]).toEqual([
+      [
+        'file:///test/workspace/a.cs',
+        [
+          {
+            code: 'CS0103',
+            message: 'missing',
+            severity: 1,
+            range,
+            source: 'roslyn',
+          },
+        ],
+      ],
+    ]);
+  });
+
+  it('workspaceDiagnostics returns null without workspace providers', async () => {
+    const { session, request } = createMockSession({
+      providers: [{ id: 'roslyn' }],
+    });
+
+    const result = await workspaceDiagnostics(session);
+
+    expect(result).toEqual({ ok: true, data: null });
+    expect(request).not.toHaveBeenCalled();
+  });
+
+  it('formatDocument requests range formatting and converts the edits', async () => {
+    const range = {
+      start: { line: 2, character: 0 },
+      end: { line: 4, character: 1 },
+    };
+    const { session, request } = createMockSession({
+      serverCapabilities: { documentRangeFormattingProvider: true },
+      requestImpl: () => Promise.resolve([{ range, newText: '  a();\n' }]),
+    });
+
+    const result = await formatDocument(
+      session,
+      { filePath: TEST_FILE_PATH },
+      { tabSize: 2, insertSpaces: true },
+      range
+    );
+
+    expect(result).toEqual({
+      ok: true,
+      data: {
+        [TEST_URI]: [
+          {
+            range,
+            newText: '  a();\n',
+            startLine: 3,
+            startCharacter: 1,
+            endLine: 5,
+            endCharacter: 2,
+          },
+        ],
+      },
+    });
+    expect(request).toHaveBeenCalledWith('textDocument/rangeFormatting', {
+      textDocument: { uri: TEST_URI },
+      range,
+      options: { tabSize: 2, insertSpaces: true },
+    });
+  });
+
+  it('formatDocument reports servers without a formatter', async () => {
+    const { session, request } = createMockSession({ serverCapabilities: {} });
+
+    const result = await formatDocument(
+      session,
+      { filePath: TEST_FILE_PATH },
+      { tabSize: 2, insertSpaces: true }
+    );
+
+    expect(result.ok).toBe(false);
+    if (result.ok) {
+      return;
+    }
+    expect(result.error.message).toContain(
+      'typescript does not support formatting'
+    );
+    expect(request).not.toHaveBeenCalled();
+  });
+
+  it('formatEditedRanges formats each range and drops overlapping edits', async () => {
+    const first = {
+      start: { line: 1, character: 0 },
+      end: { line: 2, character: 5 },
+    };
+    const second = {
+      start: { line: 6, character: 0 },
+      end: { line: 6, character: 9 },
+    };
+    const { session, request } = createMockSession({
+      serverCapabilities: { documentRangeFormattingProvider: true },
+      requestImpl: (_method, params) =>
+        Promise.resolve(
+          (params as { range: typeof first }).range === first
+            ? [{ range: first, newText: 'a();\n  b();' }]
+            : [
+                { range: second, newText: 'c();' },
+                { range: first, newText: 'overlapping' },
+              ]
+        ),
+    });
+
+    const result = await formatEditedRanges(
+      session,
+      { filePath: TEST_FILE_PATH },
+      { tabSize: 2, insertSpaces: true },
+      [first, second],
+      []
+    );
+
+    expect(request).toHaveBeenCalledTimes(2);
+    expect(result.ok).toBe(true);
+    if (!result.ok) {
+      return;
+    }
+    expect(result.data[TEST_URI]?.map((edit) => edit.newText)).toEqual([
+      'a();\n  b();',
+      'c();',
+    ]);
+  });
+
+  it('formatEditedRanges falls back to on-type formatting at triggers', async () => {
+    const { session, request } = createMockSession({
+      serverCapabilities: {
+        documentOnTypeFormattingProvider: {
+          firstTriggerCharacter: '}',
+          moreTriggerCharacter: [';'],
+        },
+      },
+      requestImpl: () => Promise.resolve([]),
+    });
+
+    const result = await formatEditedRanges(
+      session,
+      { filePath: TEST_FILE_PATH },
+      { tabSize: 4, insertSpaces: true },
+      [{ start: { line: 0, character: 0 }, end: { line: 2, character: 1 } }],
+      ['fn f() {', '  g();  ', '}']
+    );
+
+    expect(result).toEqual({ ok: true, data: {} });
+    expect(request.mock.calls).toEqual([
+      [
+        'textDocument/onTypeFormatting',
+        {
+          textDocument: { uri: TEST_URI },
+          position: { line: 1, character: 6 },
+          ch: ';',
+          options: { tabSize: 4, insertSpaces: true },
+        },
+      ],
+      [
+        'textDocument/onTypeFormatting',
+        {
+          textDocument: { uri: TEST_URI },
+          position: { line: 2, character: 1 },
+          ch: '}',
+          options: { tabSize: 4, insertSpaces: true },
+        },
+      ],
+    ]);
+  });
+
+  it('selectionRanges lists enclosing ranges innermost first without repeats', async () => {
+    const range = (start: number, end: number, endCharacter: number) => ({
+      start: { line: start, character: start === end ? 4 : 0 },
+      end: { line: end, character: endCharacter },
+    });
+    const { session, request } = createMockSession({
+      serverCapabilities: { selectionRangeProvider: true },
+      requestImpl: () =>
+        Promise.resolve([
+          {
+            range: range(2, 2, 9),
+            parent: {
+              range: range(1, 3, 1),
+              parent: {
+                range: range(1, 3, 1),
+                parent: { range: range(0, 4, 1) },
+              },
+            },
+          },
+        ]),
+    });
+
+    const result = await selectionRanges(session, {
+      filePath: TEST_FILE_PATH,
+      position: createOneBasedPosition(3, 5),
+      lspPosition: { line: 2, character: 4 },
+    });
+
+    expect(result.ok && result.data.result).toEqual([
+      range(2, 2, 9),
+      range(1, 3, 1),
+      range(0, 4, 1),
+    ]);
+    expect(request).toHaveBeenCalledWith('textDocument/selectionRange', {
+      textDocument: { uri: TEST_URI },
+      positions: [{ line: 2, character: 4 }],
+    });
+  });
+
+  it('selectionRanges reports servers without selection ranges', async () => {
+    const { session, request } = createMockSession({ serverCapabilities: {} });
+
+    const result = await selectionRanges(session, {
+      filePath: TEST_FILE_PATH,
+      position: createOneBasedPosition(3, 5),
+      lspPosition: { line: 2, character: 4 },
+    });
+
+    expect(result.ok).toBe(false);
+    expect(request).not.toHaveBeenCalled();
+  });
+
+  it('codeLenses resolves lenses without commands and sorts them', async () => {
+    const lens = (line: number, title?: string) => ({
+      range: {
+        start: { line, character: 0 },
+        end: { line, character: 8 },
+      },
+      ...(title ? { command: { title, command: 'run.test' } } : {}),
+    });
+    const { session, request } = createMockSession({
+      serverCapabilities: {
+        codeLensProvider: { resolveProvider: true },
+        executeCommandProvider: { commands: ['run.test'] },
+      },
+      requestImpl: (method) =>
+        method === 'textDocument/codeLens'
+          ? Promise.resolve([lens(9, 'Run test'), lens(2)])
+          : Promise.resolve(lens(2, '3 references')),
+    });
+
+    const result = await codeLenses(session, { filePath: TEST_FILE_PATH });
+
+    expect(result).toEqual({
+      ok: true,
+      data: {
+        lenses: [lens(2, '3 references'), lens(9, 'Run test')],
+        serverCommands: ['run.test'],
+      },
+    });
+    expect(request).toHaveBeenCalledWith('codeLens/resolve', lens(2));
+    expect(request).toHaveBeenCalledTimes(2);
+  });
+
+  it('executeCommand rejects commands the server does not list', async () => {
+    const { session, request } = createMockSession({
+      serverCapabilities: { executeCommandProvider: { commands: ['a'] } },
+    });
+
+    const result = await executeCommand(
+      session,
+      { filePath: TEST_FILE_PATH },
+      { title: 'Show references', command: 'editor.action.showReferences' }
+    );
+
+    expect(result.ok).toBe(false);
+    if (result.ok) {
+      return;
+    }
+    expect(result.error.message).toContain(
+      'typescript does not support command editor.action.showReferences'
+    );
+    expect(request).not.toHaveBeenCalled();
+  });
+
+  it('serverCommands lists the registered commands sorted', async () => {
+    const { session } = createMockSession({
+      serverCapabilities: {
+        executeCommandProvider: {
+          commands: ['gopls.tidy', 'gopls.gc_details'],
+        },
+      },
+    });
+
+    const result = await serverCommands(session, { filePath: TEST_FILE_PATH });
+
+    expect(result).toEqual({
+      ok: true,
+      data: ['gopls.gc_details', 'gopls.tidy'],
+    });
+  });
+
+  it('switchSourceHeader returns the paired file from clangd', async () => {
+    const { session, request } = createMockSession({
+      requestImpl: () => Promise.resolve('file:///test/workspace/test.h'),
+    });
+
+    const result = await switchSourceHeader(session, {
+      filePath: TEST_FILE_PATH,
+    });
+
+    expect(request).toHaveBeenCalledWith('textDocument/switchSourceHeader', {
+      uri: TEST_URI,
+    });
+    expect(result).toEqual({
+      ok: true,
+      data: 'file:///test/workspace/test.h',
+    });
+  });
+
+  it('virtualDocumentContents reads jdt:// classes through java/classFileContents', async () => {
+    const uri = 'jdt://contents/rt.jar/java.util/List.class';
+    const { session, request } = createMockSession({
+      requestImpl: (method) =>
+        method === 'java/classFileContents'
+          ? Promise.resolve('package java.util;\n')
+          : Promise.reject(new Error(`unexpected request ${method}`)),
+    });
+
+    const result = await virtualDocumentContents(session, uri);
+
+    expect(getVirtualDocumentLanguage(uri)).toBe('java');
+    expect(request).toHaveBeenCalledWith('java/classFileContents', { uri });
+    expect(result).toEqual({ ok: true, data: 'package java.util;\n' });
+  });
+
+  it('virtualDocumentContents decodes jar: sources through a Metals command', async () => {
+    const uri =
+      'jar:file:///cache/scala-library-2.13.12-sources.jar!/scala/Option.scala';
+    const { session, request } = createMockSession({
+      requestImpl: () =>
+        Promise.resolve({ requestedUri: uri, value: 'package scala\n' }),
+    });
+
+    const result = await virtualDocumentContents(session, uri);
+
+    expect(getVirtualDocumentLanguage(uri)).toBe('scala');
+    expect(request).toHaveBeenCalledWith('workspace/executeCommand', {
+      command: 'metals.file-decode',
+      arguments: [uri],
+    });
+    expect(result).toEqual({ ok: true, data: 'package scala\n' });
+  });
+
+  it('virtualDocumentContents rejects schemes without a known request', async () => {
+    const { session, request } = createMockSession();
+
+    const result = await virtualDocumentContents(
+      session,
+      'untitled:Untitled-1'
+    );
+
+    expect(getVirtualDocumentLanguage('untitled:Untitled-1')).toBeNull();
+    expect(request).not.toHaveBeenCalled();
+    expect(result.ok).toBe(false);
+  });
+
   it('rename uses the prepared rename payload and returns workspace edits', async () => {
     const { session, request } = createMockSession({
       requestImpl: () =>
@@ -819,6 +2171,33 @@ describe('LSP operations', () => {
     });
   });
 
+  it('rename fails early when prepareRename rejects the position', async () => {
+    const { session, request } = createMockSession({
+      serverCapabilities: { renameProvider: { prepareProvider: true } },
+      requestImpl: () => Promise.resolve(null),
+    });
+
+    const result = await rename(session, {
+      filePath: TEST_FILE_PATH,
+      position: createOneBasedPosition(1, 1),
+      lspPosition: { line: 0, character: 0 },
+      newName: 'Renamed',
+    });
+
+    expect(result.ok).toBe(false);
+    if (result.ok) {
+      return;
+    }
+    expect(result.error.message).toContain(
+      'The symbol at 1:1 cannot be renamed'
+    );
+    expect(request).toHaveBeenCalledTimes(1);
+    expect(request).toHaveBeenCalledWith('textDocument/prepareRename', {
+      textDocument: { uri: TEST_URI },
+      position: { line: 0, character: 0 },
+    });
+  });
+
   it('rename also handles WorkspaceEdit.documentChanges responses', async () => {
     const { session } = createMockSession({
       requestImpl: () =>
@@ -25,6 +25,10 @@ vi.mock('node:fs', async () => {
 vi.mock('../../src/lsp-client.js', () => ({
   createLspClient: vi.fn(),
   initializeLspClient: vi.fn(),
+  killServerProcess: vi.fn(
+    (process: ChildProcessWithoutNullStreams, signal: NodeJS.Signals) =>
+      process.kill(signal)
+  ),
   shutdownLspClient: vi.fn(),
 }));
 
@@ -271,6 +275,180 @@ describe('LspSession document lifecycle', () => {
     expect(mockReadFile).toHaveBeenCalledWith(transientPath, 'utf8');
   });
 
+  it('keeps a document open until concurrent operations on it finish', async () => {
+    const sendNotification = vi.fn().mockResolvedValue(undefined);
+    mockCreateLspClient.mockReturnValue({
+      ok: true,
+      data: {
+        client: {
+          connection: {
+            sendNotification,
+          } as never,
+          isInitialized: true,
+        },
+        process: createMockProcess(),
+      },
+    });
+    mockInitializeLspClient.mockResolvedValue({
+      ok: true,
+      data: undefined,
+    });
+
+    const session = createLspSession('typescript::/workspace', createProfile());
+    await session.start();
+
+    const started = vi.fn();
+    let finishFirst = () => {};
+    const first = session.executeWithDocumentLifecycle(
+      'src/shared.ts',
+      'transient',
+      () =>
+        new Promise((resolve) => {
+          started();
+          finishFirst = () => resolve({ ok: true, data: undefined });
+        })
+    );
+    await vi.waitFor(() => expect(started).toHaveBeenCalled());
+
+    const second = await session.executeWithDocumentLifecycle(
+      'src/shared.ts',
+      'transient',
+      () => Promise.resolve({ ok: true, data: undefined })
+    );
+    expect(second.ok).toBe(true);
+
+    const methods = () =>
+      sendNotification.mock.calls.map((call): string => call[0] as string);
+    expect(methods()).toEqual(['textDocument/didOpen']);
+
+    finishFirst();
+    await first;
+    expect(methods()).toEqual([
+      'textDocument/didOpen',
+      'textDocument/didClose',
+    ]);
+  });
+
+  it('opens a document once for operations starting on it together', async () => {
+    const sendNotification = vi.fn().mockResolvedValue(undefined);
+    mockCreateLspClient.mockReturnValue({
+      ok: true,
+      data: {
+        client: {
+          connection: {
+            sendNotification,
+          } as never,
+          isInitialized: true,
+        },
+        process: createMockProcess(),
+      },
+    });
+    mockInitializeLspClient.mockResolvedValue({
+      ok: true,
+      data: undefined,
+    });
+    mockReadFile.mockResolvedValue('const shared = 1;');
+
+    const session = createLspSession('typescript::/workspace', createProfile());
+    await session.start();
+
+    const started = vi.fn();
+    let finishFirst = () => {};
+    const first = session.executeWithDocumentLifecycle(
+      'src/shared.ts',
+      'transient',
+      () =>
+        new Promise((resolve) => {
+          started();
+          finishFirst = () => resolve({ ok: true, data: undefined });
+        })
+    );
+    const second = session.executeWithDocumentLifecycle(
+      'src/shared.ts',
+      'transient',
+      () => Promise.resolve({ ok: true, data: undefined })
+    );
+
+    expect((await second).ok).toBe(true);
+    const methods = () =>
+      sendNotification.mock.calls.map((call): string => call[0] as string);
+    expect(methods()).toEqual(['textDocument/didOpen']);
+
+    await vi.waitFor(() => expect(started).toHaveBeenCalled());
+    finishFirst();
+    await first;
+    expect(methods()).toEqual([
+      'textDocument/didOpen',
+      'textDocument/didClose',
+    ]);
+  });
+
+  it('flags documents that changed on disk after being sent to the server', async () => {
+    mockSuccessfulStart();
+
+    const session = createLspSession('typescript::/workspace', createProfile());
+
+    await session.start();
+
+    mockReadFile.mockResolvedValue('const value = 1;');
+    await session.executeWithDocumentLifecycle(
+      'src/value.ts',
+      'transient',
+      () => Promise.resolve({ ok: true, data: undefined })
+    );
+
+    expect(await session.isDocumentStale('src/value.ts')).toBe(false);
+
+    mockReadFile.mockResolvedValue('const value = 2;');
+    expect(await session.isDocumentStale('src/value.ts')).toBe(true);
+    expect(await session.isDocumentStale('src/unknown.ts')).toBe(false);
+  });
+
+  it('reuses document symbols until the document content changes', async () => {
+    const sendRequest = vi.fn().mockResolvedValue([{ name: 'value' }]);
+    mockCreateLspClient.mockReturnValue({
+      ok: true,
+      data: {
+        client: {
+          connection: {
+            sendNotification: vi.fn().mockResolvedValue(undefined),
+            sendRequest,
+          } as never,
+          isInitialized: true,
+        },
+        process: createMockProcess(),
+      },
+    });
+    mockInitializeLspClient.mockResolvedValue({
+      ok: true,
+      data: undefined,
+    });
+
+    const session = createLspSession('typescript::/workspace', createProfile());
+    await session.start();
+
+    const outline = async () =>
+      await session.executeWithDocumentLifecycle(
+        'src/value.ts',
+        'transient',
+        async (scope) => ({
+          ok: true,
+          data: await scope.request('textDocument/documentSymbol', {
+            textDocument: { uri: scope.uri },
+          }),
+        })
+      );
+
+    mockReadFile.mockResolvedValue('const value = 1;');
+    await outline();
+    expect(await outline()).toEqual({ ok: true, data: [{ name: 'value' }] });
+    expect(sendRequest).toHaveBeenCalledTimes(1);
+
+    mockReadFile.mockResolvedValue('const value = 2;');
+    await outline();
+    expect(sendRequest).toHaveBeenCalledTimes(2);
+  });
+
   it('falls back to default preload entries when configured ones resolve to nothing', async () => {
     const sendNotification = vi.fn().mockResolvedValue(undefined);
     mockCreateLspClient.mockReturnValue({
@@ -325,4 +503,52 @@ describe('LspSession document lifecycle', () => {
       },
     });
   });
+
+  it('answers tsserver requests from the configured TypeScript profile', async () => {
+    const sendNotification = vi.fn().mockResolvedValue(undefined);
+    const onNotification = vi.fn();
+    mockCreateLspClient.mockReturnValue({
+      ok: true,
+      data: {
+        client: {
+          connection: {
+            sendNotification,
+            onNotification,
+          } as never,
+          isInitialized: true,
+        },
+        process: createMockProcess(),
+      },
+    });
+    mockInitializeLspClient.mockResolvedValue({
+      ok: true,
+      data: undefined,
+    });
+
+    const onTsserverRequest = vi.fn().mockResolvedValue({ configFiles: [] });
+    const profile = createProfile();
+    profile.name = 'vue';
+    profile.config.tsserver_profile = 'typescript';
+    const session = createLspSession('vue::/workspace', profile, {
+      onTsserverRequest,
+    });
+
+    await session.start();
+
+    const handler = onNotification.mock.calls.find(
+      (call) => call[0] === 'tsserver/request'
+    )?.[1] as (params: unknown) => void;
+    handler([[7, '_vue:projectInfo', { file: '/workspace/App.vue' }]]);
+
+    await vi.waitFor(() =>
+      expect(sendNotification).toHaveBeenCalledWith('tsserver/response', [
+        [7, { configFiles: [] }],
+      ])
+    );
+    expect(onTsserverRequest).toHaveBeenCalledWith(
+      'typescript',
+      '_vue:projectInfo',
+      { file: '/workspace/App.vue' }
+    );
+  });
 });
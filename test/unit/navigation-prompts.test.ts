@@ -0,0 +1,40 @@
+import { describe, expect, it } from 'vitest';
+import {
+  buildCallGraphPrompt,
+  buildExplainSymbolPrompt,
+  buildUsageSummaryPrompt,
+} from '../../src/prompts/navigation.js';
+import type { GetPromptResult } from '@modelcontextprotocol/sdk/types.js';
+
+function promptText(prompt: GetPromptResult): string {
+  const content = prompt.messages[0]?.content;
+  return content?.type === 'text' ? content.text : '';
+}
+
+describe('navigation prompts', () => {
+  it('searches for a symbol given by name', () => {
+    const text = promptText(buildExplainSymbolPrompt('UserRepository.save'));
+
+    expect(text).toContain(
+      'Call `search` with the query `UserRepository.save`'
+    );
+    expect(text).toContain('`inspect`');
+    expect(text).toMatch(/^1\. /);
+  });
+
+  it('uses a file position directly', () => {
+    const text = promptText(buildUsageSummaryPrompt('src/server.ts:12:7'));
+
+    expect(text).toContain('`src/server.ts` line 12, character 7');
+    expect(text).not.toContain('`search`');
+  });
+
+  it('passes the direction and depth to call_hierarchy', () => {
+    expect(promptText(buildCallGraphPrompt('main'))).toContain(
+      'direction `both` and depth 2'
+    );
+    expect(promptText(buildCallGraphPrompt('main', 'incoming', 4))).toContain(
+      'direction `incoming` and depth 4'
+    );
+  });
+});
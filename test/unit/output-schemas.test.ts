@@ -0,0 +1,102 @@
+/**
+ * Tool Output Schema Tests
+ */
+
+import * as path from 'path';
+import { describe, test, expect } from 'vitest';
+import {
+  toFileChangeOutputs,
+  toLocationOutput,
+  toWarningsOutput,
+} from '../../src/tools/output-schemas.js';
+import { keepStructuredContent } from '../../src/tools/hooks.js';
+
+const FILE = path.join(process.cwd(), 'src', 'a.ts');
+
+describe('Tool output schemas', () => {
+  test('should convert 0-based LSP positions to 1-based locations', () => {
+    expect(
+      toLocationOutput(`file://${FILE}`, { line: 0, character: 4 })
+    ).toEqual({ file: path.join('src', 'a.ts'), line: 1, character: 5 });
+  });
+
+  test('should report edits, failures and a diff per file', () => {
+    const [output] = toFileChangeOutputs([
+      {
+        fileUri: `file://${FILE}`,
+        success: false,
+        appliedChanges: [
+          {
+            range: {
+              start: { line: 0, character: 6 },
+              end: { line: 0, character: 7 },
+            },
+            oldText: 'a',
+            newText: 'b',
+            line: 1,
+            character: 7,
+          },
+        ],
+        failedChanges: [
+          {
+            range: {
+              start: { line: 1, character: 0 },
+              end: { line: 1, character: 1 },
+            },
+            newText: 'c',
+            line: 2,
+            character: 1,
+            error: 'out of range',
+          },
+        ],
+        originalContent: 'const a = 1;\n',
+        updatedContent: 'const b = 1;\n',
+      },
+    ]);
+
+    expect(output).toMatchObject({
+      file: path.join('src', 'a.ts'),
+      applied: 1,
+      failed: [{ line: 2, character: 1, error: 'out of range' }],
+    });
+    expect(output?.diff).toContain('-const a = 1;');
+    expect(output?.diff).toContain('+const b = 1;');
+  });
+
+  test('should leave the diff out for unchanged files', () => {
+    const [output] = toFileChangeOutputs([
+      {
+        fileUri: FILE,
+        success: true,
+        appliedChanges: [],
+        failedChanges: [],
+        originalContent: 'const a = 1;\n',
+      },
+    ]);
+
+    expect(output).not.toHaveProperty('diff');
+  });
+
+  test('should split stale content warnings into lines', () => {
+    expect(toWarningsOutput(null)).toEqual([]);
+    expect(toWarningsOutput('⚠ changed\n⚠ indexing')).toEqual([
+      '⚠ changed',
+      '⚠ indexing',
+    ]);
+  });
+
+  test('should keep structured content a hook dropped', () => {
+    const original = {
+      content: [{ type: 'text' as const, text: 'a' }],
+      structuredContent: { total: 1 },
+    };
+    const rewritten = { content: [{ type: 'text' as const, text: 'b' }] };
+    const failed = { ...rewritten, isError: true };
+
+    expect(keepStructuredContent(original, rewritten)).toEqual({
+      ...rewritten,
+      structuredContent: { total: 1 },
+    });
+    expect(keepStructuredContent(original, failed)).toBe(failed);
+  });
+});
@@ -0,0 +1,74 @@
+/**
+ * Path Output Tests
+ */
+
+import { afterEach, describe, test, expect } from 'vitest';
+import {
+  configurePathOutput,
+  formatOutputPath,
+  formatReportPath,
+  isVirtualUri,
+  toRelativeFilePath,
+} from '../../src/utils/path-format.js';
+
+describe('Path Output', () => {
+  afterEach(() => {
+    configurePathOutput({ style: 'relative', baseDirectory: process.cwd() });
+  });
+
+  test('should make workspace paths relative by default', () => {
+    configurePathOutput({ baseDirectory: '/work/app' });
+
+    expect(formatOutputPath('file:///work/app/src/main.ts')).toBe(
+      'src/main.ts'
+    );
+    expect(formatOutputPath('/work/app/src/my%40file.ts')).toBe(
+      'src/my@file.ts'
+    );
+    expect(formatOutputPath('src/main.ts')).toBe('src/main.ts');
+  });
+
+  test('should keep paths outside the workspace absolute', () => {
+    configurePathOutput({ baseDirectory: '/work/app' });
+
+    expect(toRelativeFilePath('/work/app-other/main.ts')).toBe(
+      '/work/app-other/main.ts'
+    );
+    expect(toRelativeFilePath('/usr/lib/node/index.d.ts')).toBe(
+      '/usr/lib/node/index.d.ts'
+    );
+  });
+
+  test('should render absolute and URI styles', () => {
+    configurePathOutput({ baseDirectory: '/work/app', style: 'absolute' });
+    expect(formatOutputPath('src/main.ts')).toBe('/work/app/src/main.ts');
+
+    configurePathOutput({ style: 'uri' });
+    expect(formatOutputPath('file:///work/app/src/main.ts')).toBe(
+      'file:///work/app/src/main.ts'
+    );
+    expect(formatOutputPath('/work/app/a b.ts')).toBe(
+      'file:///work/app/a%20b.ts'
+    );
+  });
+
+  test('should keep virtual document URIs as they are', () => {
+    configurePathOutput({ baseDirectory: '/work/app', style: 'absolute' });
+    const uri = 'jdt://contents/rt.jar/java.util/List.class?=app/rt.jar';
+
+    expect(isVirtualUri(uri)).toBe(true);
+    expect(isVirtualUri('file:///work/app/src/main.ts')).toBe(false);
+    expect(isVirtualUri('C:\\work\\app\\main.ts')).toBe(false);
+    expect(formatOutputPath(uri)).toBe(uri);
+  });
+
+  test('should format report paths in the configured style', () => {
+    configurePathOutput({ baseDirectory: '/work/app' });
+    expect(formatReportPath('/work/app/src/main.ts')).toBe('src/main.ts');
+
+    configurePathOutput({ style: 'uri' });
+    expect(formatReportPath('/work/app/src/main.ts')).toBe(
+      'file:///work/app/src/main.ts'
+    );
+  });
+});
@@ -0,0 +1,94 @@
+/**
+ * PHP Workspace Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import { findComposerRoots } from '../../src/utils/php-workspace.js';
+import { expandEnvVarsInValue } from '../../src/utils/env-expansion.js';
+import { createIntelephenseLoader } from '../../src/workspace/loaders/intelephense.js';
+import { createWorkspaceLoaderStore } from '../../src/state/index.js';
+
+describe('PHP workspace detection', () => {
+  let workspace: string;
+
+  function write(file: string, content = ''): void {
+    const filePath = path.join(workspace, file);
+    fs.mkdirSync(path.dirname(filePath), { recursive: true });
+    fs.writeFileSync(filePath, content);
+  }
+
+  beforeEach(() => {
+    workspace = fs.realpathSync(
+      fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-php-'))
+    );
+  });
+
+  afterEach(() => {
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('finds Composer projects outside vendor code', async () => {
+    write('composer.json', '{}');
+    write('packages/billing/composer.json', '{}');
+    write('vendor/monolog/monolog/composer.json', '{}');
+
+    expect(await findComposerRoots(workspace)).toEqual([
+      workspace,
+      path.join(workspace, 'packages', 'billing'),
+    ]);
+  });
+});
+
+describe('intelephense workspace loader', () => {
+  const indexing = {
+    type: 'intelephense' as const,
+    ready: false,
+    data: { composerRoots: ['/app'], indexingStartedAt: Date.now() },
+  };
+
+  test('becomes ready when indexing ends', () => {
+    const loader = createIntelephenseLoader();
+
+    const started = loader.handleNotification!(indexing, 'indexingStarted');
+    expect(loader.isReady(started)).toBe(false);
+
+    const ended = loader.handleNotification!(started, 'indexingEnded');
+    expect(loader.isReady(ended)).toBe(true);
+  });
+
+  test('replays notifications that arrive before the loader is initialized', () => {
+    const store = createWorkspaceLoaderStore();
+    store.setLoader(createIntelephenseLoader());
+
+    store.updateState('indexingEnded');
+    expect(store.isReady()).toBe(false);
+
+    store.setState(indexing);
+    expect(store.isReady()).toBe(true);
+  });
+});
+
+describe('initialization option expansion', () => {
+  test('expands variables and leaves out unset ones', () => {
+    const env = { SYMBOLS_DATA_DIR: '/data', SYMBOLS_WORKSPACE_ID: 'app-1' };
+
+    expect(
+      expandEnvVarsInValue(
+        {
+          licenceKey: '$INTELEPHENSE_LICENCE_KEY',
+          storagePath: '$SYMBOLS_DATA_DIR/intelephense/$SYMBOLS_WORKSPACE_ID',
+          files: { exclude: ['**/vendor/**/Tests/**', '${UNSET}'] },
+          clearCache: false,
+        },
+        env
+      )
+    ).toEqual({
+      storagePath: '/data/intelephense/app-1',
+      files: { exclude: ['**/vendor/**/Tests/**'] },
+      clearCache: false,
+    });
+  });
+});
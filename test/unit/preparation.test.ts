@@ -63,6 +63,7 @@ function createMockSession(
     stop: vi.fn(() => Promise.resolve()),
     restart: vi.fn(() => Promise.resolve()),
     request: vi.fn(),
+    getServerCapabilities: vi.fn(() => null),
     getWorkspaceState: vi.fn(() => workspaceState),
     getWorkspaceLoaderStore: vi.fn(() => ({
       state: null,
@@ -77,8 +78,13 @@ function createMockSession(
     getDiagnosticsStore: vi.fn(() => ({}) as never),
     getDiagnosticProviderStore: vi.fn(() => ({}) as never),
     getWindowLogStore: vi.fn(() => ({ getMessages: vi.fn() })),
+    getProgressStore: vi.fn(() => ({ getActive: () => [] }) as never),
     executeWithCursorContext: vi.fn(),
     executeWithDocumentLifecycle: vi.fn(),
+    isDocumentStale: vi.fn(() => Promise.resolve(false)),
+    setDocumentOverlay: vi.fn(() =>
+      Promise.resolve({ ok: true as const, data: undefined })
+    ),
     claimDocument: vi.fn((filePath: string) => filePath),
     releaseDocument: vi.fn(() => null),
     listOwnedDocuments: vi.fn(() => []),
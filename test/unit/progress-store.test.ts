@@ -0,0 +1,63 @@
+/**
+ * Progress Store Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import { createProgressStore } from '../../src/state/index.js';
+
+describe('Progress store', () => {
+  test('tracks work from begin through reports until it ends', () => {
+    const store = createProgressStore();
+
+    store.handleProgress({
+      token: 'rustAnalyzer/Indexing',
+      value: { kind: 'begin', title: 'Indexing', percentage: 0 },
+    });
+    store.handleProgress({
+      token: 'rustAnalyzer/Indexing',
+      value: { kind: 'report', message: '120/300 (core)', percentage: 40 },
+    });
+
+    expect(store.getActive()).toMatchObject([
+      {
+        token: 'rustAnalyzer/Indexing',
+        title: 'Indexing',
+        message: '120/300 (core)',
+        percentage: 40,
+      },
+    ]);
+
+    store.handleProgress({
+      token: 'rustAnalyzer/Indexing',
+      value: { kind: 'end' },
+    });
+    expect(store.getActive()).toEqual([]);
+  });
+
+  test('ignores reports for work that never began', () => {
+    const store = createProgressStore();
+
+    store.handleProgress({
+      token: 7,
+      value: { kind: 'report', percentage: 50 },
+    });
+
+    expect(store.getActive()).toEqual([]);
+  });
+
+  test('waits for work to end and stay quiet', async () => {
+    const store = createProgressStore();
+    store.handleProgress({
+      token: 1,
+      value: { kind: 'begin', title: 'Loading workspace' },
+    });
+
+    expect(await store.waitForIdle(50, 10)).toBe(false);
+
+    setTimeout(() => {
+      store.handleProgress({ token: 1, value: { kind: 'end' } });
+    }, 20);
+    expect(await store.waitForIdle(2000, 10)).toBe(true);
+    expect(store.getActive()).toEqual([]);
+  });
+});
@@ -0,0 +1,47 @@
+/**
+ * Qualified Name Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  formatQualifiedName,
+  getModulePath,
+} from '../../src/utils/qualified-names.js';
+
+describe('Qualified names', () => {
+  test('should use :: for Rust and C++ files', () => {
+    expect(formatQualifiedName('method', ['module', 'Type'], 'src/lib.rs')).toBe(
+      'module::Type::method'
+    );
+    expect(formatQualifiedName('run', ['app', 'Server'], 'server.cpp')).toBe(
+      'app::Server::run'
+    );
+  });
+
+  test('should use dots for Java, TypeScript and Python files', () => {
+    expect(
+      formatQualifiedName('method', ['com.example', 'Class'], 'Class.java')
+    ).toBe('com.example.Class.method');
+    expect(formatQualifiedName('load', ['Config'], 'config.py')).toBe(
+      'Config.load'
+    );
+  });
+
+  test('should use backslashes for PHP namespaces', () => {
+    expect(formatQualifiedName('handle', ['App', 'Kernel'], 'Kernel.php')).toBe(
+      'App\\Kernel\\handle'
+    );
+  });
+
+  test('should derive module paths from file paths', () => {
+    expect(getModulePath('src/models/user.ts')).toEqual(['models', 'user']);
+    expect(getModulePath('api/users/__init__.py')).toEqual(['api', 'users']);
+    expect(getModulePath('src/net/mod.rs')).toEqual(['net']);
+    expect(getModulePath('src\\app\\index.ts')).toEqual(['app']);
+  });
+
+  test('should return the bare name for top-level symbols', () => {
+    expect(formatQualifiedName('main', undefined, 'main.go')).toBe('main');
+    expect(formatQualifiedName('main', [], 'main.go')).toBe('main');
+  });
+});
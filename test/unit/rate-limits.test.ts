@@ -0,0 +1,137 @@
+/**
+ * Rate Limit Tests
+ */
+
+import { afterEach, describe, test, expect, vi } from 'vitest';
+import {
+  configureRateLimits,
+  forgetRateLimitClient,
+  getClientId,
+  LOCAL_CLIENT_ID,
+  withRateLimit,
+} from '../../src/tools/rate-limits.js';
+import {
+  createWorkspaceContext,
+  runInWorkspace,
+} from '../../src/runtime/workspace-context.js';
+
+const LIMITS = {
+  requests_per_minute: null,
+  burst: 20,
+  max_concurrent: null,
+  max_queued_per_client: 50,
+};
+
+function deferred() {
+  let resolve!: () => void;
+  const promise = new Promise<void>((done) => {
+    resolve = done;
+  });
+  return { promise, resolve };
+}
+
+describe('Rate limits', () => {
+  afterEach(() => {
+    configureRateLimits(null);
+    vi.useRealTimers();
+  });
+
+  test('should identify clients by auth, then session', () => {
+    expect(
+      getClientId({ sessionId: 'session-1', authInfo: { clientId: 'ci' } })
+    ).toBe('ci');
+    expect(getClientId({ sessionId: 'session-1' })).toBe('session-1');
+    expect(getClientId(undefined)).toBe(LOCAL_CLIENT_ID);
+  });
+
+  test('should reject calls over the per-minute budget', async () => {
+    vi.useFakeTimers();
+    configureRateLimits({ ...LIMITS, requests_per_minute: 60, burst: 2 });
+    const call = async () => 'ok';
+
+    await expect(withRateLimit('agent-a', call)).resolves.toBe('ok');
+    await expect(withRateLimit('agent-a', call)).resolves.toBe('ok');
+    await expect(withRateLimit('agent-a', call)).rejects.toThrow(
+      'Rate limit exceeded for client agent-a'
+    );
+    // Other clients have their own budget
+    await expect(withRateLimit('agent-b', call)).resolves.toBe('ok');
+
+    vi.advanceTimersByTime(1_000);
+    await expect(withRateLimit('agent-a', call)).resolves.toBe('ok');
+  });
+
+  test('should start over for clients it was told to forget', async () => {
+    configureRateLimits({ ...LIMITS, requests_per_minute: 1, burst: 1 });
+    const call = async () => 'ok';
+
+    await expect(withRateLimit('session-1', call)).resolves.toBe('ok');
+    await expect(withRateLimit('session-1', call)).rejects.toThrow(
+      'Rate limit exceeded'
+    );
+
+    forgetRateLimitClient('session-1');
+    await expect(withRateLimit('session-1', call)).resolves.toBe('ok');
+  });
+
+  test('should keep limits and budgets per workspace', async () => {
+    configureRateLimits({ ...LIMITS, requests_per_minute: 1, burst: 1 });
+    const call = async () => 'ok';
+    await expect(withRateLimit('agent', call)).resolves.toBe('ok');
+
+    const other = createWorkspaceContext();
+    await runInWorkspace(other, async () => {
+      // Unlimited until this workspace is configured
+      await expect(withRateLimit('agent', call)).resolves.toBe('ok');
+      await expect(withRateLimit('agent', call)).resolves.toBe('ok');
+    });
+    await expect(withRateLimit('agent', call)).rejects.toThrow(
+      'Rate limit exceeded'
+    );
+  });
+
+  test('should serve waiting clients in turn', async () => {
+    configureRateLimits({ ...LIMITS, max_concurrent: 1 });
+    const order: string[] = [];
+    const blocker = deferred();
+
+    const first = withRateLimit('busy', async () => {
+      await blocker.promise;
+      order.push('busy-1');
+    });
+    const queued = [
+      withRateLimit('busy', async () => {
+        order.push('busy-2');
+      }),
+      withRateLimit('busy', async () => {
+        order.push('busy-3');
+      }),
+      withRateLimit('quiet', async () => {
+        order.push('quiet-1');
+      }),
+    ];
+
+    blocker.resolve();
+    await Promise.all([first, ...queued]);
+    expect(order).toEqual(['busy-1', 'busy-2', 'quiet-1', 'busy-3']);
+  });
+
+  test('should reject calls beyond the queue limit', async () => {
+    configureRateLimits({
+      ...LIMITS,
+      max_concurrent: 1,
+      max_queued_per_client: 1,
+    });
+    const blocker = deferred();
+
+    const running = withRateLimit('agent', async () => await blocker.promise);
+    const waiting = withRateLimit('agent', async () => 'waited');
+    await expect(withRateLimit('agent', async () => 'x')).rejects.toThrow(
+      'Too many queued requests'
+    );
+
+    blocker.resolve();
+    await running;
+    await expect(waiting).resolves.toBe('waited');
+  });
+});
@@ -0,0 +1,91 @@
+/**
+ * Ruby Workspace Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import { findGemfile, isGemBundled } from '../../src/utils/ruby-workspace.js';
+import { createRubyLoader } from '../../src/workspace/loaders/ruby.js';
+
+const LOCKFILE = `GEM
+  remote: https://rubygems.org/
+  specs:
+    prism (1.2.0)
+    ruby-lsp (0.22.1)
+      prism (>= 1.2, < 2.0)
+
+DEPENDENCIES
+  ruby-lsp
+`;
+
+describe('Ruby workspace detection', () => {
+  let workspace: string;
+
+  function write(file: string, content = ''): void {
+    const filePath = path.join(workspace, file);
+    fs.mkdirSync(path.dirname(filePath), { recursive: true });
+    fs.writeFileSync(filePath, content);
+  }
+
+  beforeEach(() => {
+    workspace = fs.realpathSync(
+      fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-ruby-'))
+    );
+  });
+
+  afterEach(() => {
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('uses the only nested Gemfile when the root has none', () => {
+    write('backend/Gemfile', "gem 'rails'\n");
+
+    expect(findGemfile(workspace)).toBe(
+      path.join(workspace, 'backend', 'Gemfile')
+    );
+
+    write('tools/Gemfile', "gem 'rake'\n");
+    expect(findGemfile(workspace)).toBeNull();
+  });
+
+  test('reads bundled gems from the lockfile, then the Gemfile', () => {
+    write('Gemfile', "gem 'solargraph', group: :development\n");
+    const gemfile = path.join(workspace, 'Gemfile');
+
+    expect(isGemBundled(gemfile, 'solargraph')).toBe(true);
+    expect(isGemBundled(gemfile, 'ruby-lsp')).toBe(false);
+
+    write('Gemfile.lock', LOCKFILE);
+    expect(isGemBundled(gemfile, 'ruby-lsp')).toBe(true);
+    expect(isGemBundled(gemfile, 'prism')).toBe(true);
+    expect(isGemBundled(gemfile, 'solargraph')).toBe(false);
+  });
+
+  test('runs a bundled server through bundle exec', () => {
+    write('Gemfile', "gem 'ruby-lsp'\n");
+    write('Gemfile.lock', LOCKFILE);
+    const loader = createRubyLoader();
+
+    expect(
+      loader.prepareCommand!(workspace, {
+        commandName: '/usr/local/bin/ruby-lsp',
+        commandArgs: ['--use-launcher'],
+      })
+    ).toEqual({
+      commandName: 'bundle',
+      commandArgs: ['exec', 'ruby-lsp', '--use-launcher'],
+      environment: { BUNDLE_GEMFILE: path.join(workspace, 'Gemfile') },
+    });
+  });
+
+  test('leaves unbundled servers as they are', () => {
+    write('Gemfile', "gem 'rails'\n");
+    const command = { commandName: 'ruby-lsp', commandArgs: [] };
+
+    expect(createRubyLoader().prepareCommand!(workspace, command)).toBe(
+      command
+    );
+  });
+});
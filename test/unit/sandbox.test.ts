@@ -0,0 +1,103 @@
+/**
+ * Sandbox Tests
+ */
+
+import { afterEach, describe, test, expect } from 'vitest';
+import {
+  buildBwrapArgs,
+  buildSandboxProfile,
+  configureSandbox,
+  wrapSandboxCommand,
+  type SandboxContext,
+} from '../../src/lsp/sandbox.js';
+
+const SANDBOX = {
+  enabled: true,
+  network: false,
+  readable_paths: ['~/.rustup'],
+  writable_paths: [],
+};
+
+const CONTEXT: SandboxContext = {
+  workspace: '/home/dev/project',
+  home: '/home/dev',
+  executable: '/home/dev/.nvm/versions/node/v22/bin/pyright-langserver',
+  env: {},
+};
+
+const COMMAND = {
+  commandName: 'pyright-langserver',
+  commandArgs: ['--stdio'],
+};
+
+function pairsAfter(args: string[], flag: string): string[] {
+  return args.flatMap((arg, index) =>
+    arg === flag ? [args[index + 1] ?? ''] : []
+  );
+}
+
+describe('Sandbox', () => {
+  afterEach(() => {
+    configureSandbox({ workspace: process.cwd(), sandbox: null });
+  });
+
+  test('should leave commands alone when disabled', () => {
+    expect(wrapSandboxCommand(COMMAND, process.env)).toBe(COMMAND);
+  });
+
+  test('should bind only the system, the install and allowed paths', () => {
+    configureSandbox({ workspace: CONTEXT.workspace, sandbox: SANDBOX });
+    const args = buildBwrapArgs(COMMAND, CONTEXT, SANDBOX);
+
+    expect(args).not.toContain('--ro-bind');
+    expect(pairsAfter(args, '--tmpfs')).toEqual(['/tmp']);
+    const readable = pairsAfter(args, '--ro-bind-try');
+    expect(readable).toContain('/usr');
+    expect(readable).toContain('/etc/resolv.conf');
+    expect(readable).not.toContain('/');
+    expect(readable).not.toContain('/etc');
+    expect(readable.filter((entry) => entry.startsWith('/home'))).toEqual([
+      '/home/dev/.nvm/versions/node/v22',
+      '/home/dev/.rustup',
+    ]);
+    expect(pairsAfter(args, '--bind-try')).toEqual([
+      '/home/dev/project',
+      '/home/dev/.cache',
+    ]);
+    // Binds must come after the tmpfs they punch through
+    expect(args.indexOf('--bind-try')).toBeGreaterThan(
+      args.lastIndexOf('--tmpfs')
+    );
+    expect(args).toContain('--unshare-net');
+    expect(args.slice(-3)).toEqual(['--', 'pyright-langserver', '--stdio']);
+  });
+
+  test('should keep the network when allowed', () => {
+    const args = buildBwrapArgs(COMMAND, CONTEXT, {
+      ...SANDBOX,
+      network: true,
+    });
+
+    expect(args).not.toContain('--unshare-net');
+  });
+
+  test('should write a deny-default sandbox-exec profile', () => {
+    configureSandbox({ workspace: CONTEXT.workspace, sandbox: SANDBOX });
+    const profile = buildSandboxProfile(CONTEXT, SANDBOX).split('\n');
+
+    expect(profile.slice(0, 2)).toEqual(['(version 1)', '(deny default)']);
+    expect(profile).not.toContain('(allow default)');
+    expect(profile).toContain('(allow file-read* (subpath "/usr"))');
+    expect(profile).toContain(
+      '(allow file-read* (subpath "/home/dev/.nvm/versions/node/v22"))'
+    );
+    expect(profile).toContain(
+      '(allow file-read* file-write* (subpath "/home/dev/project"))'
+    );
+    expect(profile).not.toContain('(allow file-read* (subpath "/home/dev"))');
+    expect(profile.some((rule) => rule.includes('network'))).toBe(false);
+
+    const online = buildSandboxProfile(CONTEXT, { ...SANDBOX, network: true });
+    expect(online.split('\n')).toContain('(allow network* system-socket)');
+  });
+});
@@ -0,0 +1,276 @@
+/**
+ * Session Recording Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { PassThrough } from 'stream';
+import * as rpc from 'vscode-jsonrpc';
+import { afterEach, describe, test, expect } from 'vitest';
+import {
+  buildReplayExchanges,
+  configureSessionRecording,
+  getRecordingPath,
+  loadSessionRecording,
+  serveSessionRecording,
+  startSessionRecording,
+  takeReplayExchange,
+  type SessionRecording,
+} from '../../src/lsp/session-recording.js';
+
+const RECORDED_ROOT = 'file:///recorded/project';
+
+function createRecording(): SessionRecording {
+  return {
+    header: {
+      version: 1,
+      profile: 'typescript',
+      command: 'typescript-language-server --stdio',
+      workspaceUri: RECORDED_ROOT,
+      recordedAt: '2026-10-01T00:00:00.000Z',
+    },
+    messages: [
+      {
+        time: 0,
+        from: 'client',
+        message: {
+          jsonrpc: '2.0',
+          id: 0,
+          method: 'initialize',
+          params: { rootUri: RECORDED_ROOT },
+        } as rpc.RequestMessage,
+      },
+      {
+        time: 40,
+        from: 'server',
+        message: {
+          jsonrpc: '2.0',
+          id: 0,
+          result: { capabilities: { hoverProvider: true } },
+        } as rpc.ResponseMessage,
+      },
+      {
+        time: 50,
+        from: 'client',
+        message: {
+          jsonrpc: '2.0',
+          method: 'textDocument/didOpen',
+          params: { textDocument: { uri: `${RECORDED_ROOT}/src/a.ts` } },
+        } as rpc.NotificationMessage,
+      },
+      {
+        time: 80,
+        from: 'server',
+        message: {
+          jsonrpc: '2.0',
+          method: 'textDocument/publishDiagnostics',
+          params: { uri: `${RECORDED_ROOT}/src/a.ts`, diagnostics: [] },
+        } as rpc.NotificationMessage,
+      },
+      {
+        time: 90,
+        from: 'client',
+        message: {
+          jsonrpc: '2.0',
+          id: 1,
+          method: 'textDocument/hover',
+          params: {
+            textDocument: { uri: `${RECORDED_ROOT}/src/a.ts` },
+            position: { line: 0, character: 4 },
+          },
+        } as rpc.RequestMessage,
+      },
+      {
+        time: 95,
+        from: 'client',
+        message: {
+          jsonrpc: '2.0',
+          id: 2,
+          method: 'textDocument/hover',
+          params: {
+            textDocument: { uri: `${RECORDED_ROOT}/src/a.ts` },
+            position: { line: 3, character: 0 },
+          },
+        } as rpc.RequestMessage,
+      },
+      {
+        time: 120,
+        from: 'server',
+        message: {
+          jsonrpc: '2.0',
+          id: 2,
+          result: { contents: 'second' },
+        } as rpc.ResponseMessage,
+      },
+      {
+        time: 130,
+        from: 'server',
+        message: {
+          jsonrpc: '2.0',
+          id: 1,
+          result: { contents: 'first' },
+        } as rpc.ResponseMessage,
+      },
+    ],
+  };
+}
+
+describe('Replay exchanges', () => {
+  test('should attach responses by id and notifications by order', () => {
+    const exchanges = buildReplayExchanges(createRecording());
+
+    expect(exchanges.map((exchange) => exchange.method)).toEqual([
+      'initialize',
+      'textDocument/didOpen',
+      'textDocument/hover',
+      'textDocument/hover',
+    ]);
+    expect(exchanges[1]?.replies[0]?.message).toMatchObject({
+      method: 'textDocument/publishDiagnostics',
+    });
+    expect(exchanges[2]?.replies[0]?.message).toMatchObject({
+      result: { contents: 'first' },
+    });
+    expect(exchanges[3]?.replies[0]?.message).toMatchObject({
+      result: { contents: 'second' },
+    });
+  });
+
+  test('should prefer exchanges with the same params', () => {
+    const exchanges = buildReplayExchanges(createRecording());
+    const method = 'textDocument/hover';
+    const params = {
+      textDocument: { uri: `${RECORDED_ROOT}/src/a.ts` },
+      position: { line: 3, character: 0 },
+    };
+
+    expect(takeReplayExchange(exchanges, method, params)).toBe(exchanges[3]);
+    // Repeated requests reuse the matching exchange
+    expect(takeReplayExchange(exchanges, method, params)).toBe(exchanges[3]);
+    expect(takeReplayExchange(exchanges, method, { other: true })).toBe(
+      exchanges[2]
+    );
+    expect(
+      takeReplayExchange(exchanges, 'textDocument/definition', params)
+    ).toBeNull();
+  });
+});
+
+describe('Replay server', () => {
+  test('should answer from the recording in a different workspace', async () => {
+    const toServer = new PassThrough();
+    const toClient = new PassThrough();
+    const served = serveSessionRecording(
+      createRecording(),
+      new rpc.StreamMessageReader(toServer),
+      new rpc.StreamMessageWriter(toClient),
+      'instant'
+    );
+
+    const connection = rpc.createMessageConnection(
+      new rpc.StreamMessageReader(toClient),
+      new rpc.StreamMessageWriter(toServer)
+    );
+    const diagnostics: unknown[] = [];
+    connection.onNotification(
+      'textDocument/publishDiagnostics',
+      (params: unknown) => {
+        diagnostics.push(params);
+      }
+    );
+    connection.listen();
+
+    const liveRoot = 'file:///checkout/project';
+    await expect(
+      connection.sendRequest('initialize', { rootUri: liveRoot })
+    ).resolves.toEqual({ capabilities: { hoverProvider: true } });
+
+    await connection.sendNotification('textDocument/didOpen', {
+      textDocument: { uri: `${liveRoot}/src/a.ts` },
+    });
+    await expect(
+      connection.sendRequest('textDocument/hover', {
+        textDocument: { uri: `${liveRoot}/src/a.ts` },
+        position: { line: 0, character: 4 },
+      })
+    ).resolves.toEqual({ contents: 'first' });
+    expect(diagnostics).toEqual([
+      { uri: `${liveRoot}/src/a.ts`, diagnostics: [] },
+    ]);
+
+    await expect(
+      connection.sendRequest('textDocument/definition', {})
+    ).rejects.toThrow('No recorded response for textDocument/definition');
+
+    await connection.sendNotification('exit');
+    await expect(served).resolves.toBe(0);
+    connection.dispose();
+  });
+});
+
+describe('Session recorder', () => {
+  let directory: string | null = null;
+
+  afterEach(() => {
+    configureSessionRecording({ record: null });
+    if (directory) {
+      fs.rmSync(directory, { recursive: true, force: true });
+      directory = null;
+    }
+  });
+
+  test('should do nothing unless a directory is configured', () => {
+    expect(
+      startSessionRecording({
+        profile: 'typescript',
+        command: 'tsserver',
+        workspaceUri: RECORDED_ROOT,
+      })
+    ).toBeNull();
+  });
+
+  test('should write traffic in both directions', async () => {
+    directory = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-recording-'));
+    configureSessionRecording({ record: directory });
+
+    const recorder = startSessionRecording({
+      profile: 'typescript',
+      command: 'typescript-language-server --stdio',
+      workspaceUri: RECORDED_ROOT,
+    });
+    const serverOutput = new PassThrough();
+    const reader = recorder!.wrapReader(
+      new rpc.StreamMessageReader(serverOutput)
+    );
+    const writer = recorder!.wrapWriter(
+      new rpc.StreamMessageWriter(new PassThrough())
+    );
+
+    const received = new Promise<rpc.Message>((resolve) => {
+      reader.listen(resolve);
+    });
+    await writer.write({
+      jsonrpc: '2.0',
+      id: 0,
+      method: 'shutdown',
+    } as rpc.RequestMessage);
+    await new rpc.StreamMessageWriter(serverOutput).write({
+      jsonrpc: '2.0',
+      id: 0,
+      result: null,
+    } as rpc.ResponseMessage);
+    await received;
+
+    const recording = loadSessionRecording(
+      getRecordingPath(directory, 'typescript')
+    );
+    expect(recording.header.profile).toBe('typescript');
+    expect(recording.messages.map((entry) => entry.from)).toEqual([
+      'client',
+      'server',
+    ]);
+    reader.dispose();
+    writer.dispose();
+  });
+});
@@ -0,0 +1,116 @@
+/**
+ * Session State Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, beforeEach, describe, test, expect, vi } from 'vitest';
+import {
+  configureSessionState,
+  flushSessionState,
+  getResumeFiles,
+  getSessionStateClient,
+  recordSessionFile,
+} from '../../src/runtime/session-state.js';
+
+const RESUME = {
+  path: 'state/session.json',
+  max_files: 2,
+  max_age_days: 7,
+  max_clients: 2,
+};
+
+describe('Session state', () => {
+  let workspace: string;
+
+  function restart(): void {
+    configureSessionState({ workspace, resume: RESUME });
+  }
+
+  beforeEach(() => {
+    workspace = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-session-'));
+    for (const file of ['a.ts', 'b.ts', 'c.ts']) {
+      fs.writeFileSync(path.join(workspace, file), '');
+    }
+    restart();
+  });
+
+  afterEach(() => {
+    configureSessionState({ workspace: process.cwd(), resume: null });
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('should reopen recent files after a restart', () => {
+    recordSessionFile('agent-1', 'a.ts');
+    recordSessionFile('agent-1', path.join(workspace, 'b.ts'));
+    recordSessionFile('agent-1', 'c.ts');
+    recordSessionFile('agent-1', 'b.ts');
+    recordSessionFile('agent-1', '../outside.ts');
+    flushSessionState();
+
+    restart();
+    expect(getResumeFiles()).toEqual([
+      path.join(workspace, 'b.ts'),
+      path.join(workspace, 'c.ts'),
+    ]);
+  });
+
+  test('should skip deleted files and forgotten clients', () => {
+    recordSessionFile('agent-1', 'a.ts');
+    recordSessionFile('agent-2', 'b.ts');
+    flushSessionState();
+
+    const file = path.join(workspace, RESUME.path);
+    const state = JSON.parse(fs.readFileSync(file, 'utf-8')) as {
+      clients: Record<string, { last_seen: string }>;
+    };
+    state.clients['agent-2']!.last_seen = '2020-01-01T00:00:00.000Z';
+    fs.writeFileSync(file, JSON.stringify(state));
+    fs.rmSync(path.join(workspace, 'a.ts'));
+
+    restart();
+    expect(getResumeFiles()).toEqual([]);
+  });
+
+  test('should know clients by identity rather than connection', () => {
+    expect(getSessionStateClient('ci', 'claude-code')).toBe('ci');
+    expect(getSessionStateClient(undefined, 'claude-code')).toBe(
+      'claude-code'
+    );
+    expect(getSessionStateClient(undefined, undefined)).toBe('local');
+  });
+
+  test('should forget the least recently seen clients beyond the cap', () => {
+    vi.useFakeTimers();
+    vi.setSystemTime(new Date('2026-01-01T00:00:00.000Z'));
+    recordSessionFile('agent-1', 'a.ts');
+    vi.setSystemTime(new Date('2026-01-01T00:01:00.000Z'));
+    recordSessionFile('agent-2', 'b.ts');
+    vi.setSystemTime(new Date('2026-01-01T00:02:00.000Z'));
+    recordSessionFile('agent-3', 'c.ts');
+    flushSessionState();
+    vi.useRealTimers();
+
+    const file = path.join(workspace, RESUME.path);
+    const state = JSON.parse(fs.readFileSync(file, 'utf-8')) as {
+      clients: Record<string, unknown>;
+    };
+    expect(Object.keys(state.clients).sort()).toEqual(['agent-2', 'agent-3']);
+  });
+
+  test('should ignore state saved for another workspace', () => {
+    recordSessionFile('agent-1', 'a.ts');
+    flushSessionState();
+
+    const file = path.join(workspace, RESUME.path);
+    const state = JSON.parse(fs.readFileSync(file, 'utf-8')) as {
+      workspace: string;
+    };
+    state.workspace = '/elsewhere';
+    fs.writeFileSync(file, JSON.stringify(state));
+
+    restart();
+    expect(getResumeFiles()).toEqual([]);
+  });
+});
@@ -0,0 +1,100 @@
+/**
+ * File Skeleton Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import { buildSkeleton } from '../../src/utils/skeleton.js';
+import { SymbolKind, type FlattenedSymbol } from '../../src/types/lsp.js';
+
+const LINES = [
+  "import { a } from 'a';",
+  "import { b } from 'b';",
+  "import { c } from 'c';",
+  '',
+  'export class Greeter {',
+  '  greet(name: string) {',
+  '    const message = `hi ${name}`;',
+  '    log(message);',
+  '    return message;',
+  '  }',
+  '',
+  '  short() {',
+  '    return 1;',
+  '  }',
+  '}',
+];
+
+function lines(start: number, end: number) {
+  return {
+    start: { line: start, character: 0 },
+    end: { line: end, character: 1 },
+  };
+}
+
+const SYMBOLS: FlattenedSymbol[] = [
+  {
+    name: 'Greeter',
+    kind: SymbolKind.Class,
+    range: lines(4, 14),
+    selectionRange: lines(4, 4),
+  },
+  {
+    name: 'greet',
+    kind: SymbolKind.Method,
+    range: lines(5, 9),
+    selectionRange: lines(5, 5),
+  },
+  {
+    name: 'message',
+    kind: SymbolKind.Variable,
+    range: lines(6, 6),
+    selectionRange: lines(6, 6),
+  },
+  {
+    name: 'short',
+    kind: SymbolKind.Method,
+    range: lines(11, 13),
+    selectionRange: lines(11, 11),
+  },
+];
+
+describe('File skeleton', () => {
+  test('should elide bodies and imports along folding ranges', () => {
+    const skeleton = buildSkeleton(LINES, SYMBOLS, [
+      { startLine: 0, endLine: 2, kind: 'imports' },
+      { startLine: 4, endLine: 13 },
+      { startLine: 5, endLine: 8 },
+      { startLine: 11, endLine: 12 },
+    ]);
+
+    expect(skeleton).toEqual([
+      " 1| import { a } from 'a';",
+      '  | ... 2 lines',
+      ' 4| ',
+      ' 5| export class Greeter {',
+      ' 6|   greet(name: string) {',
+      '  |     ... 3 lines',
+      '10|   }',
+      '11| ',
+      '12|   short() {',
+      '13|     return 1;',
+      '14|   }',
+      '15| }',
+    ]);
+  });
+
+  test('should fall back to symbol ranges without folding ranges', () => {
+    const skeleton = buildSkeleton(LINES, SYMBOLS, []);
+
+    expect(skeleton.slice(0, 3)).toEqual([
+      " 1| import { a } from 'a';",
+      " 2| import { b } from 'b';",
+      " 3| import { c } from 'c';",
+    ]);
+    expect(skeleton.slice(5, 8)).toEqual([
+      ' 6|   greet(name: string) {',
+      '  |     ... 3 lines',
+      '10|   }',
+    ]);
+  });
+});
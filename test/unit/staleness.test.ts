@@ -0,0 +1,34 @@
+/**
+ * Staleness Tests
+ */
+
+import { afterEach, describe, expect, test, vi } from 'vitest';
+import { findStaleFiles } from '../../src/tools/staleness.js';
+import { configurePathOutput } from '../../src/utils/path-format.js';
+import type { LspSession } from '../../src/runtime/lsp-session.js';
+
+describe('Staleness', () => {
+  afterEach(() => {
+    configurePathOutput({ style: 'relative', baseDirectory: process.cwd() });
+  });
+
+  test('should check every file an edit touches, by path or URI', async () => {
+    configurePathOutput({ style: 'relative', baseDirectory: '/repo' });
+    const isDocumentStale = vi.fn((filePath: string) =>
+      Promise.resolve(filePath === '/repo/src/caller.ts')
+    );
+    const session = { isDocumentStale } as unknown as LspSession;
+
+    const stale = await findStaleFiles(session, [
+      'src/target.ts',
+      'file:///repo/src/target.ts',
+      'file:///repo/src/caller.ts',
+    ]);
+
+    expect(stale).toEqual(['/repo/src/caller.ts']);
+    expect(isDocumentStale.mock.calls.map(([filePath]) => filePath)).toEqual([
+      '/repo/src/target.ts',
+      '/repo/src/caller.ts',
+    ]);
+  });
+});
@@ -0,0 +1,47 @@
+/**
+ * Symbol Cache Store Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import { createSymbolCacheStore } from '../../src/state/index.js';
+
+const URI = 'file:///workspace/src/a.ts';
+
+describe('Symbol cache store', () => {
+  test('keys document symbols by content hash', () => {
+    const store = createSymbolCacheStore();
+
+    store.setDocumentSymbols(URI, 'hash-1', [{ name: 'a' }]);
+
+    expect(store.getDocumentSymbols(URI, 'hash-1')).toEqual([{ name: 'a' }]);
+    expect(store.getDocumentSymbols(URI, 'hash-2')).toBeUndefined();
+  });
+
+  test('hands out copies of cached answers', () => {
+    const store = createSymbolCacheStore();
+    store.setWorkspaceSymbols('a', store.generation, [{ name: 'a' }]);
+
+    const first = store.getWorkspaceSymbols('a') as { name: string }[];
+    first[0]!.name = 'changed';
+
+    expect(store.getWorkspaceSymbols('a')).toEqual([{ name: 'a' }]);
+  });
+
+  test('drops every search when a file changes', () => {
+    const store = createSymbolCacheStore();
+    store.setDocumentSymbols(URI, 'hash-1', []);
+    store.setWorkspaceSymbols('a', store.generation, []);
+    store.setWorkspaceSymbols('b', store.generation, null);
+    const generation = store.generation;
+
+    store.invalidate(URI);
+
+    expect(store.getDocumentSymbols(URI, 'hash-1')).toBeUndefined();
+    expect(store.getWorkspaceSymbols('a')).toBeUndefined();
+    expect(store.getWorkspaceSymbols('b')).toBeUndefined();
+
+    // A search started before the change isn't kept
+    store.setWorkspaceSymbols('a', generation, []);
+    expect(store.getWorkspaceSymbols('a')).toBeUndefined();
+  });
+});
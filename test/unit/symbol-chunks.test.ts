@@ -0,0 +1,121 @@
+/**
+ * Symbol Chunk Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  collectChunkBody,
+  createFileChunks,
+  splitChunkBody,
+} from '../../src/map/symbol-chunks.js';
+import { SymbolKind, type FlattenedSymbol } from '../../src/types/lsp.js';
+
+function symbol(
+  name: string,
+  kind: FlattenedSymbol['kind'],
+  startLine: number,
+  endLine: number,
+  containerPath: string[] = []
+): FlattenedSymbol {
+  return {
+    name,
+    kind,
+    range: {
+      start: { line: startLine, character: 0 },
+      end: { line: endLine, character: 1 },
+    },
+    selectionRange: {
+      start: { line: startLine, character: 0 },
+      end: { line: startLine, character: name.length },
+    },
+    containerPath,
+  };
+}
+
+const lines = [
+  'class Greeter {',
+  '  prefix = "Hello";',
+  '  greet(name: string) {',
+  '    return `${this.prefix} ${name}`;',
+  '  }',
+  '}',
+];
+
+const greeter = symbol('Greeter', SymbolKind.Class, 0, 5);
+const prefix = symbol('prefix', SymbolKind.Property, 1, 1, ['Greeter']);
+const greet = symbol('greet', SymbolKind.Method, 2, 4, ['Greeter']);
+
+describe('Symbol chunks', () => {
+  test('should leave nested chunks out of a body and dedent it', () => {
+    expect(collectChunkBody(lines, greeter, [greet])).toEqual([
+      { line: 0, text: 'class Greeter {' },
+      { line: 1, text: '  prefix = "Hello";' },
+      { line: 5, text: '}' },
+    ]);
+
+    const method = collectChunkBody(lines, greet, []);
+    expect(method.map(({ text }) => text)).toEqual([
+      'greet(name: string) {',
+      '  return `${this.prefix} ${name}`;',
+      '}',
+    ]);
+  });
+
+  test('should split bodies between lines and cut long lines', () => {
+    const body = [
+      { line: 0, text: 'aaaa' },
+      { line: 1, text: 'bbbb' },
+      { line: 2, text: 'cccccccccccc' },
+    ];
+
+    expect(splitChunkBody(body, 10)).toEqual([
+      { startLine: 1, endLine: 2, text: 'aaaa\nbbbb' },
+      { startLine: 3, endLine: 3, text: 'cccccccccc' },
+    ]);
+  });
+
+  test('should emit one record per chunked symbol', () => {
+    const chunks = createFileChunks(
+      'src/greeter.ts',
+      [greeter, prefix, greet],
+      lines,
+      new Map([[greet, 'Greet someone.']]),
+      4000
+    );
+
+    expect(chunks.map((chunk) => chunk.id)).toEqual([
+      'src/greeter.ts#Greeter',
+      'src/greeter.ts#Greeter.greet',
+    ]);
+    expect(chunks[1]).toMatchObject({
+      name: 'Greeter.greet',
+      kind: 'Method',
+      signature: 'greet(name: string)',
+      doc: 'Greet someone.',
+      file: 'src/greeter.ts',
+      range: { startLine: 3, endLine: 5 },
+      part: 1,
+      parts: 1,
+    });
+    expect(chunks[1]?.hash).toMatch(/^[0-9a-f]{16}$/);
+  });
+
+  test('should number the parts of split bodies', () => {
+    const chunks = createFileChunks(
+      'src/greeter.ts',
+      [greet],
+      lines,
+      new Map(),
+      40
+    );
+
+    expect(chunks.map((chunk) => chunk.id)).toEqual([
+      'src/greeter.ts#Greeter.greet@1',
+      'src/greeter.ts#Greeter.greet@2',
+    ]);
+    expect(chunks.map((chunk) => chunk.range)).toEqual([
+      { startLine: 3, endLine: 3 },
+      { startLine: 4, endLine: 5 },
+    ]);
+  });
+});
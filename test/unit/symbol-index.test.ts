@@ -0,0 +1,174 @@
+/**
+ * Symbol Index Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { fileURLToPath, pathToFileURL } from 'url';
+import { afterEach, beforeEach, describe, test, expect, vi } from 'vitest';
+import type { LspManager } from '../../src/runtime/lsp-manager.js';
+import {
+  configureSymbolIndex,
+  invalidateSymbolIndex,
+  reconcileSymbolIndex,
+  searchSymbolIndex,
+} from '../../src/map/symbol-index.js';
+import { searchWorkspaceSymbols } from '../../src/map/workspace-symbols.js';
+import { getFileSymbols } from '../../src/map/workspace-scan.js';
+
+vi.mock('../../src/map/workspace-scan.js', async () => {
+  const actual = await vi.importActual('../../src/map/workspace-scan.js');
+  return {
+    ...actual,
+    getFileSymbols: vi.fn(),
+  };
+});
+
+const mockGetFileSymbols = vi.mocked(getFileSymbols);
+
+const INDEX = { path: 'state/index.json', max_files: 100 };
+
+const SYMBOL_NAMES: Record<string, string> = {
+  'a.ts': 'createServer',
+  'b.ts': 'listen',
+};
+
+describe('Symbol index', () => {
+  let workspace: string;
+  const manager = {
+    listProfiles: () => [{ extensions: ['.ts'] }],
+    getStartedSessions: () => [],
+    getSearchSessions: vi.fn(() => new Promise(() => {})),
+  } as unknown as LspManager;
+
+  function restart(): void {
+    configureSymbolIndex({ workspace, index: INDEX });
+  }
+
+  beforeEach(() => {
+    workspace = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-index-'));
+    for (const file of Object.keys(SYMBOL_NAMES)) {
+      fs.writeFileSync(path.join(workspace, file), 'export {};\n');
+    }
+    mockGetFileSymbols.mockReset();
+    mockGetFileSymbols.mockImplementation(async (_manager, filePath) => ({
+      symbols: [
+        {
+          name: SYMBOL_NAMES[path.basename(filePath)] ?? 'close',
+          kind: 12,
+          range: {
+            start: { line: 2, character: 0 },
+            end: { line: 4, character: 1 },
+          },
+        },
+      ],
+      lines: [],
+    }));
+    restart();
+  });
+
+  afterEach(() => {
+    configureSymbolIndex({ workspace: process.cwd(), index: null });
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('should answer searches from the index saved by a previous run', async () => {
+    expect(searchSymbolIndex('crs')).toBeNull();
+
+    await reconcileSymbolIndex(manager);
+    restart();
+
+    expect(searchSymbolIndex('crs')?.symbols).toEqual([
+      {
+        name: 'createServer',
+        kind: 12,
+        location: {
+          uri: pathToFileURL(path.join(workspace, 'a.ts')).toString(),
+          range: {
+            start: { line: 2, character: 0 },
+            end: { line: 4, character: 1 },
+          },
+        },
+        containerName: '',
+      },
+    ]);
+
+    const search = await searchWorkspaceSymbols(manager, 'listen');
+    expect(search.symbols.map((symbol) => symbol.name)).toEqual(['listen']);
+    expect(search.indexedAt).toEqual(expect.any(String));
+  });
+
+  test('should only outline files that are new or changed', async () => {
+    await reconcileSymbolIndex(manager);
+    expect(mockGetFileSymbols).toHaveBeenCalledTimes(2);
+
+    fs.rmSync(path.join(workspace, 'a.ts'));
+    fs.writeFileSync(path.join(workspace, 'b.ts'), 'export const b = 1;\n');
+    mockGetFileSymbols.mockClear();
+    restart();
+    await reconcileSymbolIndex(manager);
+
+    expect(mockGetFileSymbols).toHaveBeenCalledTimes(1);
+    expect(mockGetFileSymbols).toHaveBeenCalledWith(
+      manager,
+      path.join(workspace, 'b.ts')
+    );
+    const names = searchSymbolIndex('')?.symbols.map((symbol) => symbol.name);
+    expect(names).toEqual(['listen']);
+  });
+
+  test('should reconcile again once a watched file changes', async () => {
+    await reconcileSymbolIndex(manager);
+    mockGetFileSymbols.mockClear();
+
+    // Writing the index itself doesn't count as a change
+    invalidateSymbolIndex(path.join(workspace, INDEX.path));
+    await reconcileSymbolIndex(manager);
+    expect(mockGetFileSymbols).not.toHaveBeenCalled();
+
+    const changed = path.join(workspace, 'a.ts');
+    fs.writeFileSync(changed, 'export const a = 1;\n');
+    invalidateSymbolIndex(changed);
+    await reconcileSymbolIndex(manager);
+
+    expect(mockGetFileSymbols).toHaveBeenCalledTimes(1);
+    expect(mockGetFileSymbols).toHaveBeenCalledWith(manager, changed);
+  });
+
+  test('should only look at the paths that changed', async () => {
+    await reconcileSymbolIndex(manager);
+    mockGetFileSymbols.mockClear();
+
+    // Not reported by the watcher, so left as indexed
+    fs.writeFileSync(path.join(workspace, 'b.ts'), 'export const b = 1;\n');
+    const changed = path.join(workspace, 'a.ts');
+    fs.writeFileSync(changed, 'export const a = 1;\n');
+    invalidateSymbolIndex(changed);
+    await reconcileSymbolIndex(manager);
+
+    expect(mockGetFileSymbols).toHaveBeenCalledTimes(1);
+    expect(mockGetFileSymbols).toHaveBeenCalledWith(manager, changed);
+  });
+
+  test('should index and drop the files under a changed directory', async () => {
+    await reconcileSymbolIndex(manager);
+    const directory = path.join(workspace, 'lib');
+    fs.mkdirSync(directory);
+    fs.writeFileSync(path.join(directory, 'c.ts'), 'export {};\n');
+    invalidateSymbolIndex(directory);
+    await reconcileSymbolIndex(manager);
+
+    const files = () =>
+      searchSymbolIndex('')?.symbols.map((symbol) =>
+        path.relative(workspace, fileURLToPath(symbol.location.uri))
+      );
+    expect(files()).toEqual(['a.ts', 'b.ts', path.join('lib', 'c.ts')]);
+
+    fs.rmSync(directory, { recursive: true });
+    invalidateSymbolIndex(directory);
+    await reconcileSymbolIndex(manager);
+
+    expect(files()).toEqual(['a.ts', 'b.ts']);
+  });
+});
@@ -0,0 +1,86 @@
+/**
+ * Symbol Lookup Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import type { LspManager } from '../../src/runtime/lsp-manager.js';
+import {
+  formatLookupReport,
+  parseFilePosition,
+  runBatchQuery,
+  type LookupReport,
+} from '../../src/map/symbol-lookup.js';
+
+describe('Symbol lookup', () => {
+  test('should parse file positions from the end', () => {
+    expect(parseFilePosition('src/server.ts:12:17')).toEqual({
+      file: 'src/server.ts',
+      line: 12,
+      character: 17,
+    });
+    expect(parseFilePosition('C:\\repo\\main.go:3:1')).toEqual({
+      file: 'C:\\repo\\main.go',
+      line: 3,
+      character: 1,
+    });
+  });
+
+  test('should reject incomplete or zero-based positions', () => {
+    expect(() => parseFilePosition('src/server.ts:12')).toThrow(
+      'Expected file:line:character'
+    );
+    expect(() => parseFilePosition('src/server.ts:0:4')).toThrow('1-based');
+  });
+
+  test('should print one location per line with kind and name', () => {
+    const report: LookupReport = {
+      command: 'outline',
+      target: 'src/server.ts',
+      total: 3,
+      locations: [
+        {
+          file: 'src/server.ts',
+          line: 3,
+          character: 14,
+          name: 'Server',
+          kind: 'Class',
+        },
+        {
+          file: 'src/server.ts',
+          line: 5,
+          character: 3,
+          name: 'listen',
+          kind: 'Method',
+          container: 'Server',
+        },
+      ],
+      truncated: true,
+      errors: [],
+    };
+
+    expect(formatLookupReport(report, 'markdown')).toBe(
+      [
+        '# Outline of src/server.ts',
+        '3 location(s)',
+        'Showing the first 2',
+        '- src/server.ts:3:14 Class Server\n- src/server.ts:5:3 Method Server.listen',
+      ].join('\n\n')
+    );
+    expect(JSON.parse(formatLookupReport(report, 'json'))).toEqual(report);
+  });
+
+  test('should answer malformed batch lines with an error result', async () => {
+    const manager = {} as LspManager;
+
+    expect(await runBatchQuery(manager, '{"command":', 100)).toEqual({
+      error: expect.stringContaining('Invalid JSON') as string,
+    });
+    expect(
+      await runBatchQuery(
+        manager,
+        '{"id":7,"command":"rename","target":"a.ts"}',
+        100
+      )
+    ).toEqual({ error: expect.stringContaining('command') as string });
+  });
+});
@@ -0,0 +1,69 @@
+/**
+ * Symbol Modifier Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  formatSymbolModifiers,
+  inferSymbolModifiers,
+  matchesModifierFilter,
+} from '../../src/utils/symbol-modifiers.js';
+
+describe('Symbol modifiers', () => {
+  test('should read TypeScript keywords before the name', () => {
+    expect(
+      inferSymbolModifiers('export async function ', 'load', 'src/index.ts')
+    ).toEqual({ visibility: 'public', modifiers: ['async'] });
+    expect(
+      inferSymbolModifiers('  private static readonly ', 'cache', 'a.ts')
+    ).toEqual({ visibility: 'private', modifiers: ['static', 'readonly'] });
+  });
+
+  test('should distinguish Rust pub and pub(crate)', () => {
+    expect(inferSymbolModifiers('pub async fn ', 'run', 'lib.rs')).toEqual({
+      visibility: 'public',
+      modifiers: ['async'],
+    });
+    expect(inferSymbolModifiers('pub(crate) fn ', 'helper', 'lib.rs')).toEqual(
+      { visibility: 'crate', modifiers: [] }
+    );
+    expect(inferSymbolModifiers('fn ', 'inner', 'lib.rs').visibility).toBe(
+      'private'
+    );
+  });
+
+  test('should fall back to Go and Python naming conventions', () => {
+    expect(inferSymbolModifiers('func ', 'Serve', 'main.go').visibility).toBe(
+      'public'
+    );
+    expect(inferSymbolModifiers('func ', 'serve', 'main.go').visibility).toBe(
+      'private'
+    );
+    expect(
+      inferSymbolModifiers('@staticmethod def ', '_parse', 'app.py')
+    ).toEqual({ visibility: 'private', modifiers: ['static'] });
+  });
+
+  test('should mark deprecated symbols', () => {
+    expect(
+      inferSymbolModifiers('public ', 'old', 'Api.java', true).modifiers
+    ).toEqual(['deprecated']);
+  });
+
+  test('should ignore identifiers that collide with object prototype keys', () => {
+    expect(inferSymbolModifiers('constructor ', 'x', 'a.ts').modifiers).toEqual(
+      []
+    );
+  });
+
+  test('should filter by visibility and required modifiers', () => {
+    const info = { visibility: 'public' as const, modifiers: ['async' as const] };
+    expect(matchesModifierFilter(info, { visibility: ['public'] })).toBe(true);
+    expect(matchesModifierFilter(info, { visibility: ['private'] })).toBe(
+      false
+    );
+    expect(matchesModifierFilter(info, { modifiers: ['async'] })).toBe(true);
+    expect(matchesModifierFilter(info, { modifiers: ['static'] })).toBe(false);
+    expect(formatSymbolModifiers(info)).toBe('[public async]');
+  });
+});
@@ -0,0 +1,119 @@
+/**
+ * Symbol Query Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import { SymbolKind } from '../../src/types/lsp.js';
+import {
+  compareCount,
+  getServerQuery,
+  matchesSymbolQuery,
+  parseSymbolQuery,
+} from '../../src/utils/symbol-query.js';
+
+describe('Symbol query parsing', () => {
+  test('should split filters from free text', () => {
+    const query = parseSymbolQuery(
+      'kind:function vis:pub path:src/** name:~^create refs:>10 User'
+    );
+
+    expect(query.text).toBe('User');
+    expect(query.filters).toEqual({
+      kinds: [SymbolKind.Function],
+      visibility: ['public'],
+      paths: ['src/**'],
+      name: { pattern: '^create', regex: true },
+      refs: { operator: '>', count: 10 },
+      owners: [],
+    });
+    expect(query.filterTokens).toHaveLength(5);
+  });
+
+  test('should accept kind lists and aliases', () => {
+    expect(parseSymbolQuery('kind:fn,method').filters.kinds).toEqual([
+      SymbolKind.Function,
+      SymbolKind.Method,
+    ]);
+    expect(parseSymbolQuery('kind:type').filters.kinds).toContain(
+      SymbolKind.Interface
+    );
+  });
+
+  test('should keep tokens with unknown keys in the text', () => {
+    const query = parseSymbolQuery('std::vector http:get owner:@core');
+
+    expect(query.text).toBe('std::vector http:get');
+    expect(query.filters.owners).toEqual(['@core']);
+  });
+
+  test('should default refs to an exact count', () => {
+    expect(parseSymbolQuery('refs:0').filters.refs).toEqual({
+      operator: '=',
+      count: 0,
+    });
+  });
+
+  test('should reject invalid filter values', () => {
+    expect(() => parseSymbolQuery('kind:widget')).toThrow(
+      'Unknown symbol kind'
+    );
+    expect(() => parseSymbolQuery('vis:secret')).toThrow('Unknown visibility');
+    expect(() => parseSymbolQuery('refs:many')).toThrow('Invalid refs filter');
+    expect(() => parseSymbolQuery('name:~(')).toThrow('Invalid name pattern');
+  });
+});
+
+describe('Server query', () => {
+  test('should prefer the free text', () => {
+    expect(getServerQuery(parseSymbolQuery('User name:~^create'))).toBe(
+      'User'
+    );
+  });
+
+  test('should fall back to the longest literal of a name pattern', () => {
+    expect(getServerQuery(parseSymbolQuery('name:~^create\\w+Handler$'))).toBe(
+      'Handler'
+    );
+    expect(getServerQuery(parseSymbolQuery('name:Repo'))).toBe('Repo');
+  });
+
+  test('should send an empty query for alternatives', () => {
+    expect(getServerQuery(parseSymbolQuery('name:~^(get|set)'))).toBe('');
+  });
+});
+
+describe('Symbol query matching', () => {
+  const symbol = {
+    name: 'createUser',
+    kind: SymbolKind.Function,
+    path: 'src/users/factory.ts',
+  };
+
+  test('should match kind, path and name filters', () => {
+    expect(
+      matchesSymbolQuery(
+        symbol,
+        parseSymbolQuery('kind:function path:src/** name:~^create').filters
+      )
+    ).toBe(true);
+    expect(
+      matchesSymbolQuery(symbol, parseSymbolQuery('kind:class').filters)
+    ).toBe(false);
+    expect(
+      matchesSymbolQuery(symbol, parseSymbolQuery('path:test/**').filters)
+    ).toBe(false);
+  });
+
+  test('should match literal names case-insensitively', () => {
+    expect(
+      matchesSymbolQuery(symbol, parseSymbolQuery('name:USER').filters)
+    ).toBe(true);
+  });
+
+  test('should compare reference counts', () => {
+    expect(compareCount(11, { operator: '>', count: 10 })).toBe(true);
+    expect(compareCount(10, { operator: '>', count: 10 })).toBe(false);
+    expect(compareCount(10, { operator: '<=', count: 10 })).toBe(true);
+    expect(compareCount(3, { operator: '=', count: 3 })).toBe(true);
+  });
+});
@@ -0,0 +1,49 @@
+/**
+ * Test-Code Classification Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  isTestFile,
+  isTestSymbol,
+  matchesTestFilter,
+} from '../../src/utils/test-classification.js';
+
+describe('Test-code classification', () => {
+  test.each([
+    'pkg/server/handler_test.go',
+    'src/__tests__/app.tsx',
+    'src/app.test.ts',
+    'src/app.spec.js',
+    'tests/test_models.py',
+    'src/test/java/com/example/AppTest.java',
+    'spec/models/user_spec.rb',
+    'Project.Tests/ServiceTests.cs',
+  ])('should classify %s as test code', (filePath) => {
+    expect(isTestFile(filePath)).toBe(true);
+  });
+
+  test.each([
+    'pkg/server/handler.go',
+    'src/app.ts',
+    'src/main/java/com/example/App.java',
+    'src/latest/release.py',
+    'lib/contest.rb',
+  ])('should classify %s as production code', (filePath) => {
+    expect(isTestFile(filePath)).toBe(false);
+  });
+
+  test('should classify Rust inline test modules and test functions', () => {
+    expect(isTestSymbol('src/lib.rs', ['tests'])).toBe(true);
+    expect(isTestSymbol('src/lib.rs', [], '#[cfg(test)] mod ')).toBe(true);
+    expect(isTestSymbol('src/lib.rs', ['parser'], '#[test] fn ')).toBe(true);
+    expect(isTestSymbol('src/lib.rs', ['parser'], 'pub fn ')).toBe(false);
+  });
+
+  test('should apply include, exclude and only filters', () => {
+    expect(matchesTestFilter(true, 'include')).toBe(true);
+    expect(matchesTestFilter(true, 'exclude')).toBe(false);
+    expect(matchesTestFilter(false, 'exclude')).toBe(true);
+    expect(matchesTestFilter(false, 'only')).toBe(false);
+  });
+});
@@ -0,0 +1,87 @@
+/**
+ * Text Helper Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  detectIndentation,
+  detectLineEnding,
+  sliceGraphemes,
+  splitLines,
+  truncateGraphemes,
+} from '../../src/utils/text.js';
+import { createSignaturePreview } from '../../src/tools/enrichment.js';
+
+describe('Text helpers', () => {
+  describe('line endings', () => {
+    test('should detect CRLF files', () => {
+      expect(detectLineEnding('a\r\nb\r\nc')).toBe('\r\n');
+    });
+
+    test('should default to LF for LF and empty files', () => {
+      expect(detectLineEnding('a\nb')).toBe('\n');
+      expect(detectLineEnding('')).toBe('\n');
+    });
+
+    test('should split lines without carriage returns', () => {
+      expect(splitLines('a\r\nb\nc')).toEqual(['a', 'b', 'c']);
+    });
+  });
+
+  describe('sliceGraphemes', () => {
+    test('should slice ASCII lines by offset', () => {
+      expect(sliceGraphemes('const value = 1;', 6, 11)).toBe('value');
+    });
+
+    test('should not split surrogate pairs', () => {
+      // '😀' occupies two UTF-16 units starting at offset 4
+      const line = 'let 😀 = 1;';
+      expect(sliceGraphemes(line, 5, 8)).toBe('😀 =');
+      expect(sliceGraphemes(line, 0, 5)).toBe('let 😀');
+    });
+
+    test('should keep combining marks with their base character', () => {
+      const line = 'cafe\u0301 bar';
+      expect(sliceGraphemes(line, 0, 4)).toBe('cafe\u0301');
+    });
+  });
+
+  describe('indentation', () => {
+    test('should detect the space indent size', () => {
+      const content =
+        'class A {\n    /**\n     * Doc\n     */\n    run() {\n        go();\n    }\n}\n';
+      expect(detectIndentation(content)).toEqual({
+        tabSize: 4,
+        insertSpaces: true,
+      });
+    });
+
+    test('should detect tab indentation', () => {
+      expect(detectIndentation('func a() {\n\tgo()\n\tstop()\n}\n')).toEqual({
+        tabSize: 4,
+        insertSpaces: false,
+      });
+    });
+
+    test('should default to two spaces for flat files', () => {
+      expect(detectIndentation('a = 1\nb = 2\n')).toEqual({
+        tabSize: 2,
+        insertSpaces: true,
+      });
+    });
+  });
+
+  describe('truncateGraphemes', () => {
+    test('should stop before a cluster that would be split', () => {
+      expect(truncateGraphemes('ab😀cd', 3)).toBe('ab');
+      expect(truncateGraphemes('ab😀cd', 4)).toBe('ab😀');
+    });
+  });
+
+  test('signature previews should not contain lone surrogates', () => {
+    const snippet = `${'x'.repeat(96)}😀😀😀`;
+    const preview = createSignaturePreview(snippet, 100);
+    expect(preview.endsWith('...')).toBe(true);
+    expect(preview).not.toMatch(/[\uD800-\uDBFF](?![\uDC00-\uDFFF])/);
+  });
+});
@@ -0,0 +1,107 @@
+/**
+ * Tool Hooks Tests
+ */
+
+import { afterEach, describe, test, expect } from 'vitest';
+import type { CallToolResult } from '@modelcontextprotocol/sdk/types.js';
+import {
+  applyToolHooks,
+  configureToolHooks,
+  getToolHooks,
+} from '../../src/tools/hooks.js';
+
+const RESULT: CallToolResult = {
+  content: [{ type: 'text', text: 'Found 1 match in /home/alice/src/a.ts' }],
+};
+
+// Hook running a node script on the JSON it reads from stdin; scripts avoid
+// double quotes so the shell passes them through unchanged
+function nodeHook(script: string) {
+  const source = `let input = '';
+process.stdin.on('data', (chunk) => (input += chunk));
+process.stdin.on('end', () => { const hook = JSON.parse(input); ${script} });`;
+  return {
+    command: `"${process.execPath}" -e "${source.replace(/\n/g, ' ')}"`,
+    timeout_ms: 5000,
+  };
+}
+
+describe('Tool hooks', () => {
+  afterEach(() => {
+    configureToolHooks({ workspace: process.cwd(), hooks: {} });
+  });
+
+  test('should run tool hooks before hooks for every tool', () => {
+    const searchHook = { command: 'search-hook', timeout_ms: 1000 };
+    const allHook = { command: 'all-hook', timeout_ms: 1000 };
+    configureToolHooks({
+      workspace: process.cwd(),
+      hooks: { search: [searchHook], '*': [allHook] },
+    });
+
+    expect(getToolHooks('search')).toEqual([searchHook, allHook]);
+    expect(getToolHooks('outline')).toEqual([allHook]);
+  });
+
+  test('should replace the result with what the hook prints', async () => {
+    configureToolHooks({
+      workspace: process.cwd(),
+      hooks: {
+        search: [
+          nodeHook(`const text = hook.result.content[0].text;
+process.stdout.write(JSON.stringify({ content: [{ type: 'text',
+  text: text.replace('/home/alice', '~') + ' (' + hook.tool + ')' }] }));`),
+        ],
+      },
+    });
+
+    await expect(
+      applyToolHooks('search', { query: 'a' }, RESULT)
+    ).resolves.toEqual({
+      content: [{ type: 'text', text: 'Found 1 match in ~/src/a.ts (search)' }],
+    });
+  });
+
+  test('should keep the result when the hook prints nothing', async () => {
+    configureToolHooks({
+      workspace: process.cwd(),
+      hooks: { '*': [nodeHook('')] },
+    });
+
+    await expect(applyToolHooks('outline', {}, RESULT)).resolves.toBe(RESULT);
+  });
+
+  test('should fail the call when a hook fails', async () => {
+    configureToolHooks({
+      workspace: process.cwd(),
+      hooks: { '*': [nodeHook("console.error('denied'); process.exit(3);")] },
+    });
+
+    const result = await applyToolHooks('outline', {}, RESULT);
+    expect(result.isError).toBe(true);
+    expect(result._meta).toEqual({
+      errorCode: 'HOOK_FAILED',
+      errorCategory: 'config',
+    });
+    expect(result.content[0]).toMatchObject({
+      text: expect.stringContaining('exited with code 3: denied') as string,
+    });
+  });
+
+  test('should fail the call when a hook prints too much', async () => {
+    configureToolHooks({
+      workspace: process.cwd(),
+      hooks: {
+        '*': [nodeHook("process.stdout.write('x'.repeat(5 * 1024 * 1024));")],
+      },
+    });
+
+    const result = await applyToolHooks('outline', {}, RESULT);
+    expect(result.isError).toBe(true);
+    expect(result.content[0]).toMatchObject({
+      text: expect.stringContaining(
+        'printed more than 4194304 bytes'
+      ) as string,
+    });
+  });
+});
@@ -0,0 +1,70 @@
+/**
+ * Tool Middleware Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import type { CallToolResult } from '@modelcontextprotocol/sdk/types.js';
+import {
+  authScopeMiddleware,
+  composeToolMiddlewares,
+  errorResultMiddleware,
+  type ToolCall,
+  type ToolMiddleware,
+} from '../../src/tools/middleware.js';
+
+const RESULT: CallToolResult = {
+  content: [{ type: 'text', text: 'ok' }],
+};
+
+function createCall(extra: unknown = {}): ToolCall {
+  return { tool: 'rename', arguments: {}, extra, client: 'local' };
+}
+
+describe('Tool middleware', () => {
+  test('should run middlewares outermost first and results back out', async () => {
+    const steps: string[] = [];
+    const step =
+      (name: string): ToolMiddleware =>
+      async (_call, next) => {
+        steps.push(`${name} in`);
+        const result = await next();
+        steps.push(`${name} out`);
+        return result;
+      };
+
+    const middleware = composeToolMiddlewares([step('outer'), step('inner')]);
+    const result = await middleware(createCall(), async () => {
+      steps.push('tool');
+      return RESULT;
+    });
+
+    expect(result).toBe(RESULT);
+    expect(steps).toEqual([
+      'outer in',
+      'inner in',
+      'tool',
+      'inner out',
+      'outer out',
+    ]);
+  });
+
+  test('should refuse clients without the scope as an error result', async () => {
+    const middleware = composeToolMiddlewares([
+      errorResultMiddleware,
+      authScopeMiddleware,
+    ]);
+    const authInfo = { token: 't', clientId: 'reader', scopes: ['read'] };
+
+    const result = await middleware(createCall({ authInfo }), async () => {
+      throw new Error('The tool should not run');
+    });
+
+    expect(result.isError).toBe(true);
+    expect(result._meta).toEqual({
+      errorCode: 'FORBIDDEN',
+      errorCategory: 'auth',
+    });
+    const allowed = await middleware(createCall(), async () => RESULT);
+    expect(allowed).toBe(RESULT);
+  });
+});
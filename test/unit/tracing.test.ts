@@ -0,0 +1,135 @@
+/**
+ * Tracing Tests
+ */
+
+import { afterEach, describe, test, expect, vi } from 'vitest';
+import {
+  configureTracing,
+  flushTraces,
+  parseTraceparent,
+  resolveTracingSettings,
+  SPAN_KIND_CLIENT,
+  SPAN_KIND_SERVER,
+  withSpan,
+} from '../../src/utils/tracing.js';
+
+const TRACE_ID = '4bf92f3577b34da6a3ce929d0e0e4736';
+const PARENT_SPAN_ID = '00f067aa0ba902b7';
+
+interface ExportedSpan {
+  traceId: string;
+  spanId: string;
+  parentSpanId?: string;
+  name: string;
+  status: { code: number; message?: string };
+}
+
+interface ExportedPayload {
+  resourceSpans: { scopeSpans: { spans: ExportedSpan[] }[] }[];
+}
+
+describe('Tracing settings', () => {
+  test('should derive the traces endpoint from the base endpoint', () => {
+    expect(
+      resolveTracingSettings({
+        OTEL_EXPORTER_OTLP_ENDPOINT: 'http://collector:4318/',
+        OTEL_EXPORTER_OTLP_HEADERS: 'authorization=Bearer%20abc,x-team=infra',
+      })
+    ).toEqual({
+      endpoint: 'http://collector:4318/v1/traces',
+      serviceName: 'symbols',
+      headers: { authorization: 'Bearer abc', 'x-team': 'infra' },
+    });
+  });
+
+  test('should stay disabled without an endpoint or when disabled', () => {
+    expect(resolveTracingSettings({}).endpoint).toBeNull();
+    expect(
+      resolveTracingSettings({
+        OTEL_EXPORTER_OTLP_TRACES_ENDPOINT: 'http://collector/v1/traces',
+        OTEL_SDK_DISABLED: 'true',
+      }).endpoint
+    ).toBeNull();
+  });
+
+  test('should parse W3C traceparent values', () => {
+    expect(parseTraceparent(`00-${TRACE_ID}-${PARENT_SPAN_ID}-01`)).toEqual({
+      traceId: TRACE_ID,
+      spanId: PARENT_SPAN_ID,
+    });
+    expect(
+      parseTraceparent(`00-${'0'.repeat(32)}-${PARENT_SPAN_ID}-01`)
+    ).toBeNull();
+    expect(parseTraceparent('not-a-traceparent')).toBeNull();
+    expect(parseTraceparent(undefined)).toBeNull();
+  });
+});
+
+describe('Spans', () => {
+  afterEach(() => {
+    configureTracing({ endpoint: null, serviceName: 'symbols', headers: {} });
+    vi.unstubAllGlobals();
+  });
+
+  test('should run without recording when tracing is disabled', async () => {
+    const fetch = vi.fn();
+    vi.stubGlobal('fetch', fetch);
+
+    await expect(
+      withSpan('tools/call search', SPAN_KIND_SERVER, {}, async () => 42)
+    ).resolves.toBe(42);
+    await flushTraces();
+    expect(fetch).not.toHaveBeenCalled();
+  });
+
+  test('should nest spans under the client trace context', async () => {
+    const fetch = vi.fn(async () => new Response(null, { status: 200 }));
+    vi.stubGlobal('fetch', fetch);
+    configureTracing({
+      endpoint: 'http://collector/v1/traces',
+      serviceName: 'symbols',
+      headers: {},
+    });
+
+    await withSpan(
+      'tools/call references',
+      SPAN_KIND_SERVER,
+      { 'mcp.tool.name': 'references' },
+      async () => {
+        await withSpan(
+          'textDocument/references',
+          SPAN_KIND_CLIENT,
+          { 'rpc.method': 'textDocument/references' },
+          async () => {
+            throw new Error('server crashed');
+          }
+        ).catch(() => undefined);
+      },
+      { traceId: TRACE_ID, spanId: PARENT_SPAN_ID }
+    );
+    await flushTraces();
+
+    expect(fetch).toHaveBeenCalledOnce();
+    const [url, init] = fetch.mock.calls[0] as unknown as [
+      string,
+      { body: string },
+    ];
+    expect(url).toBe('http://collector/v1/traces');
+    const payload = JSON.parse(init.body) as ExportedPayload;
+    const [lspSpan, toolSpan] =
+      payload.resourceSpans[0]!.scopeSpans[0]!.spans;
+
+    expect(toolSpan).toMatchObject({
+      traceId: TRACE_ID,
+      parentSpanId: PARENT_SPAN_ID,
+      name: 'tools/call references',
+      status: { code: 1 },
+    });
+    expect(lspSpan).toMatchObject({
+      traceId: TRACE_ID,
+      parentSpanId: toolSpan!.spanId,
+      name: 'textDocument/references',
+      status: { code: 2, message: 'server crashed' },
+    });
+  });
+});
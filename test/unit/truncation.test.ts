@@ -0,0 +1,62 @@
+/**
+ * Truncation Marker Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  formatPageMarker,
+  formatPageRange,
+  formatTruncationMarker,
+  paginate,
+} from '../../src/tools/truncation.js';
+
+const items = Array.from({ length: 25 }, (_, index) => index);
+
+describe('Truncation', () => {
+  describe('paginate', () => {
+    test('should return the first page with a continuation offset', () => {
+      const page = paginate(items, 0, 10);
+      expect(page.items).toEqual([0, 1, 2, 3, 4, 5, 6, 7, 8, 9]);
+      expect(page.total).toBe(25);
+      expect(page.nextOffset).toBe(10);
+    });
+
+    test('should omit the continuation offset on the last page', () => {
+      const page = paginate(items, 20, 10);
+      expect(page.items).toEqual([20, 21, 22, 23, 24]);
+      expect(page.nextOffset).toBeUndefined();
+    });
+
+    test('should clamp offsets past the end', () => {
+      const page = paginate(items, 40, 10);
+      expect(page.items).toEqual([]);
+      expect(formatPageRange(page)).toBe('offset 25 is past the end');
+    });
+  });
+
+  describe('markers', () => {
+    test('should include omitted count and continuation handle', () => {
+      const page = paginate(items, 10, 10);
+      expect(formatPageRange(page)).toBe('showing 11-20');
+      expect(formatPageMarker(page, 'match')).toBe(
+        '... 5 matches not shown (truncated: continue with offset 20)'
+      );
+    });
+
+    test('should not emit a marker when nothing is omitted', () => {
+      expect(formatPageMarker(paginate(items, 0, 50), 'reference')).toBe(
+        undefined
+      );
+      expect(formatTruncationMarker(0, 'reference')).toBeUndefined();
+    });
+
+    test('should pluralize nouns', () => {
+      expect(formatTruncationMarker(1, 'property')).toBe(
+        '... 1 property not shown'
+      );
+      expect(formatTruncationMarker(3, 'property')).toBe(
+        '... 3 properties not shown'
+      );
+    });
+  });
+});
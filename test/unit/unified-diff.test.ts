@@ -0,0 +1,68 @@
+/**
+ * Unified Diff Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  createUnifiedDiff,
+  getChangedLineRanges,
+} from '../../src/utils/unified-diff.js';
+
+const lines = (count: number) =>
+  Array.from({ length: count }, (_, index) => `line ${index + 1}`);
+
+describe('Unified diff', () => {
+  test('should return nothing for identical content', () => {
+    expect(createUnifiedDiff('a.ts', 'same\n', 'same\n')).toBe('');
+  });
+
+  test('should show removals before additions with context', () => {
+    const diff = createUnifiedDiff('a.ts', 'a\nb\nc\n', 'a\nB\nc\n');
+
+    expect(diff.split('\n')).toEqual([
+      '--- a/a.ts',
+      '+++ b/a.ts',
+      '@@ -1,3 +1,3 @@',
+      ' a',
+      '-b',
+      '+B',
+      ' c',
+    ]);
+  });
+
+  test('should split distant changes into separate hunks', () => {
+    const before = lines(20);
+    const after = [...before];
+    after.splice(2, 1);
+    after[13] = 'changed';
+
+    const diff = createUnifiedDiff(
+      'a.ts',
+      `${before.join('\n')}\n`,
+      `${after.join('\n')}\n`
+    );
+
+    expect(diff.split('\n').filter((line) => line.startsWith('@@'))).toEqual([
+      '@@ -1,6 +1,5 @@',
+      '@@ -12,7 +11,7 @@',
+    ]);
+    expect(diff).toContain('-line 3');
+    expect(diff).toContain('-line 15\n+changed');
+  });
+
+  test('should number empty ranges from the line before', () => {
+    expect(createUnifiedDiff('new.ts', '', 'x\n')).toBe(
+      ['--- a/new.ts', '+++ b/new.ts', '@@ -0,0 +1,1 @@', '+x'].join('\n')
+    );
+  });
+
+  test('should list the added and changed lines of the new content', () => {
+    const before = ['a', 'b', 'c', 'd', 'e'].join('\n');
+    const after = ['a', 'B', 'new', 'c', 'e', 'f'].join('\n');
+
+    expect(getChangedLineRanges(before, after)).toEqual([
+      { start: 1, end: 2 },
+      { start: 5, end: 5 },
+    ]);
+  });
+});
@@ -0,0 +1,134 @@
+/**
+ * Usage Report Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import {
+  computeHotspots,
+  computeModuleUsage,
+  formatUsageReport,
+  type UsageReport,
+} from '../../src/map/usage-report.js';
+
+describe('Usage report', () => {
+  test('should count distinct modules for fan-in and fan-out', () => {
+    const modules = computeModuleUsage(
+      ['api', 'core', 'cli'],
+      [
+        { from: 'api', to: 'core' },
+        { from: 'api', to: 'core' },
+        { from: 'cli', to: 'core' },
+        { from: 'cli', to: 'api' },
+        { from: 'core', to: 'core' },
+      ]
+    );
+
+    expect(modules).toEqual([
+      {
+        module: 'api',
+        fanIn: 1,
+        fanOut: 1,
+        incomingReferences: 1,
+        outgoingReferences: 2,
+      },
+      {
+        module: 'cli',
+        fanIn: 0,
+        fanOut: 2,
+        incomingReferences: 0,
+        outgoingReferences: 2,
+      },
+      {
+        module: 'core',
+        fanIn: 2,
+        fanOut: 0,
+        incomingReferences: 3,
+        outgoingReferences: 0,
+      },
+    ]);
+  });
+
+  test('should rank hotspots by references times commits', () => {
+    const hotspots = computeHotspots(
+      new Map([
+        ['src/a.ts', 10],
+        ['src/b.ts', 3],
+        ['src/c.ts', 40],
+      ]),
+      new Map([
+        ['src/a.ts', 2],
+        ['src/b.ts', 9],
+        ['README.md', 5],
+      ])
+    );
+
+    expect(hotspots).toEqual([
+      { file: 'src/b.ts', references: 3, commits: 9, score: 27 },
+      { file: 'src/a.ts', references: 10, commits: 2, score: 20 },
+    ]);
+  });
+
+  const report: UsageReport = {
+    workspace: '/project',
+    since: '90 days ago',
+    symbols: [
+      {
+        name: 'Parser.parse',
+        kind: 'Method',
+        file: 'src/parser.ts',
+        line: 12,
+        references: 8,
+        externalReferences: 5,
+      },
+      {
+        name: 'format, pretty',
+        kind: 'Function',
+        file: 'src/format.ts',
+        line: 3,
+        references: 1,
+        externalReferences: 0,
+      },
+    ],
+    modules: [
+      {
+        module: 'src',
+        fanIn: 1,
+        fanOut: 0,
+        incomingReferences: 5,
+        outgoingReferences: 0,
+      },
+    ],
+    hotspots: [{ file: 'src/parser.ts', references: 8, commits: 4, score: 32 }],
+    truncated: false,
+  };
+
+  test('should render the top entries as markdown', () => {
+    const text = formatUsageReport(report, 'markdown', 1);
+
+    expect(text).toContain(
+      '- Parser.parse (Method) - 8 reference(s), 5 from other modules - src/parser.ts:12'
+    );
+    expect(text).not.toContain('format, pretty');
+    expect(text).toContain('- src - fan-in 1, fan-out 0 (5 in, 0 out)');
+    expect(text).toContain('## Hotspots (commits since 90 days ago)');
+    expect(text).toContain(
+      '- src/parser.ts - score 32 (8 reference(s) x 4 commit(s))'
+    );
+  });
+
+  test('should export every row as CSV with escaped values', () => {
+    const lines = formatUsageReport(report, 'csv', 1).split('\n');
+
+    expect(lines[0]).toBe(
+      'section,name,kind,file,line,references,external_references,fan_in,fan_out,commits,score'
+    );
+    expect(lines).toContain(
+      'symbol,Parser.parse,Method,src/parser.ts,12,8,5,,,,'
+    );
+    expect(lines).toContain(
+      'symbol,"format, pretty",Function,src/format.ts,3,1,0,,,,'
+    );
+    expect(lines).toContain('module,src,,,,5,,1,0,,');
+    expect(lines).toContain('hotspot,,,src/parser.ts,,8,,,,4,32');
+  });
+});
@@ -103,13 +103,19 @@ function createMockContext(overrides: MockSessionOverrides = {}): LspSession {
     stop: vi.fn(() => Promise.resolve()),
     restart: vi.fn(() => Promise.resolve()),
     request: vi.fn(),
+    getServerCapabilities: vi.fn(() => null),
     getWorkspaceState: vi.fn(() => workspaceState),
     getWorkspaceLoaderStore: vi.fn(() => workspaceLoaderStore),
     getDiagnosticsStore: vi.fn(() => ({})),
     getDiagnosticProviderStore: vi.fn(() => ({})),
     getWindowLogStore: vi.fn(() => ({ getMessages: vi.fn() })),
+    getProgressStore: vi.fn(() => ({ getActive: () => [] }) as never),
     executeWithCursorContext: vi.fn(),
     executeWithDocumentLifecycle: vi.fn(),
+    isDocumentStale: vi.fn(() => Promise.resolve(false)),
+    setDocumentOverlay: vi.fn(() =>
+      Promise.resolve({ ok: true as const, data: undefined })
+    ),
     claimDocument: vi.fn((filePath: string) => filePath),
     releaseDocument: vi.fn(() => null),
     listOwnedDocuments: vi.fn(() => []),
@@ -0,0 +1,315 @@
+/**
+ * WASM Plugin Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { pathToFileURL } from 'url';
+import { afterEach, beforeEach, describe, test, expect } from 'vitest';
+import type { CallToolResult } from '@modelcontextprotocol/sdk/types.js';
+import type { LspManager } from '../../src/runtime/lsp-manager.js';
+import { getFileSymbols } from '../../src/map/workspace-scan.js';
+import { searchWorkspaceSymbols } from '../../src/map/workspace-symbols.js';
+import {
+  applyWasmEnrichers,
+  configureWasmPlugins,
+  extractSymbolsWithPlugin,
+  findSymbolPlugin,
+  loadWasmPlugin,
+  type WasmPluginConfig,
+} from '../../src/plugins/wasm-plugins.js';
+
+function unsignedLeb(value: number): number[] {
+  const bytes: number[] = [];
+  do {
+    let byte = value & 0x7f;
+    value >>>= 7;
+    if (value !== 0) byte |= 0x80;
+    bytes.push(byte);
+  } while (value !== 0);
+  return bytes;
+}
+
+function signedLeb(value: bigint): number[] {
+  const bytes: number[] = [];
+  for (;;) {
+    const byte = Number(value & 0x7fn);
+    value >>= 7n;
+    const done =
+      (value === 0n && (byte & 0x40) === 0) ||
+      (value === -1n && (byte & 0x40) !== 0);
+    bytes.push(done ? byte : byte | 0x80);
+    if (done) return bytes;
+  }
+}
+
+function name(text: string): number[] {
+  const bytes = [...new TextEncoder().encode(text)];
+  return [...unsignedLeb(bytes.length), ...bytes];
+}
+
+function section(id: number, entries: number[][]): number[] {
+  const body = [...unsignedLeb(entries.length), ...entries.flat()];
+  return [id, ...unsignedLeb(body.length), ...body];
+}
+
+/**
+ * Build a plugin whose exports ignore their input and return fixed JSON
+ * from a data segment, or loop forever with `spin`. `alloc` bumps a heap
+ * pointer starting at 1024.
+ */
+function buildPlugin(
+  outputs: { extract_symbols?: string; enrich?: string },
+  options: { imports?: boolean; spin?: boolean } = {}
+): Uint8Array {
+  const imported = options.imports ? 1 : 0;
+  const exported = Object.entries(outputs);
+  const segments: number[][] = [];
+  const bodies: number[][] = [
+    // global.get 0, global.get 0, local.get 0, i32.add, global.set 0
+    [0x00, 0x23, 0x00, 0x23, 0x00, 0x20, 0x00, 0x6a, 0x24, 0x00, 0x0b],
+  ];
+  let offset = 64;
+  for (const [, json] of exported) {
+    const bytes = [...new TextEncoder().encode(json)];
+    segments.push([
+      0x00,
+      0x41,
+      ...signedLeb(BigInt(offset)),
+      0x0b,
+      ...unsignedLeb(bytes.length),
+      ...bytes,
+    ]);
+    const packed = (BigInt(offset) << 32n) | BigInt(bytes.length);
+    bodies.push(
+      options.spin
+        ? // loop, br 0, end, unreachable
+          [0x00, 0x03, 0x40, 0x0c, 0x00, 0x0b, 0x00, 0x0b]
+        : [0x00, 0x42, ...signedLeb(packed), 0x0b]
+    );
+    offset += bytes.length;
+  }
+
+  return new Uint8Array([
+    0x00,
+    0x61,
+    0x73,
+    0x6d,
+    0x01,
+    0x00,
+    0x00,
+    0x00,
+    // (i32) -> i32 and (i32, i32) -> i64
+    ...section(1, [
+      [0x60, 0x01, 0x7f, 0x01, 0x7f],
+      [0x60, 0x02, 0x7f, 0x7f, 0x01, 0x7e],
+    ]),
+    ...(imported
+      ? section(2, [[...name('env'), ...name('log'), 0x00, 0x00]])
+      : []),
+    ...section(3, [[0x00], ...exported.map(() => [0x01])]),
+    ...section(5, [[0x00, 0x01]]),
+    ...section(6, [[0x7f, 0x01, 0x41, ...signedLeb(1024n), 0x0b]]),
+    ...section(7, [
+      [...name('memory'), 0x02, 0x00],
+      [...name('alloc'), 0x00, imported],
+      ...exported.map(([exportName], index) => [
+        ...name(exportName),
+        0x00,
+        imported + 1 + index,
+      ]),
+    ]),
+    ...section(
+      10,
+      bodies.map((body) => [...unsignedLeb(body.length), ...body])
+    ),
+    ...section(11, segments),
+  ]);
+}
+
+const SYMBOLS = JSON.stringify([
+  {
+    name: 'Config',
+    kind: 23,
+    range: {
+      start: { line: 0, character: 0 },
+      end: { line: 4, character: 1 },
+    },
+    selectionRange: {
+      start: { line: 0, character: 7 },
+      end: { line: 0, character: 13 },
+    },
+    children: [
+      {
+        name: 'port',
+        kind: 8,
+        range: {
+          start: { line: 1, character: 2 },
+          end: { line: 1, character: 11 },
+        },
+        selectionRange: {
+          start: { line: 1, character: 2 },
+          end: { line: 1, character: 6 },
+        },
+      },
+    ],
+  },
+]);
+
+const ENRICHED = JSON.stringify({
+  content: [{ type: 'text', text: 'enriched' }],
+});
+
+const RESULT: CallToolResult = {
+  content: [{ type: 'text', text: 'original' }],
+};
+
+describe('WASM plugins', () => {
+  let workspace: string;
+
+  function writePlugin(fileName: string, bytes: Uint8Array): void {
+    fs.writeFileSync(path.join(workspace, fileName), bytes);
+  }
+
+  function configure(plugins: Partial<WasmPluginConfig>[]) {
+    configureWasmPlugins({
+      workspace,
+      plugins: plugins.map((plugin) => ({
+        path: 'plugin.wasm',
+        extensions: [],
+        tools: ['*'],
+        timeout_ms: 5000,
+        ...plugin,
+      })),
+    });
+  }
+
+  beforeEach(() => {
+    workspace = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-plugins-'));
+  });
+
+  afterEach(() => {
+    configureWasmPlugins({ workspace: process.cwd(), plugins: [] });
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('should find the plugin for a file by extension', () => {
+    configure([{ path: 'conf.wasm', extensions: ['.conf'] }]);
+
+    expect(findSymbolPlugin('/project/app.CONF')?.path).toBe('conf.wasm');
+    expect(findSymbolPlugin('/project/app.ts')).toBeNull();
+  });
+
+  test('should flatten symbols returned by extract_symbols', async () => {
+    writePlugin('conf.wasm', buildPlugin({ extract_symbols: SYMBOLS }));
+    const filePath = path.join(workspace, 'app.conf');
+    fs.writeFileSync(filePath, 'struct Config {\n  port = 80\n}\n');
+    configure([{ path: 'conf.wasm', extensions: ['.conf'] }]);
+
+    const symbols = await extractSymbolsWithPlugin(
+      findSymbolPlugin(filePath)!,
+      filePath
+    );
+
+    expect(
+      symbols.map((symbol) => [symbol.name, symbol.containerName])
+    ).toEqual([
+      ['Config', undefined],
+      ['port', 'Config'],
+    ]);
+  });
+
+  test('should stop a plugin that outlives its deadline', async () => {
+    writePlugin(
+      'conf.wasm',
+      buildPlugin({ extract_symbols: SYMBOLS }, { spin: true })
+    );
+    const filePath = path.join(workspace, 'app.conf');
+    fs.writeFileSync(filePath, 'struct Config {}\n');
+    configure([{ path: 'conf.wasm', extensions: ['.conf'], timeout_ms: 200 }]);
+
+    await expect(
+      extractSymbolsWithPlugin(findSymbolPlugin(filePath)!, filePath)
+    ).rejects.toThrow('timed out after 200ms in extract_symbols');
+  });
+
+  test('should feed plugin symbols to scans and searches', async () => {
+    writePlugin('conf.wasm', buildPlugin({ extract_symbols: SYMBOLS }));
+    const filePath = path.join(workspace, 'app.conf');
+    fs.writeFileSync(filePath, 'struct Config {\n  port = 80\n}\n');
+    configure([{ path: 'conf.wasm', extensions: ['.conf'] }]);
+    const manager = {
+      listProfiles: () => [],
+      getStartedSessions: () => [],
+      getSearchSessions: () =>
+        Promise.reject(new Error('No language servers configured')),
+      getStatus: () => ({ workspacePath: workspace }),
+    } as unknown as LspManager;
+
+    const scanned = await getFileSymbols(manager, filePath);
+    expect(scanned.symbols.map((symbol) => symbol.name)).toEqual([
+      'Config',
+      'port',
+    ]);
+
+    const search = await searchWorkspaceSymbols(manager, 'port');
+    expect(search.symbols).toEqual([
+      {
+        name: 'port',
+        kind: 8,
+        location: {
+          uri: pathToFileURL(filePath).toString(),
+          range: {
+            start: { line: 1, character: 2 },
+            end: { line: 1, character: 11 },
+          },
+        },
+        containerName: 'Config',
+      },
+    ]);
+    expect(search.errors.map((error) => error.message)).toEqual([
+      'No language servers configured',
+    ]);
+  });
+
+  test('should reject plugins that import host functions', async () => {
+    writePlugin(
+      'plugin.wasm',
+      buildPlugin({ enrich: ENRICHED }, { imports: true })
+    );
+
+    await expect(
+      loadWasmPlugin(
+        {
+          path: 'plugin.wasm',
+          extensions: [],
+          tools: ['*'],
+          timeout_ms: 5000,
+        },
+        workspace
+      )
+    ).rejects.toThrow('imports host functions');
+  });
+
+  test('should replace results for the tools a plugin enriches', async () => {
+    writePlugin('plugin.wasm', buildPlugin({ enrich: ENRICHED }));
+    configure([{ tools: ['search'] }]);
+
+    await expect(applyWasmEnrichers('search', {}, RESULT)).resolves.toEqual(
+      JSON.parse(ENRICHED)
+    );
+    await expect(applyWasmEnrichers('outline', {}, RESULT)).resolves.toBe(
+      RESULT
+    );
+  });
+
+  test('should skip plugins without an enrich export', async () => {
+    writePlugin('plugin.wasm', buildPlugin({ extract_symbols: SYMBOLS }));
+    configure([{}]);
+
+    await expect(applyWasmEnrichers('search', {}, RESULT)).resolves.toBe(
+      RESULT
+    );
+  });
+});
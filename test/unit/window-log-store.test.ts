@@ -0,0 +1,34 @@
+/**
+ * Window Log Store Tests
+ */
+
+import { describe, test, expect } from 'vitest';
+import { createWindowLogStore } from '../../src/state/index.js';
+
+describe('Window log store', () => {
+  test('keeps logged and shown messages in arrival order', () => {
+    const store = createWindowLogStore();
+
+    store.addMessage({ type: 3, message: 'Loading workspace' });
+    store.addMessage({ type: 1, message: 'cargo metadata failed' }, true);
+
+    expect(store.getMessages()).toMatchObject([
+      { type: 3, message: 'Loading workspace' },
+      { type: 1, message: 'cargo metadata failed', shown: true },
+    ]);
+    expect(store.getMessages()[0]).not.toHaveProperty('shown');
+    expect(store.getMessages()[1]!.receivedAt).toBeInstanceOf(Date);
+  });
+
+  test('drops the oldest messages past its capacity', () => {
+    const store = createWindowLogStore();
+
+    for (let index = 0; index < 1005; index++) {
+      store.addMessage({ type: 4, message: `message ${index}` });
+    }
+
+    const messages = store.getMessages();
+    expect(messages).toHaveLength(1000);
+    expect(messages[0]!.message).toBe('message 5');
+  });
+});
@@ -0,0 +1,160 @@
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, describe, expect, it, vi } from 'vitest';
+import { Client } from '@modelcontextprotocol/sdk/client/index.js';
+import { InMemoryTransport } from '@modelcontextprotocol/sdk/inMemory.js';
+import { McpServer } from '@modelcontextprotocol/sdk/server/mcp.js';
+import { ResourceUpdatedNotificationSchema } from '@modelcontextprotocol/sdk/types.js';
+import type { LspManager } from '../../src/runtime/lsp-manager.js';
+import * as LspOperations from '../../src/lsp/operations/index.js';
+import {
+  registerWorkspaceFileResources,
+  resolveWorkspaceResourceUri,
+  toWorkspaceResourceUri,
+} from '../../src/resources/workspace-files.js';
+
+vi.mock('../../src/lsp/operations/index.js', async () => {
+  const actual = await vi.importActual('../../src/lsp/operations/index.js');
+  return {
+    ...actual,
+    getDiagnostics: vi.fn(),
+  };
+});
+
+vi.mock('../../src/preparation.js', async () => {
+  const actual = await vi.importActual('../../src/preparation.js');
+  return {
+    ...actual,
+    prepareFileRequest: vi.fn((_session, { file }: { file: string }) => ({
+      ok: true,
+      data: { filePath: file },
+    })),
+  };
+});
+
+const mockGetDiagnostics = vi.mocked(LspOperations.getDiagnostics);
+
+async function connect(workspace: string) {
+  const manager = {
+    getStatus: () => ({ workspacePath: workspace }),
+    listProfiles: () => [{ extensions: ['.ts'] }],
+    getSessionForFile: vi.fn(async () => ({})),
+  } as unknown as LspManager;
+  const server = new McpServer({ name: 'test', version: '1.0.0' });
+  registerWorkspaceFileResources(server, manager);
+
+  const client = new Client({ name: 'test-client', version: '1.0.0' });
+  const [clientTransport, serverTransport] =
+    InMemoryTransport.createLinkedPair();
+  await Promise.all([
+    server.connect(serverTransport),
+    client.connect(clientTransport),
+  ]);
+  return { server, client };
+}
+
+describe('workspace file resources', () => {
+  const dirs: string[] = [];
+  const servers: McpServer[] = [];
+
+  afterEach(async () => {
+    await Promise.all(servers.splice(0).map((server) => server.close()));
+    for (const dir of dirs.splice(0)) {
+      fs.rmSync(dir, { recursive: true, force: true });
+    }
+  });
+
+  const createWorkspace = () => {
+    const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-resources-'));
+    dirs.push(dir);
+    fs.mkdirSync(path.join(dir, 'src'));
+    fs.writeFileSync(path.join(dir, 'src', 'my file.ts'), 'export {};\n');
+    return dir;
+  };
+
+  it('round-trips workspace paths through resource URIs', () => {
+    const uri = toWorkspaceResourceUri('symbols', 'src/my file.ts');
+
+    expect(uri).toBe('workspace://symbols/src/my%20file.ts');
+    expect(resolveWorkspaceResourceUri('/repo', uri)).toBe(
+      path.resolve('/repo', 'src/my file.ts')
+    );
+    expect(() =>
+      resolveWorkspaceResourceUri('/repo', 'workspace://files/../etc/passwd')
+    ).toThrow('outside the workspace');
+  });
+
+  it('lists and reads workspace files', async () => {
+    const { server, client } = await connect(createWorkspace());
+    servers.push(server);
+
+    const { resources } = await client.listResources();
+    expect(resources.map((resource) => resource.uri)).toContain(
+      'workspace://files/src/my%20file.ts'
+    );
+
+    const { contents } = await client.readResource({
+      uri: 'workspace://files/src/my%20file.ts',
+    });
+    expect(contents[0]).toMatchObject({ text: 'export {};\n' });
+  });
+
+  it('notifies subscribers when a file changes', async () => {
+    const workspace = createWorkspace();
+    const { server, client } = await connect(workspace);
+    servers.push(server);
+    const updated = vi.fn();
+    client.setNotificationHandler(
+      ResourceUpdatedNotificationSchema,
+      (notification) => updated(notification.params.uri)
+    );
+
+    const uri = 'workspace://files/src/my%20file.ts';
+    await client.subscribeResource({ uri });
+    fs.writeFileSync(path.join(workspace, 'src', 'my file.ts'), 'export {};');
+
+    await vi.waitFor(() => expect(updated).toHaveBeenCalledWith(uri), {
+      timeout: 2000,
+    });
+  });
+
+  it('notifies diagnostics subscribers when diagnostics change', async () => {
+    const workspace = createWorkspace();
+    const { server, client } = await connect(workspace);
+    servers.push(server);
+    const updated = vi.fn();
+    client.setNotificationHandler(
+      ResourceUpdatedNotificationSchema,
+      (notification) => updated(notification.params.uri)
+    );
+    const error = {
+      code: '2322',
+      message: "Type 'string' is not assignable to type 'number'.",
+      severity: 1,
+      range: {
+        start: { line: 0, character: 6 },
+        end: { line: 0, character: 7 },
+      },
+      source: 'typescript',
+    };
+    mockGetDiagnostics
+      .mockResolvedValueOnce({ ok: true, data: [] })
+      .mockResolvedValue({ ok: true, data: [error] });
+
+    const uri = 'workspace://diagnostics/src/my%20file.ts';
+    await client.subscribeResource({ uri });
+    fs.writeFileSync(
+      path.join(workspace, 'src', 'my file.ts'),
+      "const a: number = '1';\n"
+    );
+
+    await vi.waitFor(() => expect(updated).toHaveBeenCalledWith(uri), {
+      timeout: 2000,
+    });
+    const { contents } = await client.readResource({ uri });
+    expect(JSON.parse(String(contents[0]?.text))).toMatchObject({
+      diagnostics: [{ line: 1, character: 7, severity: 'Error', code: '2322' }],
+    });
+  });
+});
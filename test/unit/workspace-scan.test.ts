@@ -0,0 +1,39 @@
+/**
+ * Workspace Scan Tests
+ */
+
+import { afterEach, describe, test, expect } from 'vitest';
+import {
+  configureWorkspaceScan,
+  mapWithParallelism,
+} from '../../src/map/workspace-scan.js';
+
+describe('Workspace scan', () => {
+  afterEach(() => {
+    configureWorkspaceScan(null);
+  });
+
+  test('should keep item order with at most the configured requests in flight', async () => {
+    configureWorkspaceScan({ parallelism: 3 });
+    let inFlight = 0;
+    let maxInFlight = 0;
+
+    const results = await mapWithParallelism(
+      [50, 10, 30, 0, 20, 40, 10],
+      async (delay) => {
+        inFlight++;
+        maxInFlight = Math.max(maxInFlight, inFlight);
+        await new Promise((resolve) => setTimeout(resolve, delay));
+        inFlight--;
+        return delay * 2;
+      }
+    );
+
+    expect(results).toEqual([100, 20, 60, 0, 40, 80, 20]);
+    expect(maxInFlight).toBe(3);
+  });
+
+  test('should resolve to no results for no items', async () => {
+    await expect(mapWithParallelism([], async () => 1)).resolves.toEqual([]);
+  });
+});
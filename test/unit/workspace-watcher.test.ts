@@ -0,0 +1,105 @@
+/**
+ * Workspace Watcher Tests
+ */
+
+import * as fs from 'fs';
+import * as os from 'os';
+import * as path from 'path';
+import { afterEach, beforeEach, describe, expect, test, vi } from 'vitest';
+import {
+  subscribeWorkspaceFiles,
+  watchWorkspaceFiles,
+  type WorkspaceWatcher,
+} from '../../src/runtime/workspace-watcher.js';
+
+describe('Workspace watcher', () => {
+  let workspace: string;
+  let watcher: WorkspaceWatcher | null = null;
+
+  beforeEach(() => {
+    workspace = fs.mkdtempSync(path.join(os.tmpdir(), 'symbols-watch-'));
+    for (const directory of ['src', 'node_modules/dep', '.git']) {
+      fs.mkdirSync(path.join(workspace, directory), { recursive: true });
+    }
+  });
+
+  afterEach(() => {
+    watcher?.close();
+    watcher = null;
+    fs.rmSync(workspace, { recursive: true, force: true });
+  });
+
+  test('should report changes outside the directories scans skip', async () => {
+    const changed = vi.fn();
+    watcher = watchWorkspaceFiles(workspace, changed, () => {});
+    await watcher.ready;
+
+    fs.writeFileSync(path.join(workspace, 'node_modules/dep/index.js'), '');
+    fs.writeFileSync(path.join(workspace, '.git/HEAD'), '');
+    const source = path.join(workspace, 'src/value.ts');
+    fs.writeFileSync(source, 'export {};\n');
+
+    await vi.waitFor(() => expect(changed).toHaveBeenCalledWith(source), {
+      timeout: 2000,
+    });
+    const reported = changed.mock.calls.map(([changedPath]) => changedPath);
+    expect(reported).not.toContain(
+      path.join(workspace, 'node_modules/dep/index.js')
+    );
+    expect(reported).not.toContain(path.join(workspace, '.git/HEAD'));
+  });
+
+  test('should watch directories created after it started', async () => {
+    const changed = vi.fn();
+    watcher = watchWorkspaceFiles(workspace, changed, () => {});
+    await watcher.ready;
+
+    const directory = path.join(workspace, 'lib');
+    fs.mkdirSync(directory);
+    await vi.waitFor(() => expect(changed).toHaveBeenCalledWith(directory), {
+      timeout: 2000,
+    });
+
+    const source = path.join(directory, 'value.ts');
+    fs.writeFileSync(source, 'export {};\n');
+    await vi.waitFor(() => expect(changed).toHaveBeenCalledWith(source), {
+      timeout: 2000,
+    });
+  });
+
+  test('should share one watcher between the sessions of a workspace', async () => {
+    const first = vi.fn();
+    const second = vi.fn();
+    const firstWatcher = subscribeWorkspaceFiles(workspace, {
+      onChange: first,
+      onError: () => {},
+    });
+    watcher = subscribeWorkspaceFiles(workspace, {
+      onChange: second,
+      onError: () => {},
+    });
+
+    expect(watcher.ready).toBe(firstWatcher.ready);
+    await watcher.ready;
+
+    firstWatcher.close();
+    const source = path.join(workspace, 'src/value.ts');
+    fs.writeFileSync(source, 'export {};\n');
+
+    await vi.waitFor(() => expect(second).toHaveBeenCalledWith(source), {
+      timeout: 2000,
+    });
+    expect(first).not.toHaveBeenCalled();
+  });
+
+  test('should start a fresh watcher after the last listener left', async () => {
+    const listener = { onChange: () => {}, onError: () => {} };
+    const previous = subscribeWorkspaceFiles(workspace, listener);
+    await previous.ready;
+    previous.close();
+
+    watcher = subscribeWorkspaceFiles(workspace, listener);
+    expect(watcher.ready).not.toBe(previous.ready);
+    await watcher.ready;
+  });
+});